@@ -32,4 +32,12 @@ impl verifier_resolver::VerifierResolver for DIDKeyVerifierResolver {
         let verifier_bytes = did_resource.did().to_verifier_bytes().into_owned();
         Ok(Box::new(verifier_bytes))
     }
+    /// A did:key DID has exactly one key, which is valid for every verification relationship.
+    async fn resolve_for_purpose(
+        &self,
+        verifier_str: &str,
+        _verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        self.resolve(verifier_str).await
+    }
 }
@@ -4,10 +4,15 @@ mod did_key_verifier_resolver;
 mod did_resource;
 mod did_resource_str;
 mod did_str;
-
 #[cfg(feature = "verifier-resolver")]
-pub use crate::did_key_verifier_resolver::DIDKeyVerifierResolver;
+mod trusted_did_key_verifier_resolver;
+
 pub use crate::{
     did::DID, did_resource::DIDResource, did_resource_str::DIDResourceStr, did_str::DIDStr,
 };
+#[cfg(feature = "verifier-resolver")]
+pub use crate::{
+    did_key_verifier_resolver::DIDKeyVerifierResolver,
+    trusted_did_key_verifier_resolver::TrustedDIDKeyVerifierResolver,
+};
 pub use anyhow::{Error, Result};
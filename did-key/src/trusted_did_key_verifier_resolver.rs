@@ -0,0 +1,62 @@
+use crate::{DID, DIDKeyVerifierResolver, DIDResourceStr};
+use verifier_resolver::VerifierResolver;
+
+/// This is a VerifierResolver for did:key DIDResource-s that only resolves verifiers whose DID is exactly
+/// one of the given trusted did:key DIDs.  This is suitable for an allow-list of trusted signers (e.g.
+/// trusted attesters), unlike a VerifierResolverMap, which classifies verifiers by prefix and so would
+/// trust every did:key DID that begins with a given prefix.
+pub struct TrustedDIDKeyVerifierResolver {
+    trusted_did_v: Vec<DID>,
+}
+
+impl TrustedDIDKeyVerifierResolver {
+    pub fn new(trusted_did_v: Vec<DID>) -> Self {
+        Self { trusted_did_v }
+    }
+    pub fn trusted_did_v(&self) -> &[DID] {
+        &self.trusted_did_v
+    }
+    fn ensure_trusted(&self, verifier_str: &str) -> verifier_resolver::Result<()> {
+        let did_resource = DIDResourceStr::new_ref(verifier_str).map_err(|e| {
+            verifier_resolver::Error::InvalidVerifier(
+                format!("invalid did:key value {:?}; error was: {}", verifier_str, e).into(),
+            )
+        })?;
+        if !self
+            .trusted_did_v
+            .iter()
+            .any(|trusted_did| trusted_did.as_did_key_str() == did_resource.did())
+        {
+            Err(verifier_resolver::Error::UntrustedVerifier(
+                format!(
+                    "verifier {:?} is not one of the trusted did:key DIDs",
+                    verifier_str
+                )
+                .into(),
+            ))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl VerifierResolver for TrustedDIDKeyVerifierResolver {
+    async fn resolve(
+        &self,
+        verifier_str: &str,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        self.ensure_trusted(verifier_str)?;
+        DIDKeyVerifierResolver.resolve(verifier_str).await
+    }
+    async fn resolve_for_purpose(
+        &self,
+        verifier_str: &str,
+        verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        self.ensure_trusted(verifier_str)?;
+        DIDKeyVerifierResolver
+            .resolve_for_purpose(verifier_str, verification_relationship)
+            .await
+    }
+}
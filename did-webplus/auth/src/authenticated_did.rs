@@ -0,0 +1,88 @@
+use crate::{DID_AUTH_AUTHORIZATION_SCHEME, DIDAuthChallenge, DIDAuthVerifier};
+use axum::{
+    Json,
+    extract::{FromRef, FromRequestParts, State},
+    http::{StatusCode, header, request::Parts},
+};
+use did_webplus_core::DIDFullyQualified;
+use std::sync::Arc;
+
+/// An axum extractor yielding the DID authenticated by the DID Auth response carried in the request's
+/// "Authorization" header, which must have the form "DIDAuth <response>", where the response is one of the
+/// JWT forms accepted by DIDAuthVerifier::verify_response.  The state must provide an Arc<DIDAuthVerifier>
+/// (e.g. via FromRef).  Because each challenge can be responded to only once, this is meant to be used in
+/// the handler that logs a party in (e.g. by starting a session), not in every request.  The request is
+/// rejected with status 401 if the response doesn't verify.
+#[derive(Clone, Debug)]
+pub struct AuthenticatedDID(pub DIDFullyQualified);
+
+impl<S> FromRequestParts<S> for AuthenticatedDID
+where
+    Arc<DIDAuthVerifier>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, String);
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let did_auth_verifier_a = Arc::<DIDAuthVerifier>::from_ref(state);
+        let authorization = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .ok_or_else(|| {
+                (
+                    StatusCode::UNAUTHORIZED,
+                    "Authorization header not provided".to_string(),
+                )
+            })?
+            .to_str()
+            .map_err(|_| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    "malformed Authorization header".to_string(),
+                )
+            })?;
+        let response = match authorization.split_once(' ') {
+            Some((scheme, response))
+                if scheme.eq_ignore_ascii_case(DID_AUTH_AUTHORIZATION_SCHEME) =>
+            {
+                response
+            }
+            _ => {
+                return Err((
+                    StatusCode::UNAUTHORIZED,
+                    format!(
+                        "expected Authorization header of the form \"{} <response>\"",
+                        DID_AUTH_AUTHORIZATION_SCHEME
+                    ),
+                ));
+            }
+        };
+
+        let mut verification_report = verifier_resolver::VerificationReport::new();
+        match did_auth_verifier_a
+            .verify_response(response, &mut verification_report)
+            .await
+        {
+            Ok(did_fully_qualified) => {
+                tracing::debug!(?did_fully_qualified, "DID Auth succeeded");
+                Ok(Self(did_fully_qualified))
+            }
+            Err(e) => {
+                tracing::debug!(?verification_report, "DID Auth failed: {}", e);
+                Err((StatusCode::UNAUTHORIZED, e.to_string()))
+            }
+        }
+    }
+}
+
+/// An axum handler that issues a DID Auth challenge using the DIDAuthVerifier from the state, returning
+/// it as JSON.
+pub async fn issue_did_auth_challenge(
+    State(did_auth_verifier_a): State<Arc<DIDAuthVerifier>>,
+) -> Result<Json<DIDAuthChallenge>, (StatusCode, String)> {
+    let did_auth_challenge = did_auth_verifier_a
+        .issue_challenge()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(did_auth_challenge))
+}
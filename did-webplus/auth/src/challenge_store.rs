@@ -0,0 +1,15 @@
+use crate::{DIDAuthChallenge, Result};
+
+/// Keeps track of the outstanding challenges issued by a DIDAuthVerifier.  Implementations must ensure that
+/// a challenge can be taken at most once, even if several responses to it arrive concurrently, since that's
+/// what prevents a response from being replayed.  A service having several instances behind a load balancer
+/// should use an implementation backed by storage shared by all of them.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait ChallengeStore: Send + Sync {
+    /// Record a newly issued challenge.
+    async fn insert_challenge(&self, did_auth_challenge: DIDAuthChallenge) -> Result<()>;
+    /// Remove and return the given challenge, if it's outstanding.  Expired challenges may or may not be
+    /// returned; the caller is responsible for checking expiration.
+    async fn take_challenge(&self, challenge: &str) -> Result<Option<DIDAuthChallenge>>;
+}
@@ -0,0 +1,43 @@
+use crate::{ChallengeStore, DIDAuthChallenge, Error, Result};
+use std::{collections::HashMap, sync::Mutex};
+
+/// A ChallengeStore that keeps the outstanding challenges in memory.  This is only suitable for a service
+/// that runs as a single instance, and outstanding challenges are lost if it restarts.  Expired challenges
+/// are pruned whenever a new challenge is inserted.
+#[derive(Debug, Default)]
+pub struct ChallengeStoreInMemory {
+    challenge_m: Mutex<HashMap<String, DIDAuthChallenge>>,
+}
+
+impl ChallengeStoreInMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Returns the number of outstanding challenges (some of which may have expired).
+    pub fn len(&self) -> usize {
+        self.challenge_m.lock().unwrap().len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl ChallengeStore for ChallengeStoreInMemory {
+    async fn insert_challenge(&self, did_auth_challenge: DIDAuthChallenge) -> Result<()> {
+        let now = time::OffsetDateTime::now_utc();
+        let mut challenge_m = self.challenge_m.lock().unwrap();
+        challenge_m.retain(|_, did_auth_challenge| !did_auth_challenge.is_expired_at(now));
+        if challenge_m.contains_key(&did_auth_challenge.challenge) {
+            return Err(Error::ChallengeStoreError(
+                "challenge was already issued".into(),
+            ));
+        }
+        challenge_m.insert(did_auth_challenge.challenge.clone(), did_auth_challenge);
+        Ok(())
+    }
+    async fn take_challenge(&self, challenge: &str) -> Result<Option<DIDAuthChallenge>> {
+        Ok(self.challenge_m.lock().unwrap().remove(challenge))
+    }
+}
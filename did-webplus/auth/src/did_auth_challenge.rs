@@ -0,0 +1,39 @@
+/// A challenge issued by a DIDAuthVerifier to a party that wants to authenticate using its DID.  The party
+/// responds by signing the challenge and the domain using one of its DID's authentication keys (see
+/// did_auth_response_jwt and did_auth_response_vp_jwt).  Each challenge can be used at most once, and only
+/// until it expires.
+#[derive(Clone, Debug, serde::Deserialize, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DIDAuthChallenge {
+    /// A random, unguessable nonce.  This is the "nonce" claim of a JWT response, or the "challenge" field
+    /// of the proof of a Data Integrity-secured VP response.
+    pub challenge: String,
+    /// Identifies the verifier, so that a response can't be relayed to a different verifier.  This is the
+    /// "aud" claim of a JWT response, or the "domain" field of the proof of a Data Integrity-secured VP response.
+    pub domain: String,
+    /// The time after which a response to this challenge is no longer accepted.
+    #[serde(with = "time::serde::rfc3339")]
+    pub expires_at: time::OffsetDateTime,
+}
+
+impl DIDAuthChallenge {
+    /// Generate a new challenge for the given domain having a random nonce, which expires after the given
+    /// lifetime.
+    pub fn generate(domain: String, lifetime: time::Duration) -> crate::Result<Self> {
+        let mut challenge_bytes = [0u8; 32];
+        getrandom::fill(&mut challenge_bytes).map_err(|e| {
+            crate::Error::GenericError(format!("failed to generate challenge: {}", e).into())
+        })?;
+        use base64::Engine;
+        let challenge = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(challenge_bytes);
+        Ok(Self {
+            challenge,
+            domain,
+            expires_at: time::OffsetDateTime::now_utc() + lifetime,
+        })
+    }
+    /// Returns true if this challenge has expired as of the given time.
+    pub fn is_expired_at(&self, time: time::OffsetDateTime) -> bool {
+        time > self.expires_at
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{DID_AUTH_JWT_TYP, DIDAuthChallenge, Error, Result};
+use std::sync::Arc;
+
+/// Respond to the given challenge with a JWT having "typ" header DID_AUTH_JWT_TYP, whose "nonce" claim is the
+/// challenge and whose "aud" claim is the domain.  The JWT expires when the challenge does.  The signer must use
+/// an authentication key of the DID being authenticated.
+pub async fn did_auth_response_jwt<W: did_webplus_wallet::Wallet>(
+    did_auth_challenge: &DIDAuthChallenge,
+    wallet_based_signer: &did_webplus_wallet::WalletBasedSigner<W>,
+) -> Result<String> {
+    ensure_authentication_key(wallet_based_signer)?;
+    let mut claims = did_webplus_jws::JWTClaims {
+        aud: Some(did_auth_challenge.domain.clone().into()),
+        iat: Some(time::OffsetDateTime::now_utc().unix_timestamp()),
+        exp: Some(did_auth_challenge.expires_at.unix_timestamp()),
+        ..Default::default()
+    };
+    claims.additional_claim_m.insert(
+        "nonce".to_string(),
+        did_auth_challenge.challenge.clone().into(),
+    );
+    let jwt = did_webplus_jws::JWT::async_signed_with_typ(
+        Some(DID_AUTH_JWT_TYP.to_string()),
+        claims,
+        wallet_based_signer.key_fully_qualified().to_string(),
+        wallet_based_signer,
+    )
+    .await
+    .map_err(|e| Error::GenericError(e.to_string().into()))?;
+    Ok(jwt.into_string())
+}
+
+/// Respond to the given challenge with an empty JWT-secured VCDM 2.0 VP whose "nonce" claim is the challenge
+/// and whose "aud" claim is the domain.  The signer must use an authentication key of the DID being authenticated.
+pub async fn did_auth_response_vp_jwt<W: did_webplus_wallet::Wallet>(
+    did_auth_challenge: &DIDAuthChallenge,
+    wallet_based_signer: &did_webplus_wallet::WalletBasedSigner<W>,
+) -> Result<String> {
+    ensure_authentication_key(wallet_based_signer)?;
+    let vp_jwt = did_webplus_ssi::issue_vp_jwt_v2(
+        did_webplus_ssi::new_unsigned_presentation_v2(None, None, Vec::new()),
+        issue_vp_parameters(did_auth_challenge),
+        wallet_based_signer,
+    )
+    .await
+    .map_err(|e| Error::GenericError(e.to_string().into()))?;
+    Ok(vp_jwt.into_string())
+}
+
+/// Respond to the given challenge with an empty Data Integrity-secured VCDM 2.0 VP (in JSON form) whose proof
+/// has the challenge and the domain.  The signer must use an authentication key of the DID being authenticated.
+pub async fn did_auth_response_vp_ldp<W: did_webplus_wallet::Wallet + Clone>(
+    did_auth_challenge: &DIDAuthChallenge,
+    wallet_based_signer: &did_webplus_wallet::WalletBasedSigner<W>,
+    did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+) -> Result<String> {
+    ensure_authentication_key(wallet_based_signer)?;
+    let vp_ldp = did_webplus_ssi::issue_vp_ldp_v2(
+        did_webplus_ssi::new_unsigned_presentation_v2(None, None, Vec::new()),
+        issue_vp_parameters(did_auth_challenge),
+        wallet_based_signer,
+        did_resolver_a,
+    )
+    .await
+    .map_err(|e| Error::GenericError(e.to_string().into()))?;
+    serde_json::to_string(&vp_ldp).map_err(|e| Error::GenericError(e.to_string().into()))
+}
+
+fn issue_vp_parameters(
+    did_auth_challenge: &DIDAuthChallenge,
+) -> did_webplus_ssi::IssueVPParameters {
+    did_webplus_ssi::IssueVPParameters {
+        challenge_o: Some(did_auth_challenge.challenge.clone()),
+        domains_vo: Some(vec![did_auth_challenge.domain.clone()]),
+        nonce_o: None,
+    }
+}
+
+fn ensure_authentication_key<W: did_webplus_wallet::Wallet>(
+    wallet_based_signer: &did_webplus_wallet::WalletBasedSigner<W>,
+) -> Result<()> {
+    if wallet_based_signer.key_purpose() != did_webplus_core::KeyPurpose::Authentication {
+        return Err(Error::GenericError(
+            format!(
+                "DID Auth response must be signed using an authentication key, but got a {} key",
+                wallet_based_signer.key_purpose()
+            )
+            .into(),
+        ));
+    }
+    Ok(())
+}
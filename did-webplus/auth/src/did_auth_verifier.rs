@@ -0,0 +1,360 @@
+use crate::{ChallengeStore, DIDAuthChallenge, Error, Result};
+use did_webplus_core::{DIDFullyQualified, DIDKeyResourceFullyQualifiedStr};
+use std::{str::FromStr, sync::Arc};
+use verifier_resolver::{VerificationCheckKind, VerificationRelationship, VerificationReport};
+
+/// The "typ" header value of a DID Auth JWT response (see did_auth_response_jwt).
+pub const DID_AUTH_JWT_TYP: &str = "did-auth+jwt";
+
+/// The HTTP "Authorization" header scheme used to carry a DID Auth response, i.e. "DIDAuth <response>".
+pub const DID_AUTH_AUTHORIZATION_SCHEME: &str = "DIDAuth";
+
+/// Issues DID Auth challenges and verifies the responses to them, so that a service can authenticate a party
+/// as the controller of a did:webplus DID.  A response must be signed by one of the DID's authentication keys,
+/// and that key must still be valid, i.e. it must be in the latest version of the DID document.  A response
+/// is one of:
+/// - a JWT having "typ" header DID_AUTH_JWT_TYP, whose "nonce" claim is the challenge and whose "aud" claim
+///   is the domain (see did_auth_response_jwt),
+/// - a JWT-secured VCDM 2.0 VP whose "nonce" claim is the challenge and whose "aud" claim contains the domain
+///   (see did_auth_response_vp_jwt),
+/// - a Data Integrity-secured VCDM 2.0 VP (in JSON form) having a single proof, whose "challenge" field is the
+///   challenge and whose "domain" field contains the domain (see did_auth_response_vp_ldp).
+///
+/// The credentials contained in a VP response are not verified; that must be done separately if needed.
+pub struct DIDAuthVerifier {
+    domain: String,
+    challenge_lifetime: time::Duration,
+    challenge_store_a: Arc<dyn ChallengeStore>,
+    did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+}
+
+impl DIDAuthVerifier {
+    /// The lifetime of an issued challenge unless otherwise specified.
+    pub const DEFAULT_CHALLENGE_LIFETIME: time::Duration = time::Duration::minutes(5);
+
+    /// The domain identifies this verifier (e.g. "example.com") and is what responses must be bound to.
+    pub fn new(
+        domain: String,
+        challenge_store_a: Arc<dyn ChallengeStore>,
+        did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+    ) -> Self {
+        Self {
+            domain,
+            challenge_lifetime: Self::DEFAULT_CHALLENGE_LIFETIME,
+            challenge_store_a,
+            did_resolver_a,
+        }
+    }
+    /// Sets the lifetime of subsequently issued challenges.
+    pub fn with_challenge_lifetime(mut self, challenge_lifetime: time::Duration) -> Self {
+        self.challenge_lifetime = challenge_lifetime;
+        self
+    }
+    pub fn domain(&self) -> &str {
+        self.domain.as_str()
+    }
+    pub fn challenge_lifetime(&self) -> time::Duration {
+        self.challenge_lifetime
+    }
+    /// Issue a new challenge, recording it in the challenge store so that a response to it can be verified.
+    pub async fn issue_challenge(&self) -> Result<DIDAuthChallenge> {
+        let did_auth_challenge =
+            DIDAuthChallenge::generate(self.domain.clone(), self.challenge_lifetime)?;
+        self.challenge_store_a
+            .insert_challenge(did_auth_challenge.clone())
+            .await?;
+        Ok(did_auth_challenge)
+    }
+    /// Verify a response to a challenge previously issued by this verifier, returning the fully qualified DID
+    /// (i.e. the DID document version) of the authentication key that signed it.  The challenge is consumed,
+    /// even if verification fails, so that each challenge can be responded to at most once.  Each check
+    /// performed is recorded in verification_report.
+    pub async fn verify_response(
+        &self,
+        response: &str,
+        verification_report: &mut VerificationReport,
+    ) -> Result<DIDFullyQualified> {
+        let response = response.trim();
+        if response.starts_with('{') {
+            return self
+                .verify_vp_ldp_response(response, verification_report)
+                .await;
+        }
+        let jwt = did_webplus_jws::JWT::from_str(response)
+            .map_err(|e| Error::MalformedResponse(e.to_string().into()))?;
+        match jwt.jws().header().typ.as_deref() {
+            Some(DID_AUTH_JWT_TYP) => self.verify_jwt_response(&jwt, verification_report).await,
+            Some(did_webplus_ssi::VP_JWT_V2_TYP) => {
+                self.verify_vp_jwt_response(&jwt, verification_report).await
+            }
+            typ_o => Err(Error::MalformedResponse(
+                format!(
+                    "unsupported response JWT \"typ\" header {:?}; expected {:?} or {:?}",
+                    typ_o,
+                    DID_AUTH_JWT_TYP,
+                    did_webplus_ssi::VP_JWT_V2_TYP
+                )
+                .into(),
+            )),
+        }
+    }
+
+    async fn verify_jwt_response(
+        &self,
+        jwt: &did_webplus_jws::JWT,
+        verification_report: &mut VerificationReport,
+    ) -> Result<DIDFullyQualified> {
+        self.take_challenge(jwt_nonce(jwt)?).await?;
+        let kid = jwt.jws().header().kid.as_str();
+        let key_id = did_webplus_key_id(kid)?;
+
+        let verifier_b = verification_report
+            .resolve_for_purpose(
+                kid,
+                VerificationRelationship::Authentication,
+                self.did_resolver_a.as_verifier_resolver(),
+            )
+            .await
+            .map_err(verification_failed)?;
+        verification_report
+            .record_result(
+                VerificationCheckKind::Signature,
+                Some(kid),
+                verification_report
+                    .resolved_did_document_version(kid)
+                    .as_deref(),
+                jwt.jws().verify(verifier_b.as_ref(), None),
+            )
+            .map_err(verification_failed)?;
+        let validation_options = did_webplus_jws::JWTValidationOptions {
+            expected_audience_o: Some(self.domain.clone()),
+            ..Default::default()
+        };
+        verification_report
+            .record_result(
+                VerificationCheckKind::Claims,
+                None,
+                None,
+                jwt.validate_claims(&validation_options),
+            )
+            .map_err(verification_failed)?;
+
+        self.ensure_key_is_currently_valid(key_id, verification_report)
+            .await?;
+        Ok(key_id.without_fragment().to_owned())
+    }
+
+    async fn verify_vp_jwt_response(
+        &self,
+        jwt: &did_webplus_jws::JWT,
+        verification_report: &mut VerificationReport,
+    ) -> Result<DIDFullyQualified> {
+        let did_auth_challenge = self.take_challenge(jwt_nonce(jwt)?).await?;
+        let key_id = did_webplus_key_id(jwt.jws().header().kid.as_str())?;
+
+        // This checks the signature, the key purpose, the holder, the "aud" claim, and the "nonce" claim.
+        did_webplus_ssi::verify_vp_jwt_v2(
+            jwt.as_str(),
+            Some(self.domain.as_str()),
+            Some(did_auth_challenge.challenge.as_str()),
+            self.did_resolver_a.clone(),
+            verification_report,
+        )
+        .await
+        .map_err(verification_failed)?;
+
+        self.ensure_key_is_currently_valid(key_id, verification_report)
+            .await?;
+        Ok(key_id.without_fragment().to_owned())
+    }
+
+    async fn verify_vp_ldp_response(
+        &self,
+        response: &str,
+        verification_report: &mut VerificationReport,
+    ) -> Result<DIDFullyQualified> {
+        let vp_ldp: ssi_claims::data_integrity::DataIntegrity<
+            ssi_claims::vc::v2::JsonPresentation<serde_json::Value>,
+            ssi_claims::data_integrity::AnySuite,
+        > = serde_json::from_str(response)
+            .map_err(|e| Error::MalformedResponse(e.to_string().into()))?;
+        let presentation: serde_json::Value = serde_json::from_str(response)
+            .map_err(|e| Error::MalformedResponse(e.to_string().into()))?;
+
+        let proof = match presentation.get("proof") {
+            Some(proof @ serde_json::Value::Object(_)) => proof,
+            Some(serde_json::Value::Array(proof_v)) if proof_v.len() == 1 => &proof_v[0],
+            _ => {
+                return Err(Error::MalformedResponse(
+                    "VP response must have exactly one proof".into(),
+                ));
+            }
+        };
+        let challenge = proof
+            .get("challenge")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| {
+                Error::MalformedResponse("VP response proof is missing \"challenge\" field".into())
+            })?;
+        self.take_challenge(challenge).await?;
+        let kid = proof
+            .get("verificationMethod")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| {
+                Error::MalformedResponse(
+                    "VP response proof is missing \"verificationMethod\" field".into(),
+                )
+            })?;
+        let key_id = did_webplus_key_id(kid)?;
+
+        verification_report
+            .record_result(
+                VerificationCheckKind::Claims,
+                None,
+                None,
+                ensure_vp_ldp_bound_to(&presentation, proof, key_id, self.domain.as_str()),
+            )
+            .map_err(verification_failed)?;
+        // This checks the signature and that the key is an authentication key.
+        did_webplus_ssi::verify_vp_ldp_v2(
+            &vp_ldp,
+            self.did_resolver_a.clone(),
+            verification_report,
+        )
+        .await
+        .map_err(verification_failed)?
+        .map_err(verification_failed)?;
+
+        self.ensure_key_is_currently_valid(key_id, verification_report)
+            .await?;
+        Ok(key_id.without_fragment().to_owned())
+    }
+
+    /// Take the given challenge from the challenge store, ensuring that it was issued by this verifier and
+    /// hasn't expired.
+    async fn take_challenge(&self, challenge: &str) -> Result<DIDAuthChallenge> {
+        let did_auth_challenge = self
+            .challenge_store_a
+            .take_challenge(challenge)
+            .await?
+            .ok_or_else(|| {
+                Error::UnknownChallenge(
+                    "challenge was not issued by this verifier, or was already used".into(),
+                )
+            })?;
+        if did_auth_challenge.domain != self.domain {
+            return Err(Error::UnknownChallenge(
+                format!(
+                    "challenge was issued for domain {:?}, not {:?}",
+                    did_auth_challenge.domain, self.domain
+                )
+                .into(),
+            ));
+        }
+        if did_auth_challenge.is_expired_at(time::OffsetDateTime::now_utc()) {
+            return Err(Error::ExpiredChallenge(
+                format!("challenge expired at {}", did_auth_challenge.expires_at).into(),
+            ));
+        }
+        Ok(did_auth_challenge)
+    }
+
+    /// A signature from a key that has since been rotated out must not be usable to log in, so the key must
+    /// be valid now, and not only at its claimed signing time.
+    async fn ensure_key_is_currently_valid(
+        &self,
+        key_id: &DIDKeyResourceFullyQualifiedStr,
+        verification_report: &mut VerificationReport,
+    ) -> Result<()> {
+        let key_validity_r = did_webplus_resolver::key_validity_at(
+            self.did_resolver_a.as_ref(),
+            key_id,
+            time::OffsetDateTime::now_utc(),
+        )
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|key_validity| {
+            if key_validity.is_valid() {
+                Ok(())
+            } else {
+                Err(format!(
+                    "key {} is not currently valid: {}",
+                    key_id.as_str(),
+                    key_validity
+                ))
+            }
+        });
+        verification_report
+            .record_result(
+                VerificationCheckKind::KeyValidityWindow,
+                Some(key_id.as_str()),
+                Some(key_id.without_fragment().as_str()),
+                key_validity_r,
+            )
+            .map_err(verification_failed)
+    }
+}
+
+/// Returns the "nonce" claim of the given JWT, which is the challenge it responds to.
+fn jwt_nonce(jwt: &did_webplus_jws::JWT) -> Result<&str> {
+    jwt.claims()
+        .additional_claim_m
+        .get("nonce")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| Error::MalformedResponse("response JWT is missing \"nonce\" claim".into()))
+}
+
+/// Only did:webplus DIDs can be authenticated, and their key IDs must be fully qualified.
+fn did_webplus_key_id(kid: &str) -> Result<&DIDKeyResourceFullyQualifiedStr> {
+    DIDKeyResourceFullyQualifiedStr::new_ref(kid).map_err(|e| {
+        Error::MalformedResponse(
+            format!(
+                "response must be signed using a fully qualified did:webplus key ID, but got {:?}: {}",
+                kid, e
+            )
+            .into(),
+        )
+    })
+}
+
+/// Ensure that the Data Integrity-secured VP is bound to the given domain and that its holder is the DID
+/// of the signing key.
+fn ensure_vp_ldp_bound_to(
+    presentation: &serde_json::Value,
+    proof: &serde_json::Value,
+    key_id: &DIDKeyResourceFullyQualifiedStr,
+    domain: &str,
+) -> std::result::Result<(), String> {
+    let domain_matches = match proof.get("domain") {
+        Some(serde_json::Value::String(proof_domain)) => proof_domain == domain,
+        Some(serde_json::Value::Array(proof_domain_v)) => proof_domain_v
+            .iter()
+            .any(|proof_domain| proof_domain.as_str() == Some(domain)),
+        _ => false,
+    };
+    if !domain_matches {
+        return Err(format!(
+            "VP proof \"domain\" field does not contain the expected domain {:?}",
+            domain
+        ));
+    }
+    let holder_o = match presentation.get("holder") {
+        Some(serde_json::Value::String(holder)) => Some(holder.as_str()),
+        Some(holder) => holder.get("id").and_then(serde_json::Value::as_str),
+        None => None,
+    };
+    let holder = holder_o.ok_or_else(|| "VP is missing \"holder\" field".to_string())?;
+    let signer_did = key_id.without_fragment().did();
+    if holder.split(['?', '#']).next().unwrap() != signer_did.as_str() {
+        return Err(format!(
+            "VP holder {:?} does not match the signer {:?}",
+            holder,
+            signer_did.as_str()
+        ));
+    }
+    Ok(())
+}
+
+fn verification_failed(e: impl std::fmt::Display) -> Error {
+    Error::VerificationFailed(e.to_string().into())
+}
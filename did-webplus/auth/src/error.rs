@@ -0,0 +1,17 @@
+use std::borrow::Cow;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Challenge store error: {0}")]
+    ChallengeStoreError(Cow<'static, str>),
+    #[error("Expired challenge: {0}")]
+    ExpiredChallenge(Cow<'static, str>),
+    #[error("Generic error: {0}")]
+    GenericError(Cow<'static, str>),
+    #[error("Malformed response: {0}")]
+    MalformedResponse(Cow<'static, str>),
+    #[error("Unknown challenge: {0}")]
+    UnknownChallenge(Cow<'static, str>),
+    #[error("Verification failed: {0}")]
+    VerificationFailed(Cow<'static, str>),
+}
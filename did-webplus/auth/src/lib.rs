@@ -0,0 +1,22 @@
+#[cfg(feature = "axum")]
+mod authenticated_did;
+mod challenge_store;
+mod challenge_store_in_memory;
+mod did_auth_challenge;
+mod did_auth_response;
+mod did_auth_verifier;
+mod error;
+
+#[cfg(feature = "axum")]
+pub use crate::authenticated_did::{AuthenticatedDID, issue_did_auth_challenge};
+pub use crate::{
+    challenge_store::ChallengeStore,
+    challenge_store_in_memory::ChallengeStoreInMemory,
+    did_auth_challenge::DIDAuthChallenge,
+    did_auth_response::{
+        did_auth_response_jwt, did_auth_response_vp_jwt, did_auth_response_vp_ldp,
+    },
+    did_auth_verifier::{DID_AUTH_AUTHORIZATION_SCHEME, DID_AUTH_JWT_TYP, DIDAuthVerifier},
+    error::Error,
+};
+pub type Result<T> = std::result::Result<T, Error>;
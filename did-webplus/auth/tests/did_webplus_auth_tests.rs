@@ -0,0 +1,486 @@
+use std::sync::Arc;
+
+/// This will run once at load time (i.e. presumably before main function is called).
+#[ctor::ctor]
+fn overall_init() {
+    // It's necessary to specify EnvFilter::from_default_env in order to use RUST_LOG env var.
+    // TODO: Make env var to control full/compact/pretty/json formatting of logs
+    tracing_subscriber::fmt()
+        .with_target(true)
+        .with_line_number(true)
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .compact()
+        .init();
+}
+
+async fn test_did_auth_impl(
+    software_wallet: &did_webplus_software_wallet::SoftwareWallet,
+    vdr_did_create_endpoint: &str,
+) {
+    let did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver> = {
+        let db_url = "sqlite://:memory:";
+        let did_doc_storage =
+            did_webplus_doc_storage_sqlite::DIDDocStorageSQLite::open_url_and_run_migrations(
+                db_url, None,
+            )
+            .await
+            .expect("pass");
+        let did_doc_store = did_webplus_doc_store::DIDDocStore::new(Arc::new(did_doc_storage));
+        let did_resolver_full =
+            did_webplus_resolver::DIDResolverFull::new(did_doc_store, None, None).expect("pass");
+        Arc::new(did_resolver_full)
+    };
+    let did_auth_verifier = did_webplus_auth::DIDAuthVerifier::new(
+        "example.com".to_string(),
+        Arc::new(did_webplus_auth::ChallengeStoreInMemory::new()),
+        did_resolver_a.clone(),
+    );
+
+    // Have the wallet create a DID.
+    use did_webplus_wallet::Wallet;
+    let mb_hash_function = selfhash::MBHashFunction::blake3(mbx::Base::Base64Url);
+    let controlled_did = software_wallet
+        .create_did(
+            did_webplus_wallet::CreateDIDParameters {
+                vdr_did_create_endpoint,
+                mb_hash_function_for_did: &mb_hash_function,
+                mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                vdr_authz_key_id_o: None,
+            },
+            None,
+        )
+        .await
+        .expect("pass");
+    let wallet_based_signer = did_webplus_wallet::WalletBasedSigner::new(
+        software_wallet.clone(),
+        controlled_did.did(),
+        did_webplus_core::KeyPurpose::Authentication,
+        None,
+        None,
+        true,
+    )
+    .await
+    .expect("pass");
+    let expected_did_fully_qualified = wallet_based_signer
+        .key_fully_qualified()
+        .without_fragment()
+        .to_owned();
+
+    // Each kind of response verifies, and yields the DID.
+    for response_kind in ["jwt", "vp_jwt", "vp_ldp"] {
+        let did_auth_challenge = did_auth_verifier.issue_challenge().await.expect("pass");
+        let response = match response_kind {
+            "jwt" => {
+                did_webplus_auth::did_auth_response_jwt(&did_auth_challenge, &wallet_based_signer)
+                    .await
+            }
+            "vp_jwt" => {
+                did_webplus_auth::did_auth_response_vp_jwt(
+                    &did_auth_challenge,
+                    &wallet_based_signer,
+                )
+                .await
+            }
+            "vp_ldp" => {
+                did_webplus_auth::did_auth_response_vp_ldp(
+                    &did_auth_challenge,
+                    &wallet_based_signer,
+                    did_resolver_a.clone(),
+                )
+                .await
+            }
+            _ => unreachable!(),
+        }
+        .expect("pass");
+        tracing::debug!("{} response: {}", response_kind, response);
+
+        let mut verification_report = verifier_resolver::VerificationReport::new();
+        let did_fully_qualified = did_auth_verifier
+            .verify_response(response.as_str(), &mut verification_report)
+            .await
+            .expect("pass");
+        assert_eq!(did_fully_qualified, expected_did_fully_qualified);
+        assert!(verification_report.is_valid());
+
+        // A response can't be replayed.
+        let replay_r = did_auth_verifier
+            .verify_response(
+                response.as_str(),
+                &mut verifier_resolver::VerificationReport::new(),
+            )
+            .await;
+        assert!(matches!(
+            replay_r,
+            Err(did_webplus_auth::Error::UnknownChallenge(_))
+        ));
+    }
+
+    // A challenge not issued by the verifier is rejected.
+    {
+        let did_auth_challenge = did_webplus_auth::DIDAuthChallenge::generate(
+            "example.com".to_string(),
+            time::Duration::minutes(5),
+        )
+        .expect("pass");
+        let response =
+            did_webplus_auth::did_auth_response_jwt(&did_auth_challenge, &wallet_based_signer)
+                .await
+                .expect("pass");
+        let verify_r = did_auth_verifier
+            .verify_response(
+                response.as_str(),
+                &mut verifier_resolver::VerificationReport::new(),
+            )
+            .await;
+        assert!(matches!(
+            verify_r,
+            Err(did_webplus_auth::Error::UnknownChallenge(_))
+        ));
+    }
+
+    // A response bound to a different domain is rejected.
+    for response_kind in ["jwt", "vp_ldp"] {
+        let mut did_auth_challenge = did_auth_verifier.issue_challenge().await.expect("pass");
+        did_auth_challenge.domain = "evil.example.com".to_string();
+        let response = if response_kind == "jwt" {
+            did_webplus_auth::did_auth_response_jwt(&did_auth_challenge, &wallet_based_signer).await
+        } else {
+            did_webplus_auth::did_auth_response_vp_ldp(
+                &did_auth_challenge,
+                &wallet_based_signer,
+                did_resolver_a.clone(),
+            )
+            .await
+        }
+        .expect("pass");
+        let verify_r = did_auth_verifier
+            .verify_response(
+                response.as_str(),
+                &mut verifier_resolver::VerificationReport::new(),
+            )
+            .await;
+        tracing::debug!("{} verify_r: {:?}", response_kind, verify_r);
+        assert!(matches!(
+            verify_r,
+            Err(did_webplus_auth::Error::VerificationFailed(_))
+        ));
+    }
+
+    // An expired challenge is rejected.
+    {
+        let did_auth_verifier = did_webplus_auth::DIDAuthVerifier::new(
+            "example.com".to_string(),
+            Arc::new(did_webplus_auth::ChallengeStoreInMemory::new()),
+            did_resolver_a.clone(),
+        )
+        .with_challenge_lifetime(time::Duration::seconds(-1));
+        let did_auth_challenge = did_auth_verifier.issue_challenge().await.expect("pass");
+        let response =
+            did_webplus_auth::did_auth_response_jwt(&did_auth_challenge, &wallet_based_signer)
+                .await
+                .expect("pass");
+        let verify_r = did_auth_verifier
+            .verify_response(
+                response.as_str(),
+                &mut verifier_resolver::VerificationReport::new(),
+            )
+            .await;
+        assert!(matches!(
+            verify_r,
+            Err(did_webplus_auth::Error::ExpiredChallenge(_))
+        ));
+    }
+
+    // A non-authentication key can't be used.
+    {
+        let assertion_method_wallet_based_signer = did_webplus_wallet::WalletBasedSigner::new(
+            software_wallet.clone(),
+            controlled_did.did(),
+            did_webplus_core::KeyPurpose::AssertionMethod,
+            None,
+            None,
+            true,
+        )
+        .await
+        .expect("pass");
+        let did_auth_challenge = did_auth_verifier.issue_challenge().await.expect("pass");
+        assert!(
+            did_webplus_auth::did_auth_response_jwt(
+                &did_auth_challenge,
+                &assertion_method_wallet_based_signer
+            )
+            .await
+            .is_err()
+        );
+    }
+
+    // A response signed before the DID was updated (which rotates its keys) is rejected, since the key is no
+    // longer valid.
+    {
+        let did_auth_challenge = did_auth_verifier.issue_challenge().await.expect("pass");
+        let response =
+            did_webplus_auth::did_auth_response_jwt(&did_auth_challenge, &wallet_based_signer)
+                .await
+                .expect("pass");
+        software_wallet
+            .update_did(
+                did_webplus_wallet::UpdateDIDParameters {
+                    did: &controlled_did.did(),
+                    change_mb_hash_function_for_self_hash_o: None,
+                    mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                },
+                None,
+            )
+            .await
+            .expect("pass");
+        let mut verification_report = verifier_resolver::VerificationReport::new();
+        let verify_r = did_auth_verifier
+            .verify_response(response.as_str(), &mut verification_report)
+            .await;
+        tracing::debug!("verify_r: {:?}", verify_r);
+        assert!(matches!(
+            verify_r,
+            Err(did_webplus_auth::Error::VerificationFailed(_))
+        ));
+        assert!(
+            verification_report
+                .failed_check_iter()
+                .any(|check| check.kind
+                    == verifier_resolver::VerificationCheckKind::KeyValidityWindow)
+        );
+    }
+}
+
+#[cfg(feature = "axum")]
+async fn test_did_auth_axum_impl(
+    software_wallet: &did_webplus_software_wallet::SoftwareWallet,
+    vdr_did_create_endpoint: &str,
+) {
+    let did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver> = {
+        let db_url = "sqlite://:memory:";
+        let did_doc_storage =
+            did_webplus_doc_storage_sqlite::DIDDocStorageSQLite::open_url_and_run_migrations(
+                db_url, None,
+            )
+            .await
+            .expect("pass");
+        let did_doc_store = did_webplus_doc_store::DIDDocStore::new(Arc::new(did_doc_storage));
+        let did_resolver_full =
+            did_webplus_resolver::DIDResolverFull::new(did_doc_store, None, None).expect("pass");
+        Arc::new(did_resolver_full)
+    };
+    let did_auth_verifier_a = Arc::new(did_webplus_auth::DIDAuthVerifier::new(
+        "example.com".to_string(),
+        Arc::new(did_webplus_auth::ChallengeStoreInMemory::new()),
+        did_resolver_a,
+    ));
+
+    async fn login(
+        did_webplus_auth::AuthenticatedDID(did_fully_qualified): did_webplus_auth::AuthenticatedDID,
+    ) -> String {
+        did_fully_qualified.to_string()
+    }
+    let router = axum::Router::new()
+        .route(
+            "/challenge",
+            axum::routing::get(did_webplus_auth::issue_did_auth_challenge),
+        )
+        .route("/login", axum::routing::post(login))
+        .with_state(did_auth_verifier_a);
+
+    use did_webplus_wallet::Wallet;
+    let mb_hash_function = selfhash::MBHashFunction::blake3(mbx::Base::Base64Url);
+    let controlled_did = software_wallet
+        .create_did(
+            did_webplus_wallet::CreateDIDParameters {
+                vdr_did_create_endpoint,
+                mb_hash_function_for_did: &mb_hash_function,
+                mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                vdr_authz_key_id_o: None,
+            },
+            None,
+        )
+        .await
+        .expect("pass");
+    let wallet_based_signer = did_webplus_wallet::WalletBasedSigner::new(
+        software_wallet.clone(),
+        controlled_did.did(),
+        did_webplus_core::KeyPurpose::Authentication,
+        None,
+        None,
+        true,
+    )
+    .await
+    .expect("pass");
+
+    use tower::ServiceExt;
+    let response = router
+        .clone()
+        .oneshot(
+            axum::http::Request::get("/challenge")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("pass");
+    assert_eq!(response.status(), axum::http::StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("pass");
+    let did_auth_challenge: did_webplus_auth::DIDAuthChallenge =
+        serde_json::from_slice(&body).expect("pass");
+
+    let did_auth_response =
+        did_webplus_auth::did_auth_response_jwt(&did_auth_challenge, &wallet_based_signer)
+            .await
+            .expect("pass");
+    let login_request = || {
+        axum::http::Request::post("/login")
+            .header(
+                axum::http::header::AUTHORIZATION,
+                format!(
+                    "{} {}",
+                    did_webplus_auth::DID_AUTH_AUTHORIZATION_SCHEME,
+                    did_auth_response
+                ),
+            )
+            .body(axum::body::Body::empty())
+            .unwrap()
+    };
+    let response = router.clone().oneshot(login_request()).await.expect("pass");
+    assert_eq!(response.status(), axum::http::StatusCode::OK);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("pass");
+    assert_eq!(
+        std::str::from_utf8(&body).expect("pass"),
+        wallet_based_signer
+            .key_fully_qualified()
+            .without_fragment()
+            .as_str()
+    );
+
+    // Logging in again with the same response fails.
+    let response = router.clone().oneshot(login_request()).await.expect("pass");
+    assert_eq!(response.status(), axum::http::StatusCode::UNAUTHORIZED);
+
+    // Logging in without the Authorization header fails.
+    let response = router
+        .oneshot(
+            axum::http::Request::post("/login")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .expect("pass");
+    assert_eq!(response.status(), axum::http::StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_did_auth() {
+    // TODO: Use env vars to be able to point to a "real" VDR.
+
+    let vdr_database_url = "postgres:///test_did_auth_vdr";
+    let vdr_port = 13090;
+    let wallet_store_database_path = "tests/test_did_auth.wallet-store.db";
+
+    let (vdr_handle, vdr_did_create_endpoint, software_wallet) =
+        setup_vdr_and_wallet(vdr_database_url, vdr_port, wallet_store_database_path).await;
+
+    test_did_auth_impl(&software_wallet, &vdr_did_create_endpoint).await;
+
+    tracing::info!("Shutting down VDR");
+    vdr_handle.abort();
+}
+
+#[cfg(feature = "axum")]
+#[tokio::test]
+async fn test_did_auth_axum() {
+    // TODO: Use env vars to be able to point to a "real" VDR.
+
+    let vdr_database_url = "postgres:///test_did_auth_axum_vdr";
+    let vdr_port = 13091;
+    let wallet_store_database_path = "tests/test_did_auth_axum.wallet-store.db";
+
+    let (vdr_handle, vdr_did_create_endpoint, software_wallet) =
+        setup_vdr_and_wallet(vdr_database_url, vdr_port, wallet_store_database_path).await;
+
+    test_did_auth_axum_impl(&software_wallet, &vdr_did_create_endpoint).await;
+
+    tracing::info!("Shutting down VDR");
+    vdr_handle.abort();
+}
+
+async fn setup_vdr_and_wallet(
+    vdr_database_url: &str,
+    vdr_port: u16,
+    wallet_store_database_path: &str,
+) -> (
+    tokio::task::JoinHandle<()>,
+    String,
+    did_webplus_software_wallet::SoftwareWallet,
+) {
+    // Delete any existing database files so that we're starting from a consistent, blank start every time.
+    if std::fs::exists(wallet_store_database_path).expect("pass") {
+        std::fs::remove_file(wallet_store_database_path).expect("pass");
+    }
+
+    let vdr_config = did_webplus_vdr_lib::VDRConfig {
+        did_hostname: "localhost".to_string(),
+        did_port_o: Some(vdr_port),
+        listen_port: vdr_port,
+        database_url: vdr_database_url.to_string(),
+        database_max_connections: 10,
+        vdg_base_url_v: Vec::new(),
+        http_scheme_override: Default::default(),
+        test_authz_api_key_vo: None,
+        registrar_permissions_o: None,
+        serve_did_web: false,
+        audit_interval_seconds_o: None,
+        admission_policy_config: Default::default(),
+    };
+    let vdr_handle = did_webplus_vdr_lib::spawn_vdr(vdr_config.clone())
+        .await
+        .expect("pass");
+
+    // While that's spinning up, let's create the wallet.
+    let wallet_storage_a = {
+        let db_url = format!("sqlite://{}?mode=rwc", wallet_store_database_path);
+        let wallet_storage =
+            did_webplus_wallet_storage_sqlite::WalletStorageSQLite::open_url_and_run_migrations(
+                db_url.as_str(),
+            )
+            .await
+            .expect("pass");
+        Arc::new(wallet_storage)
+    };
+
+    let software_wallet = {
+        use storage_traits::StorageDynT;
+        let mut transaction_b = wallet_storage_a.begin_transaction().await.expect("pass");
+        let software_wallet = did_webplus_software_wallet::SoftwareWallet::create(
+            transaction_b.as_mut(),
+            wallet_storage_a,
+            Some("fancy wallet".to_string()),
+            None,
+        )
+        .await
+        .expect("pass");
+        transaction_b.commit().await.expect("pass");
+        software_wallet
+    };
+
+    test_util::wait_until_service_is_up(
+        "VDR",
+        format!("http://localhost:{}/health", vdr_config.listen_port).as_str(),
+    )
+    .await;
+
+    let vdr_scheme = "http";
+    let vdr_did_create_endpoint = format!(
+        "{}://{}:{}",
+        vdr_scheme, vdr_config.did_hostname, vdr_config.listen_port
+    );
+
+    (vdr_handle, vdr_did_create_endpoint, software_wallet)
+}
@@ -134,6 +134,88 @@ pub async fn did_key_sign_vjson(
     .await?)
 }
 
+/// Produce a DIDBundle for the given DID from the DID documents in the given DID doc store.  If version_id_o
+/// is specified, then the bundle will contain the DID documents up through that versionId, otherwise it
+/// will contain all DID documents in the DID doc store for that DID.  The latest attestation, if specified,
+/// must attest to the last DID document in the bundle.
+pub async fn did_bundle_create(
+    did_doc_store: &did_webplus_doc_store::DIDDocStore,
+    did: &did_webplus_core::DIDStr,
+    version_id_o: Option<u32>,
+    latest_attestation_o: Option<String>,
+) -> Result<did_webplus_resolver::DIDBundle> {
+    let range_end_exclusive_o = if let Some(version_id) = version_id_o {
+        let did_doc_record = did_doc_store
+            .get_did_doc_record_with_version_id(None, did, version_id)
+            .await?
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "DID doc store does not contain DID document for {} with versionId {}",
+                    did,
+                    version_id
+                )
+            })?;
+        Some(did_doc_record.did_documents_jsonl_octet_length as u64)
+    } else {
+        None
+    };
+    let did_documents_jsonl = did_doc_store
+        .get_did_documents_jsonl_range(None, did, None, range_end_exclusive_o)
+        .await?;
+    anyhow::ensure!(
+        !did_documents_jsonl.is_empty(),
+        "DID doc store does not contain any DID documents for {}",
+        did
+    );
+    let did_bundle = did_webplus_resolver::DIDBundle {
+        did: did.to_owned(),
+        did_documents_jsonl,
+        latest_attestation_o,
+    };
+    // Sanity check that the bundle is valid.
+    did_bundle.verify_did_documents()?;
+    Ok(did_bundle)
+}
+
+/// Create a DIDResolverBundle from the given DIDBundle-s.  The latest attestations in the bundles are only
+/// honored if they were signed by one of the given trusted attesters, each of which must be a full did:key
+/// DID.  An attestation is only trusted if the DID of its signing key is exactly one of the trusted attesters.
+pub async fn did_resolver_bundle(
+    did_bundle_v: &[did_webplus_resolver::DIDBundle],
+    trusted_attester_v: &[String],
+) -> Result<did_webplus_resolver::DIDResolverBundle> {
+    let mut trusted_attester_did_v = Vec::with_capacity(trusted_attester_v.len());
+    for trusted_attester in trusted_attester_v {
+        let trusted_attester_did =
+            did_key::DID::try_from(trusted_attester.clone()).map_err(|e| {
+                anyhow::anyhow!(
+                    "trusted attester must be a did:key DID, but it was {:?}; error was: {}",
+                    trusted_attester,
+                    e
+                )
+            })?;
+        if !trusted_attester_did_v.contains(&trusted_attester_did) {
+            trusted_attester_did_v.push(trusted_attester_did);
+        }
+    }
+    let attestation_verifier_resolver =
+        did_key::TrustedDIDKeyVerifierResolver::new(trusted_attester_did_v);
+    let attestation_verifier_resolver_o: Option<&dyn verifier_resolver::VerifierResolver> =
+        if trusted_attester_v.is_empty() {
+            None
+        } else {
+            Some(&attestation_verifier_resolver)
+        };
+
+    let mut did_resolver_bundle = did_webplus_resolver::DIDResolverBundle::new();
+    for did_bundle in did_bundle_v {
+        did_resolver_bundle
+            .add_did_bundle(did_bundle, attestation_verifier_resolver_o)
+            .await?;
+    }
+    Ok(did_resolver_bundle)
+}
+
 // TODO: Rename this function to something more appropriate
 pub async fn did_list(
     did_doc_storage: &dyn did_webplus_doc_store::DIDDocStorage,
@@ -147,6 +229,150 @@ pub async fn did_list(
     Ok(did_doc_record_v)
 }
 
+/// Export the DIDs in the given DID doc store into a static-hosting directory tree.  See
+/// did_webplus_doc_store::export_static_tree.  Returns the DIDs whose did-documents.jsonl files were written.
+pub async fn did_static_export(
+    did_doc_store: &did_webplus_doc_store::DIDDocStore,
+    root_dir: &std::path::Path,
+    host_o: Option<&str>,
+    incremental: bool,
+) -> Result<Vec<did_webplus_core::DID>> {
+    Ok(
+        did_webplus_doc_store::export_static_tree(did_doc_store, root_dir, host_o, incremental)
+            .await?,
+    )
+}
+
+/// Import the DID documents in the given did-documents.jsonl files into the given DID doc store, validating
+/// them as they're added; see did_webplus_doc_store::DIDDocStore::import_did_documents_jsonl.  Directories
+/// are searched recursively for files named did-documents.jsonl, so a directory tree produced by
+/// doc_store_export (or a static-hosting directory tree) can be imported directly.  Returns the number of
+/// DIDs and the number of DID documents that were added.
+pub async fn doc_store_import(
+    did_doc_store: &did_webplus_doc_store::DIDDocStore,
+    path_v: &[std::path::PathBuf],
+    batch_size: std::num::NonZeroUsize,
+) -> Result<(usize, usize)> {
+    let mut did_documents_jsonl_path_v = Vec::new();
+    for path in path_v.iter() {
+        collect_did_documents_jsonl_paths(path, &mut did_documents_jsonl_path_v)?;
+    }
+    let mut imported_did_count = 0;
+    let mut imported_did_document_count = 0;
+    for did_documents_jsonl_path in did_documents_jsonl_path_v.iter() {
+        let did_documents_jsonl = std::fs::read_to_string(did_documents_jsonl_path)?;
+        let added_count = did_doc_store
+            .import_did_documents_jsonl(&did_documents_jsonl, batch_size)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}: {}", did_documents_jsonl_path.display(), e))?;
+        tracing::debug!(
+            "imported {} DID document(s) from {}",
+            added_count,
+            did_documents_jsonl_path.display()
+        );
+        if added_count > 0 {
+            imported_did_count += 1;
+            imported_did_document_count += added_count;
+        }
+    }
+    Ok((imported_did_count, imported_did_document_count))
+}
+
+fn collect_did_documents_jsonl_paths(
+    path: &std::path::Path,
+    did_documents_jsonl_path_v: &mut Vec<std::path::PathBuf>,
+) -> Result<()> {
+    if path.is_dir() {
+        let mut entry_path_v = std::fs::read_dir(path)?
+            .map(|entry_r| entry_r.map(|entry| entry.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        // Sort so that the import order is deterministic.
+        entry_path_v.sort();
+        for entry_path in entry_path_v.iter() {
+            if entry_path.is_dir()
+                || entry_path.file_name() == Some(std::ffi::OsStr::new("did-documents.jsonl"))
+            {
+                collect_did_documents_jsonl_paths(entry_path, did_documents_jsonl_path_v)?;
+            }
+        }
+    } else {
+        did_documents_jsonl_path_v.push(path.to_path_buf());
+    }
+    Ok(())
+}
+
+/// Export every DID in the given DID doc store into the directory root_dir, which will contain one
+/// static-hosting directory tree (see did_static_export) per host, named by the host as it appears in the
+/// DID (e.g. "example.com" or "localhost%3A8085").  The result can be imported into another DID doc store
+/// using doc_store_import.  If incremental is true, then only DID documents not already present are
+/// written.  Returns the DIDs whose did-documents.jsonl files were written.
+pub async fn doc_store_export(
+    did_doc_store: &did_webplus_doc_store::DIDDocStore,
+    root_dir: &std::path::Path,
+    incremental: bool,
+) -> Result<Vec<did_webplus_core::DID>> {
+    let mut host_v = did_doc_store
+        .get_dids(None)
+        .await?
+        .iter()
+        .map(|did| match did.port_o() {
+            Some(port) => (format!("{}:{}", did.hostname(), port), Some(port)),
+            None => (did.hostname().to_string(), None),
+        })
+        .collect::<Vec<_>>();
+    host_v.sort();
+    host_v.dedup();
+    let mut exported_did_v = Vec::new();
+    for (host, port_o) in host_v.iter() {
+        // Percent-encode the port separator as in the DID itself, so that the directory name is portable.
+        let host_dir_name = match port_o {
+            Some(_) => host.replacen(':', "%3A", 1),
+            None => host.clone(),
+        };
+        exported_did_v.extend(
+            did_webplus_doc_store::export_static_tree(
+                did_doc_store,
+                &root_dir.join(host_dir_name),
+                Some(host.as_str()),
+                incremental,
+            )
+            .await?,
+        );
+    }
+    Ok(exported_did_v)
+}
+
+/// Copy every DID in the source DID doc store into the destination DID doc store, which may use a different
+/// DIDDocStorage backend (e.g. to migrate from SQLite to Postgres).  DID documents are validated as they're
+/// added, and ones already in the destination are skipped, so this can also be used to incrementally sync.
+/// Returns the number of DIDs and the number of DID documents that were added.
+pub async fn doc_store_copy(
+    source_did_doc_store: &did_webplus_doc_store::DIDDocStore,
+    dest_did_doc_store: &did_webplus_doc_store::DIDDocStore,
+    batch_size: std::num::NonZeroUsize,
+) -> Result<(usize, usize)> {
+    Ok(source_did_doc_store
+        .copy_to(dest_did_doc_store, batch_size)
+        .await?)
+}
+
+/// Re-verify every microledger in the given DID doc store from its root DID document, and report corrupted
+/// or orphaned records.  See did_webplus_doc_store::DIDDocStore::audit.
+pub async fn doc_store_audit(
+    did_doc_store: &did_webplus_doc_store::DIDDocStore,
+) -> Result<did_webplus_doc_store::DIDDocStoreAuditReport> {
+    Ok(did_doc_store.audit().await?)
+}
+
+/// Find the DIDKeyRecord-s for the given key (an MBPubKey or the hash of a hashed update key) in the given
+/// DID doc store.  See did_webplus_doc_store::DIDDocStore::find_did_key_records.
+pub async fn doc_store_find_key(
+    did_doc_store: &did_webplus_doc_store::DIDDocStore,
+    key: &str,
+) -> Result<Vec<did_webplus_doc_store::DIDKeyRecord>> {
+    Ok(did_doc_store.find_did_key_records(None, key).await?)
+}
+
 /// Resolve a DIDDocument.
 pub async fn did_resolve(
     did_query: &str,
@@ -186,10 +412,14 @@ pub async fn did_resolve_string(
     ))
 }
 
+/// Verify the given JWS, requiring that its signing key is authorized for the given verification
+/// relationship (e.g. assertionMethod).  Each check performed is recorded in verification_report.
 pub async fn jws_verify(
     jws: &did_webplus_jws::JWS<'_>,
     detached_payload_bytes_o: Option<&mut dyn std::io::Read>,
+    verification_relationship: verifier_resolver::VerificationRelationship,
     verifier_resolver: &dyn verifier_resolver::VerifierResolver,
+    verification_report: &mut verifier_resolver::VerificationReport,
 ) -> Result<()> {
     anyhow::ensure!(
         jws.header().kid.starts_with("did:"),
@@ -197,11 +427,156 @@ pub async fn jws_verify(
         jws.header().kid
     );
 
+    let kid = jws.header().kid.as_str();
     // Determine the verifier (i.e. public key) to use to verify the JWS.
-    let verifier_b = verifier_resolver.resolve(jws.header().kid.as_str()).await?;
+    let verifier_b = verification_report
+        .resolve_for_purpose(kid, verification_relationship, verifier_resolver)
+        .await?;
     // Verify the JWS.
-    jws.verify(verifier_b.as_ref(), detached_payload_bytes_o)?;
+    verification_report.record_result(
+        verifier_resolver::VerificationCheckKind::Signature,
+        Some(kid),
+        verification_report
+            .resolved_did_document_version(kid)
+            .as_deref(),
+        jws.verify(verifier_b.as_ref(), detached_payload_bytes_o),
+    )?;
+
+    Ok(())
+}
+
+/// Sign the given payload, producing a JWS in JWS JSON Serialization that has one signature from each of the
+/// given WalletBasedSigners (e.g. one for each of several parties' DIDs).
+pub async fn jws_json_sign<W: did_webplus_wallet::Wallet>(
+    payload_bytes: &mut dyn std::io::Read,
+    payload_presence: did_webplus_jws::JWSPayloadPresence,
+    payload_encoding: did_webplus_jws::JWSPayloadEncoding,
+    wallet_based_signer_v: &[&did_webplus_wallet::WalletBasedSigner<W>],
+) -> Result<did_webplus_jws::JWSJSON> {
+    let async_signer_v = wallet_based_signer_v
+        .iter()
+        .map(|wallet_based_signer| {
+            (
+                wallet_based_signer.key_fully_qualified().to_string(),
+                *wallet_based_signer as &dyn signature_dyn::AsyncSignerT,
+            )
+        })
+        .collect::<Vec<_>>();
+    Ok(did_webplus_jws::JWSJSON::async_signed(
+        payload_bytes,
+        payload_presence,
+        payload_encoding,
+        async_signer_v.as_slice(),
+    )
+    .await?)
+}
+
+/// Verify each signature of the given JWS JSON Serialization, requiring that each signing key is authorized
+/// for the given verification relationship, and then check that the verified signatures satisfy the given
+/// signer policy.  Returns the "kid" of each signature that verified.  A signature that fails to verify
+/// only causes an error if the signer policy isn't satisfied.  Each check performed (including those of
+/// signatures that failed to verify) is recorded in verification_report.
+pub async fn jws_json_verify(
+    jws_json: &did_webplus_jws::JWSJSON,
+    detached_payload_bytes_o: Option<&mut dyn std::io::Read>,
+    verification_relationship: verifier_resolver::VerificationRelationship,
+    signer_policy: did_webplus_jws::JWSSignerPolicy,
+    verifier_resolver: &dyn verifier_resolver::VerifierResolver,
+    verification_report: &mut verifier_resolver::VerificationReport,
+) -> Result<Vec<String>> {
+    // The detached payload is needed once per signature, so read it into memory.
+    let detached_payload_o = if let Some(detached_payload_bytes) = detached_payload_bytes_o {
+        let mut detached_payload = Vec::new();
+        detached_payload_bytes.read_to_end(&mut detached_payload)?;
+        Some(detached_payload)
+    } else {
+        None
+    };
+
+    let mut verified_kid_v = Vec::new();
+    let mut failure_v = Vec::new();
+    for (signature_index, signature) in jws_json.signatures().iter().enumerate() {
+        let kid = signature.header().kid.as_str();
+        let verify_result: Result<()> = async {
+            anyhow::ensure!(
+                kid.starts_with("did:"),
+                "JWS header \"kid\" field (which was {:?}) is expected to be a DID, i.e. start with \"did:\"",
+                kid
+            );
+            // Determine the verifier (i.e. public key) to use to verify this signature.
+            let verifier_b = verification_report
+                .resolve_for_purpose(kid, verification_relationship, verifier_resolver)
+                .await?;
+            let mut detached_payload_bytes_o = detached_payload_o.as_deref();
+            verification_report.record_result(
+                verifier_resolver::VerificationCheckKind::Signature,
+                Some(kid),
+                verification_report.resolved_did_document_version(kid).as_deref(),
+                jws_json.verify_signature(
+                    signature_index,
+                    verifier_b.as_ref(),
+                    detached_payload_bytes_o
+                        .as_mut()
+                        .map(|x| x as &mut dyn std::io::Read),
+                ),
+            )?;
+            Ok(())
+        }
+        .await;
+        match verify_result {
+            Ok(()) => {
+                verified_kid_v.push(kid);
+            }
+            Err(e) => {
+                tracing::debug!(
+                    "JWS signature {} (kid {:?}) failed to verify: {}",
+                    signature_index,
+                    kid,
+                    e
+                );
+                failure_v.push(format!(
+                    "signature {} (kid {:?}): {}",
+                    signature_index, kid, e
+                ));
+            }
+        }
+    }
+
+    if let Err(e) = signer_policy.check(jws_json.signatures().len(), verified_kid_v.as_slice()) {
+        if failure_v.is_empty() {
+            anyhow::bail!(e);
+        } else {
+            anyhow::bail!("{}; failures: {}", e, failure_v.join("; "));
+        }
+    }
+
+    Ok(verified_kid_v.into_iter().map(str::to_owned).collect())
+}
 
+/// Verify the given JWT, requiring that its signing key is authorized for the given verification
+/// relationship (e.g. assertionMethod), and then validate its claims, including that "iss" is the
+/// DID of the signing key.  Each check performed is recorded in verification_report.
+pub async fn jwt_verify(
+    jwt: &did_webplus_jws::JWT,
+    verification_relationship: verifier_resolver::VerificationRelationship,
+    validation_options: &did_webplus_jws::JWTValidationOptions,
+    verifier_resolver: &dyn verifier_resolver::VerifierResolver,
+    verification_report: &mut verifier_resolver::VerificationReport,
+) -> Result<()> {
+    jws_verify(
+        jwt.jws(),
+        None,
+        verification_relationship,
+        verifier_resolver,
+        verification_report,
+    )
+    .await?;
+    verification_report.record_result(
+        verifier_resolver::VerificationCheckKind::Claims,
+        None,
+        None,
+        jwt.validate_claims(validation_options),
+    )?;
     Ok(())
 }
 
@@ -272,14 +647,20 @@ pub async fn vjson_store_get_record(
     Ok(vjson_store.get_vjson_record(None, &self_hash).await?)
 }
 
+/// Verify the given VJSON, returning its self-hash.  Each check performed is recorded in verification_report.
 pub async fn vjson_verify(
     value: &serde_json::Value,
     vjson_resolver: &dyn vjson_core::VJSONResolver,
     verifier_resolver: &dyn verifier_resolver::VerifierResolver,
+    verification_report: &mut verifier_resolver::VerificationReport,
 ) -> Result<mbx::MBHash> {
     use vjson_core::Validate;
     let self_hash = value
-        .validate_and_return_self_hash(vjson_resolver, verifier_resolver)
+        .validate_and_return_self_hash_with_report(
+            vjson_resolver,
+            verifier_resolver,
+            verification_report,
+        )
         .await?;
     Ok(self_hash)
 }
@@ -287,6 +668,7 @@ pub async fn vjson_verify(
 pub async fn wallet_did_create(
     wallet: &dyn did_webplus_wallet::Wallet,
     vdr_did_create_endpoint: &str,
+    vdr_authz_key_id_o: Option<&did_webplus_core::DIDKeyResourceFullyQualifiedStr>,
     http_options_o: Option<&did_webplus_core::HTTPOptions>,
 ) -> Result<did_webplus_core::DIDFullyQualified> {
     // TODO: CLI args to choose the base and hash function.
@@ -298,6 +680,7 @@ pub async fn wallet_did_create(
                 vdr_did_create_endpoint,
                 mb_hash_function_for_did: &base64url_sha3_256,
                 mb_hash_function_for_update_key_o: Some(&base64url_sha3_256),
+                vdr_authz_key_id_o,
             },
             http_options_o,
         )
@@ -342,6 +725,44 @@ pub async fn wallet_did_deactivate(
         .await?)
 }
 
+/// Encrypt the given plaintext to the given recipients, producing a JWE.  Each recipient is a DID (in which case
+/// its first keyAgreement key of a supported key type is used) or a DID key resource specifying a keyAgreement
+/// key; see did_webplus_jwe::JWERecipientKey::resolve.  Note that encryption doesn't involve the sender's keys,
+/// so no wallet is needed.
+pub async fn wallet_did_encrypt(
+    plaintext: &[u8],
+    recipient_v: &[&str],
+    key_management_algorithm: did_webplus_jwe::JWEKeyManagementAlgorithm,
+    serialization: did_webplus_jwe::JWESerialization,
+    did_resolver: &dyn did_webplus_resolver::DIDResolver,
+) -> Result<String> {
+    let jwe = did_webplus_jwe::JWE::encrypt_to_recipients(
+        plaintext,
+        recipient_v,
+        key_management_algorithm,
+        serialization,
+        did_resolver,
+    )
+    .await?;
+    Ok(jwe.to_serialization_string(serialization)?)
+}
+
+/// Decrypt the given JWE (in any serialization) using a keyAgreement key controlled by the given wallet,
+/// optionally restricting to keys of the given controlled DID.  Returns the key ID of the keyAgreement key
+/// that was used and the plaintext.
+pub async fn wallet_did_decrypt(
+    jwe_str: &str,
+    wallet: &dyn did_webplus_wallet::Wallet,
+    controlled_did_o: Option<&did_webplus_core::DIDStr>,
+) -> Result<(
+    did_webplus_core::DIDKeyResourceFullyQualified,
+    zeroize::Zeroizing<Vec<u8>>,
+)> {
+    use std::str::FromStr;
+    let jwe = did_webplus_jwe::JWE::from_str(jwe_str)?;
+    Ok(jwe.decrypt(wallet, controlled_did_o).await?)
+}
+
 /// List DIDs controlled by the given wallet.  Optionally filter on the given DID.
 pub async fn wallet_did_list(
     wallet: &dyn did_webplus_wallet::Wallet,
@@ -350,7 +771,8 @@ pub async fn wallet_did_list(
     Ok(wallet.get_controlled_dids(did_o).await?)
 }
 
-/// Select a unique key from the wallet using the given filters.
+/// Select a unique signing key from the wallet using the given filters.  keyAgreement keys are for key
+/// exchange, not signing, so they're never selected.
 // TODO: Document precisely how the signing method filter vars work.
 async fn wallet_did_select_key(
     wallet: &dyn did_webplus_wallet::Wallet,
@@ -361,6 +783,10 @@ async fn wallet_did_select_key(
     did_webplus_wallet_store::VerificationMethodRecord,
     Box<dyn signature_dyn::AsyncSignerT + Send + Sync>,
 )> {
+    anyhow::ensure!(
+        key_purpose_o != Some(did_webplus_core::KeyPurpose::KeyAgreement),
+        "keyAgreement keys can't be used for signing"
+    );
     let query_result_v = wallet
         .get_locally_controlled_verification_methods(
             &did_webplus_wallet_store::LocallyControlledVerificationMethodFilter {
@@ -399,7 +825,43 @@ async fn wallet_did_select_key(
             }
         );
     }
-    Ok(query_result_v.into_iter().next().unwrap())
+    let (verification_method_record, signer_b) = query_result_v.into_iter().next().unwrap();
+    anyhow::ensure!(
+        !verification_method_record
+            .key_purpose_restriction_o
+            .is_some_and(|key_purpose_restriction| {
+                key_purpose_restriction.contains(did_webplus_core::KeyPurpose::KeyAgreement)
+            }),
+        "{} is a keyAgreement key, which can't be used for signing",
+        verification_method_record.did_key_resource_fully_qualified
+    );
+    Ok((verification_method_record, signer_b))
+}
+
+/// Add a signature from the specified key of the wallet to the given JWS (e.g. to co-sign a JWS that another
+/// party has signed).  detached_payload_bytes_o should be Some(_) if the JWS has a detached payload, and None
+/// if it has an attached payload.
+pub async fn wallet_did_cosign_jws(
+    jws_json: &mut did_webplus_jws::JWSJSON,
+    detached_payload_bytes_o: Option<&mut dyn std::io::Read>,
+    wallet: &dyn did_webplus_wallet::Wallet,
+    controlled_did_o: Option<&did_webplus_core::DIDStr>,
+    key_purpose_o: Option<did_webplus_core::KeyPurpose>,
+    key_id_o: Option<&str>,
+) -> Result<()> {
+    // Get the specified signing key.
+    let (verification_method_record, async_signer_b) =
+        wallet_did_select_key(wallet, controlled_did_o, key_purpose_o, key_id_o).await?;
+    // Form the kid (key ID).
+    let kid = verification_method_record
+        .did_key_resource_fully_qualified
+        .to_string();
+
+    jws_json
+        .async_add_signature(kid, async_signer_b.as_ref(), detached_payload_bytes_o)
+        .await?;
+
+    Ok(())
 }
 
 // TODO: Technically it's necessary to fetch all DID updates from the VDR to ensure that we use
@@ -434,6 +896,30 @@ pub async fn wallet_did_sign_jws(
     Ok(jws)
 }
 
+// TODO: Technically it's necessary to fetch all DID updates from the VDR to ensure that we use
+// a signing key that is actually valid.  But maybe that should be a separate step, and this
+// is naturally decomposed.
+/// Sign a JWT having the given claims.  If the "iss" claim is absent, it will be set to the signing DID.
+pub async fn wallet_did_sign_jwt(
+    claims: did_webplus_jws::JWTClaims,
+    wallet: &dyn did_webplus_wallet::Wallet,
+    controlled_did_o: Option<&did_webplus_core::DIDStr>,
+    key_purpose_o: Option<did_webplus_core::KeyPurpose>,
+    key_id_o: Option<&str>,
+) -> Result<did_webplus_jws::JWT> {
+    // Get the specified signing key.
+    let (verification_method_record, async_signer_b) =
+        wallet_did_select_key(wallet, controlled_did_o, key_purpose_o, key_id_o).await?;
+    // Form the kid (key ID).
+    let kid = verification_method_record
+        .did_key_resource_fully_qualified
+        .to_string();
+
+    let jwt = did_webplus_jws::JWT::async_signed(claims, kid, async_signer_b.as_ref()).await?;
+
+    Ok(jwt)
+}
+
 // TODO: Technically it's necessary to fetch all DID updates from the VDR to ensure that we use
 // a signing key that is actually valid.  But maybe that should be a separate step, and this
 // is naturally decomposed.
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{ops::Deref, sync::Arc};
 
 /// This will run once at load time (i.e. presumably before main function is called).
 #[ctor::ctor]
@@ -41,9 +41,15 @@ async fn test_did_key_generate_write_read_sign_jws_verify_impl(key_type: signatu
             signer_b.as_ref(),
         )
         .expect("pass");
-        did_webplus_cli_lib::jws_verify(&jws, None, &did_key::DIDKeyVerifierResolver)
-            .await
-            .expect("pass");
+        did_webplus_cli_lib::jws_verify(
+            &jws,
+            None,
+            verifier_resolver::VerificationRelationship::AssertionMethod,
+            &did_key::DIDKeyVerifierResolver,
+            &mut verifier_resolver::VerificationReport::new(),
+        )
+        .await
+        .expect("pass");
     }
     // Sign and then verify a detached-payload JWS
     {
@@ -57,7 +63,9 @@ async fn test_did_key_generate_write_read_sign_jws_verify_impl(key_type: signatu
         did_webplus_cli_lib::jws_verify(
             &jws,
             Some(&mut payload.as_bytes()),
+            verifier_resolver::VerificationRelationship::AssertionMethod,
             &did_key::DIDKeyVerifierResolver,
+            &mut verifier_resolver::VerificationReport::new(),
         )
         .await
         .expect("pass");
@@ -74,6 +82,26 @@ async fn test_did_key_generate_write_read_sign_jws_verify_secp256k1() {
     test_did_key_generate_write_read_sign_jws_verify_impl(signature_dyn::KeyType::Secp256k1).await;
 }
 
+#[tokio::test]
+async fn test_did_resolver_bundle_trusted_attesters() {
+    let signer_b = did_webplus_cli_lib::private_key_generate(signature_dyn::KeyType::Ed25519);
+    let did = did_webplus_cli_lib::did_key_from_private(signer_b.as_ref()).expect("pass");
+    // A full did:key DID is accepted as a trusted attester, and repeating it is harmless.
+    did_webplus_cli_lib::did_resolver_bundle(&[], &[did.to_string(), did.to_string()])
+        .await
+        .expect("pass");
+    // Anything that isn't a full did:key DID is rejected, in particular the bare "did:key:" prefix, which
+    // would otherwise be a way to trust every did:key attester.
+    for trusted_attester in [
+        "did:key:",
+        "did:webplus:example.com:uHiBKFaANAmUuq6Nla0RkFWYmUUAIHtvMgXgGbxKOzZbGYpAg",
+    ] {
+        did_webplus_cli_lib::did_resolver_bundle(&[], &[trusted_attester.to_string()])
+            .await
+            .expect_err("pass");
+    }
+}
+
 async fn test_did_key_sign_vjson_verify_impl(key_type: signature_dyn::KeyType) {
     let private_key_path = std::path::PathBuf::from(format!(
         "tests/test_did_key_sign_vjson_verify.{:?}.priv.pem",
@@ -125,9 +153,14 @@ async fn test_did_key_sign_vjson_verify_impl(key_type: signature_dyn::KeyType) {
         serde_json::to_string_pretty(&price_quote_schema).unwrap()
     );
     // Verify
-    did_webplus_cli_lib::vjson_verify(&price_quote_schema, &vjson_store, verifier_resolver)
-        .await
-        .expect("pass");
+    did_webplus_cli_lib::vjson_verify(
+        &price_quote_schema,
+        &vjson_store,
+        verifier_resolver,
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .expect("pass");
     // Store
     {
         use storage_traits::StorageDynT;
@@ -161,9 +194,14 @@ async fn test_did_key_sign_vjson_verify_impl(key_type: signature_dyn::KeyType) {
         serde_json::to_string_pretty(&price_quote).unwrap()
     );
     // Verify
-    did_webplus_cli_lib::vjson_verify(&price_quote, &vjson_store, &did_key::DIDKeyVerifierResolver)
-        .await
-        .expect("pass");
+    did_webplus_cli_lib::vjson_verify(
+        &price_quote,
+        &vjson_store,
+        &did_key::DIDKeyVerifierResolver,
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .expect("pass");
     // Store
     {
         use storage_traits::StorageDynT;
@@ -279,6 +317,10 @@ async fn test_wallet_did_create_update_sign_jws_verify() {
         vdg_base_url_v: Vec::new(),
         http_scheme_override: Default::default(),
         test_authz_api_key_vo: None,
+        registrar_permissions_o: None,
+        serve_did_web: false,
+        audit_interval_seconds_o: None,
+        admission_policy_config: Default::default(),
     };
     let vdr_handle = did_webplus_vdr_lib::spawn_vdr(vdr_config.clone())
         .await
@@ -340,6 +382,7 @@ async fn test_wallet_did_create_update_sign_jws_verify() {
     let controlled_did = did_webplus_cli_lib::wallet_did_create(
         &software_wallet,
         &vdr_did_create_endpoint,
+        None,
         http_options_o,
     )
     .await
@@ -367,9 +410,63 @@ async fn test_wallet_did_create_update_sign_jws_verify() {
         )
         .await
         .expect("pass");
-        did_webplus_cli_lib::jws_verify(&jws, None, &did_resolver_full)
+        let mut verification_report = verifier_resolver::VerificationReport::new();
+        did_webplus_cli_lib::jws_verify(
+            &jws,
+            None,
+            verifier_resolver::VerificationRelationship::AssertionMethod,
+            &did_resolver_full,
+            &mut verification_report,
+        )
+        .await
+        .expect("pass");
+        tracing::debug!(
+            "verification report: {}",
+            serde_json::to_string(&verification_report).unwrap()
+        );
+        assert!(verification_report.is_valid());
+        assert_eq!(
+            verification_report
+                .check_v
+                .iter()
+                .map(|check| check.kind)
+                .collect::<Vec<_>>(),
+            vec![
+                verifier_resolver::VerificationCheckKind::KeyResolution,
+                verifier_resolver::VerificationCheckKind::KeyPurpose,
+                verifier_resolver::VerificationCheckKind::Signature,
+            ]
+        );
+        // The report should record the exact DID document version that the signing key came from.
+        let did_document_version = jws.header().kid.split('#').next().unwrap();
+        for check in verification_report.check_v.iter() {
+            assert_eq!(
+                check.did_document_version_o.as_deref(),
+                Some(did_document_version)
+            );
+        }
+
+        // Verifying with the wrong key purpose should record a failed key purpose check.
+        let mut verification_report = verifier_resolver::VerificationReport::new();
+        assert!(
+            did_webplus_cli_lib::jws_verify(
+                &jws,
+                None,
+                verifier_resolver::VerificationRelationship::Authentication,
+                &did_resolver_full,
+                &mut verification_report,
+            )
             .await
-            .expect("pass");
+            .is_err()
+        );
+        assert!(!verification_report.is_valid());
+        assert_eq!(
+            verification_report
+                .failed_check_iter()
+                .map(|check| check.kind)
+                .collect::<Vec<_>>(),
+            vec![verifier_resolver::VerificationCheckKind::KeyPurpose]
+        );
     }
     // Sign and then verify a detached-payload JWS
     {
@@ -384,9 +481,85 @@ async fn test_wallet_did_create_update_sign_jws_verify() {
         )
         .await
         .expect("pass");
-        did_webplus_cli_lib::jws_verify(&jws, Some(&mut payload.as_bytes()), &did_resolver_full)
+        did_webplus_cli_lib::jws_verify(
+            &jws,
+            Some(&mut payload.as_bytes()),
+            verifier_resolver::VerificationRelationship::AssertionMethod,
+            &did_resolver_full,
+            &mut verifier_resolver::VerificationReport::new(),
+        )
+        .await
+        .expect("pass");
+    }
+    // Sign and then verify a JWT
+    {
+        let claims = did_webplus_jws::JWTClaims {
+            aud: Some(did_webplus_jws::JWTAudience::from(
+                "https://verifier.example".to_string(),
+            )),
+            exp: Some(i64::MAX),
+            ..Default::default()
+        };
+        let jwt = did_webplus_cli_lib::wallet_did_sign_jwt(
+            claims.clone(),
+            &software_wallet,
+            Some(did),
+            Some(did_webplus_core::KeyPurpose::Authentication),
+            None,
+        )
+        .await
+        .expect("pass");
+        assert_eq!(jwt.claims().iss.as_deref(), Some(did.as_str()));
+        let jwt = jwt.as_str().parse::<did_webplus_jws::JWT>().expect("pass");
+        let validation_options = did_webplus_jws::JWTValidationOptions {
+            expected_audience_o: Some("https://verifier.example".to_string()),
+            ..Default::default()
+        };
+        did_webplus_cli_lib::jwt_verify(
+            &jwt,
+            verifier_resolver::VerificationRelationship::Authentication,
+            &validation_options,
+            &did_resolver_full,
+            &mut verifier_resolver::VerificationReport::new(),
+        )
+        .await
+        .expect("pass");
+        // Wrong audience.
+        assert!(
+            did_webplus_cli_lib::jwt_verify(
+                &jwt,
+                verifier_resolver::VerificationRelationship::Authentication,
+                &did_webplus_jws::JWTValidationOptions::default(),
+                &did_resolver_full,
+                &mut verifier_resolver::VerificationReport::new(),
+            )
             .await
-            .expect("pass");
+            .is_err()
+        );
+        // Expired.
+        let expired_jwt = did_webplus_cli_lib::wallet_did_sign_jwt(
+            did_webplus_jws::JWTClaims {
+                exp: Some(1),
+                ..claims
+            },
+            &software_wallet,
+            Some(did),
+            Some(did_webplus_core::KeyPurpose::Authentication),
+            None,
+        )
+        .await
+        .expect("pass");
+        assert!(
+            did_webplus_cli_lib::jwt_verify(
+                &expired_jwt,
+                verifier_resolver::VerificationRelationship::Authentication,
+                &validation_options,
+                &did_resolver_full,
+                &mut verifier_resolver::VerificationReport::new(),
+            )
+            .await
+            .is_err()
+        );
     }
 
     tracing::info!("Shutting down VDR");
@@ -423,6 +596,10 @@ async fn test_wallet_did_sign_vjson_verify() {
         vdg_base_url_v: Vec::new(),
         http_scheme_override: Default::default(),
         test_authz_api_key_vo: None,
+        registrar_permissions_o: None,
+        serve_did_web: false,
+        audit_interval_seconds_o: None,
+        admission_policy_config: Default::default(),
     };
     let vdr_handle = did_webplus_vdr_lib::spawn_vdr(vdr_config.clone())
         .await
@@ -484,6 +661,7 @@ async fn test_wallet_did_sign_vjson_verify() {
     let controlled_did = did_webplus_cli_lib::wallet_did_create(
         &software_wallet,
         &vdr_did_create_endpoint,
+        None,
         http_options_o,
     )
     .await
@@ -508,7 +686,7 @@ async fn test_wallet_did_sign_vjson_verify() {
         &mut price_quote_schema,
         &software_wallet,
         Some(did),
-        Some(did_webplus_core::KeyPurpose::Authentication),
+        Some(did_webplus_core::KeyPurpose::AssertionMethod),
         None,
         &vjson_store,
         &did_resolver_full,
@@ -520,9 +698,14 @@ async fn test_wallet_did_sign_vjson_verify() {
         serde_json::to_string_pretty(&price_quote_schema).unwrap()
     );
     // Verify
-    did_webplus_cli_lib::vjson_verify(&price_quote_schema, &vjson_store, &did_resolver_full)
-        .await
-        .expect("pass");
+    did_webplus_cli_lib::vjson_verify(
+        &price_quote_schema,
+        &vjson_store,
+        &did_resolver_full,
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .expect("pass");
     // Store
     {
         use storage_traits::StorageDynT;
@@ -548,7 +731,7 @@ async fn test_wallet_did_sign_vjson_verify() {
         &mut price_quote,
         &software_wallet,
         Some(did),
-        Some(did_webplus_core::KeyPurpose::Authentication),
+        Some(did_webplus_core::KeyPurpose::AssertionMethod),
         None,
         &vjson_store,
         &did_resolver_full,
@@ -560,9 +743,14 @@ async fn test_wallet_did_sign_vjson_verify() {
         serde_json::to_string_pretty(&price_quote).unwrap()
     );
     // Verify
-    did_webplus_cli_lib::vjson_verify(&price_quote, &vjson_store, &did_resolver_full)
-        .await
-        .expect("pass");
+    did_webplus_cli_lib::vjson_verify(
+        &price_quote,
+        &vjson_store,
+        &did_resolver_full,
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .expect("pass");
     // Store
     {
         use storage_traits::StorageDynT;
@@ -582,3 +770,488 @@ async fn test_wallet_did_sign_vjson_verify() {
     tracing::info!("Shutting down VDR");
     vdr_handle.abort();
 }
+
+#[tokio::test]
+async fn test_wallet_did_encrypt_decrypt() {
+    // TODO: Use env vars to be able to point to a "real" VDR.
+
+    let alice_wallet_store_database_path =
+        "tests/test_wallet_did_encrypt_decrypt.alice.wallet-store.db";
+    let bob_wallet_store_database_path =
+        "tests/test_wallet_did_encrypt_decrypt.bob.wallet-store.db";
+    let did_doc_store_database_path = "tests/test_wallet_did_encrypt_decrypt.did-doc-store.db";
+
+    // Delete any existing database files so that we're starting from a consistent, blank start every time.
+    for database_path in [
+        alice_wallet_store_database_path,
+        bob_wallet_store_database_path,
+        did_doc_store_database_path,
+    ] {
+        if std::fs::exists(database_path).expect("pass") {
+            std::fs::remove_file(database_path).expect("pass");
+        }
+    }
+
+    let vdr_config = did_webplus_vdr_lib::VDRConfig {
+        did_hostname: "localhost".to_string(),
+        did_port_o: Some(14085),
+        listen_port: 14085,
+        database_url: "postgres:///test_wallet_did_encrypt_decrypt_vdr".to_string(),
+        database_max_connections: 10,
+        vdg_base_url_v: Vec::new(),
+        http_scheme_override: Default::default(),
+        test_authz_api_key_vo: None,
+        registrar_permissions_o: None,
+        serve_did_web: false,
+        audit_interval_seconds_o: None,
+        admission_policy_config: Default::default(),
+    };
+    let vdr_handle = did_webplus_vdr_lib::spawn_vdr(vdr_config.clone())
+        .await
+        .expect("pass");
+
+    let http_options_o: Option<&did_webplus_core::HTTPOptions> = None;
+    let vdr_scheme = did_webplus_core::HTTPSchemeOverride::determine_http_scheme_for_host_from(
+        http_options_o.map(|o| &o.http_scheme_override),
+        &vdr_config.did_hostname,
+    )
+    .unwrap();
+    let vdr_did_create_endpoint = format!(
+        "{}://{}:{}",
+        vdr_scheme, vdr_config.did_hostname, vdr_config.listen_port
+    );
+
+    let did_resolver_full = {
+        let db_url = format!("sqlite://{}?mode=rwc", did_doc_store_database_path);
+        let did_doc_storage =
+            did_webplus_doc_storage_sqlite::DIDDocStorageSQLite::open_url_and_run_migrations(
+                db_url.as_str(),
+                None,
+            )
+            .await
+            .expect("pass");
+        let did_doc_store = did_webplus_doc_store::DIDDocStore::new(Arc::new(did_doc_storage));
+        did_webplus_resolver::DIDResolverFull::new(did_doc_store, None, http_options_o.cloned())
+            .unwrap()
+    };
+
+    let mut software_wallet_v = Vec::new();
+    for wallet_store_database_path in [
+        alice_wallet_store_database_path,
+        bob_wallet_store_database_path,
+    ] {
+        let wallet_storage = {
+            let db_url = format!("sqlite://{}?mode=rwc", wallet_store_database_path);
+            did_webplus_wallet_storage_sqlite::WalletStorageSQLite::open_url_and_run_migrations(
+                db_url.as_str(),
+            )
+            .await
+            .expect("pass")
+        };
+        let wallet_storage_a = Arc::new(wallet_storage);
+        use storage_traits::StorageDynT;
+        let mut transaction_b = wallet_storage_a.begin_transaction().await.expect("pass");
+        let software_wallet = did_webplus_software_wallet::SoftwareWallet::create(
+            transaction_b.as_mut(),
+            wallet_storage_a,
+            Some("created by test_wallet_did_encrypt_decrypt".to_string()),
+            None,
+        )
+        .await
+        .expect("pass");
+        transaction_b.commit().await.expect("pass");
+        software_wallet_v.push(software_wallet);
+    }
+    let bob_wallet = software_wallet_v.pop().unwrap();
+    let alice_wallet = software_wallet_v.pop().unwrap();
+
+    test_util::wait_until_service_is_up(
+        "VDR",
+        format!("http://localhost:{}/health", vdr_config.listen_port).as_str(),
+    )
+    .await;
+
+    let alice_did = did_webplus_cli_lib::wallet_did_create(
+        &alice_wallet,
+        &vdr_did_create_endpoint,
+        None,
+        http_options_o,
+    )
+    .await
+    .expect("pass")
+    .did()
+    .to_owned();
+    let bob_did = did_webplus_cli_lib::wallet_did_create(
+        &bob_wallet,
+        &vdr_did_create_endpoint,
+        None,
+        http_options_o,
+    )
+    .await
+    .expect("pass")
+    .did()
+    .to_owned();
+    // Update Bob's DID, so that encryption has to use the latest keyAgreement keys.
+    let bob_controlled_did =
+        did_webplus_cli_lib::wallet_did_update(&bob_wallet, &bob_did, http_options_o)
+            .await
+            .expect("pass");
+    tracing::debug!("Alice's DID: {}, Bob's DID: {}", alice_did, bob_did);
+
+    let plaintext = r#"{"splunge": true}"#;
+    // Encrypt to Bob's DID, and to each of Bob's keyAgreement keys (X25519 is "#2" and P-256 is "#3"),
+    // using both key management algorithms and both serializations.
+    let bob_x25519_key_id = bob_controlled_did.with_fragment("2").to_string();
+    let bob_p256_key_id = bob_controlled_did.with_fragment("3").to_string();
+    for recipient in [
+        bob_did.as_str(),
+        bob_x25519_key_id.as_str(),
+        bob_p256_key_id.as_str(),
+    ] {
+        for key_management_algorithm in [
+            did_webplus_jwe::JWEKeyManagementAlgorithm::ECDHES,
+            did_webplus_jwe::JWEKeyManagementAlgorithm::ECDHESA256KW,
+        ] {
+            for serialization in [
+                did_webplus_jwe::JWESerialization::Compact,
+                did_webplus_jwe::JWESerialization::JSON,
+            ] {
+                let jwe_string = did_webplus_cli_lib::wallet_did_encrypt(
+                    plaintext.as_bytes(),
+                    &[recipient],
+                    key_management_algorithm,
+                    serialization,
+                    &did_resolver_full,
+                )
+                .await
+                .expect("pass");
+                tracing::debug!("JWE: {}", jwe_string);
+                let (key_id, decrypted) =
+                    did_webplus_cli_lib::wallet_did_decrypt(&jwe_string, &bob_wallet, None)
+                        .await
+                        .expect("pass");
+                assert_eq!(key_id.did(), bob_did.deref());
+                assert_eq!(decrypted.as_slice(), plaintext.as_bytes());
+                // Alice is not a recipient, so can't decrypt.
+                assert!(
+                    did_webplus_cli_lib::wallet_did_decrypt(&jwe_string, &alice_wallet, None)
+                        .await
+                        .is_err()
+                );
+            }
+        }
+    }
+
+    // Encrypt to both Alice and Bob, and check that each can decrypt it.
+    {
+        let jwe_string = did_webplus_cli_lib::wallet_did_encrypt(
+            plaintext.as_bytes(),
+            &[alice_did.as_str(), bob_p256_key_id.as_str()],
+            did_webplus_jwe::JWEKeyManagementAlgorithm::ECDHESA256KW,
+            did_webplus_jwe::JWESerialization::JSON,
+            &did_resolver_full,
+        )
+        .await
+        .expect("pass");
+        for (wallet, did) in [
+            (&alice_wallet, alice_did.deref()),
+            (&bob_wallet, bob_did.deref()),
+        ] {
+            let (key_id, decrypted) =
+                did_webplus_cli_lib::wallet_did_decrypt(&jwe_string, wallet, Some(did))
+                    .await
+                    .expect("pass");
+            assert_eq!(key_id.did(), did);
+            assert_eq!(decrypted.as_slice(), plaintext.as_bytes());
+        }
+        // ECDH-ES and the compact serialization only support a single recipient.
+        assert!(
+            did_webplus_cli_lib::wallet_did_encrypt(
+                plaintext.as_bytes(),
+                &[alice_did.as_str(), bob_did.as_str()],
+                did_webplus_jwe::JWEKeyManagementAlgorithm::ECDHES,
+                did_webplus_jwe::JWESerialization::JSON,
+                &did_resolver_full,
+            )
+            .await
+            .is_err()
+        );
+        assert!(
+            did_webplus_cli_lib::wallet_did_encrypt(
+                plaintext.as_bytes(),
+                &[alice_did.as_str(), bob_did.as_str()],
+                did_webplus_jwe::JWEKeyManagementAlgorithm::ECDHESA256KW,
+                did_webplus_jwe::JWESerialization::Compact,
+                &did_resolver_full,
+            )
+            .await
+            .is_err()
+        );
+    }
+
+    // A key that is not listed under keyAgreement can't be a recipient.
+    let bob_authentication_key_id = bob_controlled_did.with_fragment("0").to_string();
+    assert!(
+        did_webplus_cli_lib::wallet_did_encrypt(
+            plaintext.as_bytes(),
+            &[bob_authentication_key_id.as_str()],
+            did_webplus_jwe::JWEKeyManagementAlgorithm::ECDHESA256KW,
+            did_webplus_jwe::JWESerialization::Compact,
+            &did_resolver_full,
+        )
+        .await
+        .is_err()
+    );
+
+    tracing::info!("Shutting down VDR");
+    vdr_handle.abort();
+}
+
+#[tokio::test]
+async fn test_wallet_did_sign_jws_json_verify() {
+    // TODO: Use env vars to be able to point to a "real" VDR.
+
+    let wallet_store_database_path_v = [
+        "tests/test_wallet_did_sign_jws_json_verify.alice.wallet-store.db",
+        "tests/test_wallet_did_sign_jws_json_verify.bob.wallet-store.db",
+        "tests/test_wallet_did_sign_jws_json_verify.carol.wallet-store.db",
+    ];
+    let did_doc_store_database_path = "tests/test_wallet_did_sign_jws_json_verify.did-doc-store.db";
+
+    // Delete any existing database files so that we're starting from a consistent, blank start every time.
+    for database_path in wallet_store_database_path_v
+        .iter()
+        .copied()
+        .chain(std::iter::once(did_doc_store_database_path))
+    {
+        if std::fs::exists(database_path).expect("pass") {
+            std::fs::remove_file(database_path).expect("pass");
+        }
+    }
+
+    let vdr_config = did_webplus_vdr_lib::VDRConfig {
+        did_hostname: "localhost".to_string(),
+        did_port_o: Some(14086),
+        listen_port: 14086,
+        database_url: "postgres:///test_wallet_did_sign_jws_json_verify_vdr".to_string(),
+        database_max_connections: 10,
+        vdg_base_url_v: Vec::new(),
+        http_scheme_override: Default::default(),
+        test_authz_api_key_vo: None,
+        registrar_permissions_o: None,
+        serve_did_web: false,
+        audit_interval_seconds_o: None,
+        admission_policy_config: Default::default(),
+    };
+    let vdr_handle = did_webplus_vdr_lib::spawn_vdr(vdr_config.clone())
+        .await
+        .expect("pass");
+
+    let http_options_o: Option<&did_webplus_core::HTTPOptions> = None;
+    let vdr_scheme = did_webplus_core::HTTPSchemeOverride::determine_http_scheme_for_host_from(
+        http_options_o.map(|o| &o.http_scheme_override),
+        &vdr_config.did_hostname,
+    )
+    .unwrap();
+    let vdr_did_create_endpoint = format!(
+        "{}://{}:{}",
+        vdr_scheme, vdr_config.did_hostname, vdr_config.listen_port
+    );
+
+    let did_resolver_full = {
+        let db_url = format!("sqlite://{}?mode=rwc", did_doc_store_database_path);
+        let did_doc_storage =
+            did_webplus_doc_storage_sqlite::DIDDocStorageSQLite::open_url_and_run_migrations(
+                db_url.as_str(),
+                None,
+            )
+            .await
+            .expect("pass");
+        let did_doc_store = did_webplus_doc_store::DIDDocStore::new(Arc::new(did_doc_storage));
+        did_webplus_resolver::DIDResolverFull::new(did_doc_store, None, http_options_o.cloned())
+            .unwrap()
+    };
+    let verifier_resolver_map = verifier_resolver::VerifierResolverMap::new()
+        .with("did:webplus:", Box::new(did_resolver_full.clone()));
+
+    test_util::wait_until_service_is_up(
+        "VDR",
+        format!("http://localhost:{}/health", vdr_config.listen_port).as_str(),
+    )
+    .await;
+
+    // Create a wallet and a DID for each of Alice, Bob, and Carol.
+    let mut software_wallet_v = Vec::new();
+    let mut did_v = Vec::new();
+    for wallet_store_database_path in wallet_store_database_path_v {
+        let wallet_storage = {
+            let db_url = format!("sqlite://{}?mode=rwc", wallet_store_database_path);
+            did_webplus_wallet_storage_sqlite::WalletStorageSQLite::open_url_and_run_migrations(
+                db_url.as_str(),
+            )
+            .await
+            .expect("pass")
+        };
+        let wallet_storage_a = Arc::new(wallet_storage);
+        use storage_traits::StorageDynT;
+        let mut transaction_b = wallet_storage_a.begin_transaction().await.expect("pass");
+        let software_wallet = did_webplus_software_wallet::SoftwareWallet::create(
+            transaction_b.as_mut(),
+            wallet_storage_a,
+            Some("created by test_wallet_did_sign_jws_json_verify".to_string()),
+            None,
+        )
+        .await
+        .expect("pass");
+        transaction_b.commit().await.expect("pass");
+        let did = did_webplus_cli_lib::wallet_did_create(
+            &software_wallet,
+            &vdr_did_create_endpoint,
+            None,
+            http_options_o,
+        )
+        .await
+        .expect("pass")
+        .did()
+        .to_owned();
+        tracing::debug!("created DID: {}", did);
+        software_wallet_v.push(software_wallet);
+        did_v.push(did);
+    }
+
+    let payload = r#"{"shipment": "ABC-123", "quantity": 40}"#;
+    for payload_presence in [
+        did_webplus_jws::JWSPayloadPresence::Attached,
+        did_webplus_jws::JWSPayloadPresence::Detached,
+    ] {
+        let detached_payload_o = || {
+            if payload_presence == did_webplus_jws::JWSPayloadPresence::Detached {
+                Some(payload.as_bytes())
+            } else {
+                None
+            }
+        };
+
+        // Alice and Bob sign together using WalletBasedSigners.
+        let mut wallet_based_signer_v = Vec::new();
+        for (software_wallet, did) in software_wallet_v.iter().zip(did_v.iter()).take(2) {
+            wallet_based_signer_v.push(
+                did_webplus_wallet::WalletBasedSigner::new(
+                    software_wallet.clone(),
+                    did,
+                    did_webplus_core::KeyPurpose::AssertionMethod,
+                    None,
+                    http_options_o,
+                    false,
+                )
+                .await
+                .expect("pass"),
+            );
+        }
+        let mut jws_json = did_webplus_cli_lib::jws_json_sign(
+            &mut payload.as_bytes(),
+            payload_presence,
+            did_webplus_jws::JWSPayloadEncoding::Base64,
+            wallet_based_signer_v.iter().collect::<Vec<_>>().as_slice(),
+        )
+        .await
+        .expect("pass");
+        assert_eq!(jws_json.signatures().len(), 2);
+        tracing::debug!("JWS JSON: {}", jws_json);
+
+        for signer_policy in [
+            did_webplus_jws::JWSSignerPolicy::All,
+            did_webplus_jws::JWSSignerPolicy::Any,
+            did_webplus_jws::JWSSignerPolicy::KOfN(2),
+        ] {
+            let verified_kid_v = did_webplus_cli_lib::jws_json_verify(
+                &jws_json,
+                detached_payload_o()
+                    .as_mut()
+                    .map(|x| x as &mut dyn std::io::Read),
+                verifier_resolver::VerificationRelationship::AssertionMethod,
+                signer_policy,
+                &verifier_resolver_map,
+                &mut verifier_resolver::VerificationReport::new(),
+            )
+            .await
+            .expect("pass");
+            assert_eq!(verified_kid_v.len(), 2);
+        }
+        assert!(
+            did_webplus_cli_lib::jws_json_verify(
+                &jws_json,
+                detached_payload_o()
+                    .as_mut()
+                    .map(|x| x as &mut dyn std::io::Read),
+                verifier_resolver::VerificationRelationship::AssertionMethod,
+                did_webplus_jws::JWSSignerPolicy::KOfN(3),
+                &verifier_resolver_map,
+                &mut verifier_resolver::VerificationReport::new(),
+            )
+            .await
+            .is_err()
+        );
+
+        // Carol co-signs, but with her capabilityInvocation key, which is not authorized for assertionMethod.
+        did_webplus_cli_lib::wallet_did_cosign_jws(
+            &mut jws_json,
+            detached_payload_o()
+                .as_mut()
+                .map(|x| x as &mut dyn std::io::Read),
+            &software_wallet_v[2],
+            Some(&did_v[2]),
+            Some(did_webplus_core::KeyPurpose::CapabilityInvocation),
+            None,
+        )
+        .await
+        .expect("pass");
+        assert_eq!(jws_json.signatures().len(), 3);
+        // Round-trip through the General JWS JSON Serialization.
+        let jws_json = jws_json
+            .to_general_json_string()
+            .expect("pass")
+            .parse::<did_webplus_jws::JWSJSON>()
+            .expect("pass");
+        for (signer_policy, expected_success) in [
+            (did_webplus_jws::JWSSignerPolicy::All, false),
+            (did_webplus_jws::JWSSignerPolicy::Any, true),
+            (did_webplus_jws::JWSSignerPolicy::KOfN(2), true),
+            (did_webplus_jws::JWSSignerPolicy::KOfN(3), false),
+        ] {
+            let verify_result = did_webplus_cli_lib::jws_json_verify(
+                &jws_json,
+                detached_payload_o()
+                    .as_mut()
+                    .map(|x| x as &mut dyn std::io::Read),
+                verifier_resolver::VerificationRelationship::AssertionMethod,
+                signer_policy,
+                &verifier_resolver_map,
+                &mut verifier_resolver::VerificationReport::new(),
+            )
+            .await;
+            tracing::debug!(
+                "signer policy {}: verify result: {:?}",
+                signer_policy,
+                verify_result
+            );
+            assert_eq!(verify_result.is_ok(), expected_success);
+        }
+        // Carol's signature is valid for capabilityInvocation.
+        did_webplus_cli_lib::jws_json_verify(
+            &jws_json,
+            detached_payload_o()
+                .as_mut()
+                .map(|x| x as &mut dyn std::io::Read),
+            verifier_resolver::VerificationRelationship::CapabilityInvocation,
+            did_webplus_jws::JWSSignerPolicy::Any,
+            &verifier_resolver_map,
+            &mut verifier_resolver::VerificationReport::new(),
+        )
+        .await
+        .expect("pass");
+    }
+
+    tracing::info!("Shutting down VDR");
+    vdr_handle.abort();
+}
@@ -1,8 +1,10 @@
 use crate::{
-    DIDKeyFromPrivate, DIDKeyGenerate, DIDKeySignJWS, DIDKeySignVJSON, DIDList, DIDResolve,
-    JWSVerify, Result, VJSONDefaultSchema, VJSONSelfHash, VJSONStoreGet, VJSONVerify,
-    WalletDIDCreate, WalletDIDDeactivate, WalletDIDList, WalletDIDSignJWS, WalletDIDSignVJSON,
-    WalletDIDUpdate, WalletList,
+    DIDBundleCreate, DIDKeyFromPrivate, DIDKeyGenerate, DIDKeySignJWS, DIDKeySignVJSON, DIDList,
+    DIDResolve, DIDStaticExport, DocStoreAudit, DocStoreCopy, DocStoreExport, DocStoreFindKey,
+    DocStoreImport, JWSVerify, JWTVerify, Result, VJSONDefaultSchema, VJSONSelfHash, VJSONStoreGet,
+    VJSONVerify, WalletDIDCreate, WalletDIDDeactivate, WalletDIDDecrypt, WalletDIDEncrypt,
+    WalletDIDList, WalletDIDSignJWS, WalletDIDSignJWT, WalletDIDSignVJSON, WalletDIDUpdate,
+    WalletList,
 };
 
 /// did:webplus CLI tool for all client-side operations and related utility operations.  Note that some subcommands
@@ -14,8 +16,12 @@ pub enum Root {
     #[command(subcommand)]
     DIDKey(DIDKey),
     #[command(subcommand)]
+    DocStore(DocStore),
+    #[command(subcommand)]
     JWS(JWS),
     #[command(subcommand)]
+    JWT(JWT),
+    #[command(subcommand)]
     VJSON(VJSON),
     #[command(subcommand)]
     Wallet(Wallet),
@@ -26,7 +32,9 @@ impl Root {
         match self {
             Self::DID(x) => x.handle().await,
             Self::DIDKey(x) => x.handle().await,
+            Self::DocStore(x) => x.handle().await,
             Self::JWS(x) => x.handle().await,
+            Self::JWT(x) => x.handle().await,
             // Self::Verify(x) => x.handle().await,
             Self::VJSON(x) => x.handle().await,
             Self::Wallet(x) => x.handle().await,
@@ -38,15 +46,36 @@ impl Root {
 /// with verifying parties that don't necessarily control a DID.
 #[derive(clap::Subcommand)]
 pub enum DID {
+    #[command(subcommand)]
+    Bundle(DIDBundle),
     List(DIDList),
     Resolve(DIDResolve),
+    StaticExport(DIDStaticExport),
 }
 
 impl DID {
     pub async fn handle(self) -> Result<()> {
         match self {
+            Self::Bundle(x) => x.handle().await,
             Self::List(x) => x.handle().await,
             Self::Resolve(x) => x.handle().await,
+            Self::StaticExport(x) => x.handle().await,
+        }
+    }
+}
+
+/// DID bundle operations.  A DID bundle is a self-contained package of DID documents for a DID, which can
+/// be used to verify signatures offline.  To verify a JWS or VJSON against DID bundles, use the `jws verify`
+/// or `vjson verify` command with `--resolver bundle --bundle <FILE>`.
+#[derive(clap::Subcommand)]
+pub enum DIDBundle {
+    Create(DIDBundleCreate),
+}
+
+impl DIDBundle {
+    pub async fn handle(self) -> Result<()> {
+        match self {
+            Self::Create(x) => x.handle().await,
         }
     }
 }
@@ -86,6 +115,28 @@ impl DIDKeySign {
     }
 }
 
+/// Bulk operations on DID doc stores, e.g. for backup, auditing, or migrating between DID doc store backends.
+#[derive(clap::Subcommand)]
+pub enum DocStore {
+    Audit(DocStoreAudit),
+    Copy(DocStoreCopy),
+    Export(DocStoreExport),
+    FindKey(DocStoreFindKey),
+    Import(DocStoreImport),
+}
+
+impl DocStore {
+    pub async fn handle(self) -> Result<()> {
+        match self {
+            Self::Audit(x) => x.handle().await,
+            Self::Copy(x) => x.handle().await,
+            Self::Export(x) => x.handle().await,
+            Self::FindKey(x) => x.handle().await,
+            Self::Import(x) => x.handle().await,
+        }
+    }
+}
+
 /// JWS-related operations (JSON Web Signature).  Note that for wallet-controlled, DID-based signing, see
 /// the `wallet did sign jws` command, and for did:key-based signing, see the `did-key sign jws` command.
 
@@ -102,6 +153,21 @@ impl JWS {
     }
 }
 
+/// JWT-related operations (JSON Web Token).  Note that for wallet-controlled, DID-based signing, see
+/// the `wallet did sign jwt` command.
+#[derive(clap::Subcommand)]
+pub enum JWT {
+    Verify(JWTVerify),
+}
+
+impl JWT {
+    pub async fn handle(self) -> Result<()> {
+        match self {
+            Self::Verify(x) => x.handle().await,
+        }
+    }
+}
+
 /// VJSON-related operations (Verifiable JSON).  Note that for wallet-controlled, DID-based signing, see
 /// the `wallet did sign vjson` command, and for did:key-based signing, see the `did-key sign vjson` command.
 #[derive(clap::Subcommand)]
@@ -162,6 +228,8 @@ impl Wallet {
 pub enum WalletDID {
     Create(WalletDIDCreate),
     Deactivate(WalletDIDDeactivate),
+    Decrypt(WalletDIDDecrypt),
+    Encrypt(WalletDIDEncrypt),
     List(WalletDIDList),
     #[command(subcommand)]
     Sign(WalletDIDSign),
@@ -173,6 +241,8 @@ impl WalletDID {
         match self {
             Self::Create(x) => x.handle().await,
             Self::Deactivate(x) => x.handle().await,
+            Self::Decrypt(x) => x.handle().await,
+            Self::Encrypt(x) => x.handle().await,
             Self::List(x) => x.handle().await,
             Self::Sign(x) => x.handle().await,
             Self::Update(x) => x.handle().await,
@@ -184,6 +254,7 @@ impl WalletDID {
 #[derive(clap::Subcommand)]
 pub enum WalletDIDSign {
     JWS(WalletDIDSignJWS),
+    JWT(WalletDIDSignJWT),
     VJSON(WalletDIDSignVJSON),
 }
 
@@ -191,6 +262,7 @@ impl WalletDIDSign {
     pub async fn handle(self) -> Result<()> {
         match self {
             Self::JWS(x) => x.handle().await,
+            Self::JWT(x) => x.handle().await,
             Self::VJSON(x) => x.handle().await,
         }
     }
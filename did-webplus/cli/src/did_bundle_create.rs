@@ -0,0 +1,62 @@
+use crate::{DIDDocStoreArgs, NewlineArgs, Result};
+use std::io::Write;
+
+/// Produce a DID bundle for the specified DID from the DID documents in the specified DID doc store, and
+/// write it (as JSON) to stdout.  A DID bundle contains a prefix of the DID's did-documents.jsonl file and
+/// optionally a "latest as of" attestation, and can be used to verify signatures (e.g. JWS or VJSON) by the
+/// DID completely offline, by specifying `--resolver bundle --bundle <FILE>` to the relevant verify command.
+///
+/// Note that this only uses the DID documents already present in the DID doc store; to ensure the bundle
+/// is up to date, resolve the DID (using the "full" resolver with the same DID doc store) beforehand.
+#[derive(Debug, clap::Parser)]
+pub struct DIDBundleCreate {
+    #[command(flatten)]
+    pub did_doc_store_args: DIDDocStoreArgs,
+    /// The DID to produce a bundle for.
+    #[arg(name = "did", long, value_name = "DID")]
+    pub did: did_webplus_core::DID,
+    /// If specified, the bundle will contain the DID documents up through this versionId.  Otherwise it will
+    /// contain all DID documents for the DID that are present in the DID doc store.
+    #[arg(name = "version-id", long, value_name = "ID")]
+    pub version_id_o: Option<u32>,
+    /// Specify the file from which to read the "latest as of" attestation (a JWS, typically signed by a VDG)
+    /// to include in the bundle.  The attestation must attest to the last DID document in the bundle.
+    #[arg(name = "latest-attestation-file", long, value_name = "FILE")]
+    pub latest_attestation_file_o: Option<std::path::PathBuf>,
+    #[command(flatten)]
+    pub newline_args: NewlineArgs,
+}
+
+impl DIDBundleCreate {
+    pub async fn handle(self) -> Result<()> {
+        // Handle CLI args and input
+        let did_doc_store = self.did_doc_store_args.open_did_doc_store().await?;
+        let latest_attestation_o =
+            if let Some(latest_attestation_file) = self.latest_attestation_file_o.as_ref() {
+                Some(
+                    std::fs::read_to_string(latest_attestation_file)?
+                        .trim()
+                        .to_string(),
+                )
+            } else {
+                None
+            };
+
+        // Do the processing
+        let did_bundle = did_webplus_cli_lib::did_bundle_create(
+            &did_doc_store,
+            &self.did,
+            self.version_id_o,
+            latest_attestation_o,
+        )
+        .await?;
+
+        // Print the DID bundle and optional newline.
+        serde_json::to_writer(std::io::stdout(), &did_bundle)?;
+        self.newline_args
+            .print_newline_if_necessary(&mut std::io::stdout())?;
+        std::io::stdout().flush()?;
+
+        Ok(())
+    }
+}
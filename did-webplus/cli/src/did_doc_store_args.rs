@@ -3,8 +3,9 @@ use std::sync::Arc;
 
 #[derive(clap::Args, Debug)]
 pub struct DIDDocStoreArgs {
-    /// Specify the URL to the SQLite DID doc store to use for this operation.  The doc store is what
-    /// stores validated DID docs.  The URL should have the form `sqlite://<local-path>`.
+    /// Specify the URL to the DID doc store to use for this operation.  The doc store is what stores
+    /// validated DID docs.  The URL should have the form `sqlite://<local-path>` or (if the `postgres`
+    /// feature is enabled) `postgres://<connection-info>`.
     // TODO: Figure out how not to print the env var value, since if it ever were a general postgres
     // url, it could contain a password.
     #[arg(
@@ -21,40 +22,47 @@ impl DIDDocStoreArgs {
     pub async fn open_did_doc_storage(
         &self,
     ) -> Result<Arc<dyn did_webplus_doc_store::DIDDocStorage>> {
+        open_did_doc_storage_from_url(&self.did_doc_store_db_url).await
+    }
+    pub async fn open_did_doc_store(&self) -> Result<did_webplus_doc_store::DIDDocStore> {
+        let did_doc_storage_a = self.open_did_doc_storage().await?;
+        Ok(did_webplus_doc_store::DIDDocStore::new(did_doc_storage_a))
+    }
+}
+
+/// Open the DID doc storage at the given URL, which should have the form `sqlite://<local-path>` or (if the
+/// `postgres` feature is enabled) `postgres://<connection-info>`.
+pub async fn open_did_doc_storage_from_url(
+    did_doc_store_db_url: &str,
+) -> Result<Arc<dyn did_webplus_doc_store::DIDDocStorage>> {
+    tracing::debug!(
+        "open_did_doc_storage_from_url; did_doc_store_db_url: {}",
+        did_doc_store_db_url
+    );
+    if let Some(did_doc_db_path_str) = did_doc_store_db_url.strip_prefix("sqlite://") {
+        // Apply tilde expansion to the path.
+        let did_doc_store_db_path = expanduser::expanduser(did_doc_db_path_str)?;
+        // See https://stackoverflow.com/questions/37388107/how-to-convert-the-pathbuf-to-string
+        // TODO: Use std::path::Diplay via Path::display method.
+        let did_doc_store_db_path_string = did_doc_store_db_path
+            .as_os_str()
+            .to_str()
+            .unwrap()
+            .to_string();
         tracing::debug!(
-            "get_did_doc_storage; self.did_doc_store_db_url: {}",
-            self.did_doc_store_db_url
+            "Tilde-expanded did_doc_store DB path: {}",
+            did_doc_store_db_path_string
         );
-        let did_doc_store_db_path_string = if let Some(did_doc_db_path_str) =
-            self.did_doc_store_db_url.strip_prefix("sqlite://")
-        {
-            // Apply tilde expansion to the path.
-            let did_doc_store_db_path = expanduser::expanduser(did_doc_db_path_str)?;
-            // See https://stackoverflow.com/questions/37388107/how-to-convert-the-pathbuf-to-string
-            // TODO: Use std::path::Diplay via Path::display method.
-            let did_doc_store_db_path_string = did_doc_store_db_path
-                .as_os_str()
-                .to_str()
-                .unwrap()
-                .to_string();
-            tracing::debug!(
-                "Tilde-expanded did_doc_store DB path: {}",
-                did_doc_store_db_path_string
-            );
-            if !did_doc_store_db_path.exists() {
-                if let Some(did_doc_db_url_parent) = did_doc_store_db_path.parent() {
-                    tracing::debug!(
-                        "Ensuring did_doc_store DB parent directory exists: {}",
-                        did_doc_db_url_parent.as_os_str().to_str().unwrap()
-                    );
-                    // TODO: Probably if the dir exists already this will return an error.
-                    std::fs::create_dir_all(did_doc_db_url_parent)?;
-                }
+        if !did_doc_store_db_path.exists() {
+            if let Some(did_doc_db_url_parent) = did_doc_store_db_path.parent() {
+                tracing::debug!(
+                    "Ensuring did_doc_store DB parent directory exists: {}",
+                    did_doc_db_url_parent.as_os_str().to_str().unwrap()
+                );
+                // TODO: Probably if the dir exists already this will return an error.
+                std::fs::create_dir_all(did_doc_db_url_parent)?;
             }
-            did_doc_store_db_path_string
-        } else {
-            unimplemented!("non-SQLite did_doc_store DBs are not yet supported.");
-        };
+        }
         let did_doc_storage =
             did_webplus_doc_storage_sqlite::DIDDocStorageSQLite::open_url_and_run_migrations(
                 did_doc_store_db_path_string.as_str(),
@@ -62,9 +70,34 @@ impl DIDDocStoreArgs {
             )
             .await?;
         Ok(Arc::new(did_doc_storage))
-    }
-    pub async fn open_did_doc_store(&self) -> Result<did_webplus_doc_store::DIDDocStore> {
-        let did_doc_storage_a = self.open_did_doc_storage().await?;
-        Ok(did_webplus_doc_store::DIDDocStore::new(did_doc_storage_a))
+    } else if did_doc_store_db_url.starts_with("postgres://") {
+        #[cfg(feature = "postgres")]
+        {
+            use anyhow::Context;
+
+            let pg_pool = sqlx::postgres::PgPoolOptions::new()
+                .max_connections(4)
+                .acquire_timeout(std::time::Duration::from_secs(3))
+                .connect(did_doc_store_db_url)
+                .await
+                .context("can't connect to database")?;
+            let did_doc_storage =
+                did_webplus_doc_storage_postgres::DIDDocStoragePostgres::open_and_run_migrations(
+                    pg_pool,
+                )
+                .await?;
+            Ok(Arc::new(did_doc_storage))
+        }
+        #[cfg(not(feature = "postgres"))]
+        {
+            anyhow::bail!(
+                "postgres DID doc stores are only supported if the `postgres` feature was enabled when building did-webplus CLI"
+            );
+        }
+    } else {
+        anyhow::bail!(
+            "unsupported DID doc store URL {:?}; expected a URL of the form `sqlite://<local-path>` or `postgres://<connection-info>`",
+            did_doc_store_db_url
+        );
     }
 }
@@ -2,13 +2,16 @@ use crate::{DIDDocStoreArgs, Result};
 
 #[derive(Clone, Copy, Debug, clap::ValueEnum)]
 pub enum DIDResolverType {
+    Bundle,
     Full,
     Thin,
 }
 
 #[derive(clap::Args, Clone, Debug)]
 pub struct DIDResolverArgs {
-    /// Specify which type of DID resolver to use.  The "full" resolver fetches, validates, and
+    /// Specify which type of DID resolver to use.  The "bundle" resolver resolves only from the
+    /// DID bundles specified by the "--bundle" argument, and never makes network requests; this is
+    /// meant for offline verification.  The "full" resolver fetches, validates, and
     /// stores DID docs to the local DID doc store.  The "thin" resolver relies on a VDG (Verifiable
     /// Data Gateway) to perform fetching, validation, and storage.  The "raw" resolver does NOT
     /// perform any validation or storage, and should only be used for testing and development.
@@ -38,6 +41,16 @@ pub struct DIDResolverArgs {
     /// This is required if the resolver is set to "thin", but is optional if the resolver is set to "full".
     #[arg(name = "vdg", env = "DID_WEBPLUS_VDG", long, value_name = "HOST")]
     pub vdg_host_o: Option<String>,
+    /// Specify a DID bundle file to resolve from.  This may be specified multiple times, and is required
+    /// (at least once) if the resolver is set to "bundle".  See the `did bundle create` command.
+    #[arg(name = "bundle", long, value_name = "FILE")]
+    pub did_bundle_file_v: Vec<std::path::PathBuf>,
+    /// Specify a trusted attester (a full did:key DID) for the "latest as of" attestations in DID bundles.  This
+    /// may be specified multiple times.  Latest attestations signed by any other key are rejected, and if no
+    /// trusted attesters are specified, then latest attestations are ignored, meaning that only specific
+    /// DID documents (i.e. those specified by selfHash and/or versionId query params) can be resolved.
+    #[arg(name = "bundle-attester", long, value_name = "DID_KEY")]
+    pub trusted_attester_v: Vec<String>,
 }
 
 impl DIDResolverArgs {
@@ -46,6 +59,33 @@ impl DIDResolverArgs {
         http_options_o: Option<did_webplus_core::HTTPOptions>,
     ) -> Result<Box<dyn did_webplus_resolver::DIDResolver>> {
         match self.did_resolver_type {
+            DIDResolverType::Bundle => {
+                anyhow::ensure!(
+                    !self.did_bundle_file_v.is_empty(),
+                    "When using the \"bundle\" resolver, at least one \"--bundle\" argument is required"
+                );
+                let mut did_bundle_v = Vec::with_capacity(self.did_bundle_file_v.len());
+                for did_bundle_file in self.did_bundle_file_v.iter() {
+                    let did_bundle: did_webplus_resolver::DIDBundle = serde_json::from_reader(
+                        std::fs::File::open(did_bundle_file)?,
+                    )
+                    .map_err(|e| {
+                        anyhow::anyhow!(
+                            "Malformed DID bundle file {:?}; error was: {}",
+                            did_bundle_file,
+                            e
+                        )
+                    })?;
+                    did_bundle_v.push(did_bundle);
+                }
+                Ok(Box::new(
+                    did_webplus_cli_lib::did_resolver_bundle(
+                        &did_bundle_v,
+                        &self.trusted_attester_v,
+                    )
+                    .await?,
+                ))
+            }
             DIDResolverType::Full => {
                 anyhow::ensure!(
                     self.did_doc_store_db_url_o.is_some(),
@@ -0,0 +1,51 @@
+use crate::{DIDDocStoreArgs, NewlineArgs, Result};
+use std::io::Write;
+
+/// Export the DIDs in the specified DID doc store into a static-hosting directory tree, i.e. write each DID's
+/// did-documents.jsonl file at the path that DID resolution maps the DID to, relative to the root of its host.
+/// Serving the directory via static file hosting (e.g. S3 or nginx) at that host then makes the DIDs
+/// resolvable without running a VDR.  Since the paths don't include the host, all exported DIDs must have the
+/// same host; use --host to select one if the DID doc store contains DIDs from several hosts.
+///
+/// By default, the export is incremental: only DIDs having DID documents not already in the directory tree
+/// are written, and their existing files are appended to.  Prints the JSON array of DIDs that were written.
+#[derive(Debug, clap::Parser)]
+pub struct DIDStaticExport {
+    #[command(flatten)]
+    pub did_doc_store_args: DIDDocStoreArgs,
+    /// The root directory of the static-hosting directory tree to export into.
+    #[arg(name = "dir", long, value_name = "DIR")]
+    pub dir: std::path::PathBuf,
+    /// If specified, only export DIDs having this host, e.g. "example.com" or "localhost:8085".
+    #[arg(name = "host", long, value_name = "HOST")]
+    pub host_o: Option<String>,
+    /// Rewrite every DID's did-documents.jsonl file, instead of only appending DID documents not already present.
+    #[arg(long)]
+    pub full: bool,
+    #[command(flatten)]
+    pub newline_args: NewlineArgs,
+}
+
+impl DIDStaticExport {
+    pub async fn handle(self) -> Result<()> {
+        // Handle CLI args and input
+        let did_doc_store = self.did_doc_store_args.open_did_doc_store().await?;
+
+        // Do the processing
+        let exported_did_v = did_webplus_cli_lib::did_static_export(
+            &did_doc_store,
+            &self.dir,
+            self.host_o.as_deref(),
+            !self.full,
+        )
+        .await?;
+
+        // Print the exported DIDs and optional newline.
+        serde_json::to_writer(std::io::stdout(), &exported_did_v)?;
+        self.newline_args
+            .print_newline_if_necessary(&mut std::io::stdout())?;
+        std::io::stdout().flush()?;
+
+        Ok(())
+    }
+}
@@ -5,6 +5,8 @@
 /// This will turn a did:webplus DIDResource[FullyQualified] into a Box<dyn signature_dyn::VerifierT>.
 pub struct DIDWebplusVerifierResolver {
     pub did_resolver_factory_b: Box<dyn did_webplus_resolver::DIDResolverFactory>,
+    /// The host of the VDG that the DIDResolver uses, if any.  This is known without loading the DIDResolver.
+    pub vdg_host_o: Option<String>,
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
@@ -14,39 +16,37 @@ impl verifier_resolver::VerifierResolver for DIDWebplusVerifierResolver {
         &self,
         verifier_str: &str,
     ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
-        if !verifier_str.starts_with("did:webplus:") {
-            Err(verifier_resolver::Error::InvalidVerifier(
-                format!(
-                    "expected verifier to begin with \"did:webplus:\", but verifier was {:?}",
-                    verifier_str
-                )
-                .into(),
-            ))?;
-        }
-
-        tracing::debug!(
-            "verifier was {:?}; verifying using did:webplus method",
-            verifier_str
-        );
-        let did_key_resource_fully_qualified =
-            did_webplus_core::DIDKeyResourceFullyQualifiedStr::new_ref(verifier_str).map_err(|_| verifier_resolver::Error::InvalidVerifier(format!("if did:webplus DID is used as verifier, it must be fully qualified, i.e. it must contain the selfHash and versionId query parameters and a fragment specifying the key ID, but it was {:?}", verifier_str).into()))?;
-
-        let did_resolver_b = self.did_resolver_factory_b.did_resolver().await?;
-        let (did_document, _did_document_metadata, _did_resolution_options) = did_resolver_b
-            .resolve_did_document(
-                did_key_resource_fully_qualified.without_fragment().as_str(),
-                did_webplus_core::DIDResolutionOptions::no_metadata(false),
-            )
-            .await?;
-
-        // Retrieve the appropriate verifier from the DID document -- identified by the key_id_fragment.
-        let verification_method = did_document
-            .public_key_material
-            .verification_method_for_key_id_fragment(did_key_resource_fully_qualified.fragment())?;
-        // TODO: Convert directly to VerifierBytes instead of going through MBPubKey.
-        let pub_key = mbx::MBPubKey::try_from(&verification_method.public_key_jwk)?;
-        let verifier_bytes = signature_dyn::VerifierBytes::try_from(&pub_key)?;
-        Ok(Box::new(verifier_bytes))
+        let did_resolver = self.did_resolver_factory_b.did_resolver().await?;
+        did_webplus_resolver::verifier_resolver_impl(verifier_str, did_resolver).await
+    }
+    async fn resolve_for_purpose(
+        &self,
+        verifier_str: &str,
+        verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        let did_resolver = self.did_resolver_factory_b.did_resolver().await?;
+        did_webplus_resolver::verifier_resolver_for_purpose_impl(
+            verifier_str,
+            verification_relationship,
+            did_resolver,
+        )
+        .await
+    }
+    async fn resolve_for_purpose_with_version(
+        &self,
+        verifier_str: &str,
+        verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<(Box<dyn signature_dyn::VerifierT>, Option<String>)> {
+        let did_resolver = self.did_resolver_factory_b.did_resolver().await?;
+        did_webplus_resolver::verifier_resolver_for_purpose_with_version_impl(
+            verifier_str,
+            verification_relationship,
+            did_resolver,
+        )
+        .await
+    }
+    fn vdg_host_o(&self) -> Option<&str> {
+        self.vdg_host_o.as_deref()
     }
 }
 
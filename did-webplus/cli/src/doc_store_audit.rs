@@ -0,0 +1,38 @@
+use crate::{DIDDocStoreArgs, NewlineArgs, Result};
+use std::io::Write;
+
+/// Re-verify every DID microledger in the specified DID doc store from its root DID document, checking
+/// self-hashes, signatures, prev links, did-documents.jsonl octet lengths, and validFrom ordering.  Prints a
+/// JSON report of the number of DIDs and DID doc records audited and of any corrupted or orphaned records
+/// (i.e. records that can't be verified from the root because a predecessor is missing or corrupted).  Exits
+/// with an error if any issues were found, so this can be used in scheduled jobs.
+#[derive(Debug, clap::Parser)]
+pub struct DocStoreAudit {
+    #[command(flatten)]
+    pub did_doc_store_args: DIDDocStoreArgs,
+    #[command(flatten)]
+    pub newline_args: NewlineArgs,
+}
+
+impl DocStoreAudit {
+    pub async fn handle(self) -> Result<()> {
+        // Handle CLI args and input
+        let did_doc_store = self.did_doc_store_args.open_did_doc_store().await?;
+
+        // Do the processing
+        let audit_report = did_webplus_cli_lib::doc_store_audit(&did_doc_store).await?;
+
+        // Print the report and optional newline.
+        serde_json::to_writer(std::io::stdout(), &audit_report)?;
+        self.newline_args
+            .print_newline_if_necessary(&mut std::io::stdout())?;
+        std::io::stdout().flush()?;
+
+        anyhow::ensure!(
+            !audit_report.has_issues(),
+            "DID doc store audit found {} issue(s)",
+            audit_report.issue_v.len()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{DIDDocStoreArgs, NewlineArgs, Result};
+use std::io::Write;
+
+/// Copy every DID in the specified (source) DID doc store into the destination DID doc store, which may use a
+/// different backend, e.g. to migrate a DID doc store from SQLite to Postgres.  Every DID document is fully
+/// validated before it's stored in the destination.  DID documents already in the destination are skipped, so
+/// copying is idempotent and can be used to incrementally sync.  Prints a JSON object giving the number of
+/// DIDs and DID documents that were added.
+#[derive(Debug, clap::Parser)]
+pub struct DocStoreCopy {
+    #[command(flatten)]
+    pub did_doc_store_args: DIDDocStoreArgs,
+    /// Specify the URL to the destination DID doc store.  The URL should have the form `sqlite://<local-path>`
+    /// or (if the `postgres` feature is enabled) `postgres://<connection-info>`.
+    #[arg(name = "to", long, value_name = "URL")]
+    pub dest_did_doc_store_db_url: String,
+    /// The maximum number of DID documents of a single DID to validate and store in each transaction.
+    #[arg(name = "batch-size", long, value_name = "N", default_value = "1000")]
+    pub batch_size: std::num::NonZeroUsize,
+    #[command(flatten)]
+    pub newline_args: NewlineArgs,
+}
+
+impl DocStoreCopy {
+    pub async fn handle(self) -> Result<()> {
+        // Handle CLI args and input
+        let source_did_doc_store = self.did_doc_store_args.open_did_doc_store().await?;
+        let dest_did_doc_store = did_webplus_doc_store::DIDDocStore::new(
+            crate::open_did_doc_storage_from_url(&self.dest_did_doc_store_db_url).await?,
+        );
+
+        // Do the processing
+        let (did_count, did_document_count) = did_webplus_cli_lib::doc_store_copy(
+            &source_did_doc_store,
+            &dest_did_doc_store,
+            self.batch_size,
+        )
+        .await?;
+
+        // Print the counts and optional newline.
+        serde_json::to_writer(
+            std::io::stdout(),
+            &serde_json::json!({
+                "didCount": did_count,
+                "didDocumentCount": did_document_count,
+            }),
+        )?;
+        self.newline_args
+            .print_newline_if_necessary(&mut std::io::stdout())?;
+        std::io::stdout().flush()?;
+
+        Ok(())
+    }
+}
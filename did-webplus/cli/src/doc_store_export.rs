@@ -0,0 +1,42 @@
+use crate::{DIDDocStoreArgs, NewlineArgs, Result};
+use std::io::Write;
+
+/// Export every DID in the specified DID doc store into a directory, for backup or for importing into another
+/// DID doc store via `doc-store import`.  The directory will contain one subdirectory per host (named by the
+/// host as it appears in the DID, e.g. `example.com` or `localhost%3A8085`), each of which is a static-hosting
+/// directory tree as produced by `did static-export`.
+///
+/// By default, the export is incremental: only DIDs having DID documents not already in the directory are
+/// written, and their existing files are appended to.  Prints the JSON array of DIDs that were written.
+#[derive(Debug, clap::Parser)]
+pub struct DocStoreExport {
+    #[command(flatten)]
+    pub did_doc_store_args: DIDDocStoreArgs,
+    /// The directory to export into.
+    #[arg(name = "dir", long, value_name = "DIR")]
+    pub dir: std::path::PathBuf,
+    /// Rewrite every DID's did-documents.jsonl file, instead of only appending DID documents not already present.
+    #[arg(long)]
+    pub full: bool,
+    #[command(flatten)]
+    pub newline_args: NewlineArgs,
+}
+
+impl DocStoreExport {
+    pub async fn handle(self) -> Result<()> {
+        // Handle CLI args and input
+        let did_doc_store = self.did_doc_store_args.open_did_doc_store().await?;
+
+        // Do the processing
+        let exported_did_v =
+            did_webplus_cli_lib::doc_store_export(&did_doc_store, &self.dir, !self.full).await?;
+
+        // Print the exported DIDs and optional newline.
+        serde_json::to_writer(std::io::stdout(), &exported_did_v)?;
+        self.newline_args
+            .print_newline_if_necessary(&mut std::io::stdout())?;
+        std::io::stdout().flush()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{DIDDocStoreArgs, NewlineArgs, Result};
+use std::io::Write;
+
+/// Find which DIDs and DID document versions in the specified DID doc store contain the given key, e.g. for
+/// determining which DIDs are affected by a compromised key.  The key can be an MBPubKey, in which case
+/// update rules containing a hashed form of it are found as well, or the hash of a hashed update key.  Prints
+/// a JSON array of records, each of which has the key, DID, versionId, selfHash, keyPurposeFlags (the
+/// integer value of the purposes of the verification methods having the key), and isUpdateKey, ordered by DID
+/// and versionId.
+#[derive(Debug, clap::Parser)]
+pub struct DocStoreFindKey {
+    #[command(flatten)]
+    pub did_doc_store_args: DIDDocStoreArgs,
+    /// The key to find, which is an MBPubKey or the hash of a hashed update key.
+    pub key: String,
+    #[command(flatten)]
+    pub newline_args: NewlineArgs,
+}
+
+impl DocStoreFindKey {
+    pub async fn handle(self) -> Result<()> {
+        // Handle CLI args and input
+        let did_doc_store = self.did_doc_store_args.open_did_doc_store().await?;
+
+        // Do the processing
+        let did_key_record_v =
+            did_webplus_cli_lib::doc_store_find_key(&did_doc_store, &self.key).await?;
+
+        // Print the records and optional newline.
+        serde_json::to_writer(std::io::stdout(), &did_key_record_v)?;
+        self.newline_args
+            .print_newline_if_necessary(&mut std::io::stdout())?;
+        std::io::stdout().flush()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{DIDDocStoreArgs, NewlineArgs, Result};
+use std::io::Write;
+
+/// Import DID documents from did-documents.jsonl files into the specified DID doc store.  Every DID document is
+/// fully validated before it's stored, so this is safe to use on data from an untrusted source.  DID documents
+/// already in the DID doc store are skipped, so importing is idempotent.  Directories are searched recursively
+/// for files named did-documents.jsonl, so a directory produced by `doc-store export` (or a static-hosting
+/// directory tree) can be imported directly.  Prints a JSON object giving the number of DIDs and DID documents
+/// that were added.
+#[derive(Debug, clap::Parser)]
+pub struct DocStoreImport {
+    #[command(flatten)]
+    pub did_doc_store_args: DIDDocStoreArgs,
+    /// The maximum number of DID documents of a single DID to validate and store in each transaction.
+    #[arg(name = "batch-size", long, value_name = "N", default_value = "1000")]
+    pub batch_size: std::num::NonZeroUsize,
+    /// The did-documents.jsonl files and/or directories to import.
+    #[arg(name = "path", value_name = "PATH", required = true)]
+    pub path_v: Vec<std::path::PathBuf>,
+    #[command(flatten)]
+    pub newline_args: NewlineArgs,
+}
+
+impl DocStoreImport {
+    pub async fn handle(self) -> Result<()> {
+        // Handle CLI args and input
+        let did_doc_store = self.did_doc_store_args.open_did_doc_store().await?;
+
+        // Do the processing
+        let (did_count, did_document_count) =
+            did_webplus_cli_lib::doc_store_import(&did_doc_store, &self.path_v, self.batch_size)
+                .await?;
+
+        // Print the counts and optional newline.
+        serde_json::to_writer(
+            std::io::stdout(),
+            &serde_json::json!({
+                "didCount": did_count,
+                "didDocumentCount": did_document_count,
+            }),
+        )?;
+        self.newline_args
+            .print_newline_if_necessary(&mut std::io::stdout())?;
+        std::io::stdout().flush()?;
+
+        Ok(())
+    }
+}
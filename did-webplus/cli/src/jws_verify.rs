@@ -1,11 +1,16 @@
-use crate::{NewlineArgs, Result, VerifierResolverArgs};
+use crate::{NewlineArgs, Result, TrustPolicyArgs, VerificationReportArgs, VerifierResolverArgs};
 use std::io::{Read, Write};
 
 /// Verify a JWS signed by a did:webplus DID.  If the JWS is valid, then the JWS is written to stdout.
+/// The JWS can be in JWS Compact Serialization or in (General or Flattened) JWS JSON Serialization, the
+/// latter of which can carry multiple signatures, e.g. co-signatures from several DIDs.  In that case,
+/// the --signer-policy argument determines how many of the signatures must verify.
 #[derive(clap::Parser)]
 pub struct JWSVerify {
     #[command(flatten)]
     pub verifier_resolver_args: VerifierResolverArgs,
+    #[command(flatten)]
+    pub trust_policy_args: TrustPolicyArgs,
     /// Specify the JWS detached payload directly on the command line.  This is only suitable for small
     /// payloads that don't contain sensitive information, since typically the commandline that invoked
     /// a process is visible in the process list on a Unix system.  This argument is mutually exclusive
@@ -21,6 +26,29 @@ pub struct JWSVerify {
     /// case the JWS must not have an attached payload.
     #[arg(name = "detached-payload-file", short = 'f', long, value_name = "FILE")]
     pub detached_payload_file_o: Option<std::path::PathBuf>,
+    /// Specifies the verification relationship that the signing key must have in the signer's DID document.
+    /// Note that update-did-document is not a verification relationship.
+    #[arg(
+        name = "key-purpose",
+        long,
+        value_name = "PURPOSE",
+        value_enum,
+        default_value = "assertion-method"
+    )]
+    pub key_purpose: did_webplus_core::KeyPurpose,
+    /// Specifies how many of the signatures of a JWS in JWS JSON Serialization must verify for the JWS to be
+    /// considered valid.  "all" means every signature, "any" means at least one, and "<k>-of-n" (e.g. "2-of-n")
+    /// means signatures from at least k distinct DIDs.  A JWS in JWS Compact Serialization has exactly one
+    /// signature, which must always verify.
+    #[arg(
+        name = "signer-policy",
+        long,
+        value_name = "POLICY",
+        default_value = "all"
+    )]
+    pub signer_policy: did_webplus_jws::JWSSignerPolicy,
+    #[command(flatten)]
+    pub verification_report_args: VerificationReportArgs,
     #[command(flatten)]
     pub newline_args: NewlineArgs,
 }
@@ -33,9 +61,12 @@ impl JWSVerify {
         let mut jws_string = String::new();
         std::io::stdin().read_to_string(&mut jws_string)?;
         let jws_str = jws_string.trim();
-        let jws = did_webplus_jws::JWS::try_from(jws_str)?;
 
-        let verifier_resolver = self.verifier_resolver_args.get_verifier_resolver_map();
+        let verifier_resolver = self
+            .trust_policy_args
+            .apply_to(self.verifier_resolver_args.get_verifier_resolver_map())?;
+        let verification_relationship =
+            verifier_resolver::VerificationRelationship::try_from(self.key_purpose)?;
 
         anyhow::ensure!(
             self.detached_payload_o.is_none() || self.detached_payload_file_o.is_none(),
@@ -57,13 +88,49 @@ impl JWSVerify {
             };
 
         // Do the processing
-        did_webplus_cli_lib::jws_verify(&jws, detached_payload_bytes_o, &verifier_resolver).await?;
-        tracing::info!("Input JWS was successfully validated.");
+        let mut verification_report = verifier_resolver::VerificationReport::new();
+        let verify_r = if jws_str.starts_with('{') {
+            let jws_json = jws_str.parse::<did_webplus_jws::JWSJSON>()?;
+            did_webplus_cli_lib::jws_json_verify(
+                &jws_json,
+                detached_payload_bytes_o,
+                verification_relationship,
+                self.signer_policy,
+                &verifier_resolver,
+                &mut verification_report,
+            )
+            .await
+            .map(|verified_kid_v| {
+                tracing::info!(
+                    "Input JWS was successfully validated under signer policy {}; verified signatures: {:?}",
+                    self.signer_policy,
+                    verified_kid_v
+                );
+            })
+        } else {
+            let jws = did_webplus_jws::JWS::try_from(jws_str)?;
+            did_webplus_cli_lib::jws_verify(
+                &jws,
+                detached_payload_bytes_o,
+                verification_relationship,
+                &verifier_resolver,
+                &mut verification_report,
+            )
+            .await
+            .map(|()| {
+                tracing::info!("Input JWS was successfully validated.");
+            })
+        };
+        self.verification_report_args
+            .print_report_if_requested(&verification_report, &self.newline_args)?;
+        verify_r?;
 
-        // Print the JWS and optional newline.
-        std::io::stdout().write_all(jws_str.as_bytes())?;
-        self.newline_args
-            .print_newline_if_necessary(&mut std::io::stdout())?;
+        if !self.verification_report_args.report_is_requested() {
+            // Print the JWS and optional newline.
+            std::io::stdout().write_all(jws_str.as_bytes())?;
+            self.newline_args
+                .print_newline_if_necessary(&mut std::io::stdout())?;
+        }
 
         Ok(())
     }
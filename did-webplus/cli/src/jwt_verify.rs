@@ -0,0 +1,95 @@
+use crate::{NewlineArgs, Result, TrustPolicyArgs, VerificationReportArgs, VerifierResolverArgs};
+use std::io::{Read, Write};
+
+/// Verify a JWT signed by a did:webplus DID, and validate its claims: "iss" must be the DID of the signing
+/// key, "exp" and "nbf" must bracket the current time (up to the allowed clock skew), "iat" must not be in
+/// the future, and "aud" must contain the expected audience (if any).  If the JWT is valid, then its claims
+/// are written to stdout as JSON.
+#[derive(clap::Parser)]
+pub struct JWTVerify {
+    #[command(flatten)]
+    pub verifier_resolver_args: VerifierResolverArgs,
+    #[command(flatten)]
+    pub trust_policy_args: TrustPolicyArgs,
+    /// Specifies the verification relationship that the signing key must have in the signer's DID document.
+    /// Note that update-did-document is not a verification relationship.
+    #[arg(
+        name = "key-purpose",
+        long,
+        value_name = "PURPOSE",
+        value_enum,
+        default_value = "assertion-method"
+    )]
+    pub key_purpose: did_webplus_core::KeyPurpose,
+    /// Specifies the audience that the "aud" claim must contain.  If not specified, then the JWT must not have
+    /// an "aud" claim.
+    #[arg(name = "audience", long, value_name = "AUDIENCE")]
+    pub expected_audience_o: Option<String>,
+    /// Specifies the issuer that the "iss" claim must be.  Regardless, the "iss" claim must be the DID of the
+    /// signing key.
+    #[arg(name = "issuer", long, value_name = "DID")]
+    pub expected_issuer_o: Option<String>,
+    /// Specifies the amount of clock skew (in seconds) to tolerate when checking "exp", "nbf", and "iat".
+    #[arg(
+        name = "clock-skew",
+        long,
+        value_name = "SECONDS",
+        default_value_t = did_webplus_jws::JWTValidationOptions::DEFAULT_CLOCK_SKEW_SECONDS
+    )]
+    pub clock_skew_seconds: u64,
+    /// If specified, then the JWT must have an "exp" claim.
+    #[arg(long)]
+    pub require_exp: bool,
+    #[command(flatten)]
+    pub verification_report_args: VerificationReportArgs,
+    #[command(flatten)]
+    pub newline_args: NewlineArgs,
+}
+
+impl JWTVerify {
+    pub async fn handle(self) -> Result<()> {
+        // Handle CLI args and input
+
+        // Read the JWT from stdin, making sure to trim whitespace off the ends.
+        let mut jwt_string = String::new();
+        std::io::stdin().read_to_string(&mut jwt_string)?;
+        let jwt = jwt_string.parse::<did_webplus_jws::JWT>()?;
+
+        let verifier_resolver = self
+            .trust_policy_args
+            .apply_to(self.verifier_resolver_args.get_verifier_resolver_map())?;
+        let verification_relationship =
+            verifier_resolver::VerificationRelationship::try_from(self.key_purpose)?;
+        let validation_options = did_webplus_jws::JWTValidationOptions {
+            clock_skew_seconds: self.clock_skew_seconds,
+            expected_audience_o: self.expected_audience_o,
+            expected_issuer_o: self.expected_issuer_o,
+            require_exp: self.require_exp,
+            now_o: None,
+        };
+
+        // Do the processing
+        let mut verification_report = verifier_resolver::VerificationReport::new();
+        let verify_r = did_webplus_cli_lib::jwt_verify(
+            &jwt,
+            verification_relationship,
+            &validation_options,
+            &verifier_resolver,
+            &mut verification_report,
+        )
+        .await;
+        self.verification_report_args
+            .print_report_if_requested(&verification_report, &self.newline_args)?;
+        verify_r?;
+        tracing::info!("Input JWT was successfully validated.");
+
+        if !self.verification_report_args.report_is_requested() {
+            // Print the claims and optional newline.
+            serde_json::to_writer(std::io::stdout(), jwt.claims())?;
+            self.newline_args
+                .print_newline_if_necessary(&mut std::io::stdout())?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 mod cmd;
+mod did_bundle_create;
 mod did_doc_store_args;
 mod did_key_from_private;
 mod did_key_generate;
@@ -9,15 +10,24 @@ mod did_resolution_options_args;
 mod did_resolve;
 mod did_resolver_args;
 mod did_resolver_factory;
+mod did_static_export;
 mod did_webplus_verifier_resolver;
+mod doc_store_audit;
+mod doc_store_copy;
+mod doc_store_export;
+mod doc_store_find_key;
+mod doc_store_import;
 mod http_headers_args;
 mod http_scheme_override_args;
 mod jws_payload_args;
 mod jws_verify;
+mod jwt_verify;
 mod newline_args;
 mod private_key_file_args;
 mod self_hash_args;
+mod trust_policy_args;
 mod verification_method_args;
+mod verification_report_args;
 mod verifier_resolver_args;
 mod vjson_default_schema;
 mod vjson_self_hash;
@@ -28,14 +38,18 @@ mod vjson_verify;
 mod wallet_args;
 mod wallet_did_create;
 mod wallet_did_deactivate;
+mod wallet_did_decrypt;
+mod wallet_did_encrypt;
 mod wallet_did_list;
 mod wallet_did_sign_jws;
+mod wallet_did_sign_jwt;
 mod wallet_did_sign_vjson;
 mod wallet_did_update;
 mod wallet_list;
 
 pub use crate::{
-    did_doc_store_args::DIDDocStoreArgs,
+    did_bundle_create::DIDBundleCreate,
+    did_doc_store_args::{DIDDocStoreArgs, open_did_doc_storage_from_url},
     did_key_from_private::DIDKeyFromPrivate,
     did_key_generate::DIDKeyGenerate,
     did_key_sign_jws::DIDKeySignJWS,
@@ -45,15 +59,24 @@ pub use crate::{
     did_resolve::DIDResolve,
     did_resolver_args::{DIDResolverArgs, DIDResolverType},
     did_resolver_factory::DIDResolverFactory,
+    did_static_export::DIDStaticExport,
     did_webplus_verifier_resolver::DIDWebplusVerifierResolver,
+    doc_store_audit::DocStoreAudit,
+    doc_store_copy::DocStoreCopy,
+    doc_store_export::DocStoreExport,
+    doc_store_find_key::DocStoreFindKey,
+    doc_store_import::DocStoreImport,
     http_headers_args::HTTPHeadersArgs,
     http_scheme_override_args::HTTPSchemeOverrideArgs,
     jws_payload_args::JWSPayloadArgs,
     jws_verify::JWSVerify,
+    jwt_verify::JWTVerify,
     newline_args::NewlineArgs,
     private_key_file_args::PrivateKeyFileArgs,
     self_hash_args::SelfHashArgs,
+    trust_policy_args::TrustPolicyArgs,
     verification_method_args::VerificationMethodArgs,
+    verification_report_args::VerificationReportArgs,
     verifier_resolver_args::VerifierResolverArgs,
     vjson_default_schema::VJSONDefaultSchema,
     vjson_self_hash::VJSONSelfHash,
@@ -64,8 +87,11 @@ pub use crate::{
     wallet_args::WalletArgs,
     wallet_did_create::WalletDIDCreate,
     wallet_did_deactivate::WalletDIDDeactivate,
+    wallet_did_decrypt::WalletDIDDecrypt,
+    wallet_did_encrypt::WalletDIDEncrypt,
     wallet_did_list::WalletDIDList,
     wallet_did_sign_jws::WalletDIDSignJWS,
+    wallet_did_sign_jwt::WalletDIDSignJWT,
     wallet_did_sign_vjson::WalletDIDSignVJSON,
     wallet_did_update::WalletDIDUpdate,
     wallet_list::WalletList,
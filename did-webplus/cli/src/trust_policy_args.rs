@@ -0,0 +1,30 @@
+use crate::Result;
+
+#[derive(clap::Args, Debug)]
+pub struct TrustPolicyArgs {
+    /// Specify a trust policy file (JSON, or TOML if the file has a ".toml" extension) that determines which
+    /// signers are trusted, e.g. by issuer DID, host pattern, key type, and required VDG scope.  Signatures by
+    /// signers that the policy doesn't trust fail verification.  If not specified, then any signer is trusted.
+    #[arg(
+        name = "trust-policy",
+        env = "DID_WEBPLUS_TRUST_POLICY",
+        long,
+        value_name = "FILE"
+    )]
+    pub trust_policy_file_o: Option<std::path::PathBuf>,
+}
+
+impl TrustPolicyArgs {
+    /// If a trust policy file was specified, then read it and apply it to the given VerifierResolverMap.
+    pub fn apply_to(
+        &self,
+        verifier_resolver_map: verifier_resolver::VerifierResolverMap,
+    ) -> Result<verifier_resolver::VerifierResolverMap> {
+        if let Some(trust_policy_file) = self.trust_policy_file_o.as_deref() {
+            let trust_policy = verifier_resolver::TrustPolicy::read_from_file(trust_policy_file)?;
+            Ok(verifier_resolver_map.with_trust_policy(trust_policy))
+        } else {
+            Ok(verifier_resolver_map)
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::{NewlineArgs, Result};
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum VerificationReportFormat {
+    Json,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct VerificationReportArgs {
+    /// Write a report of each check performed during verification (e.g. key resolution, key purpose, signature),
+    /// along with its outcome and the exact DID document version used, to stdout in the given format, instead
+    /// of the usual output.  The report is written even if verification fails, in which case the process still
+    /// exits with an error.
+    #[arg(name = "report", long, value_name = "FORMAT", value_enum)]
+    pub report_format_o: Option<VerificationReportFormat>,
+}
+
+impl VerificationReportArgs {
+    pub fn report_is_requested(&self) -> bool {
+        self.report_format_o.is_some()
+    }
+    /// If a report was requested, then write the verification report to stdout in the requested format.
+    pub fn print_report_if_requested(
+        &self,
+        verification_report: &verifier_resolver::VerificationReport,
+        newline_args: &NewlineArgs,
+    ) -> Result<()> {
+        match self.report_format_o {
+            Some(VerificationReportFormat::Json) => {
+                serde_json::to_writer(std::io::stdout(), verification_report)?;
+                newline_args.print_newline_if_necessary(&mut std::io::stdout())?;
+            }
+            None => {}
+        }
+        Ok(())
+    }
+}
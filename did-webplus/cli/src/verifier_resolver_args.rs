@@ -1,6 +1,6 @@
 use crate::{
-    DIDResolverArgs, DIDResolverFactory, DIDWebplusVerifierResolver, HTTPHeadersArgs,
-    HTTPSchemeOverrideArgs,
+    DIDResolverArgs, DIDResolverFactory, DIDResolverType, DIDWebplusVerifierResolver,
+    HTTPHeadersArgs, HTTPSchemeOverrideArgs,
 };
 
 /// Arguments for specifying how to resolve a public key (aka verifier) from a string.
@@ -17,6 +17,13 @@ pub struct VerifierResolverArgs {
 
 impl VerifierResolverArgs {
     pub fn get_verifier_resolver_map(self) -> verifier_resolver::VerifierResolverMap {
+        // The "bundle" resolver never uses a VDG, even if one is specified.
+        let vdg_host_o = match self.did_resolver_args.did_resolver_type {
+            DIDResolverType::Bundle => None,
+            DIDResolverType::Full | DIDResolverType::Thin => {
+                self.did_resolver_args.vdg_host_o.clone()
+            }
+        };
         verifier_resolver::VerifierResolverMap::new()
             .with("did:key:", Box::new(did_key::DIDKeyVerifierResolver))
             .with(
@@ -27,6 +34,7 @@ impl VerifierResolverArgs {
                         self.http_headers_args,
                         self.http_scheme_args,
                     )),
+                    vdg_host_o,
                 }),
             )
     }
@@ -1,4 +1,7 @@
-use crate::{NewlineArgs, Result, VJSONStorageBehaviorArgs, VJSONStoreArgs, VerifierResolverArgs};
+use crate::{
+    NewlineArgs, Result, VJSONStorageBehaviorArgs, VJSONStoreArgs, VerificationReportArgs,
+    VerifierResolverArgs,
+};
 
 /// Read VJSON from stdin and verify it, using the "full" resolver with the specified DID doc store.
 /// Verification includes verifying self-hash and all signatures.  If valid, the VJSON will be written
@@ -16,6 +19,10 @@ pub struct VJSONVerify {
     #[command(flatten)]
     pub verifier_resolver_args: VerifierResolverArgs,
     #[command(flatten)]
+    pub trust_policy_args: TrustPolicyArgs,
+    #[command(flatten)]
+    pub verification_report_args: VerificationReportArgs,
+    #[command(flatten)]
     pub newline_args: NewlineArgs,
 }
 
@@ -24,19 +31,33 @@ impl VJSONVerify {
         // Handle CLI args and input
         let value = serde_json::from_reader(std::io::stdin())?;
         let vjson_store = self.vjson_store_args.get_vjson_store().await?;
-        let verifier_resolver = self.verifier_resolver_args.get_verifier_resolver_map();
+        let verifier_resolver = self
+            .trust_policy_args
+            .apply_to(self.verifier_resolver_args.get_verifier_resolver_map())?;
 
         // Do the processing
-        did_webplus_cli_lib::vjson_verify(&value, &vjson_store, &verifier_resolver).await?;
+        let mut verification_report = verifier_resolver::VerificationReport::new();
+        let verify_r = did_webplus_cli_lib::vjson_verify(
+            &value,
+            &vjson_store,
+            &verifier_resolver,
+            &mut verification_report,
+        )
+        .await;
+        self.verification_report_args
+            .print_report_if_requested(&verification_report, &self.newline_args)?;
+        verify_r?;
         tracing::info!("Input VJSON was successfully verified");
         self.vjson_storage_behavior_args
             .store_if_requested(&value, &vjson_store, &verifier_resolver)
             .await?;
 
-        // JCS-serialize the verified JSON to stdout, and add a newline if specified.
-        serde_json_canonicalizer::to_writer(&value, &mut std::io::stdout())?;
-        self.newline_args
-            .print_newline_if_necessary(&mut std::io::stdout())?;
+        if !self.verification_report_args.report_is_requested() {
+            // JCS-serialize the verified JSON to stdout, and add a newline if specified.
+            serde_json_canonicalizer::to_writer(&value, &mut std::io::stdout())?;
+            self.newline_args
+                .print_newline_if_necessary(&mut std::io::stdout())?;
+        }
 
         Ok(())
     }
@@ -30,6 +30,17 @@ pub struct WalletArgs {
     /// using a VDG, a "Full" DID resolver has a scope of agreement that only contains itself.
     #[arg(name = "vdg", long, env = "DID_WEBPLUS_VDG", value_name = "HOST")]
     pub vdg_host_o: Option<String>,
+    /// Optionally specify a static-hosting directory tree that DID creates and updates are written into,
+    /// instead of being sent to a VDR.  The directory can then be served via static file hosting (e.g. S3 or
+    /// nginx) at the DID's host.  When creating a DID in this mode, the VDR DID create endpoint URL only
+    /// determines the DID's host and path.  See also `did static-export`.
+    #[arg(
+        name = "static-hosting-dir",
+        long,
+        env = "DID_WEBPLUS_WALLET_STATIC_HOSTING_DIR",
+        value_name = "DIR"
+    )]
+    pub static_hosting_dir_o: Option<std::path::PathBuf>,
 }
 
 fn parse_hyphenated_uuid(s: &str) -> Result<uuid::Uuid> {
@@ -75,6 +86,14 @@ impl WalletArgs {
         Ok(Arc::new(wallet_storage))
     }
     pub async fn open_wallet(&self) -> Result<did_webplus_software_wallet::SoftwareWallet> {
+        let wallet = self.open_wallet_impl().await?;
+        if let Some(static_hosting_dir) = self.static_hosting_dir_o.as_ref() {
+            Ok(wallet.with_static_hosting_dir(static_hosting_dir.clone()))
+        } else {
+            Ok(wallet)
+        }
+    }
+    async fn open_wallet_impl(&self) -> Result<did_webplus_software_wallet::SoftwareWallet> {
         let wallet_storage_a = self.open_wallet_storage().await?;
         if let Some(wallet_uuid) = self.wallet_uuid_o.as_ref() {
             let mut transaction_b = wallet_storage_a.begin_transaction().await?;
@@ -12,6 +12,17 @@ pub struct WalletDIDCreate {
     /// A scheme of "http" is only allowed if the hostname is "localhost".  The URL must not contain a query string or fragment.
     #[arg(name = "vdr", env = "DID_WEBPLUS_VDR", short, long, value_name = "URL", value_parser = parse_url)]
     pub vdr_did_create_endpoint: url::Url,
+    /// Optionally specify the fully qualified key ID of a locally-controlled capabilityInvocation key with which
+    /// to authorize the DID create request, for VDRs that require signed authorization, e.g. a key of a
+    /// "registrar" DID that the VDR permits to create DIDs under the requested path.  If not specified, then
+    /// the new DID's own capabilityInvocation key signs the request.
+    #[arg(
+        name = "vdr-authz-key-id",
+        env = "DID_WEBPLUS_VDR_AUTHZ_KEY_ID",
+        long,
+        value_name = "KEY_ID"
+    )]
+    pub vdr_authz_key_id_o: Option<String>,
     #[command(flatten)]
     pub http_headers_args: HTTPHeadersArgs,
     #[command(flatten)]
@@ -28,11 +39,17 @@ impl WalletDIDCreate {
             http_headers_for: self.http_headers_args.http_headers_for.clone(),
             http_scheme_override: self.http_scheme_override_args.http_scheme_override.clone(),
         });
+        let vdr_authz_key_id_o = self
+            .vdr_authz_key_id_o
+            .as_deref()
+            .map(did_webplus_core::DIDKeyResourceFullyQualifiedStr::new_ref)
+            .transpose()?;
 
         // Do the processing
         let created_did = did_webplus_cli_lib::wallet_did_create(
             &wallet,
             self.vdr_did_create_endpoint.as_str(),
+            vdr_authz_key_id_o,
             http_options_o.as_ref(),
         )
         .await?;
@@ -0,0 +1,39 @@
+use crate::{Result, WalletArgs};
+use std::io::{Read, Write};
+
+/// Decrypt a JWE using a keyAgreement key controlled by the specified wallet.  The JWE (in the compact or
+/// either JSON serialization) will be read from stdin, and the plaintext will be written to stdout as-is
+/// (in particular, no newline is appended).  If no --wallet-uuid argument is specified, then there must
+/// only be one wallet in the database, and that wallet will be used.
+#[derive(clap::Parser)]
+pub struct WalletDIDDecrypt {
+    #[command(flatten)]
+    pub wallet_args: WalletArgs,
+    /// Optionally restrict decryption to the keyAgreement keys of the given DID controlled by the wallet.
+    /// If not specified, then the first JWE recipient whose key is controlled by the wallet is used.
+    #[arg(name = "did", short, long, value_name = "DID")]
+    pub controlled_did_o: Option<did_webplus_core::DID>,
+}
+
+impl WalletDIDDecrypt {
+    pub async fn handle(self) -> Result<()> {
+        // Handle CLI args and input
+        let wallet = self.wallet_args.open_wallet().await?;
+        let mut jwe_string = String::new();
+        std::io::stdin().read_to_string(&mut jwe_string)?;
+
+        // Do the processing
+        let (key_id, plaintext) = did_webplus_cli_lib::wallet_did_decrypt(
+            jwe_string.trim(),
+            &wallet,
+            self.controlled_did_o.as_deref(),
+        )
+        .await?;
+        tracing::info!("Decrypted JWE using keyAgreement key {}", key_id);
+
+        // Print the plaintext.
+        std::io::stdout().write_all(plaintext.as_slice())?;
+
+        Ok(())
+    }
+}
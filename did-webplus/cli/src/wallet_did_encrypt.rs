@@ -0,0 +1,80 @@
+use crate::{DIDResolverArgs, HTTPHeadersArgs, HTTPSchemeOverrideArgs, NewlineArgs, Result};
+use std::io::{Read, Write};
+
+/// Encrypt a payload to one or more did:webplus DIDs, producing a JWE.  The plaintext will be read from
+/// stdin, and the JWE will be written to stdout.  Each recipient's keyAgreement key is determined by
+/// resolving its DID document.  Encryption doesn't involve the sender's keys, so no wallet is used;
+/// the JWE can be decrypted using the `wallet did decrypt` command by the holder of a recipient key.
+#[derive(clap::Parser)]
+pub struct WalletDIDEncrypt {
+    /// Specify a recipient of the JWE.  This may be specified multiple times.  A recipient may be a DID
+    /// (optionally with selfHash and/or versionId query params), in which case the first keyAgreement key
+    /// of a supported key type (X25519 or P-256) in its DID document is used, or it may be a DID key resource
+    /// (i.e. having a fragment specifying the key ID) which must be listed under keyAgreement.
+    #[arg(name = "recipient", long, value_name = "DID", required = true)]
+    pub recipient_v: Vec<String>,
+    /// Specify the JWE key management algorithm.  ECDH-ES only supports a single recipient.
+    #[arg(
+        name = "alg",
+        long,
+        value_name = "ALG",
+        value_enum,
+        default_value = "ECDH-ES+A256KW"
+    )]
+    pub key_management_algorithm: did_webplus_jwe::JWEKeyManagementAlgorithm,
+    /// Specify the JWE serialization to produce.  The compact serialization only supports a single recipient.
+    #[arg(
+        name = "serialization",
+        long,
+        value_name = "SERIALIZATION",
+        value_enum,
+        default_value = "compact"
+    )]
+    pub serialization: did_webplus_jwe::JWESerialization,
+    #[command(flatten)]
+    pub did_resolver_args: DIDResolverArgs,
+    #[command(flatten)]
+    pub http_headers_args: HTTPHeadersArgs,
+    #[command(flatten)]
+    pub http_scheme_override_args: HTTPSchemeOverrideArgs,
+    #[command(flatten)]
+    pub newline_args: NewlineArgs,
+}
+
+impl WalletDIDEncrypt {
+    pub async fn handle(self) -> Result<()> {
+        // Handle CLI args and input
+        let http_options_o = Some(did_webplus_core::HTTPOptions {
+            http_headers_for: self.http_headers_args.http_headers_for.clone(),
+            http_scheme_override: self.http_scheme_override_args.http_scheme_override.clone(),
+        });
+        let did_resolver_b = self
+            .did_resolver_args
+            .get_did_resolver(http_options_o)
+            .await?;
+        let mut plaintext = Vec::new();
+        std::io::stdin().read_to_end(&mut plaintext)?;
+        let recipient_v = self
+            .recipient_v
+            .iter()
+            .map(|recipient| recipient.as_str())
+            .collect::<Vec<_>>();
+
+        // Do the processing
+        let jwe_string = did_webplus_cli_lib::wallet_did_encrypt(
+            &plaintext,
+            &recipient_v,
+            self.key_management_algorithm,
+            self.serialization,
+            did_resolver_b.as_ref(),
+        )
+        .await?;
+
+        // Print the JWE and optional newline.
+        std::io::stdout().write_all(jwe_string.as_bytes())?;
+        self.newline_args
+            .print_newline_if_necessary(&mut std::io::stdout())?;
+
+        Ok(())
+    }
+}
@@ -5,6 +5,10 @@ use std::io::Write;
 /// for the JWS will be read from stdin.  The JWS will be written to stdout.  If no --wallet-uuid
 /// argument is specified, then there must only be one wallet in the database, and that wallet will be
 /// used.  If there is more than one wallet in the database, the --wallet-uuid argument must be specified.
+///
+/// To co-sign a JWS that has already been signed (e.g. by another party's DID), use the --cosign argument,
+/// in which case this wallet's signature is added to that JWS, and the result is written in General JWS
+/// JSON Serialization.
 #[derive(clap::Parser)]
 pub struct WalletDIDSignJWS {
     #[command(flatten)]
@@ -16,6 +20,17 @@ pub struct WalletDIDSignJWS {
     pub verification_method_args: VerificationMethodArgs,
     #[command(flatten)]
     pub jws_payload_args: JWSPayloadArgs,
+    /// Specifies the serialization of the output JWS.  "compact" and "flattened" only support a single
+    /// signature, whereas "general" supports any number of signatures.  Defaults to "compact", unless
+    /// --cosign is specified, in which case it defaults to "general".
+    #[arg(name = "serialization", long, value_name = "SERIALIZATION", value_enum)]
+    pub serialization_o: Option<did_webplus_jws::JWSSerialization>,
+    /// Specify the file containing an existing JWS (in any serialization) to add this wallet's signature to,
+    /// instead of creating a new JWS.  In this case, the --payload and --encoding arguments are ignored, since
+    /// they're determined by the existing JWS, and stdin is only read if the existing JWS has a detached
+    /// payload, in which case stdin must contain that payload.
+    #[arg(name = "cosign", long, value_name = "FILE")]
+    pub cosign_file_o: Option<std::path::PathBuf>,
     #[command(flatten)]
     pub newline_args: NewlineArgs,
 }
@@ -26,19 +41,53 @@ impl WalletDIDSignJWS {
         let wallet = self.wallet_args.open_wallet().await?;
 
         // Do the processing
-        let jws = did_webplus_cli_lib::wallet_did_sign_jws(
-            &mut std::io::stdin(),
-            self.jws_payload_args.payload_presence,
-            self.jws_payload_args.payload_encoding,
-            &wallet,
-            self.verification_method_args.controlled_did_o.as_deref(),
-            Some(self.verification_method_args.key_purpose),
-            self.verification_method_args.key_id_o.as_deref(),
-        )
-        .await?;
+        let jws_string = if let Some(cosign_file) = self.cosign_file_o.as_deref() {
+            let mut jws_json =
+                std::fs::read_to_string(cosign_file)?.parse::<did_webplus_jws::JWSJSON>()?;
+            let mut stdin = std::io::stdin();
+            let detached_payload_bytes_o: Option<&mut dyn std::io::Read> =
+                if jws_json.raw_attached_payload_str_o().is_none() {
+                    Some(&mut stdin)
+                } else {
+                    None
+                };
+            did_webplus_cli_lib::wallet_did_cosign_jws(
+                &mut jws_json,
+                detached_payload_bytes_o,
+                &wallet,
+                self.verification_method_args.controlled_did_o.as_deref(),
+                Some(self.verification_method_args.key_purpose),
+                self.verification_method_args.key_id_o.as_deref(),
+            )
+            .await?;
+            jws_json.to_serialization_string(
+                self.serialization_o
+                    .unwrap_or(did_webplus_jws::JWSSerialization::General),
+            )?
+        } else {
+            let jws = did_webplus_cli_lib::wallet_did_sign_jws(
+                &mut std::io::stdin(),
+                self.jws_payload_args.payload_presence,
+                self.jws_payload_args.payload_encoding,
+                &wallet,
+                self.verification_method_args.controlled_did_o.as_deref(),
+                Some(self.verification_method_args.key_purpose),
+                self.verification_method_args.key_id_o.as_deref(),
+            )
+            .await?;
+            match self
+                .serialization_o
+                .unwrap_or(did_webplus_jws::JWSSerialization::Compact)
+            {
+                did_webplus_jws::JWSSerialization::Compact => jws.into_string(),
+                serialization => {
+                    did_webplus_jws::JWSJSON::from(&jws).to_serialization_string(serialization)?
+                }
+            }
+        };
 
         // Print the JWS and optional newline.
-        std::io::stdout().write_all(jws.as_bytes())?;
+        std::io::stdout().write_all(jws_string.as_bytes())?;
         self.newline_args
             .print_newline_if_necessary(&mut std::io::stdout())?;
 
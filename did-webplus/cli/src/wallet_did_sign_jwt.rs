@@ -0,0 +1,61 @@
+use crate::{NewlineArgs, Result, VerificationMethodArgs, WalletArgs};
+use std::io::Write;
+
+/// Sign a JWT using the specified DID and specified key purpose from the specified wallet.  The claims
+/// of the JWT (a JSON object) will be read from stdin.  If the "iss" claim is absent, it will be set to
+/// the signing DID, and if present, it must be the signing DID.  The JWT will be written to stdout.  If
+/// no --wallet-uuid argument is specified, then there must only be one wallet in the database, and that
+/// wallet will be used.  If there is more than one wallet in the database, the --wallet-uuid argument
+/// must be specified.
+#[derive(clap::Parser)]
+pub struct WalletDIDSignJWT {
+    #[command(flatten)]
+    pub wallet_args: WalletArgs,
+    #[command(flatten)]
+    pub verification_method_args: VerificationMethodArgs,
+    /// If specified, then set the "iat" claim to the current time, unless the claims already have it.
+    #[arg(long)]
+    pub issued_at_now: bool,
+    /// If specified, then set the "exp" claim to the given number of seconds after the current time, unless
+    /// the claims already have it.
+    #[arg(name = "expires-in", long, value_name = "SECONDS")]
+    pub expires_in_o: Option<u64>,
+    #[command(flatten)]
+    pub newline_args: NewlineArgs,
+}
+
+impl WalletDIDSignJWT {
+    pub async fn handle(self) -> Result<()> {
+        // Handle CLI args and input
+        let wallet = self.wallet_args.open_wallet().await?;
+        let mut claims: did_webplus_jws::JWTClaims = serde_json::from_reader(std::io::stdin())?;
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)?
+            .as_secs() as i64;
+        if self.issued_at_now && claims.iat.is_none() {
+            claims.iat = Some(now);
+        }
+        if let Some(expires_in) = self.expires_in_o {
+            if claims.exp.is_none() {
+                claims.exp = Some(now.saturating_add_unsigned(expires_in));
+            }
+        }
+
+        // Do the processing
+        let jwt = did_webplus_cli_lib::wallet_did_sign_jwt(
+            claims,
+            &wallet,
+            self.verification_method_args.controlled_did_o.as_deref(),
+            Some(self.verification_method_args.key_purpose),
+            self.verification_method_args.key_id_o.as_deref(),
+        )
+        .await?;
+
+        // Print the JWT and optional newline.
+        std::io::stdout().write_all(jwt.as_str().as_bytes())?;
+        self.newline_args
+            .print_newline_if_necessary(&mut std::io::stdout())?;
+
+        Ok(())
+    }
+}
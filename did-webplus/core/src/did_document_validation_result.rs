@@ -0,0 +1,30 @@
+/// The result of a VDR dry-run of a DID create or update request (see VDR_DRY_RUN_PATH_SUFFIX).  The VDR
+/// runs the full validation of the DID document against the latest stored DID document, including
+/// authorization and admission policies, but doesn't store it.
+#[derive(Clone, Debug, serde::Deserialize, Eq, PartialEq, serde::Serialize)]
+pub struct DIDDocumentValidationResult {
+    /// True iff the VDR would accept the request.
+    pub valid: bool,
+    /// The HTTP status code the VDR would have responded with had the request not been a dry-run.
+    pub status: u16,
+    /// If the VDR would reject the request, this is the reason.
+    #[serde(rename = "error", default, skip_serializing_if = "Option::is_none")]
+    pub error_o: Option<String>,
+}
+
+impl DIDDocumentValidationResult {
+    pub fn valid() -> Self {
+        Self {
+            valid: true,
+            status: 200,
+            error_o: None,
+        }
+    }
+    pub fn invalid(status: u16, error: String) -> Self {
+        Self {
+            valid: false,
+            status,
+            error_o: Some(error),
+        }
+    }
+}
@@ -107,14 +107,49 @@ impl DIDStr {
             url.write_fmt(format_args!(":{}", port)).unwrap();
         }
         url.push('/');
-        if let Some(path) = self.path_o().as_deref() {
-            url.push_str(&path.replace(':', "/"));
-            url.push('/');
-        }
-        url.push_str(self.root_self_hash().as_str());
-        url.push_str("/did-documents.jsonl");
+        url.push_str(&self.resolution_path_for_did_documents_jsonl());
         url
     }
+    /// Produce the path (relative to the host, without a leading '/') of the did-documents.jsonl file for
+    /// this DID, e.g. "foo/bar/uHiB.../did-documents.jsonl".  This is the inverse of
+    /// DID::from_did_documents_jsonl_resolution_url, and is also the path of the file within a static-hosting
+    /// directory tree.
+    pub fn resolution_path_for_did_documents_jsonl(&self) -> String {
+        let mut path = String::new();
+        if let Some(did_path) = self.path_o() {
+            path.push_str(&did_path.replace(':', "/"));
+            path.push('/');
+        }
+        path.push_str(self.root_self_hash().as_str());
+        path.push_str("/did-documents.jsonl");
+        path
+    }
+    /// Produce the did:web DID corresponding to this DID, i.e. the same DID but with method "web" instead
+    /// of "webplus".  Its DID document is the did.json file published alongside did-documents.jsonl
+    /// (see resolution_url_for_did_json and DIDWebDocument).
+    pub fn to_did_web_string(&self) -> String {
+        format!(
+            "did:web:{}",
+            self.as_str()
+                .strip_prefix("did:webplus:")
+                .expect("programmer error")
+        )
+    }
+    /// Produce the URL that addresses the did:web-compatible did.json file for this DID.  This is the URL
+    /// that did:web resolution of the DID produced by to_did_web_string maps to.
+    pub fn resolution_url_for_did_json(
+        &self,
+        http_scheme_override_o: Option<&HTTPSchemeOverride>,
+    ) -> String {
+        let did_documents_jsonl_url =
+            self.resolution_url_for_did_documents_jsonl(http_scheme_override_o);
+        format!(
+            "{}did.json",
+            did_documents_jsonl_url
+                .strip_suffix("did-documents.jsonl")
+                .expect("programmer error")
+        )
+    }
 }
 
 impl pneutype::Validate for DIDStr {
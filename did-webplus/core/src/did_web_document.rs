@@ -0,0 +1,118 @@
+use crate::{DIDDocument, KeyPurpose, PublicKeyParams};
+
+/// A did:web-compatible rendering of a did:webplus DID document, for publication as a `did.json` file
+/// alongside `did-documents.jsonl`, so that verifiers which only understand did:web can use did:webplus
+/// DIDs.  The did:web DID is the did:webplus DID with method "web" (see DIDStr::to_did_web_string), and
+/// `alsoKnownAs` links back to the did:webplus DID.  Note that did:web has no notion of DID document
+/// history, so this only ever reflects the latest DID document, and it carries none of the did:webplus
+/// fields (selfHash, updateRules, etc) needed to verify it.
+#[derive(Clone, Debug, serde::Deserialize, Eq, PartialEq, serde::Serialize)]
+pub struct DIDWebDocument {
+    /// The did:web DID.
+    pub id: String,
+    #[serde(rename = "alsoKnownAs", default, skip_serializing_if = "Vec::is_empty")]
+    pub also_known_as_v: Vec<String>,
+    #[serde(rename = "verificationMethod", default)]
+    pub verification_method_v: Vec<DIDWebVerificationMethod>,
+    #[serde(rename = "authentication", default)]
+    pub authentication_v: Vec<String>,
+    #[serde(rename = "assertionMethod", default)]
+    pub assertion_method_v: Vec<String>,
+    #[serde(rename = "keyAgreement", default)]
+    pub key_agreement_v: Vec<String>,
+    #[serde(rename = "capabilityInvocation", default)]
+    pub capability_invocation_v: Vec<String>,
+    #[serde(rename = "capabilityDelegation", default)]
+    pub capability_delegation_v: Vec<String>,
+}
+
+/// A verification method in a DIDWebDocument.  The key ID is `<did:web DID>#<key ID fragment>`.
+#[derive(Clone, Debug, serde::Deserialize, Eq, PartialEq, serde::Serialize)]
+pub struct DIDWebVerificationMethod {
+    pub id: String,
+    pub r#type: String,
+    pub controller: String,
+    #[serde(rename = "publicKeyJwk")]
+    pub public_key_params: PublicKeyParams,
+}
+
+impl DIDWebDocument {
+    /// Returns the verification method with the given key ID fragment, if present.
+    pub fn verification_method_for_key_id_fragment(
+        &self,
+        key_id_fragment: &str,
+    ) -> Option<&DIDWebVerificationMethod> {
+        self.verification_method_v
+            .iter()
+            .find(|verification_method| {
+                verification_method
+                    .id
+                    .rsplit_once('#')
+                    .is_some_and(|(_, fragment)| fragment == key_id_fragment)
+            })
+    }
+    /// Returns the entries of the verification relationship for the given KeyPurpose, i.e. the elements of
+    /// the "authentication", "assertionMethod", "keyAgreement", "capabilityInvocation", and
+    /// "capabilityDelegation" fields.
+    pub fn key_ids_for_purpose(&self, key_purpose: KeyPurpose) -> &[String] {
+        match key_purpose {
+            KeyPurpose::Authentication => &self.authentication_v,
+            KeyPurpose::AssertionMethod => &self.assertion_method_v,
+            KeyPurpose::KeyAgreement => &self.key_agreement_v,
+            KeyPurpose::CapabilityInvocation => &self.capability_invocation_v,
+            KeyPurpose::CapabilityDelegation => &self.capability_delegation_v,
+            KeyPurpose::UpdateDIDDocument => {
+                panic!(
+                    "programmer error: UpdateDIDDocument is not a valid KeyPurpose for a verification method"
+                );
+            }
+        }
+    }
+    /// Returns true iff the key with the given key ID fragment is listed under the given KeyPurpose.  Entries
+    /// may either be relative (`#<fragment>`) or absolute (`<did:web DID>#<fragment>`).
+    pub fn key_id_fragment_has_purpose(
+        &self,
+        key_id_fragment: &str,
+        key_purpose: KeyPurpose,
+    ) -> bool {
+        self.key_ids_for_purpose(key_purpose).iter().any(|key_id| {
+            key_id.rsplit_once('#').is_some_and(|(did_web, fragment)| {
+                fragment == key_id_fragment && (did_web.is_empty() || did_web == self.id)
+            })
+        })
+    }
+}
+
+impl From<&DIDDocument> for DIDWebDocument {
+    fn from(did_document: &DIDDocument) -> Self {
+        let did_web = did_document.did.to_did_web_string();
+        let key_id_for = |fragment: &str| format!("{}#{}", did_web, fragment);
+        let key_ids_for_purpose = |key_purpose: KeyPurpose| {
+            did_document
+                .public_key_material
+                .relative_key_resources_for_purpose(key_purpose)
+                .map(|relative_key_resource| key_id_for(relative_key_resource.fragment()))
+                .collect::<Vec<_>>()
+        };
+        Self {
+            id: did_web.clone(),
+            also_known_as_v: vec![did_document.did.to_string()],
+            verification_method_v: did_document
+                .public_key_material
+                .verification_method_v
+                .iter()
+                .map(|verification_method| DIDWebVerificationMethod {
+                    id: key_id_for(verification_method.id.fragment()),
+                    r#type: verification_method.r#type.clone(),
+                    controller: did_web.clone(),
+                    public_key_params: verification_method.public_key_jwk.public_key_params.clone(),
+                })
+                .collect(),
+            authentication_v: key_ids_for_purpose(KeyPurpose::Authentication),
+            assertion_method_v: key_ids_for_purpose(KeyPurpose::AssertionMethod),
+            key_agreement_v: key_ids_for_purpose(KeyPurpose::KeyAgreement),
+            capability_invocation_v: key_ids_for_purpose(KeyPurpose::CapabilityInvocation),
+            capability_delegation_v: key_ids_for_purpose(KeyPurpose::CapabilityDelegation),
+        }
+    }
+}
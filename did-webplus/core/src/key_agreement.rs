@@ -0,0 +1,112 @@
+use crate::{
+    Error, Result, mb_pub_key_from_x25519_public_key_bytes, x25519_public_key_bytes_from_mb_pub_key,
+};
+
+/// The key types that the wallets use for keyAgreement (i.e. ECDH) keys.  A keyAgreement key of
+/// each type is generated for each DID, so that key exchange is possible with peers that use either.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum KeyAgreementKeyType {
+    X25519,
+    P256,
+}
+
+impl KeyAgreementKeyType {
+    pub const VARIANTS: [Self; 2] = [Self::X25519, Self::P256];
+
+    /// Returns the KeyAgreementKeyType of the given pub key, or None if it isn't a supported keyAgreement key.
+    pub fn of_pub_key(pub_key: &mbx::MBPubKeyStr) -> Option<Self> {
+        if x25519_public_key_bytes_from_mb_pub_key(pub_key).is_ok() {
+            Some(Self::X25519)
+        } else if pub_key.try_into_key_type().ok() == Some(signature_dyn::KeyType::P256) {
+            Some(Self::P256)
+        } else {
+            None
+        }
+    }
+    /// Generate a new keyAgreement priv key of this type, returning its pub key and the priv key bytes to be
+    /// stored in the wallet.  signature-dyn has no X25519 key type, so an X25519 priv key is stored as the
+    /// Ed25519 seed that it's derived from (the same conversion as libsodium's
+    /// crypto_sign_ed25519_sk_to_curve25519).  That Ed25519 key is never published, and the wallet must store
+    /// this priv key as a keyAgreement-only key, so that it's never used for signing.
+    pub fn generate_priv_key(self) -> Result<(mbx::MBPubKey, signature_dyn::SignerBytes)> {
+        use signature_dyn::{ExtractableSignerT, GenerateRandom};
+        match self {
+            Self::X25519 => {
+                let signing_key = ed25519_dalek::SigningKey::generate_random();
+                let pub_key = mb_pub_key_from_x25519_public_key_bytes(
+                    &signing_key.verifying_key().to_montgomery().to_bytes(),
+                )?;
+                Ok((pub_key, signing_key.extract_signer_bytes()?))
+            }
+            Self::P256 => {
+                let signing_key_b = signature_dyn::KeyType::P256.generate_random_private_key();
+                let pub_key = mbx::MBPubKey::try_from_verifier_bytes(
+                    mbx::Base::Base64Url,
+                    &signing_key_b.get_verifier()?.to_verifier_bytes(),
+                )
+                .map_err(|e| Error::Malformed(e.to_string().into()))?;
+                Ok((pub_key, signing_key_b.extract_signer_bytes()?))
+            }
+        }
+    }
+}
+
+/// Derive the raw ECDH shared secret from the given keyAgreement priv key (as produced by
+/// KeyAgreementKeyType::generate_priv_key) and the other party's keyAgreement pub key, which must
+/// be of the same key type.
+pub fn derive_shared_secret(
+    signer_bytes: &signature_dyn::SignerBytes,
+    other_pub_key: &mbx::MBPubKeyStr,
+) -> Result<zeroize::Zeroizing<Vec<u8>>> {
+    let priv_key_bytes = zeroize::Zeroizing::new(signer_bytes.clone().into_bytes().to_vec());
+    match (
+        signer_bytes.key_type(),
+        KeyAgreementKeyType::of_pub_key(other_pub_key),
+    ) {
+        (signature_dyn::KeyType::Ed25519, Some(KeyAgreementKeyType::X25519)) => {
+            let seed = zeroize::Zeroizing::new(
+                <[u8; 32]>::try_from(priv_key_bytes.as_slice())
+                    .map_err(|_| Error::Malformed("Invalid X25519 priv key".into()))?,
+            );
+            let scalar_bytes = zeroize::Zeroizing::new(
+                ed25519_dalek::SigningKey::from_bytes(&seed).to_scalar_bytes(),
+            );
+            let other_public_key_bytes = x25519_public_key_bytes_from_mb_pub_key(other_pub_key)?;
+            let shared_secret = zeroize::Zeroizing::new(
+                curve25519_dalek::MontgomeryPoint(other_public_key_bytes)
+                    .mul_clamped(*scalar_bytes)
+                    .to_bytes(),
+            );
+            // A low-order other pub key produces the all-zero shared secret, which must be rejected.
+            if shared_secret.iter().all(|&b| b == 0) {
+                return Err(Error::Invalid(
+                    "X25519 key exchange produced an all-zero shared secret".into(),
+                ));
+            }
+            Ok(zeroize::Zeroizing::new(shared_secret.to_vec()))
+        }
+        (signature_dyn::KeyType::P256, Some(KeyAgreementKeyType::P256)) => {
+            let secret_key = p256::SecretKey::from_slice(priv_key_bytes.as_slice())
+                .map_err(|e| Error::Malformed(format!("Invalid P-256 priv key: {}", e).into()))?;
+            let other_verifier_bytes = signature_dyn::VerifierBytes::try_from(other_pub_key)
+                .map_err(|e| Error::Malformed(e.to_string().into()))?;
+            let other_public_key = p256::PublicKey::from_sec1_bytes(other_verifier_bytes.as_ref())
+                .map_err(|e| Error::Malformed(format!("Invalid P-256 pub key: {}", e).into()))?;
+            let shared_secret = p256::ecdh::diffie_hellman(
+                secret_key.to_nonzero_scalar(),
+                other_public_key.as_affine(),
+            );
+            Ok(zeroize::Zeroizing::new(
+                shared_secret.raw_secret_bytes().to_vec(),
+            ))
+        }
+        _ => Err(Error::Unsupported(
+            format!(
+                "keyAgreement priv key of type {:?} can't be used for key exchange with pub key {}",
+                signer_bytes.key_type(),
+                other_pub_key
+            )
+            .into(),
+        )),
+    }
+}
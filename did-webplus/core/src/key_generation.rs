@@ -0,0 +1,45 @@
+use crate::{KeyAgreementKeyType, KeyPurpose, PublicKeySet, Result};
+
+/// The pub keys and priv keys generated for a DID document by generate_priv_keys, keyed by KeyPurpose.
+pub type GeneratedPrivKeys =
+    enum_map::EnumMap<KeyPurpose, Vec<(mbx::MBPubKey, signature_dyn::SignerBytes)>>;
+
+/// Generate a full set of priv keys for a new DID document: an Ed25519 key for each KeyPurpose other than
+/// KeyAgreement, and a keyAgreement key of each KeyAgreementKeyType (since Ed25519 keys can't be used for
+/// ECDH).  Returns the pub keys and priv keys, keyed by KeyPurpose.
+pub fn generate_priv_keys() -> Result<GeneratedPrivKeys> {
+    use signature_dyn::{ExtractableSignerT, GenerateRandom};
+    let mut priv_key_vm = GeneratedPrivKeys::default();
+    for key_purpose in KeyPurpose::VARIANTS {
+        if key_purpose == KeyPurpose::KeyAgreement {
+            for key_agreement_key_type in KeyAgreementKeyType::VARIANTS {
+                priv_key_vm[key_purpose].push(key_agreement_key_type.generate_priv_key()?);
+            }
+        } else {
+            let signing_key = ed25519_dalek::SigningKey::generate_random();
+            let pub_key = mbx::MBPubKey::from_ed25519_dalek_verifying_key(
+                mbx::Base::Base64Url,
+                &signing_key.verifying_key(),
+            );
+            priv_key_vm[key_purpose].push((pub_key, signing_key.extract_signer_bytes()?));
+        }
+    }
+    Ok(priv_key_vm)
+}
+
+/// Forms the PublicKeySet for a DID document from the priv keys generated by generate_priv_keys.
+pub fn public_key_set(priv_key_vm: &GeneratedPrivKeys) -> PublicKeySet<&mbx::MBPubKey> {
+    let pub_keys_for = |key_purpose: KeyPurpose| {
+        priv_key_vm[key_purpose]
+            .iter()
+            .map(|(pub_key, _signer_bytes)| pub_key)
+            .collect::<Vec<_>>()
+    };
+    PublicKeySet {
+        authentication_v: pub_keys_for(KeyPurpose::Authentication),
+        assertion_method_v: pub_keys_for(KeyPurpose::AssertionMethod),
+        key_agreement_v: pub_keys_for(KeyPurpose::KeyAgreement),
+        capability_invocation_v: pub_keys_for(KeyPurpose::CapabilityInvocation),
+        capability_delegation_v: pub_keys_for(KeyPurpose::CapabilityDelegation),
+    }
+}
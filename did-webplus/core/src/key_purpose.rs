@@ -109,3 +109,40 @@ impl TryFrom<u8> for KeyPurpose {
         }
     }
 }
+
+impl From<verifier_resolver::VerificationRelationship> for KeyPurpose {
+    fn from(verification_relationship: verifier_resolver::VerificationRelationship) -> Self {
+        match verification_relationship {
+            verifier_resolver::VerificationRelationship::Authentication => {
+                KeyPurpose::Authentication
+            }
+            verifier_resolver::VerificationRelationship::AssertionMethod => {
+                KeyPurpose::AssertionMethod
+            }
+            verifier_resolver::VerificationRelationship::KeyAgreement => KeyPurpose::KeyAgreement,
+            verifier_resolver::VerificationRelationship::CapabilityInvocation => {
+                KeyPurpose::CapabilityInvocation
+            }
+            verifier_resolver::VerificationRelationship::CapabilityDelegation => {
+                KeyPurpose::CapabilityDelegation
+            }
+        }
+    }
+}
+
+/// Note that UpdateDIDDocument is not a verification relationship, so it can't be converted.
+impl TryFrom<KeyPurpose> for verifier_resolver::VerificationRelationship {
+    type Error = Error;
+    fn try_from(key_purpose: KeyPurpose) -> Result<Self, Self::Error> {
+        match key_purpose {
+            KeyPurpose::Authentication => Ok(Self::Authentication),
+            KeyPurpose::AssertionMethod => Ok(Self::AssertionMethod),
+            KeyPurpose::KeyAgreement => Ok(Self::KeyAgreement),
+            KeyPurpose::CapabilityInvocation => Ok(Self::CapabilityInvocation),
+            KeyPurpose::CapabilityDelegation => Ok(Self::CapabilityDelegation),
+            KeyPurpose::UpdateDIDDocument => Err(Error::Invalid(
+                "KeyPurpose updateDIDDocument is not a verification relationship".into(),
+            )),
+        }
+    }
+}
@@ -3,6 +3,7 @@ mod creation_metadata;
 mod did;
 mod did_document;
 mod did_document_metadata;
+mod did_document_validation_result;
 mod did_fully_qualified;
 mod did_fully_qualified_str;
 mod did_resolution_metadata;
@@ -13,12 +14,17 @@ mod did_resource_fully_qualified_str;
 mod did_resource_str;
 mod did_str;
 mod did_uri_components;
+mod did_web_document;
 mod did_with_query;
 mod did_with_query_str;
 mod error;
 mod http_headers_for;
 mod http_options;
 mod http_scheme_override;
+#[cfg(feature = "key-agreement")]
+mod key_agreement;
+#[cfg(feature = "key-agreement")]
+mod key_generation;
 mod key_purpose;
 mod key_purpose_flags;
 mod latest_update_metadata;
@@ -33,6 +39,7 @@ mod relative_resource;
 mod relative_resource_str;
 mod update_rules;
 mod verification_method;
+mod x25519;
 
 pub(crate) use crate::{
     base64::{
@@ -47,6 +54,7 @@ pub use crate::{
     did::DID,
     did_document::DIDDocument,
     did_document_metadata::DIDDocumentMetadata,
+    did_document_validation_result::DIDDocumentValidationResult,
     did_fully_qualified::DIDFullyQualified,
     did_fully_qualified_str::DIDFullyQualifiedStr,
     did_resolution_metadata::DIDResolutionMetadata,
@@ -57,6 +65,7 @@ pub use crate::{
     did_resource_str::DIDResourceStr,
     did_str::DIDStr,
     did_uri_components::DIDURIComponents,
+    did_web_document::{DIDWebDocument, DIDWebVerificationMethod},
     did_with_query::DIDWithQuery,
     did_with_query_str::DIDWithQueryStr,
     error::Error,
@@ -80,6 +89,13 @@ pub use crate::{
         UpdatesDisallowed, ValidProofData, VerifyRulesT, WeightedUpdateRules,
     },
     verification_method::VerificationMethod,
+    x25519::{mb_pub_key_from_x25519_public_key_bytes, x25519_public_key_bytes_from_mb_pub_key},
+};
+
+#[cfg(feature = "key-agreement")]
+pub use crate::{
+    key_agreement::{KeyAgreementKeyType, derive_shared_secret},
+    key_generation::{GeneratedPrivKeys, generate_priv_keys, public_key_set},
 };
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -90,6 +106,17 @@ pub type DIDKeyResource = DIDResource<str>;
 pub type DIDKeyResourceFullyQualified = DIDResourceFullyQualified<str>;
 pub type DIDKeyResourceFullyQualifiedStr = DIDResourceFullyQualifiedStr<str>;
 
+/// Name of the HTTP header that carries a detached JWS authorizing a DID create or update request to a VDR
+/// that requires signed authorization.  The JWS payload is the request body, i.e. the DID document, and its
+/// "kid" is the fully qualified key ID of a capabilityInvocation key of the signing DID.
+pub const VDR_AUTHORIZATION_JWS_HEADER: &str = "x-did-webplus-authorization-jws";
+
+/// Suffix which, when appended to the did-documents.jsonl URL of a DID create (POST) or update (PUT) request
+/// to a VDR, makes the VDR validate the request without storing the DID document, and respond with a
+/// DIDDocumentValidationResult.  This is a path suffix rather than a query parameter so that a VDR which
+/// doesn't support dry-runs rejects the request instead of carrying it out.
+pub const VDR_DRY_RUN_PATH_SUFFIX: &str = "/dry-run";
+
 /// This function returns the current time in UTC with millisecond precision.  This precision
 /// limit is required for interoperability with javascript systems (see
 /// <https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/now>).
@@ -1,6 +1,6 @@
 use crate::{Error, base64_encode_256_bits, base64_encode_456_bits};
 
-// "kty" of "OKP" is used for curves including "Ed25519".
+// "kty" of "OKP" is used for curves including "Ed25519", "Ed448", and "X25519" (a keyAgreement curve).
 #[derive(Clone, Debug, serde::Deserialize, Eq, PartialEq, serde::Serialize)]
 pub struct PublicKeyParamsOKP {
     pub crv: String,
@@ -22,6 +22,13 @@ impl PublicKeyParamsOKP {
             x,
         }
     }
+    /// Convenience function for creating the PublicKeyParamsOKP for an X25519 (keyAgreement) key.
+    pub fn x25519(x: String) -> Self {
+        Self {
+            crv: "X25519".into(),
+            x,
+        }
+    }
 }
 
 impl TryFrom<&mbx::MBPubKeyStr> for PublicKeyParamsOKP {
@@ -53,6 +60,18 @@ impl TryFrom<&mbx::MBPubKeyStr> for PublicKeyParamsOKP {
                     x: public_key_base64.to_string(),
                 })
             }
+            ssi_multicodec::X25519_PUB => {
+                let mut buffer = [0u8; 43];
+                let public_key_base64 = base64_encode_256_bits(
+                    <&[u8; 32]>::try_from(decoded.data())
+                        .map_err(|_| Error::Malformed("Invalid X25519 public key".into()))?,
+                    &mut buffer,
+                );
+                Ok(Self {
+                    crv: "X25519".into(),
+                    x: public_key_base64.to_string(),
+                })
+            }
             ssi_multicodec::SECP256K1_PUB | ssi_multicodec::P256_PUB | ssi_multicodec::P384_PUB | ssi_multicodec::P521_PUB => {
                 Err(Error::Malformed("Secp256k1, P256, P384, and P521 keys should use PublicKeyParamsEC, not PublicKeyParamsOKP".into()))
             }
@@ -125,6 +144,19 @@ impl TryFrom<&PublicKeyParamsOKP> for mbx::MBPubKey {
                     );
                 }
             }
+            "X25519" => {
+                // X25519 keys don't depend on any crypto crate feature, since they're only parsed here,
+                // not used for signature verification.
+                let mut buffer = [0u8; 33];
+                let public_key_bytes =
+                    crate::base64_decode_256_bits(public_key_params_okp.x.as_str(), &mut buffer)
+                        .map_err(|_| {
+                            Error::Malformed(
+                                "Invalid Base64URL encoding of X25519 public key".into(),
+                            )
+                        })?;
+                crate::mb_pub_key_from_x25519_public_key_bytes(public_key_bytes)
+            }
             _ => Err(Error::Unrecognized(
                 format!("OKP curve: {}", public_key_params_okp.crv).into(),
             )),
@@ -215,6 +247,25 @@ mod test {
         println!("]");
     }
 
+    #[test]
+    fn test_roundtrip_public_key_params_okp_x25519() {
+        use std::ops::Deref;
+        for _ in 0..10 {
+            // Any 32 bytes will do for testing the encoding.
+            let public_key_bytes: [u8; 32] = rand::random();
+            let mb_pub_key =
+                crate::mb_pub_key_from_x25519_public_key_bytes(&public_key_bytes).unwrap();
+            let public_key_params_okp = PublicKeyParamsOKP::try_from(mb_pub_key.deref()).unwrap();
+            assert_eq!(public_key_params_okp.crv, "X25519");
+            let recovered_mb_pub_key = mbx::MBPubKey::try_from(&public_key_params_okp).unwrap();
+            assert_eq!(recovered_mb_pub_key, mb_pub_key);
+            assert_eq!(
+                crate::x25519_public_key_bytes_from_mb_pub_key(&recovered_mb_pub_key).unwrap(),
+                public_key_bytes
+            );
+        }
+    }
+
     // NOTE: See the comment under [dev-dependencies] in Cargo.toml for the reason this test is commented out.
 
     // #[cfg(feature = "ed25519-dalek")]
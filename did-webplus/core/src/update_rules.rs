@@ -373,6 +373,40 @@ impl From<Threshold> for UpdateRules {
     }
 }
 
+impl UpdateRules {
+    /// Appends the pub keys (from `Key` rules) and hashed pub keys (from `HashedKey` rules) that occur
+    /// anywhere in these update rules to pub_key_v and hashed_pub_key_v, respectively, in order of occurrence.
+    pub fn collect_update_keys<'a>(
+        &'a self,
+        pub_key_v: &mut Vec<&'a MBPubKeyStr>,
+        hashed_pub_key_v: &mut Vec<&'a MBHashStr>,
+    ) {
+        match self {
+            UpdateRules::Key(key) => pub_key_v.push(key.pub_key.as_mb_pub_key_str()),
+            UpdateRules::HashedKey(hashed_key) => {
+                hashed_pub_key_v.push(hashed_key.hashed_pub_key())
+            }
+            UpdateRules::Any(any) => {
+                for update_rules in any.any.iter() {
+                    update_rules.collect_update_keys(pub_key_v, hashed_pub_key_v);
+                }
+            }
+            UpdateRules::All(all) => {
+                for update_rules in all.all.iter() {
+                    update_rules.collect_update_keys(pub_key_v, hashed_pub_key_v);
+                }
+            }
+            UpdateRules::Threshold(threshold) => {
+                for weighted_update_rules in threshold.of.iter() {
+                    weighted_update_rules
+                        .update_rules
+                        .collect_update_keys(pub_key_v, hashed_pub_key_v);
+                }
+            }
+        }
+    }
+}
+
 impl VerifyRulesT for UpdateRules {
     fn verify_rules(&self, valid_proof_data_v: &[ValidProofData]) -> Result<()> {
         match self {
@@ -432,6 +466,19 @@ impl<T: Into<UpdateRules>> From<T> for RootLevelUpdateRules {
     }
 }
 
+impl RootLevelUpdateRules {
+    /// See UpdateRules::collect_update_keys.  UpdatesDisallowed has no update keys.
+    pub fn collect_update_keys<'a>(
+        &'a self,
+        pub_key_v: &mut Vec<&'a MBPubKeyStr>,
+        hashed_pub_key_v: &mut Vec<&'a MBHashStr>,
+    ) {
+        if let RootLevelUpdateRules::UpdateRules(update_rules) = self {
+            update_rules.collect_update_keys(pub_key_v, hashed_pub_key_v);
+        }
+    }
+}
+
 impl VerifyRulesT for RootLevelUpdateRules {
     fn verify_rules(&self, valid_proof_data_v: &[ValidProofData]) -> Result<()> {
         match self {
@@ -545,4 +592,44 @@ mod tests {
             UpdatesDisallowed {},
         ));
     }
+
+    #[test]
+    fn test_update_rules_collect_update_keys() {
+        test_util::ctor_overall_init();
+
+        let pub_key =
+            MBPubKey::try_from("u7QEbA22Wx6DsuuqVNK04jSNYzVBx3vviEf_t4b-Xif3ZOg").unwrap();
+        let hashed_pub_key =
+            MBHash::try_from("uEiAWCleApqPkQg-DKbql-C5OOyZ7ydUgq7G_rHepYEukHg").unwrap();
+        let update_rules = RootLevelUpdateRules::from(Any {
+            any: vec![
+                UpdateKey {
+                    pub_key: pub_key.clone(),
+                }
+                .into(),
+                Threshold {
+                    at_least: 1,
+                    of: vec![
+                        HashedUpdateKey {
+                            hashed_pub_key: hashed_pub_key.clone(),
+                        }
+                        .into(),
+                    ],
+                }
+                .into(),
+            ],
+        });
+        let mut pub_key_v = Vec::new();
+        let mut hashed_pub_key_v = Vec::new();
+        update_rules.collect_update_keys(&mut pub_key_v, &mut hashed_pub_key_v);
+        assert_eq!(pub_key_v, vec![pub_key.as_mb_pub_key_str()]);
+        assert_eq!(hashed_pub_key_v, vec![hashed_pub_key.as_mb_hash_str()]);
+
+        let mut pub_key_v = Vec::new();
+        let mut hashed_pub_key_v = Vec::new();
+        RootLevelUpdateRules::UpdatesDisallowed(UpdatesDisallowed {})
+            .collect_update_keys(&mut pub_key_v, &mut hashed_pub_key_v);
+        assert!(pub_key_v.is_empty());
+        assert!(hashed_pub_key_v.is_empty());
+    }
 }
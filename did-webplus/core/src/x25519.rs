@@ -0,0 +1,38 @@
+use crate::{Error, Result};
+
+/// The unsigned varint encoding of the x25519-pub multicodec (0xec).
+const X25519_PUB_CODEC_PREFIX: [u8; 2] = [0xec, 0x01];
+
+/// Produces the multicodec-encoded, base64url-multibase-encoded form of the given X25519 public key.
+/// X25519 keys are keyAgreement (i.e. ECDH) keys, not signature keys, so mbx doesn't provide a
+/// constructor for them analogous to mbx::MBPubKey::from_ed25519_dalek_verifying_key.
+pub fn mb_pub_key_from_x25519_public_key_bytes(
+    public_key_bytes: &[u8; 32],
+) -> Result<mbx::MBPubKey> {
+    let mut multicodec_bytes = [0u8; 34];
+    multicodec_bytes[..2].copy_from_slice(&X25519_PUB_CODEC_PREFIX);
+    multicodec_bytes[2..].copy_from_slice(public_key_bytes);
+    // 34 bytes is 46 base64 chars (rounded up).
+    let mut buffer = [0u8; 46];
+    use base64::Engine;
+    base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .encode_slice(multicodec_bytes, &mut buffer)
+        .unwrap();
+    let multibase_string = format!("u{}", std::str::from_utf8(&buffer).unwrap());
+    mbx::MBPubKey::try_from(multibase_string)
+        .map_err(|e| Error::Malformed(format!("Failed to encode X25519 public key: {}", e).into()))
+}
+
+/// Returns the raw X25519 public key bytes of the given pub key, or an error if it's not an X25519 pub key.
+pub fn x25519_public_key_bytes_from_mb_pub_key(pub_key: &mbx::MBPubKeyStr) -> Result<[u8; 32]> {
+    let decoded = pub_key
+        .decoded()
+        .map_err(|e| Error::Malformed(e.to_string().into()))?;
+    if decoded.codec() != ssi_multicodec::X25519_PUB {
+        return Err(Error::Invalid(
+            format!("expected an X25519 pub key, but got {}", pub_key).into(),
+        ));
+    }
+    <[u8; 32]>::try_from(decoded.data())
+        .map_err(|_| Error::Malformed("Invalid X25519 public key".into()))
+}
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS did_key_records;
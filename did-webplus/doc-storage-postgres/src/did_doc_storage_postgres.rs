@@ -1,5 +1,7 @@
-use did_webplus_core::{DIDDocument, DIDStr};
-use did_webplus_doc_store::{DIDDocRecord, DIDDocRecordFilter, Error, Result};
+use did_webplus_core::{DID, DIDDocument, DIDStr, KeyPurposeFlags};
+use did_webplus_doc_store::{
+    DIDDocRecord, DIDDocRecordFilter, DIDKeyRecord, Error, Result, parse_did_document,
+};
 use sqlx::PgPool;
 
 #[derive(Clone)]
@@ -18,7 +20,101 @@ impl DIDDocStoragePostgres {
                 .into(),
             )
         })?;
-        Ok(Self { pg_pool })
+        let did_doc_storage = Self { pg_pool };
+        did_doc_storage.backfill_did_key_records().await?;
+        Ok(did_doc_storage)
+    }
+    /// Populate did_key_records for the DID documents that have no DIDKeyRecord-s, e.g. ones that were
+    /// stored before did_key_records existed.  Note that this re-checks DID documents having no keys at all
+    /// (e.g. those of deactivated DIDs) each time, but there are relatively few of them.
+    async fn backfill_did_key_records(&self) -> Result<()> {
+        let did_document_jcs_v = sqlx::query_scalar::<_, String>(
+            r#"
+                SELECT did_document_jcs
+                FROM did_document_records
+                WHERE self_hash NOT IN (SELECT self_hash FROM did_key_records)
+            "#,
+        )
+        .fetch_all(&self.pg_pool)
+        .await?;
+        if did_document_jcs_v.is_empty() {
+            return Ok(());
+        }
+        let did_document_v = did_document_jcs_v
+            .iter()
+            .map(|did_document_jcs| parse_did_document(did_document_jcs))
+            .collect::<Result<Vec<_>>>()?;
+        let mut transaction = self.pg_pool.begin().await?;
+        self.add_did_key_records(
+            Some(&mut transaction as &mut dyn storage_traits::TransactionDynT),
+            &did_document_v,
+        )
+        .await?;
+        transaction.commit().await?;
+        tracing::debug!(
+            "backfilled did_key_records for {} DID document(s)",
+            did_document_jcs_v.len()
+        );
+        Ok(())
+    }
+    /// Add the DIDKeyRecord-s for the given DID documents in a single batch insert.  Only the DID documents that
+    /// are present in did_document_records get DIDKeyRecord-s.
+    async fn add_did_key_records(
+        &self,
+        transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
+        did_document_v: &[DIDDocument],
+    ) -> Result<()> {
+        let mut key_v = Vec::new();
+        let mut did_v = Vec::new();
+        let mut version_id_v = Vec::new();
+        let mut self_hash_v = Vec::new();
+        let mut key_purpose_flags_v = Vec::new();
+        let mut is_update_key_v = Vec::new();
+        for did_document in did_document_v.iter() {
+            for did_key_record in DIDKeyRecord::for_did_document(did_document)? {
+                key_v.push(did_key_record.key);
+                did_v.push(did_key_record.did);
+                version_id_v.push(did_key_record.version_id);
+                self_hash_v.push(did_key_record.self_hash);
+                key_purpose_flags_v.push(did_key_record.key_purpose_flags.integer_value() as i32);
+                is_update_key_v.push(did_key_record.is_update_key);
+            }
+        }
+        if key_v.is_empty() {
+            return Ok(());
+        }
+        // A DID document row might not have been inserted, because "ON CONFLICT DO NOTHING" on
+        // did_document_records also applies to its other UNIQUE constraints.
+        let query = sqlx::query(
+            r#"
+                INSERT INTO did_key_records(key, did, version_id, self_hash, key_purpose_flags, is_update_key)
+                SELECT t.key, t.did, t.version_id, t.self_hash, t.key_purpose_flags, t.is_update_key
+                FROM UNNEST($1::TEXT[], $2::TEXT[], $3::BIGINT[], $4::TEXT[], $5::INTEGER[], $6::BOOLEAN[])
+                    AS t(key, did, version_id, self_hash, key_purpose_flags, is_update_key)
+                WHERE EXISTS (SELECT 1 FROM did_document_records WHERE self_hash = t.self_hash)
+                ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(&key_v)
+        .bind(&did_v)
+        .bind(&version_id_v)
+        .bind(&self_hash_v)
+        .bind(&key_purpose_flags_v)
+        .bind(&is_update_key_v);
+        if let Some(transaction) = transaction_o {
+            query
+                .execute(
+                    transaction
+                        .as_any_mut()
+                        .downcast_mut::<sqlx::Transaction<'static, sqlx::Postgres>>()
+                        .unwrap()
+                        .as_mut(),
+                )
+                .await?;
+        } else {
+            query.execute(&self.pg_pool).await?;
+        }
+        Ok(())
     }
 }
 
@@ -27,7 +123,7 @@ impl DIDDocStoragePostgres {
 impl did_webplus_doc_store::DIDDocStorage for DIDDocStoragePostgres {
     async fn add_did_document(
         &self,
-        transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
+        mut transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
         did_document: &DIDDocument,
         did_document_jcs: &str,
     ) -> Result<()> {
@@ -69,7 +165,7 @@ impl did_webplus_doc_store::DIDDocStorage for DIDDocStoragePostgres {
             did_document.self_hash.as_str(),
             did_document_jcs,
         );
-        if let Some(transaction) = transaction_o {
+        if let Some(transaction) = transaction_o.as_mut() {
             query
                 .execute(
                     transaction
@@ -82,6 +178,13 @@ impl did_webplus_doc_store::DIDDocStorage for DIDDocStoragePostgres {
         } else {
             query.execute(&self.pg_pool).await?;
         }
+        self.add_did_key_records(
+            transaction_o
+                .as_mut()
+                .map(|t| &mut **t as &mut dyn storage_traits::TransactionDynT),
+            std::slice::from_ref(did_document),
+        )
+        .await?;
         Ok(())
     }
     async fn add_did_documents(
@@ -91,71 +194,93 @@ impl did_webplus_doc_store::DIDDocStorage for DIDDocStoragePostgres {
         did_document_v: &[DIDDocument],
     ) -> Result<()> {
         assert_eq!(did_document_jcs_v.len(), did_document_v.len());
+        if did_document_v.is_empty() {
+            return Ok(());
+        }
 
-        // TODO: Figure out how to do batch inserts.
-        for (&did_document_jcs, did_document) in
-            did_document_jcs_v.iter().zip(did_document_v.iter())
-        {
-            // self.add_did_document(transaction_o.as_deref_mut(), did_document, did_document_jcs)
-            //     .await?;
-
-            // TEMP HACK -- should just call add_did_document, but there's some compiler error regarding lifetimes of transaction_o
-
+        let mut did_v = Vec::with_capacity(did_document_v.len());
+        let mut version_id_v = Vec::with_capacity(did_document_v.len());
+        let mut valid_from_v = Vec::with_capacity(did_document_v.len());
+        let mut self_hash_v = Vec::with_capacity(did_document_v.len());
+        for did_document in did_document_v.iter() {
             use selfhash::HashRefT;
             assert!(
                 !did_document.self_hash.is_placeholder(),
                 "programmer error: self_hash is expected to be present on a valid DID document"
             );
-            let did_str = did_document.did.as_str();
-            let version_id = did_document.version_id as i64;
-            let valid_from = did_document
-                .valid_from()
-                .map_err(|e| Error::InvalidDIDDocument(e.into()))?;
-            let self_hash_str = did_document.self_hash.as_str();
-            // Regarding "ON CONFLICT DO NOTHING", a conflict will only happen when the self_hash already exists,
-            // and that means that the DID document is verifiably already present in the database.
-            let query = sqlx::query!(
-                r#"
-                    INSERT INTO did_document_records(did, version_id, valid_from, self_hash, did_documents_jsonl_octet_length, did_document_jcs)
-                    VALUES (
-                        $1,
-                        $2,
-                        $3,
-                        $4,
-                        COALESCE(
-                            (
-                                SELECT did_documents_jsonl_octet_length
-                                FROM did_document_records
-                                WHERE did = $1
-                                ORDER BY version_id DESC
-                                LIMIT 1
-                            ),
-                            0
-                        ) + OCTET_LENGTH($5) + 1,
-                        $5
-                    )
-                    ON CONFLICT DO NOTHING
-                "#,
-                did_str,
-                version_id,
-                valid_from,
-                self_hash_str,
-                did_document_jcs,
+            did_v.push(did_document.did.as_str());
+            version_id_v.push(did_document.version_id as i64);
+            valid_from_v.push(
+                did_document
+                    .valid_from()
+                    .map_err(|e| Error::InvalidDIDDocument(e.into()))?,
             );
-            if let Some(transaction) = &mut transaction_o {
-                query
-                    .execute(
-                        transaction
-                            .as_any_mut()
-                            .downcast_mut::<sqlx::Transaction<'static, sqlx::Postgres>>()
-                            .unwrap()
-                            .as_mut(),
+            self_hash_v.push(did_document.self_hash.as_str());
+        }
+
+        // All the DID documents are inserted in a single statement.  Those whose self_hash is already present
+        // (i.e. that are verifiably already in the database) are skipped up front, so that the running sum
+        // of did_documents_jsonl_octet_length only covers the newly inserted DID documents, each of which
+        // continues from the latest DID document already stored for its DID.  The subquery for the latter sees
+        // the database as of the start of the statement, so it doesn't see any of the rows being inserted.
+        // Regarding "ON CONFLICT DO NOTHING", see add_did_document.
+        let query = sqlx::query(
+            r#"
+                WITH new_did_documents AS (
+                    SELECT t.did, t.version_id, t.valid_from, t.self_hash, t.did_document_jcs, t.ordinality
+                    FROM UNNEST($1::TEXT[], $2::BIGINT[], $3::TIMESTAMPTZ[], $4::TEXT[], $5::TEXT[])
+                        WITH ORDINALITY AS t(did, version_id, valid_from, self_hash, did_document_jcs, ordinality)
+                    WHERE NOT EXISTS (
+                        SELECT 1 FROM did_document_records WHERE self_hash = t.self_hash
                     )
-                    .await?;
-            } else {
-                query.execute(&self.pg_pool).await?;
-            }
+                )
+                INSERT INTO did_document_records(did, version_id, valid_from, self_hash, did_documents_jsonl_octet_length, did_document_jcs)
+                SELECT
+                    n.did,
+                    n.version_id,
+                    n.valid_from,
+                    n.self_hash,
+                    COALESCE(
+                        (
+                            SELECT did_documents_jsonl_octet_length
+                            FROM did_document_records
+                            WHERE did = n.did
+                            ORDER BY version_id DESC
+                            LIMIT 1
+                        ),
+                        0
+                    ) + SUM(OCTET_LENGTH(n.did_document_jcs) + 1) OVER (PARTITION BY n.did ORDER BY n.ordinality),
+                    n.did_document_jcs
+                FROM new_did_documents AS n
+                ORDER BY n.ordinality
+                ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(&did_v)
+        .bind(&version_id_v)
+        .bind(&valid_from_v)
+        .bind(&self_hash_v)
+        .bind(did_document_jcs_v);
+        if let Some(transaction) = transaction_o.as_mut() {
+            query
+                .execute(
+                    transaction
+                        .as_any_mut()
+                        .downcast_mut::<sqlx::Transaction<'static, sqlx::Postgres>>()
+                        .unwrap()
+                        .as_mut(),
+                )
+                .await?;
+        } else {
+            query.execute(&self.pg_pool).await?;
         }
+        self.add_did_key_records(
+            transaction_o
+                .as_mut()
+                .map(|t| &mut **t as &mut dyn storage_traits::TransactionDynT),
+            did_document_v,
+        )
+        .await?;
         Ok(())
     }
     async fn get_did_doc_record_with_self_hash(
@@ -294,6 +419,88 @@ impl did_webplus_doc_store::DIDDocStorage for DIDDocStoragePostgres {
         };
         Ok(did_doc_record_v)
     }
+    async fn get_dids(
+        &self,
+        transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
+    ) -> Result<Vec<DID>> {
+        let query = sqlx::query_scalar::<_, String>(
+            "SELECT DISTINCT did FROM did_document_records ORDER BY did",
+        );
+        let did_string_v = if let Some(transaction) = transaction_o {
+            query
+                .fetch_all(
+                    transaction
+                        .as_any_mut()
+                        .downcast_mut::<sqlx::Transaction<'static, sqlx::Postgres>>()
+                        .unwrap()
+                        .as_mut(),
+                )
+                .await?
+        } else {
+            query.fetch_all(&self.pg_pool).await?
+        };
+        let did_v = did_string_v
+            .into_iter()
+            .map(DID::try_from)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(did_v)
+    }
+    async fn get_did_key_records(
+        &self,
+        transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
+        key: &str,
+    ) -> Result<Vec<DIDKeyRecord>> {
+        let query = sqlx::query_as::<_, (String, String, i64, String, i32, bool)>(
+            r#"
+                SELECT key, did, version_id, self_hash, key_purpose_flags, is_update_key
+                FROM did_key_records
+                WHERE key = $1
+                ORDER BY did, version_id
+            "#,
+        )
+        .bind(key);
+        let row_v = if let Some(transaction) = transaction_o {
+            query
+                .fetch_all(
+                    transaction
+                        .as_any_mut()
+                        .downcast_mut::<sqlx::Transaction<'static, sqlx::Postgres>>()
+                        .unwrap()
+                        .as_mut(),
+                )
+                .await?
+        } else {
+            query.fetch_all(&self.pg_pool).await?
+        };
+        row_v
+            .into_iter()
+            .map(
+                |(key, did, version_id, self_hash, key_purpose_flags, is_update_key)| {
+                    let key_purpose_flags = u8::try_from(key_purpose_flags)
+                        .map_err(|_| ())
+                        .and_then(|x| KeyPurposeFlags::try_from(x).map_err(|_| ()))
+                        .map_err(|_| {
+                            Error::RecordCorruption(
+                                format!(
+                                    "invalid key_purpose_flags {} in did_key_records",
+                                    key_purpose_flags
+                                )
+                                .into(),
+                                self_hash.clone().into(),
+                            )
+                        })?;
+                    Ok(DIDKeyRecord {
+                        key,
+                        did,
+                        version_id,
+                        self_hash,
+                        key_purpose_flags,
+                        is_update_key,
+                    })
+                },
+            )
+            .collect()
+    }
     async fn get_did_doc_records_for_did_documents_jsonl_range(
         &self,
         transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
@@ -0,0 +1,247 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use did_webplus_core::DIDDocument;
+use did_webplus_doc_storage_postgres::DIDDocStoragePostgres;
+use did_webplus_doc_store::{DIDDocRecordFilter, DIDDocStorage};
+use did_webplus_mock::{MicroledgerView, MockVDR, MockVDRClient, MockWallet};
+use storage_traits::StorageDynT;
+
+/// This will run once at load time (i.e. presumably before main function is called).
+#[ctor::ctor]
+fn overall_init() {
+    test_util::ctor_overall_init();
+}
+
+/// Build a valid microledger having the given number of DID documents via MockWallet / MockVDR, returning
+/// the DID documents and their JCS.
+fn build_microledger(version_count: usize) -> (Vec<DIDDocument>, Vec<String>) {
+    assert!(version_count > 0);
+    let hostname = "example.com".to_string();
+    let mock_vdr_la = Arc::new(RwLock::new(MockVDR::new_with(
+        hostname.clone(),
+        None,
+        None,
+        None,
+    )));
+    let mock_vdr_lam = {
+        let mut mock_vdr_lam = HashMap::new();
+        mock_vdr_lam.insert(hostname.clone(), mock_vdr_la);
+        mock_vdr_lam
+    };
+    let mock_vdr_client_a = Arc::new(MockVDRClient::new(
+        "doc-storage-postgres test MockVDRClient".to_string(),
+        mock_vdr_lam,
+    ));
+    let mut wallet = MockWallet::new(
+        "doc-storage-postgres test wallet".to_string(),
+        mock_vdr_client_a,
+    );
+
+    let key_type = signature_dyn::KeyType::Ed25519;
+    let mb_hash_function = selfhash::MBHashFunction::blake3(mbx::Base::Base64Url);
+
+    let did = wallet
+        .create_did(hostname, None, None, key_type, &mb_hash_function)
+        .expect("pass");
+    for _ in 1..version_count {
+        wallet.update_did(&did, key_type).expect("pass");
+    }
+
+    let did_document_v: Vec<DIDDocument> = wallet
+        .controlled_did(&did)
+        .expect("pass")
+        .microledger()
+        .view()
+        .select_did_documents(None, None)
+        .1
+        .cloned()
+        .collect();
+    assert_eq!(did_document_v.len(), version_count);
+
+    let did_document_jcs_v: Vec<String> = did_document_v
+        .iter()
+        .map(|did_document| did_document.serialize_canonically().expect("pass"))
+        .collect();
+
+    (did_document_v, did_document_jcs_v)
+}
+
+/// Check that the stored DID doc records for the microledger's DID are exactly the given DID documents, with
+/// correctly chained did_documents_jsonl_octet_length values.
+async fn check_stored_microledger(
+    did_doc_storage: &DIDDocStoragePostgres,
+    did_document_v: &[DIDDocument],
+    did_document_jcs_v: &[String],
+) {
+    let mut did_doc_record_v = did_doc_storage
+        .get_did_doc_records(
+            None,
+            &DIDDocRecordFilter {
+                did_o: Some(did_document_v[0].did.to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("pass");
+    did_doc_record_v.sort_by_key(|did_doc_record| did_doc_record.version_id);
+    assert_eq!(did_doc_record_v.len(), did_document_v.len());
+    let mut did_documents_jsonl_octet_length = 0i64;
+    for ((did_doc_record, did_document), did_document_jcs) in did_doc_record_v
+        .iter()
+        .zip(did_document_v.iter())
+        .zip(did_document_jcs_v.iter())
+    {
+        did_documents_jsonl_octet_length += did_document_jcs.len() as i64 + 1;
+        assert_eq!(did_doc_record.version_id, did_document.version_id as i64);
+        assert_eq!(did_doc_record.self_hash, did_document.self_hash.as_str());
+        assert_eq!(did_doc_record.did_document_jcs, *did_document_jcs);
+        assert_eq!(
+            did_doc_record.did_documents_jsonl_octet_length,
+            did_documents_jsonl_octet_length
+        );
+    }
+}
+
+async fn open_did_doc_storage(database_url: &str) -> DIDDocStoragePostgres {
+    let pg_pool = sqlx::postgres::PgPoolOptions::new()
+        .max_connections(4)
+        .connect(database_url)
+        .await
+        .expect("pass");
+    DIDDocStoragePostgres::open_and_run_migrations(pg_pool)
+        .await
+        .expect("pass")
+}
+
+/// Checks that DIDDocStoragePostgres::add_did_documents (which does a single batch insert) chains
+/// did_documents_jsonl_octet_length correctly, including when some of the DID documents are already present,
+/// and that it indexes each DID document's keys.
+#[tokio::test]
+async fn test_did_doc_storage_postgres_batch_insert() {
+    let did_doc_storage =
+        open_did_doc_storage("postgres:///test_did_doc_storage_postgres_batch_insert").await;
+
+    // Each DID is newly generated, so these don't collide with anything already in the database.
+    let (did_document_v, did_document_jcs_v) = build_microledger(10);
+    let did_document_jcs_v_ref = did_document_jcs_v
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+
+    // Insert all the DID documents in a single batch.
+    let mut transaction_b = did_doc_storage.begin_transaction().await.expect("pass");
+    did_doc_storage
+        .add_did_documents(
+            Some(transaction_b.as_mut()),
+            &did_document_jcs_v_ref,
+            &did_document_v,
+        )
+        .await
+        .expect("pass");
+    transaction_b.commit().await.expect("pass");
+    check_stored_microledger(&did_doc_storage, &did_document_v, &did_document_jcs_v).await;
+
+    // Batch insert a microledger in two overlapping parts; the already-present DID documents must be skipped
+    // without throwing off did_documents_jsonl_octet_length.
+    let (did_document_v, did_document_jcs_v) = build_microledger(10);
+    let did_document_jcs_v_ref = did_document_jcs_v
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    did_doc_storage
+        .add_did_documents(None, &did_document_jcs_v_ref[..4], &did_document_v[..4])
+        .await
+        .expect("pass");
+    check_stored_microledger(
+        &did_doc_storage,
+        &did_document_v[..4],
+        &did_document_jcs_v[..4],
+    )
+    .await;
+    did_doc_storage
+        .add_did_documents(None, &did_document_jcs_v_ref, &did_document_v)
+        .await
+        .expect("pass");
+    check_stored_microledger(&did_doc_storage, &did_document_v, &did_document_jcs_v).await;
+
+    // Each DID document's keys are indexed.
+    for did_document in did_document_v.iter() {
+        for did_key_record in
+            did_webplus_doc_store::DIDKeyRecord::for_did_document(did_document).expect("pass")
+        {
+            assert!(
+                did_doc_storage
+                    .get_did_key_records(None, did_key_record.key.as_str())
+                    .await
+                    .expect("pass")
+                    .contains(&did_key_record)
+            );
+        }
+    }
+}
+
+/// Benchmark of DIDDocStoragePostgres::add_did_documents (which does a single batch insert) against inserting
+/// the same number of DID documents one at a time via add_did_document, each within a single transaction.
+/// The correctness of the batch insert is checked by test_did_doc_storage_postgres_batch_insert.
+// NOTE: This test is ignored because it's a benchmark.  Like test_did_doc_storage_postgres_batch_insert, it
+// requires a running PostgreSQL server having a database named test_did_doc_storage_postgres_batch_insert.
+// Run it via
+//     cargo test -p did-webplus-doc-storage-postgres --release -- --ignored --nocapture
+#[tokio::test]
+#[ignore]
+async fn test_did_doc_storage_postgres_batch_insert_benchmark() {
+    const VERSION_COUNT: usize = 1000;
+
+    let did_doc_storage =
+        open_did_doc_storage("postgres:///test_did_doc_storage_postgres_batch_insert").await;
+
+    // Each DID is newly generated, so these don't collide with anything already in the database.
+    let (did_document_v, did_document_jcs_v) = build_microledger(VERSION_COUNT);
+    let (batch_did_document_v, batch_did_document_jcs_v) = build_microledger(VERSION_COUNT);
+
+    // Insert one DID document at a time.
+    let row_by_row_duration = {
+        let time_start = std::time::Instant::now();
+        let mut transaction_b = did_doc_storage.begin_transaction().await.expect("pass");
+        for (did_document, did_document_jcs) in did_document_v.iter().zip(did_document_jcs_v.iter())
+        {
+            did_doc_storage
+                .add_did_document(Some(transaction_b.as_mut()), did_document, did_document_jcs)
+                .await
+                .expect("pass");
+        }
+        transaction_b.commit().await.expect("pass");
+        time_start.elapsed()
+    };
+
+    // Insert all the DID documents in a single batch.
+    let batch_duration = {
+        let batch_did_document_jcs_v = batch_did_document_jcs_v
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        let time_start = std::time::Instant::now();
+        let mut transaction_b = did_doc_storage.begin_transaction().await.expect("pass");
+        did_doc_storage
+            .add_did_documents(
+                Some(transaction_b.as_mut()),
+                &batch_did_document_jcs_v,
+                &batch_did_document_v,
+            )
+            .await
+            .expect("pass");
+        transaction_b.commit().await.expect("pass");
+        time_start.elapsed()
+    };
+
+    println!(
+        "inserted {} DID documents: one at a time took {:?}, batch insert took {:?} ({:.1}x speedup)",
+        VERSION_COUNT,
+        row_by_row_duration,
+        batch_duration,
+        row_by_row_duration.as_secs_f64() / batch_duration.as_secs_f64()
+    );
+}
@@ -0,0 +1,16 @@
+-- This is a reverse index from keys to the DID documents that contain them, and is maintained on insert
+-- into did_document_records.  See did_webplus_doc_store::DIDKeyRecord.
+CREATE TABLE did_key_records (
+    -- An MBPubKey, or for a hashed update key, an MBHash.
+    key TEXT NOT NULL,
+    did TEXT NOT NULL,
+    version_id BIGINT NOT NULL,
+    self_hash TEXT NOT NULL REFERENCES did_document_records(self_hash) ON DELETE CASCADE,
+    -- The integer value of the KeyPurposeFlags of the verification method(s) having this key.
+    key_purpose_flags INTEGER NOT NULL,
+    -- True iff the key occurs (directly or hashed) in the DID document's update rules.
+    is_update_key BOOLEAN NOT NULL,
+
+    PRIMARY KEY (key, self_hash)
+);
+CREATE INDEX did_key_records_self_hash_idx ON did_key_records(self_hash);
@@ -1,5 +1,7 @@
-use did_webplus_core::{DIDDocument, DIDStr};
-use did_webplus_doc_store::{DIDDocRecord, DIDDocRecordFilter, Error, Result};
+use did_webplus_core::{DID, DIDDocument, DIDStr, KeyPurposeFlags};
+use did_webplus_doc_store::{
+    DIDDocRecord, DIDDocRecordFilter, DIDKeyRecord, Error, Result, parse_did_document,
+};
 use sqlx::SqlitePool;
 
 #[derive(Clone)]
@@ -18,7 +20,9 @@ impl DIDDocStorageSQLite {
                 .into(),
             )
         })?;
-        Ok(Self { sqlite_pool })
+        let did_doc_storage = Self { sqlite_pool };
+        did_doc_storage.backfill_did_key_records().await?;
+        Ok(did_doc_storage)
     }
     /// If synchronous_mode_o is None, default to "FULL".  Otherwise, the synchronous mode must be
     /// one of Some("OFF"), Some("NORMAL"), Some("FULL"), Some("EXTRA").
@@ -63,6 +67,78 @@ impl DIDDocStorageSQLite {
 
         Self::open_and_run_migrations(sqlite_pool).await
     }
+    /// Populate did_key_records for the DID documents that have no DIDKeyRecord-s, e.g. ones that were
+    /// stored before did_key_records existed.  Note that this re-checks DID documents having no keys at all
+    /// (e.g. those of deactivated DIDs) each time, but there are relatively few of them.
+    async fn backfill_did_key_records(&self) -> Result<()> {
+        let did_document_jcs_v = sqlx::query_scalar::<_, String>(
+            r#"
+                SELECT did_document_jcs
+                FROM did_document_records
+                WHERE self_hash NOT IN (SELECT self_hash FROM did_key_records)
+            "#,
+        )
+        .fetch_all(&self.sqlite_pool)
+        .await?;
+        if did_document_jcs_v.is_empty() {
+            return Ok(());
+        }
+        let mut transaction = self.sqlite_pool.begin().await?;
+        for did_document_jcs in did_document_jcs_v.iter() {
+            let did_document = parse_did_document(did_document_jcs)?;
+            self.add_did_key_records(
+                Some(&mut transaction as &mut dyn storage_traits::TransactionDynT),
+                &did_document,
+            )
+            .await?;
+        }
+        transaction.commit().await?;
+        tracing::debug!(
+            "backfilled did_key_records for {} DID document(s)",
+            did_document_jcs_v.len()
+        );
+        Ok(())
+    }
+    /// Add the DIDKeyRecord-s for the given DID document, which must already be present in
+    /// did_document_records (otherwise nothing is added).
+    async fn add_did_key_records(
+        &self,
+        mut transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
+        did_document: &DIDDocument,
+    ) -> Result<()> {
+        for did_key_record in DIDKeyRecord::for_did_document(did_document)? {
+            // The DID document row might not have been inserted, because "ON CONFLICT DO NOTHING" on
+            // did_document_records also applies to its other UNIQUE constraints.
+            let query = sqlx::query(
+                r#"
+                    INSERT INTO did_key_records(key, did, version_id, self_hash, key_purpose_flags, is_update_key)
+                    SELECT $1, $2, $3, $4, $5, $6
+                    WHERE EXISTS (SELECT 1 FROM did_document_records WHERE self_hash = $4)
+                    ON CONFLICT DO NOTHING
+                "#,
+            )
+            .bind(did_key_record.key.as_str())
+            .bind(did_key_record.did.as_str())
+            .bind(did_key_record.version_id)
+            .bind(did_key_record.self_hash.as_str())
+            .bind(did_key_record.key_purpose_flags.integer_value() as i32)
+            .bind(did_key_record.is_update_key);
+            if let Some(transaction) = transaction_o.as_mut() {
+                query
+                    .execute(
+                        transaction
+                            .as_any_mut()
+                            .downcast_mut::<sqlx::Transaction<'static, sqlx::Sqlite>>()
+                            .unwrap()
+                            .as_mut(),
+                    )
+                    .await?;
+            } else {
+                query.execute(&self.sqlite_pool).await?;
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
@@ -70,7 +146,7 @@ impl DIDDocStorageSQLite {
 impl did_webplus_doc_store::DIDDocStorage for DIDDocStorageSQLite {
     async fn add_did_document(
         &self,
-        transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
+        mut transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
         did_document: &DIDDocument,
         did_document_jcs: &str,
     ) -> Result<()> {
@@ -115,7 +191,7 @@ impl did_webplus_doc_store::DIDDocStorage for DIDDocStorageSQLite {
             self_hash_str,
             did_document_jcs,
         );
-        if let Some(transaction) = transaction_o {
+        if let Some(transaction) = transaction_o.as_mut() {
             query
                 .execute(
                     transaction
@@ -128,6 +204,13 @@ impl did_webplus_doc_store::DIDDocStorage for DIDDocStorageSQLite {
         } else {
             query.execute(&self.sqlite_pool).await?;
         }
+        self.add_did_key_records(
+            transaction_o
+                .as_mut()
+                .map(|t| &mut **t as &mut dyn storage_traits::TransactionDynT),
+            did_document,
+        )
+        .await?;
         Ok(())
     }
     async fn add_did_documents(
@@ -142,65 +225,14 @@ impl did_webplus_doc_store::DIDDocStorage for DIDDocStorageSQLite {
         for (&did_document_jcs, did_document) in
             did_document_jcs_v.iter().zip(did_document_v.iter())
         {
-            // self.add_did_document(transaction_o.as_deref_mut(), did_document, did_document_jcs)
-            //     .await?;
-
-            // TEMP HACK -- should just call add_did_document, but there's some compiler error regarding lifetimes of transaction_o
-
-            use selfhash::HashRefT;
-            assert!(
-                !did_document.self_hash.is_placeholder(),
-                "programmer error: self_hash is expected to be present on a valid DID document"
-            );
-            let did_str = did_document.did.as_str();
-            let version_id = did_document.version_id as i64;
-            let valid_from = did_document
-                .valid_from()
-                .map_err(|e| Error::InvalidDIDDocument(e.into()))?;
-            let self_hash_str = did_document.self_hash.as_str();
-            // Regarding "ON CONFLICT DO NOTHING", a conflict will only happen when the self_hash already exists,
-            // and that means that the DID document is verifiably already present in the database.
-            let query = sqlx::query!(
-                r#"
-                INSERT INTO did_document_records(did, version_id, valid_from, self_hash, did_documents_jsonl_octet_length, did_document_jcs)
-                VALUES (
-                    $1,
-                    $2,
-                    $3,
-                    $4,
-                    COALESCE(
-                        (
-                            SELECT did_documents_jsonl_octet_length
-                            FROM did_document_records
-                            WHERE did = $1
-                            ORDER BY version_id DESC
-                            LIMIT 1
-                        ),
-                        0
-                    ) + OCTET_LENGTH($5) + 1,
-                    $5
-                )
-                ON CONFLICT DO NOTHING
-            "#,
-                did_str,
-                version_id,
-                valid_from,
-                self_hash_str,
+            self.add_did_document(
+                transaction_o
+                    .as_deref_mut()
+                    .map(|t| t as &mut dyn storage_traits::TransactionDynT),
+                did_document,
                 did_document_jcs,
-            );
-            if let Some(transaction) = &mut transaction_o {
-                query
-                    .execute(
-                        transaction
-                            .as_any_mut()
-                            .downcast_mut::<sqlx::Transaction<'static, sqlx::Sqlite>>()
-                            .unwrap()
-                            .as_mut(),
-                    )
-                    .await?;
-            } else {
-                query.execute(&self.sqlite_pool).await?;
-            }
+            )
+            .await?;
         }
         Ok(())
     }
@@ -352,6 +384,88 @@ impl did_webplus_doc_store::DIDDocStorage for DIDDocStorageSQLite {
         .collect::<Result<Vec<_>>>()?;
         Ok(did_doc_record_v)
     }
+    async fn get_dids(
+        &self,
+        transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
+    ) -> Result<Vec<DID>> {
+        let query = sqlx::query_scalar::<_, String>(
+            "SELECT DISTINCT did FROM did_document_records ORDER BY did",
+        );
+        let did_string_v = if let Some(transaction) = transaction_o {
+            query
+                .fetch_all(
+                    transaction
+                        .as_any_mut()
+                        .downcast_mut::<sqlx::Transaction<'static, sqlx::Sqlite>>()
+                        .unwrap()
+                        .as_mut(),
+                )
+                .await?
+        } else {
+            query.fetch_all(&self.sqlite_pool).await?
+        };
+        let did_v = did_string_v
+            .into_iter()
+            .map(DID::try_from)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(did_v)
+    }
+    async fn get_did_key_records(
+        &self,
+        transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
+        key: &str,
+    ) -> Result<Vec<DIDKeyRecord>> {
+        let query = sqlx::query_as::<_, (String, String, i64, String, i32, bool)>(
+            r#"
+                SELECT key, did, version_id, self_hash, key_purpose_flags, is_update_key
+                FROM did_key_records
+                WHERE key = $1
+                ORDER BY did, version_id
+            "#,
+        )
+        .bind(key);
+        let row_v = if let Some(transaction) = transaction_o {
+            query
+                .fetch_all(
+                    transaction
+                        .as_any_mut()
+                        .downcast_mut::<sqlx::Transaction<'static, sqlx::Sqlite>>()
+                        .unwrap()
+                        .as_mut(),
+                )
+                .await?
+        } else {
+            query.fetch_all(&self.sqlite_pool).await?
+        };
+        row_v
+            .into_iter()
+            .map(
+                |(key, did, version_id, self_hash, key_purpose_flags, is_update_key)| {
+                    let key_purpose_flags = u8::try_from(key_purpose_flags)
+                        .map_err(|_| ())
+                        .and_then(|x| KeyPurposeFlags::try_from(x).map_err(|_| ()))
+                        .map_err(|_| {
+                            Error::RecordCorruption(
+                                format!(
+                                    "invalid key_purpose_flags {} in did_key_records",
+                                    key_purpose_flags
+                                )
+                                .into(),
+                                self_hash.clone().into(),
+                            )
+                        })?;
+                    Ok(DIDKeyRecord {
+                        key,
+                        did,
+                        version_id,
+                        self_hash,
+                        key_purpose_flags,
+                        is_update_key,
+                    })
+                },
+            )
+            .collect()
+    }
     async fn get_did_doc_records_for_did_documents_jsonl_range(
         &self,
         transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
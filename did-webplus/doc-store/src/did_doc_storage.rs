@@ -1,5 +1,5 @@
-use crate::{DIDDocRecord, DIDDocRecordFilter, Result};
-use did_webplus_core::{DIDDocument, DIDStr};
+use crate::{DIDDocRecord, DIDDocRecordFilter, DIDKeyRecord, Result, parse_did_document};
+use did_webplus_core::{DID, DIDDocument, DIDStr};
 
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
@@ -14,7 +14,11 @@ pub trait DIDDocStorage: Send + storage_traits::StorageDynT + Sync + 'static {
         did_document: &DIDDocument,
         did_document_jcs: &str,
     ) -> Result<()>;
-    // TEMP HACK
+    /// Attempt to add a sequence of DID documents to the store in a single batch.  The DID documents must
+    /// belong to the same DID, be in order of versionId, and the first must directly follow the latest DID
+    /// document for that DID already in the store (or be its root DID document).  DID documents that are
+    /// already present (i.e. having the same self-hash) are skipped, so that re-adding is idempotent.  Like
+    /// add_did_document, this doesn't validate the DID documents; see DIDDocStore::validate_and_add_did_docs.
     async fn add_did_documents(
         &self,
         transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
@@ -50,6 +54,47 @@ pub trait DIDDocStorage: Send + storage_traits::StorageDynT + Sync + 'static {
         transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
         did_doc_record_filter: &DIDDocRecordFilter,
     ) -> Result<Vec<DIDDocRecord>>;
+    /// Get the distinct DIDs having DIDDocRecord-s in the store, in sorted order.  The default implementation
+    /// uses get_did_doc_records, and so reads every DIDDocRecord; implementations should override this with
+    /// something more efficient.
+    async fn get_dids(
+        &self,
+        transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
+    ) -> Result<Vec<DID>> {
+        let did_doc_record_v = self
+            .get_did_doc_records(transaction_o, &DIDDocRecordFilter::default())
+            .await?;
+        let mut did_v = did_doc_record_v
+            .into_iter()
+            .map(|did_doc_record| DID::try_from(did_doc_record.did))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        did_v.sort();
+        did_v.dedup();
+        Ok(did_v)
+    }
+    /// Get the DIDKeyRecord-s for the given key (an MBPubKey, or an MBHash for a hashed update key), i.e. the
+    /// DID documents that contain it, ordered by DID and then versionId.  The default implementation scans
+    /// every DID document in the store; implementations should override this with an index lookup.
+    async fn get_did_key_records(
+        &self,
+        transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
+        key: &str,
+    ) -> Result<Vec<DIDKeyRecord>> {
+        let did_doc_record_v = self
+            .get_did_doc_records(transaction_o, &DIDDocRecordFilter::default())
+            .await?;
+        let mut did_key_record_v = Vec::new();
+        for did_doc_record in did_doc_record_v.iter() {
+            let did_document = parse_did_document(&did_doc_record.did_document_jcs)?;
+            did_key_record_v.extend(
+                DIDKeyRecord::for_did_document(&did_document)?
+                    .into_iter()
+                    .filter(|did_key_record| did_key_record.key == key),
+            );
+        }
+        did_key_record_v.sort_by(|a, b| (&a.did, a.version_id).cmp(&(&b.did, b.version_id)));
+        Ok(did_key_record_v)
+    }
     /// Get the DIDDocRecord-s whose DID documents' place in the did-documents.jsonl file for the given DID
     /// overlap with the specified range of bytes.  If None is provided for either range parameter, then it
     /// means "unbounded" In particular, if range_begin_inclusive_o is None, then the range starts at byte 0,
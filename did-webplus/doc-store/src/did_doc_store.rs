@@ -1,6 +1,14 @@
-use crate::{DIDDocRecord, DIDDocRecordFilter, DIDDocStorage, Result, parse_did_document};
-use did_webplus_core::{DIDDocument, DIDStr};
-use std::sync::Arc;
+use crate::{
+    DIDDocRecord, DIDDocRecordFilter, DIDDocStorage, DIDDocStoreAuditIssue,
+    DIDDocStoreAuditIssueKind, DIDDocStoreAuditReport, DIDKeyRecord, Error, Result,
+    parse_did_document,
+};
+use did_webplus_core::{DID, DIDDocument, DIDStr};
+use std::{
+    num::{NonZeroU64, NonZeroUsize},
+    sync::Arc,
+};
+use storage_traits::StorageDynT;
 
 #[derive(Clone)]
 pub struct DIDDocStore {
@@ -182,6 +190,388 @@ impl DIDDocStore {
             )
             .await
     }
+    /// Get all DIDDocRecord-s in the store, subject to the given filter.
+    pub async fn get_did_doc_records(
+        &self,
+        transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
+        did_doc_record_filter: &DIDDocRecordFilter,
+    ) -> Result<Vec<DIDDocRecord>> {
+        self.did_doc_storage_a
+            .get_did_doc_records(transaction_o, did_doc_record_filter)
+            .await
+    }
+    /// Get the distinct DIDs having DID documents in the store, in sorted order.
+    pub async fn get_dids(
+        &self,
+        transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
+    ) -> Result<Vec<DID>> {
+        self.did_doc_storage_a.get_dids(transaction_o).await
+    }
+    /// Validate and add the DID documents in the given did-documents.jsonl content (i.e. the newline-separated
+    /// JCS-serialized DID documents of a single DID, in order, starting at any versionId) to the store.  DID
+    /// documents already in the store are skipped, after checking that the content agrees with the store,
+    /// so importing is idempotent.  The remaining DID documents are validated and added in batches of at most
+    /// batch_size, each in its own transaction, so that arbitrarily long microledgers don't produce
+    /// arbitrarily large transactions; if a batch fails, the previously imported batches remain.  Returns
+    /// the number of DID documents that were added.
+    pub async fn import_did_documents_jsonl(
+        &self,
+        did_documents_jsonl: &str,
+        batch_size: NonZeroUsize,
+    ) -> Result<usize> {
+        let did_document_jcs_v = did_documents_jsonl
+            .split('\n')
+            .filter(|did_document_jcs| !did_document_jcs.is_empty())
+            .collect::<Vec<_>>();
+        if did_document_jcs_v.is_empty() {
+            return Ok(0);
+        }
+        let did_document_v = did_document_jcs_v
+            .iter()
+            .map(|did_document_jcs| parse_did_document(did_document_jcs))
+            .collect::<Result<Vec<_>>>()?;
+        let did = did_document_v[0].did.clone();
+        if let Some(did_document) = did_document_v
+            .iter()
+            .find(|did_document| did_document.did != did)
+        {
+            return Err(Error::InvalidDIDDocument(
+                did_webplus_core::Error::Malformed(
+                    format!(
+                        "did-documents.jsonl content must be for a single DID, but contains DID documents for both {} and {}",
+                        did, did_document.did
+                    )
+                    .into(),
+                ),
+            ));
+        }
+
+        // Determine which DID documents are already present, and check that the content agrees with the store.
+        let mut prev_did_document_o = None;
+        let first_version_id = did_document_v[0].version_id;
+        let new_did_document_index_begin = match self
+            .get_latest_known_did_doc_record(None, &did)
+            .await?
+        {
+            None => 0,
+            Some(latest_did_doc_record) => {
+                let latest_version_id = latest_did_doc_record.version_id as u32;
+                if latest_version_id < first_version_id {
+                    // The first DID document must directly follow the latest one, which is checked below.
+                    prev_did_document_o =
+                        Some(parse_did_document(&latest_did_doc_record.did_document_jcs)?);
+                    0
+                } else {
+                    let overlap_index = (latest_version_id - first_version_id) as usize;
+                    let overlap_index = overlap_index.min(did_document_v.len() - 1);
+                    let overlap_did_document = &did_document_v[overlap_index];
+                    let stored_did_doc_record = self
+                        .get_did_doc_record_with_version_id(
+                            None,
+                            &did,
+                            overlap_did_document.version_id,
+                        )
+                        .await?
+                        .ok_or_else(|| {
+                            Error::RecordCorruption(
+                                format!(
+                                    "DID doc store is missing DID document versionId {} for {}, but has later versionId {}",
+                                    overlap_did_document.version_id, did, latest_version_id
+                                )
+                                .into(),
+                                latest_did_doc_record.self_hash.clone().into(),
+                            )
+                        })?;
+                    if stored_did_doc_record.self_hash.as_str()
+                        != overlap_did_document.self_hash.as_str()
+                    {
+                        return Err(Error::AlreadyExists(
+                            format!(
+                                "DID document versionId {} for {} has self-hash {}, which conflicts with the stored self-hash {}",
+                                overlap_did_document.version_id,
+                                did,
+                                overlap_did_document.self_hash,
+                                stored_did_doc_record.self_hash
+                            )
+                            .into(),
+                        ));
+                    }
+                    prev_did_document_o = Some(overlap_did_document.clone());
+                    overlap_index + 1
+                }
+            }
+        };
+        if let Some(first_new_did_document) = did_document_v.get(new_did_document_index_begin) {
+            let expected_version_id = prev_did_document_o
+                .as_ref()
+                .map(|prev_did_document| prev_did_document.version_id + 1)
+                .unwrap_or(0);
+            if first_new_did_document.version_id != expected_version_id {
+                return Err(Error::NotFound(
+                    format!(
+                        "can't import DID document versionId {} for {} because its predecessor(s) are not present (expected versionId {})",
+                        first_new_did_document.version_id, did, expected_version_id
+                    )
+                    .into(),
+                ));
+            }
+        }
+
+        let mut added_count = 0;
+        for (did_document_jcs_chunk, did_document_chunk) in did_document_jcs_v
+            [new_did_document_index_begin..]
+            .chunks(batch_size.get())
+            .zip(did_document_v[new_did_document_index_begin..].chunks(batch_size.get()))
+        {
+            let mut transaction_b = self.begin_transaction().await?;
+            self.validate_and_add_did_docs(
+                Some(transaction_b.as_mut()),
+                did_document_jcs_chunk,
+                did_document_chunk,
+                prev_did_document_o.as_ref(),
+            )
+            .await?;
+            transaction_b.commit().await?;
+            added_count += did_document_chunk.len();
+            prev_did_document_o = did_document_chunk.last().cloned();
+        }
+        tracing::debug!("imported {} new DID document(s) for {}", added_count, did);
+        Ok(added_count)
+    }
+    /// Export the full did-documents.jsonl content for the given DID, which can be imported into another
+    /// store using import_did_documents_jsonl.  Returns the empty string if the DID isn't in the store.
+    pub async fn export_did_documents_jsonl(
+        &self,
+        transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
+        did: &DIDStr,
+    ) -> Result<String> {
+        self.get_did_documents_jsonl_range(transaction_o, did, None, None)
+            .await
+    }
+    /// The default maximum number of bytes of did-documents.jsonl content that copy_to and audit read at a
+    /// time.
+    pub const DEFAULT_CHUNK_OCTET_LENGTH: NonZeroU64 = NonZeroU64::new(1 << 20).unwrap();
+
+    /// Copy every DID in this store into dest_did_doc_store, validating each DID document as it's added (so
+    /// that migrating from an untrusted or damaged store can't propagate invalid DID documents).  The stores
+    /// may use different DIDDocStorage backends.  DID documents already present in dest_did_doc_store are
+    /// skipped, so copying is idempotent and can be used to incrementally sync.  Returns the number of DIDs
+    /// and the number of DID documents that were added to dest_did_doc_store.
+    pub async fn copy_to(
+        &self,
+        dest_did_doc_store: &DIDDocStore,
+        batch_size: NonZeroUsize,
+    ) -> Result<(usize, usize)> {
+        self.copy_to_in_chunks(
+            dest_did_doc_store,
+            batch_size,
+            Self::DEFAULT_CHUNK_OCTET_LENGTH,
+        )
+        .await
+    }
+    /// This is copy_to, but reading the DID documents of each DID in chunks of at most chunk_octet_length bytes
+    /// of did-documents.jsonl content (plus the remainder of a DID document that straddles a chunk boundary),
+    /// so that arbitrarily long microledgers aren't read into memory all at once.
+    pub async fn copy_to_in_chunks(
+        &self,
+        dest_did_doc_store: &DIDDocStore,
+        batch_size: NonZeroUsize,
+        chunk_octet_length: NonZeroU64,
+    ) -> Result<(usize, usize)> {
+        let did_v = self.get_dids(None).await?;
+        let mut copied_did_count = 0;
+        let mut copied_did_document_count = 0;
+        for did in did_v.iter() {
+            let mut added_count = 0;
+            let mut range_begin_inclusive = 0u64;
+            loop {
+                let did_doc_record_v = self
+                    .get_did_doc_record_chunk(did, range_begin_inclusive, chunk_octet_length)
+                    .await?;
+                let Some(range_end_exclusive) = did_doc_record_chunk_end(&did_doc_record_v) else {
+                    break;
+                };
+                range_begin_inclusive = range_end_exclusive;
+                // Whole DID documents are imported, so each chunk is valid did-documents.jsonl content.
+                let mut did_documents_jsonl = String::new();
+                for did_doc_record in did_doc_record_v.iter() {
+                    did_documents_jsonl.push_str(&did_doc_record.did_document_jcs);
+                    did_documents_jsonl.push('\n');
+                }
+                added_count += dest_did_doc_store
+                    .import_did_documents_jsonl(&did_documents_jsonl, batch_size)
+                    .await?;
+            }
+            if added_count > 0 {
+                copied_did_count += 1;
+                copied_did_document_count += added_count;
+            }
+        }
+        Ok((copied_did_count, copied_did_document_count))
+    }
+    /// Get the DIDDocRecord-s of the given DID whose DID documents overlap the chunk of did-documents.jsonl
+    /// content beginning at range_begin_inclusive and having at most chunk_octet_length bytes.  Whole
+    /// DIDDocRecord-s are returned (rather than a range of did-documents.jsonl content), so a chunk never
+    /// splits a DID document; use did_doc_record_chunk_end to get where the next chunk begins.
+    async fn get_did_doc_record_chunk(
+        &self,
+        did: &DIDStr,
+        range_begin_inclusive: u64,
+        chunk_octet_length: NonZeroU64,
+    ) -> Result<Vec<DIDDocRecord>> {
+        // The storage backends use i64 octet lengths.
+        let range_end_exclusive = range_begin_inclusive
+            .saturating_add(chunk_octet_length.get())
+            .min(i64::MAX as u64);
+        self.did_doc_storage_a
+            .get_did_doc_records_for_did_documents_jsonl_range(
+                None,
+                did,
+                Some(range_begin_inclusive),
+                Some(range_end_exclusive),
+            )
+            .await
+    }
+    /// Re-verify every microledger in the store from its root DID document, independently of how the records
+    /// were originally validated.  This checks each DIDDocRecord's internal consistency (see
+    /// DIDDocRecord::validate_consistency), each DID document's self-hashes, signatures, and prev link against
+    /// its predecessor, that the did_documents_jsonl_octet_length values are the cumulative sums of the DID
+    /// documents' lengths, and that validFrom is strictly increasing.  Records that fail these checks are
+    /// reported as corrupted, and records that can't be reached from a valid root DID document (because of
+    /// a missing or corrupted predecessor) are reported as orphaned.  This only returns an error if the store
+    /// itself can't be read.
+    pub async fn audit(&self) -> Result<DIDDocStoreAuditReport> {
+        self.audit_in_chunks(Self::DEFAULT_CHUNK_OCTET_LENGTH).await
+    }
+    /// This is audit, but reading the DIDDocRecord-s of each DID in chunks of at most chunk_octet_length bytes
+    /// of did-documents.jsonl content (plus the remainder of a DID document that straddles a chunk boundary),
+    /// so that arbitrarily long microledgers aren't read into memory all at once.  Chunks are located using
+    /// the stored did_documents_jsonl_octet_length values, which may themselves be corrupted, so any records
+    /// past the last one found in a chunk are then read individually by versionId.
+    pub async fn audit_in_chunks(
+        &self,
+        chunk_octet_length: NonZeroU64,
+    ) -> Result<DIDDocStoreAuditReport> {
+        let did_v = self.get_dids(None).await?;
+        let mut audit_report = DIDDocStoreAuditReport {
+            did_count: did_v.len(),
+            ..Default::default()
+        };
+        for did in did_v.iter() {
+            let mut did_doc_record_auditor = DIDDocRecordAuditor::default();
+            let mut range_begin_inclusive = 0u64;
+            loop {
+                let mut did_doc_record_v = self
+                    .get_did_doc_record_chunk(did, range_begin_inclusive, chunk_octet_length)
+                    .await?;
+                let Some(range_end_exclusive) = did_doc_record_chunk_end(&did_doc_record_v) else {
+                    break;
+                };
+                range_begin_inclusive = range_end_exclusive;
+                did_doc_record_v.sort_by_key(|did_doc_record| did_doc_record.version_id);
+                audit_report.did_doc_record_count += did_doc_record_v.len();
+
+                // Verification is CPU-bound, so treat it like validate_and_add_did_docs does.
+                #[cfg(not(target_arch = "wasm32"))]
+                match tokio::runtime::Handle::try_current() {
+                    Ok(handle)
+                        if handle.runtime_flavor()
+                            == tokio::runtime::RuntimeFlavor::MultiThread =>
+                    {
+                        tokio::task::block_in_place(|| {
+                            did_doc_record_auditor.audit_did_doc_records(did_doc_record_v)
+                        })
+                    }
+                    _ => did_doc_record_auditor.audit_did_doc_records(did_doc_record_v),
+                }
+                #[cfg(target_arch = "wasm32")]
+                did_doc_record_auditor.audit_did_doc_records(did_doc_record_v);
+            }
+            if let Some(latest_did_doc_record) =
+                self.get_latest_known_did_doc_record(None, did).await?
+            {
+                for version_id in
+                    did_doc_record_auditor.next_version_id()..=latest_did_doc_record.version_id
+                {
+                    if let Some(did_doc_record) = self
+                        .get_did_doc_record_with_version_id(None, did, version_id)
+                        .await?
+                    {
+                        audit_report.did_doc_record_count += 1;
+                        did_doc_record_auditor.audit_did_doc_records(vec![did_doc_record]);
+                    }
+                }
+            }
+            audit_report.issue_v.extend(did_doc_record_auditor.issue_v);
+        }
+        Ok(audit_report)
+    }
+    /// Get the DIDKeyRecord-s for the given key (an MBPubKey, or an MBHash for a hashed update key), i.e. the
+    /// DID documents that contain it, ordered by DID and then versionId.
+    pub async fn get_did_key_records(
+        &self,
+        transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
+        key: &str,
+    ) -> Result<Vec<DIDKeyRecord>> {
+        self.did_doc_storage_a
+            .get_did_key_records(transaction_o, key)
+            .await
+    }
+    /// Get the DIDKeyRecord-s for all DID documents that contain the given public key, either as a verification
+    /// method, as an update key, or as a hashed update key.  Since a hashed update key doesn't specify which
+    /// public key it's the hash of, the public key is hashed using each of the given hash functions (see
+    /// default_hashed_update_key_hash_functions) to look them up.  The results are ordered by DID and then
+    /// versionId.
+    pub async fn get_did_key_records_for_pub_key(
+        &self,
+        mut transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
+        pub_key: &mbx::MBPubKeyStr,
+        hash_function_v: &[selfhash::MBHashFunction],
+    ) -> Result<Vec<DIDKeyRecord>> {
+        let mut key_v = vec![pub_key.to_string()];
+        for mb_hash_function in hash_function_v.iter() {
+            let hashed_update_key =
+                did_webplus_core::HashedUpdateKey::from_pub_key(mb_hash_function, pub_key);
+            key_v.push(hashed_update_key.hashed_pub_key().to_string());
+        }
+        key_v.sort();
+        key_v.dedup();
+
+        let mut did_key_record_v = Vec::new();
+        for key in key_v.iter() {
+            did_key_record_v.extend(
+                self.did_doc_storage_a
+                    .get_did_key_records(
+                        transaction_o
+                            .as_mut()
+                            .map(|t| &mut **t as &mut dyn storage_traits::TransactionDynT),
+                        key,
+                    )
+                    .await?,
+            );
+        }
+        did_key_record_v.sort_by(|a, b| (&a.did, a.version_id).cmp(&(&b.did, b.version_id)));
+        Ok(did_key_record_v)
+    }
+    /// Convenience method for looking up a key given as a string.  If it's an MBPubKey, then this uses
+    /// get_did_key_records_for_pub_key with default_hashed_update_key_hash_functions, so that hashed update
+    /// keys are found as well.  Otherwise (e.g. it's the MBHash of a hashed update key), it's looked up as-is.
+    pub async fn find_did_key_records(
+        &self,
+        transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
+        key: &str,
+    ) -> Result<Vec<DIDKeyRecord>> {
+        if let Ok(pub_key) = mbx::MBPubKey::try_from(key) {
+            self.get_did_key_records_for_pub_key(
+                transaction_o,
+                &pub_key,
+                &crate::default_hashed_update_key_hash_functions(),
+            )
+            .await
+        } else {
+            self.get_did_key_records(transaction_o, key).await
+        }
+    }
     pub async fn get_did_documents_jsonl_range(
         &self,
         transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
@@ -300,3 +690,148 @@ impl storage_traits::StorageDynT for DIDDocStore {
         self.did_doc_storage_a.begin_transaction().await
     }
 }
+
+/// Returns the did-documents.jsonl octet offset at which the chunk following the given chunk of DIDDocRecord-s
+/// (see DIDDocStore::get_did_doc_record_chunk) begins, or None if the chunk is empty.  Each DIDDocRecord of a
+/// chunk ends after the chunk's beginning, so this always makes progress.
+fn did_doc_record_chunk_end(did_doc_record_v: &[DIDDocRecord]) -> Option<u64> {
+    did_doc_record_v
+        .iter()
+        .map(|did_doc_record| did_doc_record.did_documents_jsonl_octet_length as u64)
+        .max()
+}
+
+/// Verifies the DIDDocRecord-s of a single DID from the root DID document, a chunk at a time.  See
+/// DIDDocStore::audit.
+#[derive(Default)]
+struct DIDDocRecordAuditor {
+    /// The last DID document that was verified from the root, along with its record.
+    prev_o: Option<(DIDDocRecord, DIDDocument)>,
+    /// Set once the chain from the root is broken; every later record is then orphaned.
+    chain_is_broken: bool,
+    did_documents_jsonl_octet_length: i64,
+    /// The largest versionId of the records audited so far.
+    max_version_id_o: Option<u32>,
+    issue_v: Vec<DIDDocStoreAuditIssue>,
+}
+
+impl DIDDocRecordAuditor {
+    /// Returns the versionId following that of every record audited so far.
+    fn next_version_id(&self) -> u32 {
+        self.max_version_id_o
+            .map(|max_version_id| max_version_id + 1)
+            .unwrap_or(0)
+    }
+    /// Verify the given DIDDocRecord-s, which must be sorted by version_id and follow those of the previous
+    /// call, recording any issues that are found.
+    fn audit_did_doc_records(&mut self, did_doc_record_v: Vec<DIDDocRecord>) {
+        let issue =
+            |did_doc_record: &DIDDocRecord, kind, description: String| DIDDocStoreAuditIssue {
+                did: did_doc_record.did.clone(),
+                version_id: did_doc_record.version_id,
+                self_hash: did_doc_record.self_hash.clone(),
+                kind,
+                description,
+            };
+
+        for did_doc_record in did_doc_record_v.into_iter() {
+            self.max_version_id_o = self.max_version_id_o.max(Some(did_doc_record.version_id));
+            let expected_version_id = self
+                .prev_o
+                .as_ref()
+                .map(|(prev_did_doc_record, _)| prev_did_doc_record.version_id + 1)
+                .unwrap_or(0);
+            if did_doc_record.version_id < expected_version_id {
+                self.issue_v.push(issue(
+                    &did_doc_record,
+                    DIDDocStoreAuditIssueKind::Corrupted,
+                    format!(
+                        "duplicate record for versionId {}",
+                        did_doc_record.version_id
+                    ),
+                ));
+                continue;
+            }
+            if self.chain_is_broken {
+                self.issue_v.push(issue(
+                    &did_doc_record,
+                    DIDDocStoreAuditIssueKind::Orphaned,
+                    "a predecessor DID document is corrupted, so this can't be verified"
+                        .to_string(),
+                ));
+                continue;
+            }
+            if did_doc_record.version_id > expected_version_id {
+                self.issue_v.push(issue(
+                    &did_doc_record,
+                    DIDDocStoreAuditIssueKind::Orphaned,
+                    format!(
+                        "predecessor DID document with versionId {} is missing",
+                        expected_version_id
+                    ),
+                ));
+                self.chain_is_broken = true;
+                continue;
+            }
+
+            if let Err(e) = did_doc_record.validate_consistency() {
+                self.issue_v.push(issue(
+                    &did_doc_record,
+                    DIDDocStoreAuditIssueKind::Corrupted,
+                    e.to_string(),
+                ));
+                self.chain_is_broken = true;
+                continue;
+            }
+            // validate_consistency already parsed and checked the DID document.
+            let did_document = parse_did_document(&did_doc_record.did_document_jcs).unwrap();
+            if let Some((prev_did_doc_record, _)) = self.prev_o.as_ref() {
+                if did_doc_record.valid_from <= prev_did_doc_record.valid_from {
+                    self.issue_v.push(issue(
+                        &did_doc_record,
+                        DIDDocStoreAuditIssueKind::Corrupted,
+                        format!(
+                            "valid_from {} is not after the previous record's valid_from {}",
+                            did_doc_record.valid_from, prev_did_doc_record.valid_from
+                        ),
+                    ));
+                    self.chain_is_broken = true;
+                    continue;
+                }
+            }
+            if let Err(e) = did_document.verify_nonrecursive(
+                self.prev_o
+                    .as_ref()
+                    .map(|(_, prev_did_document)| prev_did_document),
+            ) {
+                self.issue_v.push(issue(
+                    &did_doc_record,
+                    DIDDocStoreAuditIssueKind::Corrupted,
+                    format!("DID document failed verification: {}", e),
+                ));
+                self.chain_is_broken = true;
+                continue;
+            }
+            self.did_documents_jsonl_octet_length +=
+                did_doc_record.did_document_jcs.len() as i64 + 1;
+            if did_doc_record.did_documents_jsonl_octet_length
+                != self.did_documents_jsonl_octet_length
+            {
+                // The DID document itself is valid, so this doesn't break the chain.  Resync to the stored
+                // value so that only this record is reported.
+                self.issue_v.push(issue(
+                    &did_doc_record,
+                    DIDDocStoreAuditIssueKind::Corrupted,
+                    format!(
+                        "did_documents_jsonl_octet_length is {}, but the cumulative length of the DID documents through this one is {}",
+                        did_doc_record.did_documents_jsonl_octet_length,
+                        self.did_documents_jsonl_octet_length
+                    ),
+                ));
+                self.did_documents_jsonl_octet_length =
+                    did_doc_record.did_documents_jsonl_octet_length;
+            }
+            self.prev_o = Some((did_doc_record, did_document));
+        }
+    }
+}
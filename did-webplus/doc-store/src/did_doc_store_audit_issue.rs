@@ -0,0 +1,12 @@
+use crate::DIDDocStoreAuditIssueKind;
+
+/// A problem found with a particular DIDDocRecord by DIDDocStore::audit.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DIDDocStoreAuditIssue {
+    pub did: String,
+    pub version_id: i64,
+    pub self_hash: String,
+    pub kind: DIDDocStoreAuditIssueKind,
+    pub description: String,
+}
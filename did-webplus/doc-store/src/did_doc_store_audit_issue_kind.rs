@@ -0,0 +1,12 @@
+/// The kind of problem found with a DIDDocRecord by DIDDocStore::audit.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DIDDocStoreAuditIssueKind {
+    /// The record is internally inconsistent, or its DID document is invalid with respect to its predecessor
+    /// (e.g. bad self-hash, bad signature, broken prev link, wrong did_documents_jsonl_octet_length, or
+    /// non-increasing validFrom).
+    Corrupted,
+    /// The record can't be verified from the DID's root DID document because a predecessor is missing or
+    /// corrupted.
+    Orphaned,
+}
@@ -0,0 +1,20 @@
+use crate::DIDDocStoreAuditIssue;
+
+/// The result of DIDDocStore::audit.
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DIDDocStoreAuditReport {
+    /// Number of DIDs that were audited.
+    pub did_count: usize,
+    /// Number of DIDDocRecord-s that were audited.
+    pub did_doc_record_count: usize,
+    /// The problems found, in order of DID and then versionId.
+    #[serde(rename = "issues")]
+    pub issue_v: Vec<DIDDocStoreAuditIssue>,
+}
+
+impl DIDDocStoreAuditReport {
+    pub fn has_issues(&self) -> bool {
+        !self.issue_v.is_empty()
+    }
+}
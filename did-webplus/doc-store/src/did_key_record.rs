@@ -0,0 +1,81 @@
+use crate::Result;
+use did_webplus_core::{DIDDocument, KeyPurposeFlags};
+use std::collections::BTreeMap;
+
+/// Records that a key occurs in a particular DID document, either as the public key of a verification method,
+/// or in the DID document's update rules (directly, or hashed).  These form a reverse index from keys to the
+/// DIDs and DID document versions that contain them, e.g. for determining the impact of a compromised key.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DIDKeyRecord {
+    /// The key, which is an MBPubKey, or for a hashed update key, an MBHash.
+    pub key: String,
+    pub did: String,
+    pub version_id: i64,
+    /// The self-hash of the DID document containing the key.
+    pub self_hash: String,
+    /// The purposes of the verification method(s) having this key, which is empty if the key only occurs in
+    /// the update rules.
+    pub key_purpose_flags: KeyPurposeFlags,
+    /// True iff this key occurs in the update rules.
+    pub is_update_key: bool,
+}
+
+impl DIDKeyRecord {
+    /// Produce the DIDKeyRecord-s for the given DID document, one per distinct key, ordered by key.
+    pub fn for_did_document(did_document: &DIDDocument) -> Result<Vec<Self>> {
+        // Maps each key to its (key_purpose_flags, is_update_key).
+        let mut key_m: BTreeMap<String, (KeyPurposeFlags, bool)> = BTreeMap::new();
+        for verification_method in did_document
+            .public_key_material
+            .verification_method_v
+            .iter()
+        {
+            let pub_key = mbx::MBPubKey::try_from(&verification_method.public_key_jwk)?;
+            let key_purpose_flags = did_document
+                .public_key_material
+                .key_purpose_flags_for_key_id_fragment(verification_method.id.fragment());
+            key_m
+                .entry(pub_key.to_string())
+                .or_insert((KeyPurposeFlags::NONE, false))
+                .0 |= key_purpose_flags;
+        }
+        let mut pub_key_v = Vec::new();
+        let mut hashed_pub_key_v = Vec::new();
+        did_document
+            .update_rules
+            .collect_update_keys(&mut pub_key_v, &mut hashed_pub_key_v);
+        for key in pub_key_v.iter().map(|pub_key| pub_key.to_string()).chain(
+            hashed_pub_key_v
+                .iter()
+                .map(|hashed_pub_key| hashed_pub_key.to_string()),
+        ) {
+            key_m.entry(key).or_insert((KeyPurposeFlags::NONE, false)).1 = true;
+        }
+        Ok(key_m
+            .into_iter()
+            .map(|(key, (key_purpose_flags, is_update_key))| Self {
+                key,
+                did: did_document.did.to_string(),
+                version_id: did_document.version_id as i64,
+                self_hash: did_document.self_hash.to_string(),
+                key_purpose_flags,
+                is_update_key,
+            })
+            .collect())
+    }
+}
+
+/// The hash functions used by default to look up hashed update keys for a given public key; see
+/// DIDDocStore::get_did_key_records_for_pub_key.  This covers the hash functions and bases that are
+/// commonly used in update rules.
+pub fn default_hashed_update_key_hash_functions() -> Vec<selfhash::MBHashFunction> {
+    let mut mb_hash_function_v = Vec::new();
+    for base in [mbx::Base::Base64Url, mbx::Base::Base58Btc] {
+        mb_hash_function_v.push(selfhash::MBHashFunction::blake3(base));
+        mb_hash_function_v.push(selfhash::MBHashFunction::sha256(base));
+        mb_hash_function_v.push(selfhash::MBHashFunction::sha512(base));
+        mb_hash_function_v.push(selfhash::MBHashFunction::sha3_256(base));
+    }
+    mb_hash_function_v
+}
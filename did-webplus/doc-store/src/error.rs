@@ -16,6 +16,12 @@ pub enum Error {
     StorageError(Cow<'static, str>),
 }
 
+impl From<storage_traits::Error> for Error {
+    fn from(err: storage_traits::Error) -> Self {
+        Self::StorageError(err.into_inner())
+    }
+}
+
 #[cfg(feature = "sqlx")]
 impl From<sqlx::Error> for Error {
     fn from(err: sqlx::Error) -> Self {
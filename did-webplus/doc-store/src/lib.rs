@@ -2,11 +2,31 @@ mod did_doc_record;
 mod did_doc_record_filter;
 mod did_doc_storage;
 mod did_doc_store;
+mod did_doc_store_audit_issue;
+mod did_doc_store_audit_issue_kind;
+mod did_doc_store_audit_report;
+mod did_key_record;
 mod error;
+#[cfg(not(target_arch = "wasm32"))]
+mod periodic_audit_task;
+mod static_tree;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::periodic_audit_task::PeriodicAuditTask;
 pub use crate::{
-    did_doc_record::DIDDocRecord, did_doc_record_filter::DIDDocRecordFilter,
-    did_doc_storage::DIDDocStorage, did_doc_store::DIDDocStore, error::Error,
+    did_doc_record::DIDDocRecord,
+    did_doc_record_filter::DIDDocRecordFilter,
+    did_doc_storage::DIDDocStorage,
+    did_doc_store::DIDDocStore,
+    did_doc_store_audit_issue::DIDDocStoreAuditIssue,
+    did_doc_store_audit_issue_kind::DIDDocStoreAuditIssueKind,
+    did_doc_store_audit_report::DIDDocStoreAuditReport,
+    did_key_record::{DIDKeyRecord, default_hashed_update_key_hash_functions},
+    error::Error,
+    static_tree::{
+        append_did_document_to_static_tree, export_static_tree, read_static_did_documents_jsonl,
+        static_did_documents_jsonl_path,
+    },
 };
 pub type Result<T> = std::result::Result<T, Error>;
 
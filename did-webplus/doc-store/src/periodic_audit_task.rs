@@ -0,0 +1,59 @@
+use crate::DIDDocStore;
+
+/// A background task that periodically runs DIDDocStore::audit and logs the results, for use by long-running
+/// services such as a VDR or VDG.  Each found issue is logged at error level.  The task is aborted when this
+/// is dropped.
+pub struct PeriodicAuditTask {
+    join_handle: tokio::task::JoinHandle<()>,
+}
+
+impl PeriodicAuditTask {
+    /// The longest interval (in seconds) between audits (100 years), so that scheduling an audit can't
+    /// overflow.  Services should use this to bound their audit interval arguments.
+    pub const MAX_INTERVAL_SECONDS: u64 = 100 * 365 * 24 * 60 * 60;
+
+    /// Spawn the task.  The first audit runs one interval after this is called, so that audits don't
+    /// slow down service startup.  The interval is clamped to MAX_INTERVAL_SECONDS.
+    pub fn spawn(did_doc_store: DIDDocStore, interval: std::time::Duration) -> Self {
+        let interval = interval.min(std::time::Duration::from_secs(Self::MAX_INTERVAL_SECONDS));
+        let join_handle = tokio::task::spawn(async move {
+            let mut audit_interval =
+                tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+            audit_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                audit_interval.tick().await;
+                tracing::info!("starting DID doc store audit");
+                match did_doc_store.audit().await {
+                    Ok(audit_report) => {
+                        for issue in audit_report.issue_v.iter() {
+                            tracing::error!(
+                                "DID doc store audit: {:?} record for {} with versionId {} and selfHash {}: {}",
+                                issue.kind,
+                                issue.did,
+                                issue.version_id,
+                                issue.self_hash,
+                                issue.description
+                            );
+                        }
+                        tracing::info!(
+                            "finished DID doc store audit of {} DID(s) and {} DID doc record(s); found {} issue(s)",
+                            audit_report.did_count,
+                            audit_report.did_doc_record_count,
+                            audit_report.issue_v.len()
+                        );
+                    }
+                    Err(e) => {
+                        tracing::error!("DID doc store audit failed: {}", e);
+                    }
+                }
+            }
+        });
+        Self { join_handle }
+    }
+}
+
+impl Drop for PeriodicAuditTask {
+    fn drop(&mut self) {
+        self.join_handle.abort();
+    }
+}
@@ -0,0 +1,209 @@
+use crate::{DIDDocStore, Error, Result, parse_did_document};
+use did_webplus_core::{DID, DIDDocument, DIDStr};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Returns the path of the did-documents.jsonl file for the given DID within a static-hosting directory
+/// tree rooted at root_dir.  Serving root_dir as the document root of the DID's host (e.g. via S3 or nginx)
+/// makes the file available at the URL that DID resolution fetches.
+pub fn static_did_documents_jsonl_path(root_dir: &Path, did: &DIDStr) -> PathBuf {
+    root_dir.join(did.resolution_path_for_did_documents_jsonl())
+}
+
+/// Read the did-documents.jsonl file for the given DID from the static-hosting directory tree rooted at
+/// root_dir, returning None if the file doesn't exist.
+pub fn read_static_did_documents_jsonl(root_dir: &Path, did: &DIDStr) -> Result<Option<String>> {
+    let path = static_did_documents_jsonl_path(root_dir, did);
+    match std::fs::read_to_string(&path) {
+        Ok(did_documents_jsonl) => Ok(Some(did_documents_jsonl)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(io_error(&path, e)),
+    }
+}
+
+/// Append the given DID document to its did-documents.jsonl file in the static-hosting directory tree rooted
+/// at root_dir.  The DID document must be the root DID document of a DID whose file doesn't yet exist, or
+/// must directly follow the last DID document in the existing file.  This is the static-hosting equivalent
+/// of POST-ing (for the root DID document) or PUT-ing (otherwise) the DID document to a VDR, and so the
+/// DID document is expected to have already been validated.
+pub fn append_did_document_to_static_tree(
+    root_dir: &Path,
+    did_document: &DIDDocument,
+    did_document_jcs: &str,
+) -> Result<()> {
+    let did = did_document.did.as_did_str();
+    let path = static_did_documents_jsonl_path(root_dir, did);
+    let did_documents_jsonl_o = read_static_did_documents_jsonl(root_dir, did)?;
+    let last_did_document_o = did_documents_jsonl_o
+        .as_deref()
+        .and_then(|did_documents_jsonl| did_documents_jsonl.trim_end().rsplit('\n').next())
+        .filter(|last_did_document_jcs| !last_did_document_jcs.is_empty())
+        .map(parse_did_document)
+        .transpose()?;
+    match (
+        last_did_document_o.as_ref(),
+        did_document.prev_did_document_self_hash_o.as_ref(),
+    ) {
+        (None, None) => {}
+        (Some(last_did_document), Some(prev_did_document_self_hash))
+            if last_did_document.self_hash == *prev_did_document_self_hash => {}
+        (Some(last_did_document), _) => {
+            return Err(Error::AlreadyExists(
+                format!(
+                    "{} has DID document versionId {} as its latest, so DID document versionId {} does not directly follow it",
+                    path.display(),
+                    last_did_document.version_id,
+                    did_document.version_id
+                )
+                .into(),
+            ));
+        }
+        (None, Some(_)) => {
+            return Err(Error::NotFound(
+                format!(
+                    "{} does not exist, so non-root DID document versionId {} can't be appended to it",
+                    path.display(),
+                    did_document.version_id
+                )
+                .into(),
+            ));
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| io_error(parent, e))?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| io_error(&path, e))?;
+    // Files are always written with a trailing newline, but tolerate one that is missing it.
+    if did_documents_jsonl_o.is_some_and(|did_documents_jsonl| {
+        !did_documents_jsonl.is_empty() && !did_documents_jsonl.ends_with('\n')
+    }) {
+        file.write_all(b"\n").map_err(|e| io_error(&path, e))?;
+    }
+    file.write_all(did_document_jcs.as_bytes())
+        .and_then(|_| file.write_all(b"\n"))
+        .map_err(|e| io_error(&path, e))?;
+    Ok(())
+}
+
+/// Export every DID in the given DIDDocStore into a static-hosting directory tree rooted at root_dir, i.e.
+/// write each DID's did-documents.jsonl file at the path given by static_did_documents_jsonl_path, so that
+/// the DIDs can be resolved from plain static file hosting instead of a VDR.  Because the path within the
+/// tree doesn't include the host, all exported DIDs must have the same host (hostname and optional port);
+/// if host_o is specified, only DIDs with that host (e.g. "example.com" or "localhost:8085") are exported.
+///
+/// If incremental is true, then existing files are only appended to, and only for DIDs having DID documents
+/// not already present in the file; because did-documents.jsonl is append-only, the length of the existing
+/// file determines which DID documents it already contains.  Otherwise, every file is rewritten.  Returns
+/// the DIDs whose files were written.
+pub async fn export_static_tree(
+    did_doc_store: &DIDDocStore,
+    root_dir: &Path,
+    host_o: Option<&str>,
+    incremental: bool,
+) -> Result<Vec<DID>> {
+    let mut did_v = did_doc_store.get_dids(None).await?;
+    if let Some(host) = host_o {
+        did_v.retain(|did| did_host(did) == host);
+    }
+    if let Some(first_did) = did_v.first() {
+        let first_host = did_host(first_did);
+        if let Some(other_did) = did_v.iter().find(|did| did_host(did) != first_host) {
+            return Err(Error::InternalError(
+                format!(
+                    "can't export DIDs having different hosts ({} and {}) into a single static-hosting directory tree; specify which host to export",
+                    first_host,
+                    did_host(other_did)
+                )
+                .into(),
+            ));
+        }
+    }
+
+    let mut exported_did_v = Vec::new();
+    for did in did_v.into_iter() {
+        let mut did_doc_record_v = did_doc_store.get_all_did_doc_records(None, &did).await?;
+        did_doc_record_v.sort_by_key(|did_doc_record| did_doc_record.version_id);
+        let path = static_did_documents_jsonl_path(root_dir, &did);
+        let existing_octet_length_o = if incremental {
+            match std::fs::metadata(&path) {
+                Ok(metadata) => Some(metadata.len() as i64),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                Err(e) => return Err(io_error(&path, e)),
+            }
+        } else {
+            None
+        };
+        // Determine the DIDDocRecord-s not already in the file.
+        let new_did_doc_record_index_begin = match existing_octet_length_o {
+            None | Some(0) => 0,
+            Some(existing_octet_length) => {
+                match did_doc_record_v.iter().position(|did_doc_record| {
+                    did_doc_record.did_documents_jsonl_octet_length == existing_octet_length
+                }) {
+                    Some(index) => index + 1,
+                    None => {
+                        return Err(Error::InternalError(
+                            format!(
+                                "existing file {} (length {}) is not a prefix of the did-documents.jsonl for {} in the DID doc store; export it non-incrementally to overwrite it",
+                                path.display(),
+                                existing_octet_length,
+                                did
+                            )
+                            .into(),
+                        ));
+                    }
+                }
+            }
+        };
+        if new_did_doc_record_index_begin == did_doc_record_v.len() {
+            tracing::trace!("{} is unchanged; skipping", path.display());
+            continue;
+        }
+
+        let mut did_documents_jsonl = String::new();
+        for did_doc_record in did_doc_record_v[new_did_doc_record_index_begin..].iter() {
+            did_documents_jsonl.push_str(&did_doc_record.did_document_jcs);
+            did_documents_jsonl.push('\n');
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| io_error(parent, e))?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(new_did_doc_record_index_begin > 0)
+            .truncate(new_did_doc_record_index_begin == 0)
+            .open(&path)
+            .map_err(|e| io_error(&path, e))?;
+        file.write_all(did_documents_jsonl.as_bytes())
+            .map_err(|e| io_error(&path, e))?;
+        tracing::debug!(
+            "exported {} DID document(s) for {} to {}",
+            did_doc_record_v.len() - new_did_doc_record_index_begin,
+            did,
+            path.display()
+        );
+        exported_did_v.push(did);
+    }
+
+    Ok(exported_did_v)
+}
+
+fn did_host(did: &DIDStr) -> String {
+    if let Some(port) = did.port_o() {
+        format!("{}:{}", did.hostname(), port)
+    } else {
+        did.hostname().to_string()
+    }
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> Error {
+    Error::StorageError(format!("{}: {}", path.display(), e).into())
+}
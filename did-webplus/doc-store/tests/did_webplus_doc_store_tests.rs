@@ -4,7 +4,7 @@ use std::{
 };
 
 use did_webplus_core::DIDDocument;
-use did_webplus_doc_store::{DIDDocStorage, DIDDocStore, Error};
+use did_webplus_doc_store::{DIDDocStorage, DIDDocStore, DIDKeyRecord, Error};
 use did_webplus_mock::{MicroledgerView, MockVDR, MockVDRClient, MockWallet};
 
 /// This will run once at load time (i.e. presumably before main function is called).
@@ -16,6 +16,13 @@ fn overall_init() {
 /// Build a valid create → update → deactivate microledger via MockWallet / MockVDR, returning
 /// owned DID documents and their JCS serializations.
 fn build_valid_create_update_deactivate_microledger() -> (Vec<DIDDocument>, Vec<String>) {
+    build_valid_create_update_deactivate_microledger_with_path(None)
+}
+
+/// This is build_valid_create_update_deactivate_microledger, but for a DID having the given path.
+fn build_valid_create_update_deactivate_microledger_with_path(
+    did_path_o: Option<String>,
+) -> (Vec<DIDDocument>, Vec<String>) {
     let hostname = "example.com".to_string();
     let mock_vdr_la = Arc::new(RwLock::new(MockVDR::new_with(
         hostname.clone(),
@@ -38,7 +45,7 @@ fn build_valid_create_update_deactivate_microledger() -> (Vec<DIDDocument>, Vec<
     let mb_hash_function = selfhash::MBHashFunction::blake3(mbx::Base::Base64Url);
 
     let did = wallet
-        .create_did(hostname, None, None, key_type, &mb_hash_function)
+        .create_did(hostname, None, did_path_o, key_type, &mb_hash_function)
         .expect("pass");
     wallet.update_did(&did, key_type).expect("pass");
     wallet.deactivate_did(&did).expect("pass");
@@ -263,6 +270,556 @@ async fn test_doc_store_validate_and_add_did_docs_with_storage_sqlite() {
     test_doc_store_validate_and_add_did_docs_impl(Arc::new(did_doc_storage)).await;
 }
 
+#[tokio::test]
+async fn test_static_tree() {
+    let (did_document_v, did_document_jcs_v) = build_valid_create_update_deactivate_microledger();
+    let did = did_document_v[0].did.clone();
+    let did_documents_jsonl = did_document_jcs_v
+        .iter()
+        .map(|did_document_jcs| format!("{}\n", did_document_jcs))
+        .collect::<String>();
+
+    let export_dir = std::path::Path::new("tests/test_static_tree_export");
+    let append_dir = std::path::Path::new("tests/test_static_tree_append");
+    for dir in [export_dir, append_dir] {
+        if std::fs::exists(dir).expect("pass") {
+            std::fs::remove_dir_all(dir).expect("pass");
+        }
+    }
+
+    // The file is at the path that DID resolution maps the DID to.
+    let path = did_webplus_doc_store::static_did_documents_jsonl_path(export_dir, &did);
+    let relative_path = path
+        .strip_prefix(export_dir)
+        .expect("pass")
+        .to_str()
+        .expect("pass");
+    assert_eq!(
+        did_webplus_core::DID::from_did_documents_jsonl_resolution_url(
+            did.hostname(),
+            did.port_o(),
+            relative_path
+        )
+        .expect("pass"),
+        did
+    );
+
+    let did_doc_store = DIDDocStore::new(Arc::new(
+        did_webplus_doc_storage_mock::DIDDocStorageMock::new(),
+    ));
+    for (did_document, did_document_jcs) in
+        did_document_v.iter().zip(did_document_jcs_v.iter()).take(2)
+    {
+        did_doc_store
+            .validate_and_add_did_doc(
+                None,
+                did_document,
+                did_document
+                    .prev_did_document_self_hash_o
+                    .as_ref()
+                    .map(|_| &did_document_v[did_document.version_id as usize - 1]),
+                did_document_jcs,
+            )
+            .await
+            .expect("pass");
+    }
+    let exported_did_v =
+        did_webplus_doc_store::export_static_tree(&did_doc_store, export_dir, None, true)
+            .await
+            .expect("pass");
+    assert_eq!(exported_did_v, vec![did.clone()]);
+    let expected_did_documents_jsonl =
+        format!("{}\n{}\n", did_document_jcs_v[0], did_document_jcs_v[1]);
+    assert_eq!(
+        std::fs::read_to_string(&path).expect("pass"),
+        expected_did_documents_jsonl
+    );
+
+    // Re-exporting incrementally skips unchanged DIDs.
+    let exported_did_v =
+        did_webplus_doc_store::export_static_tree(&did_doc_store, export_dir, None, true)
+            .await
+            .expect("pass");
+    assert!(exported_did_v.is_empty());
+    // DIDs on other hosts are not exported.
+    let exported_did_v = did_webplus_doc_store::export_static_tree(
+        &did_doc_store,
+        export_dir,
+        Some("other.example.com"),
+        false,
+    )
+    .await
+    .expect("pass");
+    assert!(exported_did_v.is_empty());
+
+    // After an update, re-exporting incrementally appends only the new DID document.
+    did_doc_store
+        .validate_and_add_did_doc(
+            None,
+            &did_document_v[2],
+            Some(&did_document_v[1]),
+            &did_document_jcs_v[2],
+        )
+        .await
+        .expect("pass");
+    let exported_did_v =
+        did_webplus_doc_store::export_static_tree(&did_doc_store, export_dir, None, true)
+            .await
+            .expect("pass");
+    assert_eq!(exported_did_v, vec![did.clone()]);
+    assert_eq!(
+        std::fs::read_to_string(&path).expect("pass"),
+        did_documents_jsonl
+    );
+
+    // A file that isn't a prefix of the microledger is not appended to, unless exporting non-incrementally.
+    std::fs::write(&path, "garbage\n").expect("pass");
+    did_webplus_doc_store::export_static_tree(&did_doc_store, export_dir, None, true)
+        .await
+        .expect_err("pass");
+    did_webplus_doc_store::export_static_tree(&did_doc_store, export_dir, None, false)
+        .await
+        .expect("pass");
+    assert_eq!(
+        std::fs::read_to_string(&path).expect("pass"),
+        did_documents_jsonl
+    );
+
+    // Appending DID documents one at a time produces the same file, and DID documents must be appended in order.
+    did_webplus_doc_store::append_did_document_to_static_tree(
+        append_dir,
+        &did_document_v[1],
+        &did_document_jcs_v[1],
+    )
+    .expect_err("pass");
+    for (did_document, did_document_jcs) in did_document_v.iter().zip(did_document_jcs_v.iter()) {
+        did_webplus_doc_store::append_did_document_to_static_tree(
+            append_dir,
+            did_document,
+            did_document_jcs,
+        )
+        .expect("pass");
+    }
+    did_webplus_doc_store::append_did_document_to_static_tree(
+        append_dir,
+        &did_document_v[1],
+        &did_document_jcs_v[1],
+    )
+    .expect_err("pass");
+    assert_eq!(
+        did_webplus_doc_store::read_static_did_documents_jsonl(append_dir, &did)
+            .expect("pass")
+            .expect("pass"),
+        did_documents_jsonl
+    );
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn test_doc_store_import_export_copy() {
+    let (did_document_v, did_document_jcs_v) = build_valid_create_update_deactivate_microledger();
+    let did = did_document_v[0].did.clone();
+    let did_documents_jsonl = did_document_jcs_v
+        .iter()
+        .map(|did_document_jcs| format!("{}\n", did_document_jcs))
+        .collect::<String>();
+    let batch_size = std::num::NonZeroUsize::new(2).unwrap();
+
+    let source_did_doc_store = DIDDocStore::new(Arc::new(
+        did_webplus_doc_storage_mock::DIDDocStorageMock::new(),
+    ));
+
+    // Importing only a non-root suffix into an empty store fails, since the predecessors aren't present.
+    let suffix_did_documents_jsonl = format!("{}\n", did_document_jcs_v[2]);
+    source_did_doc_store
+        .import_did_documents_jsonl(&suffix_did_documents_jsonl, batch_size)
+        .await
+        .expect_err("pass");
+
+    // Import a prefix, then the whole microledger (in batches of 2), which only adds the remainder.
+    let prefix_did_documents_jsonl = format!("{}\n", did_document_jcs_v[0]);
+    assert_eq!(
+        source_did_doc_store
+            .import_did_documents_jsonl(&prefix_did_documents_jsonl, batch_size)
+            .await
+            .expect("pass"),
+        1
+    );
+    assert_eq!(
+        source_did_doc_store
+            .import_did_documents_jsonl(&did_documents_jsonl, batch_size)
+            .await
+            .expect("pass"),
+        2
+    );
+    // Re-importing is idempotent.
+    assert_eq!(
+        source_did_doc_store
+            .import_did_documents_jsonl(&did_documents_jsonl, batch_size)
+            .await
+            .expect("pass"),
+        0
+    );
+    assert_eq!(
+        source_did_doc_store.get_dids(None).await.expect("pass"),
+        vec![did.clone()]
+    );
+    assert_eq!(
+        source_did_doc_store
+            .export_did_documents_jsonl(None, &did)
+            .await
+            .expect("pass"),
+        did_documents_jsonl
+    );
+
+    // An invalid microledger is rejected by a fresh store, and nothing past the last valid batch is stored.
+    let (_, invalid_did_document_jcs_v) =
+        invalid_microledger_with_mutated_middle_proof(&did_document_v);
+    let invalid_did_documents_jsonl = invalid_did_document_jcs_v
+        .iter()
+        .map(|did_document_jcs| format!("{}\n", did_document_jcs))
+        .collect::<String>();
+    let invalid_did_doc_store = DIDDocStore::new(Arc::new(
+        did_webplus_doc_storage_mock::DIDDocStorageMock::new(),
+    ));
+    invalid_did_doc_store
+        .import_did_documents_jsonl(
+            &invalid_did_documents_jsonl,
+            std::num::NonZeroUsize::new(1).unwrap(),
+        )
+        .await
+        .expect_err("pass");
+    assert_eq!(
+        invalid_did_doc_store
+            .export_did_documents_jsonl(None, &did)
+            .await
+            .expect("pass"),
+        prefix_did_documents_jsonl
+    );
+    // Importing a DID document that conflicts with the stored one is rejected.
+    let conflicting_did_documents_jsonl = format!("{}\n", invalid_did_document_jcs_v[1]);
+    source_did_doc_store
+        .import_did_documents_jsonl(&conflicting_did_documents_jsonl, batch_size)
+        .await
+        .expect_err("pass");
+
+    // Copy from the mock-backed store into a SQLite-backed one; copying again adds nothing.
+    let did_doc_store_database_path = "tests/test_doc_store_import_export_copy.db";
+    if std::fs::exists(did_doc_store_database_path).expect("pass") {
+        std::fs::remove_file(did_doc_store_database_path).expect("pass");
+    }
+    let db_url = format!("sqlite://{}?mode=rwc", did_doc_store_database_path);
+    let dest_did_doc_store = DIDDocStore::new(Arc::new(
+        did_webplus_doc_storage_sqlite::DIDDocStorageSQLite::open_url_and_run_migrations(
+            db_url.as_str(),
+            None,
+        )
+        .await
+        .expect("pass"),
+    ));
+    assert_eq!(
+        source_did_doc_store
+            .copy_to(&dest_did_doc_store, batch_size)
+            .await
+            .expect("pass"),
+        (1, 3)
+    );
+    assert_eq!(
+        source_did_doc_store
+            .copy_to(&dest_did_doc_store, batch_size)
+            .await
+            .expect("pass"),
+        (0, 0)
+    );
+    assert_eq!(
+        dest_did_doc_store.get_dids(None).await.expect("pass"),
+        vec![did.clone()]
+    );
+    assert_eq!(
+        dest_did_doc_store
+            .export_did_documents_jsonl(None, &did)
+            .await
+            .expect("pass"),
+        did_documents_jsonl
+    );
+
+    // Copying in chunks much smaller than a DID document gives the same result.
+    let chunked_dest_did_doc_store = DIDDocStore::new(Arc::new(
+        did_webplus_doc_storage_mock::DIDDocStorageMock::new(),
+    ));
+    assert_eq!(
+        source_did_doc_store
+            .copy_to_in_chunks(
+                &chunked_dest_did_doc_store,
+                batch_size,
+                std::num::NonZeroU64::new(7).unwrap(),
+            )
+            .await
+            .expect("pass"),
+        (1, 3)
+    );
+    assert_eq!(
+        chunked_dest_did_doc_store
+            .export_did_documents_jsonl(None, &did)
+            .await
+            .expect("pass"),
+        did_documents_jsonl
+    );
+}
+
+#[tokio::test]
+async fn test_doc_store_copy_non_ascii_in_chunks() {
+    // A DID path can have non-ASCII chars, so the did-documents.jsonl content can have multi-byte UTF-8 chars.
+    let (did_document_v, did_document_jcs_v) =
+        build_valid_create_update_deactivate_microledger_with_path(Some("ünïcödé".to_string()));
+    let did = did_document_v[0].did.clone();
+    let did_documents_jsonl = did_document_jcs_v
+        .iter()
+        .map(|did_document_jcs| format!("{}\n", did_document_jcs))
+        .collect::<String>();
+    assert!(!did_documents_jsonl.is_ascii());
+    let batch_size = std::num::NonZeroUsize::new(2).unwrap();
+
+    let source_did_doc_store = DIDDocStore::new(Arc::new(
+        did_webplus_doc_storage_mock::DIDDocStorageMock::new(),
+    ));
+    assert_eq!(
+        source_did_doc_store
+            .import_did_documents_jsonl(&did_documents_jsonl, batch_size)
+            .await
+            .expect("pass"),
+        3
+    );
+
+    // Chunk sizes this small put chunk boundaries in the middle of multi-byte chars.
+    for chunk_octet_length in [1, 2, 3, 5, 7] {
+        let dest_did_doc_store = DIDDocStore::new(Arc::new(
+            did_webplus_doc_storage_mock::DIDDocStorageMock::new(),
+        ));
+        assert_eq!(
+            source_did_doc_store
+                .copy_to_in_chunks(
+                    &dest_did_doc_store,
+                    batch_size,
+                    std::num::NonZeroU64::new(chunk_octet_length).unwrap(),
+                )
+                .await
+                .expect("pass"),
+            (1, 3)
+        );
+        assert_eq!(
+            dest_did_doc_store
+                .export_did_documents_jsonl(None, &did)
+                .await
+                .expect("pass"),
+            did_documents_jsonl
+        );
+    }
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn test_doc_store_audit() {
+    let (did_document_v, did_document_jcs_v) = build_valid_create_update_deactivate_microledger();
+    let did_document_jcs_v = did_document_jcs_v
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+
+    let did_doc_store_database_path = "tests/test_doc_store_audit.db";
+    if std::fs::exists(did_doc_store_database_path).expect("pass") {
+        std::fs::remove_file(did_doc_store_database_path).expect("pass");
+    }
+    let db_url = format!("sqlite://{}?mode=rwc", did_doc_store_database_path);
+    let did_doc_store = DIDDocStore::new(Arc::new(
+        did_webplus_doc_storage_sqlite::DIDDocStorageSQLite::open_url_and_run_migrations(
+            db_url.as_str(),
+            None,
+        )
+        .await
+        .expect("pass"),
+    ));
+    did_doc_store
+        .validate_and_add_did_docs(None, &did_document_jcs_v, &did_document_v, None)
+        .await
+        .expect("pass");
+
+    for audit_report in audit_with_and_without_small_chunks(&did_doc_store).await {
+        assert_eq!(audit_report.did_count, 1);
+        assert_eq!(audit_report.did_doc_record_count, 3);
+        assert!(!audit_report.has_issues());
+    }
+
+    // Corrupt the stored records directly, bypassing DIDDocStorage.
+    let sqlite_pool = sqlx::SqlitePool::connect(db_url.as_str())
+        .await
+        .expect("pass");
+    sqlx::query(
+        "UPDATE did_document_records SET did_documents_jsonl_octet_length = did_documents_jsonl_octet_length + 1 WHERE version_id = 2",
+    )
+    .execute(&sqlite_pool)
+    .await
+    .expect("pass");
+    for audit_report in audit_with_and_without_small_chunks(&did_doc_store).await {
+        assert_eq!(audit_report.did_doc_record_count, 3);
+        assert_eq!(audit_report.issue_v.len(), 1);
+        assert_eq!(audit_report.issue_v[0].version_id, 2);
+        assert_eq!(
+            audit_report.issue_v[0].kind,
+            did_webplus_doc_store::DIDDocStoreAuditIssueKind::Corrupted
+        );
+    }
+
+    // With the middle record missing, the last one is orphaned.
+    sqlx::query("DELETE FROM did_document_records WHERE version_id = 1")
+        .execute(&sqlite_pool)
+        .await
+        .expect("pass");
+    for audit_report in audit_with_and_without_small_chunks(&did_doc_store).await {
+        assert_eq!(audit_report.did_doc_record_count, 2);
+        assert_eq!(audit_report.issue_v.len(), 1);
+        assert_eq!(audit_report.issue_v[0].version_id, 2);
+        assert_eq!(
+            audit_report.issue_v[0].kind,
+            did_webplus_doc_store::DIDDocStoreAuditIssueKind::Orphaned
+        );
+    }
+}
+
+/// Audit the given store both normally and reading a single byte of did-documents.jsonl content at a time,
+/// which must give the same results, even when the stored octet lengths are corrupted.
+async fn audit_with_and_without_small_chunks(
+    did_doc_store: &DIDDocStore,
+) -> Vec<did_webplus_doc_store::DIDDocStoreAuditReport> {
+    vec![
+        did_doc_store.audit().await.expect("pass"),
+        did_doc_store
+            .audit_in_chunks(std::num::NonZeroU64::new(1).unwrap())
+            .await
+            .expect("pass"),
+    ]
+}
+
+async fn test_doc_store_did_key_records_impl(did_doc_storage_a: Arc<dyn DIDDocStorage>) {
+    let (did_document_v, did_document_jcs_v) = build_valid_create_update_deactivate_microledger();
+    let did_document_jcs_v = did_document_jcs_v
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+
+    let did_doc_store = DIDDocStore::new(did_doc_storage_a);
+    did_doc_store
+        .validate_and_add_did_docs(None, &did_document_jcs_v, &did_document_v, None)
+        .await
+        .expect("pass");
+
+    // Every key of every DID document should be found, and only in the DID documents that contain it.
+    for did_document in did_document_v.iter() {
+        for did_key_record in DIDKeyRecord::for_did_document(did_document).expect("pass") {
+            let did_key_record_v = did_doc_store
+                .get_did_key_records(None, did_key_record.key.as_str())
+                .await
+                .expect("pass");
+            assert!(did_key_record_v.contains(&did_key_record));
+            for other_did_key_record in did_key_record_v.iter() {
+                let other_did_document = &did_document_v[other_did_key_record.version_id as usize];
+                assert!(
+                    DIDKeyRecord::for_did_document(other_did_document)
+                        .expect("pass")
+                        .contains(other_did_key_record)
+                );
+            }
+        }
+    }
+
+    // The root DID document's verification method key is found by MBPubKey.
+    let root_did_document = &did_document_v[0];
+    let pub_key = mbx::MBPubKey::try_from(
+        &root_did_document.public_key_material.verification_method_v[0].public_key_jwk,
+    )
+    .expect("pass");
+    let did_key_record_v = did_doc_store
+        .find_did_key_records(None, pub_key.as_str())
+        .await
+        .expect("pass");
+    assert_eq!(did_key_record_v.len(), 1);
+    assert_eq!(did_key_record_v[0].did, root_did_document.did.as_str());
+    assert_eq!(did_key_record_v[0].version_id, 0);
+    assert_ne!(
+        did_key_record_v[0].key_purpose_flags,
+        did_webplus_core::KeyPurposeFlags::NONE
+    );
+    assert!(!did_key_record_v[0].is_update_key);
+
+    // The deactivated DID document has no keys.
+    assert!(
+        DIDKeyRecord::for_did_document(&did_document_v[2])
+            .expect("pass")
+            .is_empty()
+    );
+
+    // Unknown keys have no records.
+    assert!(
+        did_doc_store
+            .find_did_key_records(None, "uHiBKHZUE3HHlYcyVIF-vPm0Xg71vqJla2L1OGXHMSK4NEA")
+            .await
+            .expect("pass")
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn test_doc_store_did_key_records_with_storage_mock() {
+    let did_doc_storage = did_webplus_doc_storage_mock::DIDDocStorageMock::new();
+    test_doc_store_did_key_records_impl(Arc::new(did_doc_storage)).await;
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn test_doc_store_did_key_records_with_storage_sqlite() {
+    let did_doc_store_database_path = "tests/test_doc_store_did_key_records.db";
+    if std::fs::exists(did_doc_store_database_path).expect("pass") {
+        std::fs::remove_file(did_doc_store_database_path).expect("pass");
+    }
+    let db_url = format!("sqlite://{}?mode=rwc", did_doc_store_database_path);
+    let did_doc_storage =
+        did_webplus_doc_storage_sqlite::DIDDocStorageSQLite::open_url_and_run_migrations(
+            db_url.as_str(),
+            None,
+        )
+        .await
+        .expect("pass");
+    test_doc_store_did_key_records_impl(Arc::new(did_doc_storage)).await;
+
+    // Clear did_key_records and check that they're backfilled when the database is reopened.
+    let sqlite_pool = sqlx::SqlitePool::connect(db_url.as_str())
+        .await
+        .expect("pass");
+    let did_key_record_count_before: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM did_key_records")
+            .fetch_one(&sqlite_pool)
+            .await
+            .expect("pass");
+    assert!(did_key_record_count_before > 0);
+    sqlx::query("DELETE FROM did_key_records")
+        .execute(&sqlite_pool)
+        .await
+        .expect("pass");
+    did_webplus_doc_storage_sqlite::DIDDocStorageSQLite::open_url_and_run_migrations(
+        db_url.as_str(),
+        None,
+    )
+    .await
+    .expect("pass");
+    let did_key_record_count_after: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM did_key_records")
+            .fetch_one(&sqlite_pool)
+            .await
+            .expect("pass");
+    assert_eq!(did_key_record_count_after, did_key_record_count_before);
+}
+
 /// Parsed form of a fixture's meta.json, which specifies the DID of the fixture's microledger,
 /// the expected validation outcome ("accept", "accept-prefix", or "reject"), and a
 /// human-readable description of what the fixture exercises.
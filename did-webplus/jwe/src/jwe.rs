@@ -0,0 +1,517 @@
+use crate::{
+    Error, JWEHeader, JWEKeyManagementAlgorithm, JWERecipient, JWERecipientKey, JWESerialization,
+    Result, bail,
+    concat_kdf::concat_kdf,
+    error,
+    key_agreement::{ephemeral_key_agreement, random_fill},
+    require,
+};
+use base64::Engine;
+use std::str::FromStr;
+
+/// The only supported content encryption algorithm.
+const A256GCM: &str = "A256GCM";
+
+/// A JWE (RFC 7516) whose content is encrypted using A256GCM, and whose content encryption key is established
+/// for each recipient using ECDH-ES or ECDH-ES+A256KW (RFC 7518 Section 4.6) with the recipient's keyAgreement
+/// key.  This type has the structure of the General JWE JSON Serialization.  The JWE Compact Serialization and
+/// the Flattened JWE JSON Serialization can also be parsed into it, and the JWE Compact Serialization can be
+/// produced from it when it has a single recipient and all header parameters are in the protected header.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct JWE {
+    /// base64url(UTF8(JSON(protected header)))
+    pub protected: String,
+    /// Shared unprotected header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unprotected: Option<JWEHeader>,
+    pub recipients: Vec<JWERecipient>,
+    /// Base64url-encoded additional authenticated data.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aad: Option<String>,
+    /// Base64url-encoded initialization vector.
+    pub iv: String,
+    /// Base64url-encoded ciphertext.
+    pub ciphertext: String,
+    /// Base64url-encoded authentication tag.
+    pub tag: String,
+}
+
+impl JWE {
+    /// Encrypt the given plaintext to the given recipient keys (see JWERecipientKey::resolve).  ECDH-ES
+    /// only supports a single recipient, as does the JWE Compact Serialization.  In the JWE Compact
+    /// Serialization, all header parameters are in the protected header.  Otherwise, "enc" is in the
+    /// protected header, and "alg", "kid", and "epk" are in each recipient's unprotected header.
+    pub fn encrypt(
+        plaintext: &[u8],
+        recipient_key_v: &[JWERecipientKey],
+        key_management_algorithm: JWEKeyManagementAlgorithm,
+        serialization: JWESerialization,
+    ) -> Result<Self> {
+        require!(
+            !recipient_key_v.is_empty(),
+            "JWE must have at least one recipient"
+        );
+        require!(
+            key_management_algorithm != JWEKeyManagementAlgorithm::ECDHES
+                || recipient_key_v.len() == 1,
+            "ECDH-ES only supports a single recipient; use ECDH-ES+A256KW for multiple recipients"
+        );
+        require!(
+            serialization != JWESerialization::Compact || recipient_key_v.len() == 1,
+            "JWE Compact Serialization only supports a single recipient; use JSON serialization for multiple recipients"
+        );
+
+        // Establish the content encryption key, and the per-recipient header and encrypted key.
+        let mut content_encryption_key = zeroize::Zeroizing::new(Vec::new());
+        let mut recipient_header_and_encrypted_key_v = Vec::with_capacity(recipient_key_v.len());
+        if key_management_algorithm == JWEKeyManagementAlgorithm::ECDHESA256KW {
+            content_encryption_key.resize(32, 0u8);
+            random_fill(content_encryption_key.as_mut_slice())?;
+        }
+        for recipient_key in recipient_key_v {
+            let (epk, shared_secret) = ephemeral_key_agreement(&recipient_key.pub_key)?;
+            let recipient_header = JWEHeader {
+                alg: Some(key_management_algorithm.as_str().to_string()),
+                kid: Some(recipient_key.key_id.to_string()),
+                epk: Some(epk),
+                ..Default::default()
+            };
+            let encrypted_key_o = match key_management_algorithm {
+                JWEKeyManagementAlgorithm::ECDHES => {
+                    content_encryption_key = concat_kdf(&shared_secret, A256GCM, &[], &[], 256);
+                    None
+                }
+                JWEKeyManagementAlgorithm::ECDHESA256KW => {
+                    let key_encryption_key = concat_kdf(
+                        &shared_secret,
+                        key_management_algorithm.as_str(),
+                        &[],
+                        &[],
+                        256,
+                    );
+                    let encrypted_key = aes_kw_for(&key_encryption_key)?
+                        .wrap_vec(content_encryption_key.as_slice())
+                        .map_err(|e| error!("Failed to wrap content encryption key: {}", e))?;
+                    Some(base64url_encode(&encrypted_key))
+                }
+            };
+            recipient_header_and_encrypted_key_v.push((recipient_header, encrypted_key_o));
+        }
+
+        // Form the protected header and the recipients.
+        let (protected_header, recipients) = match serialization {
+            JWESerialization::Compact => {
+                let (recipient_header, encrypted_key_o) =
+                    recipient_header_and_encrypted_key_v.pop().unwrap();
+                let protected_header = JWEHeader {
+                    enc: Some(A256GCM.to_string()),
+                    ..recipient_header
+                };
+                let recipients = vec![JWERecipient {
+                    header: None,
+                    encrypted_key: encrypted_key_o,
+                }];
+                (protected_header, recipients)
+            }
+            JWESerialization::JSON => {
+                let protected_header = JWEHeader {
+                    enc: Some(A256GCM.to_string()),
+                    ..Default::default()
+                };
+                let recipients = recipient_header_and_encrypted_key_v
+                    .into_iter()
+                    .map(|(recipient_header, encrypted_key_o)| JWERecipient {
+                        header: Some(recipient_header),
+                        encrypted_key: encrypted_key_o,
+                    })
+                    .collect();
+                (protected_header, recipients)
+            }
+        };
+        let protected = base64url_encode(serde_json::to_string(&protected_header)?.as_bytes());
+
+        // Encrypt the plaintext.  The additional authenticated data is the ASCII of the encoded protected
+        // header (see RFC 7516 Section 5.1).
+        let mut iv = [0u8; 12];
+        random_fill(&mut iv)?;
+        let mut buffer = plaintext.to_vec();
+        use aes_gcm::{AeadInPlace, KeyInit};
+        let tag = aes_gcm::Aes256Gcm::new_from_slice(content_encryption_key.as_slice())
+            .map_err(|e| error!("Invalid content encryption key: {}", e))?
+            .encrypt_in_place_detached(
+                aes_gcm::Nonce::from_slice(&iv),
+                protected.as_bytes(),
+                &mut buffer,
+            )
+            .map_err(|e| error!("Failed to encrypt JWE content: {}", e))?;
+
+        Ok(Self {
+            protected,
+            unprotected: None,
+            recipients,
+            aad: None,
+            iv: base64url_encode(&iv),
+            ciphertext: base64url_encode(&buffer),
+            tag: base64url_encode(tag.as_slice()),
+        })
+    }
+    /// Resolve each recipient's keyAgreement key (see JWERecipientKey::resolve) using the given DIDResolver,
+    /// and then encrypt the given plaintext to them (see JWE::encrypt).
+    pub async fn encrypt_to_recipients(
+        plaintext: &[u8],
+        recipient_v: &[&str],
+        key_management_algorithm: JWEKeyManagementAlgorithm,
+        serialization: JWESerialization,
+        did_resolver: &dyn did_webplus_resolver::DIDResolver,
+    ) -> Result<Self> {
+        let mut recipient_key_v = Vec::with_capacity(recipient_v.len());
+        for recipient in recipient_v {
+            recipient_key_v.push(JWERecipientKey::resolve(recipient, did_resolver).await?);
+        }
+        Self::encrypt(
+            plaintext,
+            &recipient_key_v,
+            key_management_algorithm,
+            serialization,
+        )
+    }
+    /// Returns the decoded protected header.
+    pub fn protected_header(&self) -> Result<JWEHeader> {
+        let protected_header_bytes = base64url_decode(&self.protected, "protected header")?;
+        serde_json::from_slice(&protected_header_bytes).map_err(|e| {
+            error!(
+                "JWE protected header failed to parse as expected JSON structure: {}",
+                e
+            )
+        })
+    }
+    /// Returns the header that applies to the recipient having the given index, which is the union of the
+    /// protected header, the shared unprotected header, and the recipient's unprotected header.
+    pub fn recipient_header(&self, recipient_index: usize) -> Result<JWEHeader> {
+        let recipient = self
+            .recipients
+            .get(recipient_index)
+            .ok_or_else(|| error!("JWE recipient index {} out of range", recipient_index))?;
+        let mut header = self.protected_header()?;
+        if let Some(unprotected) = self.unprotected.as_ref() {
+            header = header.union(unprotected)?;
+        }
+        if let Some(recipient_header) = recipient.header.as_ref() {
+            header = header.union(recipient_header)?;
+        }
+        Ok(header)
+    }
+    /// Decrypt this JWE using a keyAgreement key controlled by the given wallet.  The recipients are checked in
+    /// order, and the first one whose "kid" is a keyAgreement key controlled by the wallet (and belonging to
+    /// the DID specified by controlled_did_o, if specified) is used.  The ECDH key agreement is performed by
+    /// the wallet (see Wallet::derive_shared_secret), which records it as usage of that key.  Returns the key
+    /// ID of the keyAgreement key that was used and the plaintext.
+    pub async fn decrypt(
+        &self,
+        wallet: &dyn did_webplus_wallet::Wallet,
+        controlled_did_o: Option<&did_webplus_core::DIDStr>,
+    ) -> Result<(
+        did_webplus_core::DIDKeyResourceFullyQualified,
+        zeroize::Zeroizing<Vec<u8>>,
+    )> {
+        for recipient_index in 0..self.recipients.len() {
+            let header = self.recipient_header(recipient_index)?;
+            let key_id = match header
+                .kid
+                .as_deref()
+                .map(did_webplus_core::DIDKeyResourceFullyQualified::from_str)
+            {
+                Some(Ok(key_id)) => key_id,
+                _ => {
+                    // This recipient's key is not a did:webplus key, so it can't be controlled by the wallet.
+                    continue;
+                }
+            };
+            if let Some(controlled_did) = controlled_did_o {
+                if key_id.did() != controlled_did {
+                    continue;
+                }
+            }
+            let query_result_v = wallet
+                .get_locally_controlled_verification_methods(
+                    &did_webplus_wallet_store::LocallyControlledVerificationMethodFilter {
+                        did_o: Some(key_id.did().to_owned()),
+                        version_id_o: Some(key_id.query_version_id()),
+                        key_purpose_o: Some(did_webplus_core::KeyPurpose::KeyAgreement),
+                        key_id_o: Some(key_id.fragment().to_string()),
+                        result_limit_o: Some(1),
+                    },
+                )
+                .await
+                .map_err(|e| error!("{}", e))?;
+            if query_result_v.is_empty() {
+                continue;
+            }
+            tracing::debug!("decrypting JWE using keyAgreement key {}", key_id);
+            let plaintext = self
+                .decrypt_for_recipient(recipient_index, &header, &key_id, wallet)
+                .await?;
+            return Ok((key_id, plaintext));
+        }
+        bail!(
+            "None of the JWE's {} recipient(s) is a keyAgreement key controlled by the wallet{}",
+            self.recipients.len(),
+            if let Some(controlled_did) = controlled_did_o {
+                format!(" for DID {}", controlled_did)
+            } else {
+                String::new()
+            }
+        );
+    }
+    async fn decrypt_for_recipient(
+        &self,
+        recipient_index: usize,
+        header: &JWEHeader,
+        key_id: &did_webplus_core::DIDKeyResourceFullyQualifiedStr,
+        wallet: &dyn did_webplus_wallet::Wallet,
+    ) -> Result<zeroize::Zeroizing<Vec<u8>>> {
+        require!(
+            header.crit.is_none(),
+            "JWE \"crit\" header parameter is not supported"
+        );
+        require!(
+            header.enc.as_deref() == Some(A256GCM),
+            "Unsupported JWE content encryption algorithm {:?}; only {:?} is supported",
+            header.enc,
+            A256GCM
+        );
+        let key_management_algorithm = JWEKeyManagementAlgorithm::from_str(
+            header
+                .alg
+                .as_deref()
+                .ok_or_else(|| error!("JWE is missing \"alg\" header parameter"))?,
+        )?;
+        let epk = header
+            .epk
+            .as_ref()
+            .ok_or_else(|| error!("JWE is missing \"epk\" header parameter"))?;
+        let epk_pub_key = mbx::MBPubKey::try_from(epk)
+            .map_err(|e| error!("Invalid JWE \"epk\" header parameter: {}", e))?;
+        let apu = match header.apu.as_deref() {
+            Some(apu) => base64url_decode(apu, "\"apu\" header parameter")?,
+            None => Vec::new(),
+        };
+        let apv = match header.apv.as_deref() {
+            Some(apv) => base64url_decode(apv, "\"apv\" header parameter")?,
+            None => Vec::new(),
+        };
+
+        let shared_secret = wallet
+            .derive_shared_secret(key_id, &epk_pub_key)
+            .await
+            .map_err(|e| error!("{}", e))?;
+
+        let encrypted_key_o = self.recipients[recipient_index]
+            .encrypted_key
+            .as_deref()
+            .filter(|encrypted_key| !encrypted_key.is_empty());
+        let content_encryption_key = match key_management_algorithm {
+            JWEKeyManagementAlgorithm::ECDHES => {
+                require!(
+                    encrypted_key_o.is_none(),
+                    "JWE encrypted key must be empty when using ECDH-ES"
+                );
+                concat_kdf(&shared_secret, A256GCM, &apu, &apv, 256)
+            }
+            JWEKeyManagementAlgorithm::ECDHESA256KW => {
+                let encrypted_key = base64url_decode(
+                    encrypted_key_o
+                        .ok_or_else(|| error!("JWE is missing encrypted key for ECDH-ES+A256KW"))?,
+                    "encrypted key",
+                )?;
+                let key_encryption_key = concat_kdf(
+                    &shared_secret,
+                    key_management_algorithm.as_str(),
+                    &apu,
+                    &apv,
+                    256,
+                );
+                zeroize::Zeroizing::new(
+                    aes_kw_for(&key_encryption_key)?
+                        .unwrap_vec(&encrypted_key)
+                        .map_err(|e| {
+                            error!("Failed to unwrap JWE content encryption key: {}", e)
+                        })?,
+                )
+            }
+        };
+
+        // The additional authenticated data is the ASCII of the encoded protected header, followed by '.' and
+        // the encoded aad if present (see RFC 7516 Section 5.2).
+        let additional_authenticated_data = if let Some(aad) = self.aad.as_deref() {
+            format!("{}.{}", self.protected, aad)
+        } else {
+            self.protected.clone()
+        };
+        let iv = base64url_decode(&self.iv, "initialization vector")?;
+        require!(
+            iv.len() == 12,
+            "JWE initialization vector must be 96 bits for A256GCM"
+        );
+        let tag = base64url_decode(&self.tag, "authentication tag")?;
+        require!(
+            tag.len() == 16,
+            "JWE authentication tag must be 128 bits for A256GCM"
+        );
+        let mut buffer = zeroize::Zeroizing::new(base64url_decode(&self.ciphertext, "ciphertext")?);
+        use aes_gcm::{AeadInPlace, KeyInit};
+        aes_gcm::Aes256Gcm::new_from_slice(content_encryption_key.as_slice())
+            .map_err(|_| error!("JWE content encryption key must be 256 bits for A256GCM"))?
+            .decrypt_in_place_detached(
+                aes_gcm::Nonce::from_slice(&iv),
+                additional_authenticated_data.as_bytes(),
+                &mut buffer,
+                aes_gcm::Tag::from_slice(&tag),
+            )
+            .map_err(|_| error!("JWE failed to decrypt"))?;
+        Ok(buffer)
+    }
+    /// Produce the JWE Compact Serialization.  This requires that there be a single recipient, and that all
+    /// header parameters are in the protected header (in particular, there can't be unprotected headers or aad).
+    pub fn to_compact_string(&self) -> Result<String> {
+        require!(
+            self.recipients.len() == 1,
+            "JWE Compact Serialization requires exactly one recipient, but there are {}",
+            self.recipients.len()
+        );
+        let recipient = &self.recipients[0];
+        require!(
+            self.unprotected.is_none() && recipient.header.is_none(),
+            "JWE Compact Serialization can't represent unprotected headers"
+        );
+        require!(
+            self.aad.is_none(),
+            "JWE Compact Serialization can't represent additional authenticated data"
+        );
+        Ok(format!(
+            "{}.{}.{}.{}.{}",
+            self.protected,
+            recipient.encrypted_key.as_deref().unwrap_or(""),
+            self.iv,
+            self.ciphertext,
+            self.tag
+        ))
+    }
+    /// Produce the General JWE JSON Serialization.
+    pub fn to_json_string(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+    /// Produce the specified serialization.
+    pub fn to_serialization_string(&self, serialization: JWESerialization) -> Result<String> {
+        match serialization {
+            JWESerialization::Compact => self.to_compact_string(),
+            JWESerialization::JSON => self.to_json_string(),
+        }
+    }
+}
+
+impl std::str::FromStr for JWE {
+    type Err = Error;
+    /// Parses the JWE Compact Serialization, the General JWE JSON Serialization, or the Flattened JWE JSON
+    /// Serialization.
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if s.starts_with('{') {
+            /// The Flattened JWE JSON Serialization (RFC 7516 Section 7.2.2), in which the single recipient's
+            /// "header" and "encrypted_key" are at the top level.
+            #[derive(serde::Deserialize)]
+            struct FlattenedJWE {
+                protected: String,
+                unprotected: Option<JWEHeader>,
+                header: Option<JWEHeader>,
+                encrypted_key: Option<String>,
+                aad: Option<String>,
+                iv: String,
+                ciphertext: String,
+                tag: String,
+            }
+
+            let value: serde_json::Value = serde_json::from_str(s)?;
+            let jwe = if value.get("recipients").is_some() {
+                serde_json::from_value::<JWE>(value)?
+            } else {
+                let flattened_jwe = serde_json::from_value::<FlattenedJWE>(value)?;
+                JWE {
+                    protected: flattened_jwe.protected,
+                    unprotected: flattened_jwe.unprotected,
+                    recipients: vec![JWERecipient {
+                        header: flattened_jwe.header,
+                        encrypted_key: flattened_jwe.encrypted_key,
+                    }],
+                    aad: flattened_jwe.aad,
+                    iv: flattened_jwe.iv,
+                    ciphertext: flattened_jwe.ciphertext,
+                    tag: flattened_jwe.tag,
+                }
+            };
+            require!(
+                !jwe.recipients.is_empty(),
+                "JWE must have at least one recipient"
+            );
+            Ok(jwe)
+        } else {
+            let part_v = s.split('.').collect::<Vec<_>>();
+            require!(
+                part_v.len() == 5,
+                "JWE Compact Serialization must have 5 parts, but there were {}",
+                part_v.len()
+            );
+            for (part, part_name) in part_v.iter().zip([
+                "protected header",
+                "encrypted key",
+                "initialization vector",
+                "ciphertext",
+                "authentication tag",
+            ]) {
+                require!(
+                    is_base64url_encoded(part),
+                    "JWE {} is not base64url-encoded",
+                    part_name
+                );
+            }
+            Ok(JWE {
+                protected: part_v[0].to_string(),
+                unprotected: None,
+                recipients: vec![JWERecipient {
+                    header: None,
+                    encrypted_key: if part_v[1].is_empty() {
+                        None
+                    } else {
+                        Some(part_v[1].to_string())
+                    },
+                }],
+                aad: None,
+                iv: part_v[2].to_string(),
+                ciphertext: part_v[3].to_string(),
+                tag: part_v[4].to_string(),
+            })
+        }
+    }
+}
+
+fn aes_kw_for(key_encryption_key: &[u8]) -> Result<aes_kw::KekAes256> {
+    let key_encryption_key = <[u8; 32]>::try_from(key_encryption_key)
+        .map_err(|_| error!("AES-256 key wrap requires a 256-bit key encryption key"))?;
+    Ok(aes_kw::KekAes256::from(key_encryption_key))
+}
+
+fn base64url_encode(bytes: &[u8]) -> String {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn base64url_decode(s: &str, part_name: &str) -> Result<Vec<u8>> {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(s.as_bytes())
+        .map_err(|e| error!("JWE {} is not valid base64url: {}", part_name, e))
+}
+
+/// This is base64url without padding.
+fn is_base64url_encoded(s: &str) -> bool {
+    s.chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
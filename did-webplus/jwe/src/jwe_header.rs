@@ -0,0 +1,66 @@
+use crate::{Result, bail};
+
+/// JOSE header for a JWE.  See RFC 7516 Section 4.  The same type is used for the protected header, the
+/// shared unprotected header, and the per-recipient unprotected headers.  The header that applies to a
+/// particular recipient is the union of these, and they must be disjoint (see RFC 7516 Section 7.2.1).
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct JWEHeader {
+    /// Key management algorithm, e.g. "ECDH-ES+A256KW".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+    /// Content encryption algorithm, e.g. "A256GCM".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enc: Option<String>,
+    /// Fully qualified DID key ID of the recipient's keyAgreement key.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    /// Ephemeral public key generated by the sender for ECDH.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epk: Option<did_webplus_core::PublicKeyParams>,
+    /// Agreement PartyUInfo, base64url-encoded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub apu: Option<String>,
+    /// Agreement PartyVInfo, base64url-encoded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub apv: Option<String>,
+    /// Media type of the complete JWE.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typ: Option<String>,
+    /// Media type of the plaintext.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cty: Option<String>,
+    /// Specifies extensions that must be understood and processed.  No extensions are supported, so
+    /// a JWE having this header parameter can't be decrypted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crit: Option<Vec<String>>,
+}
+
+impl JWEHeader {
+    /// Returns the union of this header and other, returning an error if any header parameter is present in both.
+    pub fn union(&self, other: &Self) -> Result<Self> {
+        Ok(Self {
+            alg: union_param("alg", &self.alg, &other.alg)?,
+            enc: union_param("enc", &self.enc, &other.enc)?,
+            kid: union_param("kid", &self.kid, &other.kid)?,
+            epk: union_param("epk", &self.epk, &other.epk)?,
+            apu: union_param("apu", &self.apu, &other.apu)?,
+            apv: union_param("apv", &self.apv, &other.apv)?,
+            typ: union_param("typ", &self.typ, &other.typ)?,
+            cty: union_param("cty", &self.cty, &other.cty)?,
+            crit: union_param("crit", &self.crit, &other.crit)?,
+        })
+    }
+}
+
+fn union_param<T: Clone>(name: &str, a_o: &Option<T>, b_o: &Option<T>) -> Result<Option<T>> {
+    match (a_o, b_o) {
+        (Some(_), Some(_)) => {
+            bail!(
+                "JWE header parameter {:?} is present in more than one header",
+                name
+            );
+        }
+        (Some(x), None) | (None, Some(x)) => Ok(Some(x.clone())),
+        (None, None) => Ok(None),
+    }
+}
@@ -0,0 +1,45 @@
+use crate::{Error, error};
+
+/// Key management algorithms supported for JWE (see RFC 7518 Section 4.6).  In both cases, an ephemeral
+/// keypair is generated for each recipient and ECDH is performed with the recipient's keyAgreement key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen::prelude::wasm_bindgen)]
+pub enum JWEKeyManagementAlgorithm {
+    /// "ECDH-ES" -- Direct Key Agreement, in which the key derived from the ECDH shared secret is used
+    /// directly as the content encryption key.  This only supports a single recipient.
+    #[cfg_attr(feature = "clap", value(name = "ECDH-ES"))]
+    ECDHES,
+    /// "ECDH-ES+A256KW" -- Key Agreement with Key Wrapping, in which the key derived from the ECDH shared
+    /// secret is used to wrap a random content encryption key using AES-256 Key Wrap.  This supports any
+    /// number of recipients.
+    #[cfg_attr(feature = "clap", value(name = "ECDH-ES+A256KW"))]
+    ECDHESA256KW,
+}
+
+impl JWEKeyManagementAlgorithm {
+    /// Produce the "alg" header parameter value for this variant.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ECDHES => "ECDH-ES",
+            Self::ECDHESA256KW => "ECDH-ES+A256KW",
+        }
+    }
+}
+
+impl std::fmt::Display for JWEKeyManagementAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for JWEKeyManagementAlgorithm {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ECDH-ES" => Ok(Self::ECDHES),
+            "ECDH-ES+A256KW" => Ok(Self::ECDHESA256KW),
+            _ => Err(error!("Unsupported JWE key management algorithm {:?}", s)),
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use crate::JWEHeader;
+
+/// The per-recipient part of a JWE.  See RFC 7516 Section 7.2.1.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct JWERecipient {
+    /// Per-recipient unprotected header.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<JWEHeader>,
+    /// The base64url-encoded content encryption key, encrypted to this recipient.  This is absent when the
+    /// key management algorithm is ECDH-ES (i.e. Direct Key Agreement).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encrypted_key: Option<String>,
+}
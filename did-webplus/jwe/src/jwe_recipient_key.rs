@@ -0,0 +1,88 @@
+use crate::{Result, bail, error, key_agreement::is_supported_key_agreement_pub_key, require};
+use did_webplus_core::{DIDKeyResourceFullyQualified, KeyPurpose};
+
+/// A keyAgreement key of a JWE recipient, to which the content encryption key is encrypted.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct JWERecipientKey {
+    /// The fully qualified key ID of the recipient's keyAgreement verification method.  This is used as
+    /// the "kid" header parameter, so that the recipient can determine which of its keys to decrypt with.
+    pub key_id: DIDKeyResourceFullyQualified,
+    /// The recipient's keyAgreement pub key.  Must be X25519 or P-256.
+    pub pub_key: mbx::MBPubKey,
+}
+
+impl JWERecipientKey {
+    /// Resolve a recipient's keyAgreement key using the given DIDResolver.  recipient may be a DID query
+    /// (e.g. `did:webplus:example.com:EjXivDidxAi2kETdFw1o36-jZUkYkxg0ayMhSBjODAgQ`, optionally with
+    /// selfHash and/or versionId query params), in which case the first keyAgreement key of a supported
+    /// key type (X25519 or P-256) in the resolved DID document is used.  Or it may be a DID key resource
+    /// (e.g. `did:webplus:example.com:EjXivDidxAi2kETdFw1o36-jZUkYkxg0ayMhSBjODAgQ#2`, also optionally with
+    /// query params), in which case that key is used, and it must be listed under keyAgreement.
+    pub async fn resolve(
+        recipient: &str,
+        did_resolver: &dyn did_webplus_resolver::DIDResolver,
+    ) -> Result<Self> {
+        let (did_query, key_id_fragment_o) = match recipient.split_once('#') {
+            Some((did_query, key_id_fragment)) => (did_query, Some(key_id_fragment)),
+            None => (recipient, None),
+        };
+        let (did_document, _did_document_metadata, _did_resolution_metadata) = did_resolver
+            .resolve_did_document(
+                did_query,
+                did_webplus_core::DIDResolutionOptions::no_metadata(false),
+            )
+            .await
+            .map_err(|e| error!("Failed to resolve JWE recipient {}: {}", did_query, e))?;
+        let public_key_material = &did_document.public_key_material;
+
+        let key_id_fragment = if let Some(key_id_fragment) = key_id_fragment_o {
+            require!(
+                public_key_material
+                    .key_purpose_flags_for_key_id_fragment(key_id_fragment)
+                    .contains(KeyPurpose::KeyAgreement),
+                "JWE recipient {} is not listed under keyAgreement in its DID document",
+                recipient
+            );
+            key_id_fragment.to_string()
+        } else {
+            let mut supported_key_id_fragment_o = None;
+            for relative_key_resource in &public_key_material.key_agreement_relative_key_resource_v
+            {
+                let verification_method = public_key_material
+                    .verification_method_for_key_id_fragment(relative_key_resource.fragment())
+                    .map_err(|e| error!("{}", e))?;
+                let pub_key = mbx::MBPubKey::try_from(&verification_method.public_key_jwk)
+                    .map_err(|e| error!("{}", e))?;
+                if is_supported_key_agreement_pub_key(&pub_key) {
+                    supported_key_id_fragment_o =
+                        Some(relative_key_resource.fragment().to_string());
+                    break;
+                }
+            }
+            match supported_key_id_fragment_o {
+                Some(key_id_fragment) => key_id_fragment,
+                None => {
+                    bail!(
+                        "JWE recipient {} has no keyAgreement key of a supported key type (X25519 or P-256)",
+                        recipient
+                    );
+                }
+            }
+        };
+
+        let verification_method = public_key_material
+            .verification_method_for_key_id_fragment(&key_id_fragment)
+            .map_err(|e| error!("{}", e))?;
+        let pub_key = mbx::MBPubKey::try_from(&verification_method.public_key_jwk)
+            .map_err(|e| error!("{}", e))?;
+        require!(
+            is_supported_key_agreement_pub_key(&pub_key),
+            "JWE recipient {} has an unsupported key type; only X25519 and P-256 are supported",
+            recipient
+        );
+        Ok(Self {
+            key_id: verification_method.id.clone(),
+            pub_key,
+        })
+    }
+}
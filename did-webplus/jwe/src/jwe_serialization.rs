@@ -0,0 +1,11 @@
+/// Specifies which JWE serialization to produce (see RFC 7516 Section 7).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen::prelude::wasm_bindgen)]
+pub enum JWESerialization {
+    /// JWE Compact Serialization, which only supports a single recipient.  Has the form
+    /// `<protected header>.<encrypted key>.<iv>.<ciphertext>.<tag>`, where each part is base64url-encoded.
+    Compact,
+    /// General JWE JSON Serialization, which supports any number of recipients.
+    JSON,
+}
@@ -0,0 +1,86 @@
+use crate::{Result, error, require};
+
+/// Returns true iff the given pub key is of a key type that can be used for JWE key agreement (i.e. ECDH),
+/// which is X25519 or P-256.
+pub(crate) fn is_supported_key_agreement_pub_key(pub_key: &mbx::MBPubKeyStr) -> bool {
+    did_webplus_core::x25519_public_key_bytes_from_mb_pub_key(pub_key).is_ok()
+        || pub_key.try_into_key_type().ok() == Some(signature_dyn::KeyType::P256)
+}
+
+/// Generate an ephemeral keypair of the same key type as the given recipient keyAgreement pub key, and
+/// derive the raw ECDH shared secret between them.  Returns the ephemeral pub key (for use in the "epk"
+/// header parameter) and the shared secret.  The ephemeral priv key is discarded.
+pub(crate) fn ephemeral_key_agreement(
+    recipient_pub_key: &mbx::MBPubKeyStr,
+) -> Result<(
+    did_webplus_core::PublicKeyParams,
+    zeroize::Zeroizing<Vec<u8>>,
+)> {
+    if let Ok(recipient_public_key_bytes) =
+        did_webplus_core::x25519_public_key_bytes_from_mb_pub_key(recipient_pub_key)
+    {
+        let mut ephemeral_scalar_bytes = zeroize::Zeroizing::new([0u8; 32]);
+        random_fill(ephemeral_scalar_bytes.as_mut_slice())?;
+        let ephemeral_public_key_bytes =
+            curve25519_dalek::MontgomeryPoint::mul_base_clamped(*ephemeral_scalar_bytes).to_bytes();
+        let shared_secret = zeroize::Zeroizing::new(
+            curve25519_dalek::MontgomeryPoint(recipient_public_key_bytes)
+                .mul_clamped(*ephemeral_scalar_bytes)
+                .to_bytes(),
+        );
+        // A low-order recipient pub key produces the all-zero shared secret, which must be rejected.
+        require!(
+            shared_secret.iter().any(|&b| b != 0),
+            "X25519 key agreement with {} produced an all-zero shared secret",
+            recipient_pub_key
+        );
+        let mut buffer = [0u8; 43];
+        let epk = did_webplus_core::PublicKeyParamsOKP::x25519(
+            did_webplus_core::base64_encode_256_bits(&ephemeral_public_key_bytes, &mut buffer)
+                .to_string(),
+        );
+        Ok((epk.into(), zeroize::Zeroizing::new(shared_secret.to_vec())))
+    } else if recipient_pub_key.try_into_key_type().ok() == Some(signature_dyn::KeyType::P256) {
+        let recipient_public_key = p256_public_key_from_mb_pub_key(recipient_pub_key)?;
+        // Rejection sampling; the probability of needing more than one iteration is negligible.
+        let ephemeral_secret_key = loop {
+            let mut ephemeral_secret_key_bytes = zeroize::Zeroizing::new([0u8; 32]);
+            random_fill(ephemeral_secret_key_bytes.as_mut_slice())?;
+            if let Ok(ephemeral_secret_key) =
+                p256::SecretKey::from_slice(ephemeral_secret_key_bytes.as_slice())
+            {
+                break ephemeral_secret_key;
+            }
+        };
+        let shared_secret = p256::ecdh::diffie_hellman(
+            ephemeral_secret_key.to_nonzero_scalar(),
+            recipient_public_key.as_affine(),
+        );
+        let ephemeral_verifying_key =
+            p256::ecdsa::VerifyingKey::from_affine(*ephemeral_secret_key.public_key().as_affine())
+                .map_err(|e| error!("Invalid ephemeral P-256 public key: {}", e))?;
+        let epk = did_webplus_core::PublicKeyParamsEC::try_from(&ephemeral_verifying_key)
+            .map_err(|e| error!("Failed to encode ephemeral P-256 public key: {}", e))?;
+        Ok((
+            epk.into(),
+            zeroize::Zeroizing::new(shared_secret.raw_secret_bytes().to_vec()),
+        ))
+    } else {
+        Err(error!(
+            "Unsupported key type for JWE key agreement: {}; only X25519 and P-256 are supported",
+            recipient_pub_key
+        ))
+    }
+}
+
+fn p256_public_key_from_mb_pub_key(pub_key: &mbx::MBPubKeyStr) -> Result<p256::PublicKey> {
+    let verifier_bytes = signature_dyn::VerifierBytes::try_from(pub_key)
+        .map_err(|e| error!("Invalid P-256 pub key {}: {}", pub_key, e))?;
+    p256::PublicKey::from_sec1_bytes(verifier_bytes.as_ref())
+        .map_err(|e| error!("Invalid P-256 pub key {}: {}", pub_key, e))
+}
+
+/// Fill the given buffer with cryptographically secure random bytes.
+pub(crate) fn random_fill(buffer: &mut [u8]) -> Result<()> {
+    getrandom::fill(buffer).map_err(|e| error!("Failed to generate random bytes: {}", e))
+}
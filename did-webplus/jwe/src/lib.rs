@@ -0,0 +1,16 @@
+mod concat_kdf;
+mod error;
+mod jwe;
+mod jwe_header;
+mod jwe_key_management_algorithm;
+mod jwe_recipient;
+mod jwe_recipient_key;
+mod jwe_serialization;
+mod key_agreement;
+
+pub use crate::{
+    error::Error, jwe::JWE, jwe_header::JWEHeader,
+    jwe_key_management_algorithm::JWEKeyManagementAlgorithm, jwe_recipient::JWERecipient,
+    jwe_recipient_key::JWERecipientKey, jwe_serialization::JWESerialization,
+};
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,213 @@
+use did_webplus_jwe::{
+    JWE, JWEHeader, JWEKeyManagementAlgorithm, JWERecipientKey, JWESerialization,
+};
+use std::str::FromStr;
+
+fn x25519_recipient_key(key_id: &str) -> JWERecipientKey {
+    JWERecipientKey {
+        key_id: did_webplus_core::DIDKeyResourceFullyQualified::from_str(key_id).expect("pass"),
+        pub_key: did_webplus_core::mb_pub_key_from_x25519_public_key_bytes(&rand::random())
+            .expect("pass"),
+    }
+}
+
+fn p256_recipient_key(key_id: &str) -> JWERecipientKey {
+    let signer_b = signature_dyn::KeyType::P256.generate_random_private_key();
+    JWERecipientKey {
+        key_id: did_webplus_core::DIDKeyResourceFullyQualified::from_str(key_id).expect("pass"),
+        pub_key: mbx::MBPubKey::try_from_verifier_bytes(
+            mbx::Base::Base64Url,
+            &signer_b.get_verifier().expect("pass").to_verifier_bytes(),
+        )
+        .expect("pass"),
+    }
+}
+
+const KEY_ID_0: &str = "did:webplus:example.com:uEiDGhNThUEd9HQ2h-nyPWbsm0esForTI8sp5IMKM2D63Yg?selfHash=uEiDGhNThUEd9HQ2h-nyPWbsm0esForTI8sp5IMKM2D63Yg&versionId=0#2";
+const KEY_ID_1: &str = "did:webplus:example.com:user:uEiDv2BoBvkWlFbe9WSEwvzC1ZBcAjagy9FqN7QshZtMzJQ?selfHash=uEiDv2BoBvkWlFbe9WSEwvzC1ZBcAjagy9FqN7QshZtMzJQ&versionId=0#3";
+
+#[test]
+fn test_jwe_encrypt_compact_serialization() {
+    let plaintext = br#"{"splunge": true}"#;
+    for recipient_key in [x25519_recipient_key(KEY_ID_0), p256_recipient_key(KEY_ID_0)] {
+        for key_management_algorithm in [
+            JWEKeyManagementAlgorithm::ECDHES,
+            JWEKeyManagementAlgorithm::ECDHESA256KW,
+        ] {
+            let jwe = JWE::encrypt(
+                plaintext,
+                std::slice::from_ref(&recipient_key),
+                key_management_algorithm,
+                JWESerialization::Compact,
+            )
+            .expect("pass");
+            // All header parameters are in the protected header.
+            let protected_header = jwe.protected_header().expect("pass");
+            assert_eq!(protected_header.enc.as_deref(), Some("A256GCM"));
+            assert_eq!(
+                protected_header.alg.as_deref(),
+                Some(key_management_algorithm.as_str())
+            );
+            assert_eq!(protected_header.kid.as_deref(), Some(KEY_ID_0));
+            assert!(protected_header.epk.is_some());
+            assert_eq!(jwe.recipients.len(), 1);
+            assert!(jwe.recipients[0].header.is_none());
+            assert_eq!(
+                jwe.recipients[0].encrypted_key.is_some(),
+                key_management_algorithm == JWEKeyManagementAlgorithm::ECDHESA256KW
+            );
+            // The epk must be of the same key type as the recipient's key.
+            let epk_pub_key =
+                mbx::MBPubKey::try_from(protected_header.epk.as_ref().unwrap()).expect("pass");
+            assert_eq!(
+                epk_pub_key.decoded().expect("pass").codec(),
+                recipient_key.pub_key.decoded().expect("pass").codec()
+            );
+
+            let compact_string = jwe.to_compact_string().expect("pass");
+            println!("compact JWE: {}", compact_string);
+            assert_eq!(compact_string.split('.').count(), 5);
+            assert_eq!(JWE::from_str(&compact_string).expect("pass"), jwe);
+            // The compact form can also be expressed as JSON.
+            let json_string = jwe.to_json_string().expect("pass");
+            assert_eq!(JWE::from_str(&json_string).expect("pass"), jwe);
+        }
+    }
+}
+
+#[test]
+fn test_jwe_encrypt_json_serialization_multiple_recipients() {
+    let plaintext = br#"{"splunge": true}"#;
+    let recipient_key_v = vec![x25519_recipient_key(KEY_ID_0), p256_recipient_key(KEY_ID_1)];
+    let jwe = JWE::encrypt(
+        plaintext,
+        &recipient_key_v,
+        JWEKeyManagementAlgorithm::ECDHESA256KW,
+        JWESerialization::JSON,
+    )
+    .expect("pass");
+    assert_eq!(
+        jwe.protected_header().expect("pass"),
+        JWEHeader {
+            enc: Some("A256GCM".to_string()),
+            ..Default::default()
+        }
+    );
+    assert_eq!(jwe.recipients.len(), 2);
+    for (recipient_index, recipient_key) in recipient_key_v.iter().enumerate() {
+        let recipient_header = jwe.recipient_header(recipient_index).expect("pass");
+        assert_eq!(recipient_header.alg.as_deref(), Some("ECDH-ES+A256KW"));
+        assert_eq!(
+            recipient_header.kid.as_deref(),
+            Some(recipient_key.key_id.as_str())
+        );
+        assert!(recipient_header.epk.is_some());
+        assert!(jwe.recipients[recipient_index].encrypted_key.is_some());
+    }
+    // The ephemeral keys must be distinct per recipient.
+    assert_ne!(
+        jwe.recipients[0].header.as_ref().unwrap().epk,
+        jwe.recipients[1].header.as_ref().unwrap().epk
+    );
+
+    let json_string = jwe.to_json_string().expect("pass");
+    println!("JSON JWE: {}", json_string);
+    assert_eq!(JWE::from_str(&json_string).expect("pass"), jwe);
+    // Multiple recipients can't be expressed in the compact serialization.
+    assert!(jwe.to_compact_string().is_err());
+}
+
+#[test]
+fn test_jwe_encrypt_invalid_parameters() {
+    let plaintext = b"hippos";
+    let recipient_key_v = vec![
+        x25519_recipient_key(KEY_ID_0),
+        x25519_recipient_key(KEY_ID_1),
+    ];
+    // No recipients.
+    assert!(
+        JWE::encrypt(
+            plaintext,
+            &[],
+            JWEKeyManagementAlgorithm::ECDHESA256KW,
+            JWESerialization::JSON,
+        )
+        .is_err()
+    );
+    // ECDH-ES only supports a single recipient.
+    assert!(
+        JWE::encrypt(
+            plaintext,
+            &recipient_key_v,
+            JWEKeyManagementAlgorithm::ECDHES,
+            JWESerialization::JSON,
+        )
+        .is_err()
+    );
+    // Compact serialization only supports a single recipient.
+    assert!(
+        JWE::encrypt(
+            plaintext,
+            &recipient_key_v,
+            JWEKeyManagementAlgorithm::ECDHESA256KW,
+            JWESerialization::Compact,
+        )
+        .is_err()
+    );
+    // Ed25519 keys can't be used for key agreement.
+    use signature_dyn::GenerateRandom;
+    let ed25519_recipient_key = JWERecipientKey {
+        key_id: did_webplus_core::DIDKeyResourceFullyQualified::from_str(KEY_ID_0).expect("pass"),
+        pub_key: mbx::MBPubKey::from_ed25519_dalek_verifying_key(
+            mbx::Base::Base64Url,
+            &ed25519_dalek::SigningKey::generate_random().verifying_key(),
+        ),
+    };
+    assert!(
+        JWE::encrypt(
+            plaintext,
+            &[ed25519_recipient_key],
+            JWEKeyManagementAlgorithm::ECDHESA256KW,
+            JWESerialization::JSON,
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_jwe_parse_flattened_json_serialization() {
+    let jwe = JWE::encrypt(
+        b"hippos",
+        &[x25519_recipient_key(KEY_ID_0)],
+        JWEKeyManagementAlgorithm::ECDHESA256KW,
+        JWESerialization::JSON,
+    )
+    .expect("pass");
+    let recipient = &jwe.recipients[0];
+    let flattened_value = serde_json::json!({
+        "protected": jwe.protected,
+        "header": recipient.header,
+        "encrypted_key": recipient.encrypted_key,
+        "iv": jwe.iv,
+        "ciphertext": jwe.ciphertext,
+        "tag": jwe.tag,
+    });
+    assert_eq!(
+        JWE::from_str(&flattened_value.to_string()).expect("pass"),
+        jwe
+    );
+}
+
+#[test]
+fn test_jwe_parse_malformed() {
+    for malformed in [
+        "",
+        "a.b.c.d",
+        "a.b.c.d.e.f",
+        "a.b.c.d.e!",
+        "{}",
+        r#"{"protected":"e30","recipients":[],"iv":"","ciphertext":"","tag":""}"#,
+    ] {
+        assert!(JWE::from_str(malformed).is_err(), "{:?}", malformed);
+    }
+}
@@ -41,8 +41,7 @@ impl<'j> JWS<'j> {
     }
     /// Indicates if the payload should be base64url-encoded in producing the signing input.
     pub fn payload_is_base64url_encoded(&self) -> bool {
-        // None, which is the default, is interpreted as true.
-        self.header.b64 != Some(false)
+        self.header.payload_is_base64url_encoded()
     }
     /// This is the attached payload, which is the value between the two '.' separators in the JWS.
     /// In the case of a detached payload, this will be an empty string.  However, an attached payload can
@@ -112,8 +111,27 @@ impl<'j> JWS<'j> {
         payload_presence: JWSPayloadPresence,
         payload_encoding: JWSPayloadEncoding,
         signer: &dyn signature_dyn::SignerT,
+    ) -> Result<Self> {
+        Self::signed_with_typ(
+            None,
+            kid,
+            payload_bytes,
+            payload_presence,
+            payload_encoding,
+            signer,
+        )
+    }
+    /// Like JWS::signed, but additionally sets the "typ" field of the header, if specified.
+    pub fn signed_with_typ(
+        typ_o: Option<String>,
+        kid: String,
+        payload_bytes: &mut dyn std::io::Read,
+        payload_presence: JWSPayloadPresence,
+        payload_encoding: JWSPayloadEncoding,
+        signer: &dyn signature_dyn::SignerT,
     ) -> Result<Self> {
         let (header, signing_input, jws) = Self::assemble_header_signing_input_and_jws(
+            typ_o,
             kid,
             payload_bytes,
             payload_presence,
@@ -134,8 +152,28 @@ impl<'j> JWS<'j> {
         payload_presence: JWSPayloadPresence,
         payload_encoding: JWSPayloadEncoding,
         async_signer: &dyn signature_dyn::AsyncSignerT,
+    ) -> Result<Self> {
+        Self::async_signed_with_typ(
+            None,
+            kid,
+            payload_bytes,
+            payload_presence,
+            payload_encoding,
+            async_signer,
+        )
+        .await
+    }
+    /// Async version of JWS::signed_with_typ.
+    pub async fn async_signed_with_typ(
+        typ_o: Option<String>,
+        kid: String,
+        payload_bytes: &mut dyn std::io::Read,
+        payload_presence: JWSPayloadPresence,
+        payload_encoding: JWSPayloadEncoding,
+        async_signer: &dyn signature_dyn::AsyncSignerT,
     ) -> Result<Self> {
         let (header, signing_input, jws) = Self::assemble_header_signing_input_and_jws(
+            typ_o,
             kid,
             payload_bytes,
             payload_presence,
@@ -153,26 +191,22 @@ impl<'j> JWS<'j> {
 
         Self::attach_signature_to_jws(header, jws, signature)
     }
-    /// Returns the header, signing input, and JWS for the given kid, payload bytes, payload presence,
+    /// Returns the header, signing input, and JWS for the given typ, kid, payload bytes, payload presence,
     /// and payload encoding.
     fn assemble_header_signing_input_and_jws(
+        typ_o: Option<String>,
         kid: String,
         payload_bytes: &mut dyn std::io::Read,
         payload_presence: JWSPayloadPresence,
         payload_encoding: JWSPayloadEncoding,
         signer_key_type: signature_dyn::KeyType,
     ) -> Result<(JWSHeader, Vec<u8>, Vec<u8>)> {
-        let (crit, b64) = if payload_encoding == JWSPayloadEncoding::Base64 {
-            (None, None)
-        } else {
-            (Some(vec![String::from("b64")]), Some(false))
-        };
-        let header = JWSHeader {
-            alg: signer_key_type.jose_algorithm().to_string(),
+        let mut header = JWSHeader::new(
+            signer_key_type.jose_algorithm().to_string(),
             kid,
-            crit,
-            b64,
-        };
+            payload_encoding,
+        );
+        header.typ = typ_o;
 
         // The signing input is `base64url(json(header)) || '.' || base64url(payload)`
         // if the payload is encoded (see RFC 7515, https://datatracker.ietf.org/doc/html/rfc7515)
@@ -417,7 +451,7 @@ impl<'j> TryFrom<Cow<'j, str>> for JWS<'j> {
 }
 
 /// This is base64url without padding.
-fn is_base64url_encoded(s: &str) -> bool {
+pub(crate) fn is_base64url_encoded(s: &str) -> bool {
     // Base64urlnopad encoding is a subset of base64url encoding, so we can just check for the presence of
     // characters that are not in the base64url-nopad alphabet.
     s.chars()
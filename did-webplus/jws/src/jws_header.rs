@@ -1,3 +1,5 @@
+use crate::JWSPayloadEncoding;
+
 // use did_webplus_core::DIDKeyResourceFullyQualified;
 
 /// See RFC 7515, <https://datatracker.ietf.org/doc/html/rfc7515>
@@ -5,6 +7,9 @@
 pub struct JWSHeader {
     /// Signature algorithm used to sign the JWS.
     pub alg: String,
+    /// Optionally specifies the media type of the complete JWS, e.g. "JWT" or "kb+jwt".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typ: Option<String>,
     /// Specifies the public key in some way.  This could be using a DID, or a multibase-encoded key.
     /// If it is a DID, then it could include any query parameters that are required by the DID method.
     pub kid: String,
@@ -16,3 +21,33 @@ pub struct JWSHeader {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub b64: Option<bool>,
 }
+
+impl JWSHeader {
+    /// Returns the header for a JWS signed with the given alg and kid, where "crit" and "b64" are set
+    /// according to the payload encoding (see RFC 7797).
+    pub(crate) fn new(alg: String, kid: String, payload_encoding: JWSPayloadEncoding) -> Self {
+        let (crit, b64) = if payload_encoding == JWSPayloadEncoding::Base64 {
+            (None, None)
+        } else {
+            (Some(vec![String::from("b64")]), Some(false))
+        };
+        Self {
+            alg,
+            typ: None,
+            kid,
+            crit,
+            b64,
+        }
+    }
+    /// Indicates if the payload should be base64url-encoded in producing the signing input.
+    pub fn payload_is_base64url_encoded(&self) -> bool {
+        // None, which is the default, is interpreted as true.
+        self.b64 != Some(false)
+    }
+}
+
+/// Returns the part of the kid that identifies the signer, i.e. everything before any query or fragment.
+/// For a DID-based kid, this is the DID.
+pub(crate) fn signer_of_kid(kid: &str) -> &str {
+    kid.split(['?', '#']).next().unwrap()
+}
@@ -0,0 +1,540 @@
+use crate::{
+    Error, JWS, JWSHeader, JWSJSONSignature, JWSPayloadEncoding, JWSPayloadPresence,
+    JWSSerialization, Result, error, jws::is_base64url_encoded, require,
+};
+use base64::Engine;
+
+/// This is a JWS in JWS JSON Serialization (RFC 7515 Section 7.2), which, unlike JWS Compact Serialization
+/// (see JWS), can carry multiple signatures over the same payload, e.g. co-signatures from several DIDs.
+/// Both the General syntax (any number of signatures) and the Flattened syntax (a single signature) are
+/// supported.  Has the form
+/// `{"payload":<payload>,"signatures":[{"protected":<base64url(JSON(header))>,"header":{...},"signature":<base64url(signature)>},...]}`
+/// where "payload" is base64url(payload) if encoded, the payload itself if unencoded, and absent if detached.
+/// All signatures must agree on whether the payload is encoded (RFC 7797 Section 3).
+#[derive(Clone, Debug)]
+pub struct JWSJSON {
+    /// The payload as it appears in the signing input, or None if the payload is detached.
+    payload_o: Option<String>,
+    /// The signatures, each with its own protected and unprotected headers.
+    signature_v: Vec<JWSJSONSignature>,
+}
+
+/// The per-signature members of the JWS JSON Serialization.
+#[derive(serde::Deserialize, serde::Serialize)]
+struct SignatureMembers {
+    protected: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    header: Option<serde_json::Map<String, serde_json::Value>>,
+    signature: String,
+}
+
+/// The General JWS JSON Serialization (RFC 7515 Section 7.2.1).
+#[derive(serde::Deserialize, serde::Serialize)]
+struct GeneralJWSJSON {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payload: Option<String>,
+    signatures: Vec<SignatureMembers>,
+}
+
+/// The Flattened JWS JSON Serialization (RFC 7515 Section 7.2.2), in which the single signature's members
+/// are at the top level.
+#[derive(serde::Deserialize, serde::Serialize)]
+struct FlattenedJWSJSON {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payload: Option<String>,
+    #[serde(flatten)]
+    signature: SignatureMembers,
+}
+
+impl JWSJSON {
+    /// Returns the signatures.
+    pub fn signatures(&self) -> &[JWSJSONSignature] {
+        self.signature_v.as_slice()
+    }
+    /// Sets the unprotected header of the signature at the given index.  Because the unprotected header
+    /// is not covered by the signature, this doesn't invalidate it.
+    pub fn set_unprotected_header(
+        &mut self,
+        signature_index: usize,
+        unprotected_header_o: Option<serde_json::Map<String, serde_json::Value>>,
+    ) -> Result<()> {
+        let signature_count = self.signature_v.len();
+        self.signature_v
+            .get_mut(signature_index)
+            .ok_or_else(|| {
+                error!(
+                    "JWS signature index {} is out of range (there are {} signatures)",
+                    signature_index, signature_count
+                )
+            })?
+            .set_unprotected_header(unprotected_header_o)
+    }
+    /// Indicates if the payload should be base64url-encoded in producing the signing input.
+    pub fn payload_is_base64url_encoded(&self) -> bool {
+        self.signature_v[0].header().payload_is_base64url_encoded()
+    }
+    /// This is the attached payload as it appears in the signing input, or None if the payload is detached.
+    pub fn raw_attached_payload_str_o(&self) -> Option<&str> {
+        self.payload_o.as_deref()
+    }
+    /// Returns a reader into the decoded payload bytes.  If detached_payload_bytes_o is Some(_), then the
+    /// payload is detached, and the payload is read from detached_payload_bytes_o.  Otherwise, the payload
+    /// is read from the JWS itself, and it's an error if the payload is detached.
+    pub fn decoded_payload_bytes<'r, 's: 'r>(
+        &'s self,
+        detached_payload_bytes_o: Option<&'r mut dyn std::io::Read>,
+    ) -> Result<Box<dyn std::io::Read + 'r>> {
+        if let Some(detached_payload_bytes) = detached_payload_bytes_o {
+            require!(
+                self.payload_o.is_none(),
+                "if the JWS has an attached payload, then no detached payload may be specified"
+            );
+            if self.payload_is_base64url_encoded() {
+                Ok(Box::new(base64::read::DecoderReader::new(
+                    detached_payload_bytes,
+                    &base64::engine::general_purpose::URL_SAFE_NO_PAD,
+                )))
+            } else {
+                Ok(Box::new(detached_payload_bytes))
+            }
+        } else {
+            let payload = self.payload_o.as_deref().ok_or_else(|| {
+                error!("JWS payload is detached, so a detached payload must be specified")
+            })?;
+            if self.payload_is_base64url_encoded() {
+                Ok(Box::new(base64::read::DecoderReader::new(
+                    payload.as_bytes(),
+                    &base64::engine::general_purpose::URL_SAFE_NO_PAD,
+                )))
+            } else {
+                Ok(Box::new(payload.as_bytes()))
+            }
+        }
+    }
+    /// This parses the payload as the given type, base64url-decoding the payload first if necessary.
+    pub fn parsed_decoded_payload<'r, 's: 'r, T: serde::de::DeserializeOwned>(
+        &'s self,
+        detached_payload_bytes_o: Option<&'r mut dyn std::io::Read>,
+    ) -> Result<T> {
+        serde_json::from_reader(self.decoded_payload_bytes(detached_payload_bytes_o)?).map_err(
+            |e| {
+                error!(
+                    "JWS payload failed to parse as expected JSON structure: {}",
+                    e
+                )
+            },
+        )
+    }
+    /// Generate a JWS JSON Serialization of the given payload, signed by each of the given signers, where
+    /// each signer is paired with the kid to put in its protected header.  There must be at least one signer.
+    pub fn signed(
+        payload_bytes: &mut dyn std::io::Read,
+        payload_presence: JWSPayloadPresence,
+        payload_encoding: JWSPayloadEncoding,
+        signer_v: &[(String, &dyn signature_dyn::SignerT)],
+    ) -> Result<Self> {
+        require!(!signer_v.is_empty(), "JWS must have at least one signer");
+        let (payload_o, payload_signing_input) =
+            Self::read_payload(payload_bytes, payload_presence, payload_encoding)?;
+        let mut signature_v = Vec::with_capacity(signer_v.len());
+        for (kid, signer) in signer_v {
+            let (header, protected, signing_input) = Self::assemble_header_and_signing_input(
+                kid.clone(),
+                signer.key_type(),
+                payload_encoding,
+                payload_signing_input.as_slice(),
+            )?;
+            let signature = signer
+                .try_sign_message(signing_input.as_slice())
+                .map_err(|e| error!("error while signing JWS: {}", e))?;
+            signature_v.push(JWSJSONSignature::new(
+                header,
+                protected,
+                None,
+                signature.to_signature_bytes().into_owned(),
+            )?);
+        }
+        Ok(Self {
+            payload_o,
+            signature_v,
+        })
+    }
+    /// Async version of JWSJSON::signed, e.g. for signing with multiple WalletBasedSigners.
+    pub async fn async_signed(
+        payload_bytes: &mut dyn std::io::Read,
+        payload_presence: JWSPayloadPresence,
+        payload_encoding: JWSPayloadEncoding,
+        async_signer_v: &[(String, &dyn signature_dyn::AsyncSignerT)],
+    ) -> Result<Self> {
+        require!(
+            !async_signer_v.is_empty(),
+            "JWS must have at least one signer"
+        );
+        let (payload_o, payload_signing_input) =
+            Self::read_payload(payload_bytes, payload_presence, payload_encoding)?;
+        let mut signature_v = Vec::with_capacity(async_signer_v.len());
+        for (kid, async_signer) in async_signer_v {
+            let key_type = async_signer
+                .async_key_type()
+                .await
+                .map_err(|e| error!("error while getting key type: {}", e))?;
+            let (header, protected, signing_input) = Self::assemble_header_and_signing_input(
+                kid.clone(),
+                key_type,
+                payload_encoding,
+                payload_signing_input.as_slice(),
+            )?;
+            let signature = async_signer
+                .async_try_sign_message(signing_input.as_slice())
+                .await
+                .map_err(|e| error!("error while signing JWS: {}", e))?;
+            signature_v.push(JWSJSONSignature::new(
+                header,
+                protected,
+                None,
+                signature.to_signature_bytes().into_owned(),
+            )?);
+        }
+        Ok(Self {
+            payload_o,
+            signature_v,
+        })
+    }
+    /// Adds a signature by the given signer (e.g. a co-signer) over this JWS's payload.  detached_payload_bytes_o
+    /// should be Some(_) if the payload is detached, and None if it's attached.  The new signature uses the same
+    /// payload encoding as the existing signatures.
+    pub fn add_signature(
+        &mut self,
+        kid: String,
+        signer: &dyn signature_dyn::SignerT,
+        detached_payload_bytes_o: Option<&mut dyn std::io::Read>,
+    ) -> Result<()> {
+        let payload_signing_input = self.payload_signing_input(detached_payload_bytes_o)?;
+        let (header, protected, signing_input) = Self::assemble_header_and_signing_input(
+            kid,
+            signer.key_type(),
+            self.payload_encoding(),
+            payload_signing_input.as_slice(),
+        )?;
+        let signature = signer
+            .try_sign_message(signing_input.as_slice())
+            .map_err(|e| error!("error while signing JWS: {}", e))?;
+        self.signature_v.push(JWSJSONSignature::new(
+            header,
+            protected,
+            None,
+            signature.to_signature_bytes().into_owned(),
+        )?);
+        Ok(())
+    }
+    /// Async version of JWSJSON::add_signature.
+    pub async fn async_add_signature(
+        &mut self,
+        kid: String,
+        async_signer: &dyn signature_dyn::AsyncSignerT,
+        detached_payload_bytes_o: Option<&mut dyn std::io::Read>,
+    ) -> Result<()> {
+        let payload_signing_input = self.payload_signing_input(detached_payload_bytes_o)?;
+        let key_type = async_signer
+            .async_key_type()
+            .await
+            .map_err(|e| error!("error while getting key type: {}", e))?;
+        let (header, protected, signing_input) = Self::assemble_header_and_signing_input(
+            kid,
+            key_type,
+            self.payload_encoding(),
+            payload_signing_input.as_slice(),
+        )?;
+        let signature = async_signer
+            .async_try_sign_message(signing_input.as_slice())
+            .await
+            .map_err(|e| error!("error while signing JWS: {}", e))?;
+        self.signature_v.push(JWSJSONSignature::new(
+            header,
+            protected,
+            None,
+            signature.to_signature_bytes().into_owned(),
+        )?);
+        Ok(())
+    }
+    /// Verifies the signature at the given index using the given verifier.  detached_payload_bytes_o should be
+    /// Some(_) if it's a detached payload, and None if it's an attached payload.  Deciding which verifier to use
+    /// for each signature and how many signatures must verify (see JWSSignerPolicy) is up to the caller.
+    pub fn verify_signature(
+        &self,
+        signature_index: usize,
+        verifier: &dyn signature_dyn::VerifierT,
+        detached_payload_bytes_o: Option<&mut dyn std::io::Read>,
+    ) -> Result<()> {
+        let signature = self.signature_v.get(signature_index).ok_or_else(|| {
+            error!(
+                "JWS signature index {} is out of range (there are {} signatures)",
+                signature_index,
+                self.signature_v.len()
+            )
+        })?;
+        // Verify the JWS alg field matches the verifier.
+        require!(
+            signature.header().alg.as_str() == verifier.jose_algorithm(),
+            "JWS alg {:?} does not match that of the verifier {:?}",
+            signature.header().alg,
+            verifier.jose_algorithm()
+        );
+
+        // The signing input is `base64url(json(protected header)) || '.' || <payload signing input>`.
+        let payload_signing_input = self.payload_signing_input(detached_payload_bytes_o)?;
+        let signing_input = [
+            signature.raw_protected_header_base64().as_bytes(),
+            b".",
+            payload_signing_input.as_slice(),
+        ]
+        .concat();
+
+        // Verify the signature.
+        verifier
+            .verify_message(signing_input.as_slice(), signature.signature_bytes())
+            .map_err(|e| error!("JWS failed to verify; error was: {}", e))?;
+
+        Ok(())
+    }
+    /// Converts this into a JWS Compact Serialization, which is only possible if there is exactly one
+    /// signature and it has no unprotected header.
+    pub fn to_compact_jws(&self) -> Result<JWS<'static>> {
+        require!(
+            self.signature_v.len() == 1,
+            "JWS Compact Serialization supports exactly one signature, but there are {}",
+            self.signature_v.len()
+        );
+        let signature = &self.signature_v[0];
+        require!(
+            signature.unprotected_header_o().is_none(),
+            "JWS Compact Serialization does not support an unprotected header"
+        );
+        JWS::try_from(format!(
+            "{}.{}.{}",
+            signature.raw_protected_header_base64(),
+            self.payload_o.as_deref().unwrap_or(""),
+            signature.raw_signature_base64()
+        ))
+    }
+    /// Produces the General JWS JSON Serialization.
+    pub fn to_general_json_string(&self) -> Result<String> {
+        Ok(serde_json::to_string(&GeneralJWSJSON {
+            payload: self.payload_o.clone(),
+            signatures: self.signature_v.iter().map(signature_members_of).collect(),
+        })?)
+    }
+    /// Produces the Flattened JWS JSON Serialization, which is only possible if there is exactly one signature.
+    pub fn to_flattened_json_string(&self) -> Result<String> {
+        require!(
+            self.signature_v.len() == 1,
+            "Flattened JWS JSON Serialization supports exactly one signature, but there are {}",
+            self.signature_v.len()
+        );
+        Ok(serde_json::to_string(&FlattenedJWSJSON {
+            payload: self.payload_o.clone(),
+            signature: signature_members_of(&self.signature_v[0]),
+        })?)
+    }
+    /// Produces the specified serialization.
+    pub fn to_serialization_string(&self, serialization: JWSSerialization) -> Result<String> {
+        match serialization {
+            JWSSerialization::Compact => Ok(self.to_compact_jws()?.into_string()),
+            JWSSerialization::General => self.to_general_json_string(),
+            JWSSerialization::Flattened => self.to_flattened_json_string(),
+        }
+    }
+    fn payload_encoding(&self) -> JWSPayloadEncoding {
+        if self.payload_is_base64url_encoded() {
+            JWSPayloadEncoding::Base64
+        } else {
+            JWSPayloadEncoding::None
+        }
+    }
+    /// Reads the payload into its signing input form (i.e. base64url-encoded if the payload encoding is
+    /// Base64), returning the attached payload (None if detached) along with the payload signing input.
+    fn read_payload(
+        payload_bytes: &mut dyn std::io::Read,
+        payload_presence: JWSPayloadPresence,
+        payload_encoding: JWSPayloadEncoding,
+    ) -> Result<(Option<String>, Vec<u8>)> {
+        let mut payload_signing_input = Vec::<u8>::new();
+        if payload_encoding == JWSPayloadEncoding::Base64 {
+            let mut base64url_encoder = base64::write::EncoderWriter::new(
+                &mut payload_signing_input,
+                &base64::engine::general_purpose::URL_SAFE_NO_PAD,
+            );
+            std::io::copy(payload_bytes, &mut base64url_encoder)
+                .map_err(|e| error!("error in writing encoded JWS payload: {}", e))?;
+        } else {
+            std::io::copy(payload_bytes, &mut payload_signing_input)
+                .map_err(|e| error!("error in writing unencoded JWS payload: {}", e))?;
+        }
+        let payload_o =
+            if payload_presence == JWSPayloadPresence::Attached {
+                // An unencoded, attached payload appears as a JSON string, so it must be UTF-8.
+                Some(String::from_utf8(payload_signing_input.clone()).map_err(|_| {
+                error!("unencoded, attached JWS payload must be UTF-8 in JWS JSON Serialization")
+            })?)
+            } else {
+                None
+            };
+        Ok((payload_o, payload_signing_input))
+    }
+    /// Returns the payload as it appears in the signing input, using the detached payload if specified.
+    fn payload_signing_input(
+        &self,
+        detached_payload_bytes_o: Option<&mut dyn std::io::Read>,
+    ) -> Result<Vec<u8>> {
+        if let Some(detached_payload_bytes) = detached_payload_bytes_o {
+            require!(
+                self.payload_o.is_none(),
+                "if the JWS has an attached payload, then no detached payload may be specified"
+            );
+            let mut payload_signing_input = Vec::<u8>::new();
+            if self.payload_is_base64url_encoded() {
+                let mut base64url_encoder = base64::write::EncoderWriter::new(
+                    &mut payload_signing_input,
+                    &base64::engine::general_purpose::URL_SAFE_NO_PAD,
+                );
+                std::io::copy(detached_payload_bytes, &mut base64url_encoder)
+                    .map_err(|e| error!("error while writing base64url-encoded payload: {}", e))?;
+            } else {
+                std::io::copy(detached_payload_bytes, &mut payload_signing_input)
+                    .map_err(|e| error!("error while writing payload: {}", e))?;
+            }
+            Ok(payload_signing_input)
+        } else {
+            let payload = self.payload_o.as_deref().ok_or_else(|| {
+                error!("JWS payload is detached, so a detached payload must be specified")
+            })?;
+            Ok(payload.as_bytes().to_vec())
+        }
+    }
+    /// Returns the protected header, base64url(JSON(protected header)), and the signing input
+    /// `base64url(JSON(protected header)) || '.' || <payload signing input>`.
+    fn assemble_header_and_signing_input(
+        kid: String,
+        signer_key_type: signature_dyn::KeyType,
+        payload_encoding: JWSPayloadEncoding,
+        payload_signing_input: &[u8],
+    ) -> Result<(JWSHeader, String, Vec<u8>)> {
+        let header = JWSHeader::new(
+            signer_key_type.jose_algorithm().to_string(),
+            kid,
+            payload_encoding,
+        );
+        let protected = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(
+            serde_json::to_vec(&header)
+                .map_err(|e| error!("error while writing JWS header: {}", e))?,
+        );
+        let signing_input = [protected.as_bytes(), b".", payload_signing_input].concat();
+        Ok((header, protected, signing_input))
+    }
+    /// All signatures must agree on whether the payload is encoded, and an attached, encoded payload must be
+    /// base64url-encoded.
+    fn check_payload_encoding(&self) -> Result<()> {
+        require!(
+            !self.signature_v.is_empty(),
+            "JWS must have at least one signature"
+        );
+        let payload_is_base64url_encoded = self.payload_is_base64url_encoded();
+        require!(
+            self.signature_v.iter().all(|signature| signature
+                .header()
+                .payload_is_base64url_encoded()
+                == payload_is_base64url_encoded),
+            "all JWS signatures must use the same \"b64\" payload encoding"
+        );
+        if let Some(payload) = self.payload_o.as_deref() {
+            require!(
+                !payload_is_base64url_encoded || is_base64url_encoded(payload),
+                "JWS payload was expected to be base64url-encoded"
+            );
+        }
+        Ok(())
+    }
+}
+
+fn signature_members_of(signature: &JWSJSONSignature) -> SignatureMembers {
+    SignatureMembers {
+        protected: signature.raw_protected_header_base64().to_string(),
+        header: signature.unprotected_header_o().cloned(),
+        signature: signature.raw_signature_base64(),
+    }
+}
+
+/// Converts a JWS Compact Serialization into a single-signature JWS JSON Serialization, e.g. so that co-signatures
+/// can be added to it.  Because JWS Compact Serialization can't distinguish an empty attached payload from a
+/// detached payload, an empty payload is treated as detached.
+impl From<&JWS<'_>> for JWSJSON {
+    fn from(jws: &JWS<'_>) -> Self {
+        let payload = jws.raw_attached_payload_str();
+        Self {
+            payload_o: if payload.is_empty() {
+                None
+            } else {
+                Some(payload.to_string())
+            },
+            signature_v: vec![
+                JWSJSONSignature::new(
+                    jws.header().clone(),
+                    jws.raw_header_base64().to_string(),
+                    None,
+                    jws.signature_bytes().clone().into_owned(),
+                )
+                .expect("programmer error: JWS without an unprotected header is always valid"),
+            ],
+        }
+    }
+}
+
+impl std::fmt::Display for JWSJSON {
+    /// Writes the General JWS JSON Serialization.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            self.to_general_json_string()
+                .map_err(|_| std::fmt::Error)?
+                .as_str(),
+        )
+    }
+}
+
+impl std::str::FromStr for JWSJSON {
+    type Err = Error;
+    /// Parses the General JWS JSON Serialization, the Flattened JWS JSON Serialization, or the JWS Compact
+    /// Serialization (see `From<&JWS>`).
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if !s.starts_with('{') {
+            return Ok(Self::from(&JWS::try_from(s)?));
+        }
+        let value: serde_json::Value = serde_json::from_str(s)?;
+        let (payload_o, signature_members_v) = if value.get("signatures").is_some() {
+            let general_jws_json = serde_json::from_value::<GeneralJWSJSON>(value)?;
+            (general_jws_json.payload, general_jws_json.signatures)
+        } else {
+            let flattened_jws_json = serde_json::from_value::<FlattenedJWSJSON>(value)?;
+            (
+                flattened_jws_json.payload,
+                vec![flattened_jws_json.signature],
+            )
+        };
+        let signature_v = signature_members_v
+            .into_iter()
+            .map(|signature_members| {
+                JWSJSONSignature::parse(
+                    signature_members.protected,
+                    signature_members.header,
+                    signature_members.signature.as_str(),
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let jws_json = Self {
+            payload_o,
+            signature_v,
+        };
+        jws_json.check_payload_encoding()?;
+        Ok(jws_json)
+    }
+}
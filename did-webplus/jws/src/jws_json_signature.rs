@@ -0,0 +1,134 @@
+use crate::{JWSHeader, Result, error, jws::is_base64url_encoded, require};
+use base64::Engine;
+
+/// A single signature within a JWS JSON Serialization (see JWSJSON).  Each signature has its own protected
+/// header, which must specify "alg" and "kid" so that the signing key is covered by the signature, and an
+/// optional unprotected header, which is carried along but not used in verification.
+#[derive(Clone, Debug)]
+pub struct JWSJSONSignature {
+    /// base64url(JSON(protected header)).
+    protected: String,
+    /// Parsed protected header.
+    header: JWSHeader,
+    /// The optional unprotected header, whose parameter names must be disjoint from those of the protected header.
+    unprotected_header_o: Option<serde_json::Map<String, serde_json::Value>>,
+    /// Parsed signature.
+    signature_bytes: signature_dyn::SignatureBytes<'static>,
+}
+
+impl JWSJSONSignature {
+    /// Constructs a signature from its protected header, unprotected header, and signature bytes,
+    /// as produced when signing.
+    pub(crate) fn new(
+        header: JWSHeader,
+        protected: String,
+        unprotected_header_o: Option<serde_json::Map<String, serde_json::Value>>,
+        signature_bytes: signature_dyn::SignatureBytes<'static>,
+    ) -> Result<Self> {
+        let signature = Self {
+            protected,
+            header,
+            unprotected_header_o,
+            signature_bytes,
+        };
+        signature.check_unprotected_header()?;
+        Ok(signature)
+    }
+    /// Parses a signature from the "protected", "header", and "signature" members of a JWS JSON Serialization.
+    pub(crate) fn parse(
+        protected: String,
+        unprotected_header_o: Option<serde_json::Map<String, serde_json::Value>>,
+        signature_base64: &str,
+    ) -> Result<Self> {
+        require!(
+            is_base64url_encoded(protected.as_str()),
+            "JWS protected header is not base64url-encoded"
+        );
+        require!(
+            is_base64url_encoded(signature_base64),
+            "JWS signature is not base64url-encoded"
+        );
+        let header = serde_json::from_reader::<_, JWSHeader>(base64::read::DecoderReader::new(
+            protected.as_bytes(),
+            &base64::engine::general_purpose::URL_SAFE_NO_PAD,
+        ))
+        .map_err(|e| {
+            error!(
+                "JWS protected header failed to parse as expected JSON structure: {}",
+                e
+            )
+        })?;
+        // See RFC 7797 Section 6.
+        if header.b64 == Some(false) {
+            require!(
+                header
+                    .crit
+                    .as_deref()
+                    .is_some_and(|crit| crit.iter().any(|c| c == "b64")),
+                "JWS protected header has \"b64\": false but \"crit\" does not include \"b64\""
+            );
+        }
+        let signature_byte_v = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(signature_base64.as_bytes())
+            .map_err(|_| error!("JWS signature failed to parse"))?;
+        let signature_bytes =
+            signature_dyn::SignatureBytes::new(header.alg.as_str(), signature_byte_v.into())
+                .map_err(|e| error!("error while creating signature bytes: {}", e))?;
+        Self::new(header, protected, unprotected_header_o, signature_bytes)
+    }
+    /// This is the base64url-encoded protected header.
+    pub fn raw_protected_header_base64(&self) -> &str {
+        self.protected.as_str()
+    }
+    /// Returns the parsed protected header.
+    pub fn header(&self) -> &JWSHeader {
+        &self.header
+    }
+    /// Returns the unprotected header, if present.  Note that this is not covered by the signature.
+    pub fn unprotected_header_o(&self) -> Option<&serde_json::Map<String, serde_json::Value>> {
+        self.unprotected_header_o.as_ref()
+    }
+    /// Sets the unprotected header.  Because it's not covered by the signature, this doesn't invalidate
+    /// the signature.
+    pub fn set_unprotected_header(
+        &mut self,
+        unprotected_header_o: Option<serde_json::Map<String, serde_json::Value>>,
+    ) -> Result<()> {
+        let previous_unprotected_header_o =
+            std::mem::replace(&mut self.unprotected_header_o, unprotected_header_o);
+        if let Err(e) = self.check_unprotected_header() {
+            self.unprotected_header_o = previous_unprotected_header_o;
+            return Err(e);
+        }
+        Ok(())
+    }
+    /// This is the base64url-encoded signature.
+    pub fn raw_signature_base64(&self) -> String {
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(self.signature_bytes.bytes())
+    }
+    /// This is the parsed signature.
+    pub fn signature_bytes(&self) -> &signature_dyn::SignatureBytes<'_> {
+        &self.signature_bytes
+    }
+    /// The unprotected header must not repeat any protected header parameter (RFC 7515 Section 7.2.1),
+    /// and "crit" and "b64" must only appear in the protected header (RFC 7515 Section 4.1.11 and RFC 7797
+    /// Section 3).
+    fn check_unprotected_header(&self) -> Result<()> {
+        if let Some(unprotected_header) = self.unprotected_header_o.as_ref() {
+            let protected_header_value = serde_json::to_value(&self.header)?;
+            for name in unprotected_header.keys() {
+                require!(
+                    name != "crit" && name != "b64",
+                    "JWS unprotected header must not contain {:?}",
+                    name
+                );
+                require!(
+                    protected_header_value.get(name).is_none(),
+                    "JWS unprotected header parameter {:?} is also present in the protected header",
+                    name
+                );
+            }
+        }
+        Ok(())
+    }
+}
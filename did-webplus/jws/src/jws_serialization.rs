@@ -0,0 +1,12 @@
+/// Specifies which JWS serialization to produce (see RFC 7515 Section 7).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "wasm-bindgen", wasm_bindgen::prelude::wasm_bindgen)]
+pub enum JWSSerialization {
+    /// JWS Compact Serialization, which only supports a single signature.  See JWS.
+    Compact,
+    /// General JWS JSON Serialization, which supports any number of signatures.  See JWSJSON.
+    General,
+    /// Flattened JWS JSON Serialization, which only supports a single signature.  See JWSJSON.
+    Flattened,
+}
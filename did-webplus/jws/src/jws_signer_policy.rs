@@ -0,0 +1,92 @@
+use crate::{Error, Result, bail, error, jws_header::signer_of_kid};
+
+/// Specifies how many of the signatures of a multi-signature JWS (see JWSJSON) must verify for the
+/// JWS as a whole to be considered valid.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JWSSignerPolicy {
+    /// Every signature must verify.
+    All,
+    /// At least one signature must verify.
+    Any,
+    /// At least k distinct signers must have produced signatures that verify.  Signers are distinguished
+    /// by the "kid" field with any query and fragment removed (i.e. by DID), so that a single party
+    /// signing with several keys (or several versions of the same key) only counts once.
+    KOfN(usize),
+}
+
+impl JWSSignerPolicy {
+    /// Checks this policy, where signature_count is the total number of signatures in the JWS, and
+    /// verified_kid_v contains the "kid" field of each signature that verified.
+    pub fn check(&self, signature_count: usize, verified_kid_v: &[&str]) -> Result<()> {
+        match self {
+            Self::All => {
+                if signature_count == 0 || verified_kid_v.len() < signature_count {
+                    bail!(
+                        "JWS signer policy \"all\" requires all {} signature(s) to verify, but only {} did",
+                        signature_count,
+                        verified_kid_v.len()
+                    );
+                }
+            }
+            Self::Any => {
+                if verified_kid_v.is_empty() {
+                    bail!(
+                        "JWS signer policy \"any\" requires at least one of {} signature(s) to verify, but none did",
+                        signature_count
+                    );
+                }
+            }
+            Self::KOfN(k) => {
+                let mut signer_v = verified_kid_v
+                    .iter()
+                    .map(|kid| signer_of_kid(kid))
+                    .collect::<Vec<_>>();
+                signer_v.sort_unstable();
+                signer_v.dedup();
+                if signer_v.len() < *k {
+                    bail!(
+                        "JWS signer policy \"{}-of-n\" requires signatures from at least {} distinct signer(s) to verify, but only {} did",
+                        k,
+                        k,
+                        signer_v.len()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for JWSSignerPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::All => f.write_str("all"),
+            Self::Any => f.write_str("any"),
+            Self::KOfN(k) => write!(f, "{}-of-n", k),
+        }
+    }
+}
+
+impl std::str::FromStr for JWSSignerPolicy {
+    type Err = Error;
+    /// Parses "all", "any", or "<k>-of-n" (where <k> is a positive integer).
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "all" => Ok(Self::All),
+            "any" => Ok(Self::Any),
+            _ => {
+                let k = s
+                    .strip_suffix("-of-n")
+                    .and_then(|k_str| k_str.parse::<usize>().ok())
+                    .filter(|&k| k > 0)
+                    .ok_or_else(|| {
+                        error!(
+                            "Invalid JWS signer policy {:?}; expected \"all\", \"any\", or \"<k>-of-n\" where <k> is a positive integer",
+                            s
+                        )
+                    })?;
+                Ok(Self::KOfN(k))
+            }
+        }
+    }
+}
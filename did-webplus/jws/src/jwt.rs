@@ -0,0 +1,251 @@
+use crate::{
+    Error, JWS, JWSPayloadEncoding, JWSPayloadPresence, JWTClaims, JWTValidationOptions, Result,
+    bail, error, jws_header::signer_of_kid, require,
+};
+
+/// A JWT (RFC 7519), which is a JWS in JWS Compact Serialization with an attached, base64url-encoded payload
+/// consisting of the JSON-encoded claims.  When signed using a DID, the "iss" claim must be the DID of the
+/// signing key, i.e. the "kid" field of the JWS header with its query and fragment removed.
+#[derive(Clone, Debug)]
+pub struct JWT {
+    jws: JWS<'static>,
+    claims: JWTClaims,
+}
+
+impl JWT {
+    /// Returns the underlying JWS.
+    pub fn jws(&self) -> &JWS<'static> {
+        &self.jws
+    }
+    /// Returns the claims.  Note that these are only trustworthy after JWT::verify has succeeded.
+    pub fn claims(&self) -> &JWTClaims {
+        &self.claims
+    }
+    pub fn into_claims(self) -> JWTClaims {
+        self.claims
+    }
+    pub fn as_str(&self) -> &str {
+        self.jws.as_str()
+    }
+    pub fn into_string(self) -> String {
+        self.jws.into_string()
+    }
+    /// Returns the DID (or more generally, the signer identifier) of the signing key, which is the "kid" field
+    /// of the header with its query and fragment removed.
+    pub fn signer(&self) -> &str {
+        signer_of_kid(self.jws.header().kid.as_str())
+    }
+    /// Sign the given claims using the given signer, whose key is identified by kid.  If the "iss" claim is not
+    /// present, then it will be set to the signer identified by kid (e.g. its DID).  If it is present, then it
+    /// must be that signer.
+    pub fn signed(
+        claims: JWTClaims,
+        kid: String,
+        signer: &dyn signature_dyn::SignerT,
+    ) -> Result<Self> {
+        Self::signed_with_typ(None, claims, kid, signer)
+    }
+    /// Like JWT::signed, but additionally sets the "typ" field of the header (e.g. "JWT", or an application-specific
+    /// media type), if specified.
+    pub fn signed_with_typ(
+        typ_o: Option<String>,
+        claims: JWTClaims,
+        kid: String,
+        signer: &dyn signature_dyn::SignerT,
+    ) -> Result<Self> {
+        let claims = Self::claims_with_issuer(claims, kid.as_str())?;
+        let payload = serde_json::to_vec(&claims)?;
+        let jws = JWS::signed_with_typ(
+            typ_o,
+            kid,
+            &mut payload.as_slice(),
+            JWSPayloadPresence::Attached,
+            JWSPayloadEncoding::Base64,
+            signer,
+        )?;
+        Ok(Self { jws, claims })
+    }
+    /// Async version of JWT::signed.
+    pub async fn async_signed(
+        claims: JWTClaims,
+        kid: String,
+        async_signer: &dyn signature_dyn::AsyncSignerT,
+    ) -> Result<Self> {
+        Self::async_signed_with_typ(None, claims, kid, async_signer).await
+    }
+    /// Async version of JWT::signed_with_typ.
+    pub async fn async_signed_with_typ(
+        typ_o: Option<String>,
+        claims: JWTClaims,
+        kid: String,
+        async_signer: &dyn signature_dyn::AsyncSignerT,
+    ) -> Result<Self> {
+        let claims = Self::claims_with_issuer(claims, kid.as_str())?;
+        let payload = serde_json::to_vec(&claims)?;
+        let jws = JWS::async_signed_with_typ(
+            typ_o,
+            kid,
+            &mut payload.as_slice(),
+            JWSPayloadPresence::Attached,
+            JWSPayloadEncoding::Base64,
+            async_signer,
+        )
+        .await?;
+        Ok(Self { jws, claims })
+    }
+    /// Verifies the JWT's signature using the given verifier (which the caller must have determined from the
+    /// "kid" field of the header), and then validates its claims (see JWT::validate_claims).
+    pub fn verify(
+        &self,
+        verifier: &dyn signature_dyn::VerifierT,
+        validation_options: &JWTValidationOptions,
+    ) -> Result<()> {
+        self.jws.verify(verifier, None)?;
+        self.validate_claims(validation_options)
+    }
+    /// Validates the claims of the JWT without verifying its signature.  This checks that:
+    /// - "iss" is present and is the signer identified by "kid" (and is the expected issuer, if specified),
+    /// - "exp" (if present, or if required) has not passed,
+    /// - "nbf" (if present) has passed,
+    /// - "iat" (if present) is not in the future,
+    /// - "aud" contains the expected audience if one is specified, and is absent otherwise,
+    ///
+    /// where each time comparison tolerates the configured clock skew.
+    pub fn validate_claims(&self, validation_options: &JWTValidationOptions) -> Result<()> {
+        let iss = self
+            .claims
+            .iss
+            .as_deref()
+            .ok_or_else(|| error!("JWT is missing \"iss\" claim"))?;
+        require!(
+            iss == self.signer(),
+            "JWT \"iss\" claim {:?} does not match the signer {:?} of the signing key {:?}",
+            iss,
+            self.signer(),
+            self.jws.header().kid
+        );
+        if let Some(expected_issuer) = validation_options.expected_issuer_o.as_deref() {
+            require!(
+                iss == expected_issuer,
+                "JWT \"iss\" claim {:?} is not the expected issuer {:?}",
+                iss,
+                expected_issuer
+            );
+        }
+
+        let now = if let Some(now) = validation_options.now_o {
+            now
+        } else {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_err(|e| error!("system clock is before the Unix epoch: {}", e))?
+                .as_secs() as i64
+        };
+        let clock_skew_seconds = i64::try_from(validation_options.clock_skew_seconds)
+            .map_err(|_| error!("JWT clock skew is too large"))?;
+        if let Some(exp) = self.claims.exp {
+            require!(
+                now < exp.saturating_add(clock_skew_seconds),
+                "JWT expired at {} (now is {}, clock skew tolerance is {}s)",
+                exp,
+                now,
+                clock_skew_seconds
+            );
+        } else if validation_options.require_exp {
+            bail!("JWT is missing \"exp\" claim");
+        }
+        if let Some(nbf) = self.claims.nbf {
+            require!(
+                nbf.saturating_sub(clock_skew_seconds) <= now,
+                "JWT is not valid before {} (now is {}, clock skew tolerance is {}s)",
+                nbf,
+                now,
+                clock_skew_seconds
+            );
+        }
+        if let Some(iat) = self.claims.iat {
+            require!(
+                iat.saturating_sub(clock_skew_seconds) <= now,
+                "JWT was issued in the future at {} (now is {}, clock skew tolerance is {}s)",
+                iat,
+                now,
+                clock_skew_seconds
+            );
+        }
+
+        match (
+            self.claims.aud.as_ref(),
+            validation_options.expected_audience_o.as_deref(),
+        ) {
+            (Some(aud), Some(expected_audience)) => {
+                require!(
+                    aud.contains(expected_audience),
+                    "JWT \"aud\" claim {:?} does not contain the expected audience {:?}",
+                    aud,
+                    expected_audience
+                );
+            }
+            (Some(aud), None) => {
+                bail!(
+                    "JWT has \"aud\" claim {:?}, but no expected audience was specified",
+                    aud
+                );
+            }
+            (None, Some(expected_audience)) => {
+                bail!(
+                    "JWT is missing \"aud\" claim, but audience {:?} was expected",
+                    expected_audience
+                );
+            }
+            (None, None) => {}
+        }
+
+        Ok(())
+    }
+    fn claims_with_issuer(mut claims: JWTClaims, kid: &str) -> Result<JWTClaims> {
+        let signer = signer_of_kid(kid);
+        if let Some(iss) = claims.iss.as_deref() {
+            require!(
+                iss == signer,
+                "JWT \"iss\" claim {:?} does not match the signer {:?} of the signing key {:?}",
+                iss,
+                signer,
+                kid
+            );
+        } else {
+            claims.iss = Some(signer.to_string());
+        }
+        Ok(claims)
+    }
+}
+
+impl std::fmt::Display for JWT {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.jws.as_str())
+    }
+}
+
+impl TryFrom<JWS<'static>> for JWT {
+    type Error = Error;
+    fn try_from(jws: JWS<'static>) -> Result<Self> {
+        require!(
+            !jws.raw_attached_payload_str().is_empty(),
+            "JWT must have an attached payload"
+        );
+        require!(
+            jws.payload_is_base64url_encoded(),
+            "JWT payload must be base64url-encoded"
+        );
+        let claims = jws
+            .parsed_decoded_payload::<JWTClaims>(None)
+            .map_err(|e| error!("JWT claims failed to parse: {}", e))?;
+        Ok(Self { jws, claims })
+    }
+}
+
+impl std::str::FromStr for JWT {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        Self::try_from(JWS::try_from(s.trim().to_string())?)
+    }
+}
@@ -0,0 +1,30 @@
+/// The "aud" claim of a JWT, which is either a single string or an array of strings.
+/// See RFC 7519 Section 4.1.3 <https://datatracker.ietf.org/doc/html/rfc7519#section-4.1.3>
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(untagged)]
+pub enum JWTAudience {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl JWTAudience {
+    /// Returns true iff the given audience is (one of) the audience(s).
+    pub fn contains(&self, audience: &str) -> bool {
+        match self {
+            Self::Single(s) => s == audience,
+            Self::Multiple(audience_v) => audience_v.iter().any(|s| s == audience),
+        }
+    }
+}
+
+impl From<String> for JWTAudience {
+    fn from(audience: String) -> Self {
+        Self::Single(audience)
+    }
+}
+
+impl From<Vec<String>> for JWTAudience {
+    fn from(audience_v: Vec<String>) -> Self {
+        Self::Multiple(audience_v)
+    }
+}
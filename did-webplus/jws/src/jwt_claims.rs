@@ -0,0 +1,32 @@
+use crate::JWTAudience;
+
+/// The claims of a JWT, i.e. the registered claims (RFC 7519 Section 4.1) along with any other claims.
+/// NumericDate claims ("exp", "nbf", "iat") are in seconds since the Unix epoch.
+/// See <https://datatracker.ietf.org/doc/html/rfc7519#section-4>
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct JWTClaims {
+    /// Issuer.  When signed using a DID, this must be that DID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    /// Subject.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    /// Audience.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aud: Option<JWTAudience>,
+    /// Expiration time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<i64>,
+    /// Not-before time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<i64>,
+    /// Issued-at time.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iat: Option<i64>,
+    /// JWT ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
+    /// All other (public or private) claims.
+    #[serde(flatten)]
+    pub additional_claim_m: serde_json::Map<String, serde_json::Value>,
+}
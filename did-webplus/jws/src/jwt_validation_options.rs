@@ -0,0 +1,34 @@
+/// Specifies how the claims of a JWT are validated (see JWT::validate_claims).
+#[derive(Clone, Debug)]
+pub struct JWTValidationOptions {
+    /// The amount of clock skew (in seconds) to tolerate when checking "exp", "nbf", and "iat".
+    pub clock_skew_seconds: u64,
+    /// If specified, then the "aud" claim must contain this audience.  If not specified, then the JWT
+    /// must not have an "aud" claim, since the recipient can't identify itself with the intended audience
+    /// (RFC 7519 Section 4.1.3).
+    pub expected_audience_o: Option<String>,
+    /// If specified, then the "iss" claim must be this issuer (in addition to it being the DID of the signing key).
+    pub expected_issuer_o: Option<String>,
+    /// If true, then the JWT must have an "exp" claim.
+    pub require_exp: bool,
+    /// The current time (in seconds since the Unix epoch) to validate against.  If not specified, then the
+    /// system clock is used.  Note that on wasm32, the system clock is not available, so this must be specified.
+    pub now_o: Option<i64>,
+}
+
+impl JWTValidationOptions {
+    /// The default clock skew tolerance, in seconds.
+    pub const DEFAULT_CLOCK_SKEW_SECONDS: u64 = 60;
+}
+
+impl Default for JWTValidationOptions {
+    fn default() -> Self {
+        Self {
+            clock_skew_seconds: Self::DEFAULT_CLOCK_SKEW_SECONDS,
+            expected_audience_o: None,
+            expected_issuer_o: None,
+            require_exp: false,
+            now_o: None,
+        }
+    }
+}
@@ -1,11 +1,22 @@
 mod error;
 mod jws;
 mod jws_header;
+mod jws_json;
+mod jws_json_signature;
 mod jws_payload_encoding;
 mod jws_payload_presence;
+mod jws_serialization;
+mod jws_signer_policy;
+mod jwt;
+mod jwt_audience;
+mod jwt_claims;
+mod jwt_validation_options;
 
 pub use crate::{
-    error::Error, jws::JWS, jws_header::JWSHeader, jws_payload_encoding::JWSPayloadEncoding,
-    jws_payload_presence::JWSPayloadPresence,
+    error::Error, jws::JWS, jws_header::JWSHeader, jws_json::JWSJSON,
+    jws_json_signature::JWSJSONSignature, jws_payload_encoding::JWSPayloadEncoding,
+    jws_payload_presence::JWSPayloadPresence, jws_serialization::JWSSerialization,
+    jws_signer_policy::JWSSignerPolicy, jwt::JWT, jwt_audience::JWTAudience, jwt_claims::JWTClaims,
+    jwt_validation_options::JWTValidationOptions,
 };
 pub type Result<T> = std::result::Result<T, Error>;
@@ -84,3 +84,387 @@ fn test_jws_p521() {
     let verifying_key = signing_key.verifying_key();
     test_jws_impl(&signing_key, verifying_key);
 }
+
+#[allow(dead_code)]
+fn as_reader_o<'a>(payload_bytes_o: &'a mut Option<&[u8]>) -> Option<&'a mut dyn std::io::Read> {
+    payload_bytes_o
+        .as_mut()
+        .map(|payload_bytes| payload_bytes as &mut dyn std::io::Read)
+}
+
+#[cfg(all(feature = "ed25519-dalek", feature = "p256"))]
+#[test]
+fn test_jws_json_multiple_signatures() {
+    use did_webplus_jws::{JWSJSON, JWSSerialization};
+    use signature_dyn::{GenerateRandom, SignerT};
+    use std::str::FromStr;
+
+    let signing_key_0 = ed25519_dalek::SigningKey::generate_random();
+    let signing_key_1 = p256::ecdsa::SigningKey::generate_random();
+    let signing_key_2 = ed25519_dalek::SigningKey::generate_random();
+    let verifier_0 = signing_key_0.verifying_key();
+    let verifier_1 = signing_key_1.verifying_key();
+    let verifier_2 = signing_key_2.verifying_key();
+
+    let payload = r#"{"shipment":"ABC-123"}"#.as_bytes();
+    for payload_presence in [JWSPayloadPresence::Attached, JWSPayloadPresence::Detached] {
+        for payload_encoding in [JWSPayloadEncoding::Base64, JWSPayloadEncoding::None] {
+            println!(
+                "payload_presence: {:?}, payload_encoding: {:?}",
+                payload_presence, payload_encoding
+            );
+            let detached_payload_o = || {
+                if payload_presence == JWSPayloadPresence::Detached {
+                    Some(payload)
+                } else {
+                    None
+                }
+            };
+            let mut jws_json = JWSJSON::signed(
+                &mut &*payload,
+                payload_presence,
+                payload_encoding,
+                &[
+                    (
+                        "did:example:alice#0".to_string(),
+                        &signing_key_0 as &dyn SignerT,
+                    ),
+                    ("did:example:bob#0".to_string(), &signing_key_1),
+                ],
+            )
+            .expect("pass");
+            assert_eq!(jws_json.signatures().len(), 2);
+            jws_json
+                .verify_signature(0, &verifier_0, as_reader_o(&mut detached_payload_o()))
+                .expect("pass");
+            jws_json
+                .verify_signature(1, verifier_1, as_reader_o(&mut detached_payload_o()))
+                .expect("pass");
+            // Wrong verifier.
+            jws_json
+                .verify_signature(0, &verifier_2, as_reader_o(&mut detached_payload_o()))
+                .expect_err("pass");
+            jws_json
+                .verify_signature(0, verifier_1, as_reader_o(&mut detached_payload_o()))
+                .expect_err("pass");
+
+            // Add a co-signature.
+            jws_json
+                .add_signature(
+                    "did:example:carol#0".to_string(),
+                    &signing_key_2,
+                    as_reader_o(&mut detached_payload_o()),
+                )
+                .expect("pass");
+            jws_json
+                .verify_signature(2, &verifier_2, as_reader_o(&mut detached_payload_o()))
+                .expect("pass");
+
+            // Multiple signatures can't be represented in the single-signature serializations.
+            jws_json.to_compact_jws().expect_err("pass");
+            jws_json
+                .to_serialization_string(JWSSerialization::Flattened)
+                .expect_err("pass");
+
+            // Round-trip the General JWS JSON Serialization.
+            let jws_json_string = jws_json.to_general_json_string().expect("pass");
+            println!("    jws_json: {}", jws_json_string);
+            let parsed_jws_json = JWSJSON::from_str(&jws_json_string).expect("pass");
+            assert_eq!(parsed_jws_json.signatures().len(), 3);
+            for (signature_index, verifier) in [
+                &verifier_0 as &dyn signature_dyn::VerifierT,
+                verifier_1,
+                &verifier_2,
+            ]
+            .into_iter()
+            .enumerate()
+            {
+                parsed_jws_json
+                    .verify_signature(
+                        signature_index,
+                        verifier,
+                        as_reader_o(&mut detached_payload_o()),
+                    )
+                    .expect("pass");
+            }
+            let decoded_payload: serde_json::Value = parsed_jws_json
+                .parsed_decoded_payload(as_reader_o(&mut detached_payload_o()))
+                .expect("pass");
+            assert_eq!(decoded_payload["shipment"], "ABC-123");
+
+            // A tampered payload must fail to verify.
+            if payload_presence == JWSPayloadPresence::Detached {
+                let tampered_payload = r#"{"shipment":"XYZ-999"}"#.as_bytes();
+                parsed_jws_json
+                    .verify_signature(0, &verifier_0, Some(&mut &*tampered_payload))
+                    .expect_err("pass");
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ed25519-dalek")]
+#[test]
+fn test_jws_json_single_signature_serializations() {
+    use did_webplus_jws::{JWSJSON, JWSSerialization};
+    use signature_dyn::{GenerateRandom, SignerT};
+    use std::str::FromStr;
+
+    let signing_key = ed25519_dalek::SigningKey::generate_random();
+    let verifier = signing_key.verifying_key();
+    let payload = r#""HIPPO WORLD!""#.as_bytes();
+
+    // Compact -> JSON -> Compact.
+    let jws = JWS::signed(
+        "did:example:alice#0".to_string(),
+        &mut &*payload,
+        JWSPayloadPresence::Attached,
+        JWSPayloadEncoding::Base64,
+        &signing_key,
+    )
+    .expect("pass");
+    let jws_json = JWSJSON::from(&jws);
+    jws_json.verify_signature(0, &verifier, None).expect("pass");
+    assert_eq!(
+        jws_json.to_compact_jws().expect("pass").as_str(),
+        jws.as_str()
+    );
+    // JWSJSON also parses the compact serialization.
+    let parsed_jws_json = JWSJSON::from_str(jws.as_str()).expect("pass");
+    parsed_jws_json
+        .verify_signature(0, &verifier, None)
+        .expect("pass");
+
+    // Flattened round-trip.
+    let mut jws_json = JWSJSON::signed(
+        &mut &*payload,
+        JWSPayloadPresence::Attached,
+        JWSPayloadEncoding::Base64,
+        &[(
+            "did:example:alice#0".to_string(),
+            &signing_key as &dyn SignerT,
+        )],
+    )
+    .expect("pass");
+    let flattened_string = jws_json
+        .to_serialization_string(JWSSerialization::Flattened)
+        .expect("pass");
+    println!("flattened: {}", flattened_string);
+    let flattened_value: serde_json::Value = serde_json::from_str(&flattened_string).unwrap();
+    assert!(flattened_value.get("signatures").is_none());
+    assert!(flattened_value.get("signature").is_some());
+    JWSJSON::from_str(&flattened_string)
+        .expect("pass")
+        .verify_signature(0, &verifier, None)
+        .expect("pass");
+
+    // An unprotected header is carried along, but must not repeat protected header parameters.
+    let mut unprotected_header = serde_json::Map::new();
+    unprotected_header.insert("role".to_string(), serde_json::json!("manufacturer"));
+    jws_json
+        .set_unprotected_header(0, Some(unprotected_header))
+        .expect("pass");
+    jws_json.to_compact_jws().expect_err("pass");
+    let parsed_jws_json =
+        JWSJSON::from_str(&jws_json.to_general_json_string().expect("pass")).expect("pass");
+    assert_eq!(
+        parsed_jws_json.signatures()[0]
+            .unprotected_header_o()
+            .unwrap()
+            .get("role"),
+        Some(&serde_json::json!("manufacturer"))
+    );
+    parsed_jws_json
+        .verify_signature(0, &verifier, None)
+        .expect("pass");
+    let mut bad_unprotected_header = serde_json::Map::new();
+    bad_unprotected_header.insert(
+        "kid".to_string(),
+        serde_json::json!("did:example:mallory#0"),
+    );
+    jws_json
+        .set_unprotected_header(0, Some(bad_unprotected_header))
+        .expect_err("pass");
+    jws_json.set_unprotected_header(1, None).expect_err("pass");
+
+    // Malformed inputs.
+    JWSJSON::from_str(r#"{"payload":"abc","signatures":[]}"#).expect_err("pass");
+    JWSJSON::from_str(r#"{"payload":"abc"}"#).expect_err("pass");
+    JWSJSON::from_str(r#"{"payload":"abc","protected":"!!!","signature":"abc"}"#)
+        .expect_err("pass");
+}
+
+#[test]
+fn test_jws_signer_policy() {
+    use did_webplus_jws::JWSSignerPolicy;
+    use std::str::FromStr;
+
+    assert_eq!(
+        JWSSignerPolicy::from_str("all").unwrap(),
+        JWSSignerPolicy::All
+    );
+    assert_eq!(
+        JWSSignerPolicy::from_str("any").unwrap(),
+        JWSSignerPolicy::Any
+    );
+    assert_eq!(
+        JWSSignerPolicy::from_str("2-of-n").unwrap(),
+        JWSSignerPolicy::KOfN(2)
+    );
+    assert_eq!(JWSSignerPolicy::KOfN(2).to_string(), "2-of-n");
+    JWSSignerPolicy::from_str("0-of-n").expect_err("pass");
+    JWSSignerPolicy::from_str("2").expect_err("pass");
+    JWSSignerPolicy::from_str("most").expect_err("pass");
+
+    let kid_alice_0 = "did:example:alice?versionId=0#0";
+    let kid_alice_1 = "did:example:alice?versionId=1#1";
+    let kid_bob = "did:example:bob?versionId=3#0";
+
+    JWSSignerPolicy::All
+        .check(2, &[kid_alice_0, kid_bob])
+        .expect("pass");
+    JWSSignerPolicy::All.check(2, &[kid_bob]).expect_err("pass");
+    JWSSignerPolicy::All.check(0, &[]).expect_err("pass");
+
+    JWSSignerPolicy::Any.check(3, &[kid_bob]).expect("pass");
+    JWSSignerPolicy::Any.check(3, &[]).expect_err("pass");
+
+    JWSSignerPolicy::KOfN(2)
+        .check(3, &[kid_alice_0, kid_bob])
+        .expect("pass");
+    // Two signatures from the same DID only count as one signer.
+    JWSSignerPolicy::KOfN(2)
+        .check(3, &[kid_alice_0, kid_alice_1])
+        .expect_err("pass");
+}
+
+#[cfg(feature = "ed25519-dalek")]
+#[test]
+fn test_jwt_claims_validation() {
+    use did_webplus_jws::{JWT, JWTAudience, JWTClaims, JWTValidationOptions};
+    use signature_dyn::GenerateRandom;
+    use std::str::FromStr;
+
+    let signing_key = ed25519_dalek::SigningKey::generate_random();
+    let verifier = signing_key.verifying_key();
+    let other_verifier = ed25519_dalek::SigningKey::generate_random().verifying_key();
+    let kid = "did:example:alice?versionId=3#0".to_string();
+    let now = 1_700_000_000i64;
+
+    let mut claims = JWTClaims {
+        sub: Some("did:example:bob".to_string()),
+        aud: Some(JWTAudience::from("https://verifier.example".to_string())),
+        exp: Some(now + 600),
+        nbf: Some(now - 10),
+        iat: Some(now - 10),
+        ..Default::default()
+    };
+    claims
+        .additional_claim_m
+        .insert("scope".to_string(), serde_json::json!("read"));
+    let jwt = JWT::signed(claims.clone(), kid.clone(), &signing_key).expect("pass");
+    // iss was set to the signer's DID.
+    assert_eq!(jwt.claims().iss.as_deref(), Some("did:example:alice"));
+    println!("jwt: {}", jwt);
+
+    let validation_options = JWTValidationOptions {
+        expected_audience_o: Some("https://verifier.example".to_string()),
+        now_o: Some(now),
+        ..Default::default()
+    };
+    let parsed_jwt = JWT::from_str(jwt.as_str()).expect("pass");
+    assert_eq!(parsed_jwt.claims(), jwt.claims());
+    assert_eq!(
+        parsed_jwt.claims().additional_claim_m.get("scope"),
+        Some(&serde_json::json!("read"))
+    );
+    parsed_jwt
+        .verify(&verifier, &validation_options)
+        .expect("pass");
+    parsed_jwt
+        .verify(&other_verifier, &validation_options)
+        .expect_err("pass");
+
+    // Expired, but within clock skew, then beyond it.
+    for (now, clock_skew_seconds, expect_success) in [
+        (now + 600 + 30, 60, true),
+        (now + 600 + 30, 0, false),
+        (now + 600 + 90, 60, false),
+        // Not yet valid.
+        (now - 10 - 30, 60, true),
+        (now - 10 - 90, 60, false),
+    ] {
+        let validation_options = JWTValidationOptions {
+            clock_skew_seconds,
+            now_o: Some(now),
+            ..validation_options.clone()
+        };
+        assert_eq!(
+            parsed_jwt.validate_claims(&validation_options).is_ok(),
+            expect_success,
+            "now: {}, clock_skew_seconds: {}",
+            now,
+            clock_skew_seconds
+        );
+    }
+
+    // Audience checks.
+    for (expected_audience_o, expect_success) in [
+        (Some("https://verifier.example"), true),
+        (Some("https://other.example"), false),
+        (None, false),
+    ] {
+        let validation_options = JWTValidationOptions {
+            expected_audience_o: expected_audience_o.map(str::to_string),
+            ..validation_options.clone()
+        };
+        assert_eq!(
+            parsed_jwt.validate_claims(&validation_options).is_ok(),
+            expect_success
+        );
+    }
+
+    // Expected issuer.
+    parsed_jwt
+        .validate_claims(&JWTValidationOptions {
+            expected_issuer_o: Some("did:example:alice".to_string()),
+            ..validation_options.clone()
+        })
+        .expect("pass");
+    parsed_jwt
+        .validate_claims(&JWTValidationOptions {
+            expected_issuer_o: Some("did:example:mallory".to_string()),
+            ..validation_options.clone()
+        })
+        .expect_err("pass");
+
+    // iss must match the DID of the signing key.
+    claims.iss = Some("did:example:mallory".to_string());
+    JWT::signed(claims.clone(), kid.clone(), &signing_key).expect_err("pass");
+    let forged_jws = JWS::signed(
+        kid.clone(),
+        &mut serde_json::to_vec(&claims).unwrap().as_slice(),
+        JWSPayloadPresence::Attached,
+        JWSPayloadEncoding::Base64,
+        &signing_key,
+    )
+    .expect("pass");
+    let forged_jwt = JWT::try_from(forged_jws).expect("pass");
+    forged_jwt
+        .verify(&verifier, &validation_options)
+        .expect_err("pass");
+
+    // exp can be required.
+    claims.iss = None;
+    claims.exp = None;
+    let jwt = JWT::signed(claims, kid, &signing_key).expect("pass");
+    jwt.verify(&verifier, &validation_options).expect("pass");
+    jwt.verify(
+        &verifier,
+        &JWTValidationOptions {
+            require_exp: true,
+            ..validation_options.clone()
+        },
+    )
+    .expect_err("pass");
+}
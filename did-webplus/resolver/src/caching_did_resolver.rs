@@ -0,0 +1,281 @@
+use crate::{
+    DIDResolver, Error, Result, verifier_resolver_for_purpose_impl,
+    verifier_resolver_for_purpose_with_version_impl, verifier_resolver_impl,
+};
+use did_webplus_core::{
+    DID, DIDDocument, DIDDocumentMetadata, DIDFullyQualified, DIDResolutionMetadata,
+    DIDResolutionOptions, DIDStr, DIDURIComponents, DIDWithQueryStr,
+};
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+/// A DID document that has been resolved and parsed, along with its JCS serialization.
+struct CachedDIDDocument {
+    did_document: DIDDocument,
+    did_document_jcs: String,
+}
+
+/// A latest DID document, along with the time at which it was resolved, so that it can be expired.
+struct CachedLatestDIDDocument {
+    resolved_at: time::OffsetDateTime,
+    cached_did_document_a: Arc<CachedDIDDocument>,
+}
+
+/// A decorator for any DIDResolver which keeps an in-memory LRU cache of parsed DID documents.
+///
+/// DID documents resolved via a query that specifies both the selfHash and versionId query params are
+/// immutable (the did-documents.jsonl microledger is append-only), so they're cached indefinitely
+/// (subject to LRU eviction), keyed by the fully qualified DID.  DID documents resolved via a plain DID
+/// (i.e. the latest DID document) can change at any time via DID update, so they're only cached for
+/// the configured TTL.  A TTL of zero disables caching of latest DID documents.
+///
+/// The cache is only used to answer requests that don't request any DID document metadata (e.g. the
+/// requests made by `verifier_resolver_impl`), since most DID document metadata (next, latest, and
+/// deactivated) can change over time.  Requests for metadata are always passed through to the
+/// underlying DIDResolver, though the resulting DID document is still added to the cache.
+pub struct CachingDIDResolver {
+    did_resolver_a: Arc<dyn DIDResolver>,
+    latest_ttl: time::Duration,
+    did_document_cache_l: Mutex<lru::LruCache<DIDFullyQualified, Arc<CachedDIDDocument>>>,
+    latest_did_document_cache_l: Mutex<lru::LruCache<DID, CachedLatestDIDDocument>>,
+}
+
+impl CachingDIDResolver {
+    /// Create a new CachingDIDResolver wrapping the given DIDResolver.  `capacity` is the maximum number
+    /// of entries in each of the fully qualified and latest DID document caches.  `latest_ttl` is how long
+    /// a latest DID document (i.e. the resolution of a plain DID) is cached for.
+    pub fn new(
+        did_resolver_a: Arc<dyn DIDResolver>,
+        capacity: NonZeroUsize,
+        latest_ttl: time::Duration,
+    ) -> Self {
+        Self {
+            did_resolver_a,
+            latest_ttl,
+            did_document_cache_l: Mutex::new(lru::LruCache::new(capacity)),
+            latest_did_document_cache_l: Mutex::new(lru::LruCache::new(capacity)),
+        }
+    }
+    /// Returns the underlying DIDResolver.
+    pub fn did_resolver(&self) -> &dyn DIDResolver {
+        self.did_resolver_a.as_ref()
+    }
+    /// Remove all entries from the cache.
+    pub fn clear(&self) {
+        self.did_document_cache_l.lock().unwrap().clear();
+        self.latest_did_document_cache_l.lock().unwrap().clear();
+    }
+    /// Returns the number of cached fully qualified DID documents and the number of cached latest
+    /// DID documents (including expired ones which haven't been evicted yet).
+    pub fn cache_sizes(&self) -> (usize, usize) {
+        (
+            self.did_document_cache_l.lock().unwrap().len(),
+            self.latest_did_document_cache_l.lock().unwrap().len(),
+        )
+    }
+    async fn resolve_cached_did_document(
+        &self,
+        did_query: &str,
+        did_resolution_options: DIDResolutionOptions,
+    ) -> Result<(
+        Arc<CachedDIDDocument>,
+        DIDDocumentMetadata,
+        DIDResolutionMetadata,
+    )> {
+        let metadata_requested = did_resolution_options.request_creation
+            || did_resolution_options.request_next
+            || did_resolution_options.request_latest
+            || did_resolution_options.request_deactivated;
+
+        // Determine which cache (if any) applies to this query.
+        let did_uri_components = DIDURIComponents::try_from(did_query)
+            .map_err(|err| Error::MalformedDIDQuery(err.to_string().into()))?;
+        let mut latest_did_o: Option<&DIDStr> = None;
+        let mut did_fully_qualified_o: Option<DIDFullyQualified> = None;
+        if !did_uri_components.has_fragment() {
+            if !did_uri_components.has_query() {
+                latest_did_o = Some(
+                    DIDStr::new_ref(did_query)
+                        .map_err(|err| Error::MalformedDIDQuery(err.to_string().into()))?,
+                );
+            } else {
+                let did_with_query = DIDWithQueryStr::new_ref(did_query)
+                    .map_err(|err| Error::MalformedDIDQuery(err.to_string().into()))?;
+                if let (Some(query_self_hash), Some(query_version_id)) = (
+                    did_with_query.query_self_hash_o(),
+                    did_with_query.query_version_id_o(),
+                ) {
+                    // Normalize the query, so that the order of the query params doesn't matter.
+                    did_fully_qualified_o = Some(
+                        did_with_query
+                            .did()
+                            .with_queries(query_self_hash, query_version_id),
+                    );
+                }
+            }
+        }
+
+        // Attempt to answer the request from the cache.
+        if !metadata_requested {
+            let cached_did_document_ao =
+                if let Some(did_fully_qualified) = did_fully_qualified_o.as_ref() {
+                    self.did_document_cache_l
+                        .lock()
+                        .unwrap()
+                        .get(did_fully_qualified)
+                        .cloned()
+                } else if let Some(latest_did) = latest_did_o {
+                    let mut latest_did_document_cache_g =
+                        self.latest_did_document_cache_l.lock().unwrap();
+                    let now = time::OffsetDateTime::now_utc();
+                    match latest_did_document_cache_g.get(latest_did) {
+                        Some(cached_latest_did_document)
+                            if now - cached_latest_did_document.resolved_at < self.latest_ttl =>
+                        {
+                            Some(cached_latest_did_document.cached_did_document_a.clone())
+                        }
+                        Some(_) => {
+                            // Expired, so evict it.
+                            latest_did_document_cache_g.pop(latest_did);
+                            None
+                        }
+                        None => None,
+                    }
+                } else {
+                    None
+                };
+            if let Some(cached_did_document_a) = cached_did_document_ao {
+                tracing::trace!("CachingDIDResolver cache hit for {}", did_query);
+                return Ok((
+                    cached_did_document_a,
+                    DIDDocumentMetadata {
+                        creation_metadata_o: None,
+                        next_update_metadata_o: None,
+                        latest_update_metadata_o: None,
+                        deactivated_o: None,
+                    },
+                    DIDResolutionMetadata {
+                        content_type: "application/did+json".to_string(),
+                        error_o: None,
+                        fetched_updates_from_vdr: false,
+                        did_document_resolved_locally: true,
+                        did_document_metadata_resolved_locally: true,
+                    },
+                ));
+            }
+        }
+
+        // Cache miss, so resolve using the underlying DIDResolver and populate the cache.
+        tracing::trace!("CachingDIDResolver cache miss for {}", did_query);
+        let (did_document_jcs, did_document_metadata, did_resolution_metadata) = self
+            .did_resolver_a
+            .resolve_did_document_string(did_query, did_resolution_options)
+            .await?;
+        let did_document: DIDDocument = serde_json::from_str(&did_document_jcs)
+            .map_err(|e| Error::MalformedDIDDocument(e.to_string().into()))?;
+        let did_fully_qualified = did_document
+            .did
+            .with_queries(&did_document.self_hash, did_document.version_id);
+        let cached_did_document_a = Arc::new(CachedDIDDocument {
+            did_document,
+            did_document_jcs,
+        });
+        self.did_document_cache_l
+            .lock()
+            .unwrap()
+            .put(did_fully_qualified, cached_did_document_a.clone());
+        if let Some(latest_did) = latest_did_o {
+            if self.latest_ttl.is_positive() {
+                self.latest_did_document_cache_l.lock().unwrap().put(
+                    latest_did.to_owned(),
+                    CachedLatestDIDDocument {
+                        resolved_at: time::OffsetDateTime::now_utc(),
+                        cached_did_document_a: cached_did_document_a.clone(),
+                    },
+                );
+            }
+        }
+
+        Ok((
+            cached_did_document_a,
+            did_document_metadata,
+            did_resolution_metadata,
+        ))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl DIDResolver for CachingDIDResolver {
+    async fn resolve_did_document_string(
+        &self,
+        did_query: &str,
+        did_resolution_options: DIDResolutionOptions,
+    ) -> Result<(String, DIDDocumentMetadata, DIDResolutionMetadata)> {
+        let (cached_did_document_a, did_document_metadata, did_resolution_metadata) = self
+            .resolve_cached_did_document(did_query, did_resolution_options)
+            .await?;
+        Ok((
+            cached_did_document_a.did_document_jcs.clone(),
+            did_document_metadata,
+            did_resolution_metadata,
+        ))
+    }
+    /// This overrides the default implementation so that the cached, already-parsed DID document is
+    /// used instead of re-parsing the DID document string.
+    async fn resolve_did_document(
+        &self,
+        did_query: &str,
+        did_resolution_options: DIDResolutionOptions,
+    ) -> Result<(DIDDocument, DIDDocumentMetadata, DIDResolutionMetadata)> {
+        let (cached_did_document_a, did_document_metadata, did_resolution_metadata) = self
+            .resolve_cached_did_document(did_query, did_resolution_options)
+            .await?;
+        Ok((
+            cached_did_document_a.did_document.clone(),
+            did_document_metadata,
+            did_resolution_metadata,
+        ))
+    }
+    fn as_verifier_resolver(&self) -> &dyn verifier_resolver::VerifierResolver {
+        self
+    }
+    fn as_verifier_resolver_a(self: Arc<Self>) -> Arc<dyn verifier_resolver::VerifierResolver> {
+        self
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl verifier_resolver::VerifierResolver for CachingDIDResolver {
+    async fn resolve(
+        &self,
+        verifier_str: &str,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        verifier_resolver_impl(verifier_str, self).await
+    }
+    async fn resolve_for_purpose(
+        &self,
+        verifier_str: &str,
+        verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        verifier_resolver_for_purpose_impl(verifier_str, verification_relationship, self).await
+    }
+    async fn resolve_for_purpose_with_version(
+        &self,
+        verifier_str: &str,
+        verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<(Box<dyn signature_dyn::VerifierT>, Option<String>)> {
+        verifier_resolver_for_purpose_with_version_impl(
+            verifier_str,
+            verification_relationship,
+            self,
+        )
+        .await
+    }
+    fn vdg_host_o(&self) -> Option<&str> {
+        self.did_resolver_a.as_verifier_resolver().vdg_host_o()
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{DIDBundleLatestAttestation, Error, Result};
+use did_webplus_core::{DID, DIDDocument};
+use did_webplus_doc_store::parse_did_document;
+
+/// A DIDBundle is a self-contained, offline-verifiable package of the DID documents for a single DID.
+/// It contains a prefix of the DID's did-documents.jsonl file (i.e. all DID documents from the root
+/// DID document up through some versionId) and optionally a "latest as of" attestation, which is a JWS
+/// (typically signed by a VDG) asserting that as of a particular time, the last DID document in the
+/// bundle was the latest DID document for the DID.
+///
+/// DIDBundle-s are meant to be shipped alongside credentials so that they can be verified in environments
+/// where no VDR or VDG is reachable.  Note that without a latest attestation, a DIDBundle can only be
+/// used to resolve specific DID documents (i.e. via selfHash and/or versionId query params), since there
+/// is no way to know if there are later DID documents that are not included in the bundle (unless the
+/// last DID document in the bundle is deactivated).
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DIDBundle {
+    /// The DID that this bundle is for.
+    pub did: DID,
+    /// A prefix of the DID's did-documents.jsonl file, starting at the root DID document.  Each DID document
+    /// is JCS-serialized and followed by a newline.
+    pub did_documents_jsonl: String,
+    /// Optional "latest as of" attestation, in JWS Compact Serialization.  See DIDBundleLatestAttestation.
+    #[serde(rename = "latestAttestation")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest_attestation_o: Option<String>,
+}
+
+impl DIDBundle {
+    /// Parse and fully verify the microledger contained in this bundle, returning the parsed DID documents
+    /// along with their JCS-serialized forms.  Note that this does not verify the latest attestation.
+    pub fn verify_did_documents(&self) -> Result<(Vec<DIDDocument>, Vec<String>)> {
+        let did_documents_jsonl = self.did_documents_jsonl.trim_end();
+        if did_documents_jsonl.is_empty() {
+            return Err(Error::InvalidDIDBundle(
+                format!("DID bundle for {} contains no DID documents", self.did).into(),
+            ));
+        }
+
+        let mut did_document_v: Vec<DIDDocument> = Vec::new();
+        let mut did_document_jcs_v = Vec::new();
+        for did_document_jcs in did_documents_jsonl.split('\n') {
+            let did_document = parse_did_document(did_document_jcs)?;
+            if did_document.did != self.did {
+                return Err(Error::InvalidDIDBundle(
+                    format!(
+                        "DID bundle for {} contains DID document for different DID {}",
+                        self.did, did_document.did
+                    )
+                    .into(),
+                ));
+            }
+            did_document
+                .verify_nonrecursive(did_document_v.last())
+                .map_err(|e| {
+                    Error::InvalidDIDBundle(
+                        format!(
+                            "DID bundle for {} contains invalid DID document with versionId {}: {}",
+                            self.did, did_document.version_id, e
+                        )
+                        .into(),
+                    )
+                })?;
+            did_document_jcs_v.push(did_document_jcs.to_string());
+            did_document_v.push(did_document);
+        }
+
+        Ok((did_document_v, did_document_jcs_v))
+    }
+    /// Verify the latest attestation (if present) using the given VerifierResolver, and check that it
+    /// is consistent with the given last DID document of this bundle.  Returns None if there is no
+    /// latest attestation.  The VerifierResolver determines which attesters are trusted; e.g. it could be
+    /// a verifier_resolver::VerifierResolverMap containing only the key IDs of trusted VDGs.
+    pub async fn verify_latest_attestation(
+        &self,
+        last_did_document: &DIDDocument,
+        attestation_verifier_resolver: &dyn verifier_resolver::VerifierResolver,
+    ) -> Result<Option<DIDBundleLatestAttestation>> {
+        let latest_attestation_str = match self.latest_attestation_o.as_deref() {
+            Some(latest_attestation_str) => latest_attestation_str,
+            None => {
+                return Ok(None);
+            }
+        };
+        let latest_attestation = DIDBundleLatestAttestation::verify(
+            latest_attestation_str,
+            attestation_verifier_resolver,
+        )
+        .await?;
+        if latest_attestation.did != self.did {
+            return Err(Error::InvalidDIDBundle(
+                format!(
+                    "DID bundle for {} has latest attestation for different DID {}",
+                    self.did, latest_attestation.did
+                )
+                .into(),
+            ));
+        }
+        if latest_attestation.self_hash != last_did_document.self_hash
+            || latest_attestation.version_id != last_did_document.version_id
+        {
+            return Err(Error::InvalidDIDBundle(
+                format!(
+                    "DID bundle for {} has latest attestation (selfHash: {}, versionId: {}) that doesn't match last DID document in bundle (selfHash: {}, versionId: {})",
+                    self.did,
+                    latest_attestation.self_hash,
+                    latest_attestation.version_id,
+                    last_did_document.self_hash,
+                    last_did_document.version_id
+                )
+                .into(),
+            ));
+        }
+        Ok(Some(latest_attestation))
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{Error, Result};
+use did_webplus_core::DID;
+
+/// This is the payload of the "latest as of" attestation optionally included in a DIDBundle.  It asserts
+/// that as of the time `as_of`, the DID document with the given selfHash and versionId was the latest DID
+/// document for the given DID (according to the attester, which is typically a VDG).  It is conveyed as an
+/// attached-payload JWS whose "kid" identifies the attester's key.
+#[derive(Clone, Debug, serde::Deserialize, Eq, PartialEq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DIDBundleLatestAttestation {
+    pub did: DID,
+    pub self_hash: mbx::MBHash,
+    pub version_id: u32,
+    #[serde(with = "time::serde::rfc3339")]
+    pub as_of: time::OffsetDateTime,
+}
+
+impl DIDBundleLatestAttestation {
+    /// Produce the attestation JWS (in JWS Compact Serialization) using the given key ID and signer.
+    pub fn sign(&self, kid: String, signer: &dyn signature_dyn::SignerT) -> Result<String> {
+        let payload = serde_json_canonicalizer::to_string(self)
+            .map_err(|e| Error::GenericError(e.to_string().into()))?;
+        let jws = did_webplus_jws::JWS::signed(
+            kid,
+            &mut payload.as_bytes(),
+            did_webplus_jws::JWSPayloadPresence::Attached,
+            did_webplus_jws::JWSPayloadEncoding::Base64,
+            signer,
+        )
+        .map_err(|e| Error::GenericError(e.to_string().into()))?;
+        Ok(jws.into_string())
+    }
+    /// Verify the given attestation JWS using the given VerifierResolver and return the parsed attestation.
+    /// The VerifierResolver determines which attesters are trusted.  The attestation is an assertion, so the
+    /// attester's key must be an assertionMethod key.
+    pub async fn verify(
+        latest_attestation_str: &str,
+        attestation_verifier_resolver: &dyn verifier_resolver::VerifierResolver,
+    ) -> Result<Self> {
+        let jws = did_webplus_jws::JWS::try_from(latest_attestation_str).map_err(|e| {
+            Error::InvalidDIDBundle(format!("Malformed latest attestation: {}", e).into())
+        })?;
+        let verifier_b = attestation_verifier_resolver
+            .resolve_for_purpose(
+                jws.header().kid.as_str(),
+                verifier_resolver::VerificationRelationship::AssertionMethod,
+            )
+            .await
+            .map_err(|e| {
+                Error::InvalidDIDBundle(
+                    format!(
+                        "Latest attestation key {:?} could not be resolved (it may not be trusted): {}",
+                        jws.header().kid,
+                        e
+                    )
+                    .into(),
+                )
+            })?;
+        jws.verify(verifier_b.as_ref(), None).map_err(|e| {
+            Error::InvalidDIDBundle(format!("Latest attestation failed to verify: {}", e).into())
+        })?;
+        let latest_attestation: Self = jws.parsed_decoded_payload(None).map_err(|e| {
+            Error::InvalidDIDBundle(format!("Malformed latest attestation payload: {}", e).into())
+        })?;
+        Ok(latest_attestation)
+    }
+}
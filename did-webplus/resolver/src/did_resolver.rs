@@ -39,11 +39,48 @@ pub trait DIDResolver: Send + Sync + verifier_resolver::VerifierResolver {
 }
 
 /// Implementations of DIDResolver can use this to provide the guts to the implementation
-/// of verifier_resolver::VerifierResolver.
+/// of verifier_resolver::VerifierResolver::resolve.
 pub async fn verifier_resolver_impl(
     verifier_str: &str,
     did_resolver: &dyn DIDResolver,
 ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+    let (verifier_b, _did_document_version) =
+        resolve_verifier(verifier_str, None, did_resolver).await?;
+    Ok(verifier_b)
+}
+
+/// Implementations of DIDResolver can use this to provide the guts to the implementation
+/// of verifier_resolver::VerifierResolver::resolve_for_purpose.  This checks that the key
+/// is listed under the given verification relationship in the DID document.
+pub async fn verifier_resolver_for_purpose_impl(
+    verifier_str: &str,
+    verification_relationship: verifier_resolver::VerificationRelationship,
+    did_resolver: &dyn DIDResolver,
+) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+    let (verifier_b, _did_document_version) =
+        resolve_verifier(verifier_str, Some(verification_relationship), did_resolver).await?;
+    Ok(verifier_b)
+}
+
+/// Implementations of DIDResolver can use this to provide the guts to the implementation
+/// of verifier_resolver::VerifierResolver::resolve_for_purpose_with_version.  This is like
+/// verifier_resolver_for_purpose_impl, but also returns the fully qualified DID (i.e. with the
+/// selfHash and versionId query params) of the DID document that the key was resolved from.
+pub async fn verifier_resolver_for_purpose_with_version_impl(
+    verifier_str: &str,
+    verification_relationship: verifier_resolver::VerificationRelationship,
+    did_resolver: &dyn DIDResolver,
+) -> verifier_resolver::Result<(Box<dyn signature_dyn::VerifierT>, Option<String>)> {
+    let (verifier_b, did_document_version) =
+        resolve_verifier(verifier_str, Some(verification_relationship), did_resolver).await?;
+    Ok((verifier_b, Some(did_document_version)))
+}
+
+async fn resolve_verifier(
+    verifier_str: &str,
+    verification_relationship_o: Option<verifier_resolver::VerificationRelationship>,
+    did_resolver: &dyn DIDResolver,
+) -> verifier_resolver::Result<(Box<dyn signature_dyn::VerifierT>, String)> {
     if !verifier_str.starts_with("did:webplus:") {
         Err(verifier_resolver::Error::InvalidVerifier(
             format!(
@@ -73,9 +110,29 @@ pub async fn verifier_resolver_impl(
     let verification_method = did_document
         .public_key_material
         .verification_method_for_key_id_fragment(did_key_resource_fully_qualified.fragment())?;
+    if let Some(verification_relationship) = verification_relationship_o {
+        let key_purpose = did_webplus_core::KeyPurpose::from(verification_relationship);
+        if !did_document
+            .public_key_material
+            .key_purpose_flags_for_key_id_fragment(did_key_resource_fully_qualified.fragment())
+            .contains(key_purpose)
+        {
+            Err(verifier_resolver::Error::UnauthorizedVerifier(
+                format!(
+                    "key {} is not listed under {} in its DID document",
+                    verifier_str, key_purpose
+                )
+                .into(),
+            ))?;
+        }
+    }
     // TODO: Go directly to the verifier bytes (maybe)
     let pub_key = mbx::MBPubKey::try_from(&verification_method.public_key_jwk)?;
     let verifier_bytes = signature_dyn::VerifierBytes::try_from(&pub_key)
         .map_err(|e| verifier_resolver::Error::InvalidVerifier(e.to_string().into()))?;
-    Ok(Box::new(verifier_bytes))
+    let did_document_version = did_document
+        .did
+        .with_queries(&did_document.self_hash, did_document.version_id)
+        .to_string();
+    Ok((Box::new(verifier_bytes), did_document_version))
 }
@@ -0,0 +1,335 @@
+use crate::{
+    DIDBundle, DIDBundleLatestAttestation, DIDResolver, Error, Result,
+    verifier_resolver_for_purpose_impl, verifier_resolver_for_purpose_with_version_impl,
+    verifier_resolver_impl,
+};
+use did_webplus_core::{
+    CreationMetadata, DID, DIDDocument, DIDDocumentMetadata, DIDResolutionMetadata,
+    DIDResolutionOptions, DIDStr, DIDURIComponents, DIDWithQueryStr, LatestUpdateMetadata,
+    NextUpdateMetadata,
+};
+use std::{collections::HashMap, sync::Arc};
+
+/// The verified contents of a DIDBundle.
+#[derive(Clone, Debug)]
+struct BundledDID {
+    did_document_v: Vec<DIDDocument>,
+    did_document_jcs_v: Vec<String>,
+    latest_attestation_o: Option<DIDBundleLatestAttestation>,
+}
+
+impl BundledDID {
+    /// The last DID document in the bundle is known to be the latest if there is a verified latest
+    /// attestation, or if it's deactivated (in which case there can't be any later DID documents).
+    fn latest_is_known(&self) -> bool {
+        self.latest_attestation_o.is_some() || self.did_document_v.last().unwrap().is_deactivated()
+    }
+}
+
+/// A DID resolver that resolves only from a set of DIDBundle-s, and never makes any network requests.
+/// This is meant for verifying credentials in air-gapped environments, where no VDR or VDG is reachable.
+/// Each DIDBundle is fully verified when it's added to the resolver.
+///
+/// Resolving a specific DID document (via selfHash and/or versionId query params) is always possible if
+/// that DID document is in the bundle.  However, resolving the latest DID document or requesting metadata
+/// that depends on knowing the latest DID document (next, latest, and deactivated metadata) is only
+/// possible if the latest DID document is known, i.e. if the bundle has a trusted latest attestation or
+/// if the last DID document in the bundle is deactivated.  Note that a latest attestation only asserts
+/// what the latest DID document was as of a particular time; see `latest_as_of_o`.
+#[derive(Clone, Debug, Default)]
+pub struct DIDResolverBundle {
+    bundled_did_m: HashMap<DID, BundledDID>,
+}
+
+impl DIDResolverBundle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Verify the given DIDBundle and add it to this resolver.  If attestation_verifier_resolver_o is
+    /// Some(_), then the bundle's latest attestation (if present) is verified using it, and it determines
+    /// which attesters are trusted.  If attestation_verifier_resolver_o is None, then any latest attestation
+    /// is ignored.  It is an error to add more than one bundle for the same DID.
+    pub async fn add_did_bundle(
+        &mut self,
+        did_bundle: &DIDBundle,
+        attestation_verifier_resolver_o: Option<&dyn verifier_resolver::VerifierResolver>,
+    ) -> Result<()> {
+        if self.bundled_did_m.contains_key(&did_bundle.did) {
+            return Err(Error::InvalidDIDBundle(
+                format!("Multiple DID bundles for DID {}", did_bundle.did).into(),
+            ));
+        }
+
+        let (did_document_v, did_document_jcs_v) = did_bundle.verify_did_documents()?;
+        let latest_attestation_o = if let Some(attestation_verifier_resolver) =
+            attestation_verifier_resolver_o
+        {
+            did_bundle
+                .verify_latest_attestation(
+                    did_document_v.last().unwrap(),
+                    attestation_verifier_resolver,
+                )
+                .await?
+        } else {
+            if did_bundle.latest_attestation_o.is_some() {
+                tracing::warn!(
+                    "Ignoring latest attestation in DID bundle for {} because no attestation verifier resolver was specified",
+                    did_bundle.did
+                );
+            }
+            None
+        };
+
+        self.bundled_did_m.insert(
+            did_bundle.did.clone(),
+            BundledDID {
+                did_document_v,
+                did_document_jcs_v,
+                latest_attestation_o,
+            },
+        );
+        Ok(())
+    }
+    /// Builder-style version of add_did_bundle.
+    pub async fn with_did_bundle(
+        mut self,
+        did_bundle: &DIDBundle,
+        attestation_verifier_resolver_o: Option<&dyn verifier_resolver::VerifierResolver>,
+    ) -> Result<Self> {
+        self.add_did_bundle(did_bundle, attestation_verifier_resolver_o)
+            .await?;
+        Ok(self)
+    }
+    /// Returns the time as of which the last DID document in the bundle for the given DID was attested to be
+    /// the latest, if there is such a verified attestation.
+    pub fn latest_as_of_o(&self, did: &DIDStr) -> Option<time::OffsetDateTime> {
+        self.bundled_did_m
+            .get(did)
+            .and_then(|bundled_did| bundled_did.latest_attestation_o.as_ref())
+            .map(|latest_attestation| latest_attestation.as_of)
+    }
+    fn resolve_did_document_jcs(
+        &self,
+        did_query: &str,
+        did_resolution_options: DIDResolutionOptions,
+    ) -> Result<(&str, DIDDocumentMetadata, DIDResolutionMetadata)> {
+        let mut query_self_hash_o = None;
+        let mut query_version_id_o = None;
+
+        // Determine which case we're handling; a DID with or without query params.
+        let did_uri_components = DIDURIComponents::try_from(did_query)
+            .map_err(|err| Error::MalformedDIDQuery(err.to_string().into()))?;
+        if did_uri_components.has_fragment() {
+            return Err(Error::MalformedDIDQuery(
+                "DID query contains a fragment (this is not (yet?) supported)".into(),
+            ));
+        }
+        let did = if !did_uri_components.has_query() {
+            DIDStr::new_ref(did_query)
+                .map_err(|err| Error::MalformedDIDQuery(err.to_string().into()))?
+        } else {
+            let did_with_query = DIDWithQueryStr::new_ref(did_query)
+                .map_err(|err| Error::MalformedDIDQuery(err.to_string().into()))?;
+            query_self_hash_o = did_with_query.query_self_hash_o();
+            query_version_id_o = did_with_query.query_version_id_o();
+            did_with_query.did()
+        };
+
+        let bundled_did = self
+            .bundled_did_m
+            .get(did)
+            .ok_or_else(|| resolution_failure(format!("no DID bundle is available for {}", did)))?;
+        let last_index = bundled_did.did_document_v.len() - 1;
+
+        // Determine the index of the requested DID document within the bundle.
+        let requested_index = match (query_self_hash_o, query_version_id_o) {
+            (None, None) => {
+                if !bundled_did.latest_is_known() {
+                    return Err(resolution_failure(format!(
+                        "DID bundle for {} has no trusted latest attestation, so the latest DID document can't be resolved",
+                        did
+                    )));
+                }
+                last_index
+            }
+            (Some(query_self_hash), None) => bundled_did
+                .did_document_v
+                .iter()
+                .position(|did_document| {
+                    did_document.self_hash.as_str() == query_self_hash.as_str()
+                })
+                .ok_or_else(|| {
+                    resolution_failure(format!(
+                        "DID bundle for {} does not contain DID document with selfHash {}",
+                        did, query_self_hash
+                    ))
+                })?,
+            (query_self_hash_o, Some(query_version_id)) => {
+                let requested_index = query_version_id as usize;
+                let did_document =
+                    bundled_did
+                        .did_document_v
+                        .get(requested_index)
+                        .ok_or_else(|| {
+                            resolution_failure(format!(
+                                "DID bundle for {} does not contain DID document with versionId {}",
+                                did, query_version_id
+                            ))
+                        })?;
+                if let Some(query_self_hash) = query_self_hash_o {
+                    if did_document.self_hash.as_str() != query_self_hash.as_str() {
+                        return Err(Error::FailedConstraint(
+                            format!(
+                                "DID document with versionId {} has selfHash {} which does not match requested selfHash {}",
+                                query_version_id, did_document.self_hash, query_self_hash
+                            )
+                            .into(),
+                        ));
+                    }
+                }
+                requested_index
+            }
+        };
+
+        // Check that the requested metadata is determinable from the bundle.
+        let latest_did_document_needed = did_resolution_options.request_latest
+            || did_resolution_options.request_deactivated
+            || (did_resolution_options.request_next && requested_index == last_index);
+        if latest_did_document_needed && !bundled_did.latest_is_known() {
+            return Err(metadata_unavailable(format!(
+                "DID bundle for {} has no trusted latest attestation, so the requested DID document metadata can't be determined",
+                did
+            )));
+        }
+
+        let valid_from_for = |index: usize| -> Result<time::OffsetDateTime> {
+            bundled_did.did_document_v[index]
+                .valid_from()
+                .map_err(|e| Error::MalformedDIDDocument(e.to_string().into()))
+        };
+        let creation_metadata_o = if did_resolution_options.request_creation {
+            Some(CreationMetadata::new(valid_from_for(0)?))
+        } else {
+            None
+        };
+        let next_update_metadata_o =
+            if did_resolution_options.request_next && requested_index < last_index {
+                Some(NextUpdateMetadata::new(
+                    valid_from_for(requested_index + 1)?,
+                    bundled_did.did_document_v[requested_index + 1].version_id,
+                ))
+            } else {
+                None
+            };
+        let latest_update_metadata_o = if did_resolution_options.request_latest {
+            Some(LatestUpdateMetadata::new(
+                valid_from_for(last_index)?,
+                bundled_did.did_document_v[last_index].version_id,
+            ))
+        } else {
+            None
+        };
+        let deactivated_o = if did_resolution_options.request_deactivated {
+            Some(bundled_did.did_document_v[last_index].is_deactivated())
+        } else {
+            None
+        };
+
+        Ok((
+            bundled_did.did_document_jcs_v[requested_index].as_str(),
+            DIDDocumentMetadata {
+                creation_metadata_o,
+                next_update_metadata_o,
+                latest_update_metadata_o,
+                deactivated_o,
+            },
+            DIDResolutionMetadata {
+                content_type: "application/did+json".to_string(),
+                error_o: None,
+                fetched_updates_from_vdr: false,
+                did_document_resolved_locally: true,
+                did_document_metadata_resolved_locally: true,
+            },
+        ))
+    }
+}
+
+fn resolution_failure(error: String) -> Error {
+    Error::DIDResolutionFailure2(DIDResolutionMetadata {
+        content_type: "application/did+json".to_string(),
+        error_o: Some(error),
+        fetched_updates_from_vdr: false,
+        did_document_resolved_locally: false,
+        did_document_metadata_resolved_locally: false,
+    })
+}
+
+/// The requested DID document is in the bundle, but the requested metadata isn't determinable from it.
+fn metadata_unavailable(error: String) -> Error {
+    Error::DIDResolutionFailure2(DIDResolutionMetadata {
+        content_type: "application/did+json".to_string(),
+        error_o: Some(error),
+        fetched_updates_from_vdr: false,
+        did_document_resolved_locally: true,
+        did_document_metadata_resolved_locally: false,
+    })
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl DIDResolver for DIDResolverBundle {
+    async fn resolve_did_document_string(
+        &self,
+        did_query: &str,
+        did_resolution_options: DIDResolutionOptions,
+    ) -> Result<(String, DIDDocumentMetadata, DIDResolutionMetadata)> {
+        tracing::debug!(
+            "DIDResolverBundle::resolve_did_document_string; did_query: {}; did_resolution_options: {:?}",
+            did_query,
+            did_resolution_options
+        );
+        let (did_document_jcs, did_document_metadata, did_resolution_metadata) =
+            self.resolve_did_document_jcs(did_query, did_resolution_options)?;
+        Ok((
+            did_document_jcs.to_string(),
+            did_document_metadata,
+            did_resolution_metadata,
+        ))
+    }
+    fn as_verifier_resolver(&self) -> &dyn verifier_resolver::VerifierResolver {
+        self
+    }
+    fn as_verifier_resolver_a(self: Arc<Self>) -> Arc<dyn verifier_resolver::VerifierResolver> {
+        self
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl verifier_resolver::VerifierResolver for DIDResolverBundle {
+    async fn resolve(
+        &self,
+        verifier_str: &str,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        verifier_resolver_impl(verifier_str, self).await
+    }
+    async fn resolve_for_purpose(
+        &self,
+        verifier_str: &str,
+        verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        verifier_resolver_for_purpose_impl(verifier_str, verification_relationship, self).await
+    }
+    async fn resolve_for_purpose_with_version(
+        &self,
+        verifier_str: &str,
+        verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<(Box<dyn signature_dyn::VerifierT>, Option<String>)> {
+        verifier_resolver_for_purpose_with_version_impl(
+            verifier_str,
+            verification_relationship,
+            self,
+        )
+        .await
+    }
+}
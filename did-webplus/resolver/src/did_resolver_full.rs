@@ -1,6 +1,10 @@
 #![allow(unused)]
 
-use crate::{DIDResolver, Error, Result, fetch_did_documents_jsonl_update, verifier_resolver_impl};
+use crate::{
+    DIDResolver, Error, Result, fetch_did_documents_jsonl_update,
+    verifier_resolver_for_purpose_impl, verifier_resolver_for_purpose_with_version_impl,
+    verifier_resolver_impl,
+};
 use did_webplus_core::{
     CreationMetadata, DIDDocumentMetadata, DIDResolutionMetadata, DIDResolutionOptions, DIDStr,
     DIDURIComponents, DIDWithQueryStr, LatestUpdateMetadata, NextUpdateMetadata,
@@ -22,6 +26,8 @@ pub struct DIDResolverFull {
     /// so that this resolver can take part in the scope of agreement defined by the VDG.  Without
     /// using a VDG, a DIDResolverFull has a scope of agreement that only contains itself.
     vdg_base_url_o: Option<url::Url>,
+    /// The host of the VDG, if specified (see vdg_base_url_o).
+    vdg_host_o: Option<String>,
     http_options_o: Option<did_webplus_core::HTTPOptions>,
 }
 
@@ -44,6 +50,7 @@ impl DIDResolverFull {
         Ok(Self {
             did_doc_store,
             vdg_base_url_o,
+            vdg_host_o: vdg_host_o.map(str::to_owned),
             http_options_o,
         })
     }
@@ -714,4 +721,26 @@ impl verifier_resolver::VerifierResolver for DIDResolverFull {
     ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
         verifier_resolver_impl(verifier_str, self).await
     }
+    async fn resolve_for_purpose(
+        &self,
+        verifier_str: &str,
+        verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        verifier_resolver_for_purpose_impl(verifier_str, verification_relationship, self).await
+    }
+    async fn resolve_for_purpose_with_version(
+        &self,
+        verifier_str: &str,
+        verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<(Box<dyn signature_dyn::VerifierT>, Option<String>)> {
+        verifier_resolver_for_purpose_with_version_impl(
+            verifier_str,
+            verification_relationship,
+            self,
+        )
+        .await
+    }
+    fn vdg_host_o(&self) -> Option<&str> {
+        self.vdg_host_o.as_deref()
+    }
 }
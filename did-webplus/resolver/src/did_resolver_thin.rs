@@ -3,7 +3,10 @@ use did_webplus_core::{
     HTTPSchemeOverride,
 };
 
-use crate::{DIDResolver, Error, HTTPError, REQWEST_CLIENT, Result, verifier_resolver_impl};
+use crate::{
+    DIDResolver, Error, HTTPError, REQWEST_CLIENT, Result, verifier_resolver_for_purpose_impl,
+    verifier_resolver_for_purpose_with_version_impl, verifier_resolver_impl,
+};
 use std::sync::Arc;
 
 /// Use a trusted VDG to resolve a DID.  This amounts to completely outsourcing fetching and verification
@@ -13,6 +16,8 @@ use std::sync::Arc;
 pub struct DIDResolverThin {
     /// Specifies the "base" URL of the VDG to use.  URLs for various operations of the VDG will be constructed from this.
     vdg_base_url: url::Url,
+    /// The host of the VDG (from which vdg_base_url was constructed).
+    vdg_host: String,
     http_headers_for_o: Option<HTTPHeadersFor>,
 }
 
@@ -48,6 +53,7 @@ impl DIDResolverThin {
         tracing::debug!("VDG base URL: {}", vdg_base_url);
         Ok(Self {
             vdg_base_url,
+            vdg_host: vdg_host.to_owned(),
             http_headers_for_o: http_options_o.map(|o| o.http_headers_for),
         })
     }
@@ -278,4 +284,26 @@ impl verifier_resolver::VerifierResolver for DIDResolverThin {
     ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
         verifier_resolver_impl(verifier_str, self).await
     }
+    async fn resolve_for_purpose(
+        &self,
+        verifier_str: &str,
+        verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        verifier_resolver_for_purpose_impl(verifier_str, verification_relationship, self).await
+    }
+    async fn resolve_for_purpose_with_version(
+        &self,
+        verifier_str: &str,
+        verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<(Box<dyn signature_dyn::VerifierT>, Option<String>)> {
+        verifier_resolver_for_purpose_with_version_impl(
+            verifier_str,
+            verification_relationship,
+            self,
+        )
+        .await
+    }
+    fn vdg_host_o(&self) -> Option<&str> {
+        Some(self.vdg_host.as_str())
+    }
 }
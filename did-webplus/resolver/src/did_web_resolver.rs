@@ -0,0 +1,259 @@
+use crate::{DIDResolver, Error, HTTPError, REQWEST_CLIENT, Result};
+use did_webplus_core::{DIDWebDocument, HTTPOptions, HTTPSchemeOverride};
+use std::sync::Arc;
+
+/// Resolves did:web DIDs by fetching their did.json files.  This is a VerifierResolver for prefix "did:web:",
+/// so it can be added to a VerifierResolverMap alongside a DIDResolver (which handles "did:webplus:").  If a
+/// did:webplus DIDResolver is specified via with_did_webplus_cross_check, then each resolved did:web DID
+/// document must be linked via alsoKnownAs to the corresponding did:webplus DID, and must match the
+/// did:web-compatible rendering of its latest DID document (see did_webplus_core::DIDWebDocument), as is the
+/// case for did.json files served by a did:webplus VDR.  This allows verifiers to cross-check both
+/// representations of a DID.
+#[derive(Clone)]
+pub struct DIDWebResolver {
+    http_options_o: Option<HTTPOptions>,
+    did_webplus_resolver_ao: Option<Arc<dyn DIDResolver>>,
+}
+
+impl DIDWebResolver {
+    pub fn new(http_options_o: Option<HTTPOptions>) -> Self {
+        Self {
+            http_options_o,
+            did_webplus_resolver_ao: None,
+        }
+    }
+    /// Builder-style method to cross-check each resolved did:web DID document against the latest DID
+    /// document of the corresponding did:webplus DID, as resolved by the given DIDResolver.
+    pub fn with_did_webplus_cross_check(mut self, did_resolver_a: Arc<dyn DIDResolver>) -> Self {
+        self.did_webplus_resolver_ao = Some(did_resolver_a);
+        self
+    }
+    /// Fetch and deserialize the did.json file for the given did:web DID, cross-checking it against the
+    /// corresponding did:webplus DID if so configured.
+    pub async fn resolve_did_web_document(&self, did_web: &str) -> Result<DIDWebDocument> {
+        let did_json_url = did_web_resolution_url(
+            did_web,
+            self.http_options_o
+                .as_ref()
+                .map(|http_options| &http_options.http_scheme_override),
+        )?;
+        tracing::debug!("did:web resolution URL: {}", did_json_url);
+
+        let header_map = {
+            let mut header_map = reqwest::header::HeaderMap::new();
+            if let Some(http_options) = self.http_options_o.as_ref() {
+                let did_json_url = url::Url::parse(&did_json_url)
+                    .map_err(|e| Error::MalformedDIDQuery(e.to_string().into()))?;
+                let http_header_v = http_options
+                    .http_headers_for
+                    .http_headers_for_hostname(did_json_url.host_str().unwrap())
+                    .unwrap_or_default();
+                for http_header in http_header_v {
+                    header_map.insert(
+                        reqwest::header::HeaderName::from_bytes(http_header.name.as_bytes()).map_err(|e| Error::GenericError(format!("Failed to parse HTTP header name from {:?}; error was: {}", http_header.name, e).into()))?,
+                        reqwest::header::HeaderValue::from_str(&http_header.value).map_err(|e| Error::GenericError(format!("Failed to parse HTTP header {:?} value to HeaderValue; error was: {}", http_header, e).into()))?,
+                    );
+                }
+            }
+            header_map
+        };
+        let did_json = REQWEST_CLIENT
+            .get(did_json_url.as_str())
+            .headers(header_map)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| {
+                Error::DIDResolutionFailure(HTTPError {
+                    status_code: e
+                        .status()
+                        .unwrap_or(reqwest::StatusCode::INTERNAL_SERVER_ERROR),
+                    description: e.to_string().into(),
+                })
+            })?
+            .text()
+            .await
+            .map_err(|e| {
+                Error::DIDResolutionFailure(HTTPError {
+                    status_code: reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+                    description: e.to_string().into(),
+                })
+            })?;
+        let did_web_document = serde_json::from_str::<DIDWebDocument>(&did_json)
+            .map_err(|e| Error::MalformedDIDDocument(e.to_string().into()))?;
+        if did_web_document.id != did_web {
+            return Err(Error::MalformedDIDDocument(
+                format!(
+                    "did:web DID document id {:?} does not match the DID being resolved {:?}",
+                    did_web_document.id, did_web
+                )
+                .into(),
+            ));
+        }
+
+        if let Some(did_webplus_resolver) = self.did_webplus_resolver_ao.as_deref() {
+            self.cross_check(did_webplus_resolver, &did_web_document)
+                .await?;
+        }
+
+        Ok(did_web_document)
+    }
+    async fn cross_check(
+        &self,
+        did_webplus_resolver: &dyn DIDResolver,
+        did_web_document: &DIDWebDocument,
+    ) -> Result<()> {
+        let did_webplus = did_web_document
+            .also_known_as_v
+            .iter()
+            .filter_map(|also_known_as| did_webplus_core::DIDStr::new_ref(also_known_as).ok())
+            .find(|did_webplus| did_webplus.to_did_web_string() == did_web_document.id)
+            .ok_or_else(|| {
+                Error::FailedConstraint(
+                    format!(
+                        "did:web DID document for {} is not linked via alsoKnownAs to the corresponding did:webplus DID",
+                        did_web_document.id
+                    )
+                    .into(),
+                )
+            })?;
+        let (did_document, _did_document_metadata, _did_resolution_metadata) = did_webplus_resolver
+            .resolve_did_document(
+                did_webplus.as_str(),
+                did_webplus_core::DIDResolutionOptions::default(),
+            )
+            .await?;
+        if DIDWebDocument::from(&did_document) != *did_web_document {
+            return Err(Error::FailedConstraint(
+                format!(
+                    "did:web DID document for {} does not match the latest DID document (versionId {}) of {}",
+                    did_web_document.id, did_document.version_id, did_webplus
+                )
+                .into(),
+            ));
+        }
+        Ok(())
+    }
+    async fn resolve_verifier(
+        &self,
+        verifier_str: &str,
+        verification_relationship_o: Option<verifier_resolver::VerificationRelationship>,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        if !verifier_str.starts_with("did:web:") {
+            Err(verifier_resolver::Error::InvalidVerifier(
+                format!(
+                    "expected verifier to begin with \"did:web:\", but verifier was {:?}",
+                    verifier_str
+                )
+                .into(),
+            ))?;
+        }
+
+        tracing::debug!(
+            "verifier was {:?}; verifying using did:web method",
+            verifier_str
+        );
+        let (did_web, key_id_fragment) = verifier_str.split_once('#').ok_or_else(|| {
+            verifier_resolver::Error::InvalidVerifier(
+                format!(
+                    "if did:web DID is used as verifier, it must have a fragment specifying the key ID, but it was {:?}",
+                    verifier_str
+                )
+                .into(),
+            )
+        })?;
+
+        let did_web_document = self.resolve_did_web_document(did_web).await?;
+
+        let verification_method = did_web_document
+            .verification_method_for_key_id_fragment(key_id_fragment)
+            .ok_or_else(|| {
+                verifier_resolver::Error::InvalidVerifier(
+                    format!("key {} not found in its DID document", verifier_str).into(),
+                )
+            })?;
+        if let Some(verification_relationship) = verification_relationship_o {
+            let key_purpose = did_webplus_core::KeyPurpose::from(verification_relationship);
+            if !did_web_document.key_id_fragment_has_purpose(key_id_fragment, key_purpose) {
+                Err(verifier_resolver::Error::UnauthorizedVerifier(
+                    format!(
+                        "key {} is not listed under {} in its DID document",
+                        verifier_str, key_purpose
+                    )
+                    .into(),
+                ))?;
+            }
+        }
+        let pub_key = mbx::MBPubKey::try_from(&verification_method.public_key_params)?;
+        let verifier_bytes = signature_dyn::VerifierBytes::try_from(&pub_key)
+            .map_err(|e| verifier_resolver::Error::InvalidVerifier(e.to_string().into()))?;
+        Ok(Box::new(verifier_bytes))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl verifier_resolver::VerifierResolver for DIDWebResolver {
+    async fn resolve(
+        &self,
+        verifier_str: &str,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        self.resolve_verifier(verifier_str, None).await
+    }
+    async fn resolve_for_purpose(
+        &self,
+        verifier_str: &str,
+        verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        self.resolve_verifier(verifier_str, Some(verification_relationship))
+            .await
+    }
+}
+
+/// Produce the URL of the did.json file for the given did:web DID, as specified by
+/// <https://w3c-ccg.github.io/did-method-web/#read-resolve>.  For a did:web DID corresponding to a
+/// did:webplus DID, this is the same as DIDStr::resolution_url_for_did_json.
+pub fn did_web_resolution_url(
+    did_web: &str,
+    http_scheme_override_o: Option<&HTTPSchemeOverride>,
+) -> Result<String> {
+    let method_specific_id = did_web.strip_prefix("did:web:").ok_or_else(|| {
+        Error::MalformedDIDQuery(format!("expected did:web DID, but got {:?}", did_web).into())
+    })?;
+    if method_specific_id.contains(['?', '#', '/']) {
+        return Err(Error::MalformedDIDQuery(
+            format!(
+                "did:web DID must not have a path, query, or fragment, but got {:?}",
+                did_web
+            )
+            .into(),
+        ));
+    }
+    let mut component_i = method_specific_id.split(':');
+    // A non-standard port is percent-encoded as part of the first component.
+    let host = component_i
+        .next()
+        .unwrap()
+        .replace("%3A", ":")
+        .replace("%3a", ":");
+    let hostname = host.split(':').next().unwrap();
+    if hostname.is_empty() {
+        return Err(Error::MalformedDIDQuery(
+            format!("did:web DID has empty host: {:?}", did_web).into(),
+        ));
+    }
+    let http_scheme =
+        HTTPSchemeOverride::determine_http_scheme_for_host_from(http_scheme_override_o, hostname)
+            .map_err(|e| Error::MalformedDIDQuery(e.to_string().into()))?;
+    let path_component_v = component_i.collect::<Vec<_>>();
+    if path_component_v.is_empty() {
+        Ok(format!("{}://{}/.well-known/did.json", http_scheme, host))
+    } else {
+        Ok(format!(
+            "{}://{}/{}/did.json",
+            http_scheme,
+            host,
+            path_component_v.join("/")
+        ))
+    }
+}
@@ -14,6 +14,8 @@ pub enum Error {
     FailedConstraint(Cow<'static, str>),
     #[error("Generic error: {0}")]
     GenericError(Cow<'static, str>),
+    #[error("Invalid DID bundle: {0}")]
+    InvalidDIDBundle(Cow<'static, str>),
     #[error("Invalid verifier: {0}")]
     InvalidVerifier(Cow<'static, str>),
     #[error("Malformed DID document: {0}")]
@@ -26,6 +28,21 @@ pub enum Error {
     StorageError(#[from] storage_traits::Error),
 }
 
+impl Error {
+    /// Returns true if this error means that the requested DID document was resolved, but some of the requested
+    /// DID document metadata couldn't be determined (e.g. by a local-only resolution, or by a DIDResolverBundle
+    /// that has no trusted latest attestation), so that resolving without that metadata would succeed.
+    pub fn is_did_document_metadata_unavailable(&self) -> bool {
+        match self {
+            Self::DIDResolutionFailure2(did_resolution_metadata) => {
+                did_resolution_metadata.did_document_resolved_locally
+                    && !did_resolution_metadata.did_document_metadata_resolved_locally
+            }
+            _ => false,
+        }
+    }
+}
+
 impl From<HTTPError> for Error {
     fn from(http_error: HTTPError) -> Self {
         Self::DIDResolutionFailure(http_error)
@@ -0,0 +1,155 @@
+use crate::{DIDResolver, Error, Result};
+use did_webplus_core::{DIDKeyResourceFullyQualifiedStr, DIDResolutionOptions};
+use std::str::FromStr;
+
+/// The verdict of checking if a key was valid at a claimed signing time.  The validity window of a key
+/// used in a particular DID document is from that DID document's validFrom (inclusive) up to the validFrom
+/// of the next DID document (exclusive), or indefinitely if there is no next DID document.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum KeyValidity {
+    /// The key was valid at the signing time.
+    Valid,
+    /// The signing time is before the validFrom of the DID document that the key ID references.
+    SignedBeforeValidFrom { valid_from: time::OffsetDateTime },
+    /// The signing time is at or after the DID was updated, so the referenced DID document (and therefore
+    /// the key) was no longer current.
+    SignedAfterRotation {
+        next_update_time: time::OffsetDateTime,
+        next_version_id: u32,
+    },
+    /// The signing time is at or after the DID was deactivated.
+    DIDDeactivated {
+        deactivated_at: time::OffsetDateTime,
+    },
+}
+
+impl KeyValidity {
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Self::Valid)
+    }
+}
+
+impl std::fmt::Display for KeyValidity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Valid => write!(f, "key was valid at signing time"),
+            Self::SignedBeforeValidFrom { valid_from } => write!(
+                f,
+                "signing time is before the DID document's validFrom ({})",
+                valid_from
+            ),
+            Self::SignedAfterRotation {
+                next_update_time,
+                next_version_id,
+            } => write!(
+                f,
+                "signing time is at or after the DID was updated to versionId {} ({})",
+                next_version_id, next_update_time
+            ),
+            Self::DIDDeactivated { deactivated_at } => write!(
+                f,
+                "signing time is at or after the DID was deactivated ({})",
+                deactivated_at
+            ),
+        }
+    }
+}
+
+/// Determine if the given fully qualified key was valid at the given (claimed) signing time, e.g. a JWT's
+/// "iat" claim or a VC's "issuanceDate".  This resolves the DID document referenced by the key ID along
+/// with its next-update metadata, so that the key's validity window is known.  Note that this doesn't
+/// verify any signature; it's meant to be used in addition to signature verification.
+///
+/// Because signing time claims often have only whole-second precision (e.g. JWT "iat"), if the signing
+/// time is truncated to seconds, then it's compared against the validFrom truncated to seconds, so that
+/// a signature produced in the same second as the DID document became valid isn't spuriously rejected.
+pub async fn key_validity_at(
+    did_resolver: &dyn DIDResolver,
+    key_id: &DIDKeyResourceFullyQualifiedStr,
+    signing_time: time::OffsetDateTime,
+) -> Result<KeyValidity> {
+    let (did_document, did_document_metadata, _did_resolution_metadata) = did_resolver
+        .resolve_did_document(
+            key_id.without_fragment().as_str(),
+            DIDResolutionOptions {
+                request_next: true,
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    // Check that the key is actually present in the DID document.
+    did_document
+        .public_key_material
+        .verification_method_for_key_id_fragment(key_id.fragment())
+        .map_err(|e| Error::InvalidVerifier(e.to_string().into()))?;
+
+    let valid_from = did_document
+        .valid_from()
+        .map_err(|e| Error::MalformedDIDDocument(e.to_string().into()))?;
+    let valid_from_for_comparison = if did_webplus_core::is_truncated_to_seconds(signing_time) {
+        did_webplus_core::truncated_to_seconds(valid_from)
+    } else {
+        valid_from
+    };
+    if signing_time < valid_from_for_comparison {
+        return Ok(KeyValidity::SignedBeforeValidFrom { valid_from });
+    }
+
+    let next_update_time = match did_document_metadata.next_update_time_milliseconds_o() {
+        Some(next_update_time) if signing_time >= next_update_time => next_update_time,
+        _ => {
+            return Ok(KeyValidity::Valid);
+        }
+    };
+    let next_version_id = did_document_metadata
+        .next_update_version_id_o()
+        .map(u32::from_str)
+        .transpose()
+        .map_err(|e| Error::GenericError(format!("Malformed nextVersionId: {}", e).into()))?
+        .ok_or_else(|| {
+            Error::GenericError("nextUpdate metadata is missing nextVersionId".into())
+        })?;
+
+    // The key is no longer valid; determine if that's because of deactivation.  This is a separate
+    // resolution because determining deactivation requires knowing the latest DID document, which
+    // isn't always possible (e.g. for DIDResolverBundle without a latest attestation), and isn't needed
+    // in the common case.
+    match did_resolver
+        .resolve_did_document_string(
+            key_id.without_fragment().did().as_str(),
+            DIDResolutionOptions {
+                request_latest: true,
+                request_deactivated: true,
+                ..Default::default()
+            },
+        )
+        .await
+    {
+        Ok((_, latest_did_document_metadata, _)) => {
+            // If the DID is deactivated, then the latest update is the deactivation.
+            let deactivated_at_o = if latest_did_document_metadata.deactivated_o == Some(true) {
+                latest_did_document_metadata.latest_update_time_milliseconds_o()
+            } else {
+                None
+            };
+            if let Some(deactivated_at) =
+                deactivated_at_o.filter(|&deactivated_at| signing_time >= deactivated_at)
+            {
+                return Ok(KeyValidity::DIDDeactivated { deactivated_at });
+            }
+        }
+        Err(e) => {
+            tracing::debug!(
+                "could not determine deactivation status of {}; reporting key rotation instead: {}",
+                key_id.without_fragment().did(),
+                e
+            );
+        }
+    }
+
+    Ok(KeyValidity::SignedAfterRotation {
+        next_update_time,
+        next_version_id,
+    })
+}
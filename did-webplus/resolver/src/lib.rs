@@ -1,17 +1,34 @@
+mod caching_did_resolver;
+mod did_bundle;
+mod did_bundle_latest_attestation;
 mod did_resolver;
+mod did_resolver_bundle;
 mod did_resolver_factory;
 mod did_resolver_full;
 mod did_resolver_thin;
+mod did_web_resolver;
 mod error;
 mod http;
+mod key_validity;
+mod version_time;
 
 pub use crate::{
-    did_resolver::{DIDResolver, verifier_resolver_impl},
+    caching_did_resolver::CachingDIDResolver,
+    did_bundle::DIDBundle,
+    did_bundle_latest_attestation::DIDBundleLatestAttestation,
+    did_resolver::{
+        DIDResolver, verifier_resolver_for_purpose_impl,
+        verifier_resolver_for_purpose_with_version_impl, verifier_resolver_impl,
+    },
+    did_resolver_bundle::DIDResolverBundle,
     did_resolver_factory::DIDResolverFactory,
     did_resolver_full::DIDResolverFull,
     did_resolver_thin::DIDResolverThin,
+    did_web_resolver::{DIDWebResolver, did_web_resolution_url},
     error::Error,
     http::{HTTPError, HTTPResult},
+    key_validity::{KeyValidity, key_validity_at},
+    version_time::resolve_did_document_string_at_version_time,
 };
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -0,0 +1,77 @@
+use crate::{DIDResolver, Error, Result};
+use did_webplus_core::{
+    DIDDocument, DIDDocumentMetadata, DIDResolutionMetadata, DIDResolutionOptions, DIDStr,
+};
+
+/// Resolve the DID document of the given DID that was current at the given time, i.e. the one having the
+/// greatest validFrom that is not after version_time.  This is what the DID spec's "versionTime" query
+/// parameter means.  The requested metadata (see DIDResolutionOptions) is for that DID document.  The DID
+/// documents are found by binary search over the versionIds, so this takes a logarithmic number of resolutions.
+pub async fn resolve_did_document_string_at_version_time(
+    did_resolver: &dyn DIDResolver,
+    did: &DIDStr,
+    version_time: time::OffsetDateTime,
+    did_resolution_options: DIDResolutionOptions,
+) -> Result<(String, DIDDocumentMetadata, DIDResolutionMetadata)> {
+    let local_resolution_only = did_resolution_options.local_resolution_only;
+    let valid_from_of = |did_document_string: &str| -> Result<(u32, time::OffsetDateTime)> {
+        let did_document: DIDDocument = serde_json::from_str(did_document_string)
+            .map_err(|e| Error::MalformedDIDDocument(e.to_string().into()))?;
+        let valid_from = did_document
+            .valid_from()
+            .map_err(|e| Error::MalformedDIDDocument(e.to_string().into()))?;
+        Ok((did_document.version_id, valid_from))
+    };
+
+    let (latest_did_document_string, _, _) = did_resolver
+        .resolve_did_document_string(
+            did.as_str(),
+            DIDResolutionOptions::no_metadata(local_resolution_only),
+        )
+        .await?;
+    let (latest_version_id, latest_valid_from) =
+        valid_from_of(latest_did_document_string.as_str())?;
+
+    let version_id = if latest_valid_from <= version_time {
+        latest_version_id
+    } else {
+        // Invariant: the DID document with versionId hi has validFrom after version_time, and if lo_o is
+        // Some(lo), then the DID document with versionId lo has validFrom not after version_time.
+        let mut lo_o: Option<u32> = None;
+        let mut hi = latest_version_id;
+        loop {
+            let mid = match lo_o {
+                Some(lo) if lo + 1 == hi => {
+                    break lo;
+                }
+                Some(lo) => lo + (hi - lo) / 2,
+                // Check the root DID document first, since version_time may precede the DID's creation.
+                None => 0,
+            };
+            if mid == hi {
+                return Err(Error::FailedConstraint(
+                    format!("versionTime {} is before {} was created", version_time, did).into(),
+                ));
+            }
+            let (did_document_string, _, _) = did_resolver
+                .resolve_did_document_string(
+                    did.with_query_version_id(mid).as_str(),
+                    DIDResolutionOptions::no_metadata(local_resolution_only),
+                )
+                .await?;
+            let (_, valid_from) = valid_from_of(did_document_string.as_str())?;
+            if valid_from <= version_time {
+                lo_o = Some(mid);
+            } else {
+                hi = mid;
+            }
+        }
+    };
+
+    did_resolver
+        .resolve_did_document_string(
+            did.with_query_version_id(version_id).as_str(),
+            did_resolution_options,
+        )
+        .await
+}
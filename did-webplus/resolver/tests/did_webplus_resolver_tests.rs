@@ -82,6 +82,8 @@ impl Services {
                 http_headers_for: Default::default(),
                 http_scheme_override: Default::default(),
                 test_authz_api_key_vo: None,
+                admin_api_key_vo: None,
+                audit_interval_seconds_o: None,
             };
             let vdg_handle = did_webplus_vdg_lib::spawn_vdg(vdg_config.clone())
                 .await
@@ -123,6 +125,10 @@ impl Services {
             vdg_base_url_v,
             http_scheme_override: Default::default(),
             test_authz_api_key_vo: None,
+            registrar_permissions_o: None,
+            serve_did_web: true,
+            audit_interval_seconds_o: None,
+            admission_policy_config: Default::default(),
         };
         let vdr_handle = did_webplus_vdr_lib::spawn_vdr(vdr_config.clone())
             .await
@@ -138,6 +144,20 @@ impl Services {
             )
             .await;
             tracing::info!("VDG is up");
+            // No admin API keys are configured, so the admin endpoints must not be served.
+            assert_eq!(
+                test_util::REQWEST_CLIENT
+                    .get(
+                        vdg_base_url
+                            .join("webplus/v1/admin/keys/uHiBKHZUE3HHlYcyVIF-vPm0Xg71vqJla2L1OGXHMSK4NEA")
+                            .expect("pass"),
+                    )
+                    .send()
+                    .await
+                    .expect("pass")
+                    .status(),
+                reqwest::StatusCode::NOT_FOUND
+            );
         }
         test_util::wait_until_service_is_up("VDR", vdr_url.join("health").expect("pass").as_str())
             .await;
@@ -220,6 +240,7 @@ async fn test_did_resolver() {
                 vdr_did_create_endpoint: services.vdr_url.as_str(),
                 mb_hash_function_for_did: &mb_hash_function,
                 mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                vdr_authz_key_id_o: None,
             },
             None,
         )
@@ -417,6 +438,7 @@ async fn test_did_resolver_impl(
                 vdr_did_create_endpoint: services.vdr_url.as_str(),
                 mb_hash_function_for_did: &mb_hash_function,
                 mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                vdr_authz_key_id_o: None,
             },
             None,
         )
@@ -1026,3 +1048,663 @@ async fn test_did_resolver_thin() {
 
     services.abort();
 }
+
+/// Build a valid create → update microledger via MockWallet / MockVDR, returning the DID and the
+/// corresponding did-documents.jsonl content.
+fn build_did_documents_jsonl() -> (did_webplus_core::DID, String) {
+    let hostname = "example.com".to_string();
+    let mock_vdr_la = Arc::new(std::sync::RwLock::new(did_webplus_mock::MockVDR::new_with(
+        hostname.clone(),
+        None,
+        None,
+        None,
+    )));
+    let mock_vdr_lam = {
+        let mut mock_vdr_lam = HashMap::new();
+        mock_vdr_lam.insert(hostname.clone(), mock_vdr_la);
+        mock_vdr_lam
+    };
+    let mock_vdr_client_a = Arc::new(did_webplus_mock::MockVDRClient::new(
+        "resolver test MockVDRClient".to_string(),
+        mock_vdr_lam,
+    ));
+    let mut wallet =
+        did_webplus_mock::MockWallet::new("resolver test wallet".to_string(), mock_vdr_client_a);
+
+    let key_type = signature_dyn::KeyType::Ed25519;
+    let mb_hash_function = selfhash::MBHashFunction::blake3(mbx::Base::Base64Url);
+    let did = wallet
+        .create_did(hostname, None, None, key_type, &mb_hash_function)
+        .expect("pass");
+    wallet.update_did(&did, key_type).expect("pass");
+
+    use did_webplus_mock::MicroledgerView;
+    let mut did_documents_jsonl = String::new();
+    for did_document in wallet
+        .controlled_did(&did)
+        .expect("pass")
+        .microledger()
+        .view()
+        .select_did_documents(None, None)
+        .1
+    {
+        did_documents_jsonl.push_str(&did_document.serialize_canonically().expect("pass"));
+        did_documents_jsonl.push('\n');
+    }
+
+    (did, did_documents_jsonl)
+}
+
+#[tokio::test]
+async fn test_did_resolver_bundle() {
+    use did_webplus_resolver::DIDResolver;
+
+    let (did, did_documents_jsonl) = build_did_documents_jsonl();
+    let mut did_bundle = did_webplus_resolver::DIDBundle {
+        did: did.clone(),
+        did_documents_jsonl,
+        latest_attestation_o: None,
+    };
+    let (did_document_v, _did_document_jcs_v) = did_bundle.verify_did_documents().expect("pass");
+    assert_eq!(did_document_v.len(), 2);
+    let root_did_document = &did_document_v[0];
+    let latest_did_document = &did_document_v[1];
+
+    // Without a latest attestation, only specific DID documents can be resolved.
+    {
+        let did_resolver_bundle = did_webplus_resolver::DIDResolverBundle::new()
+            .with_did_bundle(&did_bundle, None)
+            .await
+            .expect("pass");
+        let root_did_query = did.with_queries(&root_did_document.self_hash, 0);
+        let (did_document, did_document_metadata, _did_resolution_metadata) = did_resolver_bundle
+            .resolve_did_document(
+                root_did_query.as_str(),
+                did_webplus_core::DIDResolutionOptions {
+                    request_creation: true,
+                    request_next: true,
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("pass");
+        assert_eq!(&did_document, root_did_document);
+        assert_eq!(
+            did_document_metadata.next_update_time_milliseconds_o(),
+            Some(latest_did_document.valid_from().expect("pass"))
+        );
+        // The root DID document is in the bundle, but its deactivation status can't be determined.
+        let err = did_resolver_bundle
+            .resolve_did_document(
+                root_did_query.as_str(),
+                did_webplus_core::DIDResolutionOptions::all_metadata(false),
+            )
+            .await
+            .expect_err("pass");
+        assert!(err.is_did_document_metadata_unavailable());
+        // The latest DID document itself can't be determined, so that isn't just unavailable metadata.
+        let err = did_resolver_bundle
+            .resolve_did_document(
+                did.as_str(),
+                did_webplus_core::DIDResolutionOptions::default(),
+            )
+            .await
+            .expect_err("pass");
+        assert!(!err.is_did_document_metadata_unavailable());
+    }
+
+    // A latest attestation from an untrusted attester is rejected.
+    use signature_dyn::SignerT;
+    let attester_signer_b = signature_dyn::KeyType::Ed25519.generate_random_private_key();
+    let attester_kid =
+        did_key::DIDResource::try_from(&attester_signer_b.get_verifier_bytes().expect("pass"))
+            .expect("pass")
+            .to_string();
+    did_bundle.latest_attestation_o = Some(
+        did_webplus_resolver::DIDBundleLatestAttestation {
+            did: did.clone(),
+            self_hash: latest_did_document.self_hash.clone(),
+            version_id: latest_did_document.version_id,
+            as_of: did_webplus_core::now_utc_milliseconds(),
+        }
+        .sign(attester_kid.clone(), attester_signer_b.as_ref())
+        .expect("pass"),
+    );
+    {
+        let untrusted_verifier_resolver = verifier_resolver::VerifierResolverMap::new();
+        did_webplus_resolver::DIDResolverBundle::new()
+            .with_did_bundle(&did_bundle, Some(&untrusted_verifier_resolver))
+            .await
+            .expect_err("pass");
+    }
+    // A trusted attester whose DID merely shares a prefix with the attester's DID doesn't make the
+    // attestation trusted.
+    {
+        let other_signer_b = signature_dyn::KeyType::Ed25519.generate_random_private_key();
+        let other_did = did_key::DID::try_from(&other_signer_b.get_verifier_bytes().expect("pass"))
+            .expect("pass");
+        assert!(attester_kid.starts_with("did:key:z6Mk"));
+        assert!(other_did.as_str().starts_with("did:key:z6Mk"));
+        let untrusted_verifier_resolver =
+            did_key::TrustedDIDKeyVerifierResolver::new(vec![other_did]);
+        did_webplus_resolver::DIDResolverBundle::new()
+            .with_did_bundle(&did_bundle, Some(&untrusted_verifier_resolver))
+            .await
+            .expect_err("pass");
+    }
+
+    // With a trusted latest attestation, the latest DID document and its metadata can be resolved.
+    {
+        let attester_did =
+            did_key::DID::try_from(&attester_signer_b.get_verifier_bytes().expect("pass"))
+                .expect("pass");
+        let trusted_verifier_resolver =
+            did_key::TrustedDIDKeyVerifierResolver::new(vec![attester_did]);
+        let did_resolver_bundle = did_webplus_resolver::DIDResolverBundle::new()
+            .with_did_bundle(&did_bundle, Some(&trusted_verifier_resolver))
+            .await
+            .expect("pass");
+        assert!(did_resolver_bundle.latest_as_of_o(&did).is_some());
+        let (did_document, did_document_metadata, did_resolution_metadata) = did_resolver_bundle
+            .resolve_did_document(
+                did.as_str(),
+                did_webplus_core::DIDResolutionOptions::all_metadata(true),
+            )
+            .await
+            .expect("pass");
+        assert_eq!(&did_document, latest_did_document);
+        assert_eq!(did_document_metadata.next_update_metadata_o, None);
+        assert_eq!(did_document_metadata.deactivated_o, Some(false));
+        assert!(!did_resolution_metadata.fetched_updates_from_vdr);
+    }
+
+    // A tampered bundle is rejected.
+    {
+        let mut tampered_did_bundle = did_bundle.clone();
+        tampered_did_bundle.did_documents_jsonl = tampered_did_bundle
+            .did_documents_jsonl
+            .split_inclusive('\n')
+            .skip(1)
+            .collect();
+        did_webplus_resolver::DIDResolverBundle::new()
+            .with_did_bundle(&tampered_did_bundle, None)
+            .await
+            .expect_err("pass");
+    }
+}
+
+/// Wraps a DIDResolver and counts the number of resolutions, so that caching can be tested.
+struct CountingDIDResolver {
+    did_resolver_b: Box<dyn did_webplus_resolver::DIDResolver>,
+    resolution_count: std::sync::atomic::AtomicUsize,
+}
+
+impl CountingDIDResolver {
+    fn resolution_count(&self) -> usize {
+        self.resolution_count
+            .load(std::sync::atomic::Ordering::SeqCst)
+    }
+}
+
+#[async_trait::async_trait]
+impl did_webplus_resolver::DIDResolver for CountingDIDResolver {
+    async fn resolve_did_document_string(
+        &self,
+        did_query: &str,
+        did_resolution_options: did_webplus_core::DIDResolutionOptions,
+    ) -> did_webplus_resolver::Result<(
+        String,
+        did_webplus_core::DIDDocumentMetadata,
+        did_webplus_core::DIDResolutionMetadata,
+    )> {
+        self.resolution_count
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        self.did_resolver_b
+            .resolve_did_document_string(did_query, did_resolution_options)
+            .await
+    }
+    fn as_verifier_resolver(&self) -> &dyn verifier_resolver::VerifierResolver {
+        self
+    }
+    fn as_verifier_resolver_a(self: Arc<Self>) -> Arc<dyn verifier_resolver::VerifierResolver> {
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl verifier_resolver::VerifierResolver for CountingDIDResolver {
+    async fn resolve(
+        &self,
+        verifier_str: &str,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        did_webplus_resolver::verifier_resolver_impl(verifier_str, self).await
+    }
+    async fn resolve_for_purpose(
+        &self,
+        verifier_str: &str,
+        verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        did_webplus_resolver::verifier_resolver_for_purpose_impl(
+            verifier_str,
+            verification_relationship,
+            self,
+        )
+        .await
+    }
+}
+
+#[tokio::test]
+async fn test_caching_did_resolver() {
+    use did_webplus_resolver::DIDResolver;
+    use signature_dyn::SignerT;
+
+    let (did, did_documents_jsonl) = build_did_documents_jsonl();
+    let attester_signer_b = signature_dyn::KeyType::Ed25519.generate_random_private_key();
+    let attester_kid =
+        did_key::DIDResource::try_from(&attester_signer_b.get_verifier_bytes().expect("pass"))
+            .expect("pass")
+            .to_string();
+    let mut did_bundle = did_webplus_resolver::DIDBundle {
+        did: did.clone(),
+        did_documents_jsonl,
+        latest_attestation_o: None,
+    };
+    let (did_document_v, _did_document_jcs_v) = did_bundle.verify_did_documents().expect("pass");
+    let latest_did_document = did_document_v.last().unwrap();
+    did_bundle.latest_attestation_o = Some(
+        did_webplus_resolver::DIDBundleLatestAttestation {
+            did: did.clone(),
+            self_hash: latest_did_document.self_hash.clone(),
+            version_id: latest_did_document.version_id,
+            as_of: did_webplus_core::now_utc_milliseconds(),
+        }
+        .sign(attester_kid.clone(), attester_signer_b.as_ref())
+        .expect("pass"),
+    );
+    let trusted_verifier_resolver = verifier_resolver::VerifierResolverMap::new()
+        .with(&attester_kid, Box::new(did_key::DIDKeyVerifierResolver));
+    let did_resolver_bundle = did_webplus_resolver::DIDResolverBundle::new()
+        .with_did_bundle(&did_bundle, Some(&trusted_verifier_resolver))
+        .await
+        .expect("pass");
+
+    for latest_ttl in [time::Duration::ZERO, time::Duration::hours(1)] {
+        let counting_did_resolver_a = Arc::new(CountingDIDResolver {
+            did_resolver_b: Box::new(did_resolver_bundle.clone()),
+            resolution_count: std::sync::atomic::AtomicUsize::new(0),
+        });
+        let caching_did_resolver = did_webplus_resolver::CachingDIDResolver::new(
+            counting_did_resolver_a.clone(),
+            std::num::NonZeroUsize::new(16).unwrap(),
+            latest_ttl,
+        );
+
+        // Fully qualified queries are cached indefinitely, regardless of the order of query params.
+        let did_fully_qualified =
+            did.with_queries(&did_document_v[0].self_hash, did_document_v[0].version_id);
+        let reordered_did_query = format!(
+            "{}?versionId={}&selfHash={}",
+            did, did_document_v[0].version_id, did_document_v[0].self_hash
+        );
+        for did_query in [did_fully_qualified.as_str(), reordered_did_query.as_str()] {
+            let (did_document, _did_document_metadata, _did_resolution_metadata) =
+                caching_did_resolver
+                    .resolve_did_document(
+                        did_query,
+                        did_webplus_core::DIDResolutionOptions::default(),
+                    )
+                    .await
+                    .expect("pass");
+            assert_eq!(did_document, did_document_v[0]);
+        }
+        assert_eq!(counting_did_resolver_a.resolution_count(), 1);
+
+        // Requests for metadata always go to the underlying resolver.
+        caching_did_resolver
+            .resolve_did_document(
+                did_fully_qualified.as_str(),
+                did_webplus_core::DIDResolutionOptions::all_metadata(true),
+            )
+            .await
+            .expect("pass");
+        assert_eq!(counting_did_resolver_a.resolution_count(), 2);
+
+        // Latest queries are only cached if the TTL is positive.
+        for _ in 0..2 {
+            let (did_document, _did_document_metadata, _did_resolution_metadata) =
+                caching_did_resolver
+                    .resolve_did_document(
+                        did.as_str(),
+                        did_webplus_core::DIDResolutionOptions::default(),
+                    )
+                    .await
+                    .expect("pass");
+            assert_eq!(&did_document, latest_did_document);
+        }
+        if latest_ttl.is_positive() {
+            assert_eq!(counting_did_resolver_a.resolution_count(), 3);
+        } else {
+            assert_eq!(counting_did_resolver_a.resolution_count(), 4);
+        }
+
+        caching_did_resolver.clear();
+        assert_eq!(caching_did_resolver.cache_sizes(), (0, 0));
+    }
+}
+
+#[tokio::test]
+async fn test_key_validity_at() {
+    use did_webplus_resolver::KeyValidity;
+
+    // No latest attestation is used, so the validity window is only determinable for keys in DID documents
+    // that have a next DID document in the bundle.
+    let (did, did_documents_jsonl) = build_did_documents_jsonl();
+    let did_bundle = did_webplus_resolver::DIDBundle {
+        did: did.clone(),
+        did_documents_jsonl,
+        latest_attestation_o: None,
+    };
+    let (did_document_v, _did_document_jcs_v) = did_bundle.verify_did_documents().expect("pass");
+    let did_resolver_bundle = did_webplus_resolver::DIDResolverBundle::new()
+        .with_did_bundle(&did_bundle, None)
+        .await
+        .expect("pass");
+
+    let root_valid_from = did_document_v[0].valid_from().expect("pass");
+    let update_valid_from = did_document_v[1].valid_from().expect("pass");
+    let root_key_id = &did_document_v[0].public_key_material.verification_method_v[0].id;
+    let update_key_id = &did_document_v[1].public_key_material.verification_method_v[0].id;
+
+    // Signed within the validity window of the root DID document.
+    assert_eq!(
+        did_webplus_resolver::key_validity_at(&did_resolver_bundle, root_key_id, root_valid_from)
+            .await
+            .expect("pass"),
+        KeyValidity::Valid
+    );
+    // Signed before the root DID document was valid.
+    assert_eq!(
+        did_webplus_resolver::key_validity_at(
+            &did_resolver_bundle,
+            root_key_id,
+            root_valid_from - time::Duration::seconds(1)
+        )
+        .await
+        .expect("pass"),
+        KeyValidity::SignedBeforeValidFrom {
+            valid_from: root_valid_from
+        }
+    );
+    // Signed with the root DID document's key after it was rotated.
+    assert_eq!(
+        did_webplus_resolver::key_validity_at(&did_resolver_bundle, root_key_id, update_valid_from)
+            .await
+            .expect("pass"),
+        KeyValidity::SignedAfterRotation {
+            next_update_time: update_valid_from,
+            next_version_id: 1,
+        }
+    );
+    // Without a latest attestation, it's unknown if the key in the last DID document in the bundle has
+    // since been rotated, so its validity can't be determined.
+    assert!(
+        did_webplus_resolver::key_validity_at(
+            &did_resolver_bundle,
+            update_key_id,
+            update_valid_from + time::Duration::days(1),
+        )
+        .await
+        .is_err()
+    );
+
+    // A key that isn't in the DID document is an error, not a verdict.
+    let nonexistent_key_id = did
+        .with_queries(&did_document_v[0].self_hash, did_document_v[0].version_id)
+        .with_fragment("nonexistent");
+    assert!(
+        did_webplus_resolver::key_validity_at(
+            &did_resolver_bundle,
+            &nonexistent_key_id,
+            root_valid_from
+        )
+        .await
+        .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_resolve_for_purpose() {
+    use verifier_resolver::{VerificationRelationship, VerifierResolver};
+
+    let (did, did_documents_jsonl) = build_did_documents_jsonl();
+    let did_bundle = did_webplus_resolver::DIDBundle {
+        did,
+        did_documents_jsonl,
+        latest_attestation_o: None,
+    };
+    let (did_document_v, _did_document_jcs_v) = did_bundle.verify_did_documents().expect("pass");
+    let did_resolver_bundle = did_webplus_resolver::DIDResolverBundle::new()
+        .with_did_bundle(&did_bundle, None)
+        .await
+        .expect("pass");
+
+    // Each key must resolve for exactly the verification relationships it's listed under.
+    for did_document in did_document_v.iter() {
+        for verification_method in did_document
+            .public_key_material
+            .verification_method_v
+            .iter()
+        {
+            let key_purpose_flags = did_document
+                .public_key_material
+                .key_purpose_flags_for_key_id_fragment(verification_method.id.fragment());
+            for verification_relationship in [
+                VerificationRelationship::Authentication,
+                VerificationRelationship::AssertionMethod,
+                VerificationRelationship::KeyAgreement,
+                VerificationRelationship::CapabilityInvocation,
+                VerificationRelationship::CapabilityDelegation,
+            ] {
+                let result = did_resolver_bundle
+                    .resolve_for_purpose(verification_method.id.as_str(), verification_relationship)
+                    .await;
+                let key_purpose = did_webplus_core::KeyPurpose::from(verification_relationship);
+                if key_purpose_flags.contains(key_purpose) {
+                    assert!(
+                        result.is_ok(),
+                        "{} should resolve for {}",
+                        verification_method.id,
+                        verification_relationship
+                    );
+                    // The VerificationReport should record the version of the DID document that the
+                    // resolver actually resolved the key from.
+                    let did_document_version = did_document
+                        .did
+                        .with_queries(&did_document.self_hash, did_document.version_id)
+                        .to_string();
+                    let mut verification_report = verifier_resolver::VerificationReport::new();
+                    verification_report
+                        .resolve_for_purpose(
+                            verification_method.id.as_str(),
+                            verification_relationship,
+                            &did_resolver_bundle,
+                        )
+                        .await
+                        .expect("pass");
+                    for check in verification_report.check_v.iter() {
+                        assert_eq!(
+                            check.did_document_version_o.as_deref(),
+                            Some(did_document_version.as_str())
+                        );
+                    }
+                    assert_eq!(
+                        verification_report
+                            .resolved_did_document_version(verification_method.id.as_str()),
+                        Some(did_document_version)
+                    );
+                } else {
+                    assert!(
+                        matches!(
+                            result
+                                .as_ref()
+                                .map_err(|e| e.downcast_ref::<verifier_resolver::Error>()),
+                            Err(Some(verifier_resolver::Error::UnauthorizedVerifier(_)))
+                        ),
+                        "{} should not resolve for {}",
+                        verification_method.id,
+                        verification_relationship
+                    );
+                }
+            }
+            // Purpose-agnostic resolution is unaffected.
+            did_resolver_bundle
+                .resolve(verification_method.id.as_str())
+                .await
+                .expect("pass");
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_did_web_resolver() {
+    use did_webplus_resolver::DIDResolver;
+    use verifier_resolver::{VerificationRelationship, VerifierResolver};
+
+    let services_config = ServicesConfig::with_vdr(
+        50040,
+        "postgres:///test_vdr_for_did_web_resolver".to_string(),
+    );
+    let services = Services::spin_up(services_config).await;
+
+    let (_wallet_storage_a, software_wallet) = create_in_memory_software_wallet().await;
+    let mb_hash_function = selfhash::MBHashFunction::blake3(mbx::Base::Base64Url);
+    use did_webplus_wallet::Wallet;
+    let controlled_did = software_wallet
+        .create_did(
+            did_webplus_wallet::CreateDIDParameters {
+                vdr_did_create_endpoint: services.vdr_url.as_str(),
+                mb_hash_function_for_did: &mb_hash_function,
+                mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                vdr_authz_key_id_o: None,
+            },
+            None,
+        )
+        .await
+        .expect("pass");
+    let did = controlled_did.did().to_owned();
+    let did_web = did.to_did_web_string();
+    assert_eq!(
+        did_webplus_resolver::did_web_resolution_url(&did_web, None).expect("pass"),
+        did.resolution_url_for_did_json(None)
+    );
+
+    let did_resolver_full_a: Arc<dyn DIDResolver> = Arc::new(create_did_resolver_full(None).await);
+    let did_web_resolver = did_webplus_resolver::DIDWebResolver::new(None)
+        .with_did_webplus_cross_check(did_resolver_full_a.clone());
+
+    for update_count in 0..2 {
+        if update_count > 0 {
+            software_wallet
+                .update_did(
+                    did_webplus_wallet::UpdateDIDParameters {
+                        did: &did,
+                        change_mb_hash_function_for_self_hash_o: None,
+                        mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                    },
+                    None,
+                )
+                .await
+                .expect("pass");
+        }
+        let (did_document, _did_document_metadata, _did_resolution_metadata) = did_resolver_full_a
+            .resolve_did_document(
+                did.as_str(),
+                did_webplus_core::DIDResolutionOptions::default(),
+            )
+            .await
+            .expect("pass");
+        assert_eq!(did_document.version_id, update_count);
+
+        // The did.json file must reflect the latest DID document.
+        let did_web_document = did_web_resolver
+            .resolve_did_web_document(&did_web)
+            .await
+            .expect("pass");
+        tracing::debug!(
+            "did_web_document: {}",
+            serde_json::to_string_pretty(&did_web_document).expect("pass")
+        );
+        assert_eq!(did_web_document.id, did_web);
+        assert_eq!(did_web_document.also_known_as_v, vec![did.to_string()]);
+        assert_eq!(
+            did_web_document,
+            did_webplus_core::DIDWebDocument::from(&did_document)
+        );
+
+        // Keys resolve via did:web for exactly the verification relationships they're listed under.
+        let verifier_resolver_map = verifier_resolver::VerifierResolverMap::new()
+            .with(
+                "did:webplus:",
+                Box::new(create_did_resolver_full(None).await),
+            )
+            .with("did:web:", Box::new(did_web_resolver.clone()));
+        for verification_method in did_document
+            .public_key_material
+            .verification_method_v
+            .iter()
+        {
+            let key_id_fragment = verification_method.id.fragment();
+            let did_web_key_id = format!("{}#{}", did_web, key_id_fragment);
+            let did_web_verifier_b = verifier_resolver_map
+                .resolve(&did_web_key_id)
+                .await
+                .expect("pass");
+            let did_webplus_key_id = format!(
+                "{}?selfHash={}&versionId={}#{}",
+                did, did_document.self_hash, did_document.version_id, key_id_fragment
+            );
+            let did_webplus_verifier_b = verifier_resolver_map
+                .resolve(&did_webplus_key_id)
+                .await
+                .expect("pass");
+            assert_eq!(
+                did_web_verifier_b.key_type(),
+                did_webplus_verifier_b.key_type()
+            );
+            assert_eq!(
+                AsRef::<[u8]>::as_ref(&did_web_verifier_b.get_raw_bytes()),
+                AsRef::<[u8]>::as_ref(&did_webplus_verifier_b.get_raw_bytes())
+            );
+
+            let key_purpose_flags = did_document
+                .public_key_material
+                .key_purpose_flags_for_key_id_fragment(key_id_fragment);
+            for verification_relationship in [
+                VerificationRelationship::Authentication,
+                VerificationRelationship::AssertionMethod,
+                VerificationRelationship::KeyAgreement,
+                VerificationRelationship::CapabilityInvocation,
+                VerificationRelationship::CapabilityDelegation,
+            ] {
+                let result = did_web_resolver
+                    .resolve_for_purpose(&did_web_key_id, verification_relationship)
+                    .await;
+                let key_purpose = did_webplus_core::KeyPurpose::from(verification_relationship);
+                assert_eq!(result.is_ok(), key_purpose_flags.contains(key_purpose));
+            }
+        }
+    }
+
+    // A did:web DID with no corresponding DID on the VDR doesn't resolve.
+    let nonexistent_did_web = format!(
+        "{}{}",
+        did_web.strip_suffix(did.root_self_hash().as_str()).unwrap(),
+        "EVFp-xj7y-ZhG5YQXhO_WS_E-4yVX69UeTefKAC8G_YQ"
+    );
+    did_web_resolver
+        .resolve_did_web_document(&nonexistent_did_web)
+        .await
+        .expect_err("pass");
+
+    services.abort();
+}
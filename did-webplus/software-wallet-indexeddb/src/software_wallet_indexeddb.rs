@@ -443,9 +443,9 @@ impl SoftwareWalletIndexedDB {
                             if priv_key_record.deleted_at_o.is_some() {
                                 continue;
                             }
-                            priv_key_record.priv_key_bytes_o.clone().ok_or_else(|| {
+                            priv_key_record.signer_bytes_o.clone().ok_or_else(|| {
                                 Error::from(anyhow::anyhow!(
-                                    "priv key has no priv_key_bytes_o (deleted or corrupted)"
+                                    "priv key has no signer_bytes_o (deleted or corrupted)"
                                 ))
                             })?;
 
@@ -627,24 +627,31 @@ impl SoftwareWalletIndexedDB {
 
         Ok(())
     }
-    async fn fetch_did_internal(
+    /// Returns a DIDResolverFull that uses this wallet's DID doc storage (and VDG, if specified).
+    fn did_resolver_full(
         &self,
-        did: &DIDStr,
         http_options_o: Option<&did_webplus_core::HTTPOptions>,
-    ) -> did_webplus_wallet::Result<did_webplus_core::DIDDocument> {
-        // Note the version of the known latest DID document.  This will only differ from the actual latest
-        // version if more than one wallet controls the DID.
-
-        // Retrieve any unfetched updates to the DID.
+    ) -> did_webplus_resolver::DIDResolverFull {
         let did_doc_storage = self.clone();
         let did_doc_storage_a = std::sync::Arc::new(did_doc_storage);
         let did_doc_store = did_webplus_doc_store::DIDDocStore::new(did_doc_storage_a);
-        let did_resolver_full = did_webplus_resolver::DIDResolverFull::new(
+        did_webplus_resolver::DIDResolverFull::new(
             did_doc_store,
             self.vdg_host_o.as_deref(),
             http_options_o.cloned(),
         )
-        .unwrap();
+        .unwrap()
+    }
+    async fn fetch_did_internal(
+        &self,
+        did: &DIDStr,
+        http_options_o: Option<&did_webplus_core::HTTPOptions>,
+    ) -> did_webplus_wallet::Result<did_webplus_core::DIDDocument> {
+        // Note the version of the known latest DID document.  This will only differ from the actual latest
+        // version if more than one wallet controls the DID.
+
+        // Retrieve any unfetched updates to the DID.
+        let did_resolver_full = self.did_resolver_full(http_options_o);
         use did_webplus_resolver::DIDResolver;
         let (did_document, _did_document_metadata, _did_resolution_metadata) = did_resolver_full
             .resolve_did_document(
@@ -755,6 +762,64 @@ impl SoftwareWalletIndexedDB {
 
         Ok(())
     }
+    /// Derive the raw ECDH shared secret between the priv key of the given locally-controlled keyAgreement
+    /// verification method and the given pub key, and record the given usage of the priv key.
+    async fn derive_shared_secret_using_verification_method(
+        &self,
+        verification_method_record: &VerificationMethodRecord,
+        priv_key_record: &PrivKeyRecord,
+        other_pub_key: &mbx::MBPubKeyStr,
+        usage: PrivKeyUsage,
+    ) -> did_webplus_wallet::Result<zeroize::Zeroizing<Vec<u8>>> {
+        if !priv_key_record.is_key_agreement_key() {
+            return Err(did_webplus_wallet::Error::NoSuitablePrivKeyFound(
+                format!(
+                    "priv key for {} is not a keyAgreement key",
+                    priv_key_record.pub_key
+                )
+                .into(),
+            ));
+        }
+        let signer_bytes = priv_key_record.signer_bytes_o.as_ref().ok_or_else(|| {
+            did_webplus_wallet::Error::NotFound(
+                format!("priv key for {}", priv_key_record.pub_key).into(),
+            )
+        })?;
+        let shared_secret = did_webplus_core::derive_shared_secret(signer_bytes, other_pub_key)?;
+
+        // Record the key exchange.
+        let priv_key_usage_blob = PrivKeyUsageBlob {
+            wallets_rowid: self.ctx.wallets_rowid,
+            priv_key_usage_record: PrivKeyUsageRecord {
+                pub_key: priv_key_record.pub_key.clone(),
+                hashed_pub_key: priv_key_record.hashed_pub_key.clone(),
+                used_at: now_utc_milliseconds(),
+                usage,
+                verification_method_o: Some(
+                    verification_method_record
+                        .did_key_resource_fully_qualified
+                        .clone(),
+                ),
+                key_purpose_o: Some(KeyPurpose::KeyAgreement),
+            },
+        };
+        self.db()
+            .await
+            .map_err(into_wallet_error)?
+            .transaction(&[Self::PRIV_KEY_USAGES_OBJECT_STORE])
+            .rw()
+            .run(async move |transaction| {
+                transaction
+                    .object_store(Self::PRIV_KEY_USAGES_OBJECT_STORE)?
+                    .put(&serde_wasm_bindgen::to_value(&priv_key_usage_blob).unwrap())
+                    .await?;
+                Ok(())
+            })
+            .await
+            .map_err(into_wallet_error)?;
+
+        Ok(shared_secret)
+    }
     fn wallets_rowid_as_f64_jsvalue(wallets_rowid: i64) -> JsValue {
         let wallets_rowid_as_i32 = i32::try_from(wallets_rowid).expect("programmer error: overflow in wallets_rowid; this is so unlikely that it's almost certainly a programmer error");
         let wallets_rowid_as_f64 = f64::from(wallets_rowid_as_i32);
@@ -996,6 +1061,18 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
         );
         // TODO: Factor this code with that in SoftwareWallet::create_did.
 
+        // TODO: Sign DID create and update requests (see did_webplus_core::VDR_AUTHORIZATION_JWS_HEADER) so that
+        // this wallet can be used with VDRs that require signed authorization.
+        if let Some(vdr_authz_key_id) = create_did_parameters.vdr_authz_key_id_o {
+            return Err(did_webplus_wallet::Error::Malformed(
+                format!(
+                    "SoftwareWalletIndexedDB does not yet support VDR authorization keys (got {})",
+                    vdr_authz_key_id
+                )
+                .into(),
+            ));
+        }
+
         // Have to created owned versions of each of these because of the async closures below.
         let vdr_did_create_endpoint = create_did_parameters.vdr_did_create_endpoint.to_owned();
         let mb_hash_function_for_did = create_did_parameters.mb_hash_function_for_did.to_owned();
@@ -1044,26 +1121,8 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
 
         // Generate an appropriate set of keys.  Record the creation timestamp.
         let now_utc = now_utc_milliseconds();
-        // TODO: This should use SubtleCrypto, not ed25519-dalek.
-        // TODO: Somehow iterate?
-        use signature_dyn::GenerateRandom;
-        let priv_key_m = enum_map::enum_map! {
-            KeyPurpose::Authentication => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::AssertionMethod => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::KeyAgreement => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::CapabilityInvocation => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::CapabilityDelegation => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::UpdateDIDDocument => ed25519_dalek::SigningKey::generate_random(),
-        };
-        // TODO: Somehow iterate?
-        let pub_key_m = enum_map::enum_map! {
-            KeyPurpose::Authentication => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::Authentication].verifying_key()),
-            KeyPurpose::AssertionMethod => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::AssertionMethod].verifying_key()),
-            KeyPurpose::KeyAgreement => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::KeyAgreement].verifying_key()),
-            KeyPurpose::CapabilityInvocation => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::CapabilityInvocation].verifying_key()),
-            KeyPurpose::CapabilityDelegation => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::CapabilityDelegation].verifying_key()),
-            KeyPurpose::UpdateDIDDocument => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::UpdateDIDDocument].verifying_key()),
-        };
+        // TODO: This should use SubtleCrypto.
+        let priv_key_vm = did_webplus_core::generate_priv_keys()?;
 
         // Define the update rules.  For now, just a single key.
         let update_rules = if let Some(mb_hash_function_for_update_key) =
@@ -1071,11 +1130,11 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
         {
             RootLevelUpdateRules::from(HashedUpdateKey::from_pub_key(
                 mb_hash_function_for_update_key,
-                &pub_key_m[KeyPurpose::UpdateDIDDocument],
+                &priv_key_vm[KeyPurpose::UpdateDIDDocument][0].0,
             ))
         } else {
             RootLevelUpdateRules::from(UpdateKey {
-                pub_key: pub_key_m[KeyPurpose::UpdateDIDDocument].clone(),
+                pub_key: priv_key_vm[KeyPurpose::UpdateDIDDocument][0].0.clone(),
             })
         };
 
@@ -1086,13 +1145,7 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
             did_path_o.as_deref(),
             update_rules,
             now_utc,
-            did_webplus_core::PublicKeySet {
-                authentication_v: vec![&pub_key_m[KeyPurpose::Authentication]],
-                assertion_method_v: vec![&pub_key_m[KeyPurpose::AssertionMethod]],
-                key_agreement_v: vec![&pub_key_m[KeyPurpose::KeyAgreement]],
-                capability_invocation_v: vec![&pub_key_m[KeyPurpose::CapabilityInvocation]],
-                capability_delegation_v: vec![&pub_key_m[KeyPurpose::CapabilityDelegation]],
-            },
+            did_webplus_core::public_key_set(&priv_key_vm),
             &mb_hash_function_for_did,
         )
         .expect("programmer error");
@@ -1157,10 +1210,16 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
                 tracing::trace!("added did_document_blob; did_document_blob_key: {:?}", did_document_blob_key);
 
                 // Store the priv keys.
-                let mut priv_key_blob_key_v = Vec::with_capacity(KeyPurpose::VARIANTS.len());
-                for key_purpose in KeyPurpose::VARIANTS {
+                let mut priv_key_blob_key_v = Vec::new();
+                for (key_purpose, pub_key, signer_bytes) in priv_key_vm
+                    .into_iter()
+                    .flat_map(|(key_purpose, priv_key_v)| {
+                        priv_key_v
+                            .into_iter()
+                            .map(move |(pub_key, signer_bytes)| (key_purpose, pub_key, signer_bytes))
+                    })
+                {
                     tracing::trace!("adding priv key for key_purpose: {:?}", key_purpose);
-                    let pub_key = pub_key_m[key_purpose].clone();
                     let hashed_pub_key_string = if let Some(mb_hash_function_for_update_key) =
                         mb_hash_function_for_update_key_o.as_ref()
                     {
@@ -1176,7 +1235,6 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
                         None
                     };
                     let comment_o = Some("generated during DID create".to_string());
-                    use signature_dyn::ExtractableSignerT;
                     let priv_key_blob = PrivKeyBlob {
                         wallets_rowid: ctx_clone.wallets_rowid,
                         priv_key_record: PrivKeyRecord {
@@ -1189,12 +1247,14 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
                             max_usage_count_o,
                             usage_count: 0,
                             deleted_at_o: None,
-                            priv_key_bytes_o: Some(
-                                priv_key_m[key_purpose].extract_signer_bytes().map_err(|e| indexed_db::Error::from(Error::from(anyhow::anyhow!("error extracting signer bytes; error was: {}", e))))?.to_owned(),
-                            ),
+                            signer_bytes_o: Some(signer_bytes),
                             comment_o,
                         },
                     };
+                    priv_key_blob
+                        .priv_key_record
+                        .validate_key_agreement_tag()
+                        .map_err(|e| Error::from(anyhow::anyhow!("{}", e)))?;
                     let priv_key_blob_key = transaction
                         .object_store(Self::PRIV_KEYS_PROVISIONAL_OBJECT_STORE)?
                         .put(&serde_wasm_bindgen::to_value(&priv_key_blob).unwrap())
@@ -1354,25 +1414,7 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
 
         // Rotate the appropriate set of keys.  Record the creation timestamp.
         let now_utc = time::OffsetDateTime::now_utc();
-        // TODO: Somehow iterate?
-        use signature_dyn::GenerateRandom;
-        let priv_key_m = enum_map::enum_map! {
-            KeyPurpose::Authentication => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::AssertionMethod => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::KeyAgreement => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::CapabilityInvocation => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::CapabilityDelegation => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::UpdateDIDDocument => ed25519_dalek::SigningKey::generate_random(),
-        };
-        // TODO: Somehow iterate?
-        let pub_key_m = enum_map::enum_map! {
-            KeyPurpose::Authentication => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::Authentication].verifying_key()),
-            KeyPurpose::AssertionMethod => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::AssertionMethod].verifying_key()),
-            KeyPurpose::KeyAgreement => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::KeyAgreement].verifying_key()),
-            KeyPurpose::CapabilityInvocation => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::CapabilityInvocation].verifying_key()),
-            KeyPurpose::CapabilityDelegation => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::CapabilityDelegation].verifying_key()),
-            KeyPurpose::UpdateDIDDocument => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::UpdateDIDDocument].verifying_key()),
-        };
+        let priv_key_vm = did_webplus_core::generate_priv_keys()?;
 
         // First, add the DID document, priv keys, and priv key usages to the database, but under
         // the "provisional" versions of those object stores.  Then the HTTP PUT to the VDR will
@@ -1524,10 +1566,10 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
                 // Select the appropriate key to sign the update.
                 let priv_key_record_for_update = &matching_update_key_v[0];
                 let priv_key_for_update = priv_key_record_for_update
-                    .priv_key_bytes_o
+                    .signer_bytes_o
                     .as_ref()
                     .expect(
-                        "programmer error: priv_key_bytes_o was expected to be Some(_); i.e. not deleted",
+                        "programmer error: signer_bytes_o was expected to be Some(_); i.e. not deleted",
                     );
 
                 // Define the update rules.  Temporary limitation: Just specify a single key.
@@ -1537,11 +1579,11 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
                 {
                     RootLevelUpdateRules::from(HashedUpdateKey::from_pub_key(
                         mb_hash_function_for_update_key,
-                        &pub_key_m[KeyPurpose::UpdateDIDDocument],
+                        &priv_key_vm[KeyPurpose::UpdateDIDDocument][0].0,
                     ))
                 } else {
                     RootLevelUpdateRules::from(UpdateKey {
-                        pub_key: pub_key_m[KeyPurpose::UpdateDIDDocument].clone(),
+                        pub_key: priv_key_vm[KeyPurpose::UpdateDIDDocument][0].0.clone(),
                     })
                 };
 
@@ -1566,13 +1608,7 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
                     &latest_did_document,
                     update_rules,
                     now_utc,
-                    did_webplus_core::PublicKeySet {
-                        authentication_v: vec![&pub_key_m[KeyPurpose::Authentication]],
-                        assertion_method_v: vec![&pub_key_m[KeyPurpose::AssertionMethod]],
-                        key_agreement_v: vec![&pub_key_m[KeyPurpose::KeyAgreement]],
-                        capability_invocation_v: vec![&pub_key_m[KeyPurpose::CapabilityInvocation]],
-                        capability_delegation_v: vec![&pub_key_m[KeyPurpose::CapabilityDelegation]],
-                    },
+                    did_webplus_core::public_key_set(&priv_key_vm),
                     mb_hash_function_for_self_hash,
                 )
                 .expect("programmer error");
@@ -1649,10 +1685,16 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
                 tracing::trace!("added did_document_blob; did_document_blob_key: {:?}", did_document_blob_key);
 
                 // Store the priv keys.
-                let mut priv_key_blob_key_v = Vec::with_capacity(KeyPurpose::VARIANTS.len());
-                for key_purpose in KeyPurpose::VARIANTS {
+                let mut priv_key_blob_key_v = Vec::new();
+                for (key_purpose, pub_key, signer_bytes) in priv_key_vm
+                    .into_iter()
+                    .flat_map(|(key_purpose, priv_key_v)| {
+                        priv_key_v
+                            .into_iter()
+                            .map(move |(pub_key, signer_bytes)| (key_purpose, pub_key, signer_bytes))
+                    })
+                {
                     tracing::trace!("adding priv key for key_purpose: {:?}", key_purpose);
-                    let pub_key = pub_key_m[key_purpose].clone();
                     let hashed_pub_key_string = if let Some(mb_hash_function_for_update_key) =
                         mb_hash_function_for_update_key_o.as_ref()
                     {
@@ -1671,7 +1713,6 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
                         "generated during DID update versionId {} -> {}",
                         latest_did_document.version_id, updated_did_document.version_id
                     ));
-                    use signature_dyn::ExtractableSignerT;
                     let priv_key_blob = PrivKeyBlob {
                         wallets_rowid: ctx_clone.wallets_rowid,
                         priv_key_record: PrivKeyRecord {
@@ -1684,12 +1725,14 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
                             max_usage_count_o,
                             usage_count: 0,
                             deleted_at_o: None,
-                            priv_key_bytes_o: Some(
-                                priv_key_m[key_purpose].extract_signer_bytes().map_err(|e| indexed_db::Error::from(Error::from(anyhow::anyhow!("error extracting signer bytes; error was: {}", e))))?.to_owned(),
-                            ),
+                            signer_bytes_o: Some(signer_bytes),
                             comment_o,
                         },
                     };
+                    priv_key_blob
+                        .priv_key_record
+                        .validate_key_agreement_tag()
+                        .map_err(|e| Error::from(anyhow::anyhow!("{}", e)))?;
                     let priv_key_blob_key = transaction
                         .object_store(Self::PRIV_KEYS_PROVISIONAL_OBJECT_STORE)?
                         .put(&serde_wasm_bindgen::to_value(&priv_key_blob).unwrap())
@@ -1734,7 +1777,7 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
                 {
                     let mut soft_deleted_record = priv_key_blob.priv_key_record.clone();
                     soft_deleted_record.deleted_at_o = Some(now_utc);
-                    soft_deleted_record.priv_key_bytes_o = None;
+                    soft_deleted_record.signer_bytes_o = None;
                     let soft_deleted_blob = PrivKeyBlob {
                         priv_key_record: soft_deleted_record,
                         ..priv_key_blob.clone()
@@ -1763,7 +1806,7 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
                 if let Some(update_key_primary_key) = update_key_primary_keys.into_iter().next() {
                     let mut soft_deleted_record = (*priv_key_record_for_update).clone();
                     soft_deleted_record.deleted_at_o = Some(now_utc);
-                    soft_deleted_record.priv_key_bytes_o = None;
+                    soft_deleted_record.signer_bytes_o = None;
                     let soft_deleted_blob = PrivKeyBlob {
                         wallets_rowid: ctx_clone.wallets_rowid,
                         priv_key_record: soft_deleted_record,
@@ -1940,6 +1983,155 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
         );
         Ok(controlled_did)
     }
+    async fn derive_shared_secret(
+        &self,
+        key_id: &did_webplus_core::DIDKeyResourceFullyQualifiedStr,
+        other_pub_key: &mbx::MBPubKeyStr,
+    ) -> did_webplus_wallet::Result<zeroize::Zeroizing<Vec<u8>>> {
+        tracing::debug!(
+            ?key_id,
+            ?other_pub_key,
+            "SoftwareWalletIndexedDB::derive_shared_secret"
+        );
+
+        let (verification_method_record, priv_key_record) = self
+            .get_locally_controlled_verification_methods_internal(
+                &LocallyControlledVerificationMethodFilter {
+                    did_o: Some(key_id.did().to_owned()),
+                    version_id_o: Some(key_id.query_version_id()),
+                    key_purpose_o: Some(KeyPurpose::KeyAgreement),
+                    key_id_o: Some(key_id.fragment().to_string()),
+                    result_limit_o: None,
+                },
+            )
+            .await
+            .map_err(into_wallet_error)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                did_webplus_wallet::Error::NoLocallyControlledVerificationMethodFound(
+                    format!("keyAgreement key {}", key_id).into(),
+                )
+            })?;
+        self.derive_shared_secret_using_verification_method(
+            &verification_method_record,
+            &priv_key_record,
+            other_pub_key,
+            PrivKeyUsage::KeyExchange {
+                other_o: Some(other_pub_key.to_owned()),
+            },
+        )
+        .await
+    }
+    async fn derive_shared_secret_with_did(
+        &self,
+        did_o: Option<&DIDStr>,
+        other_key_id: &did_webplus_core::DIDKeyResourceFullyQualifiedStr,
+        http_options_o: Option<&did_webplus_core::HTTPOptions>,
+    ) -> did_webplus_wallet::Result<(
+        did_webplus_core::DIDKeyResourceFullyQualified,
+        zeroize::Zeroizing<Vec<u8>>,
+    )> {
+        tracing::debug!(
+            ?did_o,
+            ?other_key_id,
+            "SoftwareWalletIndexedDB::derive_shared_secret_with_did"
+        );
+        // TODO: Factor this code with that in SoftwareWallet::derive_shared_secret_with_did.
+
+        use did_webplus_wallet::Wallet;
+        let controlled_did = self.get_controlled_did(did_o).await?;
+
+        // Resolve the other key and check that it's actually a keyAgreement key.
+        let (other_did_document, _did_document_metadata, _did_resolution_metadata) = {
+            use did_webplus_resolver::DIDResolver;
+            self.did_resolver_full(http_options_o)
+                .resolve_did_document(
+                    other_key_id.without_fragment().as_str(),
+                    did_webplus_core::DIDResolutionOptions::no_metadata(false),
+                )
+                .await
+                .map_err(|e| {
+                    did_webplus_wallet::Error::DIDFetchError(
+                        format!("DID: {}, error was: {}", other_key_id.without_fragment(), e)
+                            .into(),
+                    )
+                })?
+        };
+        if !other_did_document
+            .public_key_material
+            .key_purpose_flags_for_key_id_fragment(other_key_id.fragment())
+            .contains(KeyPurpose::KeyAgreement)
+        {
+            return Err(did_webplus_wallet::Error::Malformed(
+                format!(
+                    "{} is not listed under keyAgreement in its DID document",
+                    other_key_id
+                )
+                .into(),
+            ));
+        }
+        let other_verification_method = other_did_document
+            .public_key_material
+            .verification_method_for_key_id_fragment(other_key_id.fragment())?;
+        let other_pub_key = mbx::MBPubKey::try_from(&other_verification_method.public_key_jwk)
+            .map_err(|e| did_webplus_wallet::Error::Malformed(e.to_string().into()))?;
+        let key_agreement_key_type =
+            did_webplus_core::KeyAgreementKeyType::of_pub_key(&other_pub_key).ok_or_else(|| {
+                did_webplus_wallet::Error::NoSuitablePrivKeyFound(
+                    format!(
+                        "keyAgreement key {} has an unsupported key type for key exchange",
+                        other_key_id
+                    )
+                    .into(),
+                )
+            })?;
+
+        // Select the locally-controlled keyAgreement key having the same key type as the other key.
+        let (verification_method_record, priv_key_record) = self
+            .get_locally_controlled_verification_methods_internal(
+                &LocallyControlledVerificationMethodFilter {
+                    did_o: Some(controlled_did.did().to_owned()),
+                    version_id_o: None,
+                    key_purpose_o: Some(KeyPurpose::KeyAgreement),
+                    key_id_o: None,
+                    result_limit_o: None,
+                },
+            )
+            .await
+            .map_err(into_wallet_error)?
+            .into_iter()
+            .find(|(verification_method_record, _priv_key_record)| {
+                did_webplus_core::KeyAgreementKeyType::of_pub_key(
+                    &verification_method_record.pub_key,
+                ) == Some(key_agreement_key_type)
+            })
+            .ok_or_else(|| {
+                did_webplus_wallet::Error::NoSuitablePrivKeyFound(
+                    format!(
+                        "no locally-controlled {:?} keyAgreement key for DID {}",
+                        key_agreement_key_type,
+                        controlled_did.did()
+                    )
+                    .into(),
+                )
+            })?;
+        let shared_secret = self
+            .derive_shared_secret_using_verification_method(
+                &verification_method_record,
+                &priv_key_record,
+                &other_pub_key,
+                PrivKeyUsage::KeyExchangeWithDID {
+                    other_o: Some(other_key_id.to_owned()),
+                },
+            )
+            .await?;
+
+        Ok((
+            verification_method_record.did_key_resource_fully_qualified,
+            shared_secret,
+        ))
+    }
     async fn deactivate_did(
         &self,
         deactivate_did_parameters: did_webplus_wallet::DeactivateDIDParameters<'_>,
@@ -2119,10 +2311,10 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
 
                 let priv_key_record_for_update = &matching_update_key_v[0];
                 let priv_key_for_update = priv_key_record_for_update
-                    .priv_key_bytes_o
+                    .signer_bytes_o
                     .as_ref()
                     .expect(
-                        "programmer error: priv_key_bytes_o was expected to be Some(_); i.e. not deleted",
+                        "programmer error: signer_bytes_o was expected to be Some(_); i.e. not deleted",
                     );
 
                 let update_rules = RootLevelUpdateRules::from(UpdatesDisallowed {});
@@ -2274,7 +2466,7 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
                 {
                     let mut soft_deleted_record = priv_key_blob.priv_key_record.clone();
                     soft_deleted_record.deleted_at_o = Some(now_utc);
-                    soft_deleted_record.priv_key_bytes_o = None;
+                    soft_deleted_record.signer_bytes_o = None;
                     let soft_deleted_blob = PrivKeyBlob {
                         priv_key_record: soft_deleted_record,
                         ..priv_key_blob.clone()
@@ -2302,7 +2494,7 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
                 if let Some(update_key_primary_key) = update_key_primary_keys.into_iter().next() {
                     let mut soft_deleted_record = (*priv_key_record_for_update).clone();
                     soft_deleted_record.deleted_at_o = Some(now_utc);
-                    soft_deleted_record.priv_key_bytes_o = None;
+                    soft_deleted_record.signer_bytes_o = None;
                     let soft_deleted_blob = PrivKeyBlob {
                         wallets_rowid: ctx_clone.wallets_rowid,
                         priv_key_record: soft_deleted_record,
@@ -2463,16 +2655,13 @@ impl did_webplus_wallet::Wallet for SoftwareWalletIndexedDB {
             .await
             .map_err(into_wallet_error)?;
 
-        Ok(results
+        results
             .into_iter()
             .map(|(verification_method_record, priv_key_record)| {
-                let signer_bytes = priv_key_record
-                    .priv_key_bytes_o
-                    .expect("programmer error: priv_key_bytes_o was verified Some(_) in the loop");
-                let async_signer_b: Box<dyn signature_dyn::AsyncSignerT + Send + Sync> =
-                    Box::new(signer_bytes);
-                (verification_method_record, async_signer_b)
+                // keyAgreement priv keys produce a signer that refuses to sign.
+                let async_signer_b = priv_key_record.into_async_signer()?;
+                Ok((verification_method_record, async_signer_b))
             })
-            .collect())
+            .collect()
     }
 }
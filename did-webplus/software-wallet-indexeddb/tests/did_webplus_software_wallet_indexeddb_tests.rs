@@ -55,6 +55,7 @@ async fn test_software_wallet_indexeddb_basic() {
                 vdr_did_create_endpoint: "https://vdr.did-webplus-wasm.test:8085",
                 mb_hash_function_for_did: &mb_hash_function,
                 mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                vdr_authz_key_id_o: None,
             },
             Some(&http_options),
         )
@@ -79,7 +80,10 @@ async fn test_software_wallet_indexeddb_basic() {
         !locally_controlled_verification_methods_after_create_v.is_empty(),
         "expected at least one locally controlled verification method after create_did"
     );
-    let expected_count = KeyPurpose::VERIFICATION_METHOD_VARIANTS.len();
+    // One verification method per verification method KeyPurpose, except that there's a keyAgreement key
+    // for each KeyAgreementKeyType (X25519 and P-256).
+    let expected_count = KeyPurpose::VERIFICATION_METHOD_VARIANTS.len() - 1
+        + did_webplus_core::KeyAgreementKeyType::VARIANTS.len();
     assert_eq!(
         locally_controlled_verification_methods_after_create_v.len(),
         expected_count,
@@ -156,6 +160,50 @@ async fn test_software_wallet_indexeddb_basic() {
         "expected new verification methods after update_did to have different pub_keys than the retired set"
     );
 
+    // Create another DID and check that key exchange between the two DIDs produces the same shared secret
+    // from both sides, for each keyAgreement key type.
+    let other_controlled_did = software_wallet_indexeddb
+        .create_did(
+            did_webplus_wallet::CreateDIDParameters {
+                vdr_did_create_endpoint: "https://vdr.did-webplus-wasm.test:8085",
+                mb_hash_function_for_did: &mb_hash_function,
+                mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                vdr_authz_key_id_o: None,
+            },
+            Some(&http_options),
+        )
+        .await
+        .expect("pass");
+    let other_did = other_controlled_did.did();
+    let key_agreement_query_result_v = software_wallet_indexeddb
+        .get_locally_controlled_verification_methods(&LocallyControlledVerificationMethodFilter {
+            did_o: Some(did.to_owned()),
+            version_id_o: None,
+            key_purpose_o: Some(KeyPurpose::KeyAgreement),
+            key_id_o: None,
+            result_limit_o: None,
+        })
+        .await
+        .expect("pass");
+    assert_eq!(
+        key_agreement_query_result_v.len(),
+        did_webplus_core::KeyAgreementKeyType::VARIANTS.len()
+    );
+    for (verification_method_record, _signer_b) in key_agreement_query_result_v {
+        let key_id = &verification_method_record.did_key_resource_fully_qualified;
+        let (other_key_id, other_shared_secret) = software_wallet_indexeddb
+            .derive_shared_secret_with_did(Some(&other_did), key_id, Some(&http_options))
+            .await
+            .expect("pass");
+        let (derived_key_id, shared_secret) = software_wallet_indexeddb
+            .derive_shared_secret_with_did(Some(&did), &other_key_id, Some(&http_options))
+            .await
+            .expect("pass");
+        assert_eq!(&derived_key_id, key_id);
+        assert_eq!(shared_secret, other_shared_secret);
+        assert_eq!(shared_secret.len(), 32);
+    }
+
     software_wallet_indexeddb
         .deactivate_did(
             did_webplus_wallet::DeactivateDIDParameters {
@@ -1,7 +1,9 @@
 use crate::REQWEST_CLIENT;
 use did_webplus_core::{
-    DIDDocument, DIDFullyQualified, DIDStr, HashedUpdateKey, KeyPurpose, KeyPurposeFlags,
-    RootLevelUpdateRules, UpdateKey, UpdatesDisallowed, now_utc_milliseconds,
+    DIDDocument, DIDFullyQualified, DIDKeyResourceFullyQualified, DIDKeyResourceFullyQualifiedStr,
+    DIDStr, HashedUpdateKey, KeyAgreementKeyType, KeyPurpose, KeyPurposeFlags,
+    RootLevelUpdateRules, UpdateKey, UpdatesDisallowed, derive_shared_secret, generate_priv_keys,
+    now_utc_milliseconds, public_key_set,
 };
 use did_webplus_wallet::{
     CreateDIDParameters, DeactivateDIDParameters, Error, Result, UpdateDIDParameters, Wallet,
@@ -22,6 +24,11 @@ pub struct SoftwareWallet {
     /// in the scope of agreement defined by the VDG.  Without using a VDG, a DIDResolverFull has a
     /// scope of agreement that only contains itself.
     vdg_host_o: Option<String>,
+    /// Optionally specifies a static-hosting directory tree (see did_webplus_doc_store::export_static_tree)
+    /// that DID creates and updates are written into, instead of being sent to a VDR.  Serving this directory
+    /// via static file hosting (e.g. S3 or nginx) at the host specified in each DID then makes the DIDs
+    /// resolvable without running a VDR.
+    static_hosting_dir_o: Option<std::path::PathBuf>,
 }
 
 impl SoftwareWallet {
@@ -47,6 +54,7 @@ impl SoftwareWallet {
             ctx: wallet_storage_ctx,
             wallet_storage_a,
             vdg_host_o,
+            static_hosting_dir_o: None,
         })
     }
     pub async fn open(
@@ -74,36 +82,178 @@ impl SoftwareWallet {
             ctx: wallet_storage_ctx,
             wallet_storage_a,
             vdg_host_o,
+            static_hosting_dir_o: None,
         })
     }
-    async fn fetch_did_internal(
+    /// Returns the WalletStorageCtx that identifies this wallet within its WalletStorage.
+    pub fn wallet_storage_ctx(&self) -> &WalletStorageCtx {
+        &self.ctx
+    }
+    /// Builder-style method to have this wallet write DID creates and updates into the given static-hosting
+    /// directory tree instead of sending them to a VDR.  In this mode, the vdr_did_create_endpoint parameter
+    /// of create_did only determines the DID's host and path (i.e. the URL that the directory tree will be
+    /// served at), and the latest DID documents are read from the directory tree instead of being fetched.
+    pub fn with_static_hosting_dir(mut self, static_hosting_dir: std::path::PathBuf) -> Self {
+        self.static_hosting_dir_o = Some(static_hosting_dir);
+        self
+    }
+    /// Returns a DIDResolverFull that uses this wallet's DID doc storage (and VDG, if specified).
+    fn did_resolver_full(
         &self,
-        did: &DIDStr,
         http_options_o: Option<&did_webplus_core::HTTPOptions>,
-    ) -> Result<did_webplus_core::DIDDocument> {
-        // Note the version of the known latest DID document.  This will only differ from the actual latest
-        // version if more than one wallet controls the DID.
-
-        // Retrieve any unfetched updates to the DID.
-        let did_resolver_full = did_webplus_resolver::DIDResolverFull::new(
+    ) -> did_webplus_resolver::DIDResolverFull {
+        did_webplus_resolver::DIDResolverFull::new(
             did_webplus_doc_store::DIDDocStore::new(
                 self.wallet_storage_a.clone().as_did_doc_storage_a(),
             ),
             self.vdg_host_o.as_deref(),
             http_options_o.cloned(),
         )
-        .unwrap();
+        .unwrap()
+    }
+    async fn fetch_did_internal(
+        &self,
+        did: &DIDStr,
+        http_options_o: Option<&did_webplus_core::HTTPOptions>,
+    ) -> Result<did_webplus_core::DIDDocument> {
+        // Note the version of the known latest DID document.  This will only differ from the actual latest
+        // version if more than one wallet controls the DID.
+
+        // Retrieve any unfetched updates to the DID.  In static-hosting mode, the updates come from the
+        // static-hosting directory tree, and resolution is otherwise local.
+        let local_resolution_only =
+            if let Some(static_hosting_dir) = self.static_hosting_dir_o.as_deref() {
+                self.ingest_did_updates_from_static_tree(static_hosting_dir, did)
+                    .await?;
+                true
+            } else {
+                false
+            };
+        let did_resolver_full = self.did_resolver_full(http_options_o);
         use did_webplus_resolver::DIDResolver;
         let (did_document, _did_document_metadata, _did_resolution_metadata) = did_resolver_full
             .resolve_did_document(
                 did.as_str(),
-                did_webplus_core::DIDResolutionOptions::no_metadata(false),
+                did_webplus_core::DIDResolutionOptions::no_metadata(local_resolution_only),
             )
             .await
             .map_err(|e| Error::DIDFetchError(format!("DID: {}, error was: {}", did, e).into()))?;
 
         Ok(did_document)
     }
+    /// Validate and store the DID documents in the DID's did-documents.jsonl file in the given static-hosting
+    /// directory tree that this wallet doesn't already have, e.g. those written by another wallet that
+    /// controls the DID.
+    async fn ingest_did_updates_from_static_tree(
+        &self,
+        static_hosting_dir: &std::path::Path,
+        did: &DIDStr,
+    ) -> Result<()> {
+        let did_documents_jsonl =
+            did_webplus_doc_store::read_static_did_documents_jsonl(static_hosting_dir, did)?
+                .ok_or_else(|| {
+                    Error::DIDFetchError(
+                        format!(
+                            "{} has no did-documents.jsonl in static-hosting directory {}",
+                            did,
+                            static_hosting_dir.display()
+                        )
+                        .into(),
+                    )
+                })?;
+        let did_doc_store = did_webplus_doc_store::DIDDocStore::new(
+            self.wallet_storage_a.clone().as_did_doc_storage_a(),
+        );
+        let mut prev_did_document_o = did_doc_store
+            .get_latest_known_did_doc_record(None, did)
+            .await?
+            .map(|did_doc_record| {
+                did_webplus_doc_store::parse_did_document(&did_doc_record.did_document_jcs)
+            })
+            .transpose()?;
+        for did_document_jcs in did_documents_jsonl.lines() {
+            let did_document = did_webplus_doc_store::parse_did_document(did_document_jcs)?;
+            if let Some(prev_did_document) = prev_did_document_o.as_ref() {
+                if did_document.version_id < prev_did_document.version_id {
+                    continue;
+                }
+                if did_document.version_id == prev_did_document.version_id {
+                    if did_document.self_hash != prev_did_document.self_hash {
+                        return Err(Error::DIDFetchError(format!("{} in static-hosting directory {} has DID document versionId {} with selfHash {}, which conflicts with the known DID document having selfHash {}", did, static_hosting_dir.display(), did_document.version_id, did_document.self_hash, prev_did_document.self_hash).into()));
+                    }
+                    continue;
+                }
+            }
+            did_doc_store
+                .validate_and_add_did_doc(
+                    None,
+                    &did_document,
+                    prev_did_document_o.as_ref(),
+                    did_document_jcs,
+                )
+                .await?;
+            prev_did_document_o = Some(did_document);
+        }
+        Ok(())
+    }
+    /// Derive the raw ECDH shared secret between the priv key of the given locally-controlled keyAgreement
+    /// verification method and the given pub key, and record the given usage of the priv key.
+    async fn derive_shared_secret_using_verification_method(
+        &self,
+        transaction: &mut dyn storage_traits::TransactionDynT,
+        verification_method_record: &VerificationMethodRecord,
+        other_pub_key: &mbx::MBPubKeyStr,
+        usage: PrivKeyUsage,
+    ) -> Result<zeroize::Zeroizing<Vec<u8>>> {
+        let priv_key_record = self
+            .wallet_storage_a
+            .get_priv_key(
+                Some(&mut *transaction),
+                &self.ctx,
+                &verification_method_record.pub_key,
+            )
+            .await?
+            .ok_or_else(|| {
+                Error::NotFound(
+                    format!("priv key for {}", verification_method_record.pub_key).into(),
+                )
+            })?;
+        if !priv_key_record.is_key_agreement_key() {
+            return Err(Error::NoSuitablePrivKeyFound(
+                format!(
+                    "priv key for {} is not a keyAgreement key",
+                    priv_key_record.pub_key
+                )
+                .into(),
+            ));
+        }
+        let signer_bytes = priv_key_record.signer_bytes_o.as_ref().ok_or_else(|| {
+            Error::NotFound(format!("priv key for {}", priv_key_record.pub_key).into())
+        })?;
+        let shared_secret = derive_shared_secret(signer_bytes, other_pub_key)?;
+
+        // Record the key exchange.
+        self.wallet_storage_a
+            .add_priv_key_usage(
+                Some(transaction),
+                &self.ctx,
+                &PrivKeyUsageRecord {
+                    pub_key: priv_key_record.pub_key.clone(),
+                    hashed_pub_key: priv_key_record.hashed_pub_key.clone(),
+                    used_at: now_utc_milliseconds(),
+                    usage,
+                    verification_method_o: Some(
+                        verification_method_record
+                            .did_key_resource_fully_qualified
+                            .clone(),
+                    ),
+                    key_purpose_o: Some(KeyPurpose::KeyAgreement),
+                },
+            )
+            .await?;
+
+        Ok(shared_secret)
+    }
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
@@ -157,25 +307,7 @@ impl Wallet for SoftwareWallet {
 
         // Generate an appropriate set of keys.  Record the creation timestamp.
         let now_utc = now_utc_milliseconds();
-        // TODO: Somehow iterate?
-        use signature_dyn::GenerateRandom;
-        let priv_key_m = enum_map::enum_map! {
-            KeyPurpose::Authentication => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::AssertionMethod => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::KeyAgreement => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::CapabilityInvocation => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::CapabilityDelegation => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::UpdateDIDDocument => ed25519_dalek::SigningKey::generate_random(),
-        };
-        // TODO: Somehow iterate?
-        let pub_key_m = enum_map::enum_map! {
-            KeyPurpose::Authentication => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::Authentication].verifying_key()),
-            KeyPurpose::AssertionMethod => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::AssertionMethod].verifying_key()),
-            KeyPurpose::KeyAgreement => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::KeyAgreement].verifying_key()),
-            KeyPurpose::CapabilityInvocation => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::CapabilityInvocation].verifying_key()),
-            KeyPurpose::CapabilityDelegation => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::CapabilityDelegation].verifying_key()),
-            KeyPurpose::UpdateDIDDocument => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::UpdateDIDDocument].verifying_key()),
-        };
+        let priv_key_vm = generate_priv_keys()?;
 
         // Define the update rules.  For now, just a single key.
         let update_rules = if let Some(mb_hash_function_for_update_key) =
@@ -183,11 +315,11 @@ impl Wallet for SoftwareWallet {
         {
             RootLevelUpdateRules::from(HashedUpdateKey::from_pub_key(
                 mb_hash_function_for_update_key,
-                &pub_key_m[KeyPurpose::UpdateDIDDocument],
+                &priv_key_vm[KeyPurpose::UpdateDIDDocument][0].0,
             ))
         } else {
             RootLevelUpdateRules::from(UpdateKey {
-                pub_key: pub_key_m[KeyPurpose::UpdateDIDDocument].clone(),
+                pub_key: priv_key_vm[KeyPurpose::UpdateDIDDocument][0].0.clone(),
             })
         };
 
@@ -198,13 +330,7 @@ impl Wallet for SoftwareWallet {
             did_path_o.as_deref(),
             update_rules,
             now_utc,
-            did_webplus_core::PublicKeySet {
-                authentication_v: vec![&pub_key_m[KeyPurpose::Authentication]],
-                assertion_method_v: vec![&pub_key_m[KeyPurpose::AssertionMethod]],
-                key_agreement_v: vec![&pub_key_m[KeyPurpose::KeyAgreement]],
-                capability_invocation_v: vec![&pub_key_m[KeyPurpose::CapabilityInvocation]],
-                capability_delegation_v: vec![&pub_key_m[KeyPurpose::CapabilityDelegation]],
-            },
+            public_key_set(&priv_key_vm),
             create_did_parameters.mb_hash_function_for_did,
         )
         .expect("programmer error");
@@ -257,42 +383,110 @@ impl Wallet for SoftwareWallet {
                 )
                 .await?;
 
-            let header_map = {
-                let mut header_map = reqwest::header::HeaderMap::new();
-                if let Some(http_headers_for) = http_options_o.map(|o| &o.http_headers_for) {
-                    if let Some(http_header_v) =
-                        http_headers_for.http_headers_for_hostname(did.hostname())
-                    {
-                        for http_header in http_header_v {
-                            header_map.insert(
-                                reqwest::header::HeaderName::from_bytes(http_header.name.as_bytes()).map_err(|e| Error::Malformed(format!("Failed to parse HTTP header name from {:?}; error was: {}", http_header.name, e).into()))?,
-                                reqwest::header::HeaderValue::from_str(&http_header.value).map_err(|e| Error::Malformed(format!("Failed to parse HTTP header {:?} value to HeaderValue; error was: {}", http_header, e).into()))?,
-                            );
+            if let Some(static_hosting_dir) = self.static_hosting_dir_o.as_deref() {
+                // Write the DID document into the static-hosting directory tree instead of sending it to a VDR.
+                did_webplus_doc_store::append_did_document_to_static_tree(
+                    static_hosting_dir,
+                    &did_document,
+                    did_document_jcs.as_str(),
+                )?;
+            } else {
+                let mut header_map = {
+                    let mut header_map = reqwest::header::HeaderMap::new();
+                    if let Some(http_headers_for) = http_options_o.map(|o| &o.http_headers_for) {
+                        if let Some(http_header_v) =
+                            http_headers_for.http_headers_for_hostname(did.hostname())
+                        {
+                            for http_header in http_header_v {
+                                header_map.insert(
+                                    reqwest::header::HeaderName::from_bytes(http_header.name.as_bytes()).map_err(|e| Error::Malformed(format!("Failed to parse HTTP header name from {:?}; error was: {}", http_header.name, e).into()))?,
+                                    reqwest::header::HeaderValue::from_str(&http_header.value).map_err(|e| Error::Malformed(format!("Failed to parse HTTP header {:?} value to HeaderValue; error was: {}", http_header, e).into()))?,
+                                );
+                            }
                         }
                     }
-                }
-                header_map
-            };
-
-            // HTTP POST is for DID create operation.
-            tracing::trace!("HTTP POST-ing DID document to VDR: {}", did_document_jcs);
-            REQWEST_CLIENT
-                .clone()
-                .post(did.resolution_url_for_did_documents_jsonl(
-                    http_options_o.map(|o| &o.http_scheme_override),
-                ))
-                .headers(header_map)
-                .body(did_document_jcs)
-                .send()
-                .await
-                .map_err(|e| Error::HTTPRequestError(e.to_string().into()))?
-                .error_for_status()
-                .map_err(|e| Error::HTTPOperationStatus(e.to_string().into()))?;
+                    header_map
+                };
+                // Authorize the request for VDRs that require it.  If a registrar key is specified, then use that,
+                // and otherwise the new DID's own capabilityInvocation key signs its creation.
+                let (vdr_authz_key_id, vdr_authz_signer_b) = if let Some(vdr_authz_key_id) =
+                    create_did_parameters.vdr_authz_key_id_o
+                {
+                    let (_verification_method_record, vdr_authz_signer_b) = self
+                        .wallet_storage_a
+                        .get_locally_controlled_verification_methods(
+                            Some(transaction_b.as_mut()),
+                            &self.ctx,
+                            &LocallyControlledVerificationMethodFilter {
+                                did_o: Some(vdr_authz_key_id.without_fragment().did().to_owned()),
+                                version_id_o: Some(vdr_authz_key_id.query_version_id()),
+                                key_purpose_o: Some(KeyPurpose::CapabilityInvocation),
+                                key_id_o: Some(vdr_authz_key_id.fragment().to_string()),
+                                result_limit_o: None,
+                            },
+                        )
+                        .await?
+                        .into_iter()
+                        .next()
+                        .ok_or_else(|| {
+                            Error::NoLocallyControlledVerificationMethodFound(
+                                format!("capabilityInvocation key {}", vdr_authz_key_id).into(),
+                            )
+                        })?;
+                    (vdr_authz_key_id.to_string(), vdr_authz_signer_b)
+                } else {
+                    let capability_invocation_relative_key_resource = did_document
+                        .public_key_material
+                        .relative_key_resources_for_purpose(KeyPurpose::CapabilityInvocation)
+                        .next()
+                        .expect("programmer error");
+                    let verification_method = did_document
+                        .public_key_material
+                        .verification_method_for_key_id_fragment(
+                            capability_invocation_relative_key_resource.fragment(),
+                        )
+                        .expect("programmer error");
+                    let vdr_authz_signer_b: Box<dyn signature_dyn::AsyncSignerT + Send + Sync> =
+                        Box::new(priv_key_vm[KeyPurpose::CapabilityInvocation][0].1.clone());
+                    (verification_method.id.to_string(), vdr_authz_signer_b)
+                };
+                header_map.insert(
+                    did_webplus_core::VDR_AUTHORIZATION_JWS_HEADER,
+                    vdr_authorization_jws_header_value(
+                        vdr_authz_key_id,
+                        did_document_jcs.as_str(),
+                        vdr_authz_signer_b.as_ref(),
+                    )
+                    .await?,
+                );
+
+                // HTTP POST is for DID create operation.
+                tracing::trace!("HTTP POST-ing DID document to VDR: {}", did_document_jcs);
+                REQWEST_CLIENT
+                    .clone()
+                    .post(did.resolution_url_for_did_documents_jsonl(
+                        http_options_o.map(|o| &o.http_scheme_override),
+                    ))
+                    .headers(header_map)
+                    .body(did_document_jcs)
+                    .send()
+                    .await
+                    .map_err(|e| Error::HTTPRequestError(e.to_string().into()))?
+                    .error_for_status()
+                    .map_err(|e| Error::HTTPOperationStatus(e.to_string().into()))?;
+            }
         }
 
         // Store the priv keys
-        for key_purpose in KeyPurpose::VARIANTS {
-            let pub_key = pub_key_m[key_purpose].clone();
+        for (key_purpose, pub_key, signer_bytes) in
+            priv_key_vm
+                .into_iter()
+                .flat_map(|(key_purpose, priv_key_v)| {
+                    priv_key_v
+                        .into_iter()
+                        .map(move |(pub_key, signer_bytes)| (key_purpose, pub_key, signer_bytes))
+                })
+        {
             let hashed_pub_key_string = if let Some(mb_hash_function_for_update_key) =
                 create_did_parameters.mb_hash_function_for_update_key_o
             {
@@ -308,7 +502,6 @@ impl Wallet for SoftwareWallet {
                 None
             };
             let comment_o = Some("generated during DID create".to_string());
-            use signature_dyn::ExtractableSignerT;
             self.wallet_storage_a
                 .add_priv_key(
                     Some(transaction_b.as_mut()),
@@ -323,9 +516,7 @@ impl Wallet for SoftwareWallet {
                         max_usage_count_o,
                         usage_count: 0,
                         deleted_at_o: None,
-                        signer_bytes_o: Some(
-                            priv_key_m[key_purpose].extract_signer_bytes()?.to_owned(),
-                        ),
+                        signer_bytes_o: Some(signer_bytes),
                         comment_o,
                     },
                 )
@@ -367,25 +558,7 @@ impl Wallet for SoftwareWallet {
 
         // Rotate the appropriate set of keys.  Record the creation timestamp.
         let now_utc = now_utc_milliseconds();
-        // TODO: Somehow iterate?
-        use signature_dyn::GenerateRandom;
-        let priv_key_m = enum_map::enum_map! {
-            KeyPurpose::Authentication => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::AssertionMethod => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::KeyAgreement => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::CapabilityInvocation => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::CapabilityDelegation => ed25519_dalek::SigningKey::generate_random(),
-            KeyPurpose::UpdateDIDDocument => ed25519_dalek::SigningKey::generate_random(),
-        };
-        // TODO: Somehow iterate?
-        let pub_key_m = enum_map::enum_map! {
-            KeyPurpose::Authentication => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::Authentication].verifying_key()),
-            KeyPurpose::AssertionMethod => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::AssertionMethod].verifying_key()),
-            KeyPurpose::KeyAgreement => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::KeyAgreement].verifying_key()),
-            KeyPurpose::CapabilityInvocation => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::CapabilityInvocation].verifying_key()),
-            KeyPurpose::CapabilityDelegation => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::CapabilityDelegation].verifying_key()),
-            KeyPurpose::UpdateDIDDocument => mbx::MBPubKey::from_ed25519_dalek_verifying_key(mbx::Base::Base64Url, &priv_key_m[KeyPurpose::UpdateDIDDocument].verifying_key()),
-        };
+        let priv_key_vm = generate_priv_keys()?;
 
         let mut transaction_b = self
             .wallet_storage_a
@@ -480,11 +653,11 @@ impl Wallet for SoftwareWallet {
         {
             RootLevelUpdateRules::from(HashedUpdateKey::from_pub_key(
                 mb_hash_function_for_update_key,
-                &pub_key_m[KeyPurpose::UpdateDIDDocument],
+                &priv_key_vm[KeyPurpose::UpdateDIDDocument][0].0,
             ))
         } else {
             RootLevelUpdateRules::from(UpdateKey {
-                pub_key: pub_key_m[KeyPurpose::UpdateDIDDocument].clone(),
+                pub_key: priv_key_vm[KeyPurpose::UpdateDIDDocument][0].0.clone(),
             })
         };
 
@@ -512,13 +685,7 @@ impl Wallet for SoftwareWallet {
             &latest_did_document,
             update_rules,
             now_utc,
-            did_webplus_core::PublicKeySet {
-                authentication_v: vec![&pub_key_m[KeyPurpose::Authentication]],
-                assertion_method_v: vec![&pub_key_m[KeyPurpose::AssertionMethod]],
-                key_agreement_v: vec![&pub_key_m[KeyPurpose::KeyAgreement]],
-                capability_invocation_v: vec![&pub_key_m[KeyPurpose::CapabilityInvocation]],
-                capability_delegation_v: vec![&pub_key_m[KeyPurpose::CapabilityDelegation]],
-            },
+            public_key_set(&priv_key_vm),
             mb_hash_function_for_self_hash,
         )
         .expect("programmer error");
@@ -565,49 +732,93 @@ impl Wallet for SoftwareWallet {
                 )
                 .await?;
 
-            let header_map = {
-                let mut header_map = reqwest::header::HeaderMap::new();
-                if let Some(http_headers_for) = http_options_o.map(|o| &o.http_headers_for) {
-                    if let Some(http_header_v) = http_headers_for
-                        .http_headers_for_hostname(updated_did_document.did.hostname())
-                    {
-                        for http_header in http_header_v {
-                            header_map.insert(
-                                reqwest::header::HeaderName::from_bytes(http_header.name.as_bytes()).map_err(|e| Error::Malformed(format!("Failed to parse HTTP header name from {:?}; error was: {}", http_header.name, e).into()))?,
-                                reqwest::header::HeaderValue::from_str(&http_header.value).map_err(|e| Error::Malformed(format!("Failed to parse HTTP header {:?} value to HeaderValue; error was: {}", http_header, e).into()))?,
-                            );
+            if let Some(static_hosting_dir) = self.static_hosting_dir_o.as_deref() {
+                // Write the DID document into the static-hosting directory tree instead of sending it to a VDR.
+                did_webplus_doc_store::append_did_document_to_static_tree(
+                    static_hosting_dir,
+                    &updated_did_document,
+                    updated_did_document_jcs.as_str(),
+                )?;
+            } else {
+                let mut header_map = {
+                    let mut header_map = reqwest::header::HeaderMap::new();
+                    if let Some(http_headers_for) = http_options_o.map(|o| &o.http_headers_for) {
+                        if let Some(http_header_v) = http_headers_for
+                            .http_headers_for_hostname(updated_did_document.did.hostname())
+                        {
+                            for http_header in http_header_v {
+                                header_map.insert(
+                                    reqwest::header::HeaderName::from_bytes(http_header.name.as_bytes()).map_err(|e| Error::Malformed(format!("Failed to parse HTTP header name from {:?}; error was: {}", http_header.name, e).into()))?,
+                                    reqwest::header::HeaderValue::from_str(&http_header.value).map_err(|e| Error::Malformed(format!("Failed to parse HTTP header {:?} value to HeaderValue; error was: {}", http_header, e).into()))?,
+                                );
+                            }
                         }
                     }
+                    header_map
+                };
+                // Authorize the request for VDRs that require it, using a capabilityInvocation key from the latest
+                // DID document, if one is locally controlled.
+                if let Some((verification_method_record, signer_b)) =
+                    capability_invocation_verification_method_o(
+                        &locally_controlled_verification_method_v,
+                    )
+                {
+                    header_map.insert(
+                        did_webplus_core::VDR_AUTHORIZATION_JWS_HEADER,
+                        vdr_authorization_jws_header_value(
+                            verification_method_record
+                                .did_key_resource_fully_qualified
+                                .to_string(),
+                            updated_did_document_jcs.as_str(),
+                            signer_b.as_ref(),
+                        )
+                        .await?,
+                    );
                 }
-                header_map
-            };
 
-            // HTTP PUT is for DID update operation.
-            tracing::trace!(
-                "HTTP PUT-ing DID document to VDR: {}",
-                updated_did_document_jcs
-            );
-            REQWEST_CLIENT
-                .clone()
-                .put(
-                    updated_did_document
-                        .did
-                        .resolution_url_for_did_documents_jsonl(
-                            http_options_o.map(|o| &o.http_scheme_override),
-                        ),
+                let did_documents_jsonl_url = updated_did_document
+                    .did
+                    .resolution_url_for_did_documents_jsonl(
+                        http_options_o.map(|o| &o.http_scheme_override),
+                    );
+
+                // Have the VDR validate the update before submitting it, so that if the VDR would reject it (e.g.
+                // because of one of its admission policies), the reason is reported and nothing is changed.
+                validate_did_update_with_vdr(
+                    did_documents_jsonl_url.as_str(),
+                    header_map.clone(),
+                    updated_did_document_jcs.as_str(),
                 )
-                .headers(header_map)
-                .body(updated_did_document_jcs)
-                .send()
-                .await
-                .map_err(|e| Error::HTTPRequestError(e.to_string().into()))?
-                .error_for_status()
-                .map_err(|e| Error::HTTPOperationStatus(e.to_string().into()))?;
+                .await?;
+
+                // HTTP PUT is for DID update operation.
+                tracing::trace!(
+                    "HTTP PUT-ing DID document to VDR: {}",
+                    updated_did_document_jcs
+                );
+                REQWEST_CLIENT
+                    .clone()
+                    .put(did_documents_jsonl_url)
+                    .headers(header_map)
+                    .body(updated_did_document_jcs)
+                    .send()
+                    .await
+                    .map_err(|e| Error::HTTPRequestError(e.to_string().into()))?
+                    .error_for_status()
+                    .map_err(|e| Error::HTTPOperationStatus(e.to_string().into()))?;
+            }
         }
 
         // Store the priv keys
-        for key_purpose in KeyPurpose::VARIANTS {
-            let pub_key = pub_key_m[key_purpose].clone();
+        for (key_purpose, pub_key, signer_bytes) in
+            priv_key_vm
+                .into_iter()
+                .flat_map(|(key_purpose, priv_key_v)| {
+                    priv_key_v
+                        .into_iter()
+                        .map(move |(pub_key, signer_bytes)| (key_purpose, pub_key, signer_bytes))
+                })
+        {
             let hashed_pub_key_string = if let Some(mb_hash_function_for_update_key) =
                 update_did_parameters.mb_hash_function_for_update_key_o
             {
@@ -626,7 +837,6 @@ impl Wallet for SoftwareWallet {
                 "generated during DID update versionId {} -> {}",
                 latest_did_document.version_id, updated_did_document.version_id
             ));
-            use signature_dyn::ExtractableSignerT;
             self.wallet_storage_a
                 .add_priv_key(
                     Some(transaction_b.as_mut()),
@@ -641,7 +851,7 @@ impl Wallet for SoftwareWallet {
                         max_usage_count_o,
                         usage_count: 0,
                         deleted_at_o: None,
-                        signer_bytes_o: Some(priv_key_m[key_purpose].extract_signer_bytes()?),
+                        signer_bytes_o: Some(signer_bytes),
                         comment_o,
                     },
                 )
@@ -888,45 +1098,73 @@ impl Wallet for SoftwareWallet {
                 )
                 .await?;
 
-            // Form the HTTP headers.
-            let header_map = {
-                let mut header_map = reqwest::header::HeaderMap::new();
-                if let Some(http_headers_for) = http_options_o.map(|o| &o.http_headers_for) {
-                    if let Some(http_header_v) = http_headers_for
-                        .http_headers_for_hostname(deactivate_did_parameters.did.hostname())
-                    {
-                        for http_header in http_header_v {
-                            header_map.insert(
-                                reqwest::header::HeaderName::from_bytes(http_header.name.as_bytes()).map_err(|e| Error::Malformed(format!("Failed to parse HTTP header name from {:?}; error was: {}", http_header.name, e).into()))?,
-                                reqwest::header::HeaderValue::from_str(&http_header.value).map_err(|e| Error::Malformed(format!("Failed to parse HTTP header {:?} value to HeaderValue; error was: {}", http_header, e).into()))?,
-                        );
+            if let Some(static_hosting_dir) = self.static_hosting_dir_o.as_deref() {
+                // Write the DID document into the static-hosting directory tree instead of sending it to a VDR.
+                did_webplus_doc_store::append_did_document_to_static_tree(
+                    static_hosting_dir,
+                    &deactivated_did_document,
+                    deactivated_did_document_jcs.as_str(),
+                )?;
+            } else {
+                // Form the HTTP headers.
+                let mut header_map = {
+                    let mut header_map = reqwest::header::HeaderMap::new();
+                    if let Some(http_headers_for) = http_options_o.map(|o| &o.http_headers_for) {
+                        if let Some(http_header_v) = http_headers_for
+                            .http_headers_for_hostname(deactivate_did_parameters.did.hostname())
+                        {
+                            for http_header in http_header_v {
+                                header_map.insert(
+                                    reqwest::header::HeaderName::from_bytes(http_header.name.as_bytes()).map_err(|e| Error::Malformed(format!("Failed to parse HTTP header name from {:?}; error was: {}", http_header.name, e).into()))?,
+                                    reqwest::header::HeaderValue::from_str(&http_header.value).map_err(|e| Error::Malformed(format!("Failed to parse HTTP header {:?} value to HeaderValue; error was: {}", http_header, e).into()))?,
+                            );
+                            }
                         }
                     }
+                    header_map
+                };
+                // Authorize the request for VDRs that require it, using a capabilityInvocation key from the latest
+                // DID document, if one is locally controlled.
+                if let Some((verification_method_record, signer_b)) =
+                    capability_invocation_verification_method_o(
+                        &locally_controlled_verification_method_v,
+                    )
+                {
+                    header_map.insert(
+                        did_webplus_core::VDR_AUTHORIZATION_JWS_HEADER,
+                        vdr_authorization_jws_header_value(
+                            verification_method_record
+                                .did_key_resource_fully_qualified
+                                .to_string(),
+                            deactivated_did_document_jcs.as_str(),
+                            signer_b.as_ref(),
+                        )
+                        .await?,
+                    );
                 }
-                header_map
-            };
 
-            // HTTP PUT is for DID update operation (which includes deactivation).
-            tracing::trace!(
-                "HTTP PUT-ing DID document to VDR: {}",
-                deactivated_did_document_jcs
-            );
-            REQWEST_CLIENT
-                .clone()
-                .put(
-                    deactivate_did_parameters
-                        .did
-                        .resolution_url_for_did_documents_jsonl(
-                            http_options_o.map(|o| &o.http_scheme_override),
-                        ),
-                )
-                .headers(header_map)
-                .body(deactivated_did_document_jcs)
-                .send()
-                .await
-                .map_err(|e| Error::HTTPRequestError(e.to_string().into()))?
-                .error_for_status()
-                .map_err(|e| Error::HTTPOperationStatus(e.to_string().into()))?;
+                // HTTP PUT is for DID update operation (which includes deactivation).
+                tracing::trace!(
+                    "HTTP PUT-ing DID document to VDR: {}",
+                    deactivated_did_document_jcs
+                );
+                REQWEST_CLIENT
+                    .clone()
+                    .put(
+                        deactivate_did_parameters
+                            .did
+                            .resolution_url_for_did_documents_jsonl(
+                                http_options_o.map(|o| &o.http_scheme_override),
+                            ),
+                    )
+                    .headers(header_map)
+                    .body(deactivated_did_document_jcs)
+                    .send()
+                    .await
+                    .map_err(|e| Error::HTTPRequestError(e.to_string().into()))?
+                    .error_for_status()
+                    .map_err(|e| Error::HTTPOperationStatus(e.to_string().into()))?;
+            }
         }
 
         let controlled_did = deactivate_did_parameters.did.with_queries(
@@ -980,6 +1218,171 @@ impl Wallet for SoftwareWallet {
 
         Ok(controlled_did)
     }
+    async fn derive_shared_secret(
+        &self,
+        key_id: &DIDKeyResourceFullyQualifiedStr,
+        other_pub_key: &mbx::MBPubKeyStr,
+    ) -> Result<zeroize::Zeroizing<Vec<u8>>> {
+        tracing::debug!(?key_id, ?other_pub_key, "deriving shared secret");
+
+        let mut transaction_b = self
+            .wallet_storage_a
+            .begin_transaction()
+            .await
+            .map_err(|e| did_webplus_wallet_store::Error::from(e))?;
+
+        let verification_method_record = self
+            .wallet_storage_a
+            .get_locally_controlled_verification_methods(
+                Some(transaction_b.as_mut()),
+                &self.ctx,
+                &LocallyControlledVerificationMethodFilter {
+                    did_o: Some(key_id.did().to_owned()),
+                    version_id_o: Some(key_id.query_version_id()),
+                    key_purpose_o: Some(KeyPurpose::KeyAgreement),
+                    key_id_o: Some(key_id.fragment().to_string()),
+                    result_limit_o: None,
+                },
+            )
+            .await?
+            .into_iter()
+            .map(|(verification_method_record, _signer_b)| verification_method_record)
+            .next()
+            .ok_or_else(|| {
+                Error::NoLocallyControlledVerificationMethodFound(
+                    format!("keyAgreement key {}", key_id).into(),
+                )
+            })?;
+        let shared_secret = self
+            .derive_shared_secret_using_verification_method(
+                transaction_b.as_mut(),
+                &verification_method_record,
+                other_pub_key,
+                PrivKeyUsage::KeyExchange {
+                    other_o: Some(other_pub_key.to_owned()),
+                },
+            )
+            .await?;
+
+        transaction_b
+            .commit()
+            .await
+            .map_err(|e| did_webplus_wallet_store::Error::from(e))?;
+
+        Ok(shared_secret)
+    }
+    async fn derive_shared_secret_with_did(
+        &self,
+        did_o: Option<&DIDStr>,
+        other_key_id: &DIDKeyResourceFullyQualifiedStr,
+        http_options_o: Option<&did_webplus_core::HTTPOptions>,
+    ) -> Result<(DIDKeyResourceFullyQualified, zeroize::Zeroizing<Vec<u8>>)> {
+        tracing::debug!(?did_o, ?other_key_id, "deriving shared secret with DID");
+
+        let controlled_did = self.get_controlled_did(did_o).await?;
+
+        // Resolve the other key and check that it's actually a keyAgreement key.
+        let (other_did_document, _did_document_metadata, _did_resolution_metadata) = {
+            use did_webplus_resolver::DIDResolver;
+            self.did_resolver_full(http_options_o)
+                .resolve_did_document(
+                    other_key_id.without_fragment().as_str(),
+                    did_webplus_core::DIDResolutionOptions::no_metadata(false),
+                )
+                .await
+                .map_err(|e| {
+                    Error::DIDFetchError(
+                        format!("DID: {}, error was: {}", other_key_id.without_fragment(), e)
+                            .into(),
+                    )
+                })?
+        };
+        if !other_did_document
+            .public_key_material
+            .key_purpose_flags_for_key_id_fragment(other_key_id.fragment())
+            .contains(KeyPurpose::KeyAgreement)
+        {
+            return Err(Error::Malformed(
+                format!(
+                    "{} is not listed under keyAgreement in its DID document",
+                    other_key_id
+                )
+                .into(),
+            ));
+        }
+        let other_verification_method = other_did_document
+            .public_key_material
+            .verification_method_for_key_id_fragment(other_key_id.fragment())?;
+        let other_pub_key = mbx::MBPubKey::try_from(&other_verification_method.public_key_jwk)?;
+        let key_agreement_key_type =
+            KeyAgreementKeyType::of_pub_key(&other_pub_key).ok_or_else(|| {
+                Error::NoSuitablePrivKeyFound(
+                    format!(
+                        "keyAgreement key {} has an unsupported key type for key exchange",
+                        other_key_id
+                    )
+                    .into(),
+                )
+            })?;
+
+        let mut transaction_b = self
+            .wallet_storage_a
+            .begin_transaction()
+            .await
+            .map_err(|e| did_webplus_wallet_store::Error::from(e))?;
+
+        // Select the locally-controlled keyAgreement key having the same key type as the other key.
+        let verification_method_record = self
+            .wallet_storage_a
+            .get_locally_controlled_verification_methods(
+                Some(transaction_b.as_mut()),
+                &self.ctx,
+                &LocallyControlledVerificationMethodFilter {
+                    did_o: Some(controlled_did.did().to_owned()),
+                    version_id_o: None,
+                    key_purpose_o: Some(KeyPurpose::KeyAgreement),
+                    key_id_o: None,
+                    result_limit_o: None,
+                },
+            )
+            .await?
+            .into_iter()
+            .map(|(verification_method_record, _signer_b)| verification_method_record)
+            .find(|verification_method_record| {
+                KeyAgreementKeyType::of_pub_key(&verification_method_record.pub_key)
+                    == Some(key_agreement_key_type)
+            })
+            .ok_or_else(|| {
+                Error::NoSuitablePrivKeyFound(
+                    format!(
+                        "no locally-controlled {:?} keyAgreement key for DID {}",
+                        key_agreement_key_type,
+                        controlled_did.did()
+                    )
+                    .into(),
+                )
+            })?;
+        let shared_secret = self
+            .derive_shared_secret_using_verification_method(
+                transaction_b.as_mut(),
+                &verification_method_record,
+                &other_pub_key,
+                PrivKeyUsage::KeyExchangeWithDID {
+                    other_o: Some(other_key_id.to_owned()),
+                },
+            )
+            .await?;
+
+        transaction_b
+            .commit()
+            .await
+            .map_err(|e| did_webplus_wallet_store::Error::from(e))?;
+
+        Ok((
+            verification_method_record.did_key_resource_fully_qualified,
+            shared_secret,
+        ))
+    }
     async fn get_locally_controlled_verification_methods(
         &self,
         locally_controlled_verification_method_filter: &LocallyControlledVerificationMethodFilter,
@@ -1009,3 +1412,105 @@ impl Wallet for SoftwareWallet {
         Ok(query_result_v)
     }
 }
+
+/// Returns the first of the given locally-controlled verification methods that may be used for capabilityInvocation.
+fn capability_invocation_verification_method_o(
+    locally_controlled_verification_method_v: &[(
+        VerificationMethodRecord,
+        Box<dyn signature_dyn::AsyncSignerT + Send + Sync>,
+    )],
+) -> Option<&(
+    VerificationMethodRecord,
+    Box<dyn signature_dyn::AsyncSignerT + Send + Sync>,
+)> {
+    locally_controlled_verification_method_v.iter().find(
+        |(verification_method_record, _signer_b)| {
+            verification_method_record
+                .key_purpose_restriction_o
+                .map(|key_purpose_flags| {
+                    key_purpose_flags.contains(KeyPurpose::CapabilityInvocation)
+                })
+                .unwrap_or(true)
+        },
+    )
+}
+
+/// Forms the value of the did_webplus_core::VDR_AUTHORIZATION_JWS_HEADER header of a DID create or update request,
+/// i.e. a detached JWS over the DID document, signed by the capabilityInvocation key having the given key ID.
+async fn vdr_authorization_jws_header_value(
+    kid: String,
+    did_document_jcs: &str,
+    async_signer: &dyn signature_dyn::AsyncSignerT,
+) -> Result<reqwest::header::HeaderValue> {
+    let jws = did_webplus_jws::JWS::async_signed(
+        kid,
+        &mut did_document_jcs.as_bytes(),
+        did_webplus_jws::JWSPayloadPresence::Detached,
+        did_webplus_jws::JWSPayloadEncoding::Base64,
+        async_signer,
+    )
+    .await
+    .map_err(|e| {
+        Error::Malformed(format!("Failed to sign VDR authorization JWS; error was: {}", e).into())
+    })?;
+    Ok(reqwest::header::HeaderValue::from_str(jws.as_str()).expect("programmer error"))
+}
+
+/// Do a dry-run of the DID update with the VDR (see did_webplus_core::VDR_DRY_RUN_PATH_SUFFIX), returning
+/// Error::DIDDocumentRejectedByVDR if the VDR would reject it.  A VDR that predates the dry-run route treats
+/// the dry-run URL as a malformed resolution URL, so a 400, 404, or 405 response is taken to mean that the
+/// VDR doesn't support dry-runs, in which case the update isn't validated ahead of time.
+async fn validate_did_update_with_vdr(
+    did_documents_jsonl_url: &str,
+    header_map: reqwest::header::HeaderMap,
+    updated_did_document_jcs: &str,
+) -> Result<()> {
+    tracing::trace!(
+        "HTTP PUT-ing DID document to VDR as a dry-run: {}",
+        updated_did_document_jcs
+    );
+    let response = REQWEST_CLIENT
+        .clone()
+        .put(format!(
+            "{}{}",
+            did_documents_jsonl_url,
+            did_webplus_core::VDR_DRY_RUN_PATH_SUFFIX
+        ))
+        .headers(header_map)
+        .body(updated_did_document_jcs.to_string())
+        .send()
+        .await
+        .map_err(|e| Error::HTTPRequestError(e.to_string().into()))?;
+    if matches!(
+        response.status(),
+        reqwest::StatusCode::BAD_REQUEST
+            | reqwest::StatusCode::NOT_FOUND
+            | reqwest::StatusCode::METHOD_NOT_ALLOWED
+    ) {
+        tracing::debug!(
+            "VDR does not support dry-runs (HTTP status {}); skipping validation of DID update",
+            response.status()
+        );
+        return Ok(());
+    }
+    let did_document_validation_result = response
+        .error_for_status()
+        .map_err(|e| Error::HTTPOperationStatus(e.to_string().into()))?
+        .json::<did_webplus_core::DIDDocumentValidationResult>()
+        .await
+        .map_err(|e| Error::HTTPRequestError(e.to_string().into()))?;
+    if !did_document_validation_result.valid {
+        return Err(Error::DIDDocumentRejectedByVDR(
+            format!(
+                "HTTP status {}: {}",
+                did_document_validation_result.status,
+                did_document_validation_result
+                    .error_o
+                    .as_deref()
+                    .unwrap_or("<no reason given>")
+            )
+            .into(),
+        ));
+    }
+    Ok(())
+}
@@ -6,7 +6,10 @@ fn overall_init() {
     test_util::ctor_overall_init();
 }
 
-async fn test_software_wallet_impl(software_wallet: &did_webplus_software_wallet::SoftwareWallet) {
+async fn test_software_wallet_impl(
+    software_wallet: &did_webplus_software_wallet::SoftwareWallet,
+    wallet_storage: &dyn did_webplus_wallet_store::WalletStorage,
+) {
     // TODO: Use env vars to be able to point to a "real" VDR.
 
     // Delete any existing database files so that we're starting from a consistent, blank start every time.
@@ -22,6 +25,10 @@ async fn test_software_wallet_impl(software_wallet: &did_webplus_software_wallet
         vdg_base_url_v: Vec::new(),
         http_scheme_override: Default::default(),
         test_authz_api_key_vo: None,
+        registrar_permissions_o: None,
+        serve_did_web: false,
+        audit_interval_seconds_o: None,
+        admission_policy_config: Default::default(),
     };
     let vdr_handle = did_webplus_vdr_lib::spawn_vdr(vdr_config.clone())
         .await
@@ -71,6 +78,7 @@ async fn test_software_wallet_impl(software_wallet: &did_webplus_software_wallet
                         vdr_did_create_endpoint: vdr_did_create_endpoint.as_str(),
                         mb_hash_function_for_did: &mb_hash_function,
                         mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                        vdr_authz_key_id_o: None,
                     },
                     Some(&http_options),
                 )
@@ -92,6 +100,107 @@ async fn test_software_wallet_impl(software_wallet: &did_webplus_software_wallet
                 .expect("pass");
             tracing::debug!("updated DID: {} - fully qualified: {}", did, controlled_did);
 
+            // Create another DID and check that key exchange between the two DIDs produces the same
+            // shared secret from both sides, for each keyAgreement key type.
+            let other_controlled_did = software_wallet
+                .create_did(
+                    did_webplus_wallet::CreateDIDParameters {
+                        vdr_did_create_endpoint: vdr_did_create_endpoint.as_str(),
+                        mb_hash_function_for_did: &mb_hash_function,
+                        mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                        vdr_authz_key_id_o: None,
+                    },
+                    Some(&http_options),
+                )
+                .await
+                .expect("pass");
+            let other_did = other_controlled_did.did();
+            let key_agreement_query_result_v = software_wallet
+                .get_locally_controlled_verification_methods(
+                    &did_webplus_wallet_store::LocallyControlledVerificationMethodFilter {
+                        did_o: Some(did.to_owned()),
+                        version_id_o: None,
+                        key_purpose_o: Some(did_webplus_core::KeyPurpose::KeyAgreement),
+                        key_id_o: None,
+                        result_limit_o: None,
+                    },
+                )
+                .await
+                .expect("pass");
+            // One X25519 key and one P-256 key.
+            assert_eq!(key_agreement_query_result_v.len(), 2);
+            for (verification_method_record, signer_b) in key_agreement_query_result_v {
+                let key_id = &verification_method_record.did_key_resource_fully_qualified;
+                // keyAgreement priv keys must never be used for signing.
+                assert!(
+                    signer_b
+                        .async_try_sign_message(b"hippos are cool")
+                        .await
+                        .is_err()
+                );
+                assert!(signer_b.async_get_verifier_bytes().await.is_err());
+                let (other_key_id, other_shared_secret) = software_wallet
+                    .derive_shared_secret_with_did(Some(&other_did), key_id, Some(&http_options))
+                    .await
+                    .expect("pass");
+                let (derived_key_id, shared_secret) = software_wallet
+                    .derive_shared_secret_with_did(Some(&did), &other_key_id, Some(&http_options))
+                    .await
+                    .expect("pass");
+                tracing::debug!(
+                    "derived shared secret between {} and {}",
+                    key_id,
+                    other_key_id
+                );
+                assert_eq!(&derived_key_id, key_id);
+                assert_eq!(shared_secret, other_shared_secret);
+                assert_eq!(shared_secret.len(), 32);
+
+                // The key exchange should have been recorded as a KeyExchangeWithDID usage of the local key.
+                let priv_key_usage_record_v = wallet_storage
+                    .get_priv_key_usages(
+                        None,
+                        software_wallet.wallet_storage_ctx(),
+                        &did_webplus_wallet_store::PrivKeyUsageRecordFilter {
+                            pub_key_o: Some(verification_method_record.pub_key.clone()),
+                            usage_type_o: Some(
+                                did_webplus_wallet_store::PrivKeyUsageType::KeyExchangeWithDID,
+                            ),
+                            ..Default::default()
+                        },
+                    )
+                    .await
+                    .expect("pass");
+                assert_eq!(priv_key_usage_record_v.len(), 1);
+                let priv_key_usage_record = &priv_key_usage_record_v[0];
+                assert!(matches!(
+                    &priv_key_usage_record.usage,
+                    did_webplus_wallet_store::PrivKeyUsage::KeyExchangeWithDID {
+                        other_o: Some(other),
+                    } if other == &other_key_id
+                ));
+                assert_eq!(
+                    priv_key_usage_record.verification_method_o.as_ref(),
+                    Some(key_id)
+                );
+                assert_eq!(
+                    priv_key_usage_record.key_purpose_o,
+                    Some(did_webplus_core::KeyPurpose::KeyAgreement)
+                );
+            }
+            // An authentication key is not listed under keyAgreement, so can't be used for key exchange.
+            let other_authentication_key_id = other_controlled_did.with_fragment("0");
+            assert!(
+                software_wallet
+                    .derive_shared_secret_with_did(
+                        Some(&did),
+                        &other_authentication_key_id,
+                        Some(&http_options)
+                    )
+                    .await
+                    .is_err()
+            );
+
             let deactivated_did = software_wallet
                 .deactivate_did(
                     did_webplus_wallet::DeactivateDIDParameters {
@@ -140,7 +249,7 @@ async fn test_software_wallet_with_storage_sqlite() {
     let mut transaction_b = wallet_storage_a.begin_transaction().await.expect("pass");
     let software_wallet = did_webplus_software_wallet::SoftwareWallet::create(
         transaction_b.as_mut(),
-        wallet_storage_a,
+        wallet_storage_a.clone(),
         Some("fancy wallet".to_string()),
         None,
     )
@@ -148,7 +257,7 @@ async fn test_software_wallet_with_storage_sqlite() {
     .expect("pass");
     transaction_b.commit().await.expect("pass");
 
-    test_software_wallet_impl(&software_wallet).await;
+    test_software_wallet_impl(&software_wallet, wallet_storage_a.as_ref()).await;
 }
 
 #[tokio::test]
@@ -168,5 +277,557 @@ async fn test_software_wallet_with_storage_mock() {
     .expect("pass");
     transaction_b.commit().await.expect("pass");
 
-    test_software_wallet_impl(&software_wallet).await;
+    test_software_wallet_impl(&software_wallet, wallet_storage_a.as_ref()).await;
+}
+
+#[tokio::test]
+async fn test_wallet_storage_requires_key_agreement_tag() {
+    let wallet_storage_a = Arc::new(did_webplus_wallet_storage_mock::WalletStorageMock::new());
+    use storage_traits::StorageDynT;
+    let mut transaction_b = wallet_storage_a.begin_transaction().await.expect("pass");
+    let software_wallet = did_webplus_software_wallet::SoftwareWallet::create(
+        transaction_b.as_mut(),
+        wallet_storage_a.clone(),
+        None,
+        None,
+    )
+    .await
+    .expect("pass");
+    transaction_b.commit().await.expect("pass");
+
+    use did_webplus_wallet_store::WalletStorage;
+    // An X25519 priv key is stored as the Ed25519 seed it's derived from, so it must be tagged as a
+    // keyAgreement key in order to be stored, so that it can't be used for signing.
+    let (pub_key, signer_bytes) = did_webplus_core::KeyAgreementKeyType::X25519
+        .generate_priv_key()
+        .expect("pass");
+    let priv_key_record = |key_purpose_restriction_o| did_webplus_wallet_store::PrivKeyRecord {
+        pub_key: pub_key.clone(),
+        hashed_pub_key: "<not-hashed>".to_string(),
+        did_restriction_o: None,
+        key_purpose_restriction_o,
+        created_at: did_webplus_core::now_utc_milliseconds(),
+        last_used_at_o: None,
+        max_usage_count_o: None,
+        usage_count: 0,
+        deleted_at_o: None,
+        signer_bytes_o: Some(signer_bytes.clone()),
+        comment_o: None,
+    };
+    for key_purpose_restriction_o in [
+        None,
+        Some(did_webplus_core::KeyPurposeFlags::from(
+            did_webplus_core::KeyPurpose::AssertionMethod,
+        )),
+    ] {
+        wallet_storage_a
+            .add_priv_key(
+                None,
+                software_wallet.wallet_storage_ctx(),
+                priv_key_record(key_purpose_restriction_o),
+            )
+            .await
+            .expect_err("pass");
+    }
+    let key_agreement_priv_key_record = priv_key_record(Some(
+        did_webplus_core::KeyPurposeFlags::from(did_webplus_core::KeyPurpose::KeyAgreement),
+    ));
+    assert!(key_agreement_priv_key_record.is_key_agreement_key());
+    wallet_storage_a
+        .add_priv_key(
+            None,
+            software_wallet.wallet_storage_ctx(),
+            key_agreement_priv_key_record.clone(),
+        )
+        .await
+        .expect("pass");
+    // The signer for a keyAgreement priv key refuses to sign.
+    let signer_b = key_agreement_priv_key_record
+        .into_async_signer()
+        .expect("pass");
+    assert!(
+        signer_b
+            .async_try_sign_message(b"hippos are cool")
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn test_software_wallet_with_vdr_registrar_permissions() {
+    use did_webplus_wallet::Wallet;
+
+    let wallet_storage = did_webplus_wallet_storage_mock::WalletStorageMock::new();
+    let wallet_storage_a = Arc::new(wallet_storage);
+    use storage_traits::StorageDynT;
+    let mut transaction_b = wallet_storage_a.begin_transaction().await.expect("pass");
+    let software_wallet = did_webplus_software_wallet::SoftwareWallet::create(
+        transaction_b.as_mut(),
+        wallet_storage_a.clone(),
+        Some("registrar wallet".to_string()),
+        None,
+    )
+    .await
+    .expect("pass");
+    transaction_b.commit().await.expect("pass");
+
+    // The registrar DID has to exist before the VDR can be configured to trust it, so create it using a
+    // VDR that doesn't require signed authorization, but which shares its database with the VDR that does.
+    let database_url = "postgres:///test_software_wallet_with_vdr_registrar_permissions_vdr";
+    let bootstrap_vdr_config = did_webplus_vdr_lib::VDRConfig {
+        did_hostname: "localhost".to_string(),
+        did_port_o: Some(13093),
+        listen_port: 13093,
+        database_url: database_url.to_string(),
+        database_max_connections: 10,
+        vdg_base_url_v: Vec::new(),
+        http_scheme_override: Default::default(),
+        test_authz_api_key_vo: None,
+        registrar_permissions_o: None,
+        serve_did_web: false,
+        audit_interval_seconds_o: None,
+        admission_policy_config: Default::default(),
+    };
+    let bootstrap_vdr_handle = did_webplus_vdr_lib::spawn_vdr(bootstrap_vdr_config.clone())
+        .await
+        .expect("pass");
+    test_util::wait_until_service_is_up(
+        "bootstrap VDR",
+        format!(
+            "http://localhost:{}/health",
+            bootstrap_vdr_config.listen_port
+        )
+        .as_str(),
+    )
+    .await;
+
+    let http_scheme_override = did_webplus_core::HTTPSchemeOverride::new()
+        .with_override("localhost".to_string(), "http")
+        .unwrap();
+    let http_options = did_webplus_core::HTTPOptions {
+        http_headers_for: did_webplus_core::HTTPHeadersFor::new(),
+        http_scheme_override: http_scheme_override.clone(),
+    };
+    let mb_hash_function = selfhash::MBHashFunction::blake3(mbx::Base::Base64Url);
+
+    let registrar_controlled_did = software_wallet
+        .create_did(
+            did_webplus_wallet::CreateDIDParameters {
+                vdr_did_create_endpoint: "http://localhost:13093/admin",
+                mb_hash_function_for_did: &mb_hash_function,
+                mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                vdr_authz_key_id_o: None,
+            },
+            Some(&http_options),
+        )
+        .await
+        .expect("pass");
+    let registrar_did = registrar_controlled_did.did();
+    tracing::debug!("created registrar DID: {}", registrar_did);
+
+    let vdr_config = did_webplus_vdr_lib::VDRConfig {
+        did_hostname: "localhost".to_string(),
+        did_port_o: Some(13092),
+        listen_port: 13092,
+        database_url: database_url.to_string(),
+        database_max_connections: 10,
+        vdg_base_url_v: Vec::new(),
+        http_scheme_override: http_scheme_override.clone(),
+        test_authz_api_key_vo: None,
+        registrar_permissions_o: Some(
+            did_webplus_vdr_lib::RegistrarPermissions::new()
+                .with_permission(registrar_did.to_owned(), "tenant-a".to_string()),
+        ),
+        serve_did_web: false,
+        audit_interval_seconds_o: None,
+        admission_policy_config: Default::default(),
+    };
+    let vdr_handle = did_webplus_vdr_lib::spawn_vdr(vdr_config.clone())
+        .await
+        .expect("pass");
+    test_util::wait_until_service_is_up(
+        "VDR",
+        format!("http://localhost:{}/health", vdr_config.listen_port).as_str(),
+    )
+    .await;
+
+    let (registrar_verification_method_record, _registrar_signer_b) = software_wallet
+        .get_locally_controlled_verification_method(
+            did_webplus_wallet_store::LocallyControlledVerificationMethodFilter {
+                did_o: Some(registrar_did.to_owned()),
+                version_id_o: None,
+                key_purpose_o: Some(did_webplus_core::KeyPurpose::CapabilityInvocation),
+                key_id_o: None,
+                result_limit_o: None,
+            },
+        )
+        .await
+        .expect("pass");
+    let registrar_key_id = &registrar_verification_method_record.did_key_resource_fully_qualified;
+
+    // The registrar may create DIDs under its path prefix.
+    let tenant_controlled_did = software_wallet
+        .create_did(
+            did_webplus_wallet::CreateDIDParameters {
+                vdr_did_create_endpoint: "http://localhost:13092/tenant-a",
+                mb_hash_function_for_did: &mb_hash_function,
+                mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                vdr_authz_key_id_o: Some(registrar_key_id),
+            },
+            Some(&http_options),
+        )
+        .await
+        .expect("pass");
+    let tenant_did = tenant_controlled_did.did();
+    tracing::debug!("created tenant DID: {}", tenant_did);
+
+    // The registrar may not create DIDs outside its path prefix.
+    let err = software_wallet
+        .create_did(
+            did_webplus_wallet::CreateDIDParameters {
+                vdr_did_create_endpoint: "http://localhost:13092/tenant-b",
+                mb_hash_function_for_did: &mb_hash_function,
+                mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                vdr_authz_key_id_o: Some(registrar_key_id),
+            },
+            Some(&http_options),
+        )
+        .await
+        .expect_err("pass");
+    assert!(err.to_string().contains("403"), "{}", err);
+
+    // A DID that isn't a registrar may not sign its own creation.
+    let err = software_wallet
+        .create_did(
+            did_webplus_wallet::CreateDIDParameters {
+                vdr_did_create_endpoint: "http://localhost:13092/tenant-a",
+                mb_hash_function_for_did: &mb_hash_function,
+                mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                vdr_authz_key_id_o: None,
+            },
+            Some(&http_options),
+        )
+        .await
+        .expect_err("pass");
+    assert!(err.to_string().contains("403"), "{}", err);
+
+    // Sign something with the tenant DID's current capabilityInvocation key, which will be stale after the update.
+    let (tenant_verification_method_record, tenant_signer_b) = software_wallet
+        .get_locally_controlled_verification_method(
+            did_webplus_wallet_store::LocallyControlledVerificationMethodFilter {
+                did_o: Some(tenant_did.to_owned()),
+                version_id_o: Some(0),
+                key_purpose_o: Some(did_webplus_core::KeyPurpose::CapabilityInvocation),
+                key_id_o: None,
+                result_limit_o: None,
+            },
+        )
+        .await
+        .expect("pass");
+    let stale_jws = did_webplus_jws::JWS::async_signed(
+        tenant_verification_method_record
+            .did_key_resource_fully_qualified
+            .to_string(),
+        &mut b"stale".as_slice(),
+        did_webplus_jws::JWSPayloadPresence::Detached,
+        did_webplus_jws::JWSPayloadEncoding::Base64,
+        tenant_signer_b.as_ref(),
+    )
+    .await
+    .expect("pass");
+
+    // A DID may sign its own updates.
+    software_wallet
+        .update_did(
+            did_webplus_wallet::UpdateDIDParameters {
+                did: tenant_did,
+                change_mb_hash_function_for_self_hash_o: None,
+                mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+            },
+            Some(&http_options),
+        )
+        .await
+        .expect("pass");
+
+    // Unsigned requests, and requests signed by a rotated-out key, are rejected.  The body is the latest DID
+    // document, which parses successfully, so that the request is rejected by the authorization check.
+    let tenant_did_documents_jsonl_url =
+        tenant_did.resolution_url_for_did_documents_jsonl(Some(&http_scheme_override));
+    let did_documents_jsonl = test_util::REQWEST_CLIENT
+        .get(tenant_did_documents_jsonl_url.as_str())
+        .send()
+        .await
+        .expect("pass")
+        .error_for_status()
+        .expect("pass")
+        .text()
+        .await
+        .expect("pass");
+    let latest_did_document_jcs = did_documents_jsonl
+        .lines()
+        .last()
+        .expect("pass")
+        .to_string();
+    let response = test_util::REQWEST_CLIENT
+        .put(tenant_did_documents_jsonl_url.as_str())
+        .body(latest_did_document_jcs.clone())
+        .send()
+        .await
+        .expect("pass");
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+    let response = test_util::REQWEST_CLIENT
+        .put(tenant_did_documents_jsonl_url.as_str())
+        .header(
+            did_webplus_core::VDR_AUTHORIZATION_JWS_HEADER,
+            stale_jws.as_str(),
+        )
+        .body(latest_did_document_jcs)
+        .send()
+        .await
+        .expect("pass");
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    software_wallet
+        .deactivate_did(
+            did_webplus_wallet::DeactivateDIDParameters {
+                did: tenant_did,
+                change_mb_hash_function_for_self_hash_o: None,
+            },
+            Some(&http_options),
+        )
+        .await
+        .expect("pass");
+
+    tracing::info!("Shutting down VDRs");
+    vdr_handle.abort();
+    bootstrap_vdr_handle.abort();
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn test_software_wallet_update_rejected_by_vdr_dry_run() {
+    use did_webplus_wallet::Wallet;
+
+    let wallet_storage = did_webplus_wallet_storage_mock::WalletStorageMock::new();
+    let wallet_storage_a = Arc::new(wallet_storage);
+    use storage_traits::StorageDynT;
+    let mut transaction_b = wallet_storage_a.begin_transaction().await.expect("pass");
+    let software_wallet = did_webplus_software_wallet::SoftwareWallet::create(
+        transaction_b.as_mut(),
+        wallet_storage_a.clone(),
+        Some("fancy wallet".to_string()),
+        None,
+    )
+    .await
+    .expect("pass");
+    transaction_b.commit().await.expect("pass");
+
+    // This VDR rejects updates made within an hour of the previous one.
+    let vdr_config = did_webplus_vdr_lib::VDRConfig {
+        did_hostname: "localhost".to_string(),
+        did_port_o: Some(13095),
+        listen_port: 13095,
+        database_url: "postgres:///test_software_wallet_update_rejected_by_vdr_dry_run_vdr"
+            .to_string(),
+        database_max_connections: 10,
+        vdg_base_url_v: Vec::new(),
+        http_scheme_override: Default::default(),
+        test_authz_api_key_vo: None,
+        registrar_permissions_o: None,
+        serve_did_web: false,
+        audit_interval_seconds_o: None,
+        admission_policy_config: did_webplus_vdr_lib::AdmissionPolicyConfig {
+            min_update_interval_seconds_o: Some(3600),
+            ..Default::default()
+        },
+    };
+    let vdr_handle = did_webplus_vdr_lib::spawn_vdr(vdr_config.clone())
+        .await
+        .expect("pass");
+    test_util::wait_until_service_is_up(
+        "VDR",
+        format!("http://localhost:{}/health", vdr_config.listen_port).as_str(),
+    )
+    .await;
+
+    let http_scheme_override = did_webplus_core::HTTPSchemeOverride::new()
+        .with_override("localhost".to_string(), "http")
+        .unwrap();
+    let http_options = did_webplus_core::HTTPOptions {
+        http_headers_for: did_webplus_core::HTTPHeadersFor::new(),
+        http_scheme_override: http_scheme_override.clone(),
+    };
+    let mb_hash_function = selfhash::MBHashFunction::blake3(mbx::Base::Base64Url);
+
+    let controlled_did = software_wallet
+        .create_did(
+            did_webplus_wallet::CreateDIDParameters {
+                vdr_did_create_endpoint: "http://localhost:13095",
+                mb_hash_function_for_did: &mb_hash_function,
+                mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                vdr_authz_key_id_o: None,
+            },
+            Some(&http_options),
+        )
+        .await
+        .expect("pass");
+    let did = controlled_did.did();
+
+    // The dry-run should catch the rejection before the update is submitted.
+    let error = software_wallet
+        .update_did(
+            did_webplus_wallet::UpdateDIDParameters {
+                did: &did,
+                change_mb_hash_function_for_self_hash_o: None,
+                mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+            },
+            Some(&http_options),
+        )
+        .await
+        .expect_err("pass");
+    tracing::debug!("update_did error: {}", error);
+    assert!(
+        matches!(
+            error,
+            did_webplus_wallet::Error::DIDDocumentRejectedByVDR(_)
+        ),
+        "expected DIDDocumentRejectedByVDR, got {:?}",
+        error
+    );
+
+    // The VDR should not have the rejected update, and the wallet should still hold the current keys.
+    let did_documents_jsonl = test_util::REQWEST_CLIENT
+        .get(did.resolution_url_for_did_documents_jsonl(Some(&http_scheme_override)))
+        .send()
+        .await
+        .expect("pass")
+        .error_for_status()
+        .expect("pass")
+        .text()
+        .await
+        .expect("pass");
+    assert_eq!(did_documents_jsonl.lines().count(), 1);
+    let locally_controlled_verification_method_v = software_wallet
+        .get_locally_controlled_verification_methods(
+            &did_webplus_wallet_store::LocallyControlledVerificationMethodFilter {
+                did_o: Some(did.to_owned()),
+                version_id_o: Some(0),
+                key_purpose_o: None,
+                key_id_o: None,
+                result_limit_o: None,
+            },
+        )
+        .await
+        .expect("pass");
+    assert!(!locally_controlled_verification_method_v.is_empty());
+
+    vdr_handle.abort();
+}
+
+#[tokio::test]
+#[serial_test::serial]
+async fn test_software_wallet_with_static_hosting_dir() {
+    let static_hosting_dir = std::path::Path::new("tests/test_software_wallet_static_hosting");
+    if std::fs::exists(static_hosting_dir).expect("pass") {
+        std::fs::remove_dir_all(static_hosting_dir).expect("pass");
+    }
+
+    let wallet_storage_a = Arc::new(did_webplus_wallet_storage_mock::WalletStorageMock::new());
+    use storage_traits::StorageDynT;
+    let mut transaction_b = wallet_storage_a.begin_transaction().await.expect("pass");
+    let software_wallet = did_webplus_software_wallet::SoftwareWallet::create(
+        transaction_b.as_mut(),
+        wallet_storage_a.clone(),
+        Some("static hosting wallet".to_string()),
+        None,
+    )
+    .await
+    .expect("pass")
+    .with_static_hosting_dir(static_hosting_dir.to_path_buf());
+    transaction_b.commit().await.expect("pass");
+
+    // No VDR is running, so DID creates and updates must only go to the static-hosting directory.
+    let mb_hash_function = selfhash::MBHashFunction::blake3(mbx::Base::Base64Url);
+    use did_webplus_wallet::Wallet;
+    let controlled_did = software_wallet
+        .create_did(
+            did_webplus_wallet::CreateDIDParameters {
+                vdr_did_create_endpoint: "https://static.example.com/tenant",
+                mb_hash_function_for_did: &mb_hash_function,
+                mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                vdr_authz_key_id_o: None,
+            },
+            None,
+        )
+        .await
+        .expect("pass");
+    let did = controlled_did.did().to_owned();
+    assert_eq!(did.path_o(), Some("tenant"));
+    for _ in 0..2 {
+        software_wallet
+            .update_did(
+                did_webplus_wallet::UpdateDIDParameters {
+                    did: &did,
+                    change_mb_hash_function_for_self_hash_o: None,
+                    mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                },
+                None,
+            )
+            .await
+            .expect("pass");
+    }
+
+    // The did-documents.jsonl file is at the path that DID resolution maps the DID to, and is a valid microledger.
+    let did_documents_jsonl = std::fs::read_to_string(static_hosting_dir.join(format!(
+        "tenant/{}/did-documents.jsonl",
+        did.root_self_hash()
+    )))
+    .expect("pass");
+    let mut prev_did_document_o: Option<did_webplus_core::DIDDocument> = None;
+    for (version_id, did_document_jcs) in did_documents_jsonl.lines().enumerate() {
+        let did_document =
+            did_webplus_doc_store::parse_did_document(did_document_jcs).expect("pass");
+        assert_eq!(did_document.did, did);
+        assert_eq!(did_document.version_id, version_id as u32);
+        did_document
+            .verify_nonrecursive(prev_did_document_o.as_ref())
+            .expect("pass");
+        prev_did_document_o = Some(did_document);
+    }
+    assert_eq!(prev_did_document_o.expect("pass").version_id, 2);
+
+    // Another wallet using the same static-hosting directory picks up the DID documents from it.
+    let other_wallet_storage_a =
+        Arc::new(did_webplus_wallet_storage_mock::WalletStorageMock::new());
+    let mut transaction_b = other_wallet_storage_a
+        .begin_transaction()
+        .await
+        .expect("pass");
+    let other_software_wallet = did_webplus_software_wallet::SoftwareWallet::create(
+        transaction_b.as_mut(),
+        other_wallet_storage_a,
+        Some("other static hosting wallet".to_string()),
+        None,
+    )
+    .await
+    .expect("pass")
+    .with_static_hosting_dir(static_hosting_dir.to_path_buf());
+    transaction_b.commit().await.expect("pass");
+    other_software_wallet
+        .fetch_did(&did, None)
+        .await
+        .expect("pass");
+    // But it doesn't control the DID, so it can't update it.
+    other_software_wallet
+        .update_did(
+            did_webplus_wallet::UpdateDIDParameters {
+                did: &did,
+                change_mb_hash_function_for_self_hash_o: None,
+                mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+            },
+            None,
+        )
+        .await
+        .expect_err("pass");
 }
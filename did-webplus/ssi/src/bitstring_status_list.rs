@@ -0,0 +1,125 @@
+use crate::Result;
+use base64::Engine;
+
+/// A Bitstring Status List, in which the status of each credential that refers to the list is a single bit at
+/// that credential's status list index.  The bits are ordered starting with the most significant bit of the
+/// first byte.  See <https://www.w3.org/TR/vc-bitstring-status-list/>
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BitstringStatusList {
+    byte_v: Vec<u8>,
+}
+
+impl BitstringStatusList {
+    /// The minimum number of entries of a Bitstring Status List (which makes it 16KiB uncompressed).  This
+    /// provides "herd privacy" for the holders of credentials whose statuses are in the list.
+    pub const MIN_LENGTH: usize = 131072;
+    /// The maximum number of entries of a Bitstring Status List (which makes it 16MiB uncompressed).  This
+    /// bounds the memory used to decode a fetched status list, since GZIP can compress a long run of zeros
+    /// by a factor of about 1000.
+    pub const MAX_LENGTH: usize = 134217728;
+
+    /// Creates a status list with the given number of entries, all unset.  The length must be a multiple of 8,
+    /// at least MIN_LENGTH, and at most MAX_LENGTH.
+    pub fn new(length: usize) -> Result<Self> {
+        anyhow::ensure!(
+            length >= Self::MIN_LENGTH,
+            "Bitstring Status List length ({}) must be at least {}",
+            length,
+            Self::MIN_LENGTH
+        );
+        anyhow::ensure!(
+            length <= Self::MAX_LENGTH,
+            "Bitstring Status List length ({}) must be at most {}",
+            length,
+            Self::MAX_LENGTH
+        );
+        anyhow::ensure!(
+            length % 8 == 0,
+            "Bitstring Status List length ({}) must be a multiple of 8",
+            length
+        );
+        Ok(Self {
+            byte_v: vec![0u8; length / 8],
+        })
+    }
+    /// Returns the number of entries in this status list.
+    pub fn len(&self) -> usize {
+        self.byte_v.len() * 8
+    }
+    /// Returns true if this status list has no entries (never the case for one produced by new or decode).
+    pub fn is_empty(&self) -> bool {
+        self.byte_v.is_empty()
+    }
+    /// Returns the status bit at the given index.
+    pub fn get(&self, index: usize) -> Result<bool> {
+        self.ensure_index_in_range(index)?;
+        Ok(self.byte_v[index / 8] & (0x80 >> (index % 8)) != 0)
+    }
+    /// Sets the status bit at the given index, e.g. to revoke (true) a credential, or to lift (false) the
+    /// suspension of a credential.
+    pub fn set(&mut self, index: usize, status: bool) -> Result<()> {
+        self.ensure_index_in_range(index)?;
+        let mask = 0x80 >> (index % 8);
+        if status {
+            self.byte_v[index / 8] |= mask;
+        } else {
+            self.byte_v[index / 8] &= !mask;
+        }
+        Ok(())
+    }
+    /// Returns the "encodedList" form of this status list, i.e. the multibase base64url encoding (with "u"
+    /// prefix) of the GZIP-compressed bitstring.
+    pub fn encode(&self) -> Result<String> {
+        use std::io::Write;
+        let mut gz_encoder =
+            flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gz_encoder.write_all(self.byte_v.as_slice())?;
+        let compressed = gz_encoder.finish()?;
+        Ok(format!(
+            "u{}",
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(compressed)
+        ))
+    }
+    /// Parses the "encodedList" form of a status list (see encode).
+    pub fn decode(encoded_list: &str) -> Result<Self> {
+        use std::io::Read;
+        let base64url = encoded_list.strip_prefix('u').ok_or_else(|| {
+            anyhow::anyhow!(
+                "Bitstring Status List encodedList must be multibase base64url (\"u\" prefix)"
+            )
+        })?;
+        let compressed = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .decode(base64url)
+            .map_err(|e| anyhow::anyhow!("malformed Bitstring Status List encodedList: {}", e))?;
+        // Read at most one byte more than the maximum length, so that a longer list is detected without
+        // decompressing all of it.
+        let mut byte_v = Vec::new();
+        flate2::read::GzDecoder::new(compressed.as_slice())
+            .take((Self::MAX_LENGTH / 8 + 1) as u64)
+            .read_to_end(&mut byte_v)
+            .map_err(|e| {
+                anyhow::anyhow!("Bitstring Status List encodedList is not valid GZIP: {}", e)
+            })?;
+        anyhow::ensure!(
+            byte_v.len() * 8 >= Self::MIN_LENGTH,
+            "Bitstring Status List length ({}) must be at least {}",
+            byte_v.len() * 8,
+            Self::MIN_LENGTH
+        );
+        anyhow::ensure!(
+            byte_v.len() * 8 <= Self::MAX_LENGTH,
+            "Bitstring Status List length must be at most {}",
+            Self::MAX_LENGTH
+        );
+        Ok(Self { byte_v })
+    }
+    fn ensure_index_in_range(&self, index: usize) -> Result<()> {
+        anyhow::ensure!(
+            index < self.len(),
+            "status list index {} is out of range for a Bitstring Status List of length {}",
+            index,
+            self.len()
+        );
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 use std::sync::Arc;
 
-use crate::{DIDWebplus, Result};
+use crate::{
+    key_validity::{ensure_jwt_key_valid, jwt_kid_o, record_ssi_signature_checks},
+    DIDWebplus, Result,
+};
 use ssi_claims::jwt::ToDecodedJwt;
 
 pub async fn sign_jwt<Claims: serde::Serialize, Signer: ssi_jws::JwsSigner>(
@@ -11,11 +14,17 @@ pub async fn sign_jwt<Claims: serde::Serialize, Signer: ssi_jws::JwsSigner>(
     Ok(claims.sign(signer).await?)
 }
 
+/// Verify the JWT's signature, and if it was signed using a did:webplus key, that the key was valid at the
+/// JWT's claimed signing time (its "iat" claim, or if absent, its "nbf" claim, or if absent, the current time).
+/// Each check performed is recorded in verification_report.
 pub async fn verify_jwt(
     jwt: &str,
     did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+    verification_report: &mut verifier_resolver::VerificationReport,
 ) -> Result<ssi_jws::JwsBuf> {
-    let did_resolver = DIDWebplus { did_resolver_a };
+    let did_resolver = DIDWebplus {
+        did_resolver_a: did_resolver_a.clone(),
+    };
     // Also add the did:key resolver.
     let did_resolver = (did_resolver, ssi_dids::DIDKey);
     use ssi_dids::DIDResolver;
@@ -24,7 +33,21 @@ pub async fn verify_jwt(
     // Not sure why using the borrowed version requires static lifetime for jwt,
     // so have to use the owned version here.
     let jwt = ssi_jws::JwsBuf::new(jwt.to_owned())?;
-    jwt.verify_jwt(&params).await??;
+    let verification_r = jwt.verify_jwt(&params).await;
+    let kid_o = jwt_kid_o(jwt.as_str())?;
+    record_ssi_signature_checks(
+        kid_o.as_deref().as_slice(),
+        &verification_r,
+        verification_report,
+    );
+    verification_r??;
+    ensure_jwt_key_valid(
+        jwt.as_str(),
+        None,
+        did_resolver_a.as_ref(),
+        verification_report,
+    )
+    .await?;
     Ok(jwt)
 }
 
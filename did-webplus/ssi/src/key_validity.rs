@@ -0,0 +1,250 @@
+use crate::Result;
+use verifier_resolver::{VerificationCheckKind, VerificationOutcome, VerificationReport};
+
+/// Ensure that the given key is listed under the given verification relationship (if specified) and was valid
+/// at the given signing time (see did_webplus_resolver::key_validity_at).  Only did:webplus keys have a key-usage
+/// history, so keys from other DID methods (e.g. did:key) are always considered valid here.  If there is no
+/// claimed signing time, then the current time is used, i.e. the key must currently be valid.  The checks
+/// performed are recorded in verification_report.
+pub(crate) async fn ensure_key_valid_at(
+    kid: &str,
+    verification_relationship_o: Option<verifier_resolver::VerificationRelationship>,
+    signing_time_o: Option<time::OffsetDateTime>,
+    did_resolver: &dyn did_webplus_resolver::DIDResolver,
+    verification_report: &mut VerificationReport,
+) -> Result<()> {
+    if !kid.starts_with("did:webplus:") {
+        return Ok(());
+    }
+    let key_id = did_webplus_core::DIDKeyResourceFullyQualifiedStr::new_ref(kid).map_err(|e| {
+        anyhow::anyhow!(
+            "did:webplus key ID {:?} must be fully qualified: {}",
+            kid,
+            e
+        )
+    })?;
+    if let Some(verification_relationship) = verification_relationship_o {
+        verification_report
+            .resolve_for_purpose(kid, verification_relationship, did_resolver)
+            .await?;
+    }
+    let signing_time = signing_time_o.unwrap_or_else(time::OffsetDateTime::now_utc);
+    let key_validity_r = did_webplus_resolver::key_validity_at(did_resolver, key_id, signing_time)
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|key_validity| {
+            tracing::debug!(
+                "key_validity for {} at {}: {:?}",
+                kid,
+                signing_time,
+                key_validity
+            );
+            anyhow::ensure!(
+                key_validity.is_valid(),
+                "key {} was not valid at signing time {}: {}",
+                kid,
+                signing_time,
+                key_validity
+            );
+            Ok(())
+        });
+    verification_report.record_result(
+        VerificationCheckKind::KeyValidityWindow,
+        Some(kid),
+        Some(key_id.without_fragment().as_str()),
+        key_validity_r,
+    )
+}
+
+/// Ensure that the key that signed the given JWT is listed under the given verification relationship (if
+/// specified) and was valid at the JWT's claimed signing time.  The JWT's signature is not verified here; that
+/// must be done separately.  The checks performed are recorded in verification_report.
+pub(crate) async fn ensure_jwt_key_valid(
+    jwt: &str,
+    verification_relationship_o: Option<verifier_resolver::VerificationRelationship>,
+    did_resolver: &dyn did_webplus_resolver::DIDResolver,
+    verification_report: &mut VerificationReport,
+) -> Result<()> {
+    use base64::Engine;
+
+    let kid = match jwt_kid_o(jwt)? {
+        Some(kid) => kid,
+        None => {
+            // Without a kid, there's no did:webplus key whose validity could be checked.
+            return Ok(());
+        }
+    };
+    let payload_base64 = jwt
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| anyhow::anyhow!("malformed JWT"))?;
+    let claims: serde_json::Value = serde_json::from_slice(
+        &base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(payload_base64)?,
+    )?;
+    let signing_time_o = jwt_signing_time_o(&claims)?;
+    ensure_key_valid_at(
+        kid.as_str(),
+        verification_relationship_o,
+        signing_time_o,
+        did_resolver,
+        verification_report,
+    )
+    .await
+}
+
+/// Returns the "kid" field of the header of the given JWT, if present.
+pub(crate) fn jwt_kid_o(jwt: &str) -> Result<Option<String>> {
+    use base64::Engine;
+
+    let header_base64 = jwt
+        .split('.')
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("malformed JWT"))?;
+    let header: serde_json::Value = serde_json::from_slice(
+        &base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(header_base64)?,
+    )?;
+    Ok(header
+        .get("kid")
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_owned))
+}
+
+/// Ensure that the key of each proof of the given Data Integrity-secured document (in JSON form) is listed
+/// under the given verification relationship and was valid at that proof's "created" time.  The proofs are
+/// not verified here; that must be done separately.  The checks performed are recorded in verification_report.
+pub(crate) async fn ensure_ldp_keys_valid(
+    document: &serde_json::Value,
+    verification_relationship: verifier_resolver::VerificationRelationship,
+    did_resolver: &dyn did_webplus_resolver::DIDResolver,
+    verification_report: &mut VerificationReport,
+) -> Result<()> {
+    // If a proof has no "created" time, fall back to the document's own issuance time, if any.
+    let fallback_signing_time_o = claimed_time_o(document, &["issuanceDate", "validFrom"])?;
+    for proof in ldp_proof_v(document)? {
+        let kid = ldp_proof_kid(proof)?;
+        let signing_time_o = claimed_time_o(proof, &["created"])?.or(fallback_signing_time_o);
+        ensure_key_valid_at(
+            kid,
+            Some(verification_relationship),
+            signing_time_o,
+            did_resolver,
+            verification_report,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Returns the key ID of each proof of the given Data Integrity-secured document (in JSON form).
+pub(crate) fn ldp_proof_kid_v(document: &serde_json::Value) -> Result<Vec<&str>> {
+    ldp_proof_v(document)?
+        .into_iter()
+        .map(ldp_proof_kid)
+        .collect()
+}
+
+fn ldp_proof_v(document: &serde_json::Value) -> Result<Vec<&serde_json::Value>> {
+    match document.get("proof") {
+        Some(serde_json::Value::Array(proof_v)) => Ok(proof_v.iter().collect()),
+        Some(proof) => Ok(vec![proof]),
+        None => anyhow::bail!("document has no proof"),
+    }
+}
+
+fn ldp_proof_kid(proof: &serde_json::Value) -> Result<&str> {
+    match proof.get("verificationMethod") {
+        Some(serde_json::Value::String(kid)) => Ok(kid.as_str()),
+        Some(verification_method) => verification_method
+            .get("id")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("malformed proof verificationMethod")),
+        None => anyhow::bail!("proof has no verificationMethod"),
+    }
+}
+
+/// Records a signature check for each of the given key IDs (or a single one if there are none), whose outcome
+/// is that of the given ssi verification.  ssi verifies all of a document's proofs at once, so they all share
+/// the same outcome.
+pub(crate) fn record_ssi_signature_checks<E: std::fmt::Display>(
+    kid_v: &[&str],
+    verification_r: &std::result::Result<ssi_claims::Verification, E>,
+    verification_report: &mut VerificationReport,
+) {
+    let outcome = match verification_r {
+        Ok(Ok(())) => VerificationOutcome::Passed,
+        Ok(Err(e)) => VerificationOutcome::Failed {
+            reason: e.to_string(),
+        },
+        Err(e) => VerificationOutcome::Failed {
+            reason: e.to_string(),
+        },
+    };
+    if kid_v.is_empty() {
+        verification_report.record(VerificationCheckKind::Signature, None, None, outcome);
+    } else {
+        for kid in kid_v {
+            verification_report.record(
+                VerificationCheckKind::Signature,
+                Some(kid),
+                verifier_resolver::did_document_version_of(kid),
+                outcome.clone(),
+            );
+        }
+    }
+}
+
+/// Returns the claimed signing time of a JWT.  This is the "iat" claim if present, otherwise the "nbf"
+/// claim, otherwise the issuance date of the VC or VP carried in the "vc" or "vp" claim, if any.
+pub(crate) fn jwt_signing_time_o(
+    claims: &serde_json::Value,
+) -> Result<Option<time::OffsetDateTime>> {
+    for claim_name in ["iat", "nbf"] {
+        if let Some(numeric_date) = claims.get(claim_name) {
+            return Ok(Some(numeric_date_to_offset_date_time(numeric_date)?));
+        }
+    }
+    for claim_name in ["vc", "vp"] {
+        if let Some(claim) = claims.get(claim_name) {
+            if let Some(signing_time) = claimed_time_o(claim, &["issuanceDate", "validFrom"])? {
+                return Ok(Some(signing_time));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the first of the given RFC 3339 datetime-valued fields that is present in the given object.
+pub(crate) fn claimed_time_o(
+    object: &serde_json::Value,
+    field_name_v: &[&str],
+) -> Result<Option<time::OffsetDateTime>> {
+    for field_name in field_name_v {
+        if let Some(value) = object.get(field_name) {
+            let datetime_str = value
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("{:?} must be a string", field_name))?;
+            let datetime = time::OffsetDateTime::parse(
+                datetime_str,
+                &time::format_description::well_known::Rfc3339,
+            )
+            .map_err(|e| anyhow::anyhow!("malformed {:?}: {}", field_name, e))?;
+            return Ok(Some(datetime));
+        }
+    }
+    Ok(None)
+}
+
+/// Converts a JWT NumericDate (seconds since the Unix epoch, possibly non-integral) to OffsetDateTime.
+fn numeric_date_to_offset_date_time(
+    numeric_date: &serde_json::Value,
+) -> Result<time::OffsetDateTime> {
+    if let Some(seconds) = numeric_date.as_i64() {
+        Ok(time::OffsetDateTime::from_unix_timestamp(seconds)?)
+    } else if let Some(seconds) = numeric_date.as_f64() {
+        Ok(time::OffsetDateTime::from_unix_timestamp_nanos(
+            (seconds * 1.0e9) as i128,
+        )?)
+    } else {
+        anyhow::bail!("malformed NumericDate: {}", numeric_date);
+    }
+}
@@ -1,14 +1,48 @@
+mod bitstring_status_list;
 mod jwt;
+mod key_validity;
+mod sd_jwt;
+mod sd_jwt_disclosure;
+mod sd_jwt_vc;
+mod sd_jwt_verification_options;
+mod status_list;
+mod status_list_fetcher;
+mod status_list_index_allocator;
+mod status_purpose;
+mod trust_policy;
 mod vc;
+mod vc_v2;
 mod vp;
+mod vp_v2;
 
 pub use crate::{
+    bitstring_status_list::BitstringStatusList,
     jwt::{decode_jwt, sign_jwt, verify_jwt},
+    sd_jwt::{create_sd_jwt_presentation, issue_sd_jwt, verify_sd_jwt, SDJWT},
+    sd_jwt_disclosure::SDJWTDisclosure,
+    sd_jwt_vc::{issue_sd_jwt_vc, verify_sd_jwt_vc, SD_JWT_VC_TYP},
+    sd_jwt_verification_options::SDJWTVerificationOptions,
+    status_list::{
+        issue_status_list_credential_jwt, new_bitstring_status_list_entry,
+        new_unsigned_status_list_credential, parse_status_list_credential,
+        BITSTRING_STATUS_LIST_CREDENTIAL_TYPE, BITSTRING_STATUS_LIST_ENTRY_TYPE,
+    },
+    status_list_fetcher::{StatusListFetcher, StatusListFetcherHTTP, StatusListFetcherInMemory},
+    status_list_index_allocator::StatusListIndexAllocator,
+    status_purpose::StatusPurpose,
     vc::{issue_vc_jwt, issue_vc_ldp, new_unsigned_credential, verify_vc_jwt, verify_vc_ldp},
+    vc_v2::{
+        issue_vc_jwt_v2, issue_vc_ldp_v2, new_enveloped_credential_v2, new_unsigned_credential_v2,
+        verify_vc_jwt_v2, verify_vc_ldp_v2, CREDENTIALS_V2_CONTEXT, VC_JWT_V2_TYP,
+    },
     vp::{
         issue_vp_jwt, issue_vp_ldp, new_unsigned_presentation, verify_vp_jwt, verify_vp_ldp,
         IssueVPParameters,
     },
+    vp_v2::{
+        issue_vp_jwt_v2, issue_vp_ldp_v2, new_unsigned_presentation_v2, verify_vp_jwt_v2,
+        verify_vp_ldp_v2, VP_JWT_V2_TYP,
+    },
 };
 pub use anyhow::{Error, Result};
 
@@ -19,6 +53,42 @@ pub struct DIDWebplus {
     pub did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
 }
 
+impl DIDWebplus {
+    async fn resolve_did_document_string(
+        &self,
+        did: &did_webplus_core::DIDStr,
+        self_hash_o: Option<&mbx::MBHashStr>,
+        version_id_o: Option<u32>,
+        version_time_o: Option<time::OffsetDateTime>,
+        did_resolution_options: did_webplus_core::DIDResolutionOptions,
+    ) -> did_webplus_resolver::Result<(
+        String,
+        did_webplus_core::DIDDocumentMetadata,
+        did_webplus_core::DIDResolutionMetadata,
+    )> {
+        if let Some(version_time) = version_time_o {
+            return did_webplus_resolver::resolve_did_document_string_at_version_time(
+                self.did_resolver_a.as_ref(),
+                did,
+                version_time,
+                did_resolution_options,
+            )
+            .await;
+        }
+        let did_query = match (self_hash_o, version_id_o) {
+            (Some(self_hash), Some(version_id)) => {
+                did.with_queries(self_hash, version_id).to_string()
+            }
+            (Some(self_hash), None) => did.with_query_self_hash(self_hash).to_string(),
+            (None, Some(version_id)) => did.with_query_version_id(version_id).to_string(),
+            (None, None) => did.to_string(),
+        };
+        self.did_resolver_a
+            .resolve_did_document_string(did_query.as_str(), did_resolution_options)
+            .await
+    }
+}
+
 impl ssi_dids::DIDMethod for DIDWebplus {
     const DID_METHOD_NAME: &'static str = "webplus";
 }
@@ -52,30 +122,93 @@ impl ssi_dids::DIDMethodResolver for DIDWebplus {
         } else {
             None
         };
+        let version_time_o =
+            if let Some(version_time_str) = options.parameters.version_time.as_deref() {
+                let version_time = time::OffsetDateTime::parse(
+                    version_time_str,
+                    &time::format_description::well_known::Rfc3339,
+                )
+                .map_err(|e| {
+                    ssi_dids::resolution::Error::InvalidMethodSpecificId(format!(
+                        "malformed versionTime {:?}: {}",
+                        version_time_str, e
+                    ))
+                })?;
+                Some(version_time)
+            } else {
+                None
+            };
+        // did:webplus-specific resolution option; see DIDResolutionOptions::local_resolution_only.
+        let local_resolution_only = match options.parameters.additional.get("localResolutionOnly") {
+            Some(ssi_dids::resolution::Parameter::String(local_resolution_only_str)) => {
+                bool::from_str(local_resolution_only_str).map_err(|e| {
+                    ssi_dids::resolution::Error::InvalidMethodSpecificId(format!(
+                        "malformed localResolutionOnly {:?}: {}",
+                        local_resolution_only_str, e
+                    ))
+                })?
+            }
+            Some(_) => {
+                return Err(ssi_dids::resolution::Error::InvalidMethodSpecificId(
+                    "localResolutionOnly must be \"true\" or \"false\"".to_string(),
+                ));
+            }
+            None => false,
+        };
         let did = did_webplus_core::DID::try_from(format!("did:webplus:{}", method_specific_id))
             .map_err(|e| ssi_dids::resolution::Error::InvalidMethodSpecificId(e.to_string()))?;
         tracing::debug!("DIDWebplus::resolve_method_representation; did: {}", did);
-        let did_query = match (self_hash_o, version_id_o) {
-            (Some(self_hash), Some(version_id)) => {
-                did.with_queries(&self_hash, version_id).to_string()
-            }
-            (Some(self_hash), None) => did.with_query_self_hash(&self_hash).to_string(),
-            (None, Some(version_id)) => did.with_query_version_id(version_id).to_string(),
-            (None, None) => did.to_string(),
-        };
-        let (did_document_jcs, did_document_metadata, did_resolution_metadata) = self
-            .did_resolver_a
+        if version_time_o.is_some() && (self_hash_o.is_some() || version_id_o.is_some()) {
+            return Err(ssi_dids::resolution::Error::InvalidMethodSpecificId(
+                "versionTime can't be combined with selfHash or versionId".to_string(),
+            ));
+        }
+        // Not every DIDResolver can determine all DID document metadata (e.g. DIDResolverBundle without a
+        // latest attestation can't determine deactivation), and the DID spec allows metadata to be omitted, so
+        // if only the metadata is unavailable, fall back to resolving without metadata.  Any other error (e.g.
+        // the DID document not being found, or the VDR being unreachable) is returned as-is.
+        let resolution_r = match self
             .resolve_did_document_string(
-                did_query.as_str(),
-                // TEMP HACK -- no metadata -- TODO Implement, also TODO: make local-only resolution specifiable.
-                did_webplus_core::DIDResolutionOptions::no_metadata(false),
+                &did,
+                self_hash_o,
+                version_id_o,
+                version_time_o,
+                did_webplus_core::DIDResolutionOptions::all_metadata(local_resolution_only),
             )
             .await
-            .map_err(|e| ssi_dids::resolution::Error::Internal(e.to_string()))?;
+        {
+            Ok(resolution) => Ok(resolution),
+            Err(e) if e.is_did_document_metadata_unavailable() => {
+                tracing::debug!(
+                    "DIDWebplus::resolve_method_representation; resolution with metadata failed ({}); retrying without metadata",
+                    e
+                );
+                self.resolve_did_document_string(
+                    &did,
+                    self_hash_o,
+                    version_id_o,
+                    version_time_o,
+                    did_webplus_core::DIDResolutionOptions::no_metadata(local_resolution_only),
+                )
+                .await
+            }
+            Err(e) => Err(e),
+        };
+        let (did_document_jcs, did_document_metadata, did_resolution_metadata) =
+            resolution_r.map_err(|e| ssi_dids::resolution::Error::Internal(e.to_string()))?;
         tracing::debug!("DIDWebplus::resolve_method_representation; resolution successful; did_document_jcs: {}\n\tdid_document_metadata: {:?}\n\tdid_resolution_metadata: {:?}", did_document_jcs, did_document_metadata, did_resolution_metadata);
+        // ssi's DID document metadata type only models some of the standard DID document metadata properties
+        // (e.g. "deactivated"), so go through its serde form, which keeps whichever of them it supports.
+        // did_webplus_core::DIDDocumentMetadata serializes using the standard property names ("created",
+        // "updated", "nextUpdate", "nextVersionId", "versionId", "deactivated").
+        let document_metadata = serde_json::from_value(
+            serde_json::to_value(&did_document_metadata)
+                .map_err(|e| ssi_dids::resolution::Error::Internal(e.to_string()))?,
+        )
+        .map_err(|e| ssi_dids::resolution::Error::Internal(e.to_string()))?;
         Ok(ssi_dids::resolution::Output {
             document: did_document_jcs.as_bytes().to_vec(),
-            document_metadata: Default::default(),
+            document_metadata,
             metadata: ssi_dids::resolution::Metadata {
                 content_type: Some(ssi_dids::document::representation::MediaType::Json.to_string()),
             },
@@ -0,0 +1,798 @@
+use std::sync::Arc;
+
+use crate::{
+    Result, SDJWTDisclosure, SDJWTVerificationOptions, key_validity::ensure_key_valid_at,
+    trust_policy::ensure_issuer_trusted,
+};
+
+/// Top-level claims that are needed to process or validate the issuer-signed JWT, and therefore can't be
+/// selectively disclosable.
+const NON_SELECTIVELY_DISCLOSABLE_CLAIM_NAME_V: &[&str] = &[
+    "iss", "iat", "nbf", "exp", "cnf", "vct", "status", "_sd_alg",
+];
+
+/// An SD-JWT (Selective Disclosure for JWTs), i.e. an issuer-signed JWT whose selectively disclosable claims
+/// are replaced by digests, along with the Disclosures of (some of) those claims, and optionally a Key Binding
+/// JWT signed by the holder.  Its serialization is `<issuer-signed JWT>~<Disclosure 1>~...~<Disclosure N>~<KB-JWT>`,
+/// where the KB-JWT is empty if not present.
+/// See <https://datatracker.ietf.org/doc/html/draft-ietf-oauth-selective-disclosure-jwt>
+#[derive(Clone, Debug)]
+pub struct SDJWT {
+    issuer_jwt: did_webplus_jws::JWT,
+    disclosure_v: Vec<SDJWTDisclosure>,
+    key_binding_jwt_o: Option<did_webplus_jws::JWT>,
+}
+
+impl SDJWT {
+    /// The "typ" header value used for a plain SD-JWT (i.e. media type application/sd-jwt).
+    pub const TYP: &'static str = "sd-jwt";
+    /// The "typ" header value of a Key Binding JWT.
+    pub const KEY_BINDING_JWT_TYP: &'static str = "kb+jwt";
+    /// The only supported value of the "_sd_alg" claim, which is also the default if it's absent.
+    pub const SD_ALG: &'static str = "sha-256";
+
+    pub fn issuer_jwt(&self) -> &did_webplus_jws::JWT {
+        &self.issuer_jwt
+    }
+    pub fn disclosure_v(&self) -> &[SDJWTDisclosure] {
+        self.disclosure_v.as_slice()
+    }
+    pub fn key_binding_jwt_o(&self) -> Option<&did_webplus_jws::JWT> {
+        self.key_binding_jwt_o.as_ref()
+    }
+    /// Returns the "typ" header field of the issuer-signed JWT, if present.
+    pub fn typ_o(&self) -> Option<&str> {
+        self.issuer_jwt.jws().header().typ.as_deref()
+    }
+    /// Returns the DID of the holder that this SD-JWT is bound to, i.e. the "kid" member of the "cnf" claim of
+    /// the issuer-signed JWT (with any query and fragment removed), if present.
+    pub fn holder_did_o(&self) -> Result<Option<&str>> {
+        let cnf = match self.issuer_jwt.claims().additional_claim_m.get("cnf") {
+            Some(cnf) => cnf,
+            None => return Ok(None),
+        };
+        let kid = cnf
+            .get("kid")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| {
+                anyhow::anyhow!("SD-JWT \"cnf\" claim must have a string \"kid\" member")
+            })?;
+        Ok(Some(kid.split(['?', '#']).next().unwrap()))
+    }
+    /// Returns the serialization of this SD-JWT without its Key Binding JWT, i.e. ending with '~'.  This is
+    /// what the "sd_hash" claim of the Key Binding JWT is computed over.
+    pub fn without_key_binding_string(&self) -> String {
+        let mut s = self.issuer_jwt.as_str().to_string();
+        s.push('~');
+        for disclosure in self.disclosure_v.iter() {
+            s.push_str(disclosure.as_str());
+            s.push('~');
+        }
+        s
+    }
+    /// Returns the base64url-encoded SHA-256 digest of the serialization of this SD-JWT without its Key
+    /// Binding JWT, which is the expected value of the "sd_hash" claim of the Key Binding JWT.
+    pub fn sd_hash(&self) -> String {
+        use base64::Engine;
+        use sha2::Digest;
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(sha2::Sha256::digest(
+            self.without_key_binding_string().as_bytes(),
+        ))
+    }
+    /// Returns the claims of the issuer-signed JWT with the included Disclosures substituted in, and the
+    /// SD-JWT-specific "_sd" and "_sd_alg" claims (and any undisclosed array elements) removed.  This checks
+    /// that each Disclosure is referenced exactly once, but does NOT verify any signatures; use verify_sd_jwt
+    /// for that.
+    pub fn processed_claims(&self) -> Result<serde_json::Value> {
+        Ok(self.process_disclosures()?.0)
+    }
+    /// Returns the processed claims (see SDJWT::processed_claims), along with the JSON pointer (into the
+    /// processed claims) of each Disclosure.
+    fn process_disclosures(&self) -> Result<(serde_json::Value, Vec<String>)> {
+        match self.issuer_jwt.claims().additional_claim_m.get("_sd_alg") {
+            None => {}
+            Some(serde_json::Value::String(sd_alg)) if sd_alg == Self::SD_ALG => {}
+            Some(sd_alg) => anyhow::bail!("unsupported SD-JWT \"_sd_alg\" {}", sd_alg),
+        }
+        let mut disclosure_processor = DisclosureProcessor::new(self.disclosure_v.as_slice())?;
+        let mut claims = disclosure_processor
+            .process_value(&serde_json::to_value(self.issuer_jwt.claims())?, "")?;
+        claims.as_object_mut().unwrap().remove("_sd_alg");
+        let disclosure_pointer_v = disclosure_processor.into_disclosure_pointer_v()?;
+        Ok((claims, disclosure_pointer_v))
+    }
+}
+
+impl std::fmt::Display for SDJWT {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.without_key_binding_string().as_str())?;
+        if let Some(key_binding_jwt) = self.key_binding_jwt_o.as_ref() {
+            f.write_str(key_binding_jwt.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for SDJWT {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let part_v = s.trim().split('~').collect::<Vec<_>>();
+        anyhow::ensure!(
+            part_v.len() >= 2,
+            "SD-JWT must be of the form <issuer-signed JWT>~<Disclosure>~...~<optional Key Binding JWT>"
+        );
+        let issuer_jwt = did_webplus_jws::JWT::from_str(part_v[0])?;
+        let disclosure_v = part_v[1..part_v.len() - 1]
+            .iter()
+            .map(|disclosure_str| SDJWTDisclosure::from_str(disclosure_str))
+            .collect::<Result<Vec<_>>>()?;
+        let key_binding_jwt_str = part_v[part_v.len() - 1];
+        let key_binding_jwt_o = if key_binding_jwt_str.is_empty() {
+            None
+        } else {
+            Some(did_webplus_jws::JWT::from_str(key_binding_jwt_str)?)
+        };
+        Ok(Self {
+            issuer_jwt,
+            disclosure_v,
+            key_binding_jwt_o,
+        })
+    }
+}
+
+/// Issue an SD-JWT whose claims are the given claims (a JSON object), where the claims at the given JSON
+/// pointers (e.g. "/address/street_address" or "/nationalities/1") are made selectively disclosable.  Nested
+/// selectively disclosable claims are supported, e.g. both "/address" and "/address/street_address".  If
+/// holder_did_o is specified, then the SD-JWT is bound to that DID via the "cnf" claim, so that presentations
+/// of it must carry a Key Binding JWT signed by one of that DID's authentication keys.  The "iss" claim is set
+/// to the DID of the signer (which must be an assertionMethod key), and "iat" is set to the current time if absent.
+pub async fn issue_sd_jwt<W: did_webplus_wallet::Wallet>(
+    claims: serde_json::Value,
+    selectively_disclosable_pointer_v: &[&str],
+    holder_did_o: Option<&did_webplus_core::DIDStr>,
+    wallet_based_signer: &did_webplus_wallet::WalletBasedSigner<W>,
+) -> Result<SDJWT> {
+    issue_sd_jwt_with_typ(
+        SDJWT::TYP,
+        claims,
+        selectively_disclosable_pointer_v,
+        holder_did_o,
+        wallet_based_signer,
+    )
+    .await
+}
+
+pub(crate) async fn issue_sd_jwt_with_typ<W: did_webplus_wallet::Wallet>(
+    typ: &str,
+    mut claims: serde_json::Value,
+    selectively_disclosable_pointer_v: &[&str],
+    holder_did_o: Option<&did_webplus_core::DIDStr>,
+    wallet_based_signer: &did_webplus_wallet::WalletBasedSigner<W>,
+) -> Result<SDJWT> {
+    anyhow::ensure!(
+        wallet_based_signer.key_purpose() == did_webplus_core::KeyPurpose::AssertionMethod,
+        "SD-JWT must be issued using an assertionMethod key, but got a {} key",
+        wallet_based_signer.key_purpose()
+    );
+    anyhow::ensure!(
+        claims.is_object(),
+        "SD-JWT claims must be a serde_json::Value::Object"
+    );
+    {
+        let claim_m = claims.as_object_mut().unwrap();
+        anyhow::ensure!(
+            !claim_m.contains_key("_sd_alg"),
+            "\"_sd_alg\" claim must not be set before issuance"
+        );
+        if let Some(holder_did) = holder_did_o {
+            anyhow::ensure!(
+                !claim_m.contains_key("cnf"),
+                "\"cnf\" claim must not be set if holder_did_o is specified"
+            );
+            claim_m.insert(
+                "cnf".to_string(),
+                serde_json::json!({ "kid": holder_did.as_str() }),
+            );
+        }
+        if !claim_m.contains_key("iat") {
+            claim_m.insert(
+                "iat".to_string(),
+                serde_json::Value::from(time::OffsetDateTime::now_utc().unix_timestamp()),
+            );
+        }
+    }
+
+    let disclosure_v = conceal_claims(&mut claims, selectively_disclosable_pointer_v)?;
+    claims.as_object_mut().unwrap().insert(
+        "_sd_alg".to_string(),
+        serde_json::Value::String(SDJWT::SD_ALG.to_string()),
+    );
+
+    let issuer_jwt = did_webplus_jws::JWT::async_signed_with_typ(
+        Some(typ.to_string()),
+        serde_json::from_value(claims)?,
+        wallet_based_signer.key_fully_qualified().to_string(),
+        wallet_based_signer,
+    )
+    .await?;
+    Ok(SDJWT {
+        issuer_jwt,
+        disclosure_v,
+        key_binding_jwt_o: None,
+    })
+}
+
+/// Create a presentation of the given SD-JWT (as received from its issuer), which discloses only the claims at
+/// the given JSON pointers into its fully-disclosed claims (see SDJWT::processed_claims).  Disclosing a claim
+/// also discloses the claims nested within it, as well as whichever enclosing claims are needed to reach it.
+/// Claims that aren't selectively disclosable are always disclosed.  The presentation carries a Key Binding JWT
+/// for the given audience and nonce, which must be signed using an authentication key of the DID that the
+/// SD-JWT is bound to.
+pub async fn create_sd_jwt_presentation<W: did_webplus_wallet::Wallet>(
+    sd_jwt: &SDJWT,
+    disclosed_pointer_v: &[&str],
+    audience: &str,
+    nonce: &str,
+    wallet_based_signer: &did_webplus_wallet::WalletBasedSigner<W>,
+) -> Result<SDJWT> {
+    anyhow::ensure!(
+        wallet_based_signer.key_purpose() == did_webplus_core::KeyPurpose::Authentication,
+        "SD-JWT Key Binding JWT must be signed using an authentication key, but got a {} key",
+        wallet_based_signer.key_purpose()
+    );
+    let holder_did = sd_jwt.holder_did_o()?.ok_or_else(|| {
+        anyhow::anyhow!("SD-JWT is not bound to a holder (it has no \"cnf\" claim)")
+    })?;
+    anyhow::ensure!(
+        wallet_based_signer.key_fully_qualified().did().as_str() == holder_did,
+        "SD-JWT is bound to holder {}, but the Key Binding JWT signer is {}",
+        holder_did,
+        wallet_based_signer.key_fully_qualified().did()
+    );
+
+    let (claims, disclosure_pointer_v) = sd_jwt.process_disclosures()?;
+    for disclosed_pointer in disclosed_pointer_v {
+        anyhow::ensure!(
+            claims.pointer(disclosed_pointer).is_some(),
+            "SD-JWT has no claim at JSON pointer {:?}",
+            disclosed_pointer
+        );
+    }
+    let disclosure_v = sd_jwt
+        .disclosure_v
+        .iter()
+        .zip(disclosure_pointer_v.iter())
+        .filter(|(_, disclosure_pointer)| {
+            disclosed_pointer_v.iter().any(|disclosed_pointer| {
+                pointer_is_prefix_of(disclosure_pointer, disclosed_pointer)
+                    || pointer_is_prefix_of(disclosed_pointer, disclosure_pointer)
+            })
+        })
+        .map(|(disclosure, _)| disclosure.clone())
+        .collect::<Vec<_>>();
+    let mut presentation = SDJWT {
+        issuer_jwt: sd_jwt.issuer_jwt.clone(),
+        disclosure_v,
+        key_binding_jwt_o: None,
+    };
+
+    let mut key_binding_claims = did_webplus_jws::JWTClaims {
+        aud: Some(audience.to_string().into()),
+        iat: Some(time::OffsetDateTime::now_utc().unix_timestamp()),
+        ..Default::default()
+    };
+    key_binding_claims.additional_claim_m.insert(
+        "nonce".to_string(),
+        serde_json::Value::String(nonce.to_string()),
+    );
+    key_binding_claims.additional_claim_m.insert(
+        "sd_hash".to_string(),
+        serde_json::Value::String(presentation.sd_hash()),
+    );
+    // The Key Binding JWT has no "iss" claim, so it's signed as a plain JWS rather than via JWT::async_signed.
+    let payload = serde_json::to_vec(&key_binding_claims)?;
+    let key_binding_jws = did_webplus_jws::JWS::async_signed_with_typ(
+        Some(SDJWT::KEY_BINDING_JWT_TYP.to_string()),
+        wallet_based_signer.key_fully_qualified().to_string(),
+        &mut payload.as_slice(),
+        did_webplus_jws::JWSPayloadPresence::Attached,
+        did_webplus_jws::JWSPayloadEncoding::Base64,
+        wallet_based_signer,
+    )
+    .await?;
+    presentation.key_binding_jwt_o = Some(did_webplus_jws::JWT::try_from(key_binding_jws)?);
+    Ok(presentation)
+}
+
+/// Verify an SD-JWT presentation, returning its processed claims (see SDJWT::processed_claims).  This checks:
+/// - the signature of the issuer-signed JWT, whose key must be an assertionMethod key of the issuer DID that
+///   was valid at the "iat" time, and whose claims must be valid (see did_webplus_jws::JWT::validate_claims),
+/// - that each Disclosure is referenced exactly once by the issuer-signed JWT,
+/// - the Key Binding JWT (if present, or if required): its signature, whose key must be an authentication key
+///   of the DID that the SD-JWT is bound to that was valid at its "iat" time, which must be no older than
+///   key_binding_max_age_seconds, and its "aud", "nonce", and "sd_hash" claims.  If key binding is required,
+///   then the expected audience and nonce must be specified.
+///
+/// If verification_options specifies a trust policy, then this also checks that it trusts the issuer.  Each
+/// check performed is recorded in verification_report.  Note that this does not do any revocation status
+/// checking, or credential-type-specific verification.
+pub async fn verify_sd_jwt(
+    sd_jwt: &SDJWT,
+    did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+    verification_options: &SDJWTVerificationOptions,
+    verification_report: &mut verifier_resolver::VerificationReport,
+) -> Result<serde_json::Value> {
+    use verifier_resolver::VerificationCheckKind;
+
+    let now = verification_options
+        .now_o
+        .unwrap_or_else(|| time::OffsetDateTime::now_utc().unix_timestamp());
+    let clock_skew_seconds = i64::try_from(verification_options.clock_skew_seconds)
+        .map_err(|_| anyhow::anyhow!("SD-JWT clock skew is too large"))?;
+    let key_binding_max_age_seconds =
+        i64::try_from(verification_options.key_binding_max_age_seconds)
+            .map_err(|_| anyhow::anyhow!("SD-JWT Key Binding JWT max age is too large"))?;
+    if verification_options.require_key_binding {
+        // Without these, a Key Binding JWT presented to some other verifier could be replayed.
+        anyhow::ensure!(
+            verification_options.expected_audience_o.is_some(),
+            "SD-JWT key binding is required, so the expected audience must be specified"
+        );
+        anyhow::ensure!(
+            verification_options.expected_nonce_o.is_some(),
+            "SD-JWT key binding is required, so the expected nonce must be specified"
+        );
+    }
+
+    // Verify the issuer-signed JWT.
+    let issuer_jwt = sd_jwt.issuer_jwt();
+    let issuer_kid = issuer_jwt.jws().header().kid.as_str();
+    let issuer_verifier_b = verification_report
+        .resolve_for_purpose(
+            issuer_kid,
+            verifier_resolver::VerificationRelationship::AssertionMethod,
+            did_resolver_a.as_ref(),
+        )
+        .await?;
+    verification_report.record_result(
+        VerificationCheckKind::Signature,
+        Some(issuer_kid),
+        verification_report
+            .resolved_did_document_version(issuer_kid)
+            .as_deref(),
+        issuer_jwt.jws().verify(issuer_verifier_b.as_ref(), None),
+    )?;
+    verification_report.record_result(
+        VerificationCheckKind::Claims,
+        None,
+        None,
+        issuer_jwt.validate_claims(&did_webplus_jws::JWTValidationOptions {
+            clock_skew_seconds: verification_options.clock_skew_seconds,
+            expected_audience_o: None,
+            expected_issuer_o: verification_options.expected_issuer_o.clone(),
+            require_exp: false,
+            now_o: Some(now),
+        }),
+    )?;
+    ensure_key_valid_at(
+        issuer_kid,
+        None,
+        unix_timestamp_o_to_offset_date_time_o(issuer_jwt.claims().iat)?,
+        did_resolver_a.as_ref(),
+        verification_report,
+    )
+    .await?;
+    if let Some(trust_policy) = verification_options.trust_policy_o.as_ref() {
+        let vct_o = issuer_jwt
+            .claims()
+            .additional_claim_m
+            .get("vct")
+            .and_then(serde_json::Value::as_str);
+        ensure_issuer_trusted(
+            &[issuer_kid],
+            vct_o.as_slice(),
+            trust_policy,
+            did_resolver_a.as_ref(),
+            verification_report,
+        )
+        .await?;
+    }
+
+    // Check the Disclosures.
+    let claims = verification_report.record_result(
+        VerificationCheckKind::Claims,
+        None,
+        None,
+        sd_jwt.processed_claims(),
+    )?;
+
+    // Verify the Key Binding JWT.
+    if let Some(key_binding_jwt) = sd_jwt.key_binding_jwt_o() {
+        let key_binding_kid = key_binding_jwt.jws().header().kid.as_str();
+        anyhow::ensure!(
+            key_binding_jwt.jws().header().typ.as_deref() == Some(SDJWT::KEY_BINDING_JWT_TYP),
+            "SD-JWT Key Binding JWT must have \"typ\" {:?}",
+            SDJWT::KEY_BINDING_JWT_TYP
+        );
+        let holder_did = sd_jwt.holder_did_o()?.ok_or_else(|| {
+            anyhow::anyhow!(
+                "SD-JWT has a Key Binding JWT, but is not bound to a holder (it has no \"cnf\" claim)"
+            )
+        })?;
+        anyhow::ensure!(
+            key_binding_jwt.signer() == holder_did,
+            "SD-JWT is bound to holder {}, but the Key Binding JWT was signed by {}",
+            holder_did,
+            key_binding_jwt.signer()
+        );
+        let holder_verifier_b = verification_report
+            .resolve_for_purpose(
+                key_binding_kid,
+                verifier_resolver::VerificationRelationship::Authentication,
+                did_resolver_a.as_ref(),
+            )
+            .await?;
+        verification_report.record_result(
+            VerificationCheckKind::Signature,
+            Some(key_binding_kid),
+            verification_report
+                .resolved_did_document_version(key_binding_kid)
+                .as_deref(),
+            key_binding_jwt
+                .jws()
+                .verify(holder_verifier_b.as_ref(), None),
+        )?;
+
+        let key_binding_claims = key_binding_jwt.claims();
+        let key_binding_iat = key_binding_claims
+            .iat
+            .ok_or_else(|| anyhow::anyhow!("SD-JWT Key Binding JWT is missing \"iat\" claim"))?;
+        anyhow::ensure!(
+            key_binding_iat.saturating_sub(clock_skew_seconds) <= now,
+            "SD-JWT Key Binding JWT was issued in the future at {} (now is {}, clock skew tolerance is {}s)",
+            key_binding_iat,
+            now,
+            clock_skew_seconds
+        );
+        anyhow::ensure!(
+            key_binding_iat
+                .saturating_add(key_binding_max_age_seconds)
+                .saturating_add(clock_skew_seconds)
+                >= now,
+            "SD-JWT Key Binding JWT issued at {} is older than the max age of {}s (now is {}, clock skew tolerance is {}s)",
+            key_binding_iat,
+            key_binding_max_age_seconds,
+            now,
+            clock_skew_seconds
+        );
+        ensure_key_valid_at(
+            key_binding_kid,
+            None,
+            unix_timestamp_o_to_offset_date_time_o(Some(key_binding_iat))?,
+            did_resolver_a.as_ref(),
+            verification_report,
+        )
+        .await?;
+
+        let aud = key_binding_claims
+            .aud
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("SD-JWT Key Binding JWT is missing \"aud\" claim"))?;
+        if let Some(expected_audience) = verification_options.expected_audience_o.as_deref() {
+            anyhow::ensure!(
+                aud.contains(expected_audience),
+                "SD-JWT Key Binding JWT \"aud\" claim {:?} does not contain the expected audience {:?}",
+                aud,
+                expected_audience
+            );
+        }
+        let nonce = key_binding_claims
+            .additional_claim_m
+            .get("nonce")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("SD-JWT Key Binding JWT is missing \"nonce\" claim"))?;
+        if let Some(expected_nonce) = verification_options.expected_nonce_o.as_deref() {
+            anyhow::ensure!(
+                nonce == expected_nonce,
+                "SD-JWT Key Binding JWT \"nonce\" claim {:?} is not the expected nonce {:?}",
+                nonce,
+                expected_nonce
+            );
+        }
+        let sd_hash = key_binding_claims
+            .additional_claim_m
+            .get("sd_hash")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| {
+                anyhow::anyhow!("SD-JWT Key Binding JWT is missing \"sd_hash\" claim")
+            })?;
+        anyhow::ensure!(
+            sd_hash == sd_jwt.sd_hash(),
+            "SD-JWT Key Binding JWT \"sd_hash\" claim does not match the presented SD-JWT"
+        );
+    } else {
+        anyhow::ensure!(
+            !verification_options.require_key_binding,
+            "SD-JWT is missing its required Key Binding JWT"
+        );
+    }
+
+    Ok(claims)
+}
+
+/// Replaces the claims at the given JSON pointers with digests (in the "_sd" array of the enclosing object,
+/// or as a `{"...": digest}` element of the enclosing array), returning the corresponding Disclosures.
+fn conceal_claims(
+    claims: &mut serde_json::Value,
+    selectively_disclosable_pointer_v: &[&str],
+) -> Result<Vec<SDJWTDisclosure>> {
+    let mut token_vv = Vec::with_capacity(selectively_disclosable_pointer_v.len());
+    for pointer in selectively_disclosable_pointer_v {
+        anyhow::ensure!(
+            pointer.starts_with('/'),
+            "JSON pointer {:?} of selectively disclosable claim must start with '/'",
+            pointer
+        );
+        let token_v = pointer[1..]
+            .split('/')
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .collect::<Vec<_>>();
+        anyhow::ensure!(
+            !token_vv.contains(&token_v),
+            "JSON pointer {:?} of selectively disclosable claim was specified more than once",
+            pointer
+        );
+        token_vv.push(token_v);
+    }
+    // Conceal the most deeply nested claims first, so that their digests end up within the Disclosures
+    // of any enclosing selectively disclosable claims.
+    token_vv.sort_by_key(|token_v| std::cmp::Reverse(token_v.len()));
+
+    let mut disclosure_v = Vec::with_capacity(token_vv.len());
+    for token_v in token_vv {
+        let (claim_token, parent_token_v) = token_v.split_last().unwrap();
+        if parent_token_v.is_empty() {
+            anyhow::ensure!(
+                !NON_SELECTIVELY_DISCLOSABLE_CLAIM_NAME_V.contains(&claim_token.as_str()),
+                "claim {:?} can't be selectively disclosable",
+                claim_token
+            );
+        }
+        let parent_pointer = parent_token_v
+            .iter()
+            .map(|token| format!("/{}", escape_pointer_token(token)))
+            .collect::<String>();
+        let parent = claims.pointer_mut(&parent_pointer).ok_or_else(|| {
+            anyhow::anyhow!(
+                "no claim at JSON pointer {:?} to make selectively disclosable",
+                parent_pointer
+            )
+        })?;
+        match parent {
+            serde_json::Value::Object(claim_m) => {
+                let claim_value = claim_m.remove(claim_token).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "no claim {:?} at JSON pointer {:?} to make selectively disclosable",
+                        claim_token,
+                        parent_pointer
+                    )
+                })?;
+                let disclosure = SDJWTDisclosure::new(Some(claim_token.clone()), claim_value)?;
+                let sd = claim_m
+                    .entry("_sd")
+                    .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+                let digest_v = sd.as_array_mut().ok_or_else(|| {
+                    anyhow::anyhow!("\"_sd\" claim at {:?} must be an array", parent_pointer)
+                })?;
+                digest_v.push(serde_json::Value::String(disclosure.digest()));
+                // Sort the digests so that their order doesn't reveal the original order of the claims.
+                digest_v.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+                disclosure_v.push(disclosure);
+            }
+            serde_json::Value::Array(element_v) => {
+                let index = claim_token.parse::<usize>().map_err(|_| {
+                    anyhow::anyhow!(
+                        "JSON pointer token {:?} into array at {:?} must be an index",
+                        claim_token,
+                        parent_pointer
+                    )
+                })?;
+                let element = element_v.get_mut(index).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "index {} is out of bounds for array at JSON pointer {:?}",
+                        index,
+                        parent_pointer
+                    )
+                })?;
+                let disclosure = SDJWTDisclosure::new(None, element.take())?;
+                *element = serde_json::json!({ "...": disclosure.digest() });
+                disclosure_v.push(disclosure);
+            }
+            _ => {
+                anyhow::bail!(
+                    "claim at JSON pointer {:?} must be an object or array to contain selectively disclosable claims",
+                    parent_pointer
+                );
+            }
+        }
+    }
+    Ok(disclosure_v)
+}
+
+/// Substitutes Disclosures into the claims of an issuer-signed JWT, keeping track of where each Disclosure
+/// was used.
+struct DisclosureProcessor<'d> {
+    disclosure_v: &'d [SDJWTDisclosure],
+    disclosure_index_m: std::collections::HashMap<String, usize>,
+    disclosure_pointer_vo: Vec<Option<String>>,
+}
+
+impl<'d> DisclosureProcessor<'d> {
+    fn new(disclosure_v: &'d [SDJWTDisclosure]) -> Result<Self> {
+        let mut disclosure_index_m = std::collections::HashMap::with_capacity(disclosure_v.len());
+        for (index, disclosure) in disclosure_v.iter().enumerate() {
+            anyhow::ensure!(
+                disclosure_index_m
+                    .insert(disclosure.digest(), index)
+                    .is_none(),
+                "SD-JWT contains Disclosure {} more than once",
+                disclosure
+            );
+        }
+        Ok(Self {
+            disclosure_v,
+            disclosure_index_m,
+            disclosure_pointer_vo: vec![None; disclosure_v.len()],
+        })
+    }
+    /// Returns the index of the Disclosure with the given digest, if present, and records its JSON pointer.
+    fn use_disclosure_o(&mut self, digest: &str, pointer: &str) -> Result<Option<usize>> {
+        let index = match self.disclosure_index_m.get(digest) {
+            Some(&index) => index,
+            None => {
+                // This is an undisclosed claim (or a decoy digest).
+                return Ok(None);
+            }
+        };
+        anyhow::ensure!(
+            self.disclosure_pointer_vo[index].is_none(),
+            "SD-JWT Disclosure {} is referenced more than once",
+            self.disclosure_v[index]
+        );
+        self.disclosure_pointer_vo[index] = Some(pointer.to_string());
+        Ok(Some(index))
+    }
+    fn process_value(
+        &mut self,
+        value: &serde_json::Value,
+        pointer: &str,
+    ) -> Result<serde_json::Value> {
+        // Copy the slice reference so that Disclosures can be borrowed while self is mutably borrowed.
+        let disclosure_v = self.disclosure_v;
+        match value {
+            serde_json::Value::Object(claim_m) => {
+                let mut processed_claim_m = serde_json::Map::with_capacity(claim_m.len());
+                for (claim_name, claim_value) in claim_m.iter() {
+                    if claim_name == "_sd" {
+                        continue;
+                    }
+                    let claim_pointer = format!("{}/{}", pointer, escape_pointer_token(claim_name));
+                    let processed_claim_value = self.process_value(claim_value, &claim_pointer)?;
+                    processed_claim_m.insert(claim_name.clone(), processed_claim_value);
+                }
+                if let Some(sd) = claim_m.get("_sd") {
+                    let digest_v = sd.as_array().ok_or_else(|| {
+                        anyhow::anyhow!("\"_sd\" claim at {:?} must be an array", pointer)
+                    })?;
+                    for digest in digest_v {
+                        let digest = digest.as_str().ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "\"_sd\" claim at {:?} must contain only strings",
+                                pointer
+                            )
+                        })?;
+                        let index = match self.disclosure_index_m.get(digest) {
+                            Some(&index) => index,
+                            None => {
+                                // This is an undisclosed claim (or a decoy digest).
+                                continue;
+                            }
+                        };
+                        let claim_name = disclosure_v[index].claim_name_o().ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "array element Disclosure {} is referenced by \"_sd\" claim at {:?}",
+                                disclosure_v[index],
+                                pointer
+                            )
+                        })?;
+                        anyhow::ensure!(
+                            !processed_claim_m.contains_key(claim_name),
+                            "SD-JWT Disclosure for claim {:?} at {:?} conflicts with an existing claim",
+                            claim_name,
+                            pointer
+                        );
+                        let claim_pointer =
+                            format!("{}/{}", pointer, escape_pointer_token(claim_name));
+                        self.use_disclosure_o(digest, &claim_pointer)?;
+                        let processed_claim_value =
+                            self.process_value(disclosure_v[index].claim_value(), &claim_pointer)?;
+                        processed_claim_m.insert(claim_name.to_string(), processed_claim_value);
+                    }
+                }
+                Ok(serde_json::Value::Object(processed_claim_m))
+            }
+            serde_json::Value::Array(element_v) => {
+                let mut processed_element_v = Vec::with_capacity(element_v.len());
+                for element in element_v {
+                    let element_pointer = format!("{}/{}", pointer, processed_element_v.len());
+                    let digest_o = match element.as_object() {
+                        Some(element_m)
+                            if element_m.len() == 1 && element_m.contains_key("...") =>
+                        {
+                            Some(element_m["..."].as_str().ok_or_else(|| {
+                                anyhow::anyhow!(
+                                    "\"...\" digest at {:?} must be a string",
+                                    element_pointer
+                                )
+                            })?)
+                        }
+                        _ => None,
+                    };
+                    let processed_element = if let Some(digest) = digest_o {
+                        let index = match self.use_disclosure_o(digest, &element_pointer)? {
+                            Some(index) => index,
+                            None => {
+                                // Undisclosed array elements are removed.
+                                continue;
+                            }
+                        };
+                        anyhow::ensure!(
+                            disclosure_v[index].claim_name_o().is_none(),
+                            "object property Disclosure {} is referenced as an array element at {:?}",
+                            disclosure_v[index],
+                            element_pointer
+                        );
+                        self.process_value(disclosure_v[index].claim_value(), &element_pointer)?
+                    } else {
+                        self.process_value(element, &element_pointer)?
+                    };
+                    processed_element_v.push(processed_element);
+                }
+                Ok(serde_json::Value::Array(processed_element_v))
+            }
+            _ => Ok(value.clone()),
+        }
+    }
+    /// Returns the JSON pointer of each Disclosure, or an error if any Disclosure wasn't referenced.
+    fn into_disclosure_pointer_v(self) -> Result<Vec<String>> {
+        self.disclosure_pointer_vo
+            .into_iter()
+            .zip(self.disclosure_v.iter())
+            .map(|(disclosure_pointer_o, disclosure)| {
+                disclosure_pointer_o.ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "SD-JWT Disclosure {} is not referenced by the issuer-signed JWT",
+                        disclosure
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Returns true iff the claim at JSON pointer a encloses (or is) the claim at JSON pointer b.
+fn pointer_is_prefix_of(a: &str, b: &str) -> bool {
+    b == a || (b.starts_with(a) && b[a.len()..].starts_with('/'))
+}
+
+fn unix_timestamp_o_to_offset_date_time_o(
+    unix_timestamp_o: Option<i64>,
+) -> Result<Option<time::OffsetDateTime>> {
+    Ok(unix_timestamp_o
+        .map(time::OffsetDateTime::from_unix_timestamp)
+        .transpose()?)
+}
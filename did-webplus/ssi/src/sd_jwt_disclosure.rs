@@ -0,0 +1,107 @@
+use crate::Result;
+use base64::Engine;
+
+/// A single SD-JWT Disclosure, i.e. the base64url-encoded JSON array `[salt, claim_name, claim_value]` (for
+/// an object property) or `[salt, claim_value]` (for an array element).  The issuer-signed JWT only contains
+/// the digest of the encoded Disclosure, so the claim is only revealed to whoever is given the Disclosure itself.
+/// See <https://datatracker.ietf.org/doc/html/draft-ietf-oauth-selective-disclosure-jwt#section-4.2>
+#[derive(Clone, Debug)]
+pub struct SDJWTDisclosure {
+    salt: String,
+    claim_name_o: Option<String>,
+    claim_value: serde_json::Value,
+    encoded: String,
+}
+
+impl SDJWTDisclosure {
+    /// Creates a Disclosure for the given claim with a fresh 128-bit random salt.  The claim name should be
+    /// specified for an object property and omitted for an array element.
+    pub fn new(claim_name_o: Option<String>, claim_value: serde_json::Value) -> Result<Self> {
+        if let Some(claim_name) = claim_name_o.as_deref() {
+            ensure_claim_name_is_not_reserved(claim_name)?;
+        }
+        let mut salt_bytes = [0u8; 16];
+        getrandom::fill(&mut salt_bytes)
+            .map_err(|e| anyhow::anyhow!("failed to generate SD-JWT Disclosure salt: {}", e))?;
+        let salt = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(salt_bytes);
+        let disclosure_array = match claim_name_o.as_deref() {
+            Some(claim_name) => serde_json::json!([salt, claim_name, claim_value]),
+            None => serde_json::json!([salt, claim_value]),
+        };
+        let encoded = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(serde_json::to_vec(&disclosure_array)?);
+        Ok(Self {
+            salt,
+            claim_name_o,
+            claim_value,
+            encoded,
+        })
+    }
+    pub fn salt(&self) -> &str {
+        self.salt.as_str()
+    }
+    /// Returns the claim name if this Disclosure is for an object property, or None if it's for an array element.
+    pub fn claim_name_o(&self) -> Option<&str> {
+        self.claim_name_o.as_deref()
+    }
+    pub fn claim_value(&self) -> &serde_json::Value {
+        &self.claim_value
+    }
+    /// Returns the encoded form of this Disclosure, exactly as it appears in the SD-JWT.
+    pub fn as_str(&self) -> &str {
+        self.encoded.as_str()
+    }
+    /// Returns the base64url-encoded SHA-256 digest of the encoded Disclosure, which is what the issuer-signed
+    /// JWT refers to (in an "_sd" array, or in a `{"...": digest}` array element).
+    pub fn digest(&self) -> String {
+        use sha2::Digest;
+        base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(sha2::Sha256::digest(self.encoded.as_bytes()))
+    }
+}
+
+impl std::fmt::Display for SDJWTDisclosure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.encoded.as_str())
+    }
+}
+
+impl std::str::FromStr for SDJWTDisclosure {
+    type Err = anyhow::Error;
+    fn from_str(encoded: &str) -> Result<Self> {
+        let disclosure_array: Vec<serde_json::Value> = serde_json::from_slice(
+            &base64::engine::general_purpose::URL_SAFE_NO_PAD
+                .decode(encoded)
+                .map_err(|e| anyhow::anyhow!("malformed SD-JWT Disclosure: {}", e))?,
+        )
+        .map_err(|e| anyhow::anyhow!("SD-JWT Disclosure must be a JSON array: {}", e))?;
+        let (salt, claim_name_o, claim_value) = match disclosure_array.as_slice() {
+            [serde_json::Value::String(salt), serde_json::Value::String(claim_name), claim_value] =>
+            {
+                ensure_claim_name_is_not_reserved(claim_name)?;
+                (salt.clone(), Some(claim_name.clone()), claim_value.clone())
+            }
+            [serde_json::Value::String(salt), claim_value] => {
+                (salt.clone(), None, claim_value.clone())
+            }
+            _ => anyhow::bail!(
+                "SD-JWT Disclosure must be [salt, claim_name, claim_value] or [salt, claim_value]"
+            ),
+        };
+        Ok(Self {
+            salt,
+            claim_name_o,
+            claim_value,
+            encoded: encoded.to_string(),
+        })
+    }
+}
+
+fn ensure_claim_name_is_not_reserved(claim_name: &str) -> Result<()> {
+    anyhow::ensure!(
+        claim_name != "_sd" && claim_name != "_sd_alg" && claim_name != "...",
+        "SD-JWT Disclosure claim name {:?} is reserved",
+        claim_name
+    );
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use crate::{
+    sd_jwt::issue_sd_jwt_with_typ, verify_sd_jwt, Result, SDJWTVerificationOptions, SDJWT,
+};
+
+/// The "typ" header value of an SD-JWT VC.
+pub const SD_JWT_VC_TYP: &str = "dc+sd-jwt";
+/// The previous "typ" header value of an SD-JWT VC, which is still accepted during verification.
+const LEGACY_SD_JWT_VC_TYP: &str = "vc+sd-jwt";
+
+/// Issue an SD-JWT VC (see <https://datatracker.ietf.org/doc/html/draft-ietf-oauth-sd-jwt-vc>) of the given
+/// credential type (the "vct" claim), whose claims are the given claims (a JSON object).  The claims at the given
+/// JSON pointers are made selectively disclosable, and the credential is bound to holder_did_o if specified (see
+/// issue_sd_jwt for details).
+pub async fn issue_sd_jwt_vc<W: did_webplus_wallet::Wallet>(
+    vct: &str,
+    mut claims: serde_json::Value,
+    selectively_disclosable_pointer_v: &[&str],
+    holder_did_o: Option<&did_webplus_core::DIDStr>,
+    wallet_based_signer: &did_webplus_wallet::WalletBasedSigner<W>,
+) -> Result<SDJWT> {
+    anyhow::ensure!(
+        claims.is_object(),
+        "SD-JWT VC claims must be a serde_json::Value::Object"
+    );
+    anyhow::ensure!(
+        claims.as_object().unwrap().get("vct").is_none(),
+        "\"vct\" claim must not be set before issuance"
+    );
+    claims.as_object_mut().unwrap().insert(
+        "vct".to_string(),
+        serde_json::Value::String(vct.to_string()),
+    );
+    issue_sd_jwt_with_typ(
+        SD_JWT_VC_TYP,
+        claims,
+        selectively_disclosable_pointer_v,
+        holder_did_o,
+        wallet_based_signer,
+    )
+    .await
+}
+
+/// Verify an SD-JWT VC presentation (see verify_sd_jwt), additionally checking that it has the SD-JWT VC "typ"
+/// header and a "vct" claim, and if expected_vct_o is specified, that it's that credential type.  Returns the
+/// processed claims.  Each check performed is recorded in verification_report.  Note that this does not do any
+/// revocation status checking, or credential-type-specific verification.
+pub async fn verify_sd_jwt_vc(
+    sd_jwt_vc: &SDJWT,
+    expected_vct_o: Option<&str>,
+    did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+    verification_options: &SDJWTVerificationOptions,
+    verification_report: &mut verifier_resolver::VerificationReport,
+) -> Result<serde_json::Value> {
+    anyhow::ensure!(
+        matches!(
+            sd_jwt_vc.typ_o(),
+            Some(SD_JWT_VC_TYP) | Some(LEGACY_SD_JWT_VC_TYP)
+        ),
+        "SD-JWT VC must have \"typ\" {:?}, but got {:?}",
+        SD_JWT_VC_TYP,
+        sd_jwt_vc.typ_o()
+    );
+    let claims = verify_sd_jwt(
+        sd_jwt_vc,
+        did_resolver_a,
+        verification_options,
+        verification_report,
+    )
+    .await?;
+    let vct = claims
+        .get("vct")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| anyhow::anyhow!("SD-JWT VC is missing \"vct\" claim"))?;
+    if let Some(expected_vct) = expected_vct_o {
+        anyhow::ensure!(
+            vct == expected_vct,
+            "SD-JWT VC \"vct\" claim {:?} is not the expected credential type {:?}",
+            vct,
+            expected_vct
+        );
+    }
+    Ok(claims)
+}
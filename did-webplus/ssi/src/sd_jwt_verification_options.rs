@@ -0,0 +1,44 @@
+/// Specifies how an SD-JWT (or SD-JWT VC) presentation is verified (see verify_sd_jwt and verify_sd_jwt_vc).
+#[derive(Clone, Debug)]
+pub struct SDJWTVerificationOptions {
+    /// The amount of clock skew (in seconds) to tolerate when checking "exp", "nbf", and "iat" of the
+    /// issuer-signed JWT and "iat" of the Key Binding JWT.
+    pub clock_skew_seconds: u64,
+    /// If specified, then the "iss" claim of the issuer-signed JWT must be this issuer.
+    pub expected_issuer_o: Option<String>,
+    /// If true, then the presentation must have a Key Binding JWT, and expected_audience_o and expected_nonce_o
+    /// must be specified, since otherwise a Key Binding JWT presented to a different verifier could be replayed.
+    pub require_key_binding: bool,
+    /// If specified, then the "aud" claim of the Key Binding JWT must be this audience.
+    pub expected_audience_o: Option<String>,
+    /// If specified, then the "nonce" claim of the Key Binding JWT must be this nonce.
+    pub expected_nonce_o: Option<String>,
+    /// The maximum age (in seconds) of the Key Binding JWT, as given by its "iat" claim.
+    pub key_binding_max_age_seconds: u64,
+    /// If specified, then the issuer must be trusted by this trust policy, including to issue credentials of the
+    /// type given by the "vct" claim (if present).
+    pub trust_policy_o: Option<verifier_resolver::TrustPolicy>,
+    /// The current time (in seconds since the Unix epoch) to validate against.  If not specified, then the
+    /// current time is used.
+    pub now_o: Option<i64>,
+}
+
+impl SDJWTVerificationOptions {
+    /// The default maximum age of the Key Binding JWT, in seconds.
+    pub const DEFAULT_KEY_BINDING_MAX_AGE_SECONDS: u64 = 300;
+}
+
+impl Default for SDJWTVerificationOptions {
+    fn default() -> Self {
+        Self {
+            clock_skew_seconds: did_webplus_jws::JWTValidationOptions::DEFAULT_CLOCK_SKEW_SECONDS,
+            expected_issuer_o: None,
+            require_key_binding: true,
+            expected_audience_o: None,
+            expected_nonce_o: None,
+            key_binding_max_age_seconds: Self::DEFAULT_KEY_BINDING_MAX_AGE_SECONDS,
+            trust_policy_o: None,
+            now_o: None,
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use std::sync::Arc;
+
+use crate::{
+    new_unsigned_credential_v2,
+    vc_v2::{credential_issuer_did, verify_vc_jwt_v2_impl, verify_vc_ldp_v2_impl},
+    BitstringStatusList, Result, StatusListFetcher, StatusPurpose,
+};
+
+/// The "type" of a credentialStatus entry which refers to a Bitstring Status List.
+pub const BITSTRING_STATUS_LIST_ENTRY_TYPE: &str = "BitstringStatusListEntry";
+/// The "type" of a status list credential, in addition to "VerifiableCredential".
+pub const BITSTRING_STATUS_LIST_CREDENTIAL_TYPE: &str = "BitstringStatusListCredential";
+
+/// Returns a credentialStatus entry which refers to the given index of the status list credential at the given
+/// URL, for use as the credential_status_o argument of new_unsigned_credential_v2 (or as the "credentialStatus"
+/// field of a VCDM 1.1 credential).  See StatusListIndexAllocator for choosing the index.
+/// See <https://www.w3.org/TR/vc-bitstring-status-list/#bitstringstatuslistentry>
+pub fn new_bitstring_status_list_entry(
+    status_list_credential_url: &str,
+    status_purpose: StatusPurpose,
+    status_list_index: usize,
+) -> serde_json::Value {
+    serde_json::json!({
+        "id": format!("{}#{}", status_list_credential_url, status_list_index),
+        "type": BITSTRING_STATUS_LIST_ENTRY_TYPE,
+        "statusPurpose": status_purpose.as_str(),
+        "statusListIndex": status_list_index.to_string(),
+        "statusListCredential": status_list_credential_url,
+    })
+}
+
+/// Create the content of a VCDM 2.0 status list credential which will be published at the given URL, carrying
+/// the given status list in compressed form.  Sign it using issue_vc_jwt_v2 or issue_vc_ldp_v2 with the same
+/// DID that issues the credentials whose status it tracks.
+/// See <https://www.w3.org/TR/vc-bitstring-status-list/#bitstringstatuslistcredential>
+pub fn new_unsigned_status_list_credential(
+    status_list_credential_url: &str,
+    status_purpose: StatusPurpose,
+    bitstring_status_list: &BitstringStatusList,
+    valid_from: time::OffsetDateTime,
+    valid_until_o: Option<time::OffsetDateTime>,
+) -> Result<serde_json::Value> {
+    let mut unsigned_credential = new_unsigned_credential_v2(
+        None,
+        Some(status_list_credential_url),
+        valid_from,
+        valid_until_o,
+        serde_json::json!({
+            "id": format!("{}#list", status_list_credential_url),
+            "type": "BitstringStatusList",
+            "statusPurpose": status_purpose.as_str(),
+            "encodedList": bitstring_status_list.encode()?,
+        }),
+        None,
+        None,
+    )?;
+    unsigned_credential.as_object_mut().unwrap().insert(
+        "type".to_string(),
+        serde_json::json!([
+            "VerifiableCredential",
+            BITSTRING_STATUS_LIST_CREDENTIAL_TYPE
+        ]),
+    );
+    Ok(unsigned_credential)
+}
+
+/// Issue a JWT-secured status list credential (see new_unsigned_status_list_credential) which is valid from the
+/// current time.  To update the status of credentials, modify the status list and issue it again, replacing the
+/// previously published status list credential.
+pub async fn issue_status_list_credential_jwt<W: did_webplus_wallet::Wallet>(
+    status_list_credential_url: &str,
+    status_purpose: StatusPurpose,
+    bitstring_status_list: &BitstringStatusList,
+    valid_until_o: Option<time::OffsetDateTime>,
+    wallet_based_signer: &did_webplus_wallet::WalletBasedSigner<W>,
+) -> Result<did_webplus_jws::JWS<'static>> {
+    let unsigned_credential = new_unsigned_status_list_credential(
+        status_list_credential_url,
+        status_purpose,
+        bitstring_status_list,
+        time::OffsetDateTime::now_utc(),
+        valid_until_o,
+    )?;
+    crate::issue_vc_jwt_v2(unsigned_credential, wallet_based_signer).await
+}
+
+/// Returns the status purpose and the status list carried by the given status list credential (in JSON form).
+/// This doesn't verify the credential; that must be done separately.
+pub fn parse_status_list_credential(
+    status_list_credential: &serde_json::Value,
+) -> Result<(StatusPurpose, BitstringStatusList)> {
+    let is_status_list_credential = match status_list_credential.get("type") {
+        Some(serde_json::Value::Array(type_v)) => type_v
+            .iter()
+            .any(|t| t.as_str() == Some(BITSTRING_STATUS_LIST_CREDENTIAL_TYPE)),
+        Some(t) => t.as_str() == Some(BITSTRING_STATUS_LIST_CREDENTIAL_TYPE),
+        None => false,
+    };
+    anyhow::ensure!(
+        is_status_list_credential,
+        "status list credential must have type {:?}",
+        BITSTRING_STATUS_LIST_CREDENTIAL_TYPE
+    );
+    let credential_subject = status_list_credential
+        .get("credentialSubject")
+        .ok_or_else(|| anyhow::anyhow!("status list credential has no credentialSubject"))?;
+    anyhow::ensure!(
+        credential_subject
+            .get("type")
+            .and_then(serde_json::Value::as_str)
+            == Some("BitstringStatusList"),
+        "status list credential's credentialSubject must have type \"BitstringStatusList\""
+    );
+    let status_purpose = credential_subject
+        .get("statusPurpose")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| anyhow::anyhow!("status list credential has no statusPurpose"))?
+        .parse::<StatusPurpose>()?;
+    let encoded_list = credential_subject
+        .get("encodedList")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| anyhow::anyhow!("status list credential has no encodedList"))?;
+    Ok((status_purpose, BitstringStatusList::decode(encoded_list)?))
+}
+
+/// Ensure that none of the BitstringStatusListEntry statuses of the given credential (in JSON form) are set.
+/// Each status list credential is fetched using status_list_fetcher_o and verified, must have its
+/// statusListCredential URL as its id, and must have been issued by issuer_did (the DID of the issuer of the
+/// credential).  Credential status entries of other types are not supported, and cause an error.  If the
+/// credential has any credential status entries but status_list_fetcher_o is None, then the status can't be
+/// checked, which is an error.  A credential status check is recorded in verification_report for each entry,
+/// along with the checks performed while verifying the status list credentials.
+pub(crate) async fn ensure_credential_status_ok(
+    credential: &serde_json::Value,
+    issuer_did: &str,
+    status_list_fetcher_o: Option<&dyn StatusListFetcher>,
+    did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+    verification_report: &mut verifier_resolver::VerificationReport,
+) -> Result<()> {
+    let credential_status_v = match credential.get("credentialStatus") {
+        Some(serde_json::Value::Array(credential_status_v)) => {
+            credential_status_v.iter().collect::<Vec<_>>()
+        }
+        Some(credential_status) => vec![credential_status],
+        None => {
+            return Ok(());
+        }
+    };
+    for credential_status in credential_status_v {
+        let status_list_credential_url_o = credential_status
+            .get("statusListCredential")
+            .and_then(serde_json::Value::as_str);
+        let result = match status_list_fetcher_o {
+            Some(status_list_fetcher) => {
+                ensure_credential_status_entry_ok(
+                    credential_status,
+                    issuer_did,
+                    status_list_fetcher,
+                    did_resolver_a.clone(),
+                    verification_report,
+                )
+                .await
+            }
+            None => Err(anyhow::anyhow!(
+                "credential has a credentialStatus, but no StatusListFetcher was specified with which to check it"
+            )),
+        };
+        verification_report.record_result(
+            verifier_resolver::VerificationCheckKind::CredentialStatus,
+            status_list_credential_url_o,
+            None,
+            result,
+        )?;
+    }
+    Ok(())
+}
+
+/// Ensure that the status referred to by the given BitstringStatusListEntry is not set.
+async fn ensure_credential_status_entry_ok(
+    credential_status: &serde_json::Value,
+    issuer_did: &str,
+    status_list_fetcher: &dyn StatusListFetcher,
+    did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+    verification_report: &mut verifier_resolver::VerificationReport,
+) -> Result<()> {
+    let status_type = credential_status
+        .get("type")
+        .and_then(serde_json::Value::as_str);
+    anyhow::ensure!(
+        status_type == Some(BITSTRING_STATUS_LIST_ENTRY_TYPE),
+        "unsupported credentialStatus type {:?}",
+        status_type
+    );
+    let status_purpose = credential_status
+        .get("statusPurpose")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| anyhow::anyhow!("credentialStatus has no statusPurpose"))?
+        .parse::<StatusPurpose>()?;
+    let status_list_index = credential_status
+        .get("statusListIndex")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| anyhow::anyhow!("credentialStatus has no statusListIndex"))?
+        .parse::<usize>()
+        .map_err(|e| anyhow::anyhow!("malformed credentialStatus statusListIndex: {}", e))?;
+    if let Some(status_size) = credential_status.get("statusSize") {
+        anyhow::ensure!(
+            status_size.as_u64() == Some(1),
+            "unsupported credentialStatus statusSize {}; only 1 is supported",
+            status_size
+        );
+    }
+    let status_list_credential_url = credential_status
+        .get("statusListCredential")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| anyhow::anyhow!("credentialStatus has no statusListCredential"))?;
+
+    let status_list_credential = verify_status_list_credential(
+        status_list_fetcher
+            .fetch_status_list_credential(status_list_credential_url)
+            .await?
+            .as_str(),
+        did_resolver_a,
+        verification_report,
+    )
+    .await
+    .map_err(|e| {
+        anyhow::anyhow!(
+            "invalid status list credential {}: {}",
+            status_list_credential_url,
+            e
+        )
+    })?;
+    // The status list credential must be the one for this entry, and not some other list of the same issuer.
+    let status_list_credential_id_o = status_list_credential
+        .get("id")
+        .and_then(serde_json::Value::as_str);
+    anyhow::ensure!(
+        status_list_credential_id_o == Some(status_list_credential_url),
+        "status list credential fetched from {} has id {:?}, which doesn't match",
+        status_list_credential_url,
+        status_list_credential_id_o
+    );
+    let status_list_issuer_did = credential_issuer_did(&status_list_credential)?;
+    anyhow::ensure!(
+        status_list_issuer_did == issuer_did,
+        "status list credential {} was issued by {}, not by the credential's issuer {}",
+        status_list_credential_url,
+        status_list_issuer_did,
+        issuer_did
+    );
+    let (list_status_purpose, bitstring_status_list) =
+        parse_status_list_credential(&status_list_credential)?;
+    anyhow::ensure!(
+        list_status_purpose == status_purpose,
+        "status list credential {} has statusPurpose {:?}, but the credentialStatus has statusPurpose {:?}",
+        status_list_credential_url,
+        list_status_purpose.as_str(),
+        status_purpose.as_str()
+    );
+    if bitstring_status_list.get(status_list_index)? {
+        match status_purpose {
+            StatusPurpose::Revocation => anyhow::bail!("credential has been revoked"),
+            StatusPurpose::Suspension => anyhow::bail!("credential is suspended"),
+        }
+    }
+    Ok(())
+}
+
+/// Verifies a fetched status list credential, which is either JWT-secured or Data Integrity-secured, returning
+/// it in JSON form.
+async fn verify_status_list_credential(
+    status_list_credential: &str,
+    did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+    verification_report: &mut verifier_resolver::VerificationReport,
+) -> Result<serde_json::Value> {
+    let status_list_credential = status_list_credential.trim();
+    if status_list_credential.starts_with('{') {
+        let vc_ldp: ssi_claims::data_integrity::DataIntegrity<
+            ssi_claims::vc::v2::JsonCredential,
+            ssi_claims::data_integrity::AnySuite,
+        > = serde_json::from_str(status_list_credential)?;
+        verify_vc_ldp_v2_impl(&vc_ldp, did_resolver_a, verification_report)
+            .await?
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        Ok(serde_json::to_value(&vc_ldp)?)
+    } else {
+        verify_vc_jwt_v2_impl(status_list_credential, did_resolver_a, verification_report).await
+    }
+}
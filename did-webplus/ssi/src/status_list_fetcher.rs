@@ -0,0 +1,99 @@
+use crate::Result;
+
+/// Fetches status list credentials by URL (the "statusListCredential" field of a BitstringStatusListEntry), so
+/// that credential status can be checked during verification.  This is a trait so that status lists can be
+/// served from somewhere other than their URL, e.g. from a cache, or from an in-memory store in tests.
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+pub trait StatusListFetcher: Send + Sync {
+    /// Returns the status list credential at the given URL, either JWT-secured (a compact JWS) or Data
+    /// Integrity-secured (a JSON object).
+    async fn fetch_status_list_credential(
+        &self,
+        status_list_credential_url: &str,
+    ) -> Result<String>;
+}
+
+/// Fetches status list credentials from their URLs using HTTP GET.
+#[derive(Clone, Debug, Default)]
+pub struct StatusListFetcherHTTP {
+    http_headers_for_o: Option<did_webplus_core::HTTPHeadersFor>,
+}
+
+impl StatusListFetcherHTTP {
+    /// If specified, http_headers_for_o gives the HTTP headers to send to particular hosts.
+    pub fn new(http_headers_for_o: Option<did_webplus_core::HTTPHeadersFor>) -> Self {
+        Self { http_headers_for_o }
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl StatusListFetcher for StatusListFetcherHTTP {
+    async fn fetch_status_list_credential(
+        &self,
+        status_list_credential_url: &str,
+    ) -> Result<String> {
+        let url = reqwest::Url::parse(status_list_credential_url)?;
+        let mut request_builder = reqwest::Client::new().get(url.as_str());
+        if let (Some(http_headers_for), Some(host)) =
+            (self.http_headers_for_o.as_ref(), url.host_str())
+        {
+            for http_header in http_headers_for
+                .http_headers_for_hostname(host)
+                .unwrap_or_default()
+            {
+                request_builder =
+                    request_builder.header(http_header.name.as_str(), http_header.value.as_str());
+            }
+        }
+        let response = request_builder.send().await?;
+        anyhow::ensure!(
+            response.status().is_success(),
+            "failed to fetch status list credential {}: HTTP status {}",
+            status_list_credential_url,
+            response.status()
+        );
+        Ok(response.text().await?)
+    }
+}
+
+/// Serves status list credentials from memory.  Useful for tests, and for issuers that also verify.
+#[derive(Debug, Default)]
+pub struct StatusListFetcherInMemory {
+    status_list_credential_m: std::sync::RwLock<std::collections::HashMap<String, String>>,
+}
+
+impl StatusListFetcherInMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Stores (or replaces) the status list credential at the given URL.
+    pub fn insert(&self, status_list_credential_url: String, status_list_credential: String) {
+        self.status_list_credential_m
+            .write()
+            .unwrap()
+            .insert(status_list_credential_url, status_list_credential);
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl StatusListFetcher for StatusListFetcherInMemory {
+    async fn fetch_status_list_credential(
+        &self,
+        status_list_credential_url: &str,
+    ) -> Result<String> {
+        self.status_list_credential_m
+            .read()
+            .unwrap()
+            .get(status_list_credential_url)
+            .cloned()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "status list credential {} not found",
+                    status_list_credential_url
+                )
+            })
+    }
+}
@@ -0,0 +1,88 @@
+use crate::Result;
+
+/// Allocates status list indices for newly issued credentials.  Indices are chosen uniformly at random from
+/// those not yet allocated, so that a credential's index doesn't reveal anything about when it was issued
+/// relative to other credentials.  The issuer is responsible for persisting the allocated indices (see
+/// allocated_index_v) and restoring them using with_allocated_indices.
+#[derive(Clone, Debug)]
+pub struct StatusListIndexAllocator {
+    length: usize,
+    allocated_index_s: std::collections::BTreeSet<usize>,
+}
+
+impl StatusListIndexAllocator {
+    /// Creates an allocator for a status list with the given number of entries, none of which are allocated.
+    pub fn new(length: usize) -> Self {
+        Self {
+            length,
+            allocated_index_s: std::collections::BTreeSet::new(),
+        }
+    }
+    /// Creates an allocator for a status list with the given number of entries, the given ones of which are
+    /// already allocated.
+    pub fn with_allocated_indices(
+        length: usize,
+        allocated_index_i: impl IntoIterator<Item = usize>,
+    ) -> Result<Self> {
+        let allocated_index_s = allocated_index_i
+            .into_iter()
+            .collect::<std::collections::BTreeSet<_>>();
+        if let Some(&max_index) = allocated_index_s.last() {
+            anyhow::ensure!(
+                max_index < length,
+                "allocated status list index {} is out of range for a status list of length {}",
+                max_index,
+                length
+            );
+        }
+        Ok(Self {
+            length,
+            allocated_index_s,
+        })
+    }
+    pub fn length(&self) -> usize {
+        self.length
+    }
+    /// Returns the allocated indices in increasing order.
+    pub fn allocated_index_v(&self) -> Vec<usize> {
+        self.allocated_index_s.iter().copied().collect()
+    }
+    /// Returns the number of indices that can still be allocated.
+    pub fn available_count(&self) -> usize {
+        self.length - self.allocated_index_s.len()
+    }
+    /// Allocates a random unallocated index, returning an error if the status list is full, in which case the
+    /// issuer should start a new status list.
+    pub fn allocate(&mut self) -> Result<usize> {
+        anyhow::ensure!(
+            self.available_count() > 0,
+            "all {} status list indices have been allocated",
+            self.length
+        );
+        // Pick the n-th available index, where n is random, rather than retrying random indices until an
+        // unallocated one is found, which would be slow when the status list is nearly full.
+        let n = random_below(self.available_count())?;
+        let mut available_index_i =
+            (0..self.length).filter(|index| !self.allocated_index_s.contains(index));
+        let index = available_index_i.nth(n).unwrap();
+        self.allocated_index_s.insert(index);
+        Ok(index)
+    }
+}
+
+/// Returns a uniformly random number in the range [0, bound).
+fn random_below(bound: usize) -> Result<usize> {
+    assert!(bound > 0);
+    let bound = bound as u64;
+    // Reject the values that would bias the result toward small numbers.
+    let zone = u64::MAX - u64::MAX % bound;
+    loop {
+        let mut byte_v = [0u8; 8];
+        getrandom::fill(&mut byte_v)
+            .map_err(|e| anyhow::anyhow!("failed to generate random status list index: {}", e))?;
+        let value = u64::from_le_bytes(byte_v);
+        if value < zone {
+            return Ok((value % bound) as usize);
+        }
+    }
+}
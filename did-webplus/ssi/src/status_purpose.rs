@@ -0,0 +1,37 @@
+use crate::Result;
+
+/// The purpose of a Bitstring Status List, i.e. what it means for a credential's status bit to be set.
+/// See <https://www.w3.org/TR/vc-bitstring-status-list/#bitstringstatuslistentry>
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum StatusPurpose {
+    /// A set bit means that the credential has been permanently revoked.
+    Revocation,
+    /// A set bit means that the credential is temporarily suspended.  Unlike revocation, this can be undone.
+    Suspension,
+}
+
+impl StatusPurpose {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Revocation => "revocation",
+            Self::Suspension => "suspension",
+        }
+    }
+}
+
+impl std::fmt::Display for StatusPurpose {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for StatusPurpose {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "revocation" => Ok(Self::Revocation),
+            "suspension" => Ok(Self::Suspension),
+            _ => anyhow::bail!("unsupported status purpose {:?}", s),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use crate::Result;
+use verifier_resolver::{TrustPolicy, VerificationCheckKind, VerificationReport};
+
+/// Ensure that trust_policy trusts the signer of each of the given key IDs (i.e. the issuer of a credential)
+/// to issue a credential having the given types, including that the key's type is allowed and that it was
+/// resolved within the required VDG scope (see TrustPolicy).  A trust policy check is recorded in
+/// verification_report for each key.
+pub(crate) async fn ensure_issuer_trusted(
+    kid_v: &[&str],
+    credential_type_v: &[&str],
+    trust_policy: &TrustPolicy,
+    did_resolver: &dyn did_webplus_resolver::DIDResolver,
+    verification_report: &mut VerificationReport,
+) -> Result<()> {
+    if kid_v.is_empty() {
+        // Without a key ID, there's no signer that the trust policy could be evaluated against.
+        verification_report.record_result(
+            VerificationCheckKind::TrustPolicy,
+            None,
+            None,
+            Err::<(), _>(anyhow::anyhow!(
+                "no signing key ID to evaluate the trust policy against"
+            )),
+        )?;
+    }
+    for &kid in kid_v {
+        let kid_verifier_resolver: &dyn verifier_resolver::VerifierResolver =
+            if kid.starts_with("did:key:") {
+                &did_key::DIDKeyVerifierResolver
+            } else {
+                did_resolver.as_verifier_resolver()
+            };
+        let trust_r = trust_policy
+            .ensure_verifier_is_trusted_and_allowed(kid, credential_type_v, kid_verifier_resolver)
+            .await;
+        verification_report.record_result(
+            VerificationCheckKind::TrustPolicy,
+            Some(kid),
+            verifier_resolver::did_document_version_of(kid),
+            trust_r,
+        )?;
+    }
+    Ok(())
+}
+
+/// Returns the types of the given credential (in JSON form), whose "type" field may be a string or an array
+/// of strings.
+pub(crate) fn credential_type_v(credential: &serde_json::Value) -> Vec<&str> {
+    match credential.get("type") {
+        Some(serde_json::Value::String(credential_type)) => vec![credential_type.as_str()],
+        Some(serde_json::Value::Array(credential_type_v)) => credential_type_v
+            .iter()
+            .filter_map(serde_json::Value::as_str)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
@@ -1,6 +1,16 @@
 use std::sync::Arc;
 
-use crate::{pick_suite_for_did_webplus_by_id, DIDWebplus, Result};
+use crate::{
+    key_validity::{
+        ensure_jwt_key_valid, ensure_ldp_keys_valid, jwt_kid_o, ldp_proof_kid_v,
+        record_ssi_signature_checks,
+    },
+    pick_suite_for_did_webplus_by_id,
+    status_list::ensure_credential_status_ok,
+    trust_policy::{credential_type_v, ensure_issuer_trusted},
+    vc_v2::credential_issuer_did,
+    DIDWebplus, Result, StatusListFetcher,
+};
 
 pub fn new_unsigned_credential(
     additional_context_vo: Option<Vec<String>>,
@@ -83,21 +93,67 @@ pub async fn issue_vc_ldp<W: did_webplus_wallet::Wallet + Clone>(
         .await?)
 }
 
-/// Verify an LDP-formatted VC.  Note that this does not do any revocation status checking, or credential-type-specific verification.
+/// Verify an LDP-formatted VC.  If the proofs are valid, then this also checks that each did:webplus key was
+/// valid at the proof's "created" time, returning an error if not.  If the credential has a credentialStatus,
+/// then this also checks its status using status_list_fetcher_o (see StatusListFetcher), returning an error if
+/// the credential has been revoked or is suspended, or if status_list_fetcher_o is None.  If trust_policy_o is
+/// specified, then this also checks that the policy trusts the issuer to issue credentials of this type.  Note
+/// that this does not do any credential-type-specific verification.  Each check performed is recorded in
+/// verification_report.
 pub async fn verify_vc_ldp(
     vc_ldp: &ssi_claims::data_integrity::DataIntegrity<
         ssi_claims::vc::v1::JsonCredential,
         ssi_claims::data_integrity::AnySuite,
     >,
+    status_list_fetcher_o: Option<&dyn StatusListFetcher>,
+    trust_policy_o: Option<&verifier_resolver::TrustPolicy>,
     did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+    verification_report: &mut verifier_resolver::VerificationReport,
 ) -> Result<ssi_claims::Verification> {
-    let did_resolver = DIDWebplus { did_resolver_a };
+    let did_resolver = DIDWebplus {
+        did_resolver_a: did_resolver_a.clone(),
+    };
     // Also add the did:key resolver.
     let did_resolver = (did_resolver, ssi_dids::DIDKey);
     use ssi_dids::DIDResolver;
     let vm_resolver = did_resolver.into_vm_resolver::<ssi_verification_methods::AnyMethod>();
     let verification_params = ssi_claims::VerificationParameters::from_resolver(&vm_resolver);
-    Ok(vc_ldp.verify(&verification_params).await?)
+    let credential = serde_json::to_value(vc_ldp)?;
+    let verification_r = vc_ldp.verify(&verification_params).await;
+    record_ssi_signature_checks(
+        ldp_proof_kid_v(&credential)?.as_slice(),
+        &verification_r,
+        verification_report,
+    );
+    let verification = verification_r?;
+    if verification.is_ok() {
+        ensure_ldp_keys_valid(
+            &credential,
+            verifier_resolver::VerificationRelationship::AssertionMethod,
+            did_resolver_a.as_ref(),
+            verification_report,
+        )
+        .await?;
+        if let Some(trust_policy) = trust_policy_o {
+            ensure_issuer_trusted(
+                ldp_proof_kid_v(&credential)?.as_slice(),
+                credential_type_v(&credential).as_slice(),
+                trust_policy,
+                did_resolver_a.as_ref(),
+                verification_report,
+            )
+            .await?;
+        }
+        ensure_credential_status_ok(
+            &credential,
+            credential_issuer_did(&credential)?,
+            status_list_fetcher_o,
+            did_resolver_a,
+            verification_report,
+        )
+        .await?;
+    }
+    Ok(verification)
 }
 
 pub async fn issue_vc_jwt<W: did_webplus_wallet::Wallet>(
@@ -132,17 +188,80 @@ pub async fn issue_vc_jwt<W: did_webplus_wallet::Wallet>(
         .await?)
 }
 
-/// Verify a JWT-formatted VC.  Note that this does not do any revocation status checking, or credential-type-specific verification.
+/// Verify a JWT-formatted VC.  If the signature is valid, then this also checks that a did:webplus key was valid
+/// at the JWT's claimed signing time, returning an error if not.  If the credential has a credentialStatus, then
+/// this also checks its status using status_list_fetcher_o (see StatusListFetcher), returning an error if the
+/// credential has been revoked or is suspended, or if status_list_fetcher_o is None.  If trust_policy_o is
+/// specified, then this also checks that the policy trusts the issuer to issue credentials of this type.  Note
+/// that this does not do any credential-type-specific verification.  Each check performed is recorded in
+/// verification_report.
 // TODO: Accept a vm_resolver so that multiple DID methods could be supported.
 pub async fn verify_vc_jwt(
     vc_jwt: &ssi_jws::JwsBuf,
+    status_list_fetcher_o: Option<&dyn StatusListFetcher>,
+    trust_policy_o: Option<&verifier_resolver::TrustPolicy>,
     did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+    verification_report: &mut verifier_resolver::VerificationReport,
 ) -> Result<ssi_claims::Verification> {
-    let did_resolver = DIDWebplus { did_resolver_a };
+    let did_resolver = DIDWebplus {
+        did_resolver_a: did_resolver_a.clone(),
+    };
     // Also add the did:key resolver.
     let did_resolver = (did_resolver, ssi_dids::DIDKey);
     use ssi_dids::DIDResolver;
     let vm_resolver = did_resolver.into_vm_resolver::<ssi_verification_methods::AnyMethod>();
     let verification_params = ssi_claims::VerificationParameters::from_resolver(&vm_resolver);
-    Ok(vc_jwt.verify(&verification_params).await?)
+    let verification_r = vc_jwt.verify(&verification_params).await;
+    let kid_o = jwt_kid_o(vc_jwt.as_str())?;
+    record_ssi_signature_checks(
+        kid_o.as_deref().as_slice(),
+        &verification_r,
+        verification_report,
+    );
+    let verification = verification_r?;
+    if verification.is_ok() {
+        ensure_jwt_key_valid(
+            vc_jwt.as_str(),
+            Some(verifier_resolver::VerificationRelationship::AssertionMethod),
+            did_resolver_a.as_ref(),
+            verification_report,
+        )
+        .await?;
+        // The credential is carried in the "vc" claim, with its issuer moved to the "iss" claim.
+        use base64::Engine;
+        let payload_base64 = vc_jwt
+            .as_str()
+            .split('.')
+            .nth(1)
+            .ok_or_else(|| anyhow::anyhow!("malformed JWT"))?;
+        let claims: serde_json::Value = serde_json::from_slice(
+            &base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(payload_base64)?,
+        )?;
+        let credential = claims
+            .get("vc")
+            .ok_or_else(|| anyhow::anyhow!("VC JWT has no \"vc\" claim"))?;
+        if let Some(trust_policy) = trust_policy_o {
+            ensure_issuer_trusted(
+                kid_o.as_deref().as_slice(),
+                credential_type_v(credential).as_slice(),
+                trust_policy,
+                did_resolver_a.as_ref(),
+                verification_report,
+            )
+            .await?;
+        }
+        let issuer = claims
+            .get("iss")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("VC JWT has no \"iss\" claim"))?;
+        ensure_credential_status_ok(
+            credential,
+            issuer.split(['?', '#']).next().unwrap(),
+            status_list_fetcher_o,
+            did_resolver_a,
+            verification_report,
+        )
+        .await?;
+    }
+    Ok(verification)
 }
@@ -0,0 +1,480 @@
+use std::sync::Arc;
+
+use crate::{
+    key_validity::{
+        claimed_time_o, ensure_key_valid_at, ensure_ldp_keys_valid, jwt_kid_o, ldp_proof_kid_v,
+        record_ssi_signature_checks,
+    },
+    pick_suite_for_did_webplus_by_id,
+    status_list::ensure_credential_status_ok,
+    trust_policy::{credential_type_v, ensure_issuer_trusted},
+    DIDWebplus, Result, StatusListFetcher,
+};
+
+/// The base JSON-LD context of the W3C Verifiable Credentials Data Model v2.0.
+pub const CREDENTIALS_V2_CONTEXT: &str = "https://www.w3.org/ns/credentials/v2";
+/// The "typ" header value of a JWT-secured VCDM 2.0 credential.
+/// See <https://www.w3.org/TR/vc-jose-cose/#securing-with-jose>
+pub const VC_JWT_V2_TYP: &str = "vc+jwt";
+
+/// Create the content of a VCDM 2.0 VC (a JSON blob).  You can add additional credential-specific content to
+/// the JSON blob, though note that if you do, you will likely also need to specify an additional element in the
+/// additional_context_vo argument.  credential_status_o and credential_schema_o, if specified, must each be a
+/// JSON object or an array of JSON objects, each having a "type" (and for credentialSchema, also an "id").
+/// See <https://www.w3.org/TR/vc-data-model-2.0/>
+pub fn new_unsigned_credential_v2(
+    additional_context_vo: Option<Vec<String>>,
+    credential_id_o: Option<&str>,
+    valid_from: time::OffsetDateTime,
+    valid_until_o: Option<time::OffsetDateTime>,
+    credential_subject: serde_json::Value,
+    credential_status_o: Option<serde_json::Value>,
+    credential_schema_o: Option<serde_json::Value>,
+) -> Result<serde_json::Value> {
+    if let Some(valid_until) = valid_until_o {
+        anyhow::ensure!(
+            valid_from <= valid_until,
+            "validFrom ({}) must not be after validUntil ({})",
+            valid_from,
+            valid_until
+        );
+    }
+    if let Some(credential_status) = credential_status_o.as_ref() {
+        ensure_objects_have_fields(credential_status, "credentialStatus", &["type"])?;
+    }
+    if let Some(credential_schema) = credential_schema_o.as_ref() {
+        ensure_objects_have_fields(credential_schema, "credentialSchema", &["id", "type"])?;
+    }
+
+    let mut context_v = vec![CREDENTIALS_V2_CONTEXT.to_string()];
+    if let Some(additional_context_v) = additional_context_vo {
+        context_v.extend(additional_context_v);
+    }
+    let mut unsigned_credential = serde_json::json!({
+        "@context": context_v,
+        "type": ["VerifiableCredential"],
+        "validFrom": xsd_types::DateTime::from(valid_from),
+        "credentialSubject": credential_subject,
+    });
+    let credential_m = unsigned_credential.as_object_mut().unwrap();
+    if let Some(credential_id) = credential_id_o {
+        credential_m.insert("id".to_string(), credential_id.into());
+    }
+    if let Some(valid_until) = valid_until_o {
+        credential_m.insert(
+            "validUntil".to_string(),
+            serde_json::to_value(xsd_types::DateTime::from(valid_until))?,
+        );
+    }
+    if let Some(credential_status) = credential_status_o {
+        credential_m.insert("credentialStatus".to_string(), credential_status);
+    }
+    if let Some(credential_schema) = credential_schema_o {
+        credential_m.insert("credentialSchema".to_string(), credential_schema);
+    }
+    Ok(unsigned_credential)
+}
+
+/// Returns an EnvelopedVerifiableCredential which embeds the given JWT-secured VCDM 2.0 VC, for use in the
+/// "verifiableCredential" field of a VCDM 2.0 VP.
+/// See <https://www.w3.org/TR/vc-data-model-2.0/#enveloped-verifiable-credentials>
+pub fn new_enveloped_credential_v2(vc_jwt: &str) -> serde_json::Value {
+    serde_json::json!({
+        "@context": CREDENTIALS_V2_CONTEXT,
+        "id": format!("data:application/{},{}", VC_JWT_V2_TYP, vc_jwt),
+        "type": "EnvelopedVerifiableCredential",
+    })
+}
+
+/// Issue a VCDM 2.0 VC secured using Data Integrity.  Use new_unsigned_credential_v2 to create the content
+/// of the credential.  The issuer field is set to the DID of the signer.
+pub async fn issue_vc_ldp_v2<W: did_webplus_wallet::Wallet + Clone>(
+    unsigned_credential: serde_json::Value,
+    wallet_based_signer: &did_webplus_wallet::WalletBasedSigner<W>,
+    did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+) -> Result<
+    ssi_claims::data_integrity::DataIntegrity<
+        ssi_claims::vc::v2::JsonCredential,
+        ssi_claims::data_integrity::AnySuite,
+    >,
+> {
+    let unsigned_credential = with_issuer(unsigned_credential, wallet_based_signer)?;
+
+    let did_resolver = DIDWebplus { did_resolver_a };
+    use ssi_dids::DIDResolver;
+    let vm_resolver = did_resolver.into_vm_resolver::<ssi_verification_methods::AnyMethod>();
+
+    let issuer_did_url =
+        ssi_dids::DIDURLBuf::from_string(wallet_based_signer.key_fully_qualified().to_string())?;
+    let verification_method = ssi_verification_methods::ReferenceOrOwned::<
+        ssi_verification_methods::AnyMethod,
+    >::from(issuer_did_url.clone().into_iri());
+    let cryptosuite = pick_suite_for_did_webplus_by_id(issuer_did_url.as_str())
+        .ok_or_else(|| anyhow::anyhow!("could not find appropriate cryptosuite"))?;
+
+    let json_credential: ssi_claims::vc::v2::JsonCredential =
+        serde_json::from_value(unsigned_credential)?;
+    tracing::debug!("json_credential: {:?}", json_credential);
+
+    use ssi_claims::data_integrity::CryptographicSuite;
+    Ok(cryptosuite
+        .sign(
+            json_credential,
+            &vm_resolver,
+            &wallet_based_signer,
+            ssi_claims::data_integrity::ProofOptions::from_method(verification_method),
+        )
+        .await?)
+}
+
+/// Verify a VCDM 2.0 VC secured using Data Integrity.  If the proofs are valid, then this also checks that each
+/// did:webplus key was valid at the proof's "created" time, returning an error if not.  If the credential has a
+/// credentialStatus, then this also checks its status using status_list_fetcher_o (see StatusListFetcher),
+/// returning an error if the credential has been revoked or is suspended, or if status_list_fetcher_o is
+/// None.  If trust_policy_o is specified, then this also checks that the policy trusts the issuer to issue
+/// credentials of this type.  Note that this does not do any credential-type-specific verification.  Each check
+/// performed is recorded in verification_report.
+pub async fn verify_vc_ldp_v2(
+    vc_ldp: &ssi_claims::data_integrity::DataIntegrity<
+        ssi_claims::vc::v2::JsonCredential,
+        ssi_claims::data_integrity::AnySuite,
+    >,
+    status_list_fetcher_o: Option<&dyn StatusListFetcher>,
+    trust_policy_o: Option<&verifier_resolver::TrustPolicy>,
+    did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+    verification_report: &mut verifier_resolver::VerificationReport,
+) -> Result<ssi_claims::Verification> {
+    let verification =
+        verify_vc_ldp_v2_impl(vc_ldp, did_resolver_a.clone(), verification_report).await?;
+    if verification.is_err() {
+        return Ok(verification);
+    }
+    let credential = serde_json::to_value(vc_ldp)?;
+    if let Some(trust_policy) = trust_policy_o {
+        ensure_issuer_trusted(
+            ldp_proof_kid_v(&credential)?.as_slice(),
+            credential_type_v(&credential).as_slice(),
+            trust_policy,
+            did_resolver_a.as_ref(),
+            verification_report,
+        )
+        .await?;
+    }
+    ensure_credential_status_ok(
+        &credential,
+        credential_issuer_did(&credential)?,
+        status_list_fetcher_o,
+        did_resolver_a,
+        verification_report,
+    )
+    .await?;
+    Ok(verification)
+}
+
+/// This is verify_vc_ldp_v2 without the status check, which is what's used to verify status list credentials.
+pub(crate) async fn verify_vc_ldp_v2_impl(
+    vc_ldp: &ssi_claims::data_integrity::DataIntegrity<
+        ssi_claims::vc::v2::JsonCredential,
+        ssi_claims::data_integrity::AnySuite,
+    >,
+    did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+    verification_report: &mut verifier_resolver::VerificationReport,
+) -> Result<ssi_claims::Verification> {
+    let did_resolver = DIDWebplus {
+        did_resolver_a: did_resolver_a.clone(),
+    };
+    // Also add the did:key resolver.
+    let did_resolver = (did_resolver, ssi_dids::DIDKey);
+    use ssi_dids::DIDResolver;
+    let vm_resolver = did_resolver.into_vm_resolver::<ssi_verification_methods::AnyMethod>();
+    let verification_params = ssi_claims::VerificationParameters::from_resolver(&vm_resolver);
+    let credential = serde_json::to_value(vc_ldp)?;
+    let verification_r = vc_ldp.verify(&verification_params).await;
+    record_ssi_signature_checks(
+        ldp_proof_kid_v(&credential)?.as_slice(),
+        &verification_r,
+        verification_report,
+    );
+    let verification = verification_r?;
+    if verification.is_ok() {
+        verification_report.record_result(
+            verifier_resolver::VerificationCheckKind::Claims,
+            None,
+            None,
+            ensure_ldp_proofs_by_issuer(&credential),
+        )?;
+        ensure_ldp_keys_valid(
+            &credential,
+            verifier_resolver::VerificationRelationship::AssertionMethod,
+            did_resolver_a.as_ref(),
+            verification_report,
+        )
+        .await?;
+    }
+    Ok(verification)
+}
+
+/// Ensure that each proof of the given Data Integrity-secured credential was made using a verification method
+/// of the credential's issuer, which is the analog of the issuer check for JWT-secured credentials.
+fn ensure_ldp_proofs_by_issuer(credential: &serde_json::Value) -> Result<()> {
+    let issuer_did = credential_issuer_did(credential)?;
+    for kid in ldp_proof_kid_v(credential)? {
+        let signer_did = kid.split(['?', '#']).next().unwrap();
+        anyhow::ensure!(
+            signer_did == issuer_did,
+            "credential issuer {:?} does not match the signer {:?}",
+            issuer_did,
+            signer_did
+        );
+    }
+    Ok(())
+}
+
+/// Issue a VCDM 2.0 VC secured as a JWT, i.e. a JWS whose payload is the credential itself, having "typ"
+/// header "vc+jwt".  Use new_unsigned_credential_v2 to create the content of the credential.  The issuer field
+/// is set to the DID of the signer.
+/// See <https://www.w3.org/TR/vc-jose-cose/#securing-with-jose>
+pub async fn issue_vc_jwt_v2<W: did_webplus_wallet::Wallet>(
+    unsigned_credential: serde_json::Value,
+    wallet_based_signer: &did_webplus_wallet::WalletBasedSigner<W>,
+) -> Result<did_webplus_jws::JWS<'static>> {
+    let unsigned_credential = with_issuer(unsigned_credential, wallet_based_signer)?;
+    // Parse it just to check that it's a well-formed VCDM 2.0 credential.
+    serde_json::from_value::<ssi_claims::vc::v2::JsonCredential>(unsigned_credential.clone())?;
+    sign_jose_secured(VC_JWT_V2_TYP, &unsigned_credential, wallet_based_signer).await
+}
+
+/// Verify a JWT-secured VCDM 2.0 VC (see issue_vc_jwt_v2), returning the credential.  This checks that the JWS
+/// was signed by an assertionMethod key of the credential's issuer that was valid at the credential's validFrom
+/// time (or the current time if absent), and that the current time is within the credential's validity
+/// period.  If the credential has a credentialStatus, then this also checks its status using
+/// status_list_fetcher_o (see StatusListFetcher), returning an error if the credential has been revoked or is
+/// suspended, or if status_list_fetcher_o is None.  If trust_policy_o is specified, then this also checks that
+/// the policy trusts the issuer to issue credentials of this type.  Note that this does not do any
+/// credential-type-specific verification.  Each check performed is recorded in verification_report.
+pub async fn verify_vc_jwt_v2(
+    vc_jwt: &str,
+    status_list_fetcher_o: Option<&dyn StatusListFetcher>,
+    trust_policy_o: Option<&verifier_resolver::TrustPolicy>,
+    did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+    verification_report: &mut verifier_resolver::VerificationReport,
+) -> Result<serde_json::Value> {
+    let credential =
+        verify_vc_jwt_v2_impl(vc_jwt, did_resolver_a.clone(), verification_report).await?;
+    if let Some(trust_policy) = trust_policy_o {
+        ensure_issuer_trusted(
+            jwt_kid_o(vc_jwt)?.as_deref().as_slice(),
+            credential_type_v(&credential).as_slice(),
+            trust_policy,
+            did_resolver_a.as_ref(),
+            verification_report,
+        )
+        .await?;
+    }
+    ensure_credential_status_ok(
+        &credential,
+        credential_issuer_did(&credential)?,
+        status_list_fetcher_o,
+        did_resolver_a,
+        verification_report,
+    )
+    .await?;
+    Ok(credential)
+}
+
+/// This is verify_vc_jwt_v2 without the status check, which is what's used to verify status list credentials.
+pub(crate) async fn verify_vc_jwt_v2_impl(
+    vc_jwt: &str,
+    did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+    verification_report: &mut verifier_resolver::VerificationReport,
+) -> Result<serde_json::Value> {
+    let (signer_did, credential) = verify_jose_secured(
+        vc_jwt,
+        VC_JWT_V2_TYP,
+        verifier_resolver::VerificationRelationship::AssertionMethod,
+        |credential| claimed_time_o(credential, &["validFrom"]),
+        did_resolver_a.as_ref(),
+        verification_report,
+    )
+    .await?;
+    verification_report.record_result(
+        verifier_resolver::VerificationCheckKind::Claims,
+        None,
+        None,
+        ensure_credential_claims_valid(&credential, signer_did.as_str()),
+    )?;
+    Ok(credential)
+}
+
+/// Ensure that the given JWT-secured VCDM 2.0 credential is well-formed, was issued by the signer, and that the
+/// current time is within its validity period.
+fn ensure_credential_claims_valid(credential: &serde_json::Value, signer_did: &str) -> Result<()> {
+    serde_json::from_value::<ssi_claims::vc::v2::JsonCredential>(credential.clone())?;
+
+    let issuer_did = credential_issuer_did(credential)?;
+    anyhow::ensure!(
+        issuer_did == signer_did,
+        "credential issuer {:?} does not match the signer {:?}",
+        issuer_did,
+        signer_did
+    );
+
+    let now = time::OffsetDateTime::now_utc();
+    if let Some(valid_from) = claimed_time_o(credential, &["validFrom"])? {
+        anyhow::ensure!(
+            valid_from <= now,
+            "credential is not valid until {}",
+            valid_from
+        );
+    }
+    if let Some(valid_until) = claimed_time_o(credential, &["validUntil"])? {
+        anyhow::ensure!(now < valid_until, "credential expired at {}", valid_until);
+    }
+    Ok(())
+}
+
+/// Returns the DID of the issuer of the given credential (in JSON form), where the issuer may be given as a DID,
+/// a DID URL, or an object whose "id" is either.
+pub(crate) fn credential_issuer_did(credential: &serde_json::Value) -> Result<&str> {
+    let issuer = match credential.get("issuer") {
+        Some(serde_json::Value::String(issuer)) => issuer.as_str(),
+        Some(issuer) => issuer
+            .get("id")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| anyhow::anyhow!("malformed credential issuer"))?,
+        None => anyhow::bail!("credential has no issuer"),
+    };
+    Ok(issuer.split(['?', '#']).next().unwrap())
+}
+
+/// Sets the issuer field of the given unsigned VCDM 2.0 credential to the DID of the signer.
+fn with_issuer<W: did_webplus_wallet::Wallet>(
+    mut unsigned_credential: serde_json::Value,
+    wallet_based_signer: &did_webplus_wallet::WalletBasedSigner<W>,
+) -> Result<serde_json::Value> {
+    anyhow::ensure!(
+        unsigned_credential.is_object(),
+        "unsigned_credential must be a serde_json::Value::Object"
+    );
+    let credential_m = unsigned_credential.as_object_mut().unwrap();
+    anyhow::ensure!(
+        credential_m.get("issuer").is_none(),
+        "issuer field of unsigned_credential must not be set before signing"
+    );
+    ensure_v2_context(credential_m)?;
+    credential_m.insert(
+        "issuer".to_string(),
+        serde_json::Value::String(
+            wallet_based_signer
+                .key_fully_qualified()
+                .did()
+                .as_str()
+                .to_string(),
+        ),
+    );
+    Ok(unsigned_credential)
+}
+
+/// Signs the given VCDM 2.0 credential or presentation as the payload of a JWS having the given "typ" header.
+pub(crate) async fn sign_jose_secured<W: did_webplus_wallet::Wallet>(
+    typ: &str,
+    payload: &serde_json::Value,
+    wallet_based_signer: &did_webplus_wallet::WalletBasedSigner<W>,
+) -> Result<did_webplus_jws::JWS<'static>> {
+    let payload_bytes = serde_json::to_vec(payload)?;
+    Ok(did_webplus_jws::JWS::async_signed_with_typ(
+        Some(typ.to_string()),
+        wallet_based_signer.key_fully_qualified().to_string(),
+        &mut payload_bytes.as_slice(),
+        did_webplus_jws::JWSPayloadPresence::Attached,
+        did_webplus_jws::JWSPayloadEncoding::Base64,
+        wallet_based_signer,
+    )
+    .await?)
+}
+
+/// Verifies a JWS-secured VCDM 2.0 credential or presentation having the given "typ" header, whose key must be
+/// listed under the given verification relationship and must have been valid at the claimed signing time of the
+/// payload as determined by signing_time_o_of (or the current time if there is none).  Returns the DID of the
+/// signer and the payload.  The checks performed are recorded in verification_report.
+pub(crate) async fn verify_jose_secured(
+    jwt: &str,
+    typ: &str,
+    verification_relationship: verifier_resolver::VerificationRelationship,
+    signing_time_o_of: fn(&serde_json::Value) -> Result<Option<time::OffsetDateTime>>,
+    did_resolver: &dyn did_webplus_resolver::DIDResolver,
+    verification_report: &mut verifier_resolver::VerificationReport,
+) -> Result<(String, serde_json::Value)> {
+    let jws = did_webplus_jws::JWS::try_from(jwt.trim().to_string())?;
+    anyhow::ensure!(
+        jws.header().typ.as_deref() == Some(typ),
+        "expected \"typ\" {:?}, but got {:?}",
+        typ,
+        jws.header().typ
+    );
+    let kid = jws.header().kid.as_str();
+    let verifier_b = verification_report
+        .resolve_for_purpose(kid, verification_relationship, did_resolver)
+        .await?;
+    verification_report.record_result(
+        verifier_resolver::VerificationCheckKind::Signature,
+        Some(kid),
+        verification_report
+            .resolved_did_document_version(kid)
+            .as_deref(),
+        jws.verify(verifier_b.as_ref(), None),
+    )?;
+    let payload: serde_json::Value = jws.parsed_decoded_payload(None)?;
+    anyhow::ensure!(payload.is_object(), "payload must be a JSON object");
+    ensure_v2_context(payload.as_object().unwrap())?;
+    let signing_time_o = signing_time_o_of(&payload)?;
+    ensure_key_valid_at(kid, None, signing_time_o, did_resolver, verification_report).await?;
+    Ok((kid.split(['?', '#']).next().unwrap().to_string(), payload))
+}
+
+/// Ensure that the first element of the "@context" field is the VCDM 2.0 base context.
+pub(crate) fn ensure_v2_context(
+    document_m: &serde_json::Map<String, serde_json::Value>,
+) -> Result<()> {
+    let first_context_o = match document_m.get("@context") {
+        Some(serde_json::Value::Array(context_v)) => context_v.first(),
+        Some(context) => Some(context),
+        None => None,
+    };
+    anyhow::ensure!(
+        first_context_o.and_then(serde_json::Value::as_str) == Some(CREDENTIALS_V2_CONTEXT),
+        "the first element of \"@context\" must be {:?}",
+        CREDENTIALS_V2_CONTEXT
+    );
+    Ok(())
+}
+
+/// Ensure that the given value is a JSON object, or a non-empty array of JSON objects, each of which has
+/// the given fields.
+fn ensure_objects_have_fields(
+    value: &serde_json::Value,
+    name: &str,
+    field_name_v: &[&str],
+) -> Result<()> {
+    let object_v = match value {
+        serde_json::Value::Array(object_v) => object_v.iter().collect::<Vec<_>>(),
+        object => vec![object],
+    };
+    anyhow::ensure!(!object_v.is_empty(), "{} must not be empty", name);
+    for object in object_v {
+        anyhow::ensure!(
+            object.is_object(),
+            "{} must be an object or array of objects",
+            name
+        );
+        for field_name in field_name_v {
+            anyhow::ensure!(
+                object.get(field_name).is_some(),
+                "{} is missing its {:?} field",
+                name,
+                field_name
+            );
+        }
+    }
+    Ok(())
+}
@@ -1,6 +1,12 @@
 use std::{str::FromStr, sync::Arc};
 
-use crate::{pick_suite_for_did_webplus_by_id, DIDWebplus, Result};
+use crate::{
+    key_validity::{
+        ensure_jwt_key_valid, ensure_ldp_keys_valid, jwt_kid_o, ldp_proof_kid_v,
+        record_ssi_signature_checks,
+    },
+    pick_suite_for_did_webplus_by_id, DIDWebplus, Result,
+};
 
 pub fn new_unsigned_presentation(
     id_o: Option<&str>,
@@ -122,23 +128,46 @@ pub async fn issue_vp_ldp<C: serde::Serialize, W: did_webplus_wallet::Wallet + C
         .await?)
 }
 
+/// Verify an LDP-formatted VP.  If the proofs are valid, then this also checks that each did:webplus key was
+/// valid at the proof's "created" time, returning an error if not.  This verifies the VP, but not the credentials
+/// it contains.  Each check performed is recorded in verification_report.
 pub async fn verify_vp_ldp<C>(
     vp_ldp: &ssi_claims::data_integrity::DataIntegrity<
         ssi_claims::vc::v1::JsonPresentation<C>,
         ssi_claims::data_integrity::AnySuite,
     >,
     did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+    verification_report: &mut verifier_resolver::VerificationReport,
 ) -> Result<ssi_claims::Verification>
 where
     C: serde::Serialize,
 {
-    let did_resolver = DIDWebplus { did_resolver_a };
+    let did_resolver = DIDWebplus {
+        did_resolver_a: did_resolver_a.clone(),
+    };
     // Also add the did:key resolver.
     let did_resolver = (did_resolver, ssi_dids::DIDKey);
     use ssi_dids::DIDResolver;
     let vm_resolver = did_resolver.into_vm_resolver::<ssi_verification_methods::AnyMethod>();
     let verification_params = ssi_claims::VerificationParameters::from_resolver(&vm_resolver);
-    Ok(vp_ldp.verify(&verification_params).await?)
+    let presentation = serde_json::to_value(vp_ldp)?;
+    let verification_r = vp_ldp.verify(&verification_params).await;
+    record_ssi_signature_checks(
+        ldp_proof_kid_v(&presentation)?.as_slice(),
+        &verification_r,
+        verification_report,
+    );
+    let verification = verification_r?;
+    if verification.is_ok() {
+        ensure_ldp_keys_valid(
+            &presentation,
+            verifier_resolver::VerificationRelationship::Authentication,
+            did_resolver_a.as_ref(),
+            verification_report,
+        )
+        .await?;
+    }
+    Ok(verification)
 }
 
 pub async fn issue_vp_jwt<C: serde::Serialize, W: did_webplus_wallet::Wallet>(
@@ -206,17 +235,39 @@ pub async fn issue_vp_jwt<C: serde::Serialize, W: did_webplus_wallet::Wallet>(
 }
 
 /// This verifies the VP, but not the credentials it contains.  Verifying credentials is its own complex procedure,
-/// so it should be done separately and explicitly.
+/// so it should be done separately and explicitly.  If the signature is valid, then this also checks that a
+/// did:webplus key was valid at the JWT's claimed signing time, returning an error if not.  Each check performed
+/// is recorded in verification_report.
 // TODO: Accept a vm_resolver so that multiple DID methods could be supported.
 pub async fn verify_vp_jwt(
     vp_jwt: &ssi_jws::JwsBuf,
     did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+    verification_report: &mut verifier_resolver::VerificationReport,
 ) -> Result<ssi_claims::Verification> {
-    let did_resolver = DIDWebplus { did_resolver_a };
+    let did_resolver = DIDWebplus {
+        did_resolver_a: did_resolver_a.clone(),
+    };
     // Also add the did:key resolver.
     let did_resolver = (did_resolver, ssi_dids::DIDKey);
     use ssi_dids::DIDResolver;
     let vm_resolver = did_resolver.into_vm_resolver::<ssi_verification_methods::AnyMethod>();
     let verification_params = ssi_claims::VerificationParameters::from_resolver(&vm_resolver);
-    Ok(vp_jwt.verify(&verification_params).await?)
+    let verification_r = vp_jwt.verify(&verification_params).await;
+    let kid_o = jwt_kid_o(vp_jwt.as_str())?;
+    record_ssi_signature_checks(
+        kid_o.as_deref().as_slice(),
+        &verification_r,
+        verification_report,
+    );
+    let verification = verification_r?;
+    if verification.is_ok() {
+        ensure_jwt_key_valid(
+            vp_jwt.as_str(),
+            Some(verifier_resolver::VerificationRelationship::Authentication),
+            did_resolver_a.as_ref(),
+            verification_report,
+        )
+        .await?;
+    }
+    Ok(verification)
 }
@@ -0,0 +1,305 @@
+use std::sync::Arc;
+
+use crate::{
+    key_validity::{
+        ensure_ldp_keys_valid, jwt_signing_time_o, ldp_proof_kid_v, record_ssi_signature_checks,
+    },
+    pick_suite_for_did_webplus_by_id,
+    vc_v2::{ensure_v2_context, sign_jose_secured, verify_jose_secured},
+    DIDWebplus, IssueVPParameters, Result, CREDENTIALS_V2_CONTEXT,
+};
+
+/// The "typ" header value of a JWT-secured VCDM 2.0 presentation.
+/// See <https://www.w3.org/TR/vc-jose-cose/#securing-with-jose>
+pub const VP_JWT_V2_TYP: &str = "vp+jwt";
+
+/// Create the content of a VCDM 2.0 VP (a JSON blob) containing the given VCs.  A Data Integrity-secured VC
+/// is embedded as-is, whereas a JWT-secured VC must be embedded using new_enveloped_credential_v2.
+pub fn new_unsigned_presentation_v2(
+    additional_context_vo: Option<Vec<String>>,
+    presentation_id_o: Option<&str>,
+    verifiable_credential_v: Vec<serde_json::Value>,
+) -> serde_json::Value {
+    let mut context_v = vec![CREDENTIALS_V2_CONTEXT.to_string()];
+    if let Some(additional_context_v) = additional_context_vo {
+        context_v.extend(additional_context_v);
+    }
+    let mut unsigned_presentation = serde_json::json!({
+        "@context": context_v,
+        "type": ["VerifiablePresentation"],
+        "verifiableCredential": verifiable_credential_v,
+    });
+    if let Some(presentation_id) = presentation_id_o {
+        unsigned_presentation
+            .as_object_mut()
+            .unwrap()
+            .insert("id".to_string(), presentation_id.into());
+    }
+    unsigned_presentation
+}
+
+/// Issue a VCDM 2.0 VP secured using Data Integrity.  Use new_unsigned_presentation_v2 to create the content
+/// of the presentation.  The holder field is set to the DID of the signer, which must be an authentication key.
+pub async fn issue_vp_ldp_v2<W: did_webplus_wallet::Wallet + Clone>(
+    unsigned_presentation: serde_json::Value,
+    issue_vp_parameters: IssueVPParameters,
+    wallet_based_signer: &did_webplus_wallet::WalletBasedSigner<W>,
+    did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+) -> Result<
+    ssi_claims::data_integrity::DataIntegrity<
+        ssi_claims::vc::v2::JsonPresentation<serde_json::Value>,
+        ssi_claims::data_integrity::AnySuite,
+    >,
+> {
+    let unsigned_presentation = with_holder(unsigned_presentation, wallet_based_signer)?;
+    let json_presentation: ssi_claims::vc::v2::JsonPresentation<serde_json::Value> =
+        serde_json::from_value(unsigned_presentation)?;
+
+    let did_resolver = DIDWebplus { did_resolver_a };
+    use ssi_dids::DIDResolver;
+    let vm_resolver = did_resolver.into_vm_resolver::<ssi_verification_methods::AnyMethod>();
+
+    let proof_options = {
+        let did_url = ssi_dids::DIDURLBuf::from_string(
+            wallet_based_signer.key_fully_qualified().to_string(),
+        )?;
+        // Verification method as IRI reference (resolver will resolve when needed).
+        let verification_method = ssi_verification_methods::ReferenceOrOwned::<
+            ssi_verification_methods::AnyMethod,
+        >::from(did_url.into_iri());
+
+        let mut proof_options =
+            ssi_claims::data_integrity::ProofOptions::from_method(verification_method);
+        proof_options.proof_purpose = ssi_verification_methods::ProofPurpose::Authentication;
+        proof_options.challenge = issue_vp_parameters.challenge_o;
+        if let Some(domains_v) = issue_vp_parameters.domains_vo {
+            proof_options.domains.extend(domains_v);
+        }
+        proof_options.nonce = issue_vp_parameters.nonce_o;
+        proof_options
+    };
+
+    let cryptosuite =
+        pick_suite_for_did_webplus_by_id(wallet_based_signer.key_fully_qualified().as_str())
+            .ok_or_else(|| anyhow::anyhow!("could not find appropriate cryptosuite"))?;
+
+    use ssi_claims::data_integrity::CryptographicSuite;
+    Ok(cryptosuite
+        .sign(
+            json_presentation,
+            &vm_resolver,
+            &wallet_based_signer,
+            proof_options,
+        )
+        .await?)
+}
+
+/// Verify a VCDM 2.0 VP secured using Data Integrity.  If the proofs are valid, then this also checks that each
+/// did:webplus key was valid at the proof's "created" time, returning an error if not.  This verifies the VP,
+/// but not the credentials it contains.  Each check performed is recorded in verification_report.
+pub async fn verify_vp_ldp_v2<C>(
+    vp_ldp: &ssi_claims::data_integrity::DataIntegrity<
+        ssi_claims::vc::v2::JsonPresentation<C>,
+        ssi_claims::data_integrity::AnySuite,
+    >,
+    did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+    verification_report: &mut verifier_resolver::VerificationReport,
+) -> Result<ssi_claims::Verification>
+where
+    C: serde::Serialize,
+{
+    let did_resolver = DIDWebplus {
+        did_resolver_a: did_resolver_a.clone(),
+    };
+    // Also add the did:key resolver.
+    let did_resolver = (did_resolver, ssi_dids::DIDKey);
+    use ssi_dids::DIDResolver;
+    let vm_resolver = did_resolver.into_vm_resolver::<ssi_verification_methods::AnyMethod>();
+    let verification_params = ssi_claims::VerificationParameters::from_resolver(&vm_resolver);
+    let presentation = serde_json::to_value(vp_ldp)?;
+    let verification_r = vp_ldp.verify(&verification_params).await;
+    record_ssi_signature_checks(
+        ldp_proof_kid_v(&presentation)?.as_slice(),
+        &verification_r,
+        verification_report,
+    );
+    let verification = verification_r?;
+    if verification.is_ok() {
+        ensure_ldp_keys_valid(
+            &presentation,
+            verifier_resolver::VerificationRelationship::Authentication,
+            did_resolver_a.as_ref(),
+            verification_report,
+        )
+        .await?;
+    }
+    Ok(verification)
+}
+
+/// Issue a VCDM 2.0 VP secured as a JWT, i.e. a JWS whose payload is the presentation itself, having "typ"
+/// header "vp+jwt".  Use new_unsigned_presentation_v2 to create the content of the presentation.  The holder
+/// field is set to the DID of the signer, which must be an authentication key.  The "iat" claim is set to the
+/// current time, the "aud" claim is set to the domains (if any), and the "nonce" claim is set to the challenge or
+/// nonce (at most one of which may be specified).
+/// See <https://www.w3.org/TR/vc-jose-cose/#securing-with-jose>
+pub async fn issue_vp_jwt_v2<W: did_webplus_wallet::Wallet>(
+    unsigned_presentation: serde_json::Value,
+    issue_vp_parameters: IssueVPParameters,
+    wallet_based_signer: &did_webplus_wallet::WalletBasedSigner<W>,
+) -> Result<did_webplus_jws::JWS<'static>> {
+    anyhow::ensure!(
+        issue_vp_parameters.challenge_o.is_none() || issue_vp_parameters.nonce_o.is_none(),
+        "at most one of challenge and nonce may be specified for a JWT-secured VP"
+    );
+    let mut unsigned_presentation = with_holder(unsigned_presentation, wallet_based_signer)?;
+    // Parse it just to check that it's a well-formed VCDM 2.0 presentation.
+    serde_json::from_value::<ssi_claims::vc::v2::JsonPresentation<serde_json::Value>>(
+        unsigned_presentation.clone(),
+    )?;
+
+    let presentation_m = unsigned_presentation.as_object_mut().unwrap();
+    for claim_name in ["iat", "aud", "nonce"] {
+        anyhow::ensure!(
+            !presentation_m.contains_key(claim_name),
+            "{:?} field of unsigned_presentation must not be set before signing",
+            claim_name
+        );
+    }
+    presentation_m.insert(
+        "iat".to_string(),
+        time::OffsetDateTime::now_utc().unix_timestamp().into(),
+    );
+    if let Some(domains_v) = issue_vp_parameters.domains_vo {
+        presentation_m.insert("aud".to_string(), domains_v.into());
+    }
+    if let Some(nonce) = issue_vp_parameters
+        .challenge_o
+        .or(issue_vp_parameters.nonce_o)
+    {
+        presentation_m.insert("nonce".to_string(), nonce.into());
+    }
+
+    sign_jose_secured(VP_JWT_V2_TYP, &unsigned_presentation, wallet_based_signer).await
+}
+
+/// Verify a JWT-secured VCDM 2.0 VP (see issue_vp_jwt_v2), returning the presentation.  This checks that the JWS
+/// was signed by an authentication key of the presentation's holder that was valid at its "iat" time, and if
+/// expected_audience_o and expected_nonce_o are specified, that the "aud" claim contains that audience and that the
+/// "nonce" claim is that nonce.  This verifies the VP, but not the credentials it contains.  Each check performed
+/// is recorded in verification_report.
+pub async fn verify_vp_jwt_v2(
+    vp_jwt: &str,
+    expected_audience_o: Option<&str>,
+    expected_nonce_o: Option<&str>,
+    did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+    verification_report: &mut verifier_resolver::VerificationReport,
+) -> Result<serde_json::Value> {
+    let (signer_did, presentation) = verify_jose_secured(
+        vp_jwt,
+        VP_JWT_V2_TYP,
+        verifier_resolver::VerificationRelationship::Authentication,
+        jwt_signing_time_o,
+        did_resolver_a.as_ref(),
+        verification_report,
+    )
+    .await?;
+    verification_report.record_result(
+        verifier_resolver::VerificationCheckKind::Claims,
+        None,
+        None,
+        ensure_presentation_claims_valid(
+            &presentation,
+            signer_did.as_str(),
+            expected_audience_o,
+            expected_nonce_o,
+        ),
+    )?;
+    Ok(presentation)
+}
+
+/// Ensure that the given JWT-secured VCDM 2.0 presentation is well-formed, is held by the signer, and has the
+/// expected audience and nonce (if specified).
+fn ensure_presentation_claims_valid(
+    presentation: &serde_json::Value,
+    signer_did: &str,
+    expected_audience_o: Option<&str>,
+    expected_nonce_o: Option<&str>,
+) -> Result<()> {
+    serde_json::from_value::<ssi_claims::vc::v2::JsonPresentation<serde_json::Value>>(
+        presentation.clone(),
+    )?;
+
+    if let Some(holder) = presentation.get("holder") {
+        let holder = match holder {
+            serde_json::Value::String(holder) => holder.as_str(),
+            holder => holder
+                .get("id")
+                .and_then(serde_json::Value::as_str)
+                .ok_or_else(|| anyhow::anyhow!("malformed presentation holder"))?,
+        };
+        anyhow::ensure!(
+            holder.split(['?', '#']).next().unwrap() == signer_did,
+            "presentation holder {:?} does not match the signer {:?}",
+            holder,
+            signer_did
+        );
+    }
+    if let Some(expected_audience) = expected_audience_o {
+        let audience_matches = match presentation.get("aud") {
+            Some(serde_json::Value::String(aud)) => aud == expected_audience,
+            Some(serde_json::Value::Array(aud_v)) => aud_v
+                .iter()
+                .any(|aud| aud.as_str() == Some(expected_audience)),
+            _ => false,
+        };
+        anyhow::ensure!(
+            audience_matches,
+            "presentation \"aud\" claim does not contain the expected audience {:?}",
+            expected_audience
+        );
+    }
+    if let Some(expected_nonce) = expected_nonce_o {
+        anyhow::ensure!(
+            presentation
+                .get("nonce")
+                .and_then(serde_json::Value::as_str)
+                == Some(expected_nonce),
+            "presentation \"nonce\" claim is not the expected nonce {:?}",
+            expected_nonce
+        );
+    }
+    Ok(())
+}
+
+/// Sets the holder field of the given unsigned VCDM 2.0 presentation to the DID of the signer.
+fn with_holder<W: did_webplus_wallet::Wallet>(
+    mut unsigned_presentation: serde_json::Value,
+    wallet_based_signer: &did_webplus_wallet::WalletBasedSigner<W>,
+) -> Result<serde_json::Value> {
+    anyhow::ensure!(
+        wallet_based_signer.key_purpose() == did_webplus_core::KeyPurpose::Authentication,
+        "VP must be signed using an authentication key, but got a {} key",
+        wallet_based_signer.key_purpose()
+    );
+    anyhow::ensure!(
+        unsigned_presentation.is_object(),
+        "unsigned_presentation must be a serde_json::Value::Object"
+    );
+    let presentation_m = unsigned_presentation.as_object_mut().unwrap();
+    anyhow::ensure!(
+        presentation_m.get("holder").is_none(),
+        "holder field of unsigned_presentation must not be set before signing"
+    );
+    ensure_v2_context(presentation_m)?;
+    presentation_m.insert(
+        "holder".to_string(),
+        serde_json::Value::String(
+            wallet_based_signer
+                .key_fully_qualified()
+                .did()
+                .as_str()
+                .to_string(),
+        ),
+    );
+    Ok(unsigned_presentation)
+}
@@ -68,6 +68,7 @@ async fn test_ssi_jwt_issue_did_webplus_impl(
                 vdr_did_create_endpoint: vdr_did_create_endpoint,
                 mb_hash_function_for_did: &mb_hash_function,
                 mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                vdr_authz_key_id_o: None,
             },
             None,
         )
@@ -87,6 +88,18 @@ async fn test_ssi_jwt_issue_did_webplus_impl(
         email: "g@mc-p.org".to_owned(),
     });
 
+    // keyAgreement keys are for key exchange, so can't be used for signing.
+    assert!(did_webplus_wallet::WalletBasedSigner::new(
+        software_wallet.clone(),
+        controlled_did.did(),
+        did_webplus_core::KeyPurpose::KeyAgreement,
+        None,
+        None,
+        true,
+    )
+    .await
+    .is_err());
+
     let wallet_based_signer = did_webplus_wallet::WalletBasedSigner::new(
         software_wallet.clone(),
         controlled_did.did(),
@@ -120,9 +133,13 @@ async fn test_ssi_jwt_issue_did_webplus_impl(
         Arc::new(did_resolver_full)
     };
 
-    let jwt = did_webplus_ssi::verify_jwt(jwt.as_str(), did_resolver_a.clone())
-        .await
-        .expect("pass");
+    let jwt = did_webplus_ssi::verify_jwt(
+        jwt.as_str(),
+        did_resolver_a.clone(),
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .expect("pass");
 
     // Decode the jwt into claims.
     let decoded_jwt = did_webplus_ssi::decode_jwt::<Claims>(&jwt)
@@ -186,6 +203,7 @@ async fn test_ssi_vc_issue_0_impl(
                 vdr_did_create_endpoint: vdr_did_create_endpoint,
                 mb_hash_function_for_did: &mb_hash_function,
                 mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                vdr_authz_key_id_o: None,
             },
             None,
         )
@@ -203,6 +221,17 @@ async fn test_ssi_vc_issue_0_impl(
     )
     .await
     .expect("pass");
+    // VPs must be signed using an authentication key.
+    let holder_wallet_based_signer = did_webplus_wallet::WalletBasedSigner::new(
+        software_wallet.clone(),
+        controlled_did.did(),
+        did_webplus_core::KeyPurpose::Authentication,
+        None,
+        None,
+        true,
+    )
+    .await
+    .expect("pass");
 
     let unsigned_credential = {
         let issuance_date = time::OffsetDateTime::now_utc();
@@ -240,7 +269,14 @@ async fn test_ssi_vc_issue_0_impl(
     let verification_params = VerificationParameters::from_resolver(&vm_resolver);
     // Verify vc_ldp
     {
-        let vc_ldp_verify_r = did_webplus_ssi::verify_vc_ldp(&vc_ldp, did_resolver_a.clone()).await;
+        let vc_ldp_verify_r = did_webplus_ssi::verify_vc_ldp(
+            &vc_ldp,
+            None,
+            None,
+            did_resolver_a.clone(),
+            &mut verifier_resolver::VerificationReport::new(),
+        )
+        .await;
         tracing::debug!("vc_ldp_verify_r: {:?}", vc_ldp_verify_r);
         assert!(vc_ldp_verify_r.is_ok());
         let vc_ldp_verify_proof_r = vc_ldp_verify_r.unwrap();
@@ -249,7 +285,14 @@ async fn test_ssi_vc_issue_0_impl(
     }
     // Verify vc_jwt
     {
-        let vc_jwt_verify_r = did_webplus_ssi::verify_vc_jwt(&vc_jwt, did_resolver_a.clone()).await;
+        let vc_jwt_verify_r = did_webplus_ssi::verify_vc_jwt(
+            &vc_jwt,
+            None,
+            None,
+            did_resolver_a.clone(),
+            &mut verifier_resolver::VerificationReport::new(),
+        )
+        .await;
         tracing::debug!("vc_jwt_verify_r: {:?}", vc_jwt_verify_r);
         assert!(vc_jwt_verify_r.is_ok());
         let vc_jwt_verify_proof_r = vc_jwt_verify_r.unwrap();
@@ -299,7 +342,7 @@ async fn test_ssi_vc_issue_0_impl(
                 domains_vo: Some(vec!["example.org".to_string()]),
                 nonce_o: Some("abcdefghijklmnopqrstuvwxyz".to_string()),
             },
-            &wallet_based_signer,
+            &holder_wallet_based_signer,
             did_resolver_a.clone(),
         )
         .await
@@ -308,8 +351,12 @@ async fn test_ssi_vc_issue_0_impl(
 
         // Verify vp_ldp_of_vc_ldp
         {
-            let vp_ldp_of_vc_ldp_verify_r =
-                did_webplus_ssi::verify_vp_ldp(&vp_ldp_of_vc_ldp, did_resolver_a.clone()).await;
+            let vp_ldp_of_vc_ldp_verify_r = did_webplus_ssi::verify_vp_ldp(
+                &vp_ldp_of_vc_ldp,
+                did_resolver_a.clone(),
+                &mut verifier_resolver::VerificationReport::new(),
+            )
+            .await;
             tracing::debug!("vp_ldp_of_vc_ldp_verify_r: {:?}", vp_ldp_of_vc_ldp_verify_r);
             assert!(vp_ldp_of_vc_ldp_verify_r.is_ok());
             let vp_ldp_of_vc_ldp_verify_proof_r = vp_ldp_of_vc_ldp_verify_r.unwrap();
@@ -342,7 +389,7 @@ async fn test_ssi_vc_issue_0_impl(
                 domains_vo: Some(vec!["example.org".to_string()]),
                 nonce_o: Some("abcdefghijklmnopqrstuvwxyz".to_string()),
             },
-            &wallet_based_signer,
+            &holder_wallet_based_signer,
         )
         .await
         .expect("pass");
@@ -350,8 +397,12 @@ async fn test_ssi_vc_issue_0_impl(
 
         // Verify vp_jwt_of_vc_ldp
         {
-            let vp_jwt_of_vc_ldp_verify_r =
-                did_webplus_ssi::verify_vp_jwt(&vp_jwt_of_vc_ldp, did_resolver_a.clone()).await;
+            let vp_jwt_of_vc_ldp_verify_r = did_webplus_ssi::verify_vp_jwt(
+                &vp_jwt_of_vc_ldp,
+                did_resolver_a.clone(),
+                &mut verifier_resolver::VerificationReport::new(),
+            )
+            .await;
             tracing::debug!("vp_jwt_of_vc_ldp_verify_r: {:?}", vp_jwt_of_vc_ldp_verify_r);
             assert!(vp_jwt_of_vc_ldp_verify_r.is_ok());
             let vp_jwt_of_vc_ldp_verify_proof_r = vp_jwt_of_vc_ldp_verify_r.unwrap();
@@ -382,7 +433,7 @@ async fn test_ssi_vc_issue_0_impl(
                 domains_vo: Some(vec!["example.org".to_string()]),
                 nonce_o: Some("abcdefghijklmnopqrstuvwxyz".to_string()),
             },
-            &wallet_based_signer,
+            &holder_wallet_based_signer,
             did_resolver_a.clone(),
         )
         .await
@@ -395,8 +446,12 @@ async fn test_ssi_vc_issue_0_impl(
 
         // Verify vp_ldp_of_vc_jwt
         {
-            let vp_ldp_of_vc_jwt_verify_r =
-                did_webplus_ssi::verify_vp_ldp(&vp_ldp_of_vc_jwt, did_resolver_a.clone()).await;
+            let vp_ldp_of_vc_jwt_verify_r = did_webplus_ssi::verify_vp_ldp(
+                &vp_ldp_of_vc_jwt,
+                did_resolver_a.clone(),
+                &mut verifier_resolver::VerificationReport::new(),
+            )
+            .await;
             tracing::debug!("vp_ldp_of_vc_jwt_verify_r: {:?}", vp_ldp_of_vc_jwt_verify_r);
             assert!(vp_ldp_of_vc_jwt_verify_r.is_ok());
             let vp_ldp_of_vc_jwt_verify_proof_r = vp_ldp_of_vc_jwt_verify_r.unwrap();
@@ -446,7 +501,7 @@ async fn test_ssi_vc_issue_0_impl(
                 domains_vo: Some(vec!["example.org".to_string()]),
                 nonce_o: Some("abcdefghijklmnopqrstuvwxyz".to_string()),
             },
-            &wallet_based_signer,
+            &holder_wallet_based_signer,
         )
         .await
         .expect("pass");
@@ -454,8 +509,12 @@ async fn test_ssi_vc_issue_0_impl(
 
         // Verify vp_jwt_of_vc_jwt
         {
-            let vp_jwt_of_vc_jwt_verify_r =
-                did_webplus_ssi::verify_vp_jwt(&vp_jwt_of_vc_jwt, did_resolver_a.clone()).await;
+            let vp_jwt_of_vc_jwt_verify_r = did_webplus_ssi::verify_vp_jwt(
+                &vp_jwt_of_vc_jwt,
+                did_resolver_a.clone(),
+                &mut verifier_resolver::VerificationReport::new(),
+            )
+            .await;
             tracing::debug!("vp_jwt_of_vc_jwt_verify_r: {:?}", vp_jwt_of_vc_jwt_verify_r);
             assert!(vp_jwt_of_vc_jwt_verify_r.is_ok());
             let vp_jwt_of_vc_jwt_verify_proof_r = vp_jwt_of_vc_jwt_verify_r.unwrap();
@@ -485,8 +544,14 @@ async fn test_ssi_vc_issue_0_impl(
             .expect("pass");
         // Verify
         {
-            let vc_ldp_verify_r =
-                did_webplus_ssi::verify_vc_ldp(&vc_ldp, did_resolver_a.clone()).await;
+            let vc_ldp_verify_r = did_webplus_ssi::verify_vc_ldp(
+                &vc_ldp,
+                None,
+                None,
+                did_resolver_a.clone(),
+                &mut verifier_resolver::VerificationReport::new(),
+            )
+            .await;
             tracing::debug!("vc_ldp_verify_r: {:?}", vc_ldp_verify_r);
             assert!(vc_ldp_verify_r.is_ok());
             let vc_ldp_verify_proof_r = vc_ldp_verify_r.unwrap();
@@ -513,6 +578,1550 @@ async fn test_ssi_vc_issue_0() {
     vdr_handle.abort();
 }
 
+async fn test_ssi_sd_jwt_vc_impl(
+    software_wallet: &did_webplus_software_wallet::SoftwareWallet,
+    vdr_did_create_endpoint: &str,
+) {
+    let did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver> = {
+        let db_url = "sqlite://:memory:";
+        let did_doc_storage =
+            did_webplus_doc_storage_sqlite::DIDDocStorageSQLite::open_url_and_run_migrations(
+                db_url, None,
+            )
+            .await
+            .expect("pass");
+        let did_doc_store = did_webplus_doc_store::DIDDocStore::new(Arc::new(did_doc_storage));
+        let did_resolver_full =
+            did_webplus_resolver::DIDResolverFull::new(did_doc_store, None, None).expect("pass");
+        Arc::new(did_resolver_full)
+    };
+
+    // Have the wallet create an issuer DID and a holder DID.
+    use did_webplus_wallet::Wallet;
+    let mb_hash_function = selfhash::MBHashFunction::blake3(mbx::Base::Base64Url);
+    let mut controlled_did_v = Vec::new();
+    for _ in 0..2 {
+        let controlled_did = software_wallet
+            .create_did(
+                did_webplus_wallet::CreateDIDParameters {
+                    vdr_did_create_endpoint: vdr_did_create_endpoint,
+                    mb_hash_function_for_did: &mb_hash_function,
+                    mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                    vdr_authz_key_id_o: None,
+                },
+                None,
+            )
+            .await
+            .expect("pass");
+        controlled_did_v.push(controlled_did);
+    }
+    let issuer_did = controlled_did_v[0].did();
+    let holder_did = controlled_did_v[1].did();
+
+    let issuer_wallet_based_signer = did_webplus_wallet::WalletBasedSigner::new(
+        software_wallet.clone(),
+        issuer_did,
+        did_webplus_core::KeyPurpose::AssertionMethod,
+        None,
+        None,
+        true,
+    )
+    .await
+    .expect("pass");
+    // Key Binding JWTs must be signed using an authentication key.
+    let holder_wallet_based_signer = did_webplus_wallet::WalletBasedSigner::new(
+        software_wallet.clone(),
+        holder_did,
+        did_webplus_core::KeyPurpose::Authentication,
+        None,
+        None,
+        true,
+    )
+    .await
+    .expect("pass");
+    let holder_assertion_method_wallet_based_signer = did_webplus_wallet::WalletBasedSigner::new(
+        software_wallet.clone(),
+        holder_did,
+        did_webplus_core::KeyPurpose::AssertionMethod,
+        None,
+        None,
+        true,
+    )
+    .await
+    .expect("pass");
+
+    let claims = serde_json::json!({
+        "sub": holder_did.as_str(),
+        "given_name": "Grunty",
+        "family_name": "McParty",
+        "email": "g@mc-p.org",
+        "address": {
+            "street_address": "123 Main St",
+            "locality": "Anytown",
+            "country": "US",
+        },
+        "nationalities": ["US", "DE"],
+    });
+    let selectively_disclosable_pointer_v = [
+        "/given_name",
+        "/family_name",
+        "/email",
+        "/address",
+        "/address/street_address",
+        "/nationalities/0",
+        "/nationalities/1",
+    ];
+
+    // Issuance must use an assertionMethod key.
+    assert!(did_webplus_ssi::issue_sd_jwt_vc(
+        "https://example.org/IdentityCredential",
+        claims.clone(),
+        &selectively_disclosable_pointer_v,
+        Some(holder_did),
+        &holder_wallet_based_signer,
+    )
+    .await
+    .is_err());
+    // Claims needed for validation can't be selectively disclosable.
+    assert!(did_webplus_ssi::issue_sd_jwt_vc(
+        "https://example.org/IdentityCredential",
+        claims.clone(),
+        &["/sub", "/vct"],
+        Some(holder_did),
+        &issuer_wallet_based_signer,
+    )
+    .await
+    .is_err());
+
+    let sd_jwt_vc = did_webplus_ssi::issue_sd_jwt_vc(
+        "https://example.org/IdentityCredential",
+        claims.clone(),
+        &selectively_disclosable_pointer_v,
+        Some(holder_did),
+        &issuer_wallet_based_signer,
+    )
+    .await
+    .expect("pass");
+    tracing::info!("sd_jwt_vc: {}", sd_jwt_vc);
+    assert_eq!(sd_jwt_vc.typ_o(), Some(did_webplus_ssi::SD_JWT_VC_TYP));
+    assert_eq!(
+        sd_jwt_vc.disclosure_v().len(),
+        selectively_disclosable_pointer_v.len()
+    );
+    assert!(sd_jwt_vc.key_binding_jwt_o().is_none());
+    // None of the selectively disclosable claims should appear in the issuer-signed JWT.
+    for claim_name in ["given_name", "family_name", "email", "address"] {
+        assert!(!sd_jwt_vc
+            .issuer_jwt()
+            .claims()
+            .additional_claim_m
+            .contains_key(claim_name));
+    }
+    // The holder can see all the claims.
+    {
+        let processed_claims = sd_jwt_vc.processed_claims().expect("pass");
+        for (claim_name, claim_value) in claims.as_object().unwrap() {
+            assert_eq!(processed_claims.get(claim_name), Some(claim_value));
+        }
+    }
+    // Round trip through the serialization.
+    let sd_jwt_vc = did_webplus_ssi::SDJWT::from_str(sd_jwt_vc.to_string().as_str()).expect("pass");
+
+    // Create a presentation which only discloses the family name and the locality and country of the address.
+    let presentation = did_webplus_ssi::create_sd_jwt_presentation(
+        &sd_jwt_vc,
+        &["/family_name", "/address/locality", "/address/country"],
+        "https://verifier.example.org",
+        "nonce-1234",
+        &holder_wallet_based_signer,
+    )
+    .await
+    .expect("pass");
+    tracing::info!("presentation: {}", presentation);
+    // Only the "family_name" and "address" Disclosures are needed.
+    assert_eq!(presentation.disclosure_v().len(), 2);
+    assert!(presentation.key_binding_jwt_o().is_some());
+    let presentation_string = presentation.to_string();
+
+    // A presentation's Key Binding JWT must be signed using an authentication key of the holder.
+    assert!(did_webplus_ssi::create_sd_jwt_presentation(
+        &sd_jwt_vc,
+        &["/family_name"],
+        "https://verifier.example.org",
+        "nonce-1234",
+        &holder_assertion_method_wallet_based_signer,
+    )
+    .await
+    .is_err());
+    let issuer_authentication_wallet_based_signer = did_webplus_wallet::WalletBasedSigner::new(
+        software_wallet.clone(),
+        issuer_did,
+        did_webplus_core::KeyPurpose::Authentication,
+        None,
+        None,
+        true,
+    )
+    .await
+    .expect("pass");
+    assert!(did_webplus_ssi::create_sd_jwt_presentation(
+        &sd_jwt_vc,
+        &["/family_name"],
+        "https://verifier.example.org",
+        "nonce-1234",
+        &issuer_authentication_wallet_based_signer,
+    )
+    .await
+    .is_err());
+
+    let verification_options = did_webplus_ssi::SDJWTVerificationOptions {
+        expected_issuer_o: Some(issuer_did.to_string()),
+        expected_audience_o: Some("https://verifier.example.org".to_string()),
+        expected_nonce_o: Some("nonce-1234".to_string()),
+        ..Default::default()
+    };
+    let verified_claims = did_webplus_ssi::verify_sd_jwt_vc(
+        &did_webplus_ssi::SDJWT::from_str(presentation_string.as_str()).expect("pass"),
+        Some("https://example.org/IdentityCredential"),
+        did_resolver_a.clone(),
+        &verification_options,
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .expect("pass");
+    tracing::info!("verified_claims: {}", verified_claims);
+    assert_eq!(
+        verified_claims.get("family_name"),
+        Some(&serde_json::json!("McParty"))
+    );
+    assert!(verified_claims.get("given_name").is_none());
+    assert!(verified_claims.get("email").is_none());
+    assert_eq!(
+        verified_claims.get("address"),
+        Some(&serde_json::json!({ "locality": "Anytown", "country": "US" }))
+    );
+    // Undisclosed array elements are removed.
+    assert_eq!(
+        verified_claims.get("nationalities"),
+        Some(&serde_json::json!([]))
+    );
+    assert!(verified_claims.get("_sd_alg").is_none());
+
+    // Wrong credential type, audience, or nonce.
+    assert!(did_webplus_ssi::verify_sd_jwt_vc(
+        &presentation,
+        Some("https://example.org/OtherCredential"),
+        did_resolver_a.clone(),
+        &verification_options,
+        &mut verifier_resolver::VerificationReport::new()
+    )
+    .await
+    .is_err());
+    assert!(did_webplus_ssi::verify_sd_jwt(
+        &presentation,
+        did_resolver_a.clone(),
+        &did_webplus_ssi::SDJWTVerificationOptions {
+            expected_audience_o: Some("https://other-verifier.example.org".to_string()),
+            ..verification_options.clone()
+        },
+        &mut verifier_resolver::VerificationReport::new()
+    )
+    .await
+    .is_err());
+    assert!(did_webplus_ssi::verify_sd_jwt(
+        &presentation,
+        did_resolver_a.clone(),
+        &did_webplus_ssi::SDJWTVerificationOptions {
+            expected_nonce_o: Some("nonce-5678".to_string()),
+            ..verification_options.clone()
+        },
+        &mut verifier_resolver::VerificationReport::new()
+    )
+    .await
+    .is_err());
+
+    // If key binding is required, then the expected audience and nonce must be specified, since otherwise the
+    // presentation could be replayed to a different verifier.
+    for verification_options in [
+        did_webplus_ssi::SDJWTVerificationOptions {
+            expected_audience_o: None,
+            ..verification_options.clone()
+        },
+        did_webplus_ssi::SDJWTVerificationOptions {
+            expected_nonce_o: None,
+            ..verification_options.clone()
+        },
+    ] {
+        let err = did_webplus_ssi::verify_sd_jwt(
+            &presentation,
+            did_resolver_a.clone(),
+            &verification_options,
+            &mut verifier_resolver::VerificationReport::new(),
+        )
+        .await
+        .expect_err("pass");
+        tracing::debug!("expected error: {}", err);
+        assert!(err.to_string().contains("must be specified"));
+    }
+
+    // A stale Key Binding JWT is rejected.
+    {
+        let err = did_webplus_ssi::verify_sd_jwt(
+            &presentation,
+            did_resolver_a.clone(),
+            &did_webplus_ssi::SDJWTVerificationOptions {
+                now_o: Some(
+                    time::OffsetDateTime::now_utc().unix_timestamp()
+                        + 2 * did_webplus_ssi::SDJWTVerificationOptions::DEFAULT_KEY_BINDING_MAX_AGE_SECONDS
+                            as i64,
+                ),
+                ..verification_options.clone()
+            },
+            &mut verifier_resolver::VerificationReport::new(),
+        )
+        .await
+        .expect_err("pass");
+        tracing::debug!("expected error: {}", err);
+        assert!(err.to_string().contains("max age"));
+    }
+
+    // Adding a Disclosure after the Key Binding JWT was signed breaks "sd_hash".
+    {
+        let email_disclosure = sd_jwt_vc
+            .disclosure_v()
+            .iter()
+            .find(|disclosure| disclosure.claim_name_o() == Some("email"))
+            .expect("pass");
+        let (without_key_binding, key_binding_jwt) =
+            presentation_string.rsplit_once('~').expect("pass");
+        let tampered_presentation = did_webplus_ssi::SDJWT::from_str(
+            format!(
+                "{}~{}~{}",
+                without_key_binding, email_disclosure, key_binding_jwt
+            )
+            .as_str(),
+        )
+        .expect("pass");
+        assert!(did_webplus_ssi::verify_sd_jwt(
+            &tampered_presentation,
+            did_resolver_a.clone(),
+            &verification_options,
+            &mut verifier_resolver::VerificationReport::new()
+        )
+        .await
+        .is_err());
+    }
+
+    // A Disclosure that isn't referenced by the issuer-signed JWT is rejected.
+    {
+        let bogus_disclosure =
+            did_webplus_ssi::SDJWTDisclosure::new(Some("email".to_string()), "x@y.z".into())
+                .expect("pass");
+        let tampered_sd_jwt_vc = did_webplus_ssi::SDJWT::from_str(
+            format!(
+                "{}{}~",
+                sd_jwt_vc.without_key_binding_string(),
+                bogus_disclosure
+            )
+            .as_str(),
+        )
+        .expect("pass");
+        assert!(tampered_sd_jwt_vc.processed_claims().is_err());
+    }
+
+    // Without a Key Binding JWT, verification fails unless key binding is not required.
+    {
+        let presentation_without_key_binding = did_webplus_ssi::SDJWT::from_str(
+            format!("{}~", presentation_string.rsplit_once('~').unwrap().0).as_str(),
+        )
+        .expect("pass");
+        assert!(presentation_without_key_binding
+            .key_binding_jwt_o()
+            .is_none());
+        assert!(did_webplus_ssi::verify_sd_jwt_vc(
+            &presentation_without_key_binding,
+            None,
+            did_resolver_a.clone(),
+            &verification_options,
+            &mut verifier_resolver::VerificationReport::new()
+        )
+        .await
+        .is_err());
+        did_webplus_ssi::verify_sd_jwt_vc(
+            &presentation_without_key_binding,
+            None,
+            did_resolver_a.clone(),
+            &did_webplus_ssi::SDJWTVerificationOptions {
+                require_key_binding: false,
+                ..verification_options.clone()
+            },
+            &mut verifier_resolver::VerificationReport::new(),
+        )
+        .await
+        .expect("pass");
+    }
+
+    // A plain SD-JWT is not an SD-JWT VC.
+    {
+        let sd_jwt = did_webplus_ssi::issue_sd_jwt(
+            serde_json::json!({ "given_name": "Grunty" }),
+            &["/given_name"],
+            None,
+            &issuer_wallet_based_signer,
+        )
+        .await
+        .expect("pass");
+        let verification_options = did_webplus_ssi::SDJWTVerificationOptions {
+            require_key_binding: false,
+            ..Default::default()
+        };
+        let verified_claims = did_webplus_ssi::verify_sd_jwt(
+            &sd_jwt,
+            did_resolver_a.clone(),
+            &verification_options,
+            &mut verifier_resolver::VerificationReport::new(),
+        )
+        .await
+        .expect("pass");
+        assert_eq!(
+            verified_claims.get("given_name"),
+            Some(&serde_json::json!("Grunty"))
+        );
+        assert!(did_webplus_ssi::verify_sd_jwt_vc(
+            &sd_jwt,
+            None,
+            did_resolver_a.clone(),
+            &verification_options,
+            &mut verifier_resolver::VerificationReport::new()
+        )
+        .await
+        .is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_ssi_sd_jwt_vc() {
+    // TODO: Use env vars to be able to point to a "real" VDR.
+
+    let vdr_database_url = "postgres:///test_ssi_sd_jwt_vc_vdr";
+    let vdr_port = 13086;
+    let wallet_store_database_path = "tests/test_ssi_sd_jwt_vc.wallet-store.db";
+
+    let (vdr_handle, vdr_did_create_endpoint, software_wallet) =
+        setup_vdr_and_wallet(vdr_database_url, vdr_port, wallet_store_database_path).await;
+
+    test_ssi_sd_jwt_vc_impl(&software_wallet, &vdr_did_create_endpoint).await;
+
+    tracing::info!("Shutting down VDR");
+    vdr_handle.abort();
+}
+
+async fn test_ssi_vc_v2_impl(
+    software_wallet: &did_webplus_software_wallet::SoftwareWallet,
+    vdr_did_create_endpoint: &str,
+) {
+    let did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver> = {
+        let db_url = "sqlite://:memory:";
+        let did_doc_storage =
+            did_webplus_doc_storage_sqlite::DIDDocStorageSQLite::open_url_and_run_migrations(
+                db_url, None,
+            )
+            .await
+            .expect("pass");
+        let did_doc_store = did_webplus_doc_store::DIDDocStore::new(Arc::new(did_doc_storage));
+        let did_resolver_full =
+            did_webplus_resolver::DIDResolverFull::new(did_doc_store, None, None).expect("pass");
+        Arc::new(did_resolver_full)
+    };
+
+    // Have the wallet create a DID.
+    use did_webplus_wallet::Wallet;
+    let mb_hash_function = selfhash::MBHashFunction::blake3(mbx::Base::Base64Url);
+    let controlled_did = software_wallet
+        .create_did(
+            did_webplus_wallet::CreateDIDParameters {
+                vdr_did_create_endpoint: vdr_did_create_endpoint,
+                mb_hash_function_for_did: &mb_hash_function,
+                mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                vdr_authz_key_id_o: None,
+            },
+            None,
+        )
+        .await
+        .expect("pass");
+    let wallet_based_signer = did_webplus_wallet::WalletBasedSigner::new(
+        software_wallet.clone(),
+        controlled_did.did(),
+        did_webplus_core::KeyPurpose::AssertionMethod,
+        None,
+        None,
+        true,
+    )
+    .await
+    .expect("pass");
+    // VPs must be signed using an authentication key.
+    let holder_wallet_based_signer = did_webplus_wallet::WalletBasedSigner::new(
+        software_wallet.clone(),
+        controlled_did.did(),
+        did_webplus_core::KeyPurpose::Authentication,
+        None,
+        None,
+        true,
+    )
+    .await
+    .expect("pass");
+
+    let valid_from = time::OffsetDateTime::now_utc();
+    let valid_until = valid_from + time::Duration::days(365);
+    // validFrom must not be after validUntil.
+    assert!(did_webplus_ssi::new_unsigned_credential_v2(
+        None,
+        None,
+        valid_until,
+        Some(valid_from),
+        serde_json::json!({ "id": "https://example.org/#CredentialSubjectId" }),
+        None,
+        None,
+    )
+    .is_err());
+    // credentialSchema entries must have an id and a type.
+    assert!(did_webplus_ssi::new_unsigned_credential_v2(
+        None,
+        None,
+        valid_from,
+        None,
+        serde_json::json!({ "id": "https://example.org/#CredentialSubjectId" }),
+        None,
+        Some(serde_json::json!({ "type": "JsonSchema" })),
+    )
+    .is_err());
+    let unsigned_credential = did_webplus_ssi::new_unsigned_credential_v2(
+        None,
+        Some("https://example.org/#CredentialId"),
+        valid_from,
+        Some(valid_until),
+        serde_json::json!({
+            "id": "https://example.org/#CredentialSubjectId",
+            "https://example.org/#name": "Grunty McParty",
+        }),
+        // Credential status is covered by test_ssi_status_list, since checking it requires a status list.
+        None,
+        Some(serde_json::json!([{
+            "id": "https://example.org/schemas/credential.json",
+            "type": "JsonSchema",
+        }])),
+    )
+    .expect("pass");
+    tracing::info!("unsigned_credential: {}", unsigned_credential);
+    assert_eq!(
+        unsigned_credential["@context"][0],
+        did_webplus_ssi::CREDENTIALS_V2_CONTEXT
+    );
+    assert!(unsigned_credential.get("validFrom").is_some());
+    assert!(unsigned_credential.get("validUntil").is_some());
+    assert!(unsigned_credential.get("issuanceDate").is_none());
+
+    // Data Integrity-secured VC.
+    let vc_ldp = did_webplus_ssi::issue_vc_ldp_v2(
+        unsigned_credential.clone(),
+        &wallet_based_signer,
+        did_resolver_a.clone(),
+    )
+    .await
+    .expect("pass");
+    let vc_ldp_json = serde_json::to_value(&vc_ldp).expect("pass");
+    tracing::info!("vc_ldp_json: {}", vc_ldp_json);
+    assert_eq!(
+        vc_ldp_json["issuer"],
+        serde_json::json!(controlled_did.did().as_str())
+    );
+    assert!(vc_ldp_json.get("credentialSchema").is_some());
+    did_webplus_ssi::verify_vc_ldp_v2(
+        &vc_ldp,
+        None,
+        None,
+        did_resolver_a.clone(),
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .expect("pass")
+    .expect("pass");
+    // A Data Integrity-secured VC whose proof was made by a DID other than its issuer must be rejected, even
+    // though the proof itself is valid.
+    {
+        use ssi_claims::data_integrity::CryptographicSuite;
+        use ssi_dids::DIDResolver;
+        let mut misattributed_credential = unsigned_credential.clone();
+        misattributed_credential["issuer"] = serde_json::json!(
+            "did:webplus:example.com:uHiBKEnrhOBVXzwk4vGHk1idC8RYKHzZOHS7Mgt8TzJHRAA"
+        );
+        let vm_resolver = did_webplus_ssi::DIDWebplus {
+            did_resolver_a: did_resolver_a.clone(),
+        }
+        .into_vm_resolver::<ssi_verification_methods::AnyMethod>();
+        let verification_method = ssi_verification_methods::ReferenceOrOwned::<
+            ssi_verification_methods::AnyMethod,
+        >::from(
+            ssi_dids::DIDURLBuf::from_string(wallet_based_signer.key_fully_qualified().to_string())
+                .expect("pass")
+                .into_iri(),
+        );
+        let misattributed_vc_ldp = ssi_claims::data_integrity::AnySuite::JsonWebSignature2020
+            .sign(
+                serde_json::from_value::<ssi_claims::vc::v2::JsonCredential>(
+                    misattributed_credential,
+                )
+                .expect("pass"),
+                &vm_resolver,
+                &wallet_based_signer,
+                ssi_claims::data_integrity::ProofOptions::from_method(verification_method),
+            )
+            .await
+            .expect("pass");
+        let mut verification_report = verifier_resolver::VerificationReport::new();
+        assert!(did_webplus_ssi::verify_vc_ldp_v2(
+            &misattributed_vc_ldp,
+            None,
+            None,
+            did_resolver_a.clone(),
+            &mut verification_report,
+        )
+        .await
+        .is_err());
+        assert!(!verification_report.is_valid());
+    }
+
+    // JWT-secured VC.
+    let vc_jwt =
+        did_webplus_ssi::issue_vc_jwt_v2(unsigned_credential.clone(), &wallet_based_signer)
+            .await
+            .expect("pass");
+    tracing::info!("vc_jwt: {}", vc_jwt.as_str());
+    assert_eq!(
+        vc_jwt.header().typ.as_deref(),
+        Some(did_webplus_ssi::VC_JWT_V2_TYP)
+    );
+    let mut verification_report = verifier_resolver::VerificationReport::new();
+    let credential = did_webplus_ssi::verify_vc_jwt_v2(
+        vc_jwt.as_str(),
+        None,
+        None,
+        did_resolver_a.clone(),
+        &mut verification_report,
+    )
+    .await
+    .expect("pass");
+    tracing::debug!(
+        "verification report: {}",
+        serde_json::to_string(&verification_report).unwrap()
+    );
+    assert!(verification_report.is_valid());
+    assert_eq!(
+        verification_report
+            .check_v
+            .iter()
+            .map(|check| check.kind)
+            .collect::<Vec<_>>(),
+        vec![
+            verifier_resolver::VerificationCheckKind::KeyResolution,
+            verifier_resolver::VerificationCheckKind::KeyPurpose,
+            verifier_resolver::VerificationCheckKind::Signature,
+            verifier_resolver::VerificationCheckKind::KeyValidityWindow,
+            verifier_resolver::VerificationCheckKind::Claims,
+        ]
+    );
+    // Each key-related check should record the exact DID document version that the signing key came from.
+    let did_document_version = vc_jwt.header().kid.split('#').next().unwrap();
+    for check in verification_report
+        .check_v
+        .iter()
+        .filter(|check| check.kind != verifier_resolver::VerificationCheckKind::Claims)
+    {
+        assert_eq!(
+            check.did_document_version_o.as_deref(),
+            Some(did_document_version)
+        );
+    }
+    assert_eq!(
+        credential["credentialSubject"],
+        unsigned_credential["credentialSubject"]
+    );
+    // The issuer must not be set before signing.
+    assert!(
+        did_webplus_ssi::issue_vc_jwt_v2(credential.clone(), &wallet_based_signer)
+            .await
+            .is_err()
+    );
+    // A VCDM 1.1 credential is not a VCDM 2.0 credential.
+    assert!(did_webplus_ssi::issue_vc_jwt_v2(
+        did_webplus_ssi::new_unsigned_credential(
+            None,
+            "https://example.org/#CredentialId",
+            valid_from,
+            valid_until,
+            serde_json::json!({ "id": "https://example.org/#CredentialSubjectId" }),
+        ),
+        &wallet_based_signer
+    )
+    .await
+    .is_err());
+
+    // Trust policies.
+    {
+        let trusting_trust_policy = verifier_resolver::TrustPolicy::from_json_str(
+            &serde_json::json!({
+                "allowedIssuers": [controlled_did.did().as_str()],
+                "credentialTypes": {
+                    "VerifiableCredential": { "allowedIssuers": [controlled_did.did().as_str()] }
+                },
+            })
+            .to_string(),
+        )
+        .expect("pass");
+        let mut verification_report = verifier_resolver::VerificationReport::new();
+        did_webplus_ssi::verify_vc_jwt_v2(
+            vc_jwt.as_str(),
+            None,
+            Some(&trusting_trust_policy),
+            did_resolver_a.clone(),
+            &mut verification_report,
+        )
+        .await
+        .expect("pass");
+        assert!(verification_report.is_valid());
+        assert_eq!(
+            verification_report.check_v.last().unwrap().kind,
+            verifier_resolver::VerificationCheckKind::TrustPolicy
+        );
+        did_webplus_ssi::verify_vc_ldp_v2(
+            &vc_ldp,
+            None,
+            Some(&trusting_trust_policy),
+            did_resolver_a.clone(),
+            &mut verifier_resolver::VerificationReport::new(),
+        )
+        .await
+        .expect("pass")
+        .expect("pass");
+
+        let other_did =
+            "did:webplus:other.example.org:uHiBKYbaJL8Gz2hmMHHSB_NJJanrkNwFDndtAvQCFdpLm0Q";
+        for untrusting_trust_policy_json in [
+            // The issuer isn't allowed.
+            serde_json::json!({ "allowedIssuers": [other_did] }),
+            // The issuer's host isn't allowed.
+            serde_json::json!({ "allowedHostPatterns": ["*.example.org"] }),
+            // The issuer isn't allowed to issue this type of credential.
+            serde_json::json!({
+                "credentialTypes": { "VerifiableCredential": { "allowedIssuers": [other_did] } }
+            }),
+            // The DID resolver doesn't use the required VDG.
+            serde_json::json!({ "requiredVDGHosts": ["vdg.example.org"] }),
+        ] {
+            let untrusting_trust_policy = verifier_resolver::TrustPolicy::from_json_str(
+                &untrusting_trust_policy_json.to_string(),
+            )
+            .expect("pass");
+            let mut verification_report = verifier_resolver::VerificationReport::new();
+            assert!(did_webplus_ssi::verify_vc_jwt_v2(
+                vc_jwt.as_str(),
+                None,
+                Some(&untrusting_trust_policy),
+                did_resolver_a.clone(),
+                &mut verification_report,
+            )
+            .await
+            .is_err());
+            let failed_check_v = verification_report.failed_check_iter().collect::<Vec<_>>();
+            assert_eq!(failed_check_v.len(), 1);
+            assert_eq!(
+                failed_check_v[0].kind,
+                verifier_resolver::VerificationCheckKind::TrustPolicy
+            );
+            assert!(did_webplus_ssi::verify_vc_ldp_v2(
+                &vc_ldp,
+                None,
+                Some(&untrusting_trust_policy),
+                did_resolver_a.clone(),
+                &mut verifier_resolver::VerificationReport::new(),
+            )
+            .await
+            .is_err());
+        }
+    }
+
+    // VP containing both forms of the VC.
+    let unsigned_presentation = did_webplus_ssi::new_unsigned_presentation_v2(
+        None,
+        Some("https://example.org/#PresentationId"),
+        vec![
+            vc_ldp_json.clone(),
+            did_webplus_ssi::new_enveloped_credential_v2(vc_jwt.as_str()),
+        ],
+    );
+    tracing::info!("unsigned_presentation: {}", unsigned_presentation);
+
+    // VPs must be signed using an authentication key.
+    assert!(did_webplus_ssi::issue_vp_jwt_v2(
+        unsigned_presentation.clone(),
+        did_webplus_ssi::IssueVPParameters {
+            challenge_o: None,
+            domains_vo: None,
+            nonce_o: None,
+        },
+        &wallet_based_signer,
+    )
+    .await
+    .is_err());
+
+    let vp_ldp = did_webplus_ssi::issue_vp_ldp_v2(
+        unsigned_presentation.clone(),
+        did_webplus_ssi::IssueVPParameters {
+            challenge_o: Some("abc123".to_string()),
+            domains_vo: Some(vec!["https://verifier.example.org".to_string()]),
+            nonce_o: None,
+        },
+        &holder_wallet_based_signer,
+        did_resolver_a.clone(),
+    )
+    .await
+    .expect("pass");
+    tracing::info!("vp_ldp: {}", serde_json::to_string(&vp_ldp).expect("pass"));
+    did_webplus_ssi::verify_vp_ldp_v2(
+        &vp_ldp,
+        did_resolver_a.clone(),
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .expect("pass")
+    .expect("pass");
+
+    let vp_jwt = did_webplus_ssi::issue_vp_jwt_v2(
+        unsigned_presentation.clone(),
+        did_webplus_ssi::IssueVPParameters {
+            challenge_o: Some("abc123".to_string()),
+            domains_vo: Some(vec!["https://verifier.example.org".to_string()]),
+            nonce_o: None,
+        },
+        &holder_wallet_based_signer,
+    )
+    .await
+    .expect("pass");
+    tracing::info!("vp_jwt: {}", vp_jwt.as_str());
+    let presentation = did_webplus_ssi::verify_vp_jwt_v2(
+        vp_jwt.as_str(),
+        Some("https://verifier.example.org"),
+        Some("abc123"),
+        did_resolver_a.clone(),
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .expect("pass");
+    assert_eq!(
+        presentation["holder"],
+        serde_json::json!(controlled_did.did().as_str())
+    );
+    assert_eq!(
+        presentation["verifiableCredential"],
+        unsigned_presentation["verifiableCredential"]
+    );
+    // Wrong audience or nonce.
+    assert!(did_webplus_ssi::verify_vp_jwt_v2(
+        vp_jwt.as_str(),
+        Some("https://other-verifier.example.org"),
+        Some("abc123"),
+        did_resolver_a.clone(),
+        &mut verifier_resolver::VerificationReport::new()
+    )
+    .await
+    .is_err());
+    assert!(did_webplus_ssi::verify_vp_jwt_v2(
+        vp_jwt.as_str(),
+        Some("https://verifier.example.org"),
+        Some("xyz789"),
+        did_resolver_a.clone(),
+        &mut verifier_resolver::VerificationReport::new()
+    )
+    .await
+    .is_err());
+    // A VC is not a VP, and vice versa.
+    assert!(did_webplus_ssi::verify_vp_jwt_v2(
+        vc_jwt.as_str(),
+        None,
+        None,
+        did_resolver_a.clone(),
+        &mut verifier_resolver::VerificationReport::new()
+    )
+    .await
+    .is_err());
+    assert!(did_webplus_ssi::verify_vc_jwt_v2(
+        vp_jwt.as_str(),
+        None,
+        None,
+        did_resolver_a.clone(),
+        &mut verifier_resolver::VerificationReport::new()
+    )
+    .await
+    .is_err());
+}
+
+#[tokio::test]
+async fn test_ssi_vc_v2() {
+    // TODO: Use env vars to be able to point to a "real" VDR.
+
+    let vdr_database_url = "postgres:///test_ssi_vc_v2_vdr";
+    let vdr_port = 13087;
+    let wallet_store_database_path = "tests/test_ssi_vc_v2.wallet-store.db";
+
+    let (vdr_handle, vdr_did_create_endpoint, software_wallet) =
+        setup_vdr_and_wallet(vdr_database_url, vdr_port, wallet_store_database_path).await;
+
+    test_ssi_vc_v2_impl(&software_wallet, &vdr_did_create_endpoint).await;
+
+    tracing::info!("Shutting down VDR");
+    vdr_handle.abort();
+}
+
+#[test]
+fn test_bitstring_status_list() {
+    assert!(did_webplus_ssi::BitstringStatusList::new(1024).is_err());
+    assert!(did_webplus_ssi::BitstringStatusList::new(
+        did_webplus_ssi::BitstringStatusList::MIN_LENGTH + 1
+    )
+    .is_err());
+
+    let mut bitstring_status_list =
+        did_webplus_ssi::BitstringStatusList::new(did_webplus_ssi::BitstringStatusList::MIN_LENGTH)
+            .expect("pass");
+    assert_eq!(
+        bitstring_status_list.len(),
+        did_webplus_ssi::BitstringStatusList::MIN_LENGTH
+    );
+    for index in [0, 7, 8, 94567, bitstring_status_list.len() - 1] {
+        assert!(!bitstring_status_list.get(index).expect("pass"));
+        bitstring_status_list.set(index, true).expect("pass");
+        assert!(bitstring_status_list.get(index).expect("pass"));
+    }
+    assert!(!bitstring_status_list.get(1).expect("pass"));
+    assert!(bitstring_status_list
+        .get(bitstring_status_list.len())
+        .is_err());
+    assert!(bitstring_status_list
+        .set(bitstring_status_list.len(), true)
+        .is_err());
+    bitstring_status_list.set(7, false).expect("pass");
+    assert!(!bitstring_status_list.get(7).expect("pass"));
+
+    let encoded_list = bitstring_status_list.encode().expect("pass");
+    tracing::debug!("encoded_list: {}", encoded_list);
+    assert!(encoded_list.starts_with('u'));
+    // A mostly-empty list compresses well.
+    assert!(encoded_list.len() < 1024);
+    assert_eq!(
+        did_webplus_ssi::BitstringStatusList::decode(encoded_list.as_str()).expect("pass"),
+        bitstring_status_list
+    );
+    assert!(did_webplus_ssi::BitstringStatusList::decode(&encoded_list[1..]).is_err());
+    assert!(did_webplus_ssi::BitstringStatusList::new(
+        did_webplus_ssi::BitstringStatusList::MAX_LENGTH + 8
+    )
+    .is_err());
+    // A highly compressible list that's longer than the maximum length must be rejected.
+    let too_long_encoded_list = {
+        use base64::Engine;
+        use std::io::Write;
+        let mut gz_encoder =
+            flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gz_encoder
+            .write_all(&vec![
+                0u8;
+                did_webplus_ssi::BitstringStatusList::MAX_LENGTH / 8
+                    + 1
+            ])
+            .expect("pass");
+        format!(
+            "u{}",
+            base64::engine::general_purpose::URL_SAFE_NO_PAD
+                .encode(gz_encoder.finish().expect("pass"))
+        )
+    };
+    assert!(did_webplus_ssi::BitstringStatusList::decode(too_long_encoded_list.as_str()).is_err());
+
+    let mut status_list_index_allocator = did_webplus_ssi::StatusListIndexAllocator::new(4);
+    let mut index_v = (0..4)
+        .map(|_| status_list_index_allocator.allocate().expect("pass"))
+        .collect::<Vec<_>>();
+    index_v.sort();
+    assert_eq!(index_v, vec![0, 1, 2, 3]);
+    assert_eq!(status_list_index_allocator.available_count(), 0);
+    assert!(status_list_index_allocator.allocate().is_err());
+
+    let mut status_list_index_allocator =
+        did_webplus_ssi::StatusListIndexAllocator::with_allocated_indices(4, [0, 1, 3])
+            .expect("pass");
+    assert_eq!(status_list_index_allocator.allocate().expect("pass"), 2);
+    assert_eq!(
+        status_list_index_allocator.allocated_index_v(),
+        vec![0, 1, 2, 3]
+    );
+    assert!(did_webplus_ssi::StatusListIndexAllocator::with_allocated_indices(4, [4]).is_err());
+}
+
+async fn test_ssi_status_list_impl(
+    software_wallet: &did_webplus_software_wallet::SoftwareWallet,
+    vdr_did_create_endpoint: &str,
+) {
+    let did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver> = {
+        let db_url = "sqlite://:memory:";
+        let did_doc_storage =
+            did_webplus_doc_storage_sqlite::DIDDocStorageSQLite::open_url_and_run_migrations(
+                db_url, None,
+            )
+            .await
+            .expect("pass");
+        let did_doc_store = did_webplus_doc_store::DIDDocStore::new(Arc::new(did_doc_storage));
+        let did_resolver_full =
+            did_webplus_resolver::DIDResolverFull::new(did_doc_store, None, None).expect("pass");
+        Arc::new(did_resolver_full)
+    };
+
+    // Have the wallet create an issuer DID and some other DID.
+    use did_webplus_wallet::Wallet;
+    let mb_hash_function = selfhash::MBHashFunction::blake3(mbx::Base::Base64Url);
+    let mut wallet_based_signer_v = Vec::new();
+    for _ in 0..2 {
+        let controlled_did = software_wallet
+            .create_did(
+                did_webplus_wallet::CreateDIDParameters {
+                    vdr_did_create_endpoint: vdr_did_create_endpoint,
+                    mb_hash_function_for_did: &mb_hash_function,
+                    mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                    vdr_authz_key_id_o: None,
+                },
+                None,
+            )
+            .await
+            .expect("pass");
+        wallet_based_signer_v.push(
+            did_webplus_wallet::WalletBasedSigner::new(
+                software_wallet.clone(),
+                controlled_did.did(),
+                did_webplus_core::KeyPurpose::AssertionMethod,
+                None,
+                None,
+                true,
+            )
+            .await
+            .expect("pass"),
+        );
+    }
+    let issuer_wallet_based_signer = &wallet_based_signer_v[0];
+    let other_wallet_based_signer = &wallet_based_signer_v[1];
+
+    // The issuer publishes a revocation status list.
+    let status_list_credential_url = "https://example.org/status/1";
+    let mut bitstring_status_list =
+        did_webplus_ssi::BitstringStatusList::new(did_webplus_ssi::BitstringStatusList::MIN_LENGTH)
+            .expect("pass");
+    let mut status_list_index_allocator =
+        did_webplus_ssi::StatusListIndexAllocator::new(bitstring_status_list.len());
+    let status_list_fetcher = did_webplus_ssi::StatusListFetcherInMemory::new();
+    let status_list_credential = did_webplus_ssi::issue_status_list_credential_jwt(
+        status_list_credential_url,
+        did_webplus_ssi::StatusPurpose::Revocation,
+        &bitstring_status_list,
+        None,
+        issuer_wallet_based_signer,
+    )
+    .await
+    .expect("pass");
+    tracing::info!(
+        "status_list_credential: {}",
+        status_list_credential.as_str()
+    );
+    status_list_fetcher.insert(
+        status_list_credential_url.to_string(),
+        status_list_credential.as_str().to_string(),
+    );
+
+    // Issue credentials which refer to the status list, in each of the supported forms.
+    let status_list_index = status_list_index_allocator.allocate().expect("pass");
+    let credential_status = did_webplus_ssi::new_bitstring_status_list_entry(
+        status_list_credential_url,
+        did_webplus_ssi::StatusPurpose::Revocation,
+        status_list_index,
+    );
+    let valid_from = time::OffsetDateTime::now_utc();
+    let valid_until = valid_from + time::Duration::days(365);
+    let credential_subject = serde_json::json!({
+        "id": "https://example.org/#CredentialSubjectId",
+        "https://example.org/#name": "Grunty McParty",
+    });
+    let unsigned_credential_v2 = did_webplus_ssi::new_unsigned_credential_v2(
+        None,
+        Some("https://example.org/#CredentialId"),
+        valid_from,
+        Some(valid_until),
+        credential_subject.clone(),
+        Some(credential_status.clone()),
+        None,
+    )
+    .expect("pass");
+    let vc_jwt_v2 = did_webplus_ssi::issue_vc_jwt_v2(
+        unsigned_credential_v2.clone(),
+        issuer_wallet_based_signer,
+    )
+    .await
+    .expect("pass");
+    let vc_ldp_v2 = did_webplus_ssi::issue_vc_ldp_v2(
+        unsigned_credential_v2,
+        issuer_wallet_based_signer,
+        did_resolver_a.clone(),
+    )
+    .await
+    .expect("pass");
+    let vc_jwt = {
+        let mut unsigned_credential = did_webplus_ssi::new_unsigned_credential(
+            None,
+            "https://example.org/#CredentialId",
+            valid_from,
+            valid_until,
+            credential_subject,
+        );
+        unsigned_credential
+            .as_object_mut()
+            .unwrap()
+            .insert("credentialStatus".to_string(), credential_status.clone());
+        did_webplus_ssi::issue_vc_jwt(unsigned_credential, issuer_wallet_based_signer)
+            .await
+            .expect("pass")
+    };
+
+    // Not revoked yet.
+    for verify_r in verify_all(
+        &vc_jwt_v2,
+        &vc_ldp_v2,
+        &vc_jwt,
+        Some(&status_list_fetcher),
+        did_resolver_a.clone(),
+    )
+    .await
+    {
+        verify_r.expect("pass");
+    }
+
+    // Without a status list, the status can't be checked.
+    for verify_r in verify_all(
+        &vc_jwt_v2,
+        &vc_ldp_v2,
+        &vc_jwt,
+        Some(&did_webplus_ssi::StatusListFetcherInMemory::new()),
+        did_resolver_a.clone(),
+    )
+    .await
+    {
+        assert!(verify_r.is_err());
+    }
+
+    // A status list issued by a different DID must not be trusted.
+    let forged_status_list_credential = did_webplus_ssi::issue_status_list_credential_jwt(
+        status_list_credential_url,
+        did_webplus_ssi::StatusPurpose::Revocation,
+        &bitstring_status_list,
+        None,
+        other_wallet_based_signer,
+    )
+    .await
+    .expect("pass");
+    let forged_status_list_fetcher = did_webplus_ssi::StatusListFetcherInMemory::new();
+    forged_status_list_fetcher.insert(
+        status_list_credential_url.to_string(),
+        forged_status_list_credential.as_str().to_string(),
+    );
+    for verify_r in verify_all(
+        &vc_jwt_v2,
+        &vc_ldp_v2,
+        &vc_jwt,
+        Some(&forged_status_list_fetcher),
+        did_resolver_a.clone(),
+    )
+    .await
+    {
+        assert!(verify_r.is_err());
+    }
+
+    // A status list credential published for a different list must not be substituted for this one.
+    let other_status_list_credential = did_webplus_ssi::issue_status_list_credential_jwt(
+        "https://example.org/status/2",
+        did_webplus_ssi::StatusPurpose::Revocation,
+        &bitstring_status_list,
+        None,
+        issuer_wallet_based_signer,
+    )
+    .await
+    .expect("pass");
+    let substituted_status_list_fetcher = did_webplus_ssi::StatusListFetcherInMemory::new();
+    substituted_status_list_fetcher.insert(
+        status_list_credential_url.to_string(),
+        other_status_list_credential.as_str().to_string(),
+    );
+    for verify_r in verify_all(
+        &vc_jwt_v2,
+        &vc_ldp_v2,
+        &vc_jwt,
+        Some(&substituted_status_list_fetcher),
+        did_resolver_a.clone(),
+    )
+    .await
+    {
+        assert!(verify_r.is_err());
+    }
+
+    // A status list having a different purpose doesn't apply.
+    let suspension_status_list_credential = did_webplus_ssi::issue_status_list_credential_jwt(
+        status_list_credential_url,
+        did_webplus_ssi::StatusPurpose::Suspension,
+        &bitstring_status_list,
+        None,
+        issuer_wallet_based_signer,
+    )
+    .await
+    .expect("pass");
+    let suspension_status_list_fetcher = did_webplus_ssi::StatusListFetcherInMemory::new();
+    suspension_status_list_fetcher.insert(
+        status_list_credential_url.to_string(),
+        suspension_status_list_credential.as_str().to_string(),
+    );
+    for verify_r in verify_all(
+        &vc_jwt_v2,
+        &vc_ldp_v2,
+        &vc_jwt,
+        Some(&suspension_status_list_fetcher),
+        did_resolver_a.clone(),
+    )
+    .await
+    {
+        assert!(verify_r.is_err());
+    }
+
+    // Revoke the credentials by updating the status list.
+    bitstring_status_list
+        .set(status_list_index, true)
+        .expect("pass");
+    let status_list_credential = did_webplus_ssi::issue_status_list_credential_jwt(
+        status_list_credential_url,
+        did_webplus_ssi::StatusPurpose::Revocation,
+        &bitstring_status_list,
+        None,
+        issuer_wallet_based_signer,
+    )
+    .await
+    .expect("pass");
+    let (status_purpose, parsed_bitstring_status_list) =
+        did_webplus_ssi::parse_status_list_credential(
+            &did_webplus_ssi::verify_vc_jwt_v2(
+                status_list_credential.as_str(),
+                None,
+                None,
+                did_resolver_a.clone(),
+                &mut verifier_resolver::VerificationReport::new(),
+            )
+            .await
+            .expect("pass"),
+        )
+        .expect("pass");
+    assert_eq!(status_purpose, did_webplus_ssi::StatusPurpose::Revocation);
+    assert_eq!(parsed_bitstring_status_list, bitstring_status_list);
+    status_list_fetcher.insert(
+        status_list_credential_url.to_string(),
+        status_list_credential.as_str().to_string(),
+    );
+    for verify_r in verify_all(
+        &vc_jwt_v2,
+        &vc_ldp_v2,
+        &vc_jwt,
+        Some(&status_list_fetcher),
+        did_resolver_a.clone(),
+    )
+    .await
+    {
+        let err = verify_r.expect_err("pass");
+        tracing::debug!("expected error: {}", err);
+        assert!(err.to_string().contains("revoked"));
+    }
+
+    // Without a status list fetcher, the status can't be checked, so verification must fail.
+    let mut verification_report = verifier_resolver::VerificationReport::new();
+    let err = did_webplus_ssi::verify_vc_jwt_v2(
+        vc_jwt_v2.as_str(),
+        None,
+        None,
+        did_resolver_a.clone(),
+        &mut verification_report,
+    )
+    .await
+    .expect_err("pass");
+    tracing::debug!("expected error: {}", err);
+    let failed_check_v = verification_report.failed_check_iter().collect::<Vec<_>>();
+    assert_eq!(failed_check_v.len(), 1);
+    assert_eq!(
+        failed_check_v[0].kind,
+        verifier_resolver::VerificationCheckKind::CredentialStatus
+    );
+    for verify_r in verify_all(
+        &vc_jwt_v2,
+        &vc_ldp_v2,
+        &vc_jwt,
+        None,
+        did_resolver_a.clone(),
+    )
+    .await
+    {
+        assert!(verify_r.is_err());
+    }
+}
+
+/// Returns the results of verifying each of the given credentials, checking their status.
+async fn verify_all(
+    vc_jwt_v2: &did_webplus_jws::JWS<'static>,
+    vc_ldp_v2: &ssi_claims::data_integrity::DataIntegrity<
+        ssi_claims::vc::v2::JsonCredential,
+        ssi_claims::data_integrity::AnySuite,
+    >,
+    vc_jwt: &ssi_jws::JwsBuf,
+    status_list_fetcher_o: Option<&dyn did_webplus_ssi::StatusListFetcher>,
+    did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver>,
+) -> Vec<did_webplus_ssi::Result<()>> {
+    vec![
+        did_webplus_ssi::verify_vc_jwt_v2(
+            vc_jwt_v2.as_str(),
+            status_list_fetcher_o,
+            None,
+            did_resolver_a.clone(),
+            &mut verifier_resolver::VerificationReport::new(),
+        )
+        .await
+        .map(|_| ()),
+        did_webplus_ssi::verify_vc_ldp_v2(
+            vc_ldp_v2,
+            status_list_fetcher_o,
+            None,
+            did_resolver_a.clone(),
+            &mut verifier_resolver::VerificationReport::new(),
+        )
+        .await
+        .map(|verification| verification.expect("pass")),
+        did_webplus_ssi::verify_vc_jwt(
+            vc_jwt,
+            status_list_fetcher_o,
+            None,
+            did_resolver_a,
+            &mut verifier_resolver::VerificationReport::new(),
+        )
+        .await
+        .map(|verification| verification.expect("pass")),
+    ]
+}
+
+#[tokio::test]
+async fn test_ssi_status_list() {
+    // TODO: Use env vars to be able to point to a "real" VDR.
+
+    let vdr_database_url = "postgres:///test_ssi_status_list_vdr";
+    let vdr_port = 13088;
+    let wallet_store_database_path = "tests/test_ssi_status_list.wallet-store.db";
+
+    let (vdr_handle, vdr_did_create_endpoint, software_wallet) =
+        setup_vdr_and_wallet(vdr_database_url, vdr_port, wallet_store_database_path).await;
+
+    test_ssi_status_list_impl(&software_wallet, &vdr_did_create_endpoint).await;
+
+    tracing::info!("Shutting down VDR");
+    vdr_handle.abort();
+}
+
+async fn test_ssi_did_webplus_version_time_impl(
+    software_wallet: &did_webplus_software_wallet::SoftwareWallet,
+    vdr_did_create_endpoint: &str,
+) {
+    let did_resolver_a: Arc<dyn did_webplus_resolver::DIDResolver> = {
+        let db_url = "sqlite://:memory:";
+        let did_doc_storage =
+            did_webplus_doc_storage_sqlite::DIDDocStorageSQLite::open_url_and_run_migrations(
+                db_url, None,
+            )
+            .await
+            .expect("pass");
+        let did_doc_store = did_webplus_doc_store::DIDDocStore::new(Arc::new(did_doc_storage));
+        let did_resolver_full =
+            did_webplus_resolver::DIDResolverFull::new(did_doc_store, None, None).expect("pass");
+        Arc::new(did_resolver_full)
+    };
+
+    // Have the wallet create a DID and update it twice.
+    use did_webplus_wallet::Wallet;
+    let mb_hash_function = selfhash::MBHashFunction::blake3(mbx::Base::Base64Url);
+    let controlled_did = software_wallet
+        .create_did(
+            did_webplus_wallet::CreateDIDParameters {
+                vdr_did_create_endpoint: vdr_did_create_endpoint,
+                mb_hash_function_for_did: &mb_hash_function,
+                mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                vdr_authz_key_id_o: None,
+            },
+            None,
+        )
+        .await
+        .expect("pass");
+    let did = controlled_did.did().to_owned();
+    for _ in 0..2 {
+        software_wallet
+            .update_did(
+                did_webplus_wallet::UpdateDIDParameters {
+                    did: &did,
+                    change_mb_hash_function_for_self_hash_o: None,
+                    mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                },
+                None,
+            )
+            .await
+            .expect("pass");
+    }
+    let mut valid_from_v = Vec::new();
+    for version_id in 0..3 {
+        let (did_document, _, _) = did_resolver_a
+            .resolve_did_document(
+                did.with_query_version_id(version_id).as_str(),
+                did_webplus_core::DIDResolutionOptions::no_metadata(false),
+            )
+            .await
+            .expect("pass");
+        valid_from_v.push(did_document.valid_from().expect("pass"));
+    }
+    tracing::debug!("valid_from_v: {:?}", valid_from_v);
+
+    let millisecond = time::Duration::milliseconds(1);
+    for (version_time, expected_version_id) in [
+        (valid_from_v[0], 0),
+        (valid_from_v[1] - millisecond, 0),
+        (valid_from_v[1], 1),
+        (valid_from_v[2] - millisecond, 1),
+        (valid_from_v[2], 2),
+        (valid_from_v[2] + time::Duration::days(1), 2),
+    ] {
+        let (did_document_string, did_document_metadata, _) =
+            did_webplus_resolver::resolve_did_document_string_at_version_time(
+                did_resolver_a.as_ref(),
+                &did,
+                version_time,
+                did_webplus_core::DIDResolutionOptions::all_metadata(false),
+            )
+            .await
+            .expect("pass");
+        let did_document: did_webplus_core::DIDDocument =
+            serde_json::from_str(did_document_string.as_str()).expect("pass");
+        assert_eq!(did_document.version_id, expected_version_id);
+        assert_eq!(did_document_metadata.deactivated_o, Some(false));
+        assert_eq!(
+            did_document_metadata.creation_time_milliseconds_o(),
+            Some(valid_from_v[0])
+        );
+        assert_eq!(
+            did_document_metadata.latest_update_version_id_o(),
+            Some("2")
+        );
+    }
+    // Before the DID was created.
+    assert!(
+        did_webplus_resolver::resolve_did_document_string_at_version_time(
+            did_resolver_a.as_ref(),
+            &did,
+            valid_from_v[0] - millisecond,
+            did_webplus_core::DIDResolutionOptions::no_metadata(false),
+        )
+        .await
+        .is_err()
+    );
+
+    // Now do the same via ssi's DID method resolution, as used by ssi-based verifiers.
+    use ssi_dids::DIDMethodResolver;
+    let did_webplus = did_webplus_ssi::DIDWebplus {
+        did_resolver_a: did_resolver_a.clone(),
+    };
+    let method_specific_id = did.as_str().strip_prefix("did:webplus:").unwrap();
+    let version_time_string = |version_time: time::OffsetDateTime| {
+        version_time
+            .format(&time::format_description::well_known::Rfc3339)
+            .expect("pass")
+    };
+    for (version_time, expected_version_id) in [
+        (valid_from_v[1] - millisecond, 0),
+        (valid_from_v[1], 1),
+        (valid_from_v[2] + time::Duration::days(1), 2),
+    ] {
+        let mut options = ssi_dids::resolution::Options::default();
+        options.parameters.version_time = Some(version_time_string(version_time));
+        let output = did_webplus
+            .resolve_method_representation(method_specific_id, options)
+            .await
+            .expect("pass");
+        let did_document: did_webplus_core::DIDDocument =
+            serde_json::from_slice(output.document.as_slice()).expect("pass");
+        assert_eq!(did_document.version_id, expected_version_id);
+        assert_eq!(output.document_metadata.deactivated, Some(false));
+    }
+    // Local-only resolution works, since this DID is already known locally.
+    {
+        let mut options = ssi_dids::resolution::Options::default();
+        options.parameters.additional.insert(
+            "localResolutionOnly".to_string(),
+            ssi_dids::resolution::Parameter::String("true".to_string()),
+        );
+        let output = did_webplus
+            .resolve_method_representation(method_specific_id, options)
+            .await
+            .expect("pass");
+        let did_document: did_webplus_core::DIDDocument =
+            serde_json::from_slice(output.document.as_slice()).expect("pass");
+        assert_eq!(did_document.version_id, 2);
+    }
+    // versionTime can't be combined with versionId.
+    {
+        let mut options = ssi_dids::resolution::Options::default();
+        options.parameters.version_time = Some(version_time_string(valid_from_v[1]));
+        options.parameters.version_id = Some("1".to_string());
+        assert!(did_webplus
+            .resolve_method_representation(method_specific_id, options)
+            .await
+            .is_err());
+    }
+    // Malformed versionTime.
+    {
+        let mut options = ssi_dids::resolution::Options::default();
+        options.parameters.version_time = Some("yesterday".to_string());
+        assert!(did_webplus
+            .resolve_method_representation(method_specific_id, options)
+            .await
+            .is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_ssi_did_webplus_version_time() {
+    // TODO: Use env vars to be able to point to a "real" VDR.
+
+    let vdr_database_url = "postgres:///test_ssi_did_webplus_version_time_vdr";
+    let vdr_port = 13089;
+    let wallet_store_database_path = "tests/test_ssi_did_webplus_version_time.wallet-store.db";
+
+    let (vdr_handle, vdr_did_create_endpoint, software_wallet) =
+        setup_vdr_and_wallet(vdr_database_url, vdr_port, wallet_store_database_path).await;
+
+    test_ssi_did_webplus_version_time_impl(&software_wallet, &vdr_did_create_endpoint).await;
+
+    tracing::info!("Shutting down VDR");
+    vdr_handle.abort();
+}
+
 async fn setup_vdr_and_wallet(
     vdr_database_url: &str,
     vdr_port: u16,
@@ -538,6 +2147,10 @@ async fn setup_vdr_and_wallet(
         vdg_base_url_v: Vec::new(),
         http_scheme_override: Default::default(),
         test_authz_api_key_vo: None,
+        registrar_permissions_o: None,
+        serve_did_web: false,
+        audit_interval_seconds_o: None,
+        admission_policy_config: Default::default(),
     };
     let vdr_handle = did_webplus_vdr_lib::spawn_vdr(vdr_config.clone())
         .await
@@ -115,6 +115,9 @@ async fn resolve_did(
             did_webplus_resolver::Error::GenericError(description) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, description.into_owned())
             }
+            did_webplus_resolver::Error::InvalidDIDBundle(description) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, description.into_owned())
+            }
             did_webplus_resolver::Error::InvalidVerifier(description) => {
                 (StatusCode::BAD_REQUEST, description.into_owned())
             }
@@ -19,6 +19,10 @@ async fn test_urd_with_full_did_resolver_without_vdg() {
         vdg_base_url_v: Vec::new(),
         http_scheme_override: Default::default(),
         test_authz_api_key_vo: None,
+        registrar_permissions_o: None,
+        serve_did_web: false,
+        audit_interval_seconds_o: None,
+        admission_policy_config: Default::default(),
     };
     let vdr_handle = did_webplus_vdr_lib::spawn_vdr(vdr_config.clone())
         .await
@@ -77,6 +81,7 @@ async fn test_urd_with_full_did_resolver_without_vdg() {
                 vdr_did_create_endpoint: &vdr_url,
                 mb_hash_function_for_did: &mb_hash_function,
                 mb_hash_function_for_update_key_o: Some(&mb_hash_function),
+                vdr_authz_key_id_o: None,
             },
             None,
         )
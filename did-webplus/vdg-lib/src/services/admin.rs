@@ -0,0 +1,39 @@
+use crate::VDGAppState;
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    routing::get,
+};
+
+/// These routes must only be served if admin API keys are configured; see VDGConfig::admin_api_key_vo.
+pub fn get_routes(vdg_app_state: VDGAppState) -> Router {
+    Router::new()
+        .route("/webplus/v1/admin/keys/{:key}", get(get_did_key_records))
+        .with_state(vdg_app_state)
+}
+
+/// Returns the DIDKeyRecord-s for the given key as a JSON array, i.e. which DIDs and DID document versions
+/// contain it.  The key can be an MBPubKey (in which case hashed update keys are found as well, see
+/// DIDDocStore::find_did_key_records) or the MBHash of a hashed update key.
+#[tracing::instrument(err(Debug), skip(vdg_app_state, header_map))]
+async fn get_did_key_records(
+    State(vdg_app_state): State<VDGAppState>,
+    header_map: HeaderMap,
+    Path(key): Path<String>,
+) -> Result<(HeaderMap, String), (StatusCode, String)> {
+    vdg_app_state.verify_admin_authorization(&header_map)?;
+
+    let did_key_record_v = vdg_app_state
+        .did_doc_store
+        .find_did_key_records(None, &key)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut response_header_map = HeaderMap::new();
+    response_header_map.insert("Content-Type", "application/json".parse().unwrap());
+    Ok((
+        response_header_map,
+        serde_json::to_string(&did_key_record_v).unwrap(),
+    ))
+}
@@ -23,6 +23,16 @@ pub async fn spawn_vdg(vdg_config: VDGConfig) -> anyhow::Result<tokio::task::Joi
                 .await?,
             ));
 
+            let periodic_audit_task_o =
+                vdg_config
+                    .audit_interval_seconds_o
+                    .map(|audit_interval_seconds| {
+                        did_webplus_doc_store::PeriodicAuditTask::spawn(
+                            did_doc_store.clone(),
+                            std::time::Duration::from_secs(audit_interval_seconds),
+                        )
+                    });
+
             let middleware_stack = tower::ServiceBuilder::new()
                 .layer(tower_http::compression::CompressionLayer::new())
                 .layer(
@@ -42,7 +52,12 @@ pub async fn spawn_vdg(vdg_config: VDGConfig) -> anyhow::Result<tokio::task::Joi
                 vdg_config: vdg_config.clone(),
             };
 
-            let app = axum::Router::new()
+            let mut app = axum::Router::new();
+            // The admin endpoints are opt-in, since they expose information about all cached DIDs.
+            if vdg_config.admin_api_key_vo.is_some() {
+                app = app.merge(crate::services::admin::get_routes(vdg_app_state.clone()));
+            }
+            let app = app
                 .merge(crate::services::did_resolve::get_routes(vdg_app_state))
                 .layer(middleware_stack)
                 .route("/health", axum::routing::get(|| async { "OK" }));
@@ -61,6 +76,8 @@ pub async fn spawn_vdg(vdg_config: VDGConfig) -> anyhow::Result<tokio::task::Joi
             // TODO: Use Serve::with_graceful_shutdown to be able to shutdown the server gracefully, in case aborting
             // the task isn't good enough.
             Ok(tokio::task::spawn(async move {
+                // The audit task (if any) is aborted when this task ends or is aborted.
+                let _periodic_audit_task_o = periodic_audit_task_o;
                 // TODO: Figure out if error handling is needed here.
                 let _ = axum::serve(listener, app).await;
             }))
@@ -47,4 +47,64 @@ impl VDGAppState {
             Ok(())
         }
     }
+    /// Checks that the request presents one of the configured admin API keys as a bearer token.  The admin
+    /// endpoints are only served if admin API keys are configured, but this denies the request regardless
+    /// if none are.
+    pub fn verify_admin_authorization(
+        &self,
+        header_map: &axum::http::HeaderMap,
+    ) -> Result<(), (axum::http::StatusCode, String)> {
+        let Some(admin_api_key_v) = self.vdg_config.admin_api_key_vo.as_deref() else {
+            tracing::error!("admin endpoints are not enabled");
+            return Err((
+                axum::http::StatusCode::UNAUTHORIZED,
+                "admin endpoints are not enabled".to_string(),
+            ));
+        };
+        let Some(authorization) = header_map.get(axum::http::header::AUTHORIZATION) else {
+            tracing::error!("required admin API key not provided");
+            return Err((
+                axum::http::StatusCode::UNAUTHORIZED,
+                "admin API key not provided".to_string(),
+            ));
+        };
+        let api_key = authorization
+            .to_str()
+            .ok()
+            .and_then(|authorization| authorization.strip_prefix("Bearer "))
+            .ok_or_else(|| {
+                (
+                    axum::http::StatusCode::BAD_REQUEST,
+                    "malformed Authorization header; expected \"Bearer <admin API key>\""
+                        .to_string(),
+                )
+            })?;
+        // Compare against every admin API key in constant time, so that response timing doesn't reveal
+        // how much of a key was guessed correctly.
+        let is_authorized = admin_api_key_v
+            .iter()
+            .fold(false, |is_authorized, admin_api_key| {
+                is_authorized | constant_time_eq(admin_api_key.as_bytes(), api_key.as_bytes())
+            });
+        if is_authorized {
+            tracing::debug!("admin API key authorized");
+            Ok(())
+        } else {
+            tracing::error!("admin API key not authorized");
+            Err((
+                axum::http::StatusCode::UNAUTHORIZED,
+                "admin API key not authorized".to_string(),
+            ))
+        }
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter()
+        .zip(b.iter())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
 }
@@ -67,6 +67,33 @@ pub struct VDGConfig {
         value_parser = parse_comma_separated_api_keys_into_strings,
     )]
     pub test_authz_api_key_vo: Option<Vec<String>>,
+    /// Optionally specify a comma-delimited list of admin API keys, which enables the admin endpoints
+    /// (i.e. /webplus/v1/admin/keys/{key}).  Requests to the admin endpoints must present one of these keys
+    /// in an "Authorization: Bearer <key>" HTTP header, independently of --test-authz-api-keys.  If this is
+    /// not set, then the admin endpoints are not served at all.  Note that each string in the list will be
+    /// whitespace-trimmed before being parsed.
+    #[arg(
+        name = "admin-api-keys",
+        env = "DID_WEBPLUS_VDG_ADMIN_API_KEYS",
+        long,
+        value_name = "API_KEYS",
+        default_value = None,
+        value_parser = parse_comma_separated_api_keys_into_strings,
+    )]
+    pub admin_api_key_vo: Option<Vec<String>>,
+    /// Optionally specify the number of seconds between audits of the VDG's DID doc store, in which every
+    /// cached microledger is re-verified from its root DID document (see DIDDocStore::audit).  Any corrupted
+    /// or orphaned records that are found are logged at error level.  If this is not set, no audits are done.
+    /// The interval may be at most 100 years.
+    #[arg(
+        name = "audit-interval",
+        env = "DID_WEBPLUS_VDG_AUDIT_INTERVAL",
+        long,
+        value_name = "SECONDS",
+        value_parser = clap::value_parser!(u64)
+            .range(1..=did_webplus_doc_store::PeriodicAuditTask::MAX_INTERVAL_SECONDS)
+    )]
+    pub audit_interval_seconds_o: Option<u64>,
 }
 
 fn parse_comma_separated_api_keys_into_strings(s: &str) -> anyhow::Result<Option<Vec<String>>> {
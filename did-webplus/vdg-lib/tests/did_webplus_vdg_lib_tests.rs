@@ -12,6 +12,7 @@ fn overall_init() {
 }
 
 const TEST_AUTHZ_API_KEY: &str = "it's.....";
+const TEST_ADMIN_API_KEY: &str = "...the admin!";
 
 // const CACHE_DAYS: u64 = 365;
 
@@ -55,6 +56,8 @@ async fn test_vdg_operations() {
             TEST_AUTHZ_API_KEY.to_string(),
             "yet another test api key".to_string(),
         ]),
+        admin_api_key_vo: Some(vec![TEST_ADMIN_API_KEY.to_string()]),
+        audit_interval_seconds_o: None,
     };
     let vdg_handle = did_webplus_vdg_lib::spawn_vdg(vdg_config.clone())
         .await
@@ -71,6 +74,10 @@ async fn test_vdg_operations() {
         vdg_base_url_v: vec![vdg_base_url.clone()],
         http_scheme_override: Default::default(),
         test_authz_api_key_vo: None,
+        registrar_permissions_o: None,
+        serve_did_web: false,
+        audit_interval_seconds_o: None,
+        admission_policy_config: Default::default(),
     };
     let vdr_handle = did_webplus_vdr_lib::spawn_vdr(vdr_config.clone())
         .await
@@ -105,6 +112,45 @@ async fn test_vdg_operations() {
     )
     .await;
 
+    tracing::info!("Testing admin endpoint authorization");
+    let admin_keys_url = vdg_base_url
+        .join("webplus/v1/admin/keys/uHiBKHZUE3HHlYcyVIF-vPm0Xg71vqJla2L1OGXHMSK4NEA")
+        .expect("pass");
+    // The admin endpoint requires the admin API key as a bearer token; the test authz API key doesn't suffice.
+    for (header_name, header_value, expected_status) in [
+        (None, None, reqwest::StatusCode::UNAUTHORIZED),
+        (
+            Some("x-api-key"),
+            Some(TEST_AUTHZ_API_KEY.to_string()),
+            reqwest::StatusCode::UNAUTHORIZED,
+        ),
+        (
+            Some("authorization"),
+            Some(format!("Bearer {}", TEST_AUTHZ_API_KEY)),
+            reqwest::StatusCode::UNAUTHORIZED,
+        ),
+        (
+            Some("authorization"),
+            Some(TEST_ADMIN_API_KEY.to_string()),
+            reqwest::StatusCode::BAD_REQUEST,
+        ),
+        (
+            Some("authorization"),
+            Some(format!("Bearer {}", TEST_ADMIN_API_KEY)),
+            reqwest::StatusCode::OK,
+        ),
+    ] {
+        let mut request_builder = test_util::REQWEST_CLIENT.get(admin_keys_url.clone());
+        if let (Some(header_name), Some(header_value)) = (header_name, header_value) {
+            request_builder = request_builder.header(header_name, header_value);
+        }
+        let response = request_builder.send().await.expect("pass");
+        assert_eq!(response.status(), expected_status);
+        if expected_status == reqwest::StatusCode::OK {
+            assert_eq!(response.text().await.expect("pass"), "[]");
+        }
+    }
+
     tracing::info!("Shutting down VDG");
     vdg_handle.abort();
 
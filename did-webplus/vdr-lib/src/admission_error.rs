@@ -0,0 +1,46 @@
+use std::borrow::Cow;
+
+/// The reason an AdmissionPolicy rejected a DID create or update request.  Each variant maps to a specific
+/// HTTP status code (see AdmissionError::status_code).
+#[derive(Debug, thiserror::Error)]
+pub enum AdmissionError {
+    #[error("DID document too large: {0}")]
+    DIDDocumentTooLarge(Cow<'static, str>),
+    #[error("Disallowed DID path: {0}")]
+    DisallowedDIDPath(Cow<'static, str>),
+    #[error("Disallowed hash function: {0}")]
+    DisallowedHashFunction(Cow<'static, str>),
+    #[error("Disallowed key type: {0}")]
+    DisallowedKeyType(Cow<'static, str>),
+    #[error("Rejected: {0}")]
+    Rejected(Cow<'static, str>),
+    #[error("Too many verification methods: {0}")]
+    TooManyVerificationMethods(Cow<'static, str>),
+    #[error("Update too soon: {0}")]
+    UpdateTooSoon(Cow<'static, str>),
+    #[error("validFrom out of bounds: {0}")]
+    ValidFromOutOfBounds(Cow<'static, str>),
+}
+
+impl AdmissionError {
+    /// The HTTP status code that the VDR responds with when a request is rejected for this reason.
+    pub fn status_code(&self) -> axum::http::StatusCode {
+        use axum::http::StatusCode;
+        match self {
+            Self::DIDDocumentTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::DisallowedDIDPath(_) => StatusCode::BAD_REQUEST,
+            Self::DisallowedHashFunction(_)
+            | Self::DisallowedKeyType(_)
+            | Self::Rejected(_)
+            | Self::TooManyVerificationMethods(_)
+            | Self::ValidFromOutOfBounds(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::UpdateTooSoon(_) => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+}
+
+impl From<AdmissionError> for (axum::http::StatusCode, String) {
+    fn from(admission_error: AdmissionError) -> Self {
+        (admission_error.status_code(), admission_error.to_string())
+    }
+}
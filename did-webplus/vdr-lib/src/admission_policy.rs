@@ -0,0 +1,62 @@
+use crate::AdmissionError;
+use did_webplus_core::DIDDocument;
+
+/// The DID create or update request that an AdmissionPolicy decides on.  The DID document has been parsed
+/// and the request has been authorized, but the DID document has not yet been validated or stored.
+#[derive(Clone, Copy, Debug)]
+pub struct AdmissionRequest<'a> {
+    /// The DID document being created or added as an update.
+    pub did_document: &'a DIDDocument,
+    /// The body of the request, i.e. the JCS-serialized DID document.
+    pub did_document_body: &'a str,
+    /// The latest DID document already stored by the VDR, if this is an update.  This is None for DID creation.
+    pub prev_did_document_o: Option<&'a DIDDocument>,
+    /// The time at which the VDR received the request.
+    pub now_utc: time::OffsetDateTime,
+}
+
+impl AdmissionRequest<'_> {
+    pub fn is_create(&self) -> bool {
+        self.prev_did_document_o.is_none()
+    }
+}
+
+/// A hook that the VDR calls on each DID create and update request before storing the DID document, so that
+/// the operator of the VDR can restrict what it hosts beyond what the did:webplus spec requires (e.g. key
+/// types, document size, or update frequency).  See VDRConfig for the built-in policies, and
+/// spawn_vdr_with_admission_policies for how to add others.
+#[async_trait::async_trait]
+pub trait AdmissionPolicy: Send + Sync {
+    /// Returns Ok(()) if the request is admitted, and otherwise the reason it was rejected.
+    async fn admit(&self, admission_request: &AdmissionRequest<'_>) -> Result<(), AdmissionError>;
+}
+
+/// An AdmissionPolicy that admits a request iff every one of the given policies does, checking them in order.
+#[derive(Clone, Default)]
+pub struct AdmissionPolicies {
+    admission_policy_v: Vec<std::sync::Arc<dyn AdmissionPolicy>>,
+}
+
+impl AdmissionPolicies {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Builder-style method to add a policy.
+    pub fn with_policy(mut self, admission_policy_a: std::sync::Arc<dyn AdmissionPolicy>) -> Self {
+        self.admission_policy_v.push(admission_policy_a);
+        self
+    }
+    pub fn is_empty(&self) -> bool {
+        self.admission_policy_v.is_empty()
+    }
+}
+
+#[async_trait::async_trait]
+impl AdmissionPolicy for AdmissionPolicies {
+    async fn admit(&self, admission_request: &AdmissionRequest<'_>) -> Result<(), AdmissionError> {
+        for admission_policy in self.admission_policy_v.iter() {
+            admission_policy.admit(admission_request).await?;
+        }
+        Ok(())
+    }
+}
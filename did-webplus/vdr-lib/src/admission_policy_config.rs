@@ -0,0 +1,207 @@
+use crate::{
+    AdmissionPolicies, AllowedHashFunctionsAdmissionPolicy, AllowedKeyTypesAdmissionPolicy,
+    DIDDocumentLimitsAdmissionPolicy, DIDPathAdmissionPolicy, MinUpdateIntervalAdmissionPolicy,
+    ValidFromBoundsAdmissionPolicy, VerificationMethodKeyType,
+};
+use std::{str::FromStr, sync::Arc};
+
+/// The largest number of seconds accepted for the time-interval arguments (100 years), so that adding them
+/// to or subtracting them from the current time can't overflow.
+const MAX_ADMISSION_INTERVAL_SECONDS: u64 = 100 * 365 * 24 * 60 * 60;
+
+/// Configuration for the built-in admission policies that the VDR applies to DID create and update requests
+/// before storing the DID document.  Each policy is disabled unless at least one of its arguments is set.
+/// Rejected requests get a 400 (DID path), 413 (DID document size), 422 (key type, hash function, number
+/// of verification methods, validFrom), or 429 (update interval) response.
+// NOTE: It's critical that the Vec types be fully qualified as `std::vec::Vec`;
+// see https://github.com/clap-rs/clap/issues/4481#issuecomment-1314475143
+#[derive(clap::Args, Clone, Debug, Default)]
+pub struct AdmissionPolicyConfig {
+    /// Optionally specify a comma-separated list of the key types (e.g. "Ed25519,P256,X25519") that
+    /// verification methods in hosted DID documents may use.  X25519 is only usable for keyAgreement.
+    #[arg(
+        name = "admission-allowed-key-types",
+        env = "DID_WEBPLUS_VDR_ADMISSION_ALLOWED_KEY_TYPES",
+        long,
+        value_name = "KEY_TYPES",
+        value_parser = parse_comma_separated_key_types,
+    )]
+    pub allowed_key_type_vo: Option<std::vec::Vec<VerificationMethodKeyType>>,
+    /// Optionally specify a comma-separated list of the hash functions (e.g. "BLAKE3") that the
+    /// selfHash of hosted DID documents may use.
+    #[arg(
+        name = "admission-allowed-hash-functions",
+        env = "DID_WEBPLUS_VDR_ADMISSION_ALLOWED_HASH_FUNCTIONS",
+        long,
+        value_name = "HASH_FUNCTIONS",
+        value_parser = parse_comma_separated_hash_functions,
+    )]
+    pub allowed_hash_function_vo: Option<std::vec::Vec<selfhash::NamedHashFunction>>,
+    /// Optionally specify the maximum size, in bytes, of a hosted DID document.
+    #[arg(
+        name = "admission-max-did-document-size",
+        env = "DID_WEBPLUS_VDR_ADMISSION_MAX_DID_DOCUMENT_SIZE",
+        long,
+        value_name = "BYTES"
+    )]
+    pub max_did_document_size_o: Option<usize>,
+    /// Optionally specify the maximum number of verification methods in a hosted DID document.
+    #[arg(
+        name = "admission-max-verification-methods",
+        env = "DID_WEBPLUS_VDR_ADMISSION_MAX_VERIFICATION_METHODS",
+        long,
+        value_name = "COUNT"
+    )]
+    pub max_verification_method_count_o: Option<usize>,
+    /// Optionally specify the minimum number of seconds between the validFrom of a DID's latest DID document
+    /// and the VDR's receipt of an update to that DID.
+    #[arg(
+        name = "admission-min-update-interval",
+        env = "DID_WEBPLUS_VDR_ADMISSION_MIN_UPDATE_INTERVAL",
+        long,
+        value_name = "SECONDS",
+        value_parser = clap::value_parser!(u64).range(..=MAX_ADMISSION_INTERVAL_SECONDS)
+    )]
+    pub min_update_interval_seconds_o: Option<u64>,
+    /// Optionally specify how many seconds before the VDR's current time the validFrom of a created or
+    /// updated DID document may be.
+    #[arg(
+        name = "admission-valid-from-max-past",
+        env = "DID_WEBPLUS_VDR_ADMISSION_VALID_FROM_MAX_PAST",
+        long,
+        value_name = "SECONDS",
+        value_parser = clap::value_parser!(u64).range(..=MAX_ADMISSION_INTERVAL_SECONDS)
+    )]
+    pub valid_from_max_past_seconds_o: Option<u64>,
+    /// Optionally specify how many seconds after the VDR's current time the validFrom of a created or
+    /// updated DID document may be, to allow for clock skew between the VDR and its clients.
+    #[arg(
+        name = "admission-valid-from-max-future",
+        env = "DID_WEBPLUS_VDR_ADMISSION_VALID_FROM_MAX_FUTURE",
+        long,
+        value_name = "SECONDS",
+        value_parser = clap::value_parser!(u64).range(..=MAX_ADMISSION_INTERVAL_SECONDS)
+    )]
+    pub valid_from_max_future_seconds_o: Option<u64>,
+    /// Optionally specify the minimum number of colon-separated path components of a created DID.
+    #[arg(
+        name = "admission-did-path-min-components",
+        env = "DID_WEBPLUS_VDR_ADMISSION_DID_PATH_MIN_COMPONENTS",
+        long,
+        value_name = "COUNT"
+    )]
+    pub did_path_min_component_count_o: Option<usize>,
+    /// Optionally specify the maximum number of colon-separated path components of a created DID.
+    #[arg(
+        name = "admission-did-path-max-components",
+        env = "DID_WEBPLUS_VDR_ADMISSION_DID_PATH_MAX_COMPONENTS",
+        long,
+        value_name = "COUNT"
+    )]
+    pub did_path_max_component_count_o: Option<usize>,
+    /// Optionally specify the maximum length, in bytes, of each path component of a created DID.
+    #[arg(
+        name = "admission-did-path-max-component-length",
+        env = "DID_WEBPLUS_VDR_ADMISSION_DID_PATH_MAX_COMPONENT_LENGTH",
+        long,
+        value_name = "BYTES"
+    )]
+    pub did_path_max_component_length_o: Option<usize>,
+    /// Optionally specify the characters that path components of a created DID may contain, e.g.
+    /// "abcdefghijklmnopqrstuvwxyz0123456789-".
+    #[arg(
+        name = "admission-did-path-allowed-chars",
+        env = "DID_WEBPLUS_VDR_ADMISSION_DID_PATH_ALLOWED_CHARS",
+        long,
+        value_name = "CHARS"
+    )]
+    pub did_path_allowed_chars_o: Option<String>,
+}
+
+impl AdmissionPolicyConfig {
+    /// Produce the built-in admission policies enabled by this configuration.
+    pub fn admission_policies(&self) -> AdmissionPolicies {
+        let mut admission_policies = AdmissionPolicies::new();
+        if let Some(allowed_key_type_v) = self.allowed_key_type_vo.as_ref() {
+            admission_policies = admission_policies.with_policy(Arc::new(
+                AllowedKeyTypesAdmissionPolicy::new(allowed_key_type_v.clone()),
+            ));
+        }
+        if let Some(allowed_hash_function_v) = self.allowed_hash_function_vo.as_ref() {
+            admission_policies = admission_policies.with_policy(Arc::new(
+                AllowedHashFunctionsAdmissionPolicy::new(allowed_hash_function_v.clone()),
+            ));
+        }
+        if self.max_did_document_size_o.is_some() || self.max_verification_method_count_o.is_some()
+        {
+            admission_policies =
+                admission_policies.with_policy(Arc::new(DIDDocumentLimitsAdmissionPolicy {
+                    max_did_document_size_o: self.max_did_document_size_o,
+                    max_verification_method_count_o: self.max_verification_method_count_o,
+                }));
+        }
+        if let Some(min_update_interval_seconds) = self.min_update_interval_seconds_o {
+            admission_policies =
+                admission_policies.with_policy(Arc::new(MinUpdateIntervalAdmissionPolicy::new(
+                    duration_from_seconds(min_update_interval_seconds),
+                )));
+        }
+        if self.valid_from_max_past_seconds_o.is_some()
+            || self.valid_from_max_future_seconds_o.is_some()
+        {
+            admission_policies =
+                admission_policies.with_policy(Arc::new(ValidFromBoundsAdmissionPolicy {
+                    max_past_o: self
+                        .valid_from_max_past_seconds_o
+                        .map(duration_from_seconds),
+                    max_future_o: self
+                        .valid_from_max_future_seconds_o
+                        .map(duration_from_seconds),
+                }));
+        }
+        if self.did_path_min_component_count_o.is_some()
+            || self.did_path_max_component_count_o.is_some()
+            || self.did_path_max_component_length_o.is_some()
+            || self.did_path_allowed_chars_o.is_some()
+        {
+            admission_policies = admission_policies.with_policy(Arc::new(DIDPathAdmissionPolicy {
+                min_component_count_o: self.did_path_min_component_count_o,
+                max_component_count_o: self.did_path_max_component_count_o,
+                max_component_length_o: self.did_path_max_component_length_o,
+                allowed_chars_o: self.did_path_allowed_chars_o.clone(),
+            }));
+        }
+        admission_policies
+    }
+}
+
+/// Since the fields of AdmissionPolicyConfig are public, they can be set beyond the bound that the CLI args
+/// enforce, so the number of seconds is clamped to MAX_ADMISSION_INTERVAL_SECONDS here as well.
+fn duration_from_seconds(seconds: u64) -> time::Duration {
+    time::Duration::seconds(seconds.min(MAX_ADMISSION_INTERVAL_SECONDS) as i64)
+}
+
+fn parse_comma_separated_key_types(s: &str) -> anyhow::Result<Vec<VerificationMethodKeyType>> {
+    s.split(',')
+        .map(|key_type_str| {
+            VerificationMethodKeyType::from_str(key_type_str.trim()).map_err(|e| anyhow::anyhow!(e))
+        })
+        .collect()
+}
+
+fn parse_comma_separated_hash_functions(
+    s: &str,
+) -> anyhow::Result<Vec<selfhash::NamedHashFunction>> {
+    s.split(',')
+        .map(|hash_function_str| {
+            let hash_function_str = hash_function_str.trim();
+            selfhash::NamedHashFunction::from_str(hash_function_str).map_err(|e| {
+                anyhow::anyhow!(
+                    "unrecognized hash function {:?}; error was: {}",
+                    hash_function_str,
+                    e
+                )
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,49 @@
+use crate::{AdmissionError, AdmissionPolicy, AdmissionRequest};
+
+/// Rejects DID documents whose selfHash uses a hash function that isn't in the given list.
+#[derive(Clone, Debug)]
+pub struct AllowedHashFunctionsAdmissionPolicy {
+    allowed_hash_function_v: Vec<selfhash::NamedHashFunction>,
+}
+
+impl AllowedHashFunctionsAdmissionPolicy {
+    pub fn new(allowed_hash_function_v: Vec<selfhash::NamedHashFunction>) -> Self {
+        Self {
+            allowed_hash_function_v,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AdmissionPolicy for AllowedHashFunctionsAdmissionPolicy {
+    async fn admit(&self, admission_request: &AdmissionRequest<'_>) -> Result<(), AdmissionError> {
+        let self_hash = &admission_request.did_document.self_hash;
+        let codec = self_hash
+            .decoded::<64>()
+            .map_err(|e| {
+                AdmissionError::DisallowedHashFunction(
+                    format!("malformed selfHash {}; error was: {}", self_hash, e).into(),
+                )
+            })?
+            .code();
+        let is_allowed = self
+            .allowed_hash_function_v
+            .iter()
+            .any(|allowed_hash_function| {
+                allowed_hash_function
+                    .as_mb_hash_function(self_hash.base())
+                    .codec()
+                    == codec
+            });
+        if !is_allowed {
+            return Err(AdmissionError::DisallowedHashFunction(
+                format!(
+                    "selfHash {} uses a hash function that isn't allowed; allowed hash functions are {:?}",
+                    self_hash, self.allowed_hash_function_v
+                )
+                .into(),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{AdmissionError, AdmissionPolicy, AdmissionRequest};
+
+/// The key type of a verification method, as determined by the multicodec of its pub key.  This is either
+/// a signature key type, or X25519, which is only usable for keyAgreement and so isn't a signature_dyn::KeyType.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VerificationMethodKeyType {
+    Signature(signature_dyn::KeyType),
+    X25519,
+}
+
+impl VerificationMethodKeyType {
+    /// Determine the key type of the given pub key from its multicodec.
+    pub fn of_pub_key(pub_key: &mbx::MBPubKeyStr) -> Result<Self, AdmissionError> {
+        if did_webplus_core::x25519_public_key_bytes_from_mb_pub_key(pub_key).is_ok() {
+            Ok(Self::X25519)
+        } else {
+            let key_type = pub_key
+                .try_into_key_type()
+                .map_err(|e| AdmissionError::DisallowedKeyType(e.to_string().into()))?;
+            Ok(Self::Signature(key_type))
+        }
+    }
+}
+
+impl From<signature_dyn::KeyType> for VerificationMethodKeyType {
+    fn from(key_type: signature_dyn::KeyType) -> Self {
+        Self::Signature(key_type)
+    }
+}
+
+impl std::fmt::Display for VerificationMethodKeyType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Signature(key_type) => key_type.fmt(f),
+            Self::X25519 => f.write_str("X25519"),
+        }
+    }
+}
+
+impl std::str::FromStr for VerificationMethodKeyType {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "X25519" {
+            Ok(Self::X25519)
+        } else {
+            let key_type = signature_dyn::KeyType::from_str(s)
+                .map_err(|_| format!("unrecognized key type {:?}", s))?;
+            Ok(Self::Signature(key_type))
+        }
+    }
+}
+
+/// Rejects DID documents having a verification method whose key type isn't in the given list.
+#[derive(Clone, Debug)]
+pub struct AllowedKeyTypesAdmissionPolicy {
+    allowed_key_type_v: Vec<VerificationMethodKeyType>,
+}
+
+impl AllowedKeyTypesAdmissionPolicy {
+    pub fn new(allowed_key_type_v: Vec<VerificationMethodKeyType>) -> Self {
+        Self { allowed_key_type_v }
+    }
+}
+
+#[async_trait::async_trait]
+impl AdmissionPolicy for AllowedKeyTypesAdmissionPolicy {
+    async fn admit(&self, admission_request: &AdmissionRequest<'_>) -> Result<(), AdmissionError> {
+        for verification_method in admission_request
+            .did_document
+            .public_key_material
+            .verification_method_v
+            .iter()
+        {
+            let pub_key = mbx::MBPubKey::try_from(&verification_method.public_key_jwk)
+                .map_err(|e| AdmissionError::DisallowedKeyType(e.to_string().into()))?;
+            let key_type = VerificationMethodKeyType::of_pub_key(&pub_key)?;
+            if !self.allowed_key_type_v.contains(&key_type) {
+                return Err(AdmissionError::DisallowedKeyType(
+                    format!(
+                        "verification method {} has key type {}, but allowed key types are {:?}",
+                        verification_method.id, key_type, self.allowed_key_type_v
+                    )
+                    .into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
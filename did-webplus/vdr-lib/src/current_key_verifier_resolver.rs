@@ -0,0 +1,139 @@
+use std::ops::Deref;
+
+use did_webplus_core::{DIDDocument, DIDKeyResourceFullyQualifiedStr};
+use did_webplus_doc_store::DIDDocStore;
+
+/// Resolves fully qualified did:webplus key IDs using the VDR's own DID doc store, additionally requiring
+/// that the key is in the current (i.e. latest) DID document of its DID.  This is used to verify the
+/// signatures that authorize DID create and update requests, for which keys that have been rotated out must
+/// not be accepted.  Because this never fetches DID documents from elsewhere, the signing DIDs must be hosted
+/// by this VDR.  If a pending root DID document (i.e. one being created) is specified, then its keys are
+/// resolvable as well.
+pub(crate) struct CurrentKeyVerifierResolver {
+    did_doc_store: DIDDocStore,
+    pending_root_did_document_o: Option<DIDDocument>,
+}
+
+impl CurrentKeyVerifierResolver {
+    pub(crate) fn new(
+        did_doc_store: DIDDocStore,
+        pending_root_did_document_o: Option<DIDDocument>,
+    ) -> Self {
+        Self {
+            did_doc_store,
+            pending_root_did_document_o,
+        }
+    }
+    async fn resolve_impl(
+        &self,
+        verifier_str: &str,
+        verification_relationship_o: Option<verifier_resolver::VerificationRelationship>,
+    ) -> verifier_resolver::Result<(Box<dyn signature_dyn::VerifierT>, String)> {
+        let key_id = DIDKeyResourceFullyQualifiedStr::new_ref(verifier_str).map_err(|_| {
+            verifier_resolver::Error::InvalidVerifier(
+                format!(
+                    "expected a fully qualified did:webplus key ID, but got {:?}",
+                    verifier_str
+                )
+                .into(),
+            )
+        })?;
+        let did = key_id.without_fragment().did();
+
+        let current_did_document = match self.pending_root_did_document_o.as_ref() {
+            Some(pending_root_did_document)
+                if pending_root_did_document.did.as_did_str() == did =>
+            {
+                pending_root_did_document.clone()
+            }
+            _ => {
+                let did_doc_record = self
+                    .did_doc_store
+                    .get_latest_known_did_doc_record(None, did)
+                    .await?
+                    .ok_or_else(|| {
+                        verifier_resolver::Error::InvalidVerifier(
+                            format!("DID {} is not hosted by this VDR", did).into(),
+                        )
+                    })?;
+                did_webplus_doc_store::parse_did_document(&did_doc_record.did_document_jcs)?
+            }
+        };
+        if key_id.query_version_id() != current_did_document.version_id
+            || key_id.query_self_hash() != current_did_document.self_hash.deref()
+        {
+            Err(verifier_resolver::Error::InvalidVerifier(
+                format!(
+                    "key {} is not in the current DID document of {}, which has selfHash {} and versionId {}",
+                    verifier_str,
+                    did,
+                    current_did_document.self_hash,
+                    current_did_document.version_id
+                )
+                .into(),
+            ))?;
+        }
+
+        let verification_method = current_did_document
+            .public_key_material
+            .verification_method_for_key_id_fragment(key_id.fragment())?;
+        if let Some(verification_relationship) = verification_relationship_o {
+            let key_purpose = did_webplus_core::KeyPurpose::from(verification_relationship);
+            if !current_did_document
+                .public_key_material
+                .key_purpose_flags_for_key_id_fragment(key_id.fragment())
+                .contains(key_purpose)
+            {
+                Err(verifier_resolver::Error::UnauthorizedVerifier(
+                    format!(
+                        "key {} is not listed under {} in its DID document",
+                        verifier_str, key_purpose
+                    )
+                    .into(),
+                ))?;
+            }
+        }
+        let pub_key = mbx::MBPubKey::try_from(&verification_method.public_key_jwk)?;
+        let verifier_bytes = signature_dyn::VerifierBytes::try_from(&pub_key)
+            .map_err(|e| verifier_resolver::Error::InvalidVerifier(e.to_string().into()))?;
+        let did_document_version = current_did_document
+            .did
+            .with_queries(
+                &current_did_document.self_hash,
+                current_did_document.version_id,
+            )
+            .to_string();
+        Ok((Box::new(verifier_bytes), did_document_version))
+    }
+}
+
+#[async_trait::async_trait]
+impl verifier_resolver::VerifierResolver for CurrentKeyVerifierResolver {
+    async fn resolve(
+        &self,
+        verifier_str: &str,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        let (verifier_b, _did_document_version) = self.resolve_impl(verifier_str, None).await?;
+        Ok(verifier_b)
+    }
+    async fn resolve_for_purpose(
+        &self,
+        verifier_str: &str,
+        verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        let (verifier_b, _did_document_version) = self
+            .resolve_impl(verifier_str, Some(verification_relationship))
+            .await?;
+        Ok(verifier_b)
+    }
+    async fn resolve_for_purpose_with_version(
+        &self,
+        verifier_str: &str,
+        verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<(Box<dyn signature_dyn::VerifierT>, Option<String>)> {
+        let (verifier_b, did_document_version) = self
+            .resolve_impl(verifier_str, Some(verification_relationship))
+            .await?;
+        Ok((verifier_b, Some(did_document_version)))
+    }
+}
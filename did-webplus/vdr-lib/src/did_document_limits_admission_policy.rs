@@ -0,0 +1,45 @@
+use crate::{AdmissionError, AdmissionPolicy, AdmissionRequest};
+
+/// Rejects DID documents that are too large, either in bytes or in number of verification methods.
+#[derive(Clone, Debug, Default)]
+pub struct DIDDocumentLimitsAdmissionPolicy {
+    /// Maximum size of the JCS-serialized DID document, in bytes.
+    pub max_did_document_size_o: Option<usize>,
+    /// Maximum number of verification methods in the DID document.
+    pub max_verification_method_count_o: Option<usize>,
+}
+
+#[async_trait::async_trait]
+impl AdmissionPolicy for DIDDocumentLimitsAdmissionPolicy {
+    async fn admit(&self, admission_request: &AdmissionRequest<'_>) -> Result<(), AdmissionError> {
+        if let Some(max_did_document_size) = self.max_did_document_size_o {
+            let did_document_size = admission_request.did_document_body.len();
+            if did_document_size > max_did_document_size {
+                return Err(AdmissionError::DIDDocumentTooLarge(
+                    format!(
+                        "DID document is {} bytes, which exceeds the maximum of {} bytes",
+                        did_document_size, max_did_document_size
+                    )
+                    .into(),
+                ));
+            }
+        }
+        if let Some(max_verification_method_count) = self.max_verification_method_count_o {
+            let verification_method_count = admission_request
+                .did_document
+                .public_key_material
+                .verification_method_v
+                .len();
+            if verification_method_count > max_verification_method_count {
+                return Err(AdmissionError::TooManyVerificationMethods(
+                    format!(
+                        "DID document has {} verification methods, which exceeds the maximum of {}",
+                        verification_method_count, max_verification_method_count
+                    )
+                    .into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
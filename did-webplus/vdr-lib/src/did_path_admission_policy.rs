@@ -0,0 +1,80 @@
+use crate::{AdmissionError, AdmissionPolicy, AdmissionRequest};
+
+/// Rejects the creation of DIDs whose path (see DIDStr::path_o) doesn't follow the given naming rules.
+/// Updates are always admitted by this policy, since the DID was already admitted when it was created.
+#[derive(Clone, Debug, Default)]
+pub struct DIDPathAdmissionPolicy {
+    /// Minimum number of colon-separated path components, e.g. 1 to require that every DID have a path.
+    pub min_component_count_o: Option<usize>,
+    /// Maximum number of colon-separated path components, e.g. 0 to forbid DID paths entirely.
+    pub max_component_count_o: Option<usize>,
+    /// Maximum length of each path component, in bytes.
+    pub max_component_length_o: Option<usize>,
+    /// If specified, each path component may only contain these characters.
+    pub allowed_chars_o: Option<String>,
+}
+
+#[async_trait::async_trait]
+impl AdmissionPolicy for DIDPathAdmissionPolicy {
+    async fn admit(&self, admission_request: &AdmissionRequest<'_>) -> Result<(), AdmissionError> {
+        if !admission_request.is_create() {
+            return Ok(());
+        }
+        let did = &admission_request.did_document.did;
+        let component_v = did
+            .path_o()
+            .map(|path| path.split(':').collect::<Vec<_>>())
+            .unwrap_or_default();
+        if let Some(min_component_count) = self.min_component_count_o
+            && component_v.len() < min_component_count
+        {
+            return Err(AdmissionError::DisallowedDIDPath(
+                format!(
+                    "DID {} has {} path components, but at least {} are required",
+                    did,
+                    component_v.len(),
+                    min_component_count
+                )
+                .into(),
+            ));
+        }
+        if let Some(max_component_count) = self.max_component_count_o
+            && component_v.len() > max_component_count
+        {
+            return Err(AdmissionError::DisallowedDIDPath(
+                format!(
+                    "DID {} has {} path components, but at most {} are allowed",
+                    did,
+                    component_v.len(),
+                    max_component_count
+                )
+                .into(),
+            ));
+        }
+        for component in component_v.iter() {
+            if let Some(max_component_length) = self.max_component_length_o
+                && component.len() > max_component_length
+            {
+                return Err(AdmissionError::DisallowedDIDPath(
+                    format!(
+                        "DID {} has path component {:?} longer than the maximum of {} bytes",
+                        did, component, max_component_length
+                    )
+                    .into(),
+                ));
+            }
+            if let Some(allowed_chars) = self.allowed_chars_o.as_deref()
+                && let Some(c) = component.chars().find(|&c| !allowed_chars.contains(c))
+            {
+                return Err(AdmissionError::DisallowedDIDPath(
+                    format!(
+                        "DID {} has path component {:?} containing disallowed character {:?}",
+                        did, component, c
+                    )
+                    .into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
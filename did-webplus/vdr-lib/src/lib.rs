@@ -1,9 +1,36 @@
+mod admission_error;
+mod admission_policy;
+mod admission_policy_config;
+mod allowed_hash_functions_admission_policy;
+mod allowed_key_types_admission_policy;
+mod current_key_verifier_resolver;
+mod did_document_limits_admission_policy;
+mod did_path_admission_policy;
+mod min_update_interval_admission_policy;
+mod registrar_permissions;
 pub(crate) mod services;
 mod spawn_vdr;
+mod valid_from_bounds_admission_policy;
 mod vdr_app_state;
 mod vdr_config;
 
-pub use crate::{spawn_vdr::spawn_vdr, vdr_app_state::VDRAppState, vdr_config::VDRConfig};
+pub use crate::{
+    admission_error::AdmissionError,
+    admission_policy::{AdmissionPolicies, AdmissionPolicy, AdmissionRequest},
+    admission_policy_config::AdmissionPolicyConfig,
+    allowed_hash_functions_admission_policy::AllowedHashFunctionsAdmissionPolicy,
+    allowed_key_types_admission_policy::{
+        AllowedKeyTypesAdmissionPolicy, VerificationMethodKeyType,
+    },
+    did_document_limits_admission_policy::DIDDocumentLimitsAdmissionPolicy,
+    did_path_admission_policy::DIDPathAdmissionPolicy,
+    min_update_interval_admission_policy::MinUpdateIntervalAdmissionPolicy,
+    registrar_permissions::RegistrarPermissions,
+    spawn_vdr::{spawn_vdr, spawn_vdr_with_admission_policies},
+    valid_from_bounds_admission_policy::ValidFromBoundsAdmissionPolicy,
+    vdr_app_state::VDRAppState,
+    vdr_config::VDRConfig,
+};
 
 #[derive(Clone, Copy, Debug, clap::ValueEnum)]
 pub enum LogFormat {
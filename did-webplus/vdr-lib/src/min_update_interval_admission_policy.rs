@@ -0,0 +1,40 @@
+use crate::{AdmissionError, AdmissionPolicy, AdmissionRequest};
+
+/// Rejects DID updates that arrive sooner than the given interval after the validFrom of the latest DID
+/// document, as measured by the VDR's clock.  This bounds how quickly a single DID's microledger can grow.
+/// DID creation is always admitted by this policy.
+#[derive(Clone, Debug)]
+pub struct MinUpdateIntervalAdmissionPolicy {
+    min_update_interval: time::Duration,
+}
+
+impl MinUpdateIntervalAdmissionPolicy {
+    pub fn new(min_update_interval: time::Duration) -> Self {
+        Self {
+            min_update_interval,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl AdmissionPolicy for MinUpdateIntervalAdmissionPolicy {
+    async fn admit(&self, admission_request: &AdmissionRequest<'_>) -> Result<(), AdmissionError> {
+        let Some(prev_did_document) = admission_request.prev_did_document_o else {
+            return Ok(());
+        };
+        let prev_valid_from = prev_did_document
+            .valid_from()
+            .map_err(|e| AdmissionError::Rejected(e.to_string().into()))?;
+        let earliest_update_time = prev_valid_from + self.min_update_interval;
+        if admission_request.now_utc < earliest_update_time {
+            return Err(AdmissionError::UpdateTooSoon(
+                format!(
+                    "DID {} was last updated at {}, and may not be updated again until {}",
+                    prev_did_document.did, prev_valid_from, earliest_update_time
+                )
+                .into(),
+            ));
+        }
+        Ok(())
+    }
+}
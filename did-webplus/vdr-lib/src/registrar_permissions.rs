@@ -0,0 +1,148 @@
+use did_webplus_core::{DID, DIDStr};
+
+/// Specifies which "registrar" DIDs may authorize the creation and update of which DIDs hosted by the VDR,
+/// in terms of the DID path (see DIDStr::path_o).  Each registrar DID can be granted any number of path
+/// prefixes.  A path prefix matches whole path components, e.g. prefix "tenant-a" matches DIDs having path
+/// "tenant-a" or "tenant-a:users", but not "tenant-ab".  The special prefix "*" matches every DID, including
+/// those having no path.
+#[derive(Clone, Debug, Default)]
+pub struct RegistrarPermissions {
+    registrar_permission_v: Vec<(DID, String)>,
+}
+
+impl RegistrarPermissions {
+    /// Create a new `RegistrarPermissions` data structure with no registrars.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Builder-style method to grant the given registrar DID the given path prefix.
+    pub fn with_permission(mut self, registrar_did: DID, did_path_prefix: String) -> Self {
+        self.registrar_permission_v
+            .push((registrar_did, did_path_prefix));
+        self
+    }
+    /// Parse a comma-separated list of `<registrar-did>=<did-path-prefix>` pairs, e.g.
+    /// `did:webplus:example.com:admin:uHiB...=tenant-a,did:webplus:example.com:admin:uHiC...=*`.
+    /// The empty string means no registrars.
+    pub fn parse_from_comma_separated_pairs(s: &str) -> anyhow::Result<Self> {
+        let s = s.trim();
+        let mut registrar_permissions = Self::new();
+        if s.is_empty() {
+            return Ok(registrar_permissions);
+        }
+        for pair in s.split(',') {
+            let (registrar_did_str, did_path_prefix) = pair.trim().split_once('=').ok_or_else(|| {
+                anyhow::anyhow!(
+                    "expected registrar permission of the form `<registrar-did>=<did-path-prefix>`, but got {:?}",
+                    pair
+                )
+            })?;
+            let registrar_did =
+                DID::try_from(registrar_did_str.trim().to_string()).map_err(|e| {
+                    anyhow::anyhow!("malformed registrar DID {:?}: {}", registrar_did_str, e)
+                })?;
+            let did_path_prefix = did_path_prefix.trim();
+            anyhow::ensure!(
+                !did_path_prefix.is_empty(),
+                "DID path prefix for registrar {} must be nonempty (use \"*\" to match all DIDs)",
+                registrar_did
+            );
+            registrar_permissions =
+                registrar_permissions.with_permission(registrar_did, did_path_prefix.to_string());
+        }
+        Ok(registrar_permissions)
+    }
+    /// Returns true iff the given DID is a registrar, i.e. it has been granted at least one path prefix.
+    pub fn is_registrar(&self, did: &DIDStr) -> bool {
+        self.registrar_permission_v
+            .iter()
+            .any(|(registrar_did, _)| registrar_did.as_did_str() == did)
+    }
+    /// Returns true iff the given registrar DID may authorize the creation and update of the given DID.
+    pub fn permits(&self, registrar_did: &DIDStr, did: &DIDStr) -> bool {
+        self.registrar_permission_v
+            .iter()
+            .filter(|(permitted_registrar_did, _)| {
+                permitted_registrar_did.as_did_str() == registrar_did
+            })
+            .any(|(_, did_path_prefix)| path_prefix_matches(did_path_prefix, did.path_o()))
+    }
+}
+
+fn path_prefix_matches(did_path_prefix: &str, did_path_o: Option<&str>) -> bool {
+    if did_path_prefix == "*" {
+        return true;
+    }
+    match did_path_o {
+        Some(did_path) => match did_path.strip_prefix(did_path_prefix) {
+            Some(remainder) => remainder.is_empty() || remainder.starts_with(':'),
+            None => false,
+        },
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registrar_permissions() {
+        let registrar_a =
+            "did:webplus:example.com:admin:uHiBKHZUE3HHlYcyVIF-vPm0Xg71vqJla2L1OGXHMSK4NEA";
+        let registrar_b =
+            "did:webplus:example.com:admin:uHiAPukNGyeIoJnlwRBVbCTAQ-Bc_Hpo7lYyo-nuXoHXvHg";
+        let registrar_permissions = RegistrarPermissions::parse_from_comma_separated_pairs(
+            format!(
+                "{}=tenant-a, {}=tenant-b:users,{}=*",
+                registrar_a, registrar_a, registrar_b
+            )
+            .as_str(),
+        )
+        .expect("pass");
+        let registrar_a = DIDStr::new_ref(registrar_a).expect("pass");
+        let registrar_b = DIDStr::new_ref(registrar_b).expect("pass");
+        let did_for_path = |path_o: Option<&str>| {
+            DID::try_from(match path_o {
+                Some(path) => format!(
+                    "did:webplus:example.com:{}:uEiDGVipdCAqJug3y_d27fGJZFFZ0hOjg0e_QhNFtm_0OVg",
+                    path
+                ),
+                None => "did:webplus:example.com:uEiDGVipdCAqJug3y_d27fGJZFFZ0hOjg0e_QhNFtm_0OVg"
+                    .to_string(),
+            })
+            .expect("pass")
+        };
+
+        assert!(registrar_permissions.is_registrar(registrar_a));
+        assert!(registrar_permissions.is_registrar(registrar_b));
+        assert!(!registrar_permissions.is_registrar(&did_for_path(Some("tenant-a"))));
+
+        assert!(registrar_permissions.permits(registrar_a, &did_for_path(Some("tenant-a"))));
+        assert!(registrar_permissions.permits(registrar_a, &did_for_path(Some("tenant-a:x"))));
+        assert!(!registrar_permissions.permits(registrar_a, &did_for_path(Some("tenant-ab"))));
+        assert!(!registrar_permissions.permits(registrar_a, &did_for_path(Some("tenant-b"))));
+        assert!(registrar_permissions.permits(registrar_a, &did_for_path(Some("tenant-b:users"))));
+        assert!(!registrar_permissions.permits(registrar_a, &did_for_path(None)));
+        assert!(registrar_permissions.permits(registrar_b, &did_for_path(None)));
+        assert!(registrar_permissions.permits(registrar_b, &did_for_path(Some("tenant-ab"))));
+
+        assert!(
+            RegistrarPermissions::parse_from_comma_separated_pairs("")
+                .expect("pass")
+                .registrar_permission_v
+                .is_empty()
+        );
+        assert!(RegistrarPermissions::parse_from_comma_separated_pairs("tenant-a").is_err());
+        assert!(
+            RegistrarPermissions::parse_from_comma_separated_pairs("did:example:123=tenant-a")
+                .is_err()
+        );
+        assert!(
+            RegistrarPermissions::parse_from_comma_separated_pairs(
+                format!("{}=", registrar_a).as_str()
+            )
+            .is_err()
+        );
+    }
+}
@@ -1,18 +1,25 @@
-use crate::{VDRAppState, VDRConfig};
+use crate::{AdmissionPolicy, VDRAppState, VDRConfig};
 use axum::{
-    Router,
+    Json, Router,
     extract::{Path, State},
-    http::{HeaderMap, StatusCode, header},
+    http::{HeaderMap, Method, StatusCode, Uri, header},
+    response::{IntoResponse, Response},
     routing::get,
 };
-use did_webplus_core::DID;
+use did_webplus_core::{DID, DIDDocumentValidationResult};
 use did_webplus_doc_store::DIDDocStore;
+use std::sync::Arc;
 use tokio::task;
 
-pub fn get_routes(did_doc_store: DIDDocStore, vdr_config: &VDRConfig) -> Router {
+pub fn get_routes(
+    did_doc_store: DIDDocStore,
+    vdr_config: &VDRConfig,
+    admission_policy_a: Arc<dyn AdmissionPolicy>,
+) -> Router {
     let state = VDRAppState {
         did_doc_store,
         vdr_config: vdr_config.clone(),
+        admission_policy_a,
     };
 
     Router::new()
@@ -42,11 +49,60 @@ async fn get_did_documents_jsonl(
         path.as_str(),
     ) {
         get_did_document_jsonl_impl(State(vdr_app_state), header_map, did).await
+    } else if vdr_app_state.vdr_config.serve_did_web
+        && let Some(did_json_path_prefix) = path.strip_suffix("did.json")
+    {
+        // did.json lives alongside did-documents.jsonl, so parse the DID from the corresponding path.
+        let did = DID::from_did_documents_jsonl_resolution_url(
+            vdr_app_state.vdr_config.did_hostname.as_str(),
+            vdr_app_state.vdr_config.did_port_o,
+            format!("{}did-documents.jsonl", did_json_path_prefix).as_str(),
+        )
+        .map_err(|err| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("malformed did.json URL: {}", err),
+            )
+        })?;
+        get_did_json_impl(State(vdr_app_state), did).await
     } else {
         Err((StatusCode::BAD_REQUEST, "".to_string()))
     }
 }
 
+/// Serves the did:web-compatible rendering of the latest DID document for the given DID (see
+/// did_webplus_core::DIDWebDocument).  Because it's derived from the latest stored DID document on each
+/// request, it reflects each DID update as soon as the update is stored.
+async fn get_did_json_impl(
+    State(vdr_app_state): State<VDRAppState>,
+    did: DID,
+) -> Result<(HeaderMap, String), (StatusCode, String)> {
+    tracing::debug!(?did, "retrieving did:web-compatible did.json");
+
+    let latest_did_document_record = vdr_app_state
+        .did_doc_store
+        .get_latest_known_did_doc_record(None, &did)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("DID not found: {}", did)))?;
+    let latest_did_document = parse_did_document(&latest_did_document_record.did_document_jcs)
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "invalid DID document in storage".to_string(),
+            )
+        })?;
+    let did_web_document = did_webplus_core::DIDWebDocument::from(&latest_did_document);
+    let did_json = serde_json::to_string(&did_web_document)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut response_header_map = HeaderMap::new();
+    response_header_map.insert("Content-Type", "application/did+json".parse().unwrap());
+    // did.json changes with each DID update, so caches must revalidate it.
+    response_header_map.insert("Cache-Control", "no-cache".parse().unwrap());
+    Ok((response_header_map, did_json))
+}
+
 // NOTE: This is duplicated in did-webplus-vdg-lib crate.  In order to de-duplicate, there would need to be
 // an axum-aware crate common to this and that crate.
 async fn get_did_document_jsonl_impl(
@@ -164,18 +220,43 @@ async fn get_did_document_jsonl_impl(
 #[tracing::instrument(ret(Debug), err(Debug), skip(vdr_app_state, did_document_body))]
 async fn create_did(
     State(vdr_app_state): State<VDRAppState>,
+    method: Method,
+    uri: Uri,
     header_map: HeaderMap,
     Path(path): Path<String>,
     did_document_body: String,
-) -> Result<(), (StatusCode, String)> {
+) -> Result<Response, (StatusCode, String)> {
     assert!(!path.starts_with('/'));
 
     vdr_app_state.verify_authorization(&header_map)?;
 
+    let (path, dry_run) = split_dry_run_path_suffix(&path);
+    let create_did_result = create_did_impl(
+        &vdr_app_state,
+        &method,
+        &uri,
+        &header_map,
+        path,
+        did_document_body,
+        dry_run,
+    )
+    .await;
+    did_operation_response(create_did_result, dry_run)
+}
+
+async fn create_did_impl(
+    vdr_app_state: &VDRAppState,
+    method: &Method,
+    uri: &Uri,
+    header_map: &HeaderMap,
+    path: &str,
+    did_document_body: String,
+    dry_run: bool,
+) -> Result<(), (StatusCode, String)> {
     let did = DID::from_did_documents_jsonl_resolution_url(
         vdr_app_state.vdr_config.did_hostname.as_str(),
         vdr_app_state.vdr_config.did_port_o,
-        path.as_str(),
+        path,
     )
     .map_err(|err| {
         (
@@ -184,7 +265,7 @@ async fn create_did(
         )
     })?;
 
-    tracing::debug!(?did);
+    tracing::debug!(?did, dry_run);
     tracing::trace!(
         "received request to create DID using DID document: {}",
         did_document_body
@@ -201,6 +282,21 @@ async fn create_did(
         ));
     }
 
+    vdr_app_state
+        .verify_signed_authorization(
+            method,
+            uri,
+            header_map,
+            &did,
+            Some(&root_did_document),
+            &did_document_body,
+        )
+        .await?;
+
+    vdr_app_state
+        .admit(&root_did_document, &did_document_body, None)
+        .await?;
+
     use storage_traits::StorageDynT;
     let mut transaction_b = vdr_app_state
         .did_doc_store
@@ -223,6 +319,13 @@ async fn create_did(
             }
             _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
         })?;
+    if dry_run {
+        transaction_b
+            .rollback()
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        return Ok(());
+    }
     transaction_b
         .commit()
         .await
@@ -241,18 +344,43 @@ async fn create_did(
 #[tracing::instrument(ret(Debug), err(Debug), skip(vdr_app_state, did_document_body))]
 async fn update_did(
     State(vdr_app_state): State<VDRAppState>,
+    method: Method,
+    uri: Uri,
     header_map: HeaderMap,
     Path(path): Path<String>,
     did_document_body: String,
-) -> Result<(), (StatusCode, String)> {
+) -> Result<Response, (StatusCode, String)> {
     assert!(!path.starts_with('/'));
 
     vdr_app_state.verify_authorization(&header_map)?;
 
+    let (path, dry_run) = split_dry_run_path_suffix(&path);
+    let update_did_result = update_did_impl(
+        &vdr_app_state,
+        &method,
+        &uri,
+        &header_map,
+        path,
+        did_document_body,
+        dry_run,
+    )
+    .await;
+    did_operation_response(update_did_result, dry_run)
+}
+
+async fn update_did_impl(
+    vdr_app_state: &VDRAppState,
+    method: &Method,
+    uri: &Uri,
+    header_map: &HeaderMap,
+    path: &str,
+    did_document_body: String,
+    dry_run: bool,
+) -> Result<(), (StatusCode, String)> {
     let did = DID::from_did_documents_jsonl_resolution_url(
         vdr_app_state.vdr_config.did_hostname.as_str(),
         vdr_app_state.vdr_config.did_port_o,
-        path.as_str(),
+        path,
     )
     .map_err(|err| {
         (
@@ -260,13 +388,28 @@ async fn update_did(
             format!("malformed DID resolution URL: {}", err),
         )
     })?;
-    tracing::debug!(?path, ?did);
+    tracing::debug!(?path, ?did, dry_run);
     tracing::trace!("did_document_body: {}", did_document_body);
     tracing::trace!(
         "did_document_body JCS: {}",
         serde_json_canonicalizer::pipe(&did_document_body).unwrap()
     );
 
+    let new_did_document = parse_did_document(&did_document_body)?;
+    if new_did_document.did != did {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "DID in document does not match the DID in the resolution URL: {} != {}",
+                new_did_document.did, did
+            ),
+        ));
+    }
+
+    vdr_app_state
+        .verify_signed_authorization(method, uri, header_map, &did, None, &did_document_body)
+        .await?;
+
     use storage_traits::StorageDynT;
     let mut transaction_b = vdr_app_state
         .did_doc_store
@@ -286,17 +429,6 @@ async fn update_did(
     }
     let latest_did_document_record = latest_did_document_record_o.unwrap();
 
-    let new_did_document = parse_did_document(&did_document_body)?;
-    if new_did_document.did != did {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            format!(
-                "DID in document does not match the DID in the resolution URL: {} != {}",
-                new_did_document.did, did
-            ),
-        ));
-    }
-
     // TODO: Check if the previous did document is the root record if this will work. Otherwise add more logic.
     let prev_document =
         parse_did_document(&latest_did_document_record.did_document_jcs).map_err(|_| {
@@ -306,6 +438,10 @@ async fn update_did(
             )
         })?;
 
+    vdr_app_state
+        .admit(&new_did_document, &did_document_body, Some(&prev_document))
+        .await?;
+
     vdr_app_state
         .did_doc_store
         .validate_and_add_did_doc(
@@ -321,6 +457,13 @@ async fn update_did(
             }
             _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
         })?;
+    if dry_run {
+        transaction_b
+            .rollback()
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        return Ok(());
+    }
     transaction_b
         .commit()
         .await
@@ -336,6 +479,34 @@ async fn update_did(
     Ok(())
 }
 
+/// Strips did_webplus_core::VDR_DRY_RUN_PATH_SUFFIX from the request path, if present, returning the
+/// remaining path and whether the request is a dry-run.
+fn split_dry_run_path_suffix(path: &str) -> (&str, bool) {
+    match path.strip_suffix(did_webplus_core::VDR_DRY_RUN_PATH_SUFFIX) {
+        Some(path) => (path, true),
+        None => (path, false),
+    }
+}
+
+/// For a dry-run, the outcome of the DID create or update operation is reported in the body of a 200 response
+/// as a DIDDocumentValidationResult.  Otherwise it's reported via the response status, as usual.
+fn did_operation_response(
+    did_operation_result: Result<(), (StatusCode, String)>,
+    dry_run: bool,
+) -> Result<Response, (StatusCode, String)> {
+    if dry_run {
+        let did_document_validation_result = match did_operation_result {
+            Ok(()) => DIDDocumentValidationResult::valid(),
+            Err((status_code, error)) => {
+                DIDDocumentValidationResult::invalid(status_code.as_u16(), error)
+            }
+        };
+        Ok(Json(did_document_validation_result).into_response())
+    } else {
+        did_operation_result.map(|()| ().into_response())
+    }
+}
+
 lazy_static::lazy_static! {
     static ref VDG_CLIENT: reqwest::Client = reqwest::Client::new();
 }
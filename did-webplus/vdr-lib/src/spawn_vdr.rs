@@ -1,7 +1,18 @@
-use crate::VDRConfig;
+use crate::{AdmissionPolicy, VDRConfig};
+use std::sync::Arc;
 
 /// Spawn a VDR using the given VDRConfig.
 pub async fn spawn_vdr(vdr_config: VDRConfig) -> anyhow::Result<tokio::task::JoinHandle<()>> {
+    spawn_vdr_with_admission_policies(vdr_config, Vec::new()).await
+}
+
+/// Spawn a VDR using the given VDRConfig, applying the given admission policies to DID create and update
+/// requests in addition to the built-in ones configured in VDRConfig::admission_policy_config.  The built-in
+/// policies are checked first, then the given ones, in order.
+pub async fn spawn_vdr_with_admission_policies(
+    vdr_config: VDRConfig,
+    admission_policy_v: Vec<Arc<dyn AdmissionPolicy>>,
+) -> anyhow::Result<tokio::task::JoinHandle<()>> {
     // We have to process the vdg_base_url_v field here because it depends on the http_scheme_override field,
     // and that kind of inter-field dependency is not supported by clap.
     let vdr_config = {
@@ -22,11 +33,18 @@ pub async fn spawn_vdr(vdr_config: VDRConfig) -> anyhow::Result<tokio::task::Joi
 
     tracing::debug!("{:?}", vdr_config);
 
+    let admission_policy_a: Arc<dyn AdmissionPolicy> =
+        Arc::new(admission_policy_v.into_iter().fold(
+            vdr_config.admission_policy_config.admission_policies(),
+            |admission_policies, admission_policy_a| {
+                admission_policies.with_policy(admission_policy_a)
+            },
+        ));
+
     if vdr_config.database_url.starts_with("postgres://") {
         #[cfg(feature = "postgres")]
         {
             use anyhow::Context;
-            use std::sync::Arc;
 
             let pg_pool = sqlx::postgres::PgPoolOptions::new()
                 .max_connections(vdr_config.database_max_connections)
@@ -42,6 +60,16 @@ pub async fn spawn_vdr(vdr_config: VDRConfig) -> anyhow::Result<tokio::task::Joi
                 .await?,
             ));
 
+            let periodic_audit_task_o =
+                vdr_config
+                    .audit_interval_seconds_o
+                    .map(|audit_interval_seconds| {
+                        did_webplus_doc_store::PeriodicAuditTask::spawn(
+                            did_doc_store.clone(),
+                            std::time::Duration::from_secs(audit_interval_seconds),
+                        )
+                    });
+
             let middleware_stack = tower::ServiceBuilder::new()
                 .layer(tower_http::compression::CompressionLayer::new())
                 .layer(
@@ -57,7 +85,11 @@ pub async fn spawn_vdr(vdr_config: VDRConfig) -> anyhow::Result<tokio::task::Joi
                 .into_inner();
 
             let app = axum::Router::new()
-                .merge(crate::services::did::get_routes(did_doc_store, &vdr_config))
+                .merge(crate::services::did::get_routes(
+                    did_doc_store,
+                    &vdr_config,
+                    admission_policy_a,
+                ))
                 .layer(middleware_stack)
                 .route("/health", axum::routing::get(|| async { "OK" }));
 
@@ -74,6 +106,8 @@ pub async fn spawn_vdr(vdr_config: VDRConfig) -> anyhow::Result<tokio::task::Joi
             // TODO: Use Serve::with_graceful_shutdown to be able to shutdown the server gracefully, in case aborting
             // the task isn't good enough.
             Ok(tokio::task::spawn(async move {
+                // The audit task (if any) is aborted when this task ends or is aborted.
+                let _periodic_audit_task_o = periodic_audit_task_o;
                 // TODO: Figure out if error handling is needed here.
                 let _ = axum::serve(listener, app).await;
             }))
@@ -123,7 +157,7 @@ pub async fn spawn_vdr(vdr_config: VDRConfig) -> anyhow::Result<tokio::task::Joi
         //         .into_inner();
 
         //     let app = axum::Router::new()
-        //         .merge(crate::services::did::get_routes(did_doc_store, &vdr_config))
+        //         .merge(crate::services::did::get_routes(did_doc_store, &vdr_config, admission_policy_a))
         //         .layer(middleware_stack)
         //         .route("/health", axum::routing::get(|| async { "OK" }));
 
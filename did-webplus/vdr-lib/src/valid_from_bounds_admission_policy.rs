@@ -0,0 +1,46 @@
+use crate::{AdmissionError, AdmissionPolicy, AdmissionRequest};
+
+/// Rejects DID documents whose validFrom is too far in the past or in the future relative to the VDR's
+/// clock.  The did:webplus spec only requires validFrom to be monotonically increasing, so without this,
+/// a controller could backdate or postdate DID documents arbitrarily.
+#[derive(Clone, Debug, Default)]
+pub struct ValidFromBoundsAdmissionPolicy {
+    /// How far before the VDR's current time the validFrom may be.
+    pub max_past_o: Option<time::Duration>,
+    /// How far after the VDR's current time the validFrom may be.
+    pub max_future_o: Option<time::Duration>,
+}
+
+#[async_trait::async_trait]
+impl AdmissionPolicy for ValidFromBoundsAdmissionPolicy {
+    async fn admit(&self, admission_request: &AdmissionRequest<'_>) -> Result<(), AdmissionError> {
+        let valid_from = admission_request
+            .did_document
+            .valid_from()
+            .map_err(|e| AdmissionError::ValidFromOutOfBounds(e.to_string().into()))?;
+        let now_utc = admission_request.now_utc;
+        if let Some(max_past) = self.max_past_o
+            && valid_from < now_utc - max_past
+        {
+            return Err(AdmissionError::ValidFromOutOfBounds(
+                format!(
+                    "validFrom {} is more than {} before the VDR's current time {}",
+                    valid_from, max_past, now_utc
+                )
+                .into(),
+            ));
+        }
+        if let Some(max_future) = self.max_future_o
+            && valid_from > now_utc + max_future
+        {
+            return Err(AdmissionError::ValidFromOutOfBounds(
+                format!(
+                    "validFrom {} is more than {} after the VDR's current time {}",
+                    valid_from, max_future, now_utc
+                )
+                .into(),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -1,12 +1,45 @@
-use crate::VDRConfig;
+use crate::{
+    AdmissionPolicy, AdmissionRequest, VDRConfig,
+    current_key_verifier_resolver::CurrentKeyVerifierResolver,
+};
+use axum::http::StatusCode;
+use did_webplus_core::{DIDDocument, DIDKeyResourceFullyQualifiedStr, DIDStr};
+use std::sync::Arc;
+use verifier_resolver::{VerificationCheckKind, VerificationRelationship, VerificationReport};
 
 #[derive(Clone)]
 pub struct VDRAppState {
     pub did_doc_store: did_webplus_doc_store::DIDDocStore,
     pub vdr_config: VDRConfig,
+    /// The admission policies applied to each DID create and update request before the DID document is
+    /// stored.  This includes the built-in policies configured in VDRConfig::admission_policy_config.
+    pub admission_policy_a: Arc<dyn AdmissionPolicy>,
 }
 
 impl VDRAppState {
+    /// Run the admission policies on the given DID document, which is to be created (if prev_did_document_o
+    /// is None) or added as an update to the given previous DID document.  A rejection is mapped to the HTTP
+    /// status code indicated by AdmissionError::status_code.
+    pub async fn admit(
+        &self,
+        did_document: &DIDDocument,
+        did_document_body: &str,
+        prev_did_document_o: Option<&DIDDocument>,
+    ) -> Result<(), (StatusCode, String)> {
+        let admission_request = AdmissionRequest {
+            did_document,
+            did_document_body,
+            prev_did_document_o,
+            now_utc: time::OffsetDateTime::now_utc(),
+        };
+        self.admission_policy_a
+            .admit(&admission_request)
+            .await
+            .map_err(|e| {
+                tracing::error!(did = %did_document.did, "DID document not admitted: {}", e);
+                e.into()
+            })
+    }
     pub fn verify_authorization(
         &self,
         header_map: &axum::http::HeaderMap,
@@ -47,4 +80,169 @@ impl VDRAppState {
             Ok(())
         }
     }
+    /// If signed authorization is enabled (see VDRConfig::registrar_permissions_o), then verify that the
+    /// request to create or update the given DID is signed, either via RFC 9421 HTTP message signature or
+    /// via detached JWS in the VDR_AUTHORIZATION_JWS_HEADER header, by a current capabilityInvocation key of
+    /// a DID that is permitted to authorize it.  For DID creation, pending_root_did_document_o must be the
+    /// root DID document being created, so that a DID can sign its own creation.  Returns 401 if the request
+    /// isn't validly signed, and 403 if the signing DID isn't permitted to create or update the DID.
+    pub async fn verify_signed_authorization(
+        &self,
+        method: &axum::http::Method,
+        uri: &axum::http::Uri,
+        header_map: &axum::http::HeaderMap,
+        did: &DIDStr,
+        pending_root_did_document_o: Option<&DIDDocument>,
+        did_document_body: &str,
+    ) -> Result<(), (StatusCode, String)> {
+        let registrar_permissions = match self.vdr_config.registrar_permissions_o.as_ref() {
+            Some(registrar_permissions) => registrar_permissions,
+            None => {
+                tracing::trace!(
+                    "VDR signed authorization is disabled; no signature check will be performed"
+                );
+                return Ok(());
+            }
+        };
+        tracing::trace!("VDR signed authorization is enabled; conducting signature check");
+
+        let verifier_resolver = CurrentKeyVerifierResolver::new(
+            self.did_doc_store.clone(),
+            pending_root_did_document_o.cloned(),
+        );
+        let mut verification_report = VerificationReport::new();
+        let signer_key_id = if header_map.contains_key(&http_message_signatures::SIGNATURE_INPUT) {
+            let scheme = self
+                .vdr_config
+                .http_scheme_override
+                .determine_http_scheme_for_host(self.vdr_config.did_hostname.as_str())
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            let verification_options = http_message_signatures::HTTPSignatureVerificationOptions {
+                required_component_v: vec![
+                    "@method".to_string(),
+                    "@target-uri".to_string(),
+                    http_message_signatures::CONTENT_DIGEST.as_str().to_string(),
+                ],
+                verification_relationship: VerificationRelationship::CapabilityInvocation,
+                ..Default::default()
+            };
+            let http_signature_verifier = http_message_signatures::HTTPSignatureVerifier::new(
+                Arc::new(verifier_resolver),
+                verification_options,
+            )
+            .with_scheme(
+                axum::http::uri::Scheme::try_from(scheme)
+                    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?,
+            );
+            let http_signature = http_signature_verifier
+                .verify_request(
+                    method,
+                    uri,
+                    header_map,
+                    did_document_body.as_bytes(),
+                    &mut verification_report,
+                )
+                .await
+                .map_err(|e| {
+                    tracing::error!(?verification_report, "HTTP message signature not valid");
+                    (
+                        StatusCode::UNAUTHORIZED,
+                        format!("HTTP message signature not valid: {}", e),
+                    )
+                })?;
+            http_signature.signature_params().keyid.clone()
+        } else if let Some(jws_header_value) =
+            header_map.get(did_webplus_core::VDR_AUTHORIZATION_JWS_HEADER)
+        {
+            let jws = did_webplus_jws::JWS::try_from(jws_header_value.to_str().map_err(|_| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    "malformed authorization JWS".to_string(),
+                )
+            })?)
+            .map_err(|e| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("malformed authorization JWS: {}", e),
+                )
+            })?;
+            let kid = jws.header().kid.as_str();
+            let verifier_b = verification_report
+                .resolve_for_purpose(
+                    kid,
+                    VerificationRelationship::CapabilityInvocation,
+                    &verifier_resolver,
+                )
+                .await
+                .map_err(|e| {
+                    tracing::error!(?verification_report, "authorization JWS not valid");
+                    (
+                        StatusCode::UNAUTHORIZED,
+                        format!("authorization JWS not valid: {}", e),
+                    )
+                })?;
+            verification_report
+                .record_result(
+                    VerificationCheckKind::Signature,
+                    Some(kid),
+                    verification_report
+                        .resolved_did_document_version(kid)
+                        .as_deref(),
+                    jws.verify(verifier_b.as_ref(), Some(&mut did_document_body.as_bytes())),
+                )
+                .map_err(|e| {
+                    tracing::error!(?verification_report, "authorization JWS not valid");
+                    (
+                        StatusCode::UNAUTHORIZED,
+                        format!("authorization JWS not valid: {}", e),
+                    )
+                })?;
+            kid.to_string()
+        } else {
+            tracing::error!("required signature not provided");
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                format!(
+                    "request must be signed, either via HTTP message signature or via detached JWS in the {:?} header",
+                    did_webplus_core::VDR_AUTHORIZATION_JWS_HEADER
+                ),
+            ));
+        };
+
+        // The signing key resolved, so its key ID is known to be well-formed.
+        let signer_key_id = DIDKeyResourceFullyQualifiedStr::new_ref(signer_key_id.as_str())
+            .map_err(|e| (StatusCode::UNAUTHORIZED, e.to_string()))?;
+        let signer_did = signer_key_id.without_fragment().did();
+        let is_permitted = registrar_permissions.permits(signer_did, did)
+            || (signer_did == did
+                && (pending_root_did_document_o.is_none()
+                    || registrar_permissions.is_registrar(did)));
+        verification_report
+            .record_result(
+                VerificationCheckKind::TrustPolicy,
+                Some(signer_key_id.as_str()),
+                None,
+                if is_permitted {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "{} is not permitted to {} {}",
+                        signer_did,
+                        if pending_root_did_document_o.is_some() {
+                            "create"
+                        } else {
+                            "update"
+                        },
+                        did
+                    ))
+                },
+            )
+            .map_err(|e| {
+                tracing::error!(?verification_report, "signer not permitted");
+                (StatusCode::FORBIDDEN, e)
+            })?;
+
+        tracing::debug!(%signer_did, "request authorized by signature");
+        Ok(())
+    }
 }
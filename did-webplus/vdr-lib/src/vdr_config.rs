@@ -86,6 +86,54 @@ pub struct VDRConfig {
         value_parser = parse_comma_separated_api_keys_into_strings,
     )]
     pub test_authz_api_key_vo: Option<Vec<String>>,
+    /// Optionally require that DID create and update requests be signed, and specify which "registrar" DIDs
+    /// may authorize them, as a comma-separated list of `<registrar-did>=<did-path-prefix>` pairs.  A path
+    /// prefix matches whole DID path components, so e.g. prefix "tenant-a" permits DIDs of the form
+    /// `did:webplus:<hostname>:tenant-a:<...>`, and the special prefix "*" permits every DID.  A registrar DID
+    /// may appear in multiple pairs.  Each request must carry either an RFC 9421 HTTP message signature
+    /// (covering "@method", "@target-uri", and "content-digest") or a detached JWS over the DID document in
+    /// the "x-did-webplus-authorization-jws" header, made by a capabilityInvocation key in the current DID
+    /// document of the signing DID, which must be hosted by this VDR.  A request is authorized if the signing
+    /// DID is a registrar permitted for the DID's path, or if the signing DID is the DID itself and the request
+    /// is an update (or the creation of a registrar DID, so that registrars can be bootstrapped).  If this is
+    /// set and empty, then only self-signed updates are authorized.  If this is not set, no signature check is
+    /// done.  This check is done in addition to the check specified by --test-authz-api-keys.
+    #[arg(
+        name = "did-authz-registrars",
+        env = "DID_WEBPLUS_VDR_DID_AUTHZ_REGISTRARS",
+        long,
+        value_name = "REGISTRARS",
+        default_value = None,
+        value_parser = crate::RegistrarPermissions::parse_from_comma_separated_pairs,
+    )]
+    pub registrar_permissions_o: Option<crate::RegistrarPermissions>,
+    /// If set, then for each hosted DID, also serve a did:web-compatible DID document (see
+    /// did_webplus_core::DIDWebDocument) as `did.json` alongside `did-documents.jsonl`, which is the URL
+    /// that did:web resolution maps the corresponding did:web DID to.  It's derived from the latest DID
+    /// document, so it reflects each DID update immediately.
+    #[arg(
+        name = "serve-did-web",
+        env = "DID_WEBPLUS_VDR_SERVE_DID_WEB",
+        long,
+        default_value_t = false
+    )]
+    pub serve_did_web: bool,
+    /// Optionally specify the number of seconds between audits of the VDR's DID doc store, in which every
+    /// hosted microledger is re-verified from its root DID document (see DIDDocStore::audit).  Any corrupted
+    /// or orphaned records that are found are logged at error level.  If this is not set, no audits are done.
+    /// The interval may be at most 100 years.
+    #[arg(
+        name = "audit-interval",
+        env = "DID_WEBPLUS_VDR_AUDIT_INTERVAL",
+        long,
+        value_name = "SECONDS",
+        value_parser = clap::value_parser!(u64)
+            .range(1..=did_webplus_doc_store::PeriodicAuditTask::MAX_INTERVAL_SECONDS)
+    )]
+    pub audit_interval_seconds_o: Option<u64>,
+    /// Built-in admission policies applied to DID create and update requests.
+    #[command(flatten)]
+    pub admission_policy_config: crate::AdmissionPolicyConfig,
 }
 
 fn parse_comma_separated_hosts_into_urls(s: &str) -> anyhow::Result<Vec<url::Url>> {
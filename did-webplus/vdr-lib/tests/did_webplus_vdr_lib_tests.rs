@@ -32,6 +32,10 @@ async fn test_vdr_operations() {
             TEST_AUTHZ_API_KEY.to_string(),
             "other test api key".to_string(),
         ]),
+        registrar_permissions_o: None,
+        serve_did_web: false,
+        audit_interval_seconds_o: None,
+        admission_policy_config: Default::default(),
     };
     let vdr_handle = did_webplus_vdr_lib::spawn_vdr(vdr_config.clone())
         .await
@@ -292,3 +296,346 @@ async fn test_vdr_wallet_operations_impl(
         }
     }
 }
+
+/// An example of an AdmissionPolicy defined outside of the VDR, which rejects DIDs whose path begins
+/// with "blocked".
+struct BlockedPathAdmissionPolicy;
+
+#[async_trait::async_trait]
+impl did_webplus_vdr_lib::AdmissionPolicy for BlockedPathAdmissionPolicy {
+    async fn admit(
+        &self,
+        admission_request: &did_webplus_vdr_lib::AdmissionRequest<'_>,
+    ) -> Result<(), did_webplus_vdr_lib::AdmissionError> {
+        if let Some(path) = admission_request.did_document.did.path_o()
+            && path.starts_with("blocked")
+        {
+            return Err(did_webplus_vdr_lib::AdmissionError::Rejected(
+                "DID path is blocked".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_vdr_admission_policies() {
+    let vdr_config = did_webplus_vdr_lib::VDRConfig {
+        did_hostname: "localhost".to_string(),
+        did_port_o: Some(13094),
+        listen_port: 13094,
+        database_url: "postgres:///test_vdr_admission_policies_vdr".to_string(),
+        database_max_connections: 10,
+        vdg_base_url_v: Vec::new(),
+        http_scheme_override: Default::default(),
+        test_authz_api_key_vo: None,
+        registrar_permissions_o: None,
+        serve_did_web: false,
+        audit_interval_seconds_o: None,
+        admission_policy_config: did_webplus_vdr_lib::AdmissionPolicyConfig {
+            allowed_key_type_vo: Some(vec![signature_dyn::KeyType::Ed25519.into()]),
+            allowed_hash_function_vo: Some(vec![selfhash::NamedHashFunction::BLAKE3]),
+            max_did_document_size_o: Some(100_000),
+            max_verification_method_count_o: Some(5),
+            min_update_interval_seconds_o: Some(3600),
+            valid_from_max_past_seconds_o: Some(60),
+            valid_from_max_future_seconds_o: Some(60),
+            did_path_min_component_count_o: None,
+            did_path_max_component_count_o: Some(1),
+            did_path_max_component_length_o: Some(16),
+            did_path_allowed_chars_o: Some("abcdefghijklmnopqrstuvwxyz0123456789-".to_string()),
+        },
+    };
+    let vdr_handle = did_webplus_vdr_lib::spawn_vdr_with_admission_policies(
+        vdr_config.clone(),
+        vec![Arc::new(BlockedPathAdmissionPolicy)],
+    )
+    .await
+    .expect("pass");
+
+    test_util::wait_until_service_is_up(
+        "VDR",
+        format!("http://localhost:{}/health", vdr_config.listen_port).as_str(),
+    )
+    .await;
+
+    let http_scheme_override = did_webplus_core::HTTPSchemeOverride::new()
+        .with_override(vdr_config.did_hostname.clone(), "http")
+        .expect("pass");
+
+    // Create a DID using a MockWallet, then POST its root DID document to the real VDR and return
+    // the response status.  If dry_run is true, then the status is the one reported by the VDR's
+    // dry-run of the request.
+    let create_did = |did_path_o: Option<&str>,
+                      key_type: signature_dyn::KeyType,
+                      mb_hash_function: selfhash::MBHashFunction,
+                      dry_run: bool| {
+        let mock_vdr_la = Arc::new(RwLock::new(MockVDR::new_with(
+            vdr_config.did_hostname.clone(),
+            vdr_config.did_port_o,
+            did_path_o.map(str::to_string),
+            None,
+        )));
+        let mock_vdr_lam = HashMap::from([(vdr_config.did_hostname.clone(), mock_vdr_la)]);
+        let mock_vdr_client_a = Arc::new(MockVDRClient::new(
+            "Alice's MockVDRClient".to_string(),
+            mock_vdr_lam,
+        ));
+        let mut alice_wallet = MockWallet::new("Alice's Wallet".to_string(), mock_vdr_client_a);
+        let alice_did = alice_wallet
+            .create_did(
+                vdr_config.did_hostname.clone(),
+                vdr_config.did_port_o,
+                did_path_o.map(str::to_string),
+                key_type,
+                &mb_hash_function,
+            )
+            .expect("pass");
+        let alice_did_documents_jsonl_url =
+            alice_did.resolution_url_for_did_documents_jsonl(Some(&http_scheme_override));
+        let alice_did_document_jcs = alice_wallet
+            .controlled_did(&alice_did)
+            .expect("pass")
+            .microledger()
+            .view()
+            .latest_did_document()
+            .serialize_canonically()
+            .expect("pass");
+        async move {
+            let status = if dry_run {
+                let did_document_validation_result = test_util::REQWEST_CLIENT
+                    .post(format!(
+                        "{}{}",
+                        alice_did_documents_jsonl_url,
+                        did_webplus_core::VDR_DRY_RUN_PATH_SUFFIX
+                    ))
+                    .body(alice_did_document_jcs)
+                    .send()
+                    .await
+                    .expect("pass")
+                    .error_for_status()
+                    .expect("pass")
+                    .json::<did_webplus_core::DIDDocumentValidationResult>()
+                    .await
+                    .expect("pass");
+                reqwest::StatusCode::from_u16(did_document_validation_result.status).expect("pass")
+            } else {
+                test_util::REQWEST_CLIENT
+                    .post(&alice_did_documents_jsonl_url)
+                    .body(alice_did_document_jcs)
+                    .send()
+                    .await
+                    .expect("pass")
+                    .status()
+            };
+            (
+                alice_wallet,
+                alice_did,
+                alice_did_documents_jsonl_url,
+                status,
+            )
+        }
+    };
+
+    let base = mbx::Base::Base64Url;
+
+    // Each rejection should be reported the same way by a dry-run as by the real request.
+    for dry_run in [true, false] {
+        tracing::info!("Testing disallowed key type; dry_run: {}", dry_run);
+        let (_, _, _, status) = create_did(
+            Some("user"),
+            signature_dyn::KeyType::P256,
+            selfhash::MBHashFunction::blake3(base),
+            dry_run,
+        )
+        .await;
+        assert_eq!(status, reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+
+        tracing::info!("Testing disallowed hash function; dry_run: {}", dry_run);
+        let (_, _, _, status) = create_did(
+            Some("user"),
+            signature_dyn::KeyType::Ed25519,
+            selfhash::MBHashFunction::sha256(base),
+            dry_run,
+        )
+        .await;
+        assert_eq!(status, reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+
+        tracing::info!("Testing disallowed DID paths; dry_run: {}", dry_run);
+        for did_path in [
+            "two:components",
+            "UPPERCASE",
+            "much-too-long-path-component",
+        ] {
+            let (_, _, _, status) = create_did(
+                Some(did_path),
+                signature_dyn::KeyType::Ed25519,
+                selfhash::MBHashFunction::blake3(base),
+                dry_run,
+            )
+            .await;
+            assert_eq!(status, reqwest::StatusCode::BAD_REQUEST);
+        }
+
+        tracing::info!("Testing custom admission policy; dry_run: {}", dry_run);
+        let (_, _, _, status) = create_did(
+            Some("blocked"),
+            signature_dyn::KeyType::Ed25519,
+            selfhash::MBHashFunction::blake3(base),
+            dry_run,
+        )
+        .await;
+        assert_eq!(status, reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    tracing::info!("Testing dry-run of admitted DID creation");
+    let (_, bob_did, _, status) = create_did(
+        Some("user"),
+        signature_dyn::KeyType::Ed25519,
+        selfhash::MBHashFunction::blake3(base),
+        true,
+    )
+    .await;
+    assert_eq!(status, reqwest::StatusCode::OK);
+    // The dry-run must not have stored the DID document.
+    assert!(
+        test_util::REQWEST_CLIENT
+            .get(bob_did.resolution_url_for_did_documents_jsonl(Some(&http_scheme_override)))
+            .send()
+            .await
+            .expect("pass")
+            .text()
+            .await
+            .expect("pass")
+            .is_empty()
+    );
+
+    tracing::info!("Testing admitted DID creation");
+    let (mut alice_wallet, alice_did, alice_did_documents_jsonl_url, status) = create_did(
+        Some("user"),
+        signature_dyn::KeyType::Ed25519,
+        selfhash::MBHashFunction::blake3(base),
+        false,
+    )
+    .await;
+    assert_eq!(status, reqwest::StatusCode::OK);
+
+    tracing::info!("Testing update within the minimum update interval");
+    alice_wallet
+        .update_did(&alice_did, signature_dyn::KeyType::Ed25519)
+        .expect("pass");
+    let alice_did_document_jcs = alice_wallet
+        .controlled_did(&alice_did)
+        .expect("pass")
+        .microledger()
+        .view()
+        .latest_did_document()
+        .serialize_canonically()
+        .expect("pass");
+    assert_eq!(
+        test_util::REQWEST_CLIENT
+            .put(&alice_did_documents_jsonl_url)
+            .body(alice_did_document_jcs.clone())
+            .send()
+            .await
+            .expect("pass")
+            .status(),
+        reqwest::StatusCode::TOO_MANY_REQUESTS
+    );
+
+    tracing::info!("Testing dry-run of update within the minimum update interval");
+    let did_document_validation_result = test_util::REQWEST_CLIENT
+        .put(format!(
+            "{}{}",
+            alice_did_documents_jsonl_url,
+            did_webplus_core::VDR_DRY_RUN_PATH_SUFFIX
+        ))
+        .body(alice_did_document_jcs)
+        .send()
+        .await
+        .expect("pass")
+        .error_for_status()
+        .expect("pass")
+        .json::<did_webplus_core::DIDDocumentValidationResult>()
+        .await
+        .expect("pass");
+    assert!(!did_document_validation_result.valid);
+    assert_eq!(
+        did_document_validation_result.status,
+        reqwest::StatusCode::TOO_MANY_REQUESTS.as_u16()
+    );
+    assert!(did_document_validation_result.error_o.is_some());
+
+    tracing::info!("Shutting down VDR");
+    vdr_handle.abort();
+}
+
+#[tokio::test]
+async fn test_allowed_key_types_admission_policy_x25519() {
+    use did_webplus_vdr_lib::{
+        AdmissionPolicy, AdmissionRequest, AllowedKeyTypesAdmissionPolicy,
+        VerificationMethodKeyType,
+    };
+    use std::str::FromStr;
+
+    // X25519 isn't a signature key type, so it's classified by the multicodec of the pub key.
+    let ed25519_pub_key =
+        mbx::MBPubKey::try_from("z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK".to_string())
+            .expect("pass");
+    let x25519_pub_key =
+        did_webplus_core::mb_pub_key_from_x25519_public_key_bytes(&[7u8; 32]).expect("pass");
+    assert_eq!(
+        VerificationMethodKeyType::of_pub_key(&ed25519_pub_key).expect("pass"),
+        VerificationMethodKeyType::Signature(signature_dyn::KeyType::Ed25519)
+    );
+    assert_eq!(
+        VerificationMethodKeyType::of_pub_key(&x25519_pub_key).expect("pass"),
+        VerificationMethodKeyType::X25519
+    );
+    assert_eq!(
+        VerificationMethodKeyType::from_str("X25519").expect("pass"),
+        VerificationMethodKeyType::X25519
+    );
+
+    let mut did_document = did_webplus_core::DIDDocument::create_unsigned_root(
+        "localhost",
+        None,
+        Some("user"),
+        did_webplus_core::RootLevelUpdateRules::from(did_webplus_core::UpdateKey {
+            pub_key: ed25519_pub_key.clone(),
+        }),
+        did_webplus_core::now_utc_milliseconds(),
+        did_webplus_core::PublicKeySet {
+            authentication_v: vec![&ed25519_pub_key],
+            assertion_method_v: vec![&ed25519_pub_key],
+            key_agreement_v: vec![&x25519_pub_key],
+            capability_invocation_v: vec![&ed25519_pub_key],
+            capability_delegation_v: vec![&ed25519_pub_key],
+        },
+        &selfhash::MBHashFunction::blake3(mbx::Base::Base64Url),
+    )
+    .expect("pass");
+    did_document.finalize(None).expect("pass");
+    let did_document_body = did_document.serialize_canonically().expect("pass");
+    let admission_request = AdmissionRequest {
+        did_document: &did_document,
+        did_document_body: did_document_body.as_str(),
+        prev_did_document_o: None,
+        now_utc: time::OffsetDateTime::now_utc(),
+    };
+
+    // A DID document with an X25519 keyAgreement key is admitted iff X25519 is allowed.
+    AllowedKeyTypesAdmissionPolicy::new(vec![
+        signature_dyn::KeyType::Ed25519.into(),
+        VerificationMethodKeyType::X25519,
+    ])
+    .admit(&admission_request)
+    .await
+    .expect("pass");
+    assert!(matches!(
+        AllowedKeyTypesAdmissionPolicy::new(vec![signature_dyn::KeyType::Ed25519.into()])
+            .admit(&admission_request)
+            .await,
+        Err(did_webplus_vdr_lib::AdmissionError::DisallowedKeyType(_))
+    ));
+}
@@ -65,7 +65,12 @@ struct PrivKeyRow {
 impl PrivKeyRow {
     pub fn from_priv_key_record(ctx: &WalletStorageCtx, priv_key_record: PrivKeyRecord) -> Self {
         let wallets_row_id = RowId::from(ctx.wallets_rowid as usize);
-        let key_type = priv_key_record.pub_key.try_into_key_type().unwrap();
+        // The key type is that of the priv key if present, since it can differ from that of the pub key (e.g.
+        // an X25519 keyAgreement key is stored as the Ed25519 seed it's derived from).
+        let key_type = match priv_key_record.signer_bytes_o.as_ref() {
+            Some(signer_bytes) => signer_bytes.key_type(),
+            None => priv_key_record.pub_key.try_into_key_type().unwrap(),
+        };
         let (priv_key_format_o, signer_bytes_o) =
             if let Some(signer_bytes) = priv_key_record.signer_bytes_o {
                 (
@@ -789,6 +794,7 @@ impl did_webplus_wallet_store::WalletStorage for WalletStorageMock {
         ctx: &WalletStorageCtx,
         priv_key_record: PrivKeyRecord,
     ) -> did_webplus_wallet_store::Result<()> {
+        priv_key_record.validate_key_agreement_tag()?;
         let mut state_g = self.state_la.write().unwrap();
         state_g.add_priv_key(ctx, priv_key_record)?;
         Ok(())
@@ -871,25 +877,22 @@ impl did_webplus_wallet_store::WalletStorage for WalletStorageMock {
         )>,
     > {
         let state_g = self.state_la.read().unwrap();
-        Ok(state_g
+        state_g
             .get_locally_controlled_verification_methods(
                 ctx,
                 locally_controlled_verification_method_filter,
             )?
             .into_iter()
             .map(|locally_controlled_verification_method| {
-                let async_signer_b: Box<dyn signature_dyn::AsyncSignerT + Send + Sync> = Box::new(
-                    locally_controlled_verification_method
-                        .priv_key_record
-                        .signer_bytes_o
-                        .unwrap(),
-                );
-                (
+                let async_signer_b = locally_controlled_verification_method
+                    .priv_key_record
+                    .into_async_signer()?;
+                Ok((
                     locally_controlled_verification_method.verification_method_record,
                     async_signer_b,
-                )
+                ))
             })
-            .collect())
+            .collect()
     }
     fn as_did_doc_storage(&self) -> &dyn did_webplus_doc_store::DIDDocStorage {
         self
@@ -86,6 +86,16 @@ impl PrivKeyRow {
                 ));
             }
         }
+        priv_key_record.validate_key_agreement_tag()?;
+        // The key type is that of the priv key if present, since it can differ from that of the pub key (e.g.
+        // an X25519 keyAgreement key is stored as the Ed25519 seed it's derived from).
+        let key_type = match priv_key_record.signer_bytes_o.as_ref() {
+            Some(signer_bytes) => signer_bytes.key_type(),
+            None => priv_key_record
+                .pub_key
+                .try_into_key_type()
+                .map_err(|e| Error::Malformed(e.to_string().into()))?,
+        };
         let (priv_key_format_o, priv_key_bytes_o) = match priv_key_record.signer_bytes_o {
             Some(signer_bytes) => {
                 let priv_key_format = "signature_dyn::SignerBytes".to_string();
@@ -100,11 +110,7 @@ impl PrivKeyRow {
             wallets_rowid: ctx.wallets_rowid,
             pub_key: priv_key_record.pub_key.to_string(),
             hashed_pub_key: priv_key_record.hashed_pub_key,
-            key_type: priv_key_record
-                .pub_key
-                .try_into_key_type()
-                .unwrap()
-                .to_string(),
+            key_type: key_type.to_string(),
             did_restriction_o: priv_key_record.did_restriction_o,
             key_purpose_restriction_o: priv_key_record
                 .key_purpose_restriction_o
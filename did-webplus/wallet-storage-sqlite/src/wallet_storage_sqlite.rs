@@ -163,11 +163,38 @@ impl DIDDocStorage for WalletStorageSQLite {
     }
     async fn add_did_documents(
         &self,
-        _transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
-        _did_document_jcs_v: &[&str],
-        _did_document_v: &[DIDDocument],
+        mut transaction_o: Option<&mut dyn storage_traits::TransactionDynT>,
+        did_document_jcs_v: &[&str],
+        did_document_v: &[DIDDocument],
     ) -> did_webplus_doc_store::Result<()> {
-        todo!();
+        assert_eq!(did_document_jcs_v.len(), did_document_v.len());
+        // Each DID document also has rows in the verification_methods table, so just add them one at a time.
+        for (&did_document_jcs, did_document) in
+            did_document_jcs_v.iter().zip(did_document_v.iter())
+        {
+            if self
+                .get_did_doc_record_with_self_hash(
+                    transaction_o
+                        .as_mut()
+                        .map(|t| &mut **t as &mut dyn storage_traits::TransactionDynT),
+                    &did_document.did,
+                    &did_document.self_hash,
+                )
+                .await?
+                .is_some()
+            {
+                continue;
+            }
+            self.add_did_document(
+                transaction_o
+                    .as_mut()
+                    .map(|t| &mut **t as &mut dyn storage_traits::TransactionDynT),
+                did_document,
+                did_document_jcs,
+            )
+            .await?;
+        }
+        Ok(())
     }
     async fn get_did_doc_record_with_self_hash(
         &self,
@@ -1046,17 +1073,16 @@ impl WalletStorage for WalletStorageSQLite {
                 did_key_resource_fully_qualified,
                 pub_key: priv_key_record.pub_key.clone(),
                 hashed_pub_key: priv_key_record.hashed_pub_key.clone(),
-                did_restriction_o: priv_key_record.did_restriction_o,
+                did_restriction_o: priv_key_record.did_restriction_o.clone(),
                 key_purpose_restriction_o: priv_key_record.key_purpose_restriction_o,
                 created_at: priv_key_record.created_at,
                 last_used_at_o: priv_key_record.last_used_at_o,
                 max_usage_count_o: priv_key_record.max_usage_count_o,
                 usage_count: priv_key_record.usage_count,
                 deleted_at_o: priv_key_record.deleted_at_o,
-                comment_o: priv_key_record.comment_o,
+                comment_o: priv_key_record.comment_o.clone(),
             };
-            let async_signer_b: Box<dyn signature_dyn::AsyncSignerT + Send + Sync> =
-                Box::new(priv_key_record.signer_bytes_o.unwrap());
+            let async_signer_b = priv_key_record.into_async_signer()?;
             locally_controlled_verification_method_v
                 .push((verification_method_record, async_signer_b));
         }
@@ -15,11 +15,16 @@ mod wallet_storage_ctx;
 pub use crate::{
     error::Error,
     locally_controlled_verification_method_filter::LocallyControlledVerificationMethodFilter,
-    priv_key_record::PrivKeyRecord, priv_key_record_filter::PrivKeyRecordFilter,
-    priv_key_usage::PrivKeyUsage, priv_key_usage_record::PrivKeyUsageRecord,
-    priv_key_usage_record_filter::PrivKeyUsageRecordFilter, priv_key_usage_type::PrivKeyUsageType,
-    verification_method_record::VerificationMethodRecord, wallet_record::WalletRecord,
-    wallet_record_filter::WalletRecordFilter, wallet_storage::WalletStorage,
+    priv_key_record::{KeyAgreementKeySigner, PrivKeyRecord},
+    priv_key_record_filter::PrivKeyRecordFilter,
+    priv_key_usage::PrivKeyUsage,
+    priv_key_usage_record::PrivKeyUsageRecord,
+    priv_key_usage_record_filter::PrivKeyUsageRecordFilter,
+    priv_key_usage_type::PrivKeyUsageType,
+    verification_method_record::VerificationMethodRecord,
+    wallet_record::WalletRecord,
+    wallet_record_filter::WalletRecordFilter,
+    wallet_storage::WalletStorage,
     wallet_storage_ctx::WalletStorageCtx,
 };
 pub type Result<T> = std::result::Result<T, Error>;
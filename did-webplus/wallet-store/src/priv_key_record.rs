@@ -1,4 +1,5 @@
-use did_webplus_core::KeyPurposeFlags;
+use crate::{Error, Result};
+use did_webplus_core::{KeyPurpose, KeyPurposeFlags};
 
 // TODO: Consider making a "non-deleted" version of PrivKeyRecord that has those constraints.
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
@@ -38,3 +39,87 @@ pub struct PrivKeyRecord {
     /// intented usage for this key.
     pub comment_o: Option<String>,
 }
+
+impl PrivKeyRecord {
+    /// Returns true if this is a keyAgreement (i.e. ECDH) priv key.  keyAgreement priv keys are tagged by having
+    /// a key_purpose_restriction_o of exactly KeyPurpose::KeyAgreement.  Such a priv key may be stored in the
+    /// form of a signature priv key (an X25519 priv key is stored as the Ed25519 seed it's derived from), and
+    /// it must never be used for signing, since a signature would leak information related to the ECDH secret.
+    pub fn is_key_agreement_key(&self) -> bool {
+        self.key_purpose_restriction_o == Some(KeyPurposeFlags::from(KeyPurpose::KeyAgreement))
+    }
+    /// Checks that if this priv key's pub key isn't a signature pub key of the same key type as the priv key
+    /// (e.g. an X25519 pub key whose priv key is stored as an Ed25519 seed), then this priv key is tagged as a
+    /// keyAgreement priv key (see is_key_agreement_key).  This is checked by WalletStorage impls when adding
+    /// priv keys, so that such priv keys can't end up being used for signing.
+    pub fn validate_key_agreement_tag(&self) -> Result<()> {
+        let signer_bytes = match self.signer_bytes_o.as_ref() {
+            Some(signer_bytes) => signer_bytes,
+            None => return Ok(()),
+        };
+        if self.pub_key.try_into_key_type().ok() != Some(signer_bytes.key_type())
+            && !self.is_key_agreement_key()
+        {
+            return Err(Error::Malformed(
+                format!(
+                    "priv key for pub key {} has key type {:?}, which doesn't match its pub key, so it must be restricted to keyAgreement",
+                    self.pub_key,
+                    signer_bytes.key_type()
+                )
+                .into(),
+            ));
+        }
+        Ok(())
+    }
+    /// Forms the signer for this (non-deleted) priv key.  A keyAgreement priv key (see is_key_agreement_key)
+    /// produces a KeyAgreementKeySigner, which refuses to sign.
+    pub fn into_async_signer(self) -> Result<Box<dyn signature_dyn::AsyncSignerT + Send + Sync>> {
+        let is_key_agreement_key = self.is_key_agreement_key();
+        let signer_bytes = self.signer_bytes_o.ok_or_else(|| {
+            Error::NotFound(format!("priv key for {} (it was deleted)", self.pub_key).into())
+        })?;
+        if is_key_agreement_key {
+            Ok(Box::new(KeyAgreementKeySigner {
+                pub_key: self.pub_key,
+            }))
+        } else {
+            Ok(Box::new(signer_bytes))
+        }
+    }
+}
+
+/// This is the signer for a keyAgreement priv key (see PrivKeyRecord::is_key_agreement_key), which doesn't
+/// hold the priv key and refuses every signing operation.  keyAgreement priv keys are only to be used for
+/// key exchange, which wallets do via WalletStorage::get_priv_key.
+pub struct KeyAgreementKeySigner {
+    pub_key: mbx::MBPubKey,
+}
+
+impl KeyAgreementKeySigner {
+    fn refusal(&self) -> String {
+        format!(
+            "priv key for {} is a keyAgreement key, which can't be used for signing",
+            self.pub_key
+        )
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+impl signature_dyn::AsyncSignerT for KeyAgreementKeySigner {
+    async fn async_key_id(&self) -> signature_dyn::Result<Option<String>> {
+        Ok(None)
+    }
+    async fn async_key_type(&self) -> signature_dyn::Result<signature_dyn::KeyType> {
+        Err(self.refusal().into())
+    }
+    async fn async_get_verifier(&self) -> signature_dyn::Result<Box<dyn signature_dyn::VerifierT>> {
+        Err(self.refusal().into())
+    }
+    async fn async_try_sign_message(
+        &self,
+        _message_byte_v: &[u8],
+    ) -> signature_dyn::Result<Box<dyn signature_dyn::SignatureT>> {
+        Err(self.refusal().into())
+    }
+}
@@ -4,6 +4,8 @@ use std::borrow::Cow;
 pub enum Error {
     #[error(transparent)]
     DIDDocStoreError(did_webplus_doc_store::Error),
+    #[error("DID document rejected by VDR: {0}")]
+    DIDDocumentRejectedByVDR(Cow<'static, str>),
     #[error("Failed to fetch DID updates: {0}")]
     DIDFetchError(Cow<'static, str>),
     #[error("Wallet does not control DID: {0}")]
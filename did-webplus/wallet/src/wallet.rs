@@ -1,5 +1,7 @@
 use crate::{Error, Result};
-use did_webplus_core::{DIDFullyQualified, DIDStr};
+use did_webplus_core::{
+    DIDFullyQualified, DIDKeyResourceFullyQualified, DIDKeyResourceFullyQualifiedStr, DIDStr,
+};
 use did_webplus_wallet_store::{
     LocallyControlledVerificationMethodFilter, VerificationMethodRecord,
 };
@@ -19,6 +21,11 @@ pub struct CreateDIDParameters<'a> {
     /// UpdateKey ("key" in the JSON form of the update rules).  It is recommended to always use a
     /// HashedUpdateKey for pre-rotation keys.
     pub mb_hash_function_for_update_key_o: Option<&'a selfhash::MBHashFunction>,
+    /// If Some(key_id), then the DID create request will be authorized (for VDRs that require signed
+    /// authorization of DID create requests) by a detached JWS signed by the specified locally-controlled
+    /// capabilityInvocation key, e.g. a key of a "registrar" DID that the VDR permits to create DIDs under
+    /// the requested path.  If None, then the new DID's own capabilityInvocation key signs the request.
+    pub vdr_authz_key_id_o: Option<&'a did_webplus_core::DIDKeyResourceFullyQualifiedStr>,
 }
 
 #[derive(Clone, Debug)]
@@ -91,6 +98,29 @@ pub trait Wallet: Send + Sync {
         deactivate_did_parameters: DeactivateDIDParameters<'_>,
         http_options_o: Option<&did_webplus_core::HTTPOptions>,
     ) -> Result<DIDFullyQualified>;
+    /// Derive a shared secret via ECDH between the specified locally-controlled keyAgreement key and the given
+    /// pub key (typically an ephemeral pub key, as used in JWE's ECDH-ES).  The usage of the keyAgreement key is
+    /// recorded as KeyExchange.  Note that the priv keys for keys that have been rotated out of the DID document
+    /// are retired, so key exchange is only possible with the keyAgreement keys of the DID's current version.
+    /// The raw shared secret should be passed through a KDF before being used as a symmetric key.
+    async fn derive_shared_secret(
+        &self,
+        key_id: &DIDKeyResourceFullyQualifiedStr,
+        other_pub_key: &mbx::MBPubKeyStr,
+    ) -> Result<zeroize::Zeroizing<Vec<u8>>>;
+    /// Derive a shared secret via ECDH between this wallet's keyAgreement key for the specified locally-controlled
+    /// DID (or the uniquely determinable one if did_o is None) and the given keyAgreement key of another DID.  The
+    /// other DID's document is resolved, and other_key_id must be listed under keyAgreement in it.  The local
+    /// keyAgreement key of the same key type (e.g. X25519 or P-256) as the other key is used, and its usage is
+    /// recorded as KeyExchangeWithDID.  Returns the fully qualified key ID of the local key that was used (which
+    /// the other party needs in order to derive the same shared secret) and the raw shared secret.  The raw shared
+    /// secret should be passed through a KDF before being used as a symmetric key.
+    async fn derive_shared_secret_with_did(
+        &self,
+        did_o: Option<&DIDStr>,
+        other_key_id: &DIDKeyResourceFullyQualifiedStr,
+        http_options_o: Option<&did_webplus_core::HTTPOptions>,
+    ) -> Result<(DIDKeyResourceFullyQualified, zeroize::Zeroizing<Vec<u8>>)>;
 
     // Below here are lower-level methods for accessing controlled DIDs and verification methods (i.e. private keys).
 
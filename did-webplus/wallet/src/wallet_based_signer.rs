@@ -1,4 +1,4 @@
-use crate::{Result, Wallet};
+use crate::{Error, Result, Wallet};
 use ssi_claims::data_integrity::AnySignatureAlgorithm;
 use ssi_verification_methods::{SignatureProtocol, VerificationMethod, protocol::WithProtocol};
 use std::borrow::Cow;
@@ -22,6 +22,7 @@ impl<W: Wallet> WalletBasedSigner<W> {
     /// be stale). When false, only locally stored documents are used (offline signing).
     ///
     /// If the filter doesn't match exactly one verification method record, then an error will be returned.
+    /// KeyPurpose::KeyAgreement is rejected, since keyAgreement keys are for key exchange, not signing.
     pub async fn new(
         wallet: W,
         did: &did_webplus_core::DIDStr,
@@ -30,6 +31,11 @@ impl<W: Wallet> WalletBasedSigner<W> {
         http_options_o: Option<&did_webplus_core::HTTPOptions>,
         fetch_did_first: bool,
     ) -> Result<Self> {
+        if key_purpose == did_webplus_core::KeyPurpose::KeyAgreement {
+            return Err(Error::NoSuitablePrivKeyFound(
+                format!("keyAgreement keys can't be used for signing (DID {})", did).into(),
+            ));
+        }
         if fetch_did_first {
             wallet.fetch_did(did, http_options_o).await?;
         }
@@ -11,6 +11,7 @@ import init, {
   IssueVPParameters,
   LocallyControlledVerificationMethodFilter,
   MBHashFunction,
+  StatusListFetcher,
   UpdateDIDParameters,
   Wallet,
   did_resolve,
@@ -1225,9 +1226,9 @@ async function handleJwtVerify() {
 
 async function handleSimpleVerify({ kind }) {
   const map = {
-    vcJwt: { inputEl: el.vcJwtVerifyInput, statusEl: el.vcJwtVerifyStatus, resultEl: el.vcJwtVerifyResult, fn: verify_vc_jwt, json: false },
+    vcJwt: { inputEl: el.vcJwtVerifyInput, statusEl: el.vcJwtVerifyStatus, resultEl: el.vcJwtVerifyResult, fn: verify_vc_jwt, json: false, checksStatus: true },
     vpJwt: { inputEl: el.vpJwtVerifyInput, statusEl: el.vpJwtVerifyStatus, resultEl: el.vpJwtVerifyResult, fn: verify_vp_jwt, json: false },
-    vcLdp: { inputEl: el.vcLdpVerifyInput, statusEl: el.vcLdpVerifyStatus, fn: verify_vc_ldp, json: true },
+    vcLdp: { inputEl: el.vcLdpVerifyInput, statusEl: el.vcLdpVerifyStatus, fn: verify_vc_ldp, json: true, checksStatus: true },
     vpLdp: { inputEl: el.vpLdpVerifyInput, statusEl: el.vpLdpVerifyStatus, fn: verify_vp_ldp, json: true },
   };
   const cfg = map[kind];
@@ -1237,11 +1238,12 @@ async function handleSimpleVerify({ kind }) {
   try {
     const raw = (cfg.inputEl.value ?? "").trim();
     if (!raw) throw new Error("Input is empty");
-    if (cfg.json) {
-      const value = safeJsonParse(raw);
-      await cfg.fn(value, state.didResolver);
+    const input = cfg.json ? safeJsonParse(raw) : raw;
+    if (cfg.checksStatus) {
+      // VCs having a credentialStatus can only be verified if their status list can be fetched.
+      await cfg.fn(input, StatusListFetcher.new_http(undefined), state.didResolver);
     } else {
-      await cfg.fn(raw, state.didResolver);
+      await cfg.fn(input, state.didResolver);
     }
     if (cfg.resultEl) cfg.resultEl.textContent = prettyJson(decodeJwtToObject(raw));
     setInlineStatus(cfg.statusEl, "ok", "Valid");
@@ -59,6 +59,27 @@ impl verifier_resolver::VerifierResolver for DIDResolver {
     ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
         self.as_verifier_resolver().resolve(verifier_str).await
     }
+    async fn resolve_for_purpose(
+        &self,
+        verifier_str: &str,
+        verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        self.as_verifier_resolver()
+            .resolve_for_purpose(verifier_str, verification_relationship)
+            .await
+    }
+    async fn resolve_for_purpose_with_version(
+        &self,
+        verifier_str: &str,
+        verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<(Box<dyn signature_dyn::VerifierT>, Option<String>)> {
+        self.as_verifier_resolver()
+            .resolve_for_purpose_with_version(verifier_str, verification_relationship)
+            .await
+    }
+    fn vdg_host_o(&self) -> Option<&str> {
+        self.as_verifier_resolver().vdg_host_o()
+    }
 }
 
 /// Resolve the given DID query using the given DIDResolver.  Returns the JCS-serialized DID document as a String.
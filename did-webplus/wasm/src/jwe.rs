@@ -0,0 +1,46 @@
+use crate::{DIDResolver, Result, Wallet, into_js_value};
+use std::ops::Deref;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+/// Encrypt a payload to the given recipients, producing a JWE.  plaintext specifies the payload (the payload
+/// should be bytes (Vec<u8>), but for now just use a String).  Each recipient is a DID (in which case its first
+/// keyAgreement key of a supported key type is used) or a DID key resource specifying a keyAgreement key.
+/// The recipients' keyAgreement keys are determined using did_resolver.
+#[wasm_bindgen]
+pub async fn jwe_encrypt(
+    plaintext: String,
+    recipient_v: Vec<String>,
+    key_management_algorithm: did_webplus_jwe::JWEKeyManagementAlgorithm,
+    serialization: did_webplus_jwe::JWESerialization,
+    did_resolver: &DIDResolver,
+) -> Result<String> {
+    let recipient_v = recipient_v
+        .iter()
+        .map(|recipient| recipient.as_str())
+        .collect::<Vec<_>>();
+    did_webplus_cli_lib::wallet_did_encrypt(
+        plaintext.as_bytes(),
+        &recipient_v,
+        key_management_algorithm,
+        serialization,
+        did_resolver.deref(),
+    )
+    .await
+    .map_err(into_js_value)
+}
+
+/// Decrypt a JWE (in the compact or either JSON serialization) using a keyAgreement key controlled by the
+/// given wallet, optionally restricting to the keys of the given controlled DID.  The plaintext must be UTF-8.
+#[wasm_bindgen]
+pub async fn jwe_decrypt(jwe: String, wallet: &Wallet, did_o: Option<String>) -> Result<String> {
+    let did_o = did_o
+        .as_deref()
+        .map(did_webplus_core::DIDStr::new_ref)
+        .transpose()
+        .map_err(into_js_value)?;
+    let (key_id, plaintext) = did_webplus_cli_lib::wallet_did_decrypt(&jwe, wallet, did_o)
+        .await
+        .map_err(into_js_value)?;
+    tracing::debug!("jwe_decrypt used keyAgreement key {}", key_id);
+    String::from_utf8(plaintext.to_vec()).map_err(into_js_value)
+}
@@ -1,4 +1,4 @@
-use crate::{Result, VerifierResolver, WalletBasedSigner, into_js_value};
+use crate::{KeyPurpose, Result, VerifierResolver, WalletBasedSigner, into_js_value};
 use std::ops::Deref;
 use wasm_bindgen::prelude::wasm_bindgen;
 
@@ -25,29 +25,149 @@ pub async fn jws_sign(
 }
 
 /// Verify a JWS, optionally specifying a detached payload (which should be None/null if the payload is attached).
-/// Will return error if verification fails.
+/// key_purpose_o optionally specifies the verification relationship that the signing key must have in the
+/// signer's DID document, defaulting to AssertionMethod (UpdateDIDDocument is not a verification relationship
+/// and is rejected).  Will return error if verification fails.
 #[wasm_bindgen]
 pub async fn jws_verify(
     jws: String,
     detached_payload_o: Option<String>,
     verifier_resolver: &VerifierResolver,
+    key_purpose_o: Option<KeyPurpose>,
 ) -> Result<()> {
     tracing::debug!("jws_verify(jws: {:?})", jws);
+    let verification_relationship = verifier_resolver::VerificationRelationship::try_from(
+        key_purpose_o
+            .map(did_webplus_core::KeyPurpose::from)
+            .unwrap_or(did_webplus_core::KeyPurpose::AssertionMethod),
+    )
+    .map_err(into_js_value)?;
     let verifier_resolver = verifier_resolver.clone();
     let jws = did_webplus_jws::JWS::try_from(jws).map_err(into_js_value)?;
     let mut detached_payload_buffer_o = detached_payload_o.as_ref().map(|x| x.as_bytes());
+    let mut verification_report = verifier_resolver::VerificationReport::new();
     did_webplus_cli_lib::jws_verify(
         &jws,
         detached_payload_buffer_o
             .as_mut()
             .map(|x| x as &mut dyn std::io::Read),
+        verification_relationship,
         verifier_resolver.deref(),
+        &mut verification_report,
     )
     .await
     .map_err(|e| {
-        tracing::error!("jws_verify FAILED");
+        tracing::error!(
+            "jws_verify FAILED; verification report: {:?}",
+            verification_report
+        );
         into_js_value(e)
     })?;
     tracing::info!("jws_verify SUCCEEDED");
     Ok(())
 }
+
+/// Sign a JWS in JWS JSON Serialization, with one signature from each of the given WalletBasedSigners (e.g.
+/// one for each of several parties' DIDs).  serialization must be General unless there is exactly one signer.
+#[wasm_bindgen]
+pub async fn jws_json_sign(
+    payload_string: String,
+    payload_presence: did_webplus_jws::JWSPayloadPresence,
+    payload_encoding: did_webplus_jws::JWSPayloadEncoding,
+    wallet_based_signer_v: Vec<WalletBasedSigner>,
+    serialization: did_webplus_jws::JWSSerialization,
+) -> Result<String> {
+    let jws_json = did_webplus_cli_lib::jws_json_sign(
+        &mut payload_string.as_bytes(),
+        payload_presence,
+        payload_encoding,
+        wallet_based_signer_v
+            .iter()
+            .map(|wallet_based_signer| wallet_based_signer.deref())
+            .collect::<Vec<_>>()
+            .as_slice(),
+    )
+    .await
+    .map_err(into_js_value)?;
+    jws_json
+        .to_serialization_string(serialization)
+        .map_err(into_js_value)
+}
+
+/// Add a signature from the given WalletBasedSigner to an existing JWS (in any serialization), e.g. to co-sign
+/// a JWS that another party has signed.  detached_payload_o must be specified if and only if the JWS has a
+/// detached payload.
+#[wasm_bindgen]
+pub async fn jws_cosign(
+    jws: String,
+    detached_payload_o: Option<String>,
+    wallet_based_signer: &WalletBasedSigner,
+    serialization: did_webplus_jws::JWSSerialization,
+) -> Result<String> {
+    let mut jws_json = jws
+        .parse::<did_webplus_jws::JWSJSON>()
+        .map_err(into_js_value)?;
+    let mut detached_payload_buffer_o = detached_payload_o.as_ref().map(|x| x.as_bytes());
+    jws_json
+        .async_add_signature(
+            wallet_based_signer.key_fully_qualified().to_string(),
+            wallet_based_signer.deref(),
+            detached_payload_buffer_o
+                .as_mut()
+                .map(|x| x as &mut dyn std::io::Read),
+        )
+        .await
+        .map_err(into_js_value)?;
+    jws_json
+        .to_serialization_string(serialization)
+        .map_err(into_js_value)
+}
+
+/// Verify a JWS in JWS JSON Serialization (or JWS Compact Serialization), optionally specifying a detached
+/// payload (which should be None/null if the payload is attached).  key_purpose specifies the verification
+/// relationship that each signing key must have in its signer's DID document.  signer_policy specifies how
+/// many signatures must verify: "all", "any", or "<k>-of-n" (signatures from at least k distinct DIDs).
+/// Returns the "kid" of each signature that verified, or error if the signer policy isn't satisfied.
+#[wasm_bindgen]
+pub async fn jws_json_verify(
+    jws: String,
+    detached_payload_o: Option<String>,
+    key_purpose: KeyPurpose,
+    signer_policy: String,
+    verifier_resolver: &VerifierResolver,
+) -> Result<Vec<String>> {
+    tracing::debug!("jws_json_verify(jws: {:?})", jws);
+    let verification_relationship = verifier_resolver::VerificationRelationship::try_from(
+        did_webplus_core::KeyPurpose::from(key_purpose),
+    )
+    .map_err(into_js_value)?;
+    let signer_policy = signer_policy
+        .parse::<did_webplus_jws::JWSSignerPolicy>()
+        .map_err(into_js_value)?;
+    let verifier_resolver = verifier_resolver.clone();
+    let jws_json = jws
+        .parse::<did_webplus_jws::JWSJSON>()
+        .map_err(into_js_value)?;
+    let mut detached_payload_buffer_o = detached_payload_o.as_ref().map(|x| x.as_bytes());
+    let mut verification_report = verifier_resolver::VerificationReport::new();
+    let verified_kid_v = did_webplus_cli_lib::jws_json_verify(
+        &jws_json,
+        detached_payload_buffer_o
+            .as_mut()
+            .map(|x| x as &mut dyn std::io::Read),
+        verification_relationship,
+        signer_policy,
+        verifier_resolver.deref(),
+        &mut verification_report,
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!(
+            "jws_json_verify FAILED; verification report: {:?}",
+            verification_report
+        );
+        into_js_value(e)
+    })?;
+    tracing::info!("jws_json_verify SUCCEEDED");
+    Ok(verified_kid_v)
+}
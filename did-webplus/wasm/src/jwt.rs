@@ -1,4 +1,4 @@
-use crate::{DIDResolver, Result, WalletBasedSigner, into_js_value};
+use crate::{DIDResolver, KeyPurpose, Result, VerifierResolver, WalletBasedSigner, into_js_value};
 use std::ops::Deref;
 use wasm_bindgen::{JsValue, prelude::wasm_bindgen};
 
@@ -20,8 +20,73 @@ pub async fn jwt_sign(claims: JsValue, wallet_based_signer: &WalletBasedSigner)
 #[wasm_bindgen]
 pub async fn jwt_verify(jwt: String, did_resolver: &DIDResolver) -> Result<JsValue> {
     let did_resolver_a = did_resolver.as_arc().clone();
-    let jwt = did_webplus_ssi::verify_jwt(&jwt, did_resolver_a)
-        .await
-        .map_err(into_js_value)?;
+    let jwt = did_webplus_ssi::verify_jwt(
+        &jwt,
+        did_resolver_a,
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .map_err(into_js_value)?;
     Ok(serde_wasm_bindgen::to_value(&jwt).map_err(into_js_value)?)
 }
+
+/// Sign a JWT using the native did:webplus JWT implementation (rather than the ssi-based jwt_sign).  Claims is
+/// the content of the JWT, which should be a JSON object.  If the "iss" claim is absent, it will be set to the
+/// DID of the signing key, and if present, it must be that DID.
+#[wasm_bindgen]
+pub async fn native_jwt_sign(
+    claims: JsValue,
+    wallet_based_signer: &WalletBasedSigner,
+) -> Result<String> {
+    let claims: did_webplus_jws::JWTClaims =
+        serde_wasm_bindgen::from_value(claims).map_err(into_js_value)?;
+    let jwt = did_webplus_jws::JWT::async_signed(
+        claims,
+        wallet_based_signer.key_fully_qualified().to_string(),
+        wallet_based_signer.deref(),
+    )
+    .await
+    .map_err(into_js_value)?;
+    Ok(jwt.into_string())
+}
+
+/// Verify a JWT using the native did:webplus JWT implementation (rather than the ssi-based jwt_verify), which
+/// also validates its claims: "iss" must be the DID of the signing key, "exp"/"nbf"/"iat" are checked against
+/// the current time (tolerating clock_skew_seconds_o, which defaults to 60), and "aud" must contain
+/// expected_audience_o (a JWT with an "aud" claim is rejected if no audience is expected).  key_purpose specifies
+/// the verification relationship that the signing key must have in the signer's DID document.  Returns the claims.
+#[wasm_bindgen]
+pub async fn native_jwt_verify(
+    jwt: String,
+    key_purpose: KeyPurpose,
+    expected_audience_o: Option<String>,
+    clock_skew_seconds_o: Option<u32>,
+    verifier_resolver: &VerifierResolver,
+) -> Result<JsValue> {
+    let verification_relationship = verifier_resolver::VerificationRelationship::try_from(
+        did_webplus_core::KeyPurpose::from(key_purpose),
+    )
+    .map_err(into_js_value)?;
+    let jwt = jwt.parse::<did_webplus_jws::JWT>().map_err(into_js_value)?;
+    let validation_options = did_webplus_jws::JWTValidationOptions {
+        clock_skew_seconds: clock_skew_seconds_o
+            .map(u64::from)
+            .unwrap_or(did_webplus_jws::JWTValidationOptions::DEFAULT_CLOCK_SKEW_SECONDS),
+        expected_audience_o,
+        expected_issuer_o: None,
+        require_exp: false,
+        // The system clock isn't available on wasm32, so use the JS clock.
+        now_o: Some((js_sys::Date::now() / 1000.0) as i64),
+    };
+    let verifier_resolver = verifier_resolver.clone();
+    did_webplus_cli_lib::jwt_verify(
+        &jwt,
+        verification_relationship,
+        &validation_options,
+        verifier_resolver.deref(),
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .map_err(into_js_value)?;
+    Ok(serde_wasm_bindgen::to_value(jwt.claims()).map_err(into_js_value)?)
+}
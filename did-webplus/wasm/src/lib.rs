@@ -5,13 +5,16 @@ mod http_headers_for;
 mod http_options;
 mod http_scheme_override;
 mod issue_vp_parameters;
+mod jwe;
 mod jws;
 mod jwt;
 mod key_purpose;
 mod key_type;
 mod locally_controlled_verification_method_filter;
 mod mb_hash_function;
+mod sd_jwt;
 mod signer;
+mod status_list_fetcher;
 mod vc;
 mod verification_method_record;
 mod verifier_resolver;
@@ -31,20 +34,33 @@ pub use crate::{
     http_options::HTTPOptions,
     http_scheme_override::HTTPSchemeOverride,
     issue_vp_parameters::IssueVPParameters,
-    jws::{jws_sign, jws_verify},
-    jwt::{jwt_sign, jwt_verify},
+    jwe::{jwe_decrypt, jwe_encrypt},
+    jws::{jws_cosign, jws_json_sign, jws_json_verify, jws_sign, jws_verify},
+    jwt::{jwt_sign, jwt_verify, native_jwt_sign, native_jwt_verify},
     key_purpose::{KeyPurpose, key_purpose_as_str},
     key_type::KeyType,
     locally_controlled_verification_method_filter::LocallyControlledVerificationMethodFilter,
     mb_hash_function::{Base, HashFunction, MBHashFunction},
+    sd_jwt::{
+        create_sd_jwt_presentation, issue_sd_jwt, issue_sd_jwt_vc, verify_sd_jwt, verify_sd_jwt_vc,
+    },
     signer::Signer,
-    vc::{issue_vc_jwt, issue_vc_ldp, new_unsigned_credential, verify_vc_jwt, verify_vc_ldp},
+    status_list_fetcher::StatusListFetcher,
+    vc::{
+        issue_vc_jwt, issue_vc_jwt_v2, issue_vc_ldp, issue_vc_ldp_v2, new_enveloped_credential_v2,
+        new_unsigned_credential, new_unsigned_credential_v2, verify_vc_jwt, verify_vc_jwt_v2,
+        verify_vc_ldp, verify_vc_ldp_v2,
+    },
     verification_method_record::VerificationMethodRecord,
     verifier_resolver::VerifierResolver,
     vjson::{vjson_default_schema, vjson_self_hash, vjson_sign_and_self_hash, vjson_verify},
     vjson_resolver::VJSONResolver,
     vjson_store::VJSONStore,
-    vp::{issue_vp_jwt, issue_vp_ldp, new_unsigned_presentation, verify_vp_jwt, verify_vp_ldp},
+    vp::{
+        issue_vp_jwt, issue_vp_jwt_v2, issue_vp_ldp, issue_vp_ldp_v2, new_unsigned_presentation,
+        new_unsigned_presentation_v2, verify_vp_jwt, verify_vp_jwt_v2, verify_vp_ldp,
+        verify_vp_ldp_v2,
+    },
     wallet::{CreateDIDParameters, DeactivateDIDParameters, UpdateDIDParameters, Wallet},
     wallet_based_signer::WalletBasedSigner,
     wallet_record::WalletRecord,
@@ -0,0 +1,198 @@
+use crate::{DIDResolver, Result, WalletBasedSigner, into_js_value};
+use std::ops::Deref;
+use wasm_bindgen::{JsValue, prelude::wasm_bindgen};
+
+/// Issue an SD-JWT whose claims are the given JSON object, where the claims at the given JSON pointers
+/// (e.g. "/address/street_address") are made selectively disclosable.  If holder_did_o is specified, then the
+/// SD-JWT is bound to that DID, and presentations of it must carry a Key Binding JWT signed by that DID.
+/// wallet_based_signer must use an assertionMethod key.
+#[wasm_bindgen]
+pub async fn issue_sd_jwt(
+    claims: JsValue,
+    selectively_disclosable_pointer_v: Vec<String>,
+    holder_did_o: Option<String>,
+    wallet_based_signer: &WalletBasedSigner,
+) -> Result<String> {
+    let claims: serde_json::Value =
+        serde_wasm_bindgen::from_value(claims).map_err(into_js_value)?;
+    let selectively_disclosable_pointer_v = selectively_disclosable_pointer_v
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    let holder_did_o = holder_did_o
+        .as_deref()
+        .map(did_webplus_core::DIDStr::new_ref)
+        .transpose()
+        .map_err(into_js_value)?;
+    let sd_jwt = did_webplus_ssi::issue_sd_jwt(
+        claims,
+        selectively_disclosable_pointer_v.as_slice(),
+        holder_did_o,
+        wallet_based_signer.deref(),
+    )
+    .await
+    .map_err(into_js_value)?;
+    Ok(sd_jwt.to_string())
+}
+
+/// Issue an SD-JWT VC of the given credential type (the "vct" claim).  See issue_sd_jwt for the meaning of
+/// the other arguments.
+#[wasm_bindgen]
+pub async fn issue_sd_jwt_vc(
+    vct: String,
+    claims: JsValue,
+    selectively_disclosable_pointer_v: Vec<String>,
+    holder_did_o: Option<String>,
+    wallet_based_signer: &WalletBasedSigner,
+) -> Result<String> {
+    let claims: serde_json::Value =
+        serde_wasm_bindgen::from_value(claims).map_err(into_js_value)?;
+    let selectively_disclosable_pointer_v = selectively_disclosable_pointer_v
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    let holder_did_o = holder_did_o
+        .as_deref()
+        .map(did_webplus_core::DIDStr::new_ref)
+        .transpose()
+        .map_err(into_js_value)?;
+    let sd_jwt_vc = did_webplus_ssi::issue_sd_jwt_vc(
+        vct.as_str(),
+        claims,
+        selectively_disclosable_pointer_v.as_slice(),
+        holder_did_o,
+        wallet_based_signer.deref(),
+    )
+    .await
+    .map_err(into_js_value)?;
+    Ok(sd_jwt_vc.to_string())
+}
+
+/// Create a presentation of the given SD-JWT (or SD-JWT VC) which discloses only the claims at the given JSON
+/// pointers, along with a Key Binding JWT for the given audience and nonce.  wallet_based_signer must use an
+/// authentication key of the DID that the SD-JWT is bound to.
+#[wasm_bindgen]
+pub async fn create_sd_jwt_presentation(
+    sd_jwt: String,
+    disclosed_pointer_v: Vec<String>,
+    audience: String,
+    nonce: String,
+    wallet_based_signer: &WalletBasedSigner,
+) -> Result<String> {
+    let sd_jwt = sd_jwt
+        .parse::<did_webplus_ssi::SDJWT>()
+        .map_err(into_js_value)?;
+    let disclosed_pointer_v = disclosed_pointer_v
+        .iter()
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    let presentation = did_webplus_ssi::create_sd_jwt_presentation(
+        &sd_jwt,
+        disclosed_pointer_v.as_slice(),
+        audience.as_str(),
+        nonce.as_str(),
+        wallet_based_signer.deref(),
+    )
+    .await
+    .map_err(into_js_value)?;
+    Ok(presentation.to_string())
+}
+
+/// Verify an SD-JWT presentation, returning its disclosed claims.  If expected_audience_o and expected_nonce_o
+/// are specified, then the Key Binding JWT must have them as its "aud" and "nonce" claims.  If require_key_binding
+/// is true, then the presentation must have a Key Binding JWT, and expected_audience_o and expected_nonce_o must
+/// be specified.  The Key Binding JWT must have been issued no more than key_binding_max_age_seconds_o (which
+/// defaults to 300) ago.  Time-based claims are checked against the current time, tolerating clock_skew_seconds_o
+/// (which defaults to 60).  NOTE: This does NOT also verify credential-specific validity constraints; that must
+/// be done separately and explicitly.
+#[wasm_bindgen]
+pub async fn verify_sd_jwt(
+    sd_jwt: String,
+    expected_audience_o: Option<String>,
+    expected_nonce_o: Option<String>,
+    require_key_binding: bool,
+    key_binding_max_age_seconds_o: Option<u32>,
+    clock_skew_seconds_o: Option<u32>,
+    did_resolver: &DIDResolver,
+) -> Result<JsValue> {
+    let sd_jwt = sd_jwt
+        .parse::<did_webplus_ssi::SDJWT>()
+        .map_err(into_js_value)?;
+    let verification_options = verification_options(
+        expected_audience_o,
+        expected_nonce_o,
+        require_key_binding,
+        key_binding_max_age_seconds_o,
+        clock_skew_seconds_o,
+    );
+    let did_resolver_a = did_resolver.as_arc().clone();
+    let claims = did_webplus_ssi::verify_sd_jwt(
+        &sd_jwt,
+        did_resolver_a,
+        &verification_options,
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .map_err(into_js_value)?;
+    Ok(serde_wasm_bindgen::to_value(&claims).map_err(into_js_value)?)
+}
+
+/// Verify an SD-JWT VC presentation, returning its disclosed claims.  If expected_vct_o is specified, then the
+/// credential must be of that type.  See verify_sd_jwt for the meaning of the other arguments.
+#[wasm_bindgen]
+pub async fn verify_sd_jwt_vc(
+    sd_jwt_vc: String,
+    expected_vct_o: Option<String>,
+    expected_audience_o: Option<String>,
+    expected_nonce_o: Option<String>,
+    require_key_binding: bool,
+    key_binding_max_age_seconds_o: Option<u32>,
+    clock_skew_seconds_o: Option<u32>,
+    did_resolver: &DIDResolver,
+) -> Result<JsValue> {
+    let sd_jwt_vc = sd_jwt_vc
+        .parse::<did_webplus_ssi::SDJWT>()
+        .map_err(into_js_value)?;
+    let verification_options = verification_options(
+        expected_audience_o,
+        expected_nonce_o,
+        require_key_binding,
+        key_binding_max_age_seconds_o,
+        clock_skew_seconds_o,
+    );
+    let did_resolver_a = did_resolver.as_arc().clone();
+    let claims = did_webplus_ssi::verify_sd_jwt_vc(
+        &sd_jwt_vc,
+        expected_vct_o.as_deref(),
+        did_resolver_a,
+        &verification_options,
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .map_err(into_js_value)?;
+    Ok(serde_wasm_bindgen::to_value(&claims).map_err(into_js_value)?)
+}
+
+fn verification_options(
+    expected_audience_o: Option<String>,
+    expected_nonce_o: Option<String>,
+    require_key_binding: bool,
+    key_binding_max_age_seconds_o: Option<u32>,
+    clock_skew_seconds_o: Option<u32>,
+) -> did_webplus_ssi::SDJWTVerificationOptions {
+    did_webplus_ssi::SDJWTVerificationOptions {
+        clock_skew_seconds: clock_skew_seconds_o
+            .map(u64::from)
+            .unwrap_or(did_webplus_jws::JWTValidationOptions::DEFAULT_CLOCK_SKEW_SECONDS),
+        expected_issuer_o: None,
+        require_key_binding,
+        expected_audience_o,
+        expected_nonce_o,
+        key_binding_max_age_seconds: key_binding_max_age_seconds_o.map(u64::from).unwrap_or(
+            did_webplus_ssi::SDJWTVerificationOptions::DEFAULT_KEY_BINDING_MAX_AGE_SECONDS,
+        ),
+        trust_policy_o: None,
+        // The system clock isn't available on wasm32, so use the JS clock.
+        now_o: Some((js_sys::Date::now() / 1000.0) as i64),
+    }
+}
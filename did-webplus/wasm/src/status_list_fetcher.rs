@@ -0,0 +1,50 @@
+use crate::{HTTPHeadersFor, Result, into_js_value};
+use std::sync::Arc;
+use wasm_bindgen::{JsValue, prelude::wasm_bindgen};
+
+/// A StatusListFetcher fetches status list credentials by URL, so that the credentialStatus of a VC can be
+/// checked during verification.  Verifying a VC that has a credentialStatus fails if no StatusListFetcher is
+/// given.
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct StatusListFetcher(Arc<dyn did_webplus_ssi::StatusListFetcher>);
+
+impl StatusListFetcher {
+    pub fn new(status_list_fetcher_a: Arc<dyn did_webplus_ssi::StatusListFetcher>) -> Self {
+        Self(status_list_fetcher_a)
+    }
+    pub fn as_arc(&self) -> &Arc<dyn did_webplus_ssi::StatusListFetcher> {
+        &self.0
+    }
+}
+
+#[wasm_bindgen]
+impl StatusListFetcher {
+    /// Create a StatusListFetcher that fetches status list credentials from their URLs using HTTP GET.
+    /// If specified, http_headers_for_o gives the HTTP headers to send to particular hosts.
+    pub fn new_http(http_headers_for_o: Option<HTTPHeadersFor>) -> Self {
+        Self(Arc::new(did_webplus_ssi::StatusListFetcherHTTP::new(
+            http_headers_for_o.map(|h| h.into()),
+        )))
+    }
+    /// Create a StatusListFetcher that serves the given status list credentials, which must be given as an
+    /// object mapping each status list credential URL to the status list credential (a compact JWS for a
+    /// JWT-secured one, or the JSON string for a Data Integrity-secured one).
+    pub fn new_in_memory(status_list_credentials: JsValue) -> Result<Self> {
+        let status_list_credential_m: std::collections::HashMap<String, String> =
+            serde_wasm_bindgen::from_value(status_list_credentials).map_err(into_js_value)?;
+        let status_list_fetcher_in_memory = did_webplus_ssi::StatusListFetcherInMemory::new();
+        for (status_list_credential_url, status_list_credential) in status_list_credential_m {
+            status_list_fetcher_in_memory
+                .insert(status_list_credential_url, status_list_credential);
+        }
+        Ok(Self(Arc::new(status_list_fetcher_in_memory)))
+    }
+}
+
+impl std::ops::Deref for StatusListFetcher {
+    type Target = dyn did_webplus_ssi::StatusListFetcher;
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{DIDResolver, Result, WalletBasedSigner, date_to_offset_date_time, into_js_value};
+use crate::{
+    DIDResolver, Result, StatusListFetcher, WalletBasedSigner, date_to_offset_date_time,
+    into_js_value,
+};
 use std::ops::Deref;
 use wasm_bindgen::{JsValue, prelude::wasm_bindgen};
 
@@ -56,18 +59,30 @@ pub async fn issue_vc_ldp(
     Ok(vc_ldp_jsvalue)
 }
 
-/// Verify an LDP-formatted VC (a JSON blob), returning an error if the verification fails.  NOTE: This
-/// does NOT also verify credential-specific validity constraints; that must be done separately and explicitly.
+/// Verify an LDP-formatted VC (a JSON blob), returning an error if the verification fails.  If the VC has a
+/// credentialStatus, then its status is checked using status_list_fetcher_o, and the verification fails if
+/// status_list_fetcher_o is not specified.  NOTE: This does NOT also verify credential-specific validity
+/// constraints; that must be done separately and explicitly.
 #[wasm_bindgen]
-pub async fn verify_vc_ldp(vc_ldp_jsvalue: JsValue, did_resolver: &DIDResolver) -> Result<()> {
+pub async fn verify_vc_ldp(
+    vc_ldp_jsvalue: JsValue,
+    status_list_fetcher_o: Option<StatusListFetcher>,
+    did_resolver: &DIDResolver,
+) -> Result<()> {
     let did_resolver_a = did_resolver.as_arc().clone();
     let vc_ldp: ssi_claims::data_integrity::DataIntegrity<
         ssi_claims::vc::v1::JsonCredential,
         ssi_claims::data_integrity::AnySuite,
     > = serde_wasm_bindgen::from_value(vc_ldp_jsvalue).map_err(into_js_value)?;
-    let verification_r = did_webplus_ssi::verify_vc_ldp(&vc_ldp, did_resolver_a)
-        .await
-        .map_err(into_js_value)?;
+    let verification_r = did_webplus_ssi::verify_vc_ldp(
+        &vc_ldp,
+        status_list_fetcher_o.as_deref(),
+        None,
+        did_resolver_a,
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .map_err(into_js_value)?;
     verification_r.map_err(into_js_value)?;
     Ok(())
 }
@@ -86,15 +101,157 @@ pub async fn issue_vc_jwt(
     Ok(vc_jwt.into_string())
 }
 
-/// Verify a JWT-formatted VC, returning an error if the verification fails.  NOTE: This
-/// does NOT also verify credential-specific validity constraints; that must be done separately and explicitly.
+/// Verify a JWT-formatted VC, returning an error if the verification fails.  If the VC has a credentialStatus,
+/// then its status is checked using status_list_fetcher_o, and the verification fails if status_list_fetcher_o
+/// is not specified.  NOTE: This does NOT also verify credential-specific validity constraints; that must be
+/// done separately and explicitly.
 #[wasm_bindgen]
-pub async fn verify_vc_jwt(vc_jwt: String, did_resolver: &DIDResolver) -> Result<()> {
+pub async fn verify_vc_jwt(
+    vc_jwt: String,
+    status_list_fetcher_o: Option<StatusListFetcher>,
+    did_resolver: &DIDResolver,
+) -> Result<()> {
     let did_resolver_a = did_resolver.as_arc().clone();
     let vc_jwt = ssi_jws::JwsBuf::new(vc_jwt).map_err(into_js_value)?;
-    let verification_r = did_webplus_ssi::verify_vc_jwt(&vc_jwt, did_resolver_a)
-        .await
-        .map_err(into_js_value)?;
+    let verification_r = did_webplus_ssi::verify_vc_jwt(
+        &vc_jwt,
+        status_list_fetcher_o.as_deref(),
+        None,
+        did_resolver_a,
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .map_err(into_js_value)?;
+    verification_r.map_err(into_js_value)?;
+    Ok(())
+}
+
+/// Create the content of a VCDM 2.0 VC (a JSON blob), which uses validFrom and validUntil (rather than the
+/// VCDM 1.1 issuanceDate and expirationDate).  credential_status and credential_schema are optional, and if
+/// specified, must each be a JSON object or an array of JSON objects.
+#[wasm_bindgen]
+pub fn new_unsigned_credential_v2(
+    additional_context_vo: Option<Vec<String>>,
+    credential_id_o: Option<String>,
+    valid_from: js_sys::Date,
+    valid_until_o: Option<js_sys::Date>,
+    credential_subject: JsValue,
+    credential_status: JsValue,
+    credential_schema: JsValue,
+) -> Result<JsValue> {
+    let valid_from = date_to_offset_date_time(valid_from);
+    let valid_until_o = valid_until_o.map(date_to_offset_date_time);
+    let credential_subject: serde_json::Value =
+        serde_wasm_bindgen::from_value(credential_subject).map_err(into_js_value)?;
+    let credential_status_o: Option<serde_json::Value> =
+        serde_wasm_bindgen::from_value(credential_status).map_err(into_js_value)?;
+    let credential_schema_o: Option<serde_json::Value> =
+        serde_wasm_bindgen::from_value(credential_schema).map_err(into_js_value)?;
+    let unsigned_credential = did_webplus_ssi::new_unsigned_credential_v2(
+        additional_context_vo,
+        credential_id_o.as_deref(),
+        valid_from,
+        valid_until_o,
+        credential_subject,
+        credential_status_o,
+        credential_schema_o,
+    )
+    .map_err(into_js_value)?;
+    Ok(serde_wasm_bindgen::to_value(&unsigned_credential).map_err(into_js_value)?)
+}
+
+/// Create an EnvelopedVerifiableCredential (a JSON blob) which embeds the given JWT-secured VCDM 2.0 VC, for
+/// use in a VCDM 2.0 VP.
+#[wasm_bindgen]
+pub fn new_enveloped_credential_v2(vc_jwt: String) -> Result<JsValue> {
+    let enveloped_credential = did_webplus_ssi::new_enveloped_credential_v2(vc_jwt.as_str());
+    Ok(serde_wasm_bindgen::to_value(&enveloped_credential).map_err(into_js_value)?)
+}
+
+/// Issue a VCDM 2.0 VC secured using Data Integrity (a JSON blob).  Use new_unsigned_credential_v2 to create
+/// the content of the credential.
+#[wasm_bindgen]
+pub async fn issue_vc_ldp_v2(
+    unsigned_credential_jsvalue: JsValue,
+    wallet_based_signer: &WalletBasedSigner,
+    did_resolver: &DIDResolver,
+) -> Result<JsValue> {
+    let unsigned_credential: serde_json::Value =
+        serde_wasm_bindgen::from_value(unsigned_credential_jsvalue).map_err(into_js_value)?;
+    let did_resolver_a = did_resolver.as_arc().clone();
+    let vc_ldp = did_webplus_ssi::issue_vc_ldp_v2(
+        unsigned_credential,
+        wallet_based_signer.deref(),
+        did_resolver_a,
+    )
+    .await
+    .map_err(into_js_value)?;
+    let vc_ldp_json = serde_json::to_value(&vc_ldp).map_err(into_js_value)?;
+    Ok(serde_wasm_bindgen::to_value(&vc_ldp_json).map_err(into_js_value)?)
+}
+
+/// Verify a VCDM 2.0 VC secured using Data Integrity (a JSON blob), returning an error if the verification
+/// fails.  If the VC has a credentialStatus, then its status is checked using status_list_fetcher_o, and the
+/// verification fails if status_list_fetcher_o is not specified.  NOTE: This does NOT also verify credential-specific validity constraints; that must be done
+/// separately and explicitly.
+#[wasm_bindgen]
+pub async fn verify_vc_ldp_v2(
+    vc_ldp_jsvalue: JsValue,
+    status_list_fetcher_o: Option<StatusListFetcher>,
+    did_resolver: &DIDResolver,
+) -> Result<()> {
+    let did_resolver_a = did_resolver.as_arc().clone();
+    let vc_ldp: ssi_claims::data_integrity::DataIntegrity<
+        ssi_claims::vc::v2::JsonCredential,
+        ssi_claims::data_integrity::AnySuite,
+    > = serde_wasm_bindgen::from_value(vc_ldp_jsvalue).map_err(into_js_value)?;
+    let verification_r = did_webplus_ssi::verify_vc_ldp_v2(
+        &vc_ldp,
+        status_list_fetcher_o.as_deref(),
+        None,
+        did_resolver_a,
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .map_err(into_js_value)?;
     verification_r.map_err(into_js_value)?;
     Ok(())
 }
+
+/// Issue a VCDM 2.0 VC secured as a JWT ("typ" header "vc+jwt").  Use new_unsigned_credential_v2 to create
+/// the content of the credential.
+#[wasm_bindgen]
+pub async fn issue_vc_jwt_v2(
+    unsigned_credential_jsvalue: JsValue,
+    wallet_based_signer: &WalletBasedSigner,
+) -> Result<String> {
+    let unsigned_credential: serde_json::Value =
+        serde_wasm_bindgen::from_value(unsigned_credential_jsvalue).map_err(into_js_value)?;
+    let vc_jwt = did_webplus_ssi::issue_vc_jwt_v2(unsigned_credential, wallet_based_signer.deref())
+        .await
+        .map_err(into_js_value)?;
+    Ok(vc_jwt.into_string())
+}
+
+/// Verify a JWT-secured VCDM 2.0 VC, returning the credential (a JSON blob), or an error if the verification
+/// fails.  If the VC has a credentialStatus, then its status is checked using status_list_fetcher_o, and the
+/// verification fails if status_list_fetcher_o is not specified.  NOTE: This does NOT also verify credential-specific validity constraints; that must be done
+/// separately and explicitly.
+#[wasm_bindgen]
+pub async fn verify_vc_jwt_v2(
+    vc_jwt: String,
+    status_list_fetcher_o: Option<StatusListFetcher>,
+    did_resolver: &DIDResolver,
+) -> Result<JsValue> {
+    let did_resolver_a = did_resolver.as_arc().clone();
+    let credential = did_webplus_ssi::verify_vc_jwt_v2(
+        vc_jwt.as_str(),
+        status_list_fetcher_o.as_deref(),
+        None,
+        did_resolver_a,
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .map_err(into_js_value)?;
+    Ok(serde_wasm_bindgen::to_value(&credential).map_err(into_js_value)?)
+}
@@ -54,14 +54,19 @@ pub async fn vjson_verify(
     // TODO: Use serde_wasm_bindgen for more efficiency
     let json_value: serde_json::Value =
         serde_json::from_str(&json_string).map_err(into_js_value)?;
+    let mut verification_report = verifier_resolver::VerificationReport::new();
     let self_hash = did_webplus_cli_lib::vjson_verify(
         &json_value,
         vjson_resolver.deref(),
         verifier_resolver.deref(),
+        &mut verification_report,
     )
     .await
     .map_err(|e| {
-        tracing::error!("vjson_verify FAILED");
+        tracing::error!(
+            "vjson_verify FAILED; verification report: {:?}",
+            verification_report
+        );
         into_js_value(e)
     })?;
     tracing::info!("vjson_verify SUCCEEDED");
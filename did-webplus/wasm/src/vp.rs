@@ -119,9 +119,13 @@ pub async fn verify_vp_ldp(vp_ldp_jsvalue: JsValue, did_resolver: &DIDResolver)
         >,
         ssi_claims::data_integrity::AnySuite,
     > = serde_wasm_bindgen::from_value(vp_ldp_jsvalue).map_err(into_js_value)?;
-    let verification_r = did_webplus_ssi::verify_vp_ldp(&vp_ldp, did_resolver_a)
-        .await
-        .map_err(into_js_value)?;
+    let verification_r = did_webplus_ssi::verify_vp_ldp(
+        &vp_ldp,
+        did_resolver_a,
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .map_err(into_js_value)?;
     verification_r.map_err(into_js_value)?;
     Ok(())
 }
@@ -155,9 +159,126 @@ pub async fn issue_vp_jwt(
 pub async fn verify_vp_jwt(vp_jwt: String, did_resolver: &DIDResolver) -> Result<()> {
     let did_resolver_a = did_resolver.as_arc().clone();
     let vp_jwt: ssi_jws::JwsBuf = ssi_jws::JwsBuf::new(vp_jwt).map_err(into_js_value)?;
-    let verification_r = did_webplus_ssi::verify_vp_jwt(&vp_jwt, did_resolver_a)
-        .await
-        .map_err(into_js_value)?;
+    let verification_r = did_webplus_ssi::verify_vp_jwt(
+        &vp_jwt,
+        did_resolver_a,
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .map_err(into_js_value)?;
+    verification_r.map_err(into_js_value)?;
+    Ok(())
+}
+
+/// Create the content of a VCDM 2.0 VP (a JSON blob) containing the given VCs.  A JWT-secured VCDM 2.0 VC must
+/// be embedded using new_enveloped_credential_v2.
+#[wasm_bindgen]
+pub fn new_unsigned_presentation_v2(
+    additional_context_vo: Option<Vec<String>>,
+    presentation_id_o: Option<String>,
+    verifiable_credential_jsvalue_vo: Option<Vec<JsValue>>,
+) -> Result<JsValue> {
+    let mut verifiable_credential_v = Vec::new();
+    for vc_jsvalue in verifiable_credential_jsvalue_vo.unwrap_or_default() {
+        verifiable_credential_v.push(
+            serde_wasm_bindgen::from_value::<serde_json::Value>(vc_jsvalue)
+                .map_err(into_js_value)?,
+        );
+    }
+    let unsigned_presentation = did_webplus_ssi::new_unsigned_presentation_v2(
+        additional_context_vo,
+        presentation_id_o.as_deref(),
+        verifiable_credential_v,
+    );
+    Ok(serde_wasm_bindgen::to_value(&unsigned_presentation).map_err(into_js_value)?)
+}
+
+/// Issue a VCDM 2.0 VP secured using Data Integrity (a JSON blob).  Use new_unsigned_presentation_v2 to create
+/// the content of the presentation.
+#[wasm_bindgen]
+pub async fn issue_vp_ldp_v2(
+    unsigned_presentation_jsvalue: JsValue,
+    issue_vp_parameters: IssueVPParameters,
+    wallet_based_signer: &WalletBasedSigner,
+    did_resolver: &DIDResolver,
+) -> Result<JsValue> {
+    let unsigned_presentation: serde_json::Value =
+        serde_wasm_bindgen::from_value(unsigned_presentation_jsvalue).map_err(into_js_value)?;
+    let issue_vp_parameters = issue_vp_parameters.into();
+    let did_resolver_a = did_resolver.as_arc().clone();
+    let vp_ldp = did_webplus_ssi::issue_vp_ldp_v2(
+        unsigned_presentation,
+        issue_vp_parameters,
+        wallet_based_signer.deref(),
+        did_resolver_a,
+    )
+    .await
+    .map_err(into_js_value)?;
+    let vp_ldp_json = serde_json::to_value(&vp_ldp).map_err(into_js_value)?;
+    Ok(serde_wasm_bindgen::to_value(&vp_ldp_json).map_err(into_js_value)?)
+}
+
+/// Verify a VCDM 2.0 VP secured using Data Integrity (a JSON blob), returning an error if the verification
+/// fails.  NOTE: This does NOT also verify the credentials it contains.  See verify_vc_jwt_v2 and verify_vc_ldp_v2.
+#[wasm_bindgen]
+pub async fn verify_vp_ldp_v2(vp_ldp_jsvalue: JsValue, did_resolver: &DIDResolver) -> Result<()> {
+    let did_resolver_a = did_resolver.as_arc().clone();
+    let vp_ldp: ssi_claims::data_integrity::DataIntegrity<
+        ssi_claims::vc::v2::JsonPresentation<serde_json::Value>,
+        ssi_claims::data_integrity::AnySuite,
+    > = serde_wasm_bindgen::from_value(vp_ldp_jsvalue).map_err(into_js_value)?;
+    let verification_r = did_webplus_ssi::verify_vp_ldp_v2(
+        &vp_ldp,
+        did_resolver_a,
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .map_err(into_js_value)?;
     verification_r.map_err(into_js_value)?;
     Ok(())
 }
+
+/// Issue a VCDM 2.0 VP secured as a JWT ("typ" header "vp+jwt").  Use new_unsigned_presentation_v2 to create
+/// the content of the presentation.  The domains and the challenge (or nonce) of issue_vp_parameters become the
+/// "aud" and "nonce" claims respectively.
+#[wasm_bindgen]
+pub async fn issue_vp_jwt_v2(
+    unsigned_presentation_jsvalue: JsValue,
+    issue_vp_parameters: IssueVPParameters,
+    wallet_based_signer: &WalletBasedSigner,
+) -> Result<String> {
+    let unsigned_presentation: serde_json::Value =
+        serde_wasm_bindgen::from_value(unsigned_presentation_jsvalue).map_err(into_js_value)?;
+    let issue_vp_parameters = issue_vp_parameters.into();
+    let vp_jwt = did_webplus_ssi::issue_vp_jwt_v2(
+        unsigned_presentation,
+        issue_vp_parameters,
+        wallet_based_signer.deref(),
+    )
+    .await
+    .map_err(into_js_value)?;
+    Ok(vp_jwt.into_string())
+}
+
+/// Verify a JWT-secured VCDM 2.0 VP, returning the presentation (a JSON blob), or an error if the verification
+/// fails.  If expected_audience_o and expected_nonce_o are specified, then the "aud" and "nonce" claims must
+/// match.  NOTE: This does NOT also verify the credentials it contains.  See verify_vc_jwt_v2 and verify_vc_ldp_v2.
+#[wasm_bindgen]
+pub async fn verify_vp_jwt_v2(
+    vp_jwt: String,
+    expected_audience_o: Option<String>,
+    expected_nonce_o: Option<String>,
+    did_resolver: &DIDResolver,
+) -> Result<JsValue> {
+    let did_resolver_a = did_resolver.as_arc().clone();
+    let presentation = did_webplus_ssi::verify_vp_jwt_v2(
+        vp_jwt.as_str(),
+        expected_audience_o.as_deref(),
+        expected_nonce_o.as_deref(),
+        did_resolver_a,
+        &mut verifier_resolver::VerificationReport::new(),
+    )
+    .await
+    .map_err(into_js_value)?;
+    Ok(serde_wasm_bindgen::to_value(&presentation).map_err(into_js_value)?)
+}
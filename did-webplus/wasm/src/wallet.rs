@@ -27,6 +27,7 @@ impl CreateDIDParameters {
             vdr_did_create_endpoint: self.vdr_did_create_endpoint.as_str(),
             mb_hash_function_for_did: self.mb_hash_function_for_did.deref(),
             mb_hash_function_for_update_key_o: self.mb_hash_function_for_update_key_o.as_deref(),
+            vdr_authz_key_id_o: None,
         }
     }
 }
@@ -425,6 +426,26 @@ impl did_webplus_wallet::Wallet for Wallet {
             .deactivate_did(deactivate_did_parameters, http_options_o)
             .await
     }
+    async fn derive_shared_secret(
+        &self,
+        key_id: &did_webplus_core::DIDKeyResourceFullyQualifiedStr,
+        other_pub_key: &mbx::MBPubKeyStr,
+    ) -> did_webplus_wallet::Result<zeroize::Zeroizing<Vec<u8>>> {
+        self.0.derive_shared_secret(key_id, other_pub_key).await
+    }
+    async fn derive_shared_secret_with_did(
+        &self,
+        did_o: Option<&did_webplus_core::DIDStr>,
+        other_key_id: &did_webplus_core::DIDKeyResourceFullyQualifiedStr,
+        http_options_o: Option<&did_webplus_core::HTTPOptions>,
+    ) -> did_webplus_wallet::Result<(
+        did_webplus_core::DIDKeyResourceFullyQualified,
+        zeroize::Zeroizing<Vec<u8>>,
+    )> {
+        self.0
+            .derive_shared_secret_with_did(did_o, other_key_id, http_options_o)
+            .await
+    }
     async fn get_locally_controlled_verification_methods(
         &self,
         locally_controlled_verification_method_filter: &did_webplus_wallet_store::LocallyControlledVerificationMethodFilter,
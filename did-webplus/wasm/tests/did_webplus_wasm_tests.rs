@@ -214,6 +214,17 @@ async fn test_software_wallet_indexeddb() {
         )
         .await
         .expect("pass");
+    // VPs must be signed using an authentication key.
+    let holder_wallet_based_signer = wallet
+        .new_wallet_based_signer(
+            did.to_string(),
+            "authentication".to_string(),
+            None,
+            Some(http_options.clone()),
+            true,
+        )
+        .await
+        .expect("pass");
 
     // Defines the shape of our custom claims.
     #[derive(Clone, Debug, serde::Deserialize, Eq, PartialEq, serde::Serialize)]
@@ -265,9 +276,14 @@ async fn test_software_wallet_indexeddb() {
             // Verify the JWS.
             let verifier_resolver =
                 did_webplus_wasm::VerifierResolver::new_with_did_webplus(did_resolver.clone());
-            did_webplus_wasm::jws_verify(jws, None, &verifier_resolver)
-                .await
-                .expect("verification failed");
+            did_webplus_wasm::jws_verify(
+                jws,
+                None,
+                &verifier_resolver,
+                Some(did_webplus_wasm::KeyPurpose::AssertionMethod),
+            )
+            .await
+            .expect("verification failed");
         }
 
         // Attached, unencoded payload.
@@ -285,9 +301,14 @@ async fn test_software_wallet_indexeddb() {
             // Verify the JWS.
             let verifier_resolver =
                 did_webplus_wasm::VerifierResolver::new_with_did_webplus(did_resolver.clone());
-            did_webplus_wasm::jws_verify(jws, None, &verifier_resolver)
-                .await
-                .expect("verification failed");
+            did_webplus_wasm::jws_verify(
+                jws,
+                None,
+                &verifier_resolver,
+                Some(did_webplus_wasm::KeyPurpose::AssertionMethod),
+            )
+            .await
+            .expect("verification failed");
         }
 
         // Detached, encoded payload.
@@ -305,9 +326,14 @@ async fn test_software_wallet_indexeddb() {
             // Verify the JWS.
             let verifier_resolver =
                 did_webplus_wasm::VerifierResolver::new_with_did_webplus(did_resolver.clone());
-            did_webplus_wasm::jws_verify(jws, Some(payload.clone()), &verifier_resolver)
-                .await
-                .expect("verification failed");
+            did_webplus_wasm::jws_verify(
+                jws,
+                Some(payload.clone()),
+                &verifier_resolver,
+                Some(did_webplus_wasm::KeyPurpose::AssertionMethod),
+            )
+            .await
+            .expect("verification failed");
         }
 
         // Detached, unencoded payload.
@@ -325,9 +351,14 @@ async fn test_software_wallet_indexeddb() {
             // Verify the JWS.
             let verifier_resolver =
                 did_webplus_wasm::VerifierResolver::new_with_did_webplus(did_resolver.clone());
-            did_webplus_wasm::jws_verify(jws, Some(payload.clone()), &verifier_resolver)
-                .await
-                .expect("verification failed");
+            did_webplus_wasm::jws_verify(
+                jws,
+                Some(payload.clone()),
+                &verifier_resolver,
+                Some(did_webplus_wasm::KeyPurpose::AssertionMethod),
+            )
+            .await
+            .expect("verification failed");
         }
     }
 
@@ -341,9 +372,13 @@ async fn test_software_wallet_indexeddb() {
         let jwt_string = jwt.to_string();
 
         // Verify the JWT.
-        did_webplus_ssi::verify_jwt(&jwt_string, did_resolver_a.clone())
-            .await
-            .expect("verification failed");
+        did_webplus_ssi::verify_jwt(
+            &jwt_string,
+            did_resolver_a.clone(),
+            &mut verifier_resolver::VerificationReport::new(),
+        )
+        .await
+        .expect("verification failed");
     }
 
     // Sign and verify a JWT using jwt_sign and jwt_verify
@@ -412,7 +447,14 @@ async fn test_software_wallet_indexeddb() {
     };
     // Verify the VC.
     {
-        let vc_ldp_verify_r = did_webplus_ssi::verify_vc_ldp(&vc_ldp, did_resolver_a.clone()).await;
+        let vc_ldp_verify_r = did_webplus_ssi::verify_vc_ldp(
+            &vc_ldp,
+            None,
+            None,
+            did_resolver_a.clone(),
+            &mut verifier_resolver::VerificationReport::new(),
+        )
+        .await;
         tracing::debug!("vc_ldp_verify_r: {:?}", vc_ldp_verify_r);
         assert!(vc_ldp_verify_r.is_ok());
         let vc_ldp_verify_proof_r = vc_ldp_verify_r.unwrap();
@@ -438,7 +480,7 @@ async fn test_software_wallet_indexeddb() {
         }
 
         // Verify the VC.
-        did_webplus_wasm::verify_vc_ldp(vc_ldp_jsvalue.clone(), &did_resolver)
+        did_webplus_wasm::verify_vc_ldp(vc_ldp_jsvalue.clone(), None, &did_resolver)
             .await
             .expect("verification failed");
         vc_ldp_jsvalue
@@ -454,9 +496,15 @@ async fn test_software_wallet_indexeddb() {
         tracing::info!("vc_jwt: {:?}", vc_jwt);
 
         // Verify the VC.
-        did_webplus_ssi::verify_vc_jwt(&vc_jwt, did_resolver_a.clone())
-            .await
-            .expect("verification failed");
+        did_webplus_ssi::verify_vc_jwt(
+            &vc_jwt,
+            None,
+            None,
+            did_resolver_a.clone(),
+            &mut verifier_resolver::VerificationReport::new(),
+        )
+        .await
+        .expect("verification failed");
     }
 
     // Sign and verify a JWT-formatted VC using did_webplus_wasm
@@ -470,7 +518,7 @@ async fn test_software_wallet_indexeddb() {
         tracing::info!("vc_jwt: {:?}", vc_jwt);
 
         // Verify the VC.
-        did_webplus_wasm::verify_vc_jwt(vc_jwt.clone(), &did_resolver)
+        did_webplus_wasm::verify_vc_jwt(vc_jwt.clone(), None, &did_resolver)
             .await
             .expect("verification failed");
         vc_jwt
@@ -492,7 +540,7 @@ async fn test_software_wallet_indexeddb() {
         let vp_ldp_of_vc_ldp = did_webplus_wasm::issue_vp_ldp(
             unsigned_presentation_jsvalue,
             did_webplus_wasm::IssueVPParameters::new(Some("1234567890".to_string()), None, None),
-            &wallet_based_signer,
+            &holder_wallet_based_signer,
             &did_resolver,
         )
         .await
@@ -527,7 +575,7 @@ async fn test_software_wallet_indexeddb() {
         let vp_jwt_of_vc_ldp = did_webplus_wasm::issue_vp_jwt(
             unsigned_presentation_jsvalue,
             did_webplus_wasm::IssueVPParameters::new(Some("1234567890".to_string()), None, None),
-            &wallet_based_signer,
+            &holder_wallet_based_signer,
         )
         .await
         .expect("signature failed");
@@ -554,7 +602,7 @@ async fn test_software_wallet_indexeddb() {
         let vp_ldp_of_vc_jwt = did_webplus_wasm::issue_vp_ldp(
             unsigned_presentation_jsvalue,
             did_webplus_wasm::IssueVPParameters::new(Some("1234567890".to_string()), None, None),
-            &wallet_based_signer,
+            &holder_wallet_based_signer,
             &did_resolver,
         )
         .await
@@ -588,7 +636,7 @@ async fn test_software_wallet_indexeddb() {
         let vp_jwt_of_vc_jwt = did_webplus_wasm::issue_vp_jwt(
             unsigned_presentation_jsvalue,
             did_webplus_wasm::IssueVPParameters::new(Some("1234567890".to_string()), None, None),
-            &wallet_based_signer,
+            &holder_wallet_based_signer,
         )
         .await
         .expect("signature failed");
@@ -601,6 +649,39 @@ async fn test_software_wallet_indexeddb() {
         vp_jwt_of_vc_jwt
     };
 
+    // Encrypt a payload to the DID and decrypt it using the wallet's keyAgreement keys, in each key management
+    // algorithm and serialization.
+    for key_management_algorithm in [
+        did_webplus_jwe::JWEKeyManagementAlgorithm::ECDHES,
+        did_webplus_jwe::JWEKeyManagementAlgorithm::ECDHESA256KW,
+    ] {
+        for serialization in [
+            did_webplus_jwe::JWESerialization::Compact,
+            did_webplus_jwe::JWESerialization::JSON,
+        ] {
+            let plaintext = "Nobody expects the Spanish Inquisition".to_string();
+            let jwe = did_webplus_wasm::jwe_encrypt(
+                plaintext.clone(),
+                vec![did.to_string()],
+                key_management_algorithm,
+                serialization,
+                &did_resolver,
+            )
+            .await
+            .expect("pass");
+            tracing::info!(
+                "JWE ({:?}, {:?}): {}",
+                key_management_algorithm,
+                serialization,
+                jwe
+            );
+            let decrypted = did_webplus_wasm::jwe_decrypt(jwe, &wallet, Some(did.to_string()))
+                .await
+                .expect("pass");
+            assert_eq!(decrypted, plaintext);
+        }
+    }
+
     let controlled_did = wallet
         .update_did(
             did_webplus_wasm::UpdateDIDParameters::new(
@@ -621,10 +702,10 @@ async fn test_software_wallet_indexeddb() {
             .expect("pass");
 
         // Verify the VC in each format.
-        did_webplus_wasm::verify_vc_ldp(vc_ldp_jsvalue, &did_resolver)
+        did_webplus_wasm::verify_vc_ldp(vc_ldp_jsvalue, None, &did_resolver)
             .await
             .expect("verification failed");
-        did_webplus_wasm::verify_vc_jwt(vc_jwt, &did_resolver)
+        did_webplus_wasm::verify_vc_jwt(vc_jwt, None, &did_resolver)
             .await
             .expect("verification failed");
 
@@ -0,0 +1,126 @@
+use crate::{
+    Error, Result,
+    structured_field::{BareItem, DictionaryMember, Item, parse_dictionary, serialize_dictionary},
+};
+
+/// A digest algorithm for the Content-Digest field (RFC 9530).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ContentDigestAlgorithm {
+    SHA256,
+    SHA512,
+}
+
+impl ContentDigestAlgorithm {
+    /// Returns the algorithm's key in the Content-Digest field, as registered in the IANA "Hash Algorithms for
+    /// HTTP Digest Fields" registry.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SHA256 => "sha-256",
+            Self::SHA512 => "sha-512",
+        }
+    }
+    pub fn digest(self, content: &[u8]) -> Vec<u8> {
+        use sha2::Digest;
+        match self {
+            Self::SHA256 => sha2::Sha256::digest(content).to_vec(),
+            Self::SHA512 => sha2::Sha512::digest(content).to_vec(),
+        }
+    }
+    fn from_str_o(s: &str) -> Option<Self> {
+        match s {
+            "sha-256" => Some(Self::SHA256),
+            "sha-512" => Some(Self::SHA512),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the value of the Content-Digest field for the given content (i.e. message body), having a digest
+/// for each of the given algorithms.
+pub fn content_digest(
+    content: &[u8],
+    content_digest_algorithm_v: &[ContentDigestAlgorithm],
+) -> String {
+    let dictionary = content_digest_algorithm_v
+        .iter()
+        .map(|content_digest_algorithm| {
+            (
+                content_digest_algorithm.as_str().to_string(),
+                DictionaryMember::Item(Item {
+                    bare_item: BareItem::ByteSequence(content_digest_algorithm.digest(content)),
+                    parameters: Vec::new(),
+                }),
+            )
+        })
+        .collect();
+    serialize_dictionary(&dictionary)
+}
+
+/// Verify the given Content-Digest field value against the given content.  Each digest using a supported
+/// algorithm must match, and there must be at least one such digest.  Digests using other algorithms are
+/// ignored.
+pub fn verify_content_digest(content_digest: &str, content: &[u8]) -> Result<()> {
+    let mut supported_digest_count = 0;
+    for (key, member) in parse_dictionary(content_digest)? {
+        let content_digest_algorithm = match ContentDigestAlgorithm::from_str_o(key.as_str()) {
+            Some(content_digest_algorithm) => content_digest_algorithm,
+            None => continue,
+        };
+        let digest = match member {
+            DictionaryMember::Item(Item {
+                bare_item: BareItem::ByteSequence(digest),
+                ..
+            }) => digest,
+            _ => {
+                return Err(Error::Malformed(
+                    format!("Content-Digest member {:?} must be a byte sequence", key).into(),
+                ));
+            }
+        };
+        if digest != content_digest_algorithm.digest(content) {
+            return Err(Error::VerificationFailed(
+                format!("Content-Digest {:?} digest does not match the content", key).into(),
+            ));
+        }
+        supported_digest_count += 1;
+    }
+    if supported_digest_count == 0 {
+        return Err(Error::Unsupported(
+            "Content-Digest has no digest using a supported algorithm (sha-256 or sha-512)".into(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_digest() {
+        // Examples from RFC 9530 and RFC 9421.
+        let content = br#"{"hello": "world"}"#;
+        assert_eq!(
+            content_digest(content, &[ContentDigestAlgorithm::SHA256]),
+            "sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:"
+        );
+        assert_eq!(
+            content_digest(content, &[ContentDigestAlgorithm::SHA512]),
+            "sha-512=:WZDPaVn/7XgHaAy8pmojAkGWoRx2UFChF41A2svX+TaPm+AbwAgBWnrIiYllu7BNNyealdVLvRwEmTHWXvJwew==:"
+        );
+
+        let content_digest = content_digest(
+            content,
+            &[
+                ContentDigestAlgorithm::SHA256,
+                ContentDigestAlgorithm::SHA512,
+            ],
+        );
+        verify_content_digest(content_digest.as_str(), content).expect("pass");
+        assert!(verify_content_digest(content_digest.as_str(), b"tampered").is_err());
+        // Unknown algorithms are ignored, but there must be a supported one.
+        verify_content_digest(format!("md5=:AAAA:, {}", content_digest).as_str(), content)
+            .expect("pass");
+        assert!(verify_content_digest("md5=:AAAA:", content).is_err());
+    }
+}
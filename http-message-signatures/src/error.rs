@@ -0,0 +1,15 @@
+use std::borrow::Cow;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Malformed: {0}")]
+    Malformed(Cow<'static, str>),
+    #[error("Missing component: {0}")]
+    MissingComponent(Cow<'static, str>),
+    #[error("Signing failed: {0}")]
+    SigningFailed(Cow<'static, str>),
+    #[error("Unsupported: {0}")]
+    Unsupported(Cow<'static, str>),
+    #[error("Verification failed: {0}")]
+    VerificationFailed(Cow<'static, str>),
+}
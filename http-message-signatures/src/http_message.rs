@@ -0,0 +1,194 @@
+use crate::{
+    Error, Result, SignatureParams,
+    structured_field::{BareItem, InnerList},
+};
+
+/// The parts of an HTTP message that a signature can cover.  For a request, the target URI must be absolute
+/// (i.e. have a scheme and authority), since the "@target-uri", "@authority", and "@scheme" derived components
+/// are determined from it.  A server typically has to reconstruct it from the request's origin-form target and
+/// its "Host" field (see HTTPSignatureVerifier::verify_request).
+#[derive(Clone, Copy, Debug)]
+pub enum HTTPMessage<'a> {
+    Request {
+        method: &'a http::Method,
+        target_uri: &'a http::Uri,
+        header_map: &'a http::HeaderMap,
+    },
+    Response {
+        status: http::StatusCode,
+        header_map: &'a http::HeaderMap,
+    },
+}
+
+impl HTTPMessage<'_> {
+    pub fn header_map(&self) -> &http::HeaderMap {
+        match self {
+            Self::Request { header_map, .. } | Self::Response { header_map, .. } => header_map,
+        }
+    }
+    /// Returns the value of the given component (see RFC 9421 Section 2), where a component identifier
+    /// beginning with '@' is a derived component, and is otherwise a (lowercase) HTTP field name.  The
+    /// "@query-param" derived component and component parameters (e.g. "sf", "req") are not supported.
+    pub fn component_value(&self, component_identifier: &str) -> Result<String> {
+        if component_identifier.starts_with('@') {
+            self.derived_component_value(component_identifier)
+        } else {
+            self.field_value(component_identifier)
+        }
+    }
+    fn derived_component_value(&self, component_identifier: &str) -> Result<String> {
+        let (method, target_uri) = match self {
+            Self::Request {
+                method, target_uri, ..
+            } => (*method, *target_uri),
+            Self::Response { status, .. } => {
+                if component_identifier == "@status" {
+                    return Ok(status.as_u16().to_string());
+                } else {
+                    return Err(Error::MissingComponent(
+                        format!(
+                            "derived component {:?} is not defined for a response",
+                            component_identifier
+                        )
+                        .into(),
+                    ));
+                }
+            }
+        };
+        match component_identifier {
+            "@method" => Ok(method.as_str().to_string()),
+            "@target-uri" => {
+                scheme_and_authority(component_identifier, target_uri)?;
+                Ok(target_uri.to_string())
+            }
+            "@authority" => {
+                let (scheme, authority) = scheme_and_authority(component_identifier, target_uri)?;
+                Ok(normalized_authority(scheme, authority))
+            }
+            "@scheme" => {
+                let (scheme, _) = scheme_and_authority(component_identifier, target_uri)?;
+                Ok(scheme.to_ascii_lowercase())
+            }
+            "@request-target" => Ok(target_uri
+                .path_and_query()
+                .map(http::uri::PathAndQuery::as_str)
+                .unwrap_or("/")
+                .to_string()),
+            "@path" => Ok(target_uri.path().to_string()),
+            "@query" => Ok(format!("?{}", target_uri.query().unwrap_or(""))),
+            "@status" => Err(Error::MissingComponent(
+                "derived component \"@status\" is not defined for a request".into(),
+            )),
+            _ => Err(Error::Unsupported(
+                format!("derived component {:?}", component_identifier).into(),
+            )),
+        }
+    }
+    /// The value of a field is the values of all its field lines, with leading and trailing whitespace
+    /// removed, combined using ", " (RFC 9421 Section 2.1).
+    fn field_value(&self, field_name: &str) -> Result<String> {
+        if field_name.bytes().any(|c| c.is_ascii_uppercase()) {
+            return Err(Error::Malformed(
+                format!("field name {:?} must be lowercase", field_name).into(),
+            ));
+        }
+        let mut value_v = Vec::new();
+        for value in self.header_map().get_all(field_name) {
+            let value = value.to_str().map_err(|_| {
+                Error::Unsupported(format!("non-ASCII value of field {:?}", field_name).into())
+            })?;
+            value_v.push(value.trim());
+        }
+        if value_v.is_empty() {
+            return Err(Error::MissingComponent(
+                format!("field {:?} is not present", field_name).into(),
+            ));
+        }
+        Ok(value_v.join(", "))
+    }
+    /// Returns the signature base (RFC 9421 Section 2.5) for the given signature parameters, which is what's
+    /// actually signed.
+    pub fn signature_base(&self, signature_params: &SignatureParams) -> Result<String> {
+        self.signature_base_for_inner_list(&signature_params.to_inner_list())
+    }
+    pub(crate) fn signature_base_for_inner_list(
+        &self,
+        signature_params_inner_list: &InnerList,
+    ) -> Result<String> {
+        let mut signature_base = String::new();
+        let mut component_identifier_v =
+            Vec::with_capacity(signature_params_inner_list.item_v.len());
+        for item in signature_params_inner_list.item_v.iter() {
+            let component_identifier = match &item.bare_item {
+                BareItem::String(component_identifier) => component_identifier.as_str(),
+                _ => {
+                    return Err(Error::Malformed(
+                        "covered component identifiers must be strings".into(),
+                    ));
+                }
+            };
+            if !item.parameters.is_empty() {
+                return Err(Error::Unsupported(
+                    format!("parameters of covered component {:?}", component_identifier).into(),
+                ));
+            }
+            if component_identifier == "@signature-params" {
+                return Err(Error::Malformed(
+                    "\"@signature-params\" must not be a covered component".into(),
+                ));
+            }
+            if component_identifier_v.contains(&component_identifier) {
+                return Err(Error::Malformed(
+                    format!("covered component {:?} is repeated", component_identifier).into(),
+                ));
+            }
+            component_identifier_v.push(component_identifier);
+            let component_value = self.component_value(component_identifier)?;
+            if component_value.contains('\n') {
+                return Err(Error::Malformed(
+                    format!(
+                        "value of component {:?} contains a newline",
+                        component_identifier
+                    )
+                    .into(),
+                ));
+            }
+            item.serialize_into(&mut signature_base);
+            signature_base.push_str(": ");
+            signature_base.push_str(component_value.as_str());
+            signature_base.push('\n');
+        }
+        signature_base.push_str("\"@signature-params\": ");
+        signature_base.push_str(signature_params_inner_list.serialize().as_str());
+        Ok(signature_base)
+    }
+}
+
+fn scheme_and_authority<'u>(
+    component_identifier: &str,
+    target_uri: &'u http::Uri,
+) -> Result<(&'u str, &'u http::uri::Authority)> {
+    target_uri
+        .scheme_str()
+        .zip(target_uri.authority())
+        .ok_or_else(|| {
+            Error::MissingComponent(
+                format!(
+                    "derived component {:?} requires an absolute target URI, but it was {:?}",
+                    component_identifier,
+                    target_uri.to_string()
+                )
+                .into(),
+            )
+        })
+}
+
+/// The "@authority" component is the lowercased host, along with the port only if it's not the default
+/// port for the scheme (RFC 9421 Section 2.2.3).
+fn normalized_authority(scheme: &str, authority: &http::uri::Authority) -> String {
+    let host = authority.host().to_ascii_lowercase();
+    match (scheme.to_ascii_lowercase().as_str(), authority.port_u16()) {
+        (_, None) | ("http", Some(80)) | ("https", Some(443)) => host,
+        (_, Some(port)) => format!("{}:{}", host, port),
+    }
+}
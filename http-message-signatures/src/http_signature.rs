@@ -0,0 +1,264 @@
+use crate::{
+    Error, HTTPMessage, Result, SIGNATURE, SIGNATURE_INPUT, SignatureParams,
+    http_signature_algorithm,
+    structured_field::{
+        BareItem, DictionaryMember, InnerList, Item, Parameters, parse_dictionary,
+        serialize_dictionary_member,
+    },
+};
+
+/// A labeled HTTP message signature (RFC 9421 Section 4), i.e. a member of the Signature-Input field together
+/// with the corresponding member of the Signature field.
+#[derive(Clone, Debug)]
+pub struct HTTPSignature {
+    label: String,
+    signature_params: SignatureParams,
+    // This is kept as received, since the signature base is formed from its serialization.
+    signature_params_inner_list: InnerList,
+    signature_byte_v: Vec<u8>,
+}
+
+impl HTTPSignature {
+    /// Sign the given message using the given signer, producing a signature having the given label (e.g.
+    /// "sig1").  The "alg" parameter is set according to the signer's key type if it isn't already specified.
+    /// Use HTTPSignature::append_to_header_map to add the signature to the message.
+    pub fn signed(
+        message: &HTTPMessage<'_>,
+        label: String,
+        mut signature_params: SignatureParams,
+        signer: &dyn signature_dyn::SignerT,
+    ) -> Result<Self> {
+        ensure_label_is_valid(label.as_str())?;
+        if signature_params.alg_o.is_none() {
+            signature_params.alg_o =
+                http_signature_algorithm(signer.key_type()).map(str::to_string);
+        }
+        let signature_params_inner_list = signature_params.to_inner_list();
+        let signature_base = message.signature_base_for_inner_list(&signature_params_inner_list)?;
+        let signature = signer
+            .try_sign_message(signature_base.as_bytes())
+            .map_err(|e| Error::SigningFailed(e.to_string().into()))?;
+        Ok(Self {
+            label,
+            signature_params,
+            signature_params_inner_list,
+            signature_byte_v: signature.to_signature_bytes().bytes().to_vec(),
+        })
+    }
+    /// Async version of HTTPSignature::signed, e.g. for use with a WalletBasedSigner.
+    pub async fn async_signed(
+        message: &HTTPMessage<'_>,
+        label: String,
+        mut signature_params: SignatureParams,
+        async_signer: &dyn signature_dyn::AsyncSignerT,
+    ) -> Result<Self> {
+        ensure_label_is_valid(label.as_str())?;
+        if signature_params.alg_o.is_none() {
+            let key_type = async_signer
+                .async_key_type()
+                .await
+                .map_err(|e| Error::SigningFailed(e.to_string().into()))?;
+            signature_params.alg_o = http_signature_algorithm(key_type).map(str::to_string);
+        }
+        let signature_params_inner_list = signature_params.to_inner_list();
+        let signature_base = message.signature_base_for_inner_list(&signature_params_inner_list)?;
+        let signature = async_signer
+            .async_try_sign_message(signature_base.as_bytes())
+            .await
+            .map_err(|e| Error::SigningFailed(e.to_string().into()))?;
+        Ok(Self {
+            label,
+            signature_params,
+            signature_params_inner_list,
+            signature_byte_v: signature.to_signature_bytes().bytes().to_vec(),
+        })
+    }
+    pub fn label(&self) -> &str {
+        self.label.as_str()
+    }
+    pub fn signature_params(&self) -> &SignatureParams {
+        &self.signature_params
+    }
+    pub fn signature_bytes(&self) -> &[u8] {
+        self.signature_byte_v.as_slice()
+    }
+    /// Returns true iff the given component is covered by this signature.
+    pub fn covers(&self, component_identifier: &str) -> bool {
+        self.signature_params
+            .covered_component_v
+            .iter()
+            .any(|covered_component| covered_component == component_identifier)
+    }
+    /// Returns the signature base for this signature over the given message, which is what was signed.
+    pub fn signature_base(&self, message: &HTTPMessage<'_>) -> Result<String> {
+        message.signature_base_for_inner_list(&self.signature_params_inner_list)
+    }
+    /// Verify this signature over the given message using the given verifier.  This only checks the
+    /// cryptographic signature (and the "alg" parameter, if present); see verify_http_message for the full
+    /// verification procedure.
+    pub fn verify(
+        &self,
+        message: &HTTPMessage<'_>,
+        verifier: &dyn signature_dyn::VerifierT,
+    ) -> Result<()> {
+        if let Some(alg) = self.signature_params.alg_o.as_deref() {
+            let expected_alg_o = http_signature_algorithm(verifier.key_type());
+            if expected_alg_o != Some(alg) {
+                return Err(Error::VerificationFailed(
+                    format!(
+                        "signature \"alg\" parameter {:?} does not match that of the verifier's key type {}",
+                        alg,
+                        verifier.key_type()
+                    )
+                    .into(),
+                ));
+            }
+        }
+        let signature_base = self.signature_base(message)?;
+        let signature_bytes = signature_dyn::SignatureBytes::new(
+            verifier.jose_algorithm(),
+            self.signature_byte_v.as_slice().into(),
+        )
+        .map_err(|e| Error::Malformed(format!("signature bytes: {}", e).into()))?;
+        verifier
+            .verify_message(signature_base.as_bytes(), &signature_bytes)
+            .map_err(|e| {
+                Error::VerificationFailed(
+                    format!(
+                        "HTTP message signature {:?} failed to verify: {}",
+                        self.label, e
+                    )
+                    .into(),
+                )
+            })
+    }
+    /// Returns this signature's member of the Signature-Input field, e.g. `sig1=("@method" ...);created=...`.
+    pub fn signature_input_member(&self) -> String {
+        serialize_dictionary_member(
+            self.label.as_str(),
+            &DictionaryMember::InnerList(self.signature_params_inner_list.clone()),
+        )
+    }
+    /// Returns this signature's member of the Signature field, e.g. `sig1=:<base64>:`.
+    pub fn signature_member(&self) -> String {
+        serialize_dictionary_member(
+            self.label.as_str(),
+            &DictionaryMember::Item(Item {
+                bare_item: BareItem::ByteSequence(self.signature_byte_v.clone()),
+                parameters: Parameters::new(),
+            }),
+        )
+    }
+    /// Add this signature to the Signature-Input and Signature fields of the given header map, alongside
+    /// any signatures already present (which must not have the same label).
+    pub fn append_to_header_map(&self, header_map: &mut http::HeaderMap) -> Result<()> {
+        if Self::parse_all_from_header_map(header_map)?
+            .iter()
+            .any(|http_signature| http_signature.label == self.label)
+        {
+            return Err(Error::Malformed(
+                format!("message already has a signature labeled {:?}", self.label).into(),
+            ));
+        }
+        for (header_name, member) in [
+            (SIGNATURE_INPUT, self.signature_input_member()),
+            (SIGNATURE, self.signature_member()),
+        ] {
+            let header_value = http::HeaderValue::try_from(member)
+                .map_err(|e| Error::Malformed(e.to_string().into()))?;
+            header_map.append(header_name, header_value);
+        }
+        Ok(())
+    }
+    /// Parse all the signatures in the Signature-Input and Signature fields of the given header map.  Each
+    /// label must be present in both fields.
+    pub fn parse_all_from_header_map(header_map: &http::HeaderMap) -> Result<Vec<Self>> {
+        let signature_input_dictionary =
+            parse_dictionary(combined_field_value(header_map, SIGNATURE_INPUT.as_str())?.as_str())?;
+        let signature_dictionary =
+            parse_dictionary(combined_field_value(header_map, SIGNATURE.as_str())?.as_str())?;
+        if signature_input_dictionary.len() != signature_dictionary.len() {
+            return Err(Error::Malformed(
+                "Signature-Input and Signature fields have different numbers of members".into(),
+            ));
+        }
+
+        let mut http_signature_v = Vec::with_capacity(signature_input_dictionary.len());
+        for (label, signature_input_member) in signature_input_dictionary {
+            let signature_params_inner_list = match signature_input_member {
+                DictionaryMember::InnerList(inner_list) => inner_list,
+                DictionaryMember::Item(_) => {
+                    return Err(Error::Malformed(
+                        format!("Signature-Input member {:?} must be an inner list", label).into(),
+                    ));
+                }
+            };
+            let signature_byte_v = match signature_dictionary
+                .iter()
+                .find(|(signature_label, _)| *signature_label == label)
+            {
+                Some((
+                    _,
+                    DictionaryMember::Item(Item {
+                        bare_item: BareItem::ByteSequence(signature_byte_v),
+                        ..
+                    }),
+                )) => signature_byte_v.clone(),
+                Some(_) => {
+                    return Err(Error::Malformed(
+                        format!("Signature member {:?} must be a byte sequence", label).into(),
+                    ));
+                }
+                None => {
+                    return Err(Error::Malformed(
+                        format!("Signature field has no member {:?}", label).into(),
+                    ));
+                }
+            };
+            let signature_params = SignatureParams::from_inner_list(&signature_params_inner_list)?;
+            http_signature_v.push(Self {
+                label,
+                signature_params,
+                signature_params_inner_list,
+                signature_byte_v,
+            });
+        }
+        Ok(http_signature_v)
+    }
+}
+
+/// Returns the combined value of all the field lines of the given field (or the empty string if there are
+/// none), which is how a field having several lines is to be parsed.
+pub(crate) fn combined_field_value(
+    header_map: &http::HeaderMap,
+    field_name: &str,
+) -> Result<String> {
+    let mut value_v = Vec::new();
+    for value in header_map.get_all(field_name) {
+        value_v.push(value.to_str().map_err(|_| {
+            Error::Malformed(format!("non-ASCII value of field {:?}", field_name).into())
+        })?);
+    }
+    Ok(value_v.join(", "))
+}
+
+/// A label is a structured field dictionary key.
+fn ensure_label_is_valid(label: &str) -> Result<()> {
+    let mut char_iter = label.chars();
+    let is_valid = char_iter
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '*')
+        && char_iter.all(|c| {
+            c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '_' | '-' | '.' | '*')
+        });
+    if !is_valid {
+        return Err(Error::Malformed(
+            format!(
+                "signature label {:?} must be a structured field dictionary key",
+                label
+            )
+            .into(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,140 @@
+use crate::{
+    CONTENT_DIGEST, Error, HTTPMessage, HTTPSignature, HTTPSignatureVerificationOptions, Result,
+    verify_content_digest, verify_http_message,
+};
+use std::sync::Arc;
+use verifier_resolver::{VerificationCheckKind, VerificationReport, VerifierResolver};
+
+/// Server-side verification of signed HTTP requests.  This wraps verify_http_message, additionally handling
+/// the reconstruction of the target URI from the "Host" field (since a server generally receives only the
+/// origin-form target) and the verification of the Content-Digest field against the request body.
+pub struct HTTPSignatureVerifier {
+    verifier_resolver_a: Arc<dyn VerifierResolver>,
+    verification_options: HTTPSignatureVerificationOptions,
+    scheme: http::uri::Scheme,
+    max_body_size: usize,
+}
+
+impl HTTPSignatureVerifier {
+    /// The default maximum size (in bytes) of a request body that will be buffered for verification.
+    pub const DEFAULT_MAX_BODY_SIZE: usize = 1 << 20;
+
+    /// Requests will be verified according to the given options, using the given VerifierResolver (e.g.
+    /// one that resolves did:webplus key resources) to determine the signing keys.  The scheme used to
+    /// reconstruct target URIs defaults to "https".
+    pub fn new(
+        verifier_resolver_a: Arc<dyn VerifierResolver>,
+        verification_options: HTTPSignatureVerificationOptions,
+    ) -> Self {
+        Self {
+            verifier_resolver_a,
+            verification_options,
+            scheme: http::uri::Scheme::HTTPS,
+            max_body_size: Self::DEFAULT_MAX_BODY_SIZE,
+        }
+    }
+    /// Set the scheme used to reconstruct the target URI of a request whose target isn't absolute.  This
+    /// should be the scheme that clients use to reach the server, which may differ from what the server
+    /// itself sees (e.g. behind a TLS-terminating proxy).
+    pub fn with_scheme(mut self, scheme: http::uri::Scheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+    /// Set the maximum size (in bytes) of a request body that will be buffered for verification (see the
+    /// axum middleware verify_http_signature).
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+    pub fn verification_options(&self) -> &HTTPSignatureVerificationOptions {
+        &self.verification_options
+    }
+    pub fn scheme(&self) -> &http::uri::Scheme {
+        &self.scheme
+    }
+    pub fn max_body_size(&self) -> usize {
+        self.max_body_size
+    }
+    /// Verify the signature of the given request, returning it.  If the body is nonempty, then the signature
+    /// must cover the Content-Digest field, and that must match the body.  Each check performed is recorded
+    /// in verification_report.
+    pub async fn verify_request(
+        &self,
+        method: &http::Method,
+        uri: &http::Uri,
+        header_map: &http::HeaderMap,
+        body: &[u8],
+        verification_report: &mut VerificationReport,
+    ) -> Result<HTTPSignature> {
+        let target_uri = verification_report.record_result(
+            VerificationCheckKind::Claims,
+            None,
+            None,
+            self.target_uri(uri, header_map),
+        )?;
+        let message = HTTPMessage::Request {
+            method,
+            target_uri: &target_uri,
+            header_map,
+        };
+        let http_signature = verify_http_message(
+            &message,
+            &self.verification_options,
+            self.verifier_resolver_a.as_ref(),
+            verification_report,
+        )
+        .await?;
+
+        if !body.is_empty() {
+            verification_report.record_result(
+                VerificationCheckKind::Claims,
+                Some(CONTENT_DIGEST.as_str()),
+                None,
+                ensure_content_digest_is_valid(&http_signature, header_map, body),
+            )?;
+        }
+
+        Ok(http_signature)
+    }
+    /// If the given URI is absolute, then it's the target URI.  Otherwise, the target URI is formed from
+    /// the configured scheme, the "Host" field, and the given URI's path and query.
+    fn target_uri(&self, uri: &http::Uri, header_map: &http::HeaderMap) -> Result<http::Uri> {
+        if uri.scheme().is_some() && uri.authority().is_some() {
+            return Ok(uri.clone());
+        }
+        let host = header_map
+            .get(http::header::HOST)
+            .ok_or_else(|| {
+                Error::MissingComponent(
+                    "request target is not absolute and there is no Host field".into(),
+                )
+            })?
+            .to_str()
+            .map_err(|_| Error::Malformed("non-ASCII value of field \"host\"".into()))?;
+        http::Uri::builder()
+            .scheme(self.scheme.clone())
+            .authority(host)
+            .path_and_query(
+                uri.path_and_query()
+                    .map(http::uri::PathAndQuery::as_str)
+                    .unwrap_or("/"),
+            )
+            .build()
+            .map_err(|e| Error::Malformed(format!("target URI: {}", e).into()))
+    }
+}
+
+fn ensure_content_digest_is_valid(
+    http_signature: &HTTPSignature,
+    header_map: &http::HeaderMap,
+    body: &[u8],
+) -> Result<()> {
+    if !http_signature.covers(CONTENT_DIGEST.as_str()) {
+        return Err(Error::VerificationFailed(
+            "request has a body, but the signature does not cover the Content-Digest field".into(),
+        ));
+    }
+    let content_digest =
+        crate::http_signature::combined_field_value(header_map, CONTENT_DIGEST.as_str())?;
+    verify_content_digest(content_digest.as_str(), body)
+}
@@ -0,0 +1,34 @@
+mod content_digest;
+mod error;
+mod http_message;
+mod http_signature;
+mod http_signature_verifier;
+#[cfg(feature = "reqwest")]
+mod sign_reqwest_request;
+mod signature_params;
+mod structured_field;
+#[cfg(feature = "axum")]
+mod verified_http_signature;
+mod verify_http_message;
+
+#[cfg(feature = "reqwest")]
+pub use crate::sign_reqwest_request::sign_reqwest_request;
+#[cfg(feature = "axum")]
+pub use crate::verified_http_signature::{VerifiedHTTPSignature, verify_http_signature};
+pub use crate::{
+    content_digest::{ContentDigestAlgorithm, content_digest, verify_content_digest},
+    error::Error,
+    http_message::HTTPMessage,
+    http_signature::HTTPSignature,
+    http_signature_verifier::HTTPSignatureVerifier,
+    signature_params::{SignatureParams, http_signature_algorithm},
+    verify_http_message::{HTTPSignatureVerificationOptions, verify_http_message},
+};
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The Signature-Input field (RFC 9421 Section 4.1).
+pub const SIGNATURE_INPUT: http::HeaderName = http::HeaderName::from_static("signature-input");
+/// The Signature field (RFC 9421 Section 4.2).
+pub const SIGNATURE: http::HeaderName = http::HeaderName::from_static("signature");
+/// The Content-Digest field (RFC 9530 Section 2).
+pub const CONTENT_DIGEST: http::HeaderName = http::HeaderName::from_static("content-digest");
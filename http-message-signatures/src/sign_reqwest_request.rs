@@ -0,0 +1,53 @@
+use crate::{
+    CONTENT_DIGEST, ContentDigestAlgorithm, Error, HTTPMessage, HTTPSignature, Result,
+    SignatureParams, content_digest,
+};
+
+/// Sign the given reqwest request (e.g. as built by `reqwest::RequestBuilder::build`), adding the signature
+/// to its Signature-Input and Signature fields, and returning it.  If the request has a body, then a sha-256
+/// Content-Digest field is added (unless already present), and the signature is made to cover it.  A
+/// streaming body can't be signed.  Note that fields which reqwest adds while sending (e.g. "host" and
+/// "content-length") aren't present at this point, and so can't be covered.
+pub async fn sign_reqwest_request(
+    request: &mut reqwest::Request,
+    label: String,
+    mut signature_params: SignatureParams,
+    async_signer: &dyn signature_dyn::AsyncSignerT,
+) -> Result<HTTPSignature> {
+    if let Some(body) = request.body() {
+        let body_bytes = body.as_bytes().ok_or_else(|| {
+            Error::Unsupported("signing a request having a streaming body".into())
+        })?;
+        if !request.headers().contains_key(CONTENT_DIGEST) {
+            let content_digest = content_digest(body_bytes, &[ContentDigestAlgorithm::SHA256]);
+            let header_value = http::HeaderValue::try_from(content_digest)
+                .map_err(|e| Error::Malformed(e.to_string().into()))?;
+            request.headers_mut().insert(CONTENT_DIGEST, header_value);
+        }
+        if !signature_params
+            .covered_component_v
+            .iter()
+            .any(|covered_component| covered_component == CONTENT_DIGEST.as_str())
+        {
+            signature_params
+                .covered_component_v
+                .push(CONTENT_DIGEST.as_str().to_string());
+        }
+    }
+
+    let target_uri = http::Uri::try_from(request.url().as_str())
+        .map_err(|e| Error::Malformed(format!("request URL: {}", e).into()))?;
+    let http_signature = HTTPSignature::async_signed(
+        &HTTPMessage::Request {
+            method: request.method(),
+            target_uri: &target_uri,
+            header_map: request.headers(),
+        },
+        label,
+        signature_params,
+        async_signer,
+    )
+    .await?;
+    http_signature.append_to_header_map(request.headers_mut())?;
+    Ok(http_signature)
+}
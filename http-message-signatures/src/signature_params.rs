@@ -0,0 +1,132 @@
+use crate::{
+    Error, Result,
+    structured_field::{BareItem, InnerList, Item, Parameters},
+};
+
+/// The signature parameters of an HTTP message signature (RFC 9421 Section 2.3), i.e. the covered components
+/// along with the metadata that's signed with them.  These are what appear in the Signature-Input field.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignatureParams {
+    /// The covered components, in order, e.g. "@method", "@target-uri", "content-digest".  A component
+    /// identifier beginning with '@' is a derived component, and is otherwise a (lowercase) HTTP field name.
+    pub covered_component_v: Vec<String>,
+    /// Creation time, in seconds since the Unix epoch.
+    pub created_o: Option<i64>,
+    /// Expiration time, in seconds since the Unix epoch.
+    pub expires_o: Option<i64>,
+    /// A random value, which a verifier can use to detect replays.
+    pub nonce_o: Option<String>,
+    /// The HTTP signature algorithm (see http_signature_algorithm).  If not specified when signing, then it's
+    /// set according to the signer's key type, if that has a registered algorithm name.
+    pub alg_o: Option<String>,
+    /// Identifies the key used to sign, e.g. a fully qualified DID key resource, which is how the verifier
+    /// determines the key.  Note that this is optional in RFC 9421, but required here.
+    pub keyid: String,
+    /// An application-specific tag, which a verifier can use to require a signature intended for it.
+    pub tag_o: Option<String>,
+}
+
+impl SignatureParams {
+    /// Signature params covering the given components, signed by the given key, created now.
+    pub fn new(covered_component_v: Vec<String>, keyid: String) -> Self {
+        Self {
+            covered_component_v,
+            created_o: Some(time::OffsetDateTime::now_utc().unix_timestamp()),
+            expires_o: None,
+            nonce_o: None,
+            alg_o: None,
+            keyid,
+            tag_o: None,
+        }
+    }
+    /// The parameters are serialized in a fixed order: created, expires, nonce, alg, keyid, tag.
+    pub(crate) fn to_inner_list(&self) -> InnerList {
+        let item_v = self
+            .covered_component_v
+            .iter()
+            .map(|component_identifier| Item {
+                bare_item: BareItem::String(component_identifier.clone()),
+                parameters: Parameters::new(),
+            })
+            .collect();
+        let mut parameters = Parameters::new();
+        if let Some(created) = self.created_o {
+            parameters.push(("created".to_string(), BareItem::Integer(created)));
+        }
+        if let Some(expires) = self.expires_o {
+            parameters.push(("expires".to_string(), BareItem::Integer(expires)));
+        }
+        if let Some(nonce) = self.nonce_o.as_ref() {
+            parameters.push(("nonce".to_string(), BareItem::String(nonce.clone())));
+        }
+        if let Some(alg) = self.alg_o.as_ref() {
+            parameters.push(("alg".to_string(), BareItem::String(alg.clone())));
+        }
+        parameters.push(("keyid".to_string(), BareItem::String(self.keyid.clone())));
+        if let Some(tag) = self.tag_o.as_ref() {
+            parameters.push(("tag".to_string(), BareItem::String(tag.clone())));
+        }
+        InnerList { item_v, parameters }
+    }
+    /// Parameters not defined by RFC 9421 are ignored here, though they're still part of the signature base,
+    /// since that's formed from the inner list as received.
+    pub(crate) fn from_inner_list(inner_list: &InnerList) -> Result<Self> {
+        let mut covered_component_v = Vec::with_capacity(inner_list.item_v.len());
+        for item in inner_list.item_v.iter() {
+            match &item.bare_item {
+                BareItem::String(component_identifier) => {
+                    covered_component_v.push(component_identifier.clone())
+                }
+                _ => {
+                    return Err(Error::Malformed(
+                        "covered component identifiers must be strings".into(),
+                    ));
+                }
+            }
+        }
+        let mut created_o = None;
+        let mut expires_o = None;
+        let mut nonce_o = None;
+        let mut alg_o = None;
+        let mut keyid_o = None;
+        let mut tag_o = None;
+        for (key, bare_item) in inner_list.parameters.iter() {
+            match (key.as_str(), bare_item) {
+                ("created", BareItem::Integer(created)) => created_o = Some(*created),
+                ("expires", BareItem::Integer(expires)) => expires_o = Some(*expires),
+                ("nonce", BareItem::String(nonce)) => nonce_o = Some(nonce.clone()),
+                ("alg", BareItem::String(alg)) => alg_o = Some(alg.clone()),
+                ("keyid", BareItem::String(keyid)) => keyid_o = Some(keyid.clone()),
+                ("tag", BareItem::String(tag)) => tag_o = Some(tag.clone()),
+                ("created" | "expires" | "nonce" | "alg" | "keyid" | "tag", _) => {
+                    return Err(Error::Malformed(
+                        format!("signature parameter {:?} has the wrong type", key).into(),
+                    ));
+                }
+                _ => {}
+            }
+        }
+        let keyid = keyid_o
+            .ok_or_else(|| Error::Malformed("signature parameter \"keyid\" is missing".into()))?;
+        Ok(Self {
+            covered_component_v,
+            created_o,
+            expires_o,
+            nonce_o,
+            alg_o,
+            keyid,
+            tag_o,
+        })
+    }
+}
+
+/// Returns the HTTP signature algorithm name (see the IANA "HTTP Signature Algorithms" registry) for the
+/// given key type, if one is registered.
+pub fn http_signature_algorithm(key_type: signature_dyn::KeyType) -> Option<&'static str> {
+    match key_type {
+        signature_dyn::KeyType::Ed25519 => Some("ed25519"),
+        signature_dyn::KeyType::P256 => Some("ecdsa-p256-sha256"),
+        signature_dyn::KeyType::P384 => Some("ecdsa-p384-sha384"),
+        _ => None,
+    }
+}
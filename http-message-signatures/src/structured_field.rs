@@ -0,0 +1,448 @@
+//! The subset of Structured Field Values for HTTP (RFC 8941) needed for the Signature-Input, Signature, and
+//! Content-Digest fields, i.e. dictionaries whose members are items or inner lists, with parameters.  Decimals
+//! are not supported.  See <https://www.rfc-editor.org/rfc/rfc8941>
+
+use crate::{Error, Result};
+use base64::Engine;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum BareItem {
+    Integer(i64),
+    String(String),
+    Token(String),
+    ByteSequence(Vec<u8>),
+    Boolean(bool),
+}
+
+/// Parameters, in order of appearance (which matters for serialization, and therefore for signature bases).
+pub(crate) type Parameters = Vec<(String, BareItem)>;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Item {
+    pub(crate) bare_item: BareItem,
+    pub(crate) parameters: Parameters,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct InnerList {
+    pub(crate) item_v: Vec<Item>,
+    pub(crate) parameters: Parameters,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum DictionaryMember {
+    Item(Item),
+    InnerList(InnerList),
+}
+
+/// Dictionary members, in order of appearance.
+pub(crate) type Dictionary = Vec<(String, DictionaryMember)>;
+
+impl BareItem {
+    pub(crate) fn serialize_into(&self, s: &mut String) {
+        match self {
+            Self::Integer(integer) => s.push_str(integer.to_string().as_str()),
+            Self::String(string) => {
+                s.push('"');
+                for c in string.chars() {
+                    if c == '"' || c == '\\' {
+                        s.push('\\');
+                    }
+                    s.push(c);
+                }
+                s.push('"');
+            }
+            Self::Token(token) => s.push_str(token.as_str()),
+            Self::ByteSequence(byte_v) => {
+                s.push(':');
+                s.push_str(
+                    base64::engine::general_purpose::STANDARD
+                        .encode(byte_v)
+                        .as_str(),
+                );
+                s.push(':');
+            }
+            Self::Boolean(boolean) => s.push_str(if *boolean { "?1" } else { "?0" }),
+        }
+    }
+}
+
+fn serialize_parameters_into(parameters: &Parameters, s: &mut String) {
+    for (key, bare_item) in parameters {
+        s.push(';');
+        s.push_str(key.as_str());
+        if *bare_item != BareItem::Boolean(true) {
+            s.push('=');
+            bare_item.serialize_into(s);
+        }
+    }
+}
+
+impl Item {
+    pub(crate) fn serialize_into(&self, s: &mut String) {
+        self.bare_item.serialize_into(s);
+        serialize_parameters_into(&self.parameters, s);
+    }
+}
+
+impl InnerList {
+    pub(crate) fn serialize(&self) -> String {
+        let mut s = String::new();
+        s.push('(');
+        for (i, item) in self.item_v.iter().enumerate() {
+            if i > 0 {
+                s.push(' ');
+            }
+            item.serialize_into(&mut s);
+        }
+        s.push(')');
+        serialize_parameters_into(&self.parameters, &mut s);
+        s
+    }
+}
+
+impl DictionaryMember {
+    pub(crate) fn serialize_into(&self, s: &mut String) {
+        match self {
+            Self::Item(item) => {
+                if item.bare_item == BareItem::Boolean(true) {
+                    serialize_parameters_into(&item.parameters, s);
+                } else {
+                    s.push('=');
+                    item.serialize_into(s);
+                }
+            }
+            Self::InnerList(inner_list) => {
+                s.push('=');
+                s.push_str(inner_list.serialize().as_str());
+            }
+        }
+    }
+}
+
+/// Serializes a single dictionary member, i.e. "key=value".
+pub(crate) fn serialize_dictionary_member(key: &str, member: &DictionaryMember) -> String {
+    let mut s = key.to_string();
+    member.serialize_into(&mut s);
+    s
+}
+
+pub(crate) fn serialize_dictionary(dictionary: &Dictionary) -> String {
+    dictionary
+        .iter()
+        .map(|(key, member)| serialize_dictionary_member(key, member))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Parses a dictionary field value.  If the field has several lines, they must be combined (separated by ", ")
+/// before parsing.  As per RFC 8941, if a key appears more than once, the last value wins.
+pub(crate) fn parse_dictionary(input: &str) -> Result<Dictionary> {
+    let mut parser = Parser {
+        input: input.as_bytes(),
+        position: 0,
+    };
+    parser.skip_sp();
+    let mut dictionary = Dictionary::new();
+    while !parser.is_at_end() {
+        let key = parser.parse_key()?;
+        let member = if parser.peek() == Some(b'=') {
+            parser.position += 1;
+            parser.parse_item_or_inner_list()?
+        } else {
+            DictionaryMember::Item(Item {
+                bare_item: BareItem::Boolean(true),
+                parameters: parser.parse_parameters()?,
+            })
+        };
+        dictionary.retain(|(existing_key, _)| *existing_key != key);
+        dictionary.push((key, member));
+        parser.skip_ows();
+        if parser.is_at_end() {
+            break;
+        }
+        parser.expect(b',')?;
+        parser.skip_ows();
+        if parser.is_at_end() {
+            return Err(Error::Malformed(
+                "structured field dictionary has a trailing comma".into(),
+            ));
+        }
+    }
+    Ok(dictionary)
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn is_at_end(&self) -> bool {
+        self.position >= self.input.len()
+    }
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.position).copied()
+    }
+    fn expect(&mut self, c: u8) -> Result<()> {
+        if self.peek() != Some(c) {
+            return Err(self.malformed(format!("expected {:?}", c as char).as_str()));
+        }
+        self.position += 1;
+        Ok(())
+    }
+    fn malformed(&self, description: &str) -> Error {
+        Error::Malformed(
+            format!(
+                "structured field {:?} at position {}: {}",
+                String::from_utf8_lossy(self.input),
+                self.position,
+                description
+            )
+            .into(),
+        )
+    }
+    fn skip_sp(&mut self) {
+        while self.peek() == Some(b' ') {
+            self.position += 1;
+        }
+    }
+    fn skip_ows(&mut self) {
+        while matches!(self.peek(), Some(b' ') | Some(b'\t')) {
+            self.position += 1;
+        }
+    }
+    fn take_while(&mut self, predicate: impl Fn(u8) -> bool) -> &str {
+        let start = self.position;
+        while self.peek().is_some_and(&predicate) {
+            self.position += 1;
+        }
+        // All the predicates used only accept ASCII.
+        std::str::from_utf8(&self.input[start..self.position]).unwrap()
+    }
+    fn parse_key(&mut self) -> Result<String> {
+        match self.peek() {
+            Some(c) if c.is_ascii_lowercase() || c == b'*' => {}
+            _ => return Err(self.malformed("expected a key")),
+        }
+        Ok(self
+            .take_while(|c| {
+                c.is_ascii_lowercase()
+                    || c.is_ascii_digit()
+                    || matches!(c, b'_' | b'-' | b'.' | b'*')
+            })
+            .to_string())
+    }
+    fn parse_parameters(&mut self) -> Result<Parameters> {
+        let mut parameters = Parameters::new();
+        while self.peek() == Some(b';') {
+            self.position += 1;
+            self.skip_sp();
+            let key = self.parse_key()?;
+            let bare_item = if self.peek() == Some(b'=') {
+                self.position += 1;
+                self.parse_bare_item()?
+            } else {
+                BareItem::Boolean(true)
+            };
+            parameters.retain(|(existing_key, _)| *existing_key != key);
+            parameters.push((key, bare_item));
+        }
+        Ok(parameters)
+    }
+    fn parse_item_or_inner_list(&mut self) -> Result<DictionaryMember> {
+        if self.peek() == Some(b'(') {
+            Ok(DictionaryMember::InnerList(self.parse_inner_list()?))
+        } else {
+            Ok(DictionaryMember::Item(self.parse_item()?))
+        }
+    }
+    fn parse_item(&mut self) -> Result<Item> {
+        let bare_item = self.parse_bare_item()?;
+        let parameters = self.parse_parameters()?;
+        Ok(Item {
+            bare_item,
+            parameters,
+        })
+    }
+    fn parse_inner_list(&mut self) -> Result<InnerList> {
+        self.expect(b'(')?;
+        let mut item_v = Vec::new();
+        loop {
+            self.skip_sp();
+            if self.peek() == Some(b')') {
+                self.position += 1;
+                break;
+            }
+            item_v.push(self.parse_item()?);
+            match self.peek() {
+                Some(b' ') | Some(b')') => {}
+                _ => return Err(self.malformed("expected ' ' or ')' in inner list")),
+            }
+        }
+        let parameters = self.parse_parameters()?;
+        Ok(InnerList { item_v, parameters })
+    }
+    fn parse_bare_item(&mut self) -> Result<BareItem> {
+        match self.peek() {
+            Some(b'-') | Some(b'0'..=b'9') => self.parse_integer(),
+            Some(b'"') => self.parse_string(),
+            Some(b':') => self.parse_byte_sequence(),
+            Some(b'?') => self.parse_boolean(),
+            Some(c) if c.is_ascii_alphabetic() || c == b'*' => {
+                let token = self.take_while(|c| {
+                    c.is_ascii_alphanumeric()
+                        || matches!(
+                            c,
+                            b'!' | b'#'
+                                | b'$'
+                                | b'%'
+                                | b'&'
+                                | b'\''
+                                | b'*'
+                                | b'+'
+                                | b'-'
+                                | b'.'
+                                | b'^'
+                                | b'_'
+                                | b'`'
+                                | b'|'
+                                | b'~'
+                                | b':'
+                                | b'/'
+                        )
+                });
+                Ok(BareItem::Token(token.to_string()))
+            }
+            _ => Err(self.malformed("expected a bare item")),
+        }
+    }
+    fn parse_integer(&mut self) -> Result<BareItem> {
+        let negative = self.peek() == Some(b'-');
+        if negative {
+            self.position += 1;
+        }
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        if digits.is_empty() || digits.len() > 15 {
+            return Err(self.malformed("expected an integer of 1 to 15 digits"));
+        }
+        let magnitude = digits.parse::<i64>().unwrap();
+        if self.peek() == Some(b'.') {
+            return Err(self.malformed("decimals are not supported"));
+        }
+        Ok(BareItem::Integer(if negative {
+            -magnitude
+        } else {
+            magnitude
+        }))
+    }
+    fn parse_string(&mut self) -> Result<BareItem> {
+        self.expect(b'"')?;
+        let mut string = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.malformed("unterminated string")),
+                Some(b'"') => {
+                    self.position += 1;
+                    return Ok(BareItem::String(string));
+                }
+                Some(b'\\') => {
+                    self.position += 1;
+                    match self.peek() {
+                        Some(c @ (b'"' | b'\\')) => string.push(c as char),
+                        _ => return Err(self.malformed("invalid escape in string")),
+                    }
+                }
+                Some(c @ 0x20..=0x7e) => string.push(c as char),
+                Some(_) => return Err(self.malformed("invalid character in string")),
+            }
+            self.position += 1;
+        }
+    }
+    fn parse_byte_sequence(&mut self) -> Result<BareItem> {
+        self.expect(b':')?;
+        let base64 = self
+            .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, b'+' | b'/' | b'='))
+            .to_string();
+        self.expect(b':')?;
+        let byte_v = base64::engine::general_purpose::STANDARD
+            .decode(base64.as_bytes())
+            .map_err(|e| self.malformed(format!("invalid base64: {}", e).as_str()))?;
+        Ok(BareItem::ByteSequence(byte_v))
+    }
+    fn parse_boolean(&mut self) -> Result<BareItem> {
+        self.expect(b'?')?;
+        let boolean = match self.peek() {
+            Some(b'1') => true,
+            Some(b'0') => false,
+            _ => return Err(self.malformed("expected '1' or '0' in boolean")),
+        };
+        self.position += 1;
+        Ok(BareItem::Boolean(boolean))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dictionary_round_trip() {
+        for dictionary_str in [
+            r#"sig1=("@method" "@target-uri" "content-digest");created=1618884473;keyid="did:example:abc?versionId=0#0""#,
+            "sig1=:dGVzdA==:, sig2=:AAEC:",
+            "sha-256=:X48E9qOokqqrvdts8nOJRJN3OWDUoyWxBf7kbu9DBPE=:",
+            "a=?0, b, c;foo=bar",
+            r#"sig-b21=();created=1618884473;keyid="test-key-rsa-pss";nonce="b3k2pp5k7z-50gnwp.yemd""#,
+            r#"a="quote \" and backslash \\", b=-42"#,
+        ] {
+            let dictionary = parse_dictionary(dictionary_str).expect("pass");
+            assert_eq!(serialize_dictionary(&dictionary), dictionary_str);
+        }
+    }
+
+    #[test]
+    fn test_dictionary_parsing() {
+        let dictionary = parse_dictionary(" a=1 ,\tb=(x \"y\"  z);p  ").expect("pass");
+        assert_eq!(dictionary.len(), 2);
+        assert_eq!(
+            dictionary[1].1,
+            DictionaryMember::InnerList(InnerList {
+                item_v: vec![
+                    Item {
+                        bare_item: BareItem::Token("x".to_string()),
+                        parameters: Parameters::new(),
+                    },
+                    Item {
+                        bare_item: BareItem::String("y".to_string()),
+                        parameters: Parameters::new(),
+                    },
+                    Item {
+                        bare_item: BareItem::Token("z".to_string()),
+                        parameters: Parameters::new(),
+                    },
+                ],
+                parameters: vec![("p".to_string(), BareItem::Boolean(true))],
+            })
+        );
+        // Later values win.
+        let dictionary = parse_dictionary("a=1, b=2, a=3").expect("pass");
+        assert_eq!(serialize_dictionary(&dictionary), "b=2, a=3");
+
+        for malformed_str in [
+            "a=1,",
+            "A=1",
+            "a=1.5",
+            "a=\"unterminated",
+            "a=:!!:",
+            "a=(x",
+            "a=1 b=2",
+        ] {
+            assert!(
+                parse_dictionary(malformed_str).is_err(),
+                "{:?} should not parse",
+                malformed_str
+            );
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{HTTPSignature, HTTPSignatureVerifier};
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+
+/// The request extension inserted by the verify_http_signature middleware, holding the verified signature.
+/// A handler can use `Extension<VerifiedHTTPSignature>` to determine e.g. the signing key via the "keyid"
+/// signature parameter.
+#[derive(Clone, Debug)]
+pub struct VerifiedHTTPSignature(pub HTTPSignature);
+
+/// An axum middleware (for use with `axum::middleware::from_fn_with_state`) that verifies the HTTP message
+/// signature of each request using the given HTTPSignatureVerifier, rejecting the request with status 401
+/// if it doesn't verify.  The request body is buffered (up to the verifier's max body size) so that it can
+/// be checked against the Content-Digest field, and is then passed along to the handler.
+pub async fn verify_http_signature(
+    State(http_signature_verifier_a): State<Arc<HTTPSignatureVerifier>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, (StatusCode, String)> {
+    let (mut parts, body) = request.into_parts();
+    let body_bytes = axum::body::to_bytes(body, http_signature_verifier_a.max_body_size())
+        .await
+        .map_err(|e| (StatusCode::PAYLOAD_TOO_LARGE, e.to_string()))?;
+
+    let mut verification_report = verifier_resolver::VerificationReport::new();
+    match http_signature_verifier_a
+        .verify_request(
+            &parts.method,
+            &parts.uri,
+            &parts.headers,
+            body_bytes.as_ref(),
+            &mut verification_report,
+        )
+        .await
+    {
+        Ok(http_signature) => {
+            tracing::debug!(
+                keyid = http_signature.signature_params().keyid.as_str(),
+                "HTTP message signature verified"
+            );
+            parts
+                .extensions
+                .insert(VerifiedHTTPSignature(http_signature));
+        }
+        Err(e) => {
+            tracing::debug!(
+                ?verification_report,
+                "HTTP message signature failed to verify: {}",
+                e
+            );
+            return Err((StatusCode::UNAUTHORIZED, e.to_string()));
+        }
+    }
+
+    Ok(next
+        .run(Request::from_parts(parts, Body::from(body_bytes)))
+        .await)
+}
@@ -0,0 +1,179 @@
+use crate::{Error, HTTPMessage, HTTPSignature, Result};
+use verifier_resolver::{VerificationCheckKind, VerificationReport};
+
+/// Specifies how an HTTP message signature is verified (see verify_http_message).
+#[derive(Clone, Debug)]
+pub struct HTTPSignatureVerificationOptions {
+    /// If specified, then the signature having this label is verified.  Otherwise the message must have
+    /// exactly one signature.
+    pub label_o: Option<String>,
+    /// The components that the signature must cover, e.g. "@method", "@target-uri", "content-digest".
+    pub required_component_v: Vec<String>,
+    /// The verification relationship that the signing key must have, e.g. authentication.
+    pub verification_relationship: verifier_resolver::VerificationRelationship,
+    /// If specified, then the signature must have a "created" parameter no older than this many seconds.
+    pub max_age_seconds_o: Option<u64>,
+    /// If specified, then the signature must have a "tag" parameter having this value.
+    pub expected_tag_o: Option<String>,
+    /// The amount of clock skew (in seconds) to tolerate when checking "created" and "expires".
+    pub clock_skew_seconds: u64,
+    /// The current time (in seconds since the Unix epoch) to validate against.  If not specified, then the
+    /// system clock is used.
+    pub now_o: Option<i64>,
+}
+
+impl HTTPSignatureVerificationOptions {
+    /// The default clock skew tolerance, in seconds.
+    pub const DEFAULT_CLOCK_SKEW_SECONDS: u64 = 60;
+}
+
+impl Default for HTTPSignatureVerificationOptions {
+    /// By default, a signature must cover the request method and target URI, be signed by an authentication
+    /// key, and be no older than 5 minutes.
+    fn default() -> Self {
+        Self {
+            label_o: None,
+            required_component_v: vec!["@method".to_string(), "@target-uri".to_string()],
+            verification_relationship: verifier_resolver::VerificationRelationship::Authentication,
+            max_age_seconds_o: Some(300),
+            expected_tag_o: None,
+            clock_skew_seconds: Self::DEFAULT_CLOCK_SKEW_SECONDS,
+            now_o: None,
+        }
+    }
+}
+
+/// Verify a signature of the given HTTP message (see RFC 9421 Section 3.2), returning it.  This checks that
+/// the signature covers the required components, that its "created", "expires", and "tag" parameters are
+/// acceptable, that its "keyid" (e.g. a fully qualified DID key resource) resolves to a key having the
+/// required verification relationship, and that the signature is valid under that key.  Note that this doesn't
+/// check the Content-Digest field against the message body; see verify_content_digest.  Each check performed is
+/// recorded in verification_report.
+pub async fn verify_http_message(
+    message: &HTTPMessage<'_>,
+    verification_options: &HTTPSignatureVerificationOptions,
+    verifier_resolver: &dyn verifier_resolver::VerifierResolver,
+    verification_report: &mut VerificationReport,
+) -> Result<HTTPSignature> {
+    let http_signature = verification_report.record_result(
+        VerificationCheckKind::Claims,
+        None,
+        None,
+        select_http_signature(message, verification_options),
+    )?;
+    verification_report.record_result(
+        VerificationCheckKind::Claims,
+        Some(http_signature.label()),
+        None,
+        ensure_signature_params_are_acceptable(&http_signature, verification_options),
+    )?;
+
+    let keyid = http_signature.signature_params().keyid.as_str();
+    let verifier_b = verification_report
+        .resolve_for_purpose(
+            keyid,
+            verification_options.verification_relationship,
+            verifier_resolver,
+        )
+        .await
+        .map_err(|e| Error::VerificationFailed(e.to_string().into()))?;
+    verification_report.record_result(
+        VerificationCheckKind::Signature,
+        Some(keyid),
+        verification_report
+            .resolved_did_document_version(keyid)
+            .as_deref(),
+        http_signature.verify(message, verifier_b.as_ref()),
+    )?;
+
+    Ok(http_signature)
+}
+
+fn select_http_signature(
+    message: &HTTPMessage<'_>,
+    verification_options: &HTTPSignatureVerificationOptions,
+) -> Result<HTTPSignature> {
+    let mut http_signature_v = HTTPSignature::parse_all_from_header_map(message.header_map())?;
+    if let Some(label) = verification_options.label_o.as_deref() {
+        let index = http_signature_v
+            .iter()
+            .position(|http_signature| http_signature.label() == label)
+            .ok_or_else(|| {
+                Error::MissingComponent(format!("no signature labeled {:?}", label).into())
+            })?;
+        Ok(http_signature_v.swap_remove(index))
+    } else if http_signature_v.len() == 1 {
+        Ok(http_signature_v.pop().unwrap())
+    } else {
+        Err(Error::Malformed(
+            format!(
+                "expected exactly one signature, but found {}; a label must be specified",
+                http_signature_v.len()
+            )
+            .into(),
+        ))
+    }
+}
+
+fn ensure_signature_params_are_acceptable(
+    http_signature: &HTTPSignature,
+    verification_options: &HTTPSignatureVerificationOptions,
+) -> Result<()> {
+    let signature_params = http_signature.signature_params();
+    for required_component in verification_options.required_component_v.iter() {
+        if !http_signature.covers(required_component) {
+            return Err(Error::VerificationFailed(
+                format!(
+                    "signature does not cover required component {:?}",
+                    required_component
+                )
+                .into(),
+            ));
+        }
+    }
+
+    let now = verification_options
+        .now_o
+        .unwrap_or_else(|| time::OffsetDateTime::now_utc().unix_timestamp());
+    let clock_skew_seconds = verification_options.clock_skew_seconds as i64;
+    if let Some(created) = signature_params.created_o
+        && created > now + clock_skew_seconds
+    {
+        return Err(Error::VerificationFailed(
+            format!("signature \"created\" time {} is in the future", created).into(),
+        ));
+    }
+    if let Some(max_age_seconds) = verification_options.max_age_seconds_o {
+        let created = signature_params.created_o.ok_or_else(|| {
+            Error::VerificationFailed("signature has no \"created\" parameter".into())
+        })?;
+        if now - created > max_age_seconds as i64 + clock_skew_seconds {
+            return Err(Error::VerificationFailed(
+                format!(
+                    "signature \"created\" time {} is older than the maximum age of {} seconds",
+                    created, max_age_seconds
+                )
+                .into(),
+            ));
+        }
+    }
+    if let Some(expires) = signature_params.expires_o
+        && expires + clock_skew_seconds < now
+    {
+        return Err(Error::VerificationFailed(
+            format!("signature expired at {}", expires).into(),
+        ));
+    }
+    if let Some(expected_tag) = verification_options.expected_tag_o.as_deref()
+        && signature_params.tag_o.as_deref() != Some(expected_tag)
+    {
+        return Err(Error::VerificationFailed(
+            format!(
+                "signature \"tag\" parameter {:?} is not the expected tag {:?}",
+                signature_params.tag_o, expected_tag
+            )
+            .into(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,476 @@
+use http_message_signatures::{
+    ContentDigestAlgorithm, HTTPMessage, HTTPSignature, HTTPSignatureVerificationOptions,
+    HTTPSignatureVerifier, SignatureParams, content_digest, verify_content_digest,
+    verify_http_message,
+};
+use std::sync::Arc;
+
+/// Resolves exactly one keyid to its verifying key.
+struct SingleKeyVerifierResolver {
+    keyid: String,
+    verifying_key: ed25519_dalek::VerifyingKey,
+}
+
+#[async_trait::async_trait]
+impl verifier_resolver::VerifierResolver for SingleKeyVerifierResolver {
+    async fn resolve(
+        &self,
+        verifier_str: &str,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        if verifier_str != self.keyid {
+            anyhow::bail!("unknown keyid {:?}", verifier_str);
+        }
+        Ok(Box::new(self.verifying_key))
+    }
+    async fn resolve_for_purpose(
+        &self,
+        verifier_str: &str,
+        _verification_relationship: verifier_resolver::VerificationRelationship,
+    ) -> verifier_resolver::Result<Box<dyn signature_dyn::VerifierT>> {
+        self.resolve(verifier_str).await
+    }
+}
+
+/// The test request from RFC 9421 Appendix B.2.
+fn rfc_9421_test_request() -> (http::Method, http::Uri, http::HeaderMap, &'static [u8]) {
+    let mut header_map = http::HeaderMap::new();
+    for (name, value) in [
+        ("host", "example.com"),
+        ("date", "Tue, 20 Apr 2021 02:07:55 GMT"),
+        ("content-type", "application/json"),
+        (
+            "content-digest",
+            "sha-512=:WZDPaVn/7XgHaAy8pmojAkGWoRx2UFChF41A2svX+TaPm+AbwAgBWnrIiYllu7BNNyealdVLvRwEmTHWXvJwew==:",
+        ),
+        ("content-length", "18"),
+    ] {
+        header_map.append(name, http::HeaderValue::from_static(value));
+    }
+    (
+        http::Method::POST,
+        http::Uri::from_static("http://example.com/foo?param=Value&Pet=dog"),
+        header_map,
+        br#"{"hello": "world"}"#,
+    )
+}
+
+fn decode_base64url(s: &str) -> [u8; 32] {
+    use base64::Engine;
+    base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(s)
+        .expect("pass")
+        .try_into()
+        .expect("pass")
+}
+
+#[test]
+fn test_rfc_9421_signature_base() {
+    // RFC 9421 Appendix B.2.3 ("Full Coverage").
+    let (method, target_uri, mut header_map, content) = rfc_9421_test_request();
+    header_map.append(
+        "signature-input",
+        http::HeaderValue::from_static(
+            r#"sig-b23=("date" "@method" "@path" "@query" "@authority" "content-type" "content-digest" "content-length");created=1618884473;keyid="test-key-rsa-pss""#,
+        ),
+    );
+    header_map.append(
+        "signature",
+        http::HeaderValue::from_static("sig-b23=:AAAA:"),
+    );
+    let message = HTTPMessage::Request {
+        method: &method,
+        target_uri: &target_uri,
+        header_map: &header_map,
+    };
+    let http_signature_v = HTTPSignature::parse_all_from_header_map(&header_map).expect("pass");
+    assert_eq!(http_signature_v.len(), 1);
+    let http_signature = &http_signature_v[0];
+    assert_eq!(http_signature.label(), "sig-b23");
+    assert_eq!(http_signature.signature_params().keyid, "test-key-rsa-pss");
+    assert_eq!(
+        http_signature.signature_params().created_o,
+        Some(1618884473)
+    );
+    assert!(http_signature.covers("content-digest"));
+    assert_eq!(
+        http_signature.signature_base(&message).expect("pass"),
+        r#""date": Tue, 20 Apr 2021 02:07:55 GMT
+"@method": POST
+"@path": /foo
+"@query": ?param=Value&Pet=dog
+"@authority": example.com
+"content-type": application/json
+"content-digest": sha-512=:WZDPaVn/7XgHaAy8pmojAkGWoRx2UFChF41A2svX+TaPm+AbwAgBWnrIiYllu7BNNyealdVLvRwEmTHWXvJwew==:
+"content-length": 18
+"@signature-params": ("date" "@method" "@path" "@query" "@authority" "content-type" "content-digest" "content-length");created=1618884473;keyid="test-key-rsa-pss""#
+    );
+    // Re-serializing the parsed signature params must reproduce the received ones.
+    assert_eq!(
+        message
+            .signature_base(http_signature.signature_params())
+            .expect("pass"),
+        http_signature.signature_base(&message).expect("pass")
+    );
+
+    let content_digest_value = header_map.get("content-digest").unwrap().to_str().unwrap();
+    verify_content_digest(content_digest_value, content).expect("pass");
+    assert_eq!(
+        content_digest(content, &[ContentDigestAlgorithm::SHA512]),
+        content_digest_value
+    );
+
+    // Components that aren't present can't be covered.
+    let mut signature_params = http_signature.signature_params().clone();
+    signature_params
+        .covered_component_v
+        .push("x-not-present".to_string());
+    assert!(message.signature_base(&signature_params).is_err());
+    // Nor can a response-only component be covered in a request.
+    signature_params.covered_component_v = vec!["@status".to_string()];
+    assert!(message.signature_base(&signature_params).is_err());
+}
+
+#[tokio::test]
+async fn test_rfc_9421_ed25519_test_vector() {
+    // RFC 9421 Appendix B.2.6 ("Signing a Request Using ed25519"), with the key from Appendix B.1.4.
+    let keyid = "test-key-ed25519";
+    let verifying_key = ed25519_dalek::VerifyingKey::from_bytes(&decode_base64url(
+        "JrQLj5P_89iXES9-vFgrIy29clF9CC_oPPsw3c5D0bs",
+    ))
+    .expect("pass");
+    let verifier_resolver = SingleKeyVerifierResolver {
+        keyid: keyid.to_string(),
+        verifying_key,
+    };
+
+    let (method, target_uri, mut header_map, _content) = rfc_9421_test_request();
+    header_map.append(
+        "signature-input",
+        http::HeaderValue::from_static(
+            r#"sig-b26=("date" "@method" "@path" "@authority" "content-type" "content-length");created=1618884473;keyid="test-key-ed25519""#,
+        ),
+    );
+    header_map.append(
+        "signature",
+        http::HeaderValue::from_static(
+            "sig-b26=:wqcAqbmYJ2ji2glfAMaRy4gruYYnx2nEFN2HN6jrnDnQCK1u02Gb04v9EDgwUPiu4A0w6vuQv5lIp5WPpBKRCw==:",
+        ),
+    );
+    let message = HTTPMessage::Request {
+        method: &method,
+        target_uri: &target_uri,
+        header_map: &header_map,
+    };
+
+    let verification_options = HTTPSignatureVerificationOptions {
+        required_component_v: vec!["@method".to_string(), "@authority".to_string()],
+        now_o: Some(1618884473 + 10),
+        ..Default::default()
+    };
+    let mut verification_report = verifier_resolver::VerificationReport::new();
+    let http_signature = verify_http_message(
+        &message,
+        &verification_options,
+        &verifier_resolver,
+        &mut verification_report,
+    )
+    .await
+    .expect("pass");
+    assert!(verification_report.is_valid());
+    assert_eq!(http_signature.label(), "sig-b26");
+
+    // The signature doesn't cover "@target-uri", which the default options require.
+    let mut verification_report = verifier_resolver::VerificationReport::new();
+    assert!(
+        verify_http_message(
+            &message,
+            &HTTPSignatureVerificationOptions {
+                now_o: Some(1618884473 + 10),
+                ..Default::default()
+            },
+            &verifier_resolver,
+            &mut verification_report,
+        )
+        .await
+        .is_err()
+    );
+    assert!(!verification_report.is_valid());
+
+    // The signature is too old.
+    let mut verification_report = verifier_resolver::VerificationReport::new();
+    assert!(
+        verify_http_message(
+            &message,
+            &HTTPSignatureVerificationOptions {
+                now_o: None,
+                ..verification_options.clone()
+            },
+            &verifier_resolver,
+            &mut verification_report,
+        )
+        .await
+        .is_err()
+    );
+
+    // Altering a covered component invalidates the signature.
+    let other_method = http::Method::PUT;
+    let mut verification_report = verifier_resolver::VerificationReport::new();
+    assert!(
+        verify_http_message(
+            &HTTPMessage::Request {
+                method: &other_method,
+                target_uri: &target_uri,
+                header_map: &header_map,
+            },
+            &verification_options,
+            &verifier_resolver,
+            &mut verification_report,
+        )
+        .await
+        .is_err()
+    );
+    assert!(
+        verification_report
+            .failed_check_iter()
+            .any(|check| check.kind == verifier_resolver::VerificationCheckKind::Signature)
+    );
+}
+
+#[tokio::test]
+async fn test_http_signature_verifier() {
+    use signature_dyn::GenerateRandom;
+
+    let signing_key = ed25519_dalek::SigningKey::generate_random();
+    let keyid = "did:example:alice?versionId=1#0";
+    let verifier_resolver_a = Arc::new(SingleKeyVerifierResolver {
+        keyid: keyid.to_string(),
+        verifying_key: signing_key.verifying_key(),
+    });
+    let http_signature_verifier = HTTPSignatureVerifier::new(
+        verifier_resolver_a,
+        HTTPSignatureVerificationOptions {
+            expected_tag_o: Some("test-app".to_string()),
+            ..Default::default()
+        },
+    );
+
+    // Sign a request as a client would, using its absolute target URI.
+    let method = http::Method::POST;
+    let body = br#"{"amount": 100}"#;
+    let mut header_map = http::HeaderMap::new();
+    header_map.insert(
+        http::header::CONTENT_TYPE,
+        http::HeaderValue::from_static("application/json"),
+    );
+    header_map.insert(
+        http_message_signatures::CONTENT_DIGEST,
+        content_digest(body, &[ContentDigestAlgorithm::SHA256])
+            .try_into()
+            .unwrap(),
+    );
+    let signature_params = SignatureParams {
+        tag_o: Some("test-app".to_string()),
+        ..SignatureParams::new(
+            vec![
+                "@method".to_string(),
+                "@target-uri".to_string(),
+                "content-type".to_string(),
+                "content-digest".to_string(),
+            ],
+            keyid.to_string(),
+        )
+    };
+    let http_signature = HTTPSignature::signed(
+        &HTTPMessage::Request {
+            method: &method,
+            target_uri: &http::Uri::from_static("https://api.example.com/transfer?from=alice"),
+            header_map: &header_map,
+        },
+        "sig1".to_string(),
+        signature_params,
+        &signing_key,
+    )
+    .expect("pass");
+    assert_eq!(
+        http_signature.signature_params().alg_o.as_deref(),
+        Some("ed25519")
+    );
+    http_signature
+        .append_to_header_map(&mut header_map)
+        .expect("pass");
+    // A second signature having the same label is rejected.
+    assert!(
+        http_signature
+            .append_to_header_map(&mut header_map)
+            .is_err()
+    );
+    println!(
+        "Signature-Input: {}\nSignature: {}",
+        http_signature.signature_input_member(),
+        http_signature.signature_member()
+    );
+
+    // The server sees the origin-form target, so the target URI is reconstructed from the Host field.
+    let origin_form_uri = http::Uri::from_static("/transfer?from=alice");
+    header_map.insert(
+        http::header::HOST,
+        http::HeaderValue::from_static("api.example.com"),
+    );
+    let mut verification_report = verifier_resolver::VerificationReport::new();
+    let verified_http_signature = http_signature_verifier
+        .verify_request(
+            &method,
+            &origin_form_uri,
+            &header_map,
+            body,
+            &mut verification_report,
+        )
+        .await
+        .expect("pass");
+    assert!(verification_report.is_valid());
+    assert_eq!(verified_http_signature.signature_params().keyid, keyid);
+
+    // A tampered body doesn't match the Content-Digest.
+    let mut verification_report = verifier_resolver::VerificationReport::new();
+    assert!(
+        http_signature_verifier
+            .verify_request(
+                &method,
+                &origin_form_uri,
+                &header_map,
+                br#"{"amount": 999}"#,
+                &mut verification_report,
+            )
+            .await
+            .is_err()
+    );
+    assert!(!verification_report.is_valid());
+
+    // A different host changes "@target-uri".
+    let mut other_header_map = header_map.clone();
+    other_header_map.insert(
+        http::header::HOST,
+        http::HeaderValue::from_static("evil.example.com"),
+    );
+    let mut verification_report = verifier_resolver::VerificationReport::new();
+    assert!(
+        http_signature_verifier
+            .verify_request(
+                &method,
+                &origin_form_uri,
+                &other_header_map,
+                body,
+                &mut verification_report,
+            )
+            .await
+            .is_err()
+    );
+
+    // Using the http scheme changes "@target-uri" too.
+    let http_signature_verifier_http = HTTPSignatureVerifier::new(
+        Arc::new(SingleKeyVerifierResolver {
+            keyid: keyid.to_string(),
+            verifying_key: signing_key.verifying_key(),
+        }),
+        http_signature_verifier.verification_options().clone(),
+    )
+    .with_scheme(http::uri::Scheme::HTTP);
+    let mut verification_report = verifier_resolver::VerificationReport::new();
+    assert!(
+        http_signature_verifier_http
+            .verify_request(
+                &method,
+                &origin_form_uri,
+                &header_map,
+                body,
+                &mut verification_report,
+            )
+            .await
+            .is_err()
+    );
+
+    // A signature lacking the expected tag is rejected.
+    let mut untagged_header_map = header_map.clone();
+    untagged_header_map.remove(http_message_signatures::SIGNATURE_INPUT);
+    untagged_header_map.remove(http_message_signatures::SIGNATURE);
+    HTTPSignature::signed(
+        &HTTPMessage::Request {
+            method: &method,
+            target_uri: &http::Uri::from_static("https://api.example.com/transfer?from=alice"),
+            header_map: &untagged_header_map,
+        },
+        "sig1".to_string(),
+        SignatureParams::new(
+            vec![
+                "@method".to_string(),
+                "@target-uri".to_string(),
+                "content-digest".to_string(),
+            ],
+            keyid.to_string(),
+        ),
+        &signing_key,
+    )
+    .expect("pass")
+    .append_to_header_map(&mut untagged_header_map)
+    .expect("pass");
+    let mut verification_report = verifier_resolver::VerificationReport::new();
+    assert!(
+        http_signature_verifier
+            .verify_request(
+                &method,
+                &origin_form_uri,
+                &untagged_header_map,
+                body,
+                &mut verification_report,
+            )
+            .await
+            .is_err()
+    );
+
+    // A signature made by a key that the verifier resolver doesn't know is rejected.
+    let other_signing_key = ed25519_dalek::SigningKey::generate_random();
+    let mut unknown_key_header_map = untagged_header_map.clone();
+    unknown_key_header_map.remove(http_message_signatures::SIGNATURE_INPUT);
+    unknown_key_header_map.remove(http_message_signatures::SIGNATURE);
+    HTTPSignature::signed(
+        &HTTPMessage::Request {
+            method: &method,
+            target_uri: &http::Uri::from_static("https://api.example.com/transfer?from=alice"),
+            header_map: &unknown_key_header_map,
+        },
+        "sig1".to_string(),
+        SignatureParams {
+            tag_o: Some("test-app".to_string()),
+            ..SignatureParams::new(
+                vec![
+                    "@method".to_string(),
+                    "@target-uri".to_string(),
+                    "content-digest".to_string(),
+                ],
+                "did:example:mallory?versionId=1#0".to_string(),
+            )
+        },
+        &other_signing_key,
+    )
+    .expect("pass")
+    .append_to_header_map(&mut unknown_key_header_map)
+    .expect("pass");
+    let mut verification_report = verifier_resolver::VerificationReport::new();
+    assert!(
+        http_signature_verifier
+            .verify_request(
+                &method,
+                &origin_form_uri,
+                &unknown_key_header_map,
+                body,
+                &mut verification_report,
+            )
+            .await
+            .is_err()
+    );
+    assert!(
+        verification_report
+            .failed_check_iter()
+            .any(|check| check.kind == verifier_resolver::VerificationCheckKind::KeyResolution)
+    );
+}
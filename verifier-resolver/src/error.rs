@@ -4,6 +4,10 @@ use std::borrow::Cow;
 pub enum Error {
     #[error("Invalid verifier: {0}")]
     InvalidVerifier(Cow<'static, str>),
+    #[error("Unauthorized verifier: {0}")]
+    UnauthorizedVerifier(Cow<'static, str>),
+    #[error("Untrusted verifier: {0}")]
+    UntrustedVerifier(Cow<'static, str>),
     #[error("Unsupported verifier: {0}")]
     UnsupportedVerifier(Cow<'static, str>),
 }
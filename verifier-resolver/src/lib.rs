@@ -1,9 +1,20 @@
 mod error;
+mod trust_policy;
+mod verification_relationship;
+mod verification_report;
 mod verifier_resolver;
 mod verifier_resolver_map;
 
 pub use crate::{
-    error::Error, verifier_resolver::VerifierResolver, verifier_resolver_map::VerifierResolverMap,
+    error::Error,
+    trust_policy::{IssuerRequirement, TrustPolicy},
+    verification_relationship::VerificationRelationship,
+    verification_report::{
+        VerificationCheck, VerificationCheckKind, VerificationOutcome, VerificationReport,
+        did_document_version_of,
+    },
+    verifier_resolver::VerifierResolver,
+    verifier_resolver_map::VerifierResolverMap,
 };
 
 pub use anyhow::Result;
@@ -0,0 +1,526 @@
+use crate::{Error, Result, VerifierResolver};
+use std::{collections::BTreeMap, str::FromStr};
+
+/// Requirements on the issuer of a document, i.e. the DID that controls the key that signed it.  An issuer
+/// meets the requirement if it's listed in allowed_issuer_v or if it's hosted on a host matching one of
+/// allowed_host_pattern_v.  If both are empty, then any issuer meets the requirement.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct IssuerRequirement {
+    /// DIDs (without query params or fragment) of trusted issuers.
+    #[serde(
+        rename = "allowedIssuers",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub allowed_issuer_v: Vec<String>,
+    /// Patterns for the hosts of trusted issuers (only applies to DID methods that have a host, e.g.
+    /// did:webplus).  A pattern is either a host (e.g. "example.com"), which matches exactly that host, or
+    /// a wildcard (e.g. "*.example.com"), which matches any subdomain of that host.  If a pattern doesn't
+    /// specify a port, then it matches any port.
+    #[serde(
+        rename = "allowedHostPatterns",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub allowed_host_pattern_v: Vec<String>,
+}
+
+impl IssuerRequirement {
+    pub fn is_empty(&self) -> bool {
+        self.allowed_issuer_v.is_empty() && self.allowed_host_pattern_v.is_empty()
+    }
+    pub fn is_met_by(&self, issuer_did: &str) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        if self
+            .allowed_issuer_v
+            .iter()
+            .any(|allowed_issuer| allowed_issuer == issuer_did)
+        {
+            return true;
+        }
+        if let Some(host) = did_host_o(issuer_did) {
+            self.allowed_host_pattern_v
+                .iter()
+                .any(|host_pattern| host_matches_pattern(host.as_str(), host_pattern))
+        } else {
+            false
+        }
+    }
+}
+
+/// A declarative policy that determines which verifiers (e.g. the keys that sign JWSes, VJSON, or
+/// Verifiable Credentials) a verifier of documents trusts.  Without a TrustPolicy, any validly signed
+/// document is accepted, regardless of who signed it.  A TrustPolicy can be read from JSON or TOML,
+/// e.g.
+///
+/// ```json
+/// {
+///     "allowedHostPatterns": ["example.com", "*.example.org"],
+///     "credentialTypes": {
+///         "UniversityDegreeCredential": { "allowedIssuers": ["did:webplus:university.edu:uHiB..."] }
+///     },
+///     "allowedKeyTypes": ["Ed25519", "P256"],
+///     "allowedHashFunctions": ["BLAKE3"],
+///     "requiredVDGHosts": ["vdg.example.com"]
+/// }
+/// ```
+///
+/// Every field is optional, and an empty list imposes no requirement.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct TrustPolicy {
+    /// The requirement that every issuer must meet.
+    #[serde(flatten)]
+    pub issuer_requirement: IssuerRequirement,
+    /// Additional requirements that the issuer of a credential having the given type must meet.  For
+    /// example, a credential whose "type" includes "UniversityDegreeCredential" may only be trusted if
+    /// issued by particular universities.
+    #[serde(
+        rename = "credentialTypes",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub credential_type_issuer_requirement_m: BTreeMap<String, IssuerRequirement>,
+    /// The key types (e.g. "Ed25519", "P256") that signing keys must have.
+    #[serde(
+        rename = "allowedKeyTypes",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub allowed_key_type_v: Vec<String>,
+    /// The hash functions (e.g. "BLAKE3") that the self-hashes of did:webplus DIDs must use, i.e.
+    /// the root self-hash in the DID, as well as the selfHash query param of the verifier, if present.
+    #[serde(
+        rename = "allowedHashFunctions",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub allowed_hash_function_v: Vec<String>,
+    /// If non-empty, then did:webplus DIDs must be resolved within the scope of agreement of one of these
+    /// VDGs (see VerifierResolver::vdg_host_o), so that the DID documents used for verification are the same
+    /// ones that every other participant in that scope of agreement sees.
+    #[serde(
+        rename = "requiredVDGHosts",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub required_vdg_host_v: Vec<String>,
+}
+
+impl TrustPolicy {
+    pub fn from_json_str(s: &str) -> Result<Self> {
+        let trust_policy: Self = serde_json::from_str(s)
+            .map_err(|e| anyhow::anyhow!("Malformed trust policy JSON: {}", e))?;
+        trust_policy.validate()?;
+        Ok(trust_policy)
+    }
+    pub fn from_toml_str(s: &str) -> Result<Self> {
+        let trust_policy: Self =
+            toml::from_str(s).map_err(|e| anyhow::anyhow!("Malformed trust policy TOML: {}", e))?;
+        trust_policy.validate()?;
+        Ok(trust_policy)
+    }
+    /// Reads a trust policy from the given file, which is parsed as TOML if it has a ".toml" extension, and
+    /// as JSON otherwise.
+    pub fn read_from_file(path: &std::path::Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read trust policy file {:?}: {}", path, e))?;
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "toml")
+        {
+            Self::from_toml_str(&content)
+        } else {
+            Self::from_json_str(&content)
+        }
+    }
+    /// Ensures that the key types and hash functions are recognized and that the patterns and hosts are
+    /// well-formed.
+    pub fn validate(&self) -> Result<()> {
+        for allowed_key_type in self.allowed_key_type_v.iter() {
+            signature_dyn::KeyType::from_str(allowed_key_type).map_err(|_| {
+                anyhow::anyhow!(
+                    "Unrecognized key type {:?} in trust policy",
+                    allowed_key_type
+                )
+            })?;
+        }
+        for allowed_hash_function in self.allowed_hash_function_v.iter() {
+            selfhash::NamedHashFunction::from_str(allowed_hash_function).map_err(|_| {
+                anyhow::anyhow!(
+                    "Unrecognized hash function {:?} in trust policy",
+                    allowed_hash_function
+                )
+            })?;
+        }
+        for issuer_requirement in std::iter::once(&self.issuer_requirement)
+            .chain(self.credential_type_issuer_requirement_m.values())
+        {
+            for allowed_issuer in issuer_requirement.allowed_issuer_v.iter() {
+                anyhow::ensure!(
+                    allowed_issuer.starts_with("did:") && !allowed_issuer.contains(['?', '#']),
+                    "Allowed issuer {:?} in trust policy must be a DID without query params or fragment",
+                    allowed_issuer
+                );
+            }
+            for host_pattern in issuer_requirement.allowed_host_pattern_v.iter() {
+                let host = host_pattern.strip_prefix("*.").unwrap_or(host_pattern);
+                anyhow::ensure!(
+                    !host.is_empty() && !host.contains(['*', '/']),
+                    "Malformed host pattern {:?} in trust policy",
+                    host_pattern
+                );
+            }
+        }
+        for required_vdg_host in self.required_vdg_host_v.iter() {
+            anyhow::ensure!(
+                !required_vdg_host.contains("://"),
+                "Required VDG host {:?} in trust policy must not contain a scheme",
+                required_vdg_host
+            );
+        }
+        Ok(())
+    }
+    /// Ensures that the issuer of the given verifier meets this policy's issuer requirement, as well as the
+    /// issuer requirement of each of the given credential types, that the verifier was resolved within
+    /// the required VDG scope (if any), and that its self-hashes (if it's a did:webplus verifier) use allowed
+    /// hash functions.  vdg_host_o should be the VDG host of the VerifierResolver used to
+    /// resolve the verifier.  Returns Error::UntrustedVerifier if not.
+    pub fn ensure_verifier_is_trusted(
+        &self,
+        verifier_str: &str,
+        credential_type_v: &[&str],
+        vdg_host_o: Option<&str>,
+    ) -> Result<()> {
+        let issuer_did = verifier_str.split(['?', '#']).next().unwrap();
+        if !self.issuer_requirement.is_met_by(issuer_did) {
+            anyhow::bail!(Error::UntrustedVerifier(
+                format!("issuer {} is not allowed by the trust policy", issuer_did).into()
+            ));
+        }
+        for credential_type in credential_type_v {
+            if let Some(issuer_requirement) = self
+                .credential_type_issuer_requirement_m
+                .get(*credential_type)
+                && !issuer_requirement.is_met_by(issuer_did)
+            {
+                anyhow::bail!(Error::UntrustedVerifier(
+                    format!(
+                        "issuer {} is not allowed by the trust policy to issue credentials of type {:?}",
+                        issuer_did, credential_type
+                    )
+                    .into()
+                ));
+            }
+        }
+        if !self.required_vdg_host_v.is_empty() && issuer_did.starts_with("did:webplus:") {
+            let in_scope = vdg_host_o.is_some_and(|vdg_host| {
+                self.required_vdg_host_v
+                    .iter()
+                    .any(|required_vdg_host| required_vdg_host == vdg_host)
+            });
+            if !in_scope {
+                anyhow::bail!(Error::UntrustedVerifier(
+                    format!(
+                        "{} was not resolved within the scope of agreement of any of the VDGs required by the trust policy (resolver's VDG: {:?}, required VDGs: {:?})",
+                        issuer_did, vdg_host_o, self.required_vdg_host_v
+                    )
+                    .into()
+                ));
+            }
+        }
+        if !self.allowed_hash_function_v.is_empty() && issuer_did.starts_with("did:webplus:") {
+            // The root self-hash is the last component of the DID.
+            let root_self_hash_str = issuer_did.rsplit(':').next().unwrap();
+            self.ensure_self_hash_function_is_allowed(verifier_str, root_self_hash_str)?;
+            if let Some(self_hash_str) = query_param_o(verifier_str, "selfHash") {
+                self.ensure_self_hash_function_is_allowed(verifier_str, self_hash_str)?;
+            }
+        }
+        Ok(())
+    }
+    /// Ensures that the given self-hash (of the given verifier) uses a hash function allowed by this policy,
+    /// returning Error::UntrustedVerifier if not.
+    fn ensure_self_hash_function_is_allowed(
+        &self,
+        verifier_str: &str,
+        self_hash_str: &str,
+    ) -> Result<()> {
+        let self_hash = mbx::MBHashStr::new_ref(self_hash_str).map_err(|e| {
+            Error::InvalidVerifier(
+                format!(
+                    "malformed self-hash {:?} in verifier {:?}; error was: {}",
+                    self_hash_str, verifier_str, e
+                )
+                .into(),
+            )
+        })?;
+        let codec = self_hash
+            .decoded::<64>()
+            .map_err(|e| {
+                Error::InvalidVerifier(
+                    format!(
+                        "malformed self-hash {:?} in verifier {:?}; error was: {}",
+                        self_hash_str, verifier_str, e
+                    )
+                    .into(),
+                )
+            })?
+            .code();
+        let is_allowed = self
+            .allowed_hash_function_v
+            .iter()
+            .any(|allowed_hash_function| {
+                selfhash::NamedHashFunction::from_str(allowed_hash_function).is_ok_and(
+                    |allowed_hash_function| {
+                        allowed_hash_function
+                            .as_mb_hash_function(self_hash.base())
+                            .codec()
+                            == codec
+                    },
+                )
+            });
+        if !is_allowed {
+            anyhow::bail!(Error::UntrustedVerifier(
+                format!(
+                    "self-hash {} of verifier {} uses a hash function that isn't allowed by the trust policy (allowed hash functions: {:?})",
+                    self_hash_str, verifier_str, self.allowed_hash_function_v
+                )
+                .into()
+            ));
+        }
+        Ok(())
+    }
+    /// Ensures that the given key type is allowed by this policy, returning Error::UntrustedVerifier if not.
+    pub fn ensure_key_type_is_allowed(&self, key_type: signature_dyn::KeyType) -> Result<()> {
+        if self.allowed_key_type_v.is_empty() {
+            return Ok(());
+        }
+        let is_allowed = self.allowed_key_type_v.iter().any(|allowed_key_type| {
+            signature_dyn::KeyType::from_str(allowed_key_type)
+                .is_ok_and(|allowed_key_type| allowed_key_type == key_type)
+        });
+        if !is_allowed {
+            anyhow::bail!(Error::UntrustedVerifier(
+                format!(
+                    "key type {} is not allowed by the trust policy (allowed key types: {:?})",
+                    key_type, self.allowed_key_type_v
+                )
+                .into()
+            ));
+        }
+        Ok(())
+    }
+    /// Evaluates this policy in full against the given verifier, resolving it using verifier_resolver in
+    /// order to determine its key type.  See ensure_verifier_is_trusted.
+    pub async fn ensure_verifier_is_trusted_and_allowed(
+        &self,
+        verifier_str: &str,
+        credential_type_v: &[&str],
+        verifier_resolver: &dyn VerifierResolver,
+    ) -> Result<()> {
+        self.ensure_verifier_is_trusted(
+            verifier_str,
+            credential_type_v,
+            verifier_resolver.vdg_host_o(),
+        )?;
+        let verifier_b = verifier_resolver.resolve(verifier_str).await?;
+        self.ensure_key_type_is_allowed(verifier_b.key_type())
+    }
+}
+
+/// Returns the host (including the port, if any) of a DID whose method has a host, i.e. did:webplus
+/// and did:web, in which the port is percent-encoded (e.g. "did:webplus:localhost%3A3000:uHiB...").
+fn did_host_o(did: &str) -> Option<String> {
+    let method_specific_id = did
+        .strip_prefix("did:webplus:")
+        .or_else(|| did.strip_prefix("did:web:"))?;
+    let host = method_specific_id.split(':').next().unwrap();
+    Some(host.replace("%3A", ":").replace("%3a", ":"))
+}
+
+/// Returns the value of the given query param of a DID URL, if present.
+fn query_param_o<'a>(did_url: &'a str, name: &str) -> Option<&'a str> {
+    let (_, query_and_maybe_fragment) = did_url.split_once('?')?;
+    let query = query_and_maybe_fragment.split('#').next().unwrap();
+    query.split('&').find_map(|query_param| {
+        query_param
+            .split_once('=')
+            .filter(|(param_name, _)| *param_name == name)
+            .map(|(_, value)| value)
+    })
+}
+
+fn host_matches_pattern(host: &str, host_pattern: &str) -> bool {
+    // If the pattern doesn't specify a port, then the port of the host is ignored.
+    let host = if host_pattern.contains(':') {
+        host
+    } else {
+        host.split(':').next().unwrap()
+    };
+    let host = host.to_ascii_lowercase();
+    let host_pattern = host_pattern.to_ascii_lowercase();
+    if let Some(domain) = host_pattern.strip_prefix("*.") {
+        host.len() > domain.len() + 1
+            && host.ends_with(domain)
+            && host.as_bytes()[host.len() - domain.len() - 1] == b'.'
+    } else {
+        host == host_pattern
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_matches_pattern() {
+        assert!(host_matches_pattern("example.com", "example.com"));
+        assert!(host_matches_pattern("Example.COM", "example.com"));
+        assert!(host_matches_pattern("example.com:3000", "example.com"));
+        assert!(host_matches_pattern("example.com:3000", "example.com:3000"));
+        assert!(!host_matches_pattern(
+            "example.com:3001",
+            "example.com:3000"
+        ));
+        assert!(!host_matches_pattern("example.com", "example.com:3000"));
+        assert!(!host_matches_pattern("evilexample.com", "example.com"));
+        assert!(host_matches_pattern("a.example.com", "*.example.com"));
+        assert!(host_matches_pattern("a.b.example.com", "*.example.com"));
+        assert!(!host_matches_pattern("example.com", "*.example.com"));
+        assert!(!host_matches_pattern("evilexample.com", "*.example.com"));
+    }
+
+    #[test]
+    fn test_trust_policy() {
+        let trust_policy = TrustPolicy::from_json_str(
+            r#"{
+                "allowedIssuers": ["did:webplus:other.org:uHiA"],
+                "allowedHostPatterns": ["*.example.com", "localhost:3000"],
+                "credentialTypes": {
+                    "DegreeCredential": { "allowedIssuers": ["did:webplus:u.example.com:uHiB"] }
+                },
+                "requiredVDGHosts": ["vdg.example.com"]
+            }"#,
+        )
+        .expect("pass");
+        let vdg_host_o = Some("vdg.example.com");
+
+        trust_policy
+            .ensure_verifier_is_trusted(
+                "did:webplus:u.example.com:uHiB?selfHash=uHiC&versionId=1#0",
+                &[],
+                vdg_host_o,
+            )
+            .expect("pass");
+        trust_policy
+            .ensure_verifier_is_trusted("did:webplus:localhost%3A3000:uHiD#0", &[], vdg_host_o)
+            .expect("pass");
+        trust_policy
+            .ensure_verifier_is_trusted("did:webplus:other.org:uHiA#0", &[], vdg_host_o)
+            .expect("pass");
+        trust_policy
+            .ensure_verifier_is_trusted(
+                "did:webplus:u.example.com:uHiB#0",
+                &["VerifiableCredential", "DegreeCredential"],
+                vdg_host_o,
+            )
+            .expect("pass");
+
+        for (verifier_str, credential_type_v, vdg_host_o) in [
+            // Host not allowed.
+            ("did:webplus:evil.org:uHiE#0", vec![], vdg_host_o),
+            ("did:webplus:localhost%3A3001:uHiD#0", vec![], vdg_host_o),
+            // did:key has no host.
+            ("did:key:u7QE#u7QE", vec![], vdg_host_o),
+            // Issuer not allowed for credential type.
+            (
+                "did:webplus:v.example.com:uHiF#0",
+                vec!["DegreeCredential"],
+                vdg_host_o,
+            ),
+            // Not resolved within the required VDG scope.
+            ("did:webplus:u.example.com:uHiB#0", vec![], None),
+            (
+                "did:webplus:u.example.com:uHiB#0",
+                vec![],
+                Some("other-vdg.example.com"),
+            ),
+        ] {
+            let error = trust_policy
+                .ensure_verifier_is_trusted(verifier_str, credential_type_v.as_slice(), vdg_host_o)
+                .expect_err("pass");
+            assert!(
+                matches!(
+                    error.downcast_ref::<Error>(),
+                    Some(Error::UntrustedVerifier(_))
+                ),
+                "{}",
+                error
+            );
+        }
+
+        // The same policy in TOML.
+        let trust_policy_from_toml = TrustPolicy::from_toml_str(
+            r#"
+            allowedIssuers = ["did:webplus:other.org:uHiA"]
+            allowedHostPatterns = ["*.example.com", "localhost:3000"]
+            requiredVDGHosts = ["vdg.example.com"]
+
+            [credentialTypes.DegreeCredential]
+            allowedIssuers = ["did:webplus:u.example.com:uHiB"]
+            "#,
+        )
+        .expect("pass");
+        assert_eq!(trust_policy_from_toml, trust_policy);
+
+        // Hash function restrictions apply to the root self-hash and the selfHash query param.
+        let blake3_self_hash = "uHiBKYbaJL8Gz2hmMHHSB_NJJanrkNwFDndtAvQCFdpLm0Q";
+        let sha256_self_hash = "uEiAAAQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eHw";
+        let trust_policy =
+            TrustPolicy::from_json_str(r#"{ "allowedHashFunctions": ["BLAKE3"] }"#).expect("pass");
+        trust_policy
+            .ensure_verifier_is_trusted(
+                &format!(
+                    "did:webplus:example.com:{}?selfHash={}&versionId=1#0",
+                    blake3_self_hash, blake3_self_hash
+                ),
+                &[],
+                None,
+            )
+            .expect("pass");
+        // Other DID methods have no self-hash.
+        trust_policy
+            .ensure_verifier_is_trusted("did:key:u7QE#u7QE", &[], None)
+            .expect("pass");
+        for verifier_str in [
+            format!("did:webplus:example.com:{}#0", sha256_self_hash),
+            format!(
+                "did:webplus:example.com:{}?selfHash={}&versionId=1#0",
+                blake3_self_hash, sha256_self_hash
+            ),
+        ] {
+            let error = trust_policy
+                .ensure_verifier_is_trusted(verifier_str.as_str(), &[], None)
+                .expect_err("pass");
+            assert!(
+                matches!(
+                    error.downcast_ref::<Error>(),
+                    Some(Error::UntrustedVerifier(_))
+                ),
+                "{}",
+                error
+            );
+        }
+
+        // Malformed policies.
+        TrustPolicy::from_json_str(r#"{ "allowedKeyTypes": ["Rot13"] }"#).expect_err("pass");
+        TrustPolicy::from_json_str(r#"{ "allowedHashFunctions": ["Rot13"] }"#).expect_err("pass");
+        TrustPolicy::from_json_str(r#"{ "allowedIssuers": ["did:webplus:a.com:uHiA#0"] }"#)
+            .expect_err("pass");
+        TrustPolicy::from_json_str(r#"{ "allowedHostPatterns": ["*"] }"#).expect_err("pass");
+        TrustPolicy::from_json_str(r#"{ "requiredVDGHosts": ["https://vdg.example.com"] }"#)
+            .expect_err("pass");
+    }
+}
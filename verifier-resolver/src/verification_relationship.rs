@@ -0,0 +1,33 @@
+/// The verification relationships defined by the DID spec (see
+/// <https://www.w3.org/TR/did-1.0/#verification-relationships>).  These are used to require that
+/// a verifier is authorized for a particular purpose, e.g. that a key used to sign a Verifiable
+/// Credential is listed under "assertionMethod" in the issuer's DID document.  This is defined here
+/// (instead of using a DID-method-specific type) so that VerifierResolver doesn't depend on any
+/// particular DID method.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum VerificationRelationship {
+    Authentication,
+    AssertionMethod,
+    KeyAgreement,
+    CapabilityInvocation,
+    CapabilityDelegation,
+}
+
+impl VerificationRelationship {
+    /// Produce the camelCase string for this variant, as used in DID documents.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Authentication => "authentication",
+            Self::AssertionMethod => "assertionMethod",
+            Self::KeyAgreement => "keyAgreement",
+            Self::CapabilityInvocation => "capabilityInvocation",
+            Self::CapabilityDelegation => "capabilityDelegation",
+        }
+    }
+}
+
+impl std::fmt::Display for VerificationRelationship {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
@@ -0,0 +1,259 @@
+use crate::{Error, Result, VerificationRelationship, VerifierResolver};
+
+/// The kinds of checks that verification of a document (e.g. a JWS, VJSON, VC, or VP) can involve.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VerificationCheckKind {
+    /// The document's self-hash(es) match its content.
+    SelfHash,
+    /// A signature is valid under the resolved key.
+    Signature,
+    /// A key ID (e.g. a JWS "kid") resolved to a public key.
+    KeyResolution,
+    /// The key is listed under the required verification relationship in its DID document.
+    KeyPurpose,
+    /// The key was valid (i.e. not yet rotated away or deactivated) at the claimed signing time.
+    KeyValidityWindow,
+    /// The document conforms to its JSON schema.
+    SchemaValidation,
+    /// Claims about the document itself, e.g. JWT registered claims or a credential's validity period.
+    Claims,
+    /// The credential has not been revoked or suspended.
+    CredentialStatus,
+    /// The signer is trusted according to the verifier's TrustPolicy.
+    TrustPolicy,
+}
+
+/// The outcome of a single check.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase", tag = "result")]
+pub enum VerificationOutcome {
+    Passed,
+    Failed { reason: String },
+}
+
+/// A single check performed during verification.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationCheck {
+    pub kind: VerificationCheckKind,
+    /// What was checked, e.g. the key ID of a signature, if the check applies to a particular part of the document.
+    #[serde(rename = "subject", default, skip_serializing_if = "Option::is_none")]
+    pub subject_o: Option<String>,
+    /// The exact DID document version that the check used, e.g. the fully qualified DID (including selfHash
+    /// and versionId query params) of the DID document a key was resolved from.
+    #[serde(
+        rename = "didDocumentVersion",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub did_document_version_o: Option<String>,
+    pub outcome: VerificationOutcome,
+}
+
+/// A record of each check performed while verifying a document, along with its outcome, so that it's possible
+/// to determine (e.g. for auditing purposes) why a document passed or failed verification.  Verification
+/// functions append to a VerificationReport passed in by the caller, so that the report is available even
+/// when verification fails.  Note that verification typically stops at the first failed check.
+#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct VerificationReport {
+    #[serde(rename = "checks")]
+    pub check_v: Vec<VerificationCheck>,
+}
+
+impl VerificationReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Returns true if at least one check was performed and all checks passed.
+    pub fn is_valid(&self) -> bool {
+        !self.check_v.is_empty()
+            && self
+                .check_v
+                .iter()
+                .all(|check| check.outcome == VerificationOutcome::Passed)
+    }
+    pub fn failed_check_iter(&self) -> impl Iterator<Item = &VerificationCheck> {
+        self.check_v
+            .iter()
+            .filter(|check| check.outcome != VerificationOutcome::Passed)
+    }
+    /// Appends a check having the given outcome.
+    pub fn record(
+        &mut self,
+        kind: VerificationCheckKind,
+        subject_o: Option<&str>,
+        did_document_version_o: Option<&str>,
+        outcome: VerificationOutcome,
+    ) {
+        self.check_v.push(VerificationCheck {
+            kind,
+            subject_o: subject_o.map(str::to_owned),
+            did_document_version_o: did_document_version_o.map(str::to_owned),
+            outcome,
+        });
+    }
+    /// Appends a check whose outcome is determined by the given result, which is then returned, so that this
+    /// can wrap the expression that performs the check, e.g. `report.record_result(kind, None, None, check())?`.
+    pub fn record_result<T, E: std::fmt::Display>(
+        &mut self,
+        kind: VerificationCheckKind,
+        subject_o: Option<&str>,
+        did_document_version_o: Option<&str>,
+        result: std::result::Result<T, E>,
+    ) -> std::result::Result<T, E> {
+        let outcome = match &result {
+            Ok(_) => VerificationOutcome::Passed,
+            Err(e) => VerificationOutcome::Failed {
+                reason: e.to_string(),
+            },
+        };
+        self.record(kind, subject_o, did_document_version_o, outcome);
+        result
+    }
+    /// Returns the DID document version recorded by the latest passed key resolution check for the given
+    /// verifier, i.e. the exact DID document that VerificationReport::resolve_for_purpose resolved it from,
+    /// so that subsequent checks (e.g. the signature check) can record the same version.
+    pub fn resolved_did_document_version(&self, verifier_str: &str) -> Option<String> {
+        self.check_v
+            .iter()
+            .rev()
+            .find(|check| {
+                check.kind == VerificationCheckKind::KeyResolution
+                    && check.outcome == VerificationOutcome::Passed
+                    && check.subject_o.as_deref() == Some(verifier_str)
+            })
+            .and_then(|check| check.did_document_version_o.clone())
+    }
+    /// Resolves the given verifier (e.g. a JWS "kid") for the given verification relationship, recording
+    /// the key resolution and key purpose checks, or the trust policy check if verifier_resolver doesn't
+    /// trust the verifier.  The DID document version recorded is the one reported by verifier_resolver.
+    pub async fn resolve_for_purpose(
+        &mut self,
+        verifier_str: &str,
+        verification_relationship: VerificationRelationship,
+        verifier_resolver: &dyn VerifierResolver,
+    ) -> Result<Box<dyn signature_dyn::VerifierT>> {
+        let result = verifier_resolver
+            .resolve_for_purpose_with_version(verifier_str, verification_relationship)
+            .await;
+        match &result {
+            Ok((_verifier_b, did_document_version_o)) => {
+                self.record(
+                    VerificationCheckKind::KeyResolution,
+                    Some(verifier_str),
+                    did_document_version_o.as_deref(),
+                    VerificationOutcome::Passed,
+                );
+                self.record(
+                    VerificationCheckKind::KeyPurpose,
+                    Some(verifier_str),
+                    did_document_version_o.as_deref(),
+                    VerificationOutcome::Passed,
+                );
+            }
+            Err(e) => {
+                // Resolution failed, so no DID document version was resolved.
+                if let Some(Error::UntrustedVerifier(_)) = e.downcast_ref::<Error>() {
+                    // The verifier_resolver (e.g. a VerifierResolverMap having a TrustPolicy) doesn't trust the signer.
+                    self.record(
+                        VerificationCheckKind::TrustPolicy,
+                        Some(verifier_str),
+                        None,
+                        VerificationOutcome::Failed {
+                            reason: e.to_string(),
+                        },
+                    );
+                } else if let Some(Error::UnauthorizedVerifier(_)) = e.downcast_ref::<Error>() {
+                    // The key resolved, but isn't authorized for the verification relationship.
+                    self.record(
+                        VerificationCheckKind::KeyResolution,
+                        Some(verifier_str),
+                        None,
+                        VerificationOutcome::Passed,
+                    );
+                    self.record(
+                        VerificationCheckKind::KeyPurpose,
+                        Some(verifier_str),
+                        None,
+                        VerificationOutcome::Failed {
+                            reason: e.to_string(),
+                        },
+                    );
+                } else {
+                    self.record(
+                        VerificationCheckKind::KeyResolution,
+                        Some(verifier_str),
+                        None,
+                        VerificationOutcome::Failed {
+                            reason: e.to_string(),
+                        },
+                    );
+                }
+            }
+        }
+        let (verifier_b, _did_document_version_o) = result?;
+        Ok(verifier_b)
+    }
+}
+
+/// Returns the DID document version that a DID-based verifier (i.e. key ID) refers to, which is the verifier
+/// without its fragment, if it has a selfHash or versionId query param (e.g. a fully qualified did:webplus key
+/// ID), and therefore identifies the exact DID document that the key was resolved from.  Otherwise, the key
+/// ID doesn't identify a specific DID document version (e.g. it's resolved from the latest DID document), so
+/// this returns None.  This is only for checks that aren't preceded by VerificationReport::resolve_for_purpose
+/// (e.g. signatures verified by another library); otherwise use VerificationReport::resolved_did_document_version.
+pub fn did_document_version_of(verifier_str: &str) -> Option<&str> {
+    if !verifier_str.starts_with("did:") {
+        return None;
+    }
+    let did_with_query = verifier_str.split('#').next().unwrap();
+    let (_did, query) = did_with_query.split_once('?')?;
+    let identifies_version = query.split('&').any(|param| {
+        matches!(
+            param.split_once('=').map(|(name, _value)| name),
+            Some("selfHash" | "versionId")
+        )
+    });
+    identifies_version.then_some(did_with_query)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_did_document_version_of() {
+        assert_eq!(
+            did_document_version_of("did:webplus:example.com:EHash?selfHash=EHash&versionId=3#0"),
+            Some("did:webplus:example.com:EHash?selfHash=EHash&versionId=3")
+        );
+        assert_eq!(
+            did_document_version_of("did:webplus:example.com:EHash?versionId=3#0"),
+            Some("did:webplus:example.com:EHash?versionId=3")
+        );
+        assert_eq!(
+            did_document_version_of("did:webplus:example.com:EHash?selfHash=EHash#0"),
+            Some("did:webplus:example.com:EHash?selfHash=EHash")
+        );
+        // Without a selfHash or versionId, the key ID doesn't identify a specific DID document version.
+        assert_eq!(
+            did_document_version_of("did:webplus:example.com:EHash#0"),
+            None
+        );
+        assert_eq!(
+            did_document_version_of(
+                "did:webplus:example.com:EHash?versionTime=2025-01-01T00:00:00Z#0"
+            ),
+            None
+        );
+        assert_eq!(
+            did_document_version_of("did:key:z6MkExample#z6MkExample"),
+            None
+        );
+        assert_eq!(
+            did_document_version_of("u7QEJX5oaHTWRY5MfARFz1QoEAwvjmPDyTKGe8JjgH1SV0w"),
+            None
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Result;
+use crate::{Result, VerificationRelationship};
 
 /// This trait provides a way to turn a "verifier" string into a `Box<dyn signature_dyn::VerifierT>`.
 /// For example, DID methods can implement this to turn a fully-qualified DID resource (that
@@ -7,4 +7,35 @@ use crate::Result;
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 pub trait VerifierResolver: Send + Sync {
     async fn resolve(&self, verifier_str: &str) -> Result<Box<dyn signature_dyn::VerifierT>>;
+    /// Like resolve, but additionally requires that the verifier is authorized for the given
+    /// verification relationship, e.g. a DID method would check that the key is listed under that
+    /// verification relationship in the DID document, returning Error::UnauthorizedVerifier if not.
+    /// There's deliberately no default impl, so that a resolver can't silently ignore the verification
+    /// relationship; resolvers whose verifiers have no notion of verification relationships (e.g. did:key)
+    /// must say so explicitly by delegating to resolve.
+    async fn resolve_for_purpose(
+        &self,
+        verifier_str: &str,
+        verification_relationship: VerificationRelationship,
+    ) -> Result<Box<dyn signature_dyn::VerifierT>>;
+    /// Like resolve_for_purpose, but also returns the exact DID document version that the verifier was
+    /// resolved from (e.g. the DID with the selfHash and versionId query params of the resolved DID document),
+    /// which VerificationReport records.  The default impl returns None for the version, which is appropriate
+    /// for verifiers that aren't resolved from a versioned DID document (e.g. did:key).
+    async fn resolve_for_purpose_with_version(
+        &self,
+        verifier_str: &str,
+        verification_relationship: VerificationRelationship,
+    ) -> Result<(Box<dyn signature_dyn::VerifierT>, Option<String>)> {
+        let verifier_b = self
+            .resolve_for_purpose(verifier_str, verification_relationship)
+            .await?;
+        Ok((verifier_b, None))
+    }
+    /// If this resolver takes part in the scope of agreement defined by a VDG (Verifiable Data Gateway),
+    /// i.e. it resolves DID documents via that VDG, then this returns the host of that VDG.  This is used
+    /// by TrustPolicy to require a particular VDG scope.  The default impl returns None.
+    fn vdg_host_o(&self) -> Option<&str> {
+        None
+    }
 }
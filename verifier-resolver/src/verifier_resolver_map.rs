@@ -1,12 +1,14 @@
-use crate::{Error, Result, VerifierResolver};
+use crate::{Error, Result, TrustPolicy, VerificationRelationship, VerifierResolver};
 use std::collections::HashMap;
 
 /// This provides a VerifierResolver impl which can resolve many different types of verifiers,
 /// each with their own prefix.  The prefix is used to classify the verifier and determine which
-/// specifiec VerifierResolver to use to resolve it.
+/// specifiec VerifierResolver to use to resolve it.  If a TrustPolicy is specified, then it's evaluated
+/// for each verifier that's resolved, and Error::UntrustedVerifier is returned for verifiers it doesn't trust.
 pub struct VerifierResolverMap {
     verifier_prefix_v: Vec<String>,
     verifier_resolver_m: HashMap<String, Box<dyn VerifierResolver>>,
+    trust_policy_o: Option<TrustPolicy>,
 }
 
 impl VerifierResolverMap {
@@ -14,6 +16,7 @@ impl VerifierResolverMap {
         Self {
             verifier_prefix_v: Vec::new(),
             verifier_resolver_m: HashMap::new(),
+            trust_policy_o: None,
         }
     }
     pub fn with(
@@ -35,6 +38,13 @@ impl VerifierResolverMap {
         self.verifier_resolver_m
             .insert(verifier_prefix.to_owned(), verifier_resolver_b);
     }
+    pub fn with_trust_policy(mut self, trust_policy: TrustPolicy) -> Self {
+        self.trust_policy_o = Some(trust_policy);
+        self
+    }
+    pub fn trust_policy_o(&self) -> Option<&TrustPolicy> {
+        self.trust_policy_o.as_ref()
+    }
     pub fn classify_verifier(&self, verifier_str: &str) -> Result<&str> {
         for verifier_prefix in self.verifier_prefix_v.iter() {
             if verifier_str.starts_with(verifier_prefix) {
@@ -49,11 +59,57 @@ impl VerifierResolverMap {
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 impl VerifierResolver for VerifierResolverMap {
     async fn resolve(&self, verifier_str: &str) -> Result<Box<dyn signature_dyn::VerifierT>> {
+        let (verifier_b, _did_document_version_o) = self.resolve_impl(verifier_str, None).await?;
+        Ok(verifier_b)
+    }
+    async fn resolve_for_purpose(
+        &self,
+        verifier_str: &str,
+        verification_relationship: VerificationRelationship,
+    ) -> Result<Box<dyn signature_dyn::VerifierT>> {
+        let (verifier_b, _did_document_version_o) = self
+            .resolve_impl(verifier_str, Some(verification_relationship))
+            .await?;
+        Ok(verifier_b)
+    }
+    async fn resolve_for_purpose_with_version(
+        &self,
+        verifier_str: &str,
+        verification_relationship: VerificationRelationship,
+    ) -> Result<(Box<dyn signature_dyn::VerifierT>, Option<String>)> {
+        self.resolve_impl(verifier_str, Some(verification_relationship))
+            .await
+    }
+}
+
+impl VerifierResolverMap {
+    async fn resolve_impl(
+        &self,
+        verifier_str: &str,
+        verification_relationship_o: Option<VerificationRelationship>,
+    ) -> Result<(Box<dyn signature_dyn::VerifierT>, Option<String>)> {
         let verifier_class = self.classify_verifier(verifier_str)?;
-        if let Some(verifier_resolver) = self.verifier_resolver_m.get(verifier_class) {
-            Ok(verifier_resolver.resolve(verifier_str).await?)
-        } else {
+        let Some(verifier_resolver) = self.verifier_resolver_m.get(verifier_class) else {
             anyhow::bail!(Error::UnsupportedVerifier(verifier_str.to_owned().into()));
+        };
+        if let Some(trust_policy) = self.trust_policy_o.as_ref() {
+            trust_policy.ensure_verifier_is_trusted(
+                verifier_str,
+                &[],
+                verifier_resolver.vdg_host_o(),
+            )?;
+        }
+        let (verifier_b, did_document_version_o) =
+            if let Some(verification_relationship) = verification_relationship_o {
+                verifier_resolver
+                    .resolve_for_purpose_with_version(verifier_str, verification_relationship)
+                    .await?
+            } else {
+                (verifier_resolver.resolve(verifier_str).await?, None)
+            };
+        if let Some(trust_policy) = self.trust_policy_o.as_ref() {
+            trust_policy.ensure_key_type_is_allowed(verifier_b.key_type())?;
         }
+        Ok((verifier_b, did_document_version_o))
     }
 }
@@ -14,16 +14,33 @@ pub trait Validate: selfhash::SelfHashableT<mbx::MBHashStr> {
         &self,
         vjson_resolver: &dyn VJSONResolver,
         verifier_resolver: &dyn verifier_resolver::VerifierResolver,
+    ) -> Result<mbx::MBHash> {
+        let mut verification_report = verifier_resolver::VerificationReport::new();
+        self.validate_and_return_self_hash_with_report(
+            vjson_resolver,
+            verifier_resolver,
+            &mut verification_report,
+        )
+        .await
+    }
+    /// Same as validate_and_return_self_hash, but also records each check performed (and its outcome) in
+    /// verification_report, including when validation fails.
+    async fn validate_and_return_self_hash_with_report(
+        &self,
+        vjson_resolver: &dyn VJSONResolver,
+        verifier_resolver: &dyn verifier_resolver::VerifierResolver,
+        verification_report: &mut verifier_resolver::VerificationReport,
     ) -> Result<mbx::MBHash>;
 }
 
 #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
 impl Validate for serde_json::Value {
-    async fn validate_and_return_self_hash(
+    async fn validate_and_return_self_hash_with_report(
         &self,
         vjson_resolver: &dyn VJSONResolver,
         verifier_resolver: &dyn verifier_resolver::VerifierResolver,
+        verification_report: &mut verifier_resolver::VerificationReport,
     ) -> Result<mbx::MBHash> {
         tracing::debug!("validate_and_return_self_hash");
 
@@ -33,7 +50,12 @@ impl Validate for serde_json::Value {
         let (mut self_hashable_json, schema_value) =
             self_hashable_json_from(self.clone(), vjson_resolver).await?;
 
-        validate_against_json_schema(&schema_value, self_hashable_json.value())?;
+        verification_report.record_result(
+            verifier_resolver::VerificationCheckKind::SchemaValidation,
+            None,
+            None,
+            validate_against_json_schema(&schema_value, self_hashable_json.value()),
+        )?;
 
         let self_hash = {
             // The schema defines the JSONPath queries that define the self-hash [URL] slots, as well as
@@ -54,9 +76,15 @@ impl Validate for serde_json::Value {
                 "    validate_and_return_self_hash; VJSON whose self-hashes will be verified: {}",
                 self_hashable_json.value().to_string()
             );
-            let self_hash = self_hashable_json
-                .verify_self_hashes()
-                .map_err(error_invalid_vjson)?
+            let self_hash = verification_report
+                .record_result(
+                    verifier_resolver::VerificationCheckKind::SelfHash,
+                    None,
+                    None,
+                    self_hashable_json
+                        .verify_self_hashes()
+                        .map_err(error_invalid_vjson),
+                )?
                 .to_owned();
             tracing::trace!(
                 "    validate_and_return_self_hash; Input VJSON's self-hashes were successfully verified."
@@ -111,14 +139,23 @@ impl Validate for serde_json::Value {
                         jws.header()
                     );
 
-                    // Determine the verifier (i.e. public key) to use to verify the JWS.
-                    let verifier_b = verifier_resolver.resolve(&jws.header().kid).await.map_err(|e| Error::InvalidVJSON(format!("JWS header \"kid\" field was not a valid verifier; error was: {}", e).into()))?;
-
-                    jws.verify(
-                        verifier_b.as_ref(),
-                        Some(&mut detached_payload_bytes.as_slice()),
-                    )
-                    .map_err(error_invalid_vjson)?;
+                    // Determine the verifier (i.e. public key) to use to verify the JWS.  A VJSON proof
+                    // is an assertion about the VJSON, so the key must be an assertionMethod key.
+                    let kid = jws.header().kid.as_str();
+                    let verifier_b = verification_report.resolve_for_purpose(kid, verifier_resolver::VerificationRelationship::AssertionMethod, verifier_resolver).await.map_err(|e| Error::InvalidVJSON(format!("JWS header \"kid\" field was not a valid verifier; error was: {}", e).into()))?;
+
+                    verification_report.record_result(
+                        verifier_resolver::VerificationCheckKind::Signature,
+                        Some(kid),
+                        verification_report
+                            .resolved_did_document_version(kid)
+                            .as_deref(),
+                        jws.verify(
+                            verifier_b.as_ref(),
+                            Some(&mut detached_payload_bytes.as_slice()),
+                        )
+                        .map_err(error_invalid_vjson),
+                    )?;
                     valid_proof_count += 1;
                     tracing::trace!(
                         "    validate_and_return_self_hash; Proof with JWS header {:?} was verified",
@@ -129,7 +166,16 @@ impl Validate for serde_json::Value {
 
             if vjson_schema.vjson_properties.must_be_signed {
                 if valid_proof_count == 0 {
-                    return Err(Error::InvalidVJSON("VJSON required at least one element in the \"proofs\" array, but there were none".into()));
+                    let error = Error::InvalidVJSON("VJSON required at least one element in the \"proofs\" array, but there were none".into());
+                    verification_report.record(
+                        verifier_resolver::VerificationCheckKind::Signature,
+                        None,
+                        None,
+                        verifier_resolver::VerificationOutcome::Failed {
+                            reason: error.to_string(),
+                        },
+                    );
+                    return Err(error);
                 }
             }
 